@@ -82,4 +82,40 @@ pub struct GarbageCollectorConfig {
         env = "INFLUXDB_IOX_GC_RETENTION_SLEEP_INTERVAL_MINUTES"
     )]
     pub retention_sleep_interval_minutes: u64,
+
+    /// Parquet files of tables soft-deleted before this duration will be flagged for deletion.
+    /// Until then, a deleted table can be restored along with its data.
+    /// Parsed with <https://docs.rs/humantime/latest/humantime/fn.parse_duration.html>
+    ///
+    /// If not specified, defaults to 1 day ago.
+    #[clap(
+        long,
+        default_value = "1d",
+        value_parser = parse_duration,
+        env = "INFLUXDB_IOX_GC_TABLE_DELETE_CUTOFF"
+    )]
+    pub table_delete_cutoff: Duration,
+
+    /// Namespaces soft-deleted before this duration will be permanently removed from the catalog,
+    /// along with their parquet files in object storage. Until then, a deleted namespace can be
+    /// restored.
+    /// Parsed with <https://docs.rs/humantime/latest/humantime/fn.parse_duration.html>
+    ///
+    /// If not specified, defaults to 7 days ago.
+    #[clap(
+        long,
+        default_value = "7d",
+        value_parser = parse_duration,
+        env = "INFLUXDB_IOX_GC_NAMESPACE_DELETE_CUTOFF"
+    )]
+    pub namespace_delete_cutoff: Duration,
+
+    /// Number of minutes to sleep between iterations of the namespace purge code.
+    /// Defaults to 60 minutes.
+    #[clap(
+        long,
+        default_value_t = 60,
+        env = "INFLUXDB_IOX_GC_NAMESPACE_PURGE_SLEEP_INTERVAL_MINUTES"
+    )]
+    pub namespace_purge_sleep_interval_minutes: u64,
 }
@@ -2,6 +2,8 @@
 
 use std::path::PathBuf;
 
+use crate::ingester_address::IngesterAddress;
+
 /// CLI config for the ingester using the RPC write path
 #[derive(Debug, Clone, clap::Parser)]
 #[allow(missing_copy_implementations)]
@@ -69,4 +71,29 @@ pub struct IngesterConfig {
         action
     )]
     pub persist_hot_partition_cost: usize,
+
+    /// gRPC addresses of the ingesters that every write applied by this
+    /// ingester is replicated to, separated by commas.
+    ///
+    /// Writes are only acknowledged once they have been applied by all
+    /// replicas.
+    #[clap(
+        long = "replica-addresses",
+        env = "INFLUXDB_IOX_REPLICA_ADDRESSES",
+        required = false,
+        num_args = 1..,
+        value_delimiter = ','
+    )]
+    pub replica_addresses: Vec<IngesterAddress>,
+
+    /// gRPC address of the ingester this ingester is a replica of.
+    ///
+    /// If specified, the data buffered by that ingester is loaded into this
+    /// ingester at startup.
+    #[clap(
+        long = "replication-source",
+        env = "INFLUXDB_IOX_REPLICATION_SOURCE",
+        action
+    )]
+    pub replication_source: Option<IngesterAddress>,
 }
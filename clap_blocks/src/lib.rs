@@ -21,5 +21,6 @@ pub mod object_store;
 pub mod querier;
 pub mod router;
 pub mod run_config;
+pub mod self_monitoring;
 pub mod single_tenant;
 pub mod socket_addr;
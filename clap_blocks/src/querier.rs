@@ -4,7 +4,7 @@ use crate::{
     ingester_address::IngesterAddress,
     single_tenant::{CONFIG_AUTHZ_ENV_NAME, CONFIG_AUTHZ_FLAG},
 };
-use std::{collections::HashMap, num::NonZeroUsize};
+use std::{collections::HashMap, num::NonZeroUsize, path::PathBuf, time::Duration};
 
 /// CLI config for querier configuration
 #[derive(Debug, Clone, PartialEq, Eq, clap::Parser)]
@@ -72,6 +72,38 @@ pub struct QuerierConfig {
     )]
     pub ram_pool_data_bytes: usize,
 
+    /// Directory used to cache data on local disk, in addition to the RAM cache.
+    ///
+    /// The directory is created if it does not exist. Data cached within it by a previous querier process is reused.
+    ///
+    /// If not specified, data is only cached in RAM.
+    #[clap(long = "disk-cache-dir", env = "INFLUXDB_IOX_DISK_CACHE_DIR", action)]
+    pub disk_cache_dir: Option<PathBuf>,
+
+    /// Size of the disk cache used to store data in bytes.
+    ///
+    /// Only used if `--disk-cache-dir` is set.
+    #[clap(
+        long = "disk-pool-data-bytes",
+        env = "INFLUXDB_IOX_DISK_POOL_DATA_BYTES",
+        default_value = "10737418240",  // 10GB
+        action
+    )]
+    pub disk_pool_data_bytes: usize,
+
+    /// Cache the byte ranges of parquet files that are read by queries, such as footers and individual column chunks,
+    /// instead of whole files.
+    ///
+    /// This reduces the RAM used by the cache and the data fetched from the object store for queries that only read
+    /// a few columns of wide tables. Data cached on disk (see `--disk-cache-dir`) is not used for range reads.
+    #[clap(
+        long = "cache-object-ranges",
+        env = "INFLUXDB_IOX_CACHE_OBJECT_RANGES",
+        default_value = "false",
+        action
+    )]
+    pub cache_object_ranges: bool,
+
     /// Limit the number of concurrent queries.
     #[clap(
         long = "max-concurrent-queries",
@@ -81,6 +113,29 @@ pub struct QuerierConfig {
     )]
     pub max_concurrent_queries: usize,
 
+    /// Maximum time a query may run for before it is aborted, for example "10m".
+    ///
+    /// If not specified, queries are not aborted regardless of how long they run for.
+    #[clap(
+        long = "max-query-runtime",
+        env = "INFLUXDB_IOX_MAX_QUERY_RUNTIME",
+        value_parser = humantime::parse_duration,
+        action
+    )]
+    pub max_query_runtime: Option<Duration>,
+
+    /// Maximum query runtime of individual namespaces, overriding `--max-query-runtime`.
+    ///
+    /// For example "ns1:30s,ns2:1h".
+    #[clap(
+        long = "namespace-max-query-runtime",
+        env = "INFLUXDB_IOX_NAMESPACE_MAX_QUERY_RUNTIME",
+        default_value = "",
+        value_parser = parse_namespace_max_query_runtime,
+        action
+    )]
+    pub namespace_max_query_runtime: HashMap<String, Duration>,
+
     /// After how many ingester query errors should the querier enter circuit breaker mode?
     ///
     /// The querier normally contacts the ingester for any unpersisted data during query planning.
@@ -109,10 +164,64 @@ pub struct QuerierConfig {
         long = "datafusion-config",
         env = "INFLUXDB_IOX_DATAFUSION_CONFIG",
         default_value = "",
-        value_parser = parse_datafusion_config,
+        value_parser = parse_key_value_pairs,
         action
     )]
     pub datafusion_config: HashMap<String, String>,
+
+    /// Where to write an entry for every completed query, in addition to the `system.queries`
+    /// table.
+    #[clap(
+        value_enum,
+        long = "query-log-output",
+        env = "INFLUXDB_IOX_QUERY_LOG_OUTPUT",
+        default_value = "none",
+        action
+    )]
+    pub query_log_output: QueryLogOutputType,
+
+    /// File that completed queries are appended to as JSON lines.
+    ///
+    /// Required if `--query-log-output` is `file`.
+    #[clap(
+        long = "query-log-file",
+        env = "INFLUXDB_IOX_QUERY_LOG_FILE",
+        required_if_eq("query_log_output", "file"),
+        action
+    )]
+    pub query_log_file: Option<PathBuf>,
+
+    /// Size in bytes at which the query log file is rotated.
+    #[clap(
+        long = "query-log-file-max-bytes",
+        env = "INFLUXDB_IOX_QUERY_LOG_FILE_MAX_BYTES",
+        default_value = "104857600",  // 100MB
+        action
+    )]
+    pub query_log_file_max_bytes: u64,
+
+    /// Number of rotated query log files to keep.
+    #[clap(
+        long = "query-log-file-max-files",
+        env = "INFLUXDB_IOX_QUERY_LOG_FILE_MAX_FILES",
+        default_value = "5",
+        action
+    )]
+    pub query_log_file_max_files: usize,
+}
+
+/// Output of the query log.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum QueryLogOutputType {
+    /// Only keep completed queries in memory.
+    #[default]
+    None,
+
+    /// Emit a log line for every completed query.
+    Log,
+
+    /// Append every completed query to `--query-log-file`.
+    File,
 }
 
 impl QuerierConfig {
@@ -132,13 +241,41 @@ impl QuerierConfig {
         self.ram_pool_data_bytes
     }
 
+    /// Directory of the disk cache for payload, if enabled.
+    pub fn disk_cache_dir(&self) -> Option<&PathBuf> {
+        self.disk_cache_dir.as_ref()
+    }
+
+    /// Size of the disk cache pool for payload in bytes.
+    pub fn disk_pool_data_bytes(&self) -> usize {
+        self.disk_pool_data_bytes
+    }
+
+    /// Cache byte ranges of objects instead of whole objects.
+    pub fn cache_object_ranges(&self) -> bool {
+        self.cache_object_ranges
+    }
+
     /// Number of queries allowed to run concurrently
     pub fn max_concurrent_queries(&self) -> usize {
         self.max_concurrent_queries
     }
 }
 
-fn parse_datafusion_config(
+fn parse_namespace_max_query_runtime(
+    s: &str,
+) -> Result<HashMap<String, Duration>, Box<dyn std::error::Error + Send + Sync + 'static>> {
+    parse_key_value_pairs(s)?
+        .into_iter()
+        .map(|(namespace, runtime)| {
+            let runtime = humantime::parse_duration(&runtime)
+                .map_err(|e| format!("invalid runtime for namespace '{namespace}': {e}"))?;
+            Ok((namespace, runtime))
+        })
+        .collect()
+}
+
+fn parse_key_value_pairs(
     s: &str,
 ) -> Result<HashMap<String, String>, Box<dyn std::error::Error + Send + Sync + 'static>> {
     let s = s.trim();
@@ -182,6 +319,60 @@ mod tests {
         assert_eq!(actual.num_query_threads(), None);
         assert!(actual.ingester_addresses.is_empty());
         assert!(actual.datafusion_config.is_empty());
+        assert_eq!(actual.disk_cache_dir(), None);
+        assert_eq!(actual.query_log_output, QueryLogOutputType::None);
+        assert_eq!(actual.max_query_runtime, None);
+        assert!(actual.namespace_max_query_runtime.is_empty());
+    }
+
+    #[test]
+    fn test_max_query_runtime() {
+        let actual = QuerierConfig::try_parse_from([
+            "my_binary",
+            "--max-query-runtime",
+            "10m",
+            "--namespace-max-query-runtime",
+            "ns1:30s, ns2:1h",
+        ])
+        .unwrap();
+
+        assert_eq!(actual.max_query_runtime, Some(Duration::from_secs(600)));
+        assert_eq!(
+            actual.namespace_max_query_runtime,
+            HashMap::from([
+                (String::from("ns1"), Duration::from_secs(30)),
+                (String::from("ns2"), Duration::from_secs(3600)),
+            ]),
+        );
+
+        let actual =
+            QuerierConfig::try_parse_from(["my_binary", "--namespace-max-query-runtime=ns1:soon"])
+                .unwrap_err()
+                .to_string();
+        assert_contains!(actual, "invalid runtime for namespace 'ns1'");
+    }
+
+    #[test]
+    fn test_query_log_file() {
+        let actual = QuerierConfig::try_parse_from([
+            "my_binary",
+            "--query-log-output",
+            "file",
+            "--query-log-file",
+            "/tmp/queries.log",
+        ])
+        .unwrap();
+
+        assert_eq!(actual.query_log_output, QueryLogOutputType::File);
+        assert_eq!(
+            actual.query_log_file,
+            Some(PathBuf::from("/tmp/queries.log"))
+        );
+
+        let actual = QuerierConfig::try_parse_from(["my_binary", "--query-log-output", "file"])
+            .unwrap_err()
+            .to_string();
+        assert_contains!(actual, "--query-log-file <QUERY_LOG_FILE>");
     }
 
     #[test]
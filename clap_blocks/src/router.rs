@@ -121,6 +121,70 @@ pub struct RouterConfig {
     /// write failure.
     #[clap(long = "rpc-write-replicas", env = "INFLUXDB_IOX_RPC_WRITE_REPLICAS")]
     pub rpc_write_replicas: Option<NonZeroUsize>,
+
+    /// Route writes for the same (namespace, partition key) to the same
+    /// ingester, instead of distributing writes uniformly across all ingesters.
+    ///
+    /// Concentrating the data for a partition into fewer ingesters results in
+    /// fewer, larger persisted files. If the preferred ingester is unhealthy,
+    /// the write is sent to the next ingester in the configured
+    /// `--ingester-addresses` order, which also receives any replicas.
+    ///
+    /// The table is deliberately not part of the affinity key: all the tables
+    /// of a write for a partition are sent to the same ingester in a single
+    /// request, so a write either succeeds or fails (and is retried by the
+    /// client) as a whole. Keying on the table as well would split one write
+    /// across several ingesters, where some tables could be persisted while
+    /// others fail.
+    #[clap(
+        long = "rpc-write-partition-affinity",
+        env = "INFLUXDB_IOX_RPC_WRITE_PARTITION_AFFINITY",
+        default_value = "false",
+        action
+    )]
+    pub rpc_write_partition_affinity: bool,
+
+    /// Periodically write the metrics of this process to the namespace named
+    /// by `--self-monitoring-namespace`, at this interval in seconds.
+    ///
+    /// This allows the health of IOx to be queried with SQL or InfluxQL when
+    /// no Prometheus server is available to scrape the `/metrics` endpoint.
+    /// Self-monitoring is disabled if unset.
+    ///
+    /// Only the metrics of this router are written. Ingesters, queriers and
+    /// compactors write their metrics through the HTTP write API of a router
+    /// when configured with `--self-monitoring-router-addr`.
+    #[clap(
+        long = "self-monitoring-interval-seconds",
+        env = "INFLUXDB_IOX_SELF_MONITORING_INTERVAL_SECONDS",
+        value_parser = parse_duration
+    )]
+    pub self_monitoring_interval_seconds: Option<Duration>,
+
+    /// The namespace self-monitoring metrics are written to.
+    ///
+    /// The namespace must already exist unless namespace autocreation is
+    /// enabled.
+    #[clap(
+        long = "self-monitoring-namespace",
+        env = "INFLUXDB_IOX_SELF_MONITORING_NAMESPACE",
+        default_value = "_monitoring",
+        action
+    )]
+    pub self_monitoring_namespace: String,
+
+    /// The interval in seconds at which the cached namespace schemas are
+    /// replaced with those in the catalog.
+    ///
+    /// Tables and columns soft-deleted after they were cached continue to
+    /// accept writes until the next refresh.
+    #[clap(
+        long = "namespace-cache-refresh-interval-seconds",
+        env = "INFLUXDB_IOX_NAMESPACE_CACHE_REFRESH_INTERVAL_SECONDS",
+        default_value = "60",
+        value_parser = parse_duration
+    )]
+    pub namespace_cache_refresh_interval_seconds: Duration,
 }
 
 /// Map a string containing an integer number of seconds into a [`Duration`].
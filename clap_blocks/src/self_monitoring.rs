@@ -0,0 +1,113 @@
+//! CLI config for writing the metrics of a non-router server into IOx via a
+//! router.
+
+use std::{num::ParseIntError, time::Duration};
+
+/// CLI config for self-monitoring of ingesters, queriers and compactors.
+///
+/// Routers write their own metrics through their write path and are
+/// configured by the self-monitoring options of
+/// [`RouterConfig`](crate::router::RouterConfig) instead.
+#[derive(Debug, Clone, clap::Parser)]
+#[allow(missing_copy_implementations)]
+pub struct SelfMonitoringConfig {
+    /// Periodically write the metrics of this process to the namespace named
+    /// by `--self-monitoring-namespace`, at this interval in seconds.
+    ///
+    /// The metrics are written through the HTTP write API of the router at
+    /// `--self-monitoring-router-addr`, allowing the health of IOx to be
+    /// queried with SQL or InfluxQL when no Prometheus server is available to
+    /// scrape the `/metrics` endpoint. Self-monitoring is disabled if unset.
+    #[clap(
+        long = "self-monitoring-interval-seconds",
+        env = "INFLUXDB_IOX_SELF_MONITORING_INTERVAL_SECONDS",
+        value_parser = parse_duration,
+        requires("router_addr")
+    )]
+    pub interval_seconds: Option<Duration>,
+
+    /// The base URL of the router HTTP API self-monitoring metrics are
+    /// written to, for example `http://router:8080`.
+    #[clap(
+        long = "self-monitoring-router-addr",
+        env = "INFLUXDB_IOX_SELF_MONITORING_ROUTER_ADDR",
+        action
+    )]
+    pub router_addr: Option<String>,
+
+    /// The namespace self-monitoring metrics are written to, sent as the
+    /// `bucket` of the `/api/v2/write` request.
+    ///
+    /// The namespace must already exist unless namespace autocreation is
+    /// enabled on the router.
+    #[clap(
+        long = "self-monitoring-namespace",
+        env = "INFLUXDB_IOX_SELF_MONITORING_NAMESPACE",
+        default_value = "_monitoring",
+        action
+    )]
+    pub namespace: String,
+
+    /// The `org` of the `/api/v2/write` request.
+    ///
+    /// Multi-tenant routers write to the namespace `{org}_{namespace}` and
+    /// reject writes without an org, so this must be set when writing
+    /// through a multi-tenant router. Single-tenant routers ignore it.
+    #[clap(
+        long = "self-monitoring-org",
+        env = "INFLUXDB_IOX_SELF_MONITORING_ORG",
+        action
+    )]
+    pub org: Option<String>,
+
+    /// The token sent in the `Authorization` header of the write request.
+    ///
+    /// Single-tenant routers require a token with write permission for the
+    /// monitoring namespace.
+    #[clap(
+        long = "self-monitoring-token",
+        env = "INFLUXDB_IOX_SELF_MONITORING_TOKEN",
+        action
+    )]
+    pub token: Option<String>,
+}
+
+/// Map a string containing an integer number of seconds into a [`Duration`].
+fn parse_duration(input: &str) -> Result<Duration, ParseIntError> {
+    input.parse().map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    #[test]
+    fn test_default() {
+        let config = SelfMonitoringConfig::try_parse_from(["server"]).unwrap();
+        assert_eq!(config.interval_seconds, None);
+        assert_eq!(config.namespace, "_monitoring");
+    }
+
+    #[test]
+    fn test_interval_requires_router_addr() {
+        SelfMonitoringConfig::try_parse_from([
+            "server",
+            "--self-monitoring-interval-seconds",
+            "10",
+        ])
+        .expect_err("interval without a router address should be rejected");
+
+        let config = SelfMonitoringConfig::try_parse_from([
+            "server",
+            "--self-monitoring-interval-seconds",
+            "10",
+            "--self-monitoring-router-addr",
+            "http://router:8080",
+        ])
+        .unwrap();
+        assert_eq!(config.interval_seconds, Some(Duration::from_secs(10)));
+        assert_eq!(config.router_addr.as_deref(), Some("http://router:8080"));
+    }
+}
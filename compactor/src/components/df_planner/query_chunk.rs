@@ -1,5 +1,5 @@
 //! QueryableParquetChunk for building query plan
-use std::{any::Any, sync::Arc};
+use std::{any::Any, collections::HashSet, sync::Arc};
 
 use data_types::{ChunkId, ChunkOrder, DeletePredicate, PartitionId, TableSummary};
 use datafusion::error::DataFusionError;
@@ -21,7 +21,7 @@ use crate::{partition_info::PartitionInfo, plan_ir::FileIR};
 pub struct QueryableParquetChunk {
     // Data of the parquet file
     data: Arc<ParquetChunk>,
-    // We do not yet support delete but we need this to work with the straight QueryChunkMeta
+    // Tombstones of the table, removing deleted rows from the compaction output
     delete_predicates: Vec<Arc<DeletePredicate>>,
     partition_id: PartitionId,
     sort_key: Option<SortKey>,
@@ -36,6 +36,7 @@ impl QueryableParquetChunk {
         data: Arc<ParquetChunk>,
         sort_key: Option<SortKey>,
         order: ChunkOrder,
+        delete_predicates: Vec<Arc<DeletePredicate>>,
     ) -> Self {
         let summary = Arc::new(create_basic_summary(
             data.rows() as u64,
@@ -44,7 +45,7 @@ impl QueryableParquetChunk {
         ));
         Self {
             data,
-            delete_predicates: vec![],
+            delete_predicates,
             partition_id,
             sort_key,
             order,
@@ -163,16 +164,53 @@ pub fn to_query_chunks(
                 file,
                 partition_info,
                 store.clone(),
+                delete_predicates_for_file(file, partition_info),
             )) as _
         })
         .collect()
 }
 
+/// Select the delete predicates of the tombstones of the partition that delete rows of `file`.
+///
+/// A tombstone only applies to files created before it. A predicate that references a column the
+/// file does not contain cannot match any of its rows (the column is NULL everywhere), so it is
+/// dropped instead of failing the plan.
+fn delete_predicates_for_file(
+    file: &FileIR,
+    partition_info: &PartitionInfo,
+) -> Vec<Arc<DeletePredicate>> {
+    if partition_info.tombstones.is_empty() {
+        return vec![];
+    }
+
+    let column_id_lookup = partition_info.table_schema.column_id_map();
+    let columns: HashSet<&str> = file
+        .file
+        .column_set
+        .iter()
+        .flat_map(|id| column_id_lookup.get(id).copied())
+        .collect();
+
+    partition_info
+        .tombstones
+        .iter()
+        .filter(|t| t.applies_to(file.file.max_l0_created_at))
+        .map(|t| &t.delete_predicate)
+        .filter(|pred| {
+            pred.exprs
+                .iter()
+                .all(|expr| columns.contains(expr.column()))
+        })
+        .cloned()
+        .collect()
+}
+
 /// Convert to a QueryableParquetChunk
 fn to_queryable_parquet_chunk(
     file: &FileIR,
     partition_info: &PartitionInfo,
     store: ParquetStorage,
+    delete_predicates: Vec<Arc<DeletePredicate>>,
 ) -> QueryableParquetChunk {
     let column_id_lookup = partition_info.table_schema.column_id_map();
     let selection: Vec<_> = file
@@ -211,5 +249,11 @@ fn to_queryable_parquet_chunk(
     );
 
     let parquet_chunk = ParquetChunk::new(Arc::new(file.file.clone()), schema, store);
-    QueryableParquetChunk::new(partition_id, Arc::new(parquet_chunk), sort_key, file.order)
+    QueryableParquetChunk::new(
+        partition_id,
+        Arc::new(parquet_chunk),
+        sort_key,
+        file.order,
+        delete_predicates,
+    )
 }
@@ -89,6 +89,8 @@ use super::{
         split_compact::SplitCompact,
     },
     tables_source::catalog::CatalogTablesSource,
+    tombstones_sink::{catalog::CatalogTombstonesSink, mock::MockTombstonesSink, TombstonesSink},
+    tombstones_source::catalog::CatalogTombstonesSource,
     Components,
 };
 
@@ -105,6 +107,7 @@ pub fn hardcoded_components(config: &Config) -> Arc<Components> {
         partition_filter: make_partition_filter(config),
         partition_done_sink,
         commit,
+        tombstones_sink: make_tombstones_sink(config),
         ir_planner: make_ir_planner(config),
         df_planner: make_df_planner(config),
         df_plan_exec: make_df_plan_exec(config),
@@ -261,6 +264,18 @@ fn make_partitions_source_commit_partition_sink(
     (partitions_source, commit, partition_done_sink)
 }
 
+fn make_tombstones_sink(config: &Config) -> Arc<dyn TombstonesSink> {
+    if config.shadow_mode || config.compaction_type == CompactionType::Cold {
+        Arc::new(MockTombstonesSink::new())
+    } else {
+        Arc::new(CatalogTombstonesSink::new(
+            config.backoff_config.clone(),
+            Arc::clone(&config.catalog),
+            Arc::clone(&config.time_provider),
+        ))
+    }
+}
+
 fn make_partition_stream(
     config: &Config,
     partitions_source: Arc<dyn PartitionsSource>,
@@ -280,6 +295,7 @@ fn make_partition_info_source(config: &Config) -> Arc<dyn PartitionInfoSource> {
         )),
         CatalogTablesSource::new(config.backoff_config.clone(), Arc::clone(&config.catalog)),
         CatalogNamespacesSource::new(config.backoff_config.clone(), Arc::clone(&config.catalog)),
+        CatalogTombstonesSource::new(config.backoff_config.clone(), Arc::clone(&config.catalog)),
     ))
 }
 
@@ -9,6 +9,7 @@ use self::{
     partition_stream::PartitionStream,
     post_classification_partition_filter::PostClassificationPartitionFilter,
     round_info_source::RoundInfoSource, round_split::RoundSplit, scratchpad::ScratchpadGen,
+    tombstones_sink::TombstonesSink,
 };
 
 pub mod changed_files_filter;
@@ -42,6 +43,8 @@ pub mod skipped_compactions_source;
 pub mod split_or_compact;
 pub mod tables_source;
 pub mod timeout;
+pub mod tombstones_sink;
+pub mod tombstones_source;
 
 /// Pluggable system to determine compactor behavior. Please see
 /// [Crate Level Documentation](crate) for more details on the
@@ -64,6 +67,9 @@ pub struct Components {
     pub partition_done_sink: Arc<dyn PartitionDoneSink>,
     /// Commits changes (i.e. deletion and creation) to the catalog.
     pub commit: Arc<dyn Commit>,
+    /// Records the tombstones applied to created files and retires the tombstones applied to all
+    /// files of a table.
+    pub tombstones_sink: Arc<dyn TombstonesSink>,
     /// Creates `PlanIR` that describes what files should be compacted and updated
     pub ir_planner: Arc<dyn IRPlanner>,
     /// Creates an Execution plan for a `PlanIR`
@@ -137,12 +137,14 @@ mod tests {
                                 id: ColumnId::new(1),
                                 column_type: ColumnType::I64,
                                 table_id: TableId::new(1),
+                                deleted_at: None,
                             },
                             Column {
                                 name: "col2".to_string(),
                                 id: ColumnId::new(2),
                                 column_type: ColumnType::String,
                                 table_id: TableId::new(1),
+                                deleted_at: None,
                             },
                         ]),
                     },
@@ -158,18 +160,21 @@ mod tests {
                                 id: ColumnId::new(3),
                                 column_type: ColumnType::I64,
                                 table_id: TableId::new(2),
+                                deleted_at: None,
                             },
                             Column {
                                 name: "col2".to_string(),
                                 id: ColumnId::new(4),
                                 column_type: ColumnType::String,
                                 table_id: TableId::new(2),
+                                deleted_at: None,
                             },
                             Column {
                                 name: "col3".to_string(),
                                 id: ColumnId::new(5),
                                 column_type: ColumnType::F64,
                                 table_id: TableId::new(2),
+                                deleted_at: None,
                             },
                         ]),
                     },
@@ -184,14 +189,19 @@ mod tests {
                         name: "ns".to_string(),
                         max_tables: 10,
                         max_columns_per_table: 10,
+                        max_concurrent_queries: None,
+                        max_query_memory_bytes: None,
+                        max_total_query_memory_bytes: None,
                         retention_period_ns: None,
                         deleted_at: None,
+                        partition_template: None,
                     },
                     schema: NamespaceSchema {
                         id,
                         tables,
                         max_columns_per_table: 10,
                         max_tables: 42,
+                        query_limits: Default::default(),
                         retention_period_ns: None,
                         partition_template: None,
                     },
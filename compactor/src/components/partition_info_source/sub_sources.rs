@@ -2,66 +2,82 @@ use std::{fmt::Display, sync::Arc};
 
 use async_trait::async_trait;
 use data_types::PartitionId;
+use observability_deps::tracing::warn;
+use predicate::delete_predicate::parse_tombstone_predicate;
 
 use crate::{
     components::{
         namespaces_source::NamespacesSource, partition_source::PartitionSource,
-        tables_source::TablesSource,
+        tables_source::TablesSource, tombstones_source::TombstonesSource,
     },
     error::DynError,
-    partition_info::PartitionInfo,
+    partition_info::{PartitionInfo, PartitionTombstone},
 };
 
 use super::PartitionInfoSource;
 
 #[derive(Debug)]
-pub struct SubSourcePartitionInfoSource<P, T, N>
+pub struct SubSourcePartitionInfoSource<P, T, N, D>
 where
     P: PartitionSource,
     T: TablesSource,
     N: NamespacesSource,
+    D: TombstonesSource,
 {
     partition_source: P,
     tables_source: T,
     namespaces_source: N,
+    tombstones_source: D,
 }
 
-impl<P, T, N> SubSourcePartitionInfoSource<P, T, N>
+impl<P, T, N, D> SubSourcePartitionInfoSource<P, T, N, D>
 where
     P: PartitionSource,
     T: TablesSource,
     N: NamespacesSource,
+    D: TombstonesSource,
 {
-    pub fn new(partition_source: P, tables_source: T, namespaces_source: N) -> Self {
+    pub fn new(
+        partition_source: P,
+        tables_source: T,
+        namespaces_source: N,
+        tombstones_source: D,
+    ) -> Self {
         Self {
             partition_source,
             tables_source,
             namespaces_source,
+            tombstones_source,
         }
     }
 }
 
-impl<P, T, N> Display for SubSourcePartitionInfoSource<P, T, N>
+impl<P, T, N, D> Display for SubSourcePartitionInfoSource<P, T, N, D>
 where
     P: PartitionSource,
     T: TablesSource,
     N: NamespacesSource,
+    D: TombstonesSource,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "sub_sources(partition={}, tables={}, namespaces={})",
-            self.partition_source, self.tables_source, self.namespaces_source
+            "sub_sources(partition={}, tables={}, namespaces={}, tombstones={})",
+            self.partition_source,
+            self.tables_source,
+            self.namespaces_source,
+            self.tombstones_source
         )
     }
 }
 
 #[async_trait]
-impl<P, T, N> PartitionInfoSource for SubSourcePartitionInfoSource<P, T, N>
+impl<P, T, N, D> PartitionInfoSource for SubSourcePartitionInfoSource<P, T, N, D>
 where
     P: PartitionSource,
     T: TablesSource,
     N: NamespacesSource,
+    D: TombstonesSource,
 {
     async fn fetch(&self, partition_id: PartitionId) -> Result<Arc<PartitionInfo>, DynError> {
         // Get info for the partition
@@ -96,6 +112,30 @@ where
             .get(&table.name)
             .ok_or_else::<DynError, _>(|| String::from("Cannot find table schema").into())?;
 
+        // Tombstones are validated when they are recorded, so one that fails to parse is
+        // ignored rather than blocking compaction of the table forever.
+        let tombstones = self
+            .tombstones_source
+            .fetch(table.id)
+            .await
+            .into_iter()
+            .filter_map(|tombstone| match parse_tombstone_predicate(&tombstone) {
+                Ok(pred) => Some(PartitionTombstone {
+                    tombstone,
+                    delete_predicate: Arc::new(pred),
+                }),
+                Err(e) => {
+                    warn!(
+                        tombstone_id = tombstone.id.get(),
+                        table_id = table.id.get(),
+                        %e,
+                        "ignoring invalid tombstone",
+                    );
+                    None
+                }
+            })
+            .collect();
+
         Ok(Arc::new(PartitionInfo {
             partition_id,
             namespace_id: table.namespace_id,
@@ -104,6 +144,7 @@ where
             table_schema: Arc::new(table_schema.clone()),
             sort_key: partition.sort_key(),
             partition_key: partition.partition_key,
+            tombstones,
         }))
     }
 }
@@ -100,6 +100,7 @@ pub fn log_components(components: &Components) {
         post_classification_partition_filter: partition_too_large_to_compact_filter,
         partition_done_sink,
         commit,
+        tombstones_sink,
         ir_planner,
         df_planner,
         df_plan_exec,
@@ -120,6 +121,7 @@ pub fn log_components(components: &Components) {
         %partition_too_large_to_compact_filter,
         %partition_done_sink,
         %commit,
+        %tombstones_sink,
         %ir_planner,
         %df_planner,
         %df_plan_exec,
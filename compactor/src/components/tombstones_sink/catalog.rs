@@ -0,0 +1,141 @@
+use std::{collections::HashSet, fmt::Display, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use backoff::{Backoff, BackoffConfig};
+use data_types::{ParquetFile, Timestamp};
+use iox_catalog::interface::{Catalog, Error};
+use iox_time::TimeProvider;
+use observability_deps::tracing::info;
+
+use crate::partition_info::PartitionInfo;
+
+use super::TombstonesSink;
+
+/// How long a tombstone is kept after it was recorded, even once it has been applied to all files
+/// of its table.
+///
+/// An ingester may still be persisting a file that was created before the tombstone, but that is
+/// only added to the catalog after it. This must be longer than a persist takes.
+pub const TOMBSTONE_RETENTION: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug)]
+pub struct CatalogTombstonesSink {
+    backoff_config: BackoffConfig,
+    catalog: Arc<dyn Catalog>,
+    time_provider: Arc<dyn TimeProvider>,
+}
+
+impl CatalogTombstonesSink {
+    pub fn new(
+        backoff_config: BackoffConfig,
+        catalog: Arc<dyn Catalog>,
+        time_provider: Arc<dyn TimeProvider>,
+    ) -> Self {
+        Self {
+            backoff_config,
+            catalog,
+            time_provider,
+        }
+    }
+}
+
+impl Display for CatalogTombstonesSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "catalog")
+    }
+}
+
+#[async_trait]
+impl TombstonesSink for CatalogTombstonesSink {
+    async fn processed(&self, partition_info: &PartitionInfo, created: &[ParquetFile]) {
+        if partition_info.tombstones.is_empty() {
+            return;
+        }
+
+        Backoff::new(&self.backoff_config)
+            .retry_all_errors("record processed tombstones", || async {
+                let mut repos = self.catalog.repositories().await;
+
+                for file in created {
+                    for t in &partition_info.tombstones {
+                        if !t.applies_to(file.max_l0_created_at) {
+                            continue;
+                        }
+
+                        match repos
+                            .tombstones()
+                            .create_processed(t.tombstone.id, file.id)
+                            .await
+                        {
+                            Ok(()) => {}
+                            // The tombstone was retired by a compaction of another partition of
+                            // the table.
+                            Err(
+                                Error::ForeignKeyViolation { .. } | Error::TombstoneNotFound { .. },
+                            ) => {}
+                            Err(e) => return Err(e),
+                        }
+                    }
+                }
+
+                Ok(())
+            })
+            .await
+            .expect("retry forever");
+
+        let Some(cutoff) = self.time_provider.now().checked_sub(TOMBSTONE_RETENTION) else {
+            return;
+        };
+        let cutoff = Timestamp::from(cutoff);
+
+        let table_id = partition_info.table.id;
+        let retired = Backoff::new(&self.backoff_config)
+            .retry_all_errors("retire processed tombstones", || async {
+                let mut repos = self.catalog.repositories().await;
+
+                let files = repos
+                    .parquet_files()
+                    .list_by_table_not_to_delete(table_id)
+                    .await?;
+                let processed: HashSet<_> = repos
+                    .tombstones()
+                    .list_processed_by_table_id(table_id)
+                    .await?
+                    .into_iter()
+                    .map(|p| (p.tombstone_id, p.parquet_file_id))
+                    .collect();
+
+                let mut retired = vec![];
+                for t in &partition_info.tombstones {
+                    let t = &t.tombstone;
+                    if t.created_at >= cutoff {
+                        continue;
+                    }
+
+                    // Files created after the tombstone do not contain any of the rows it
+                    // deletes.
+                    let applied_to_all = files
+                        .iter()
+                        .filter(|f| t.applies_to(f.max_l0_created_at))
+                        .all(|f| processed.contains(&(t.id, f.id)));
+                    if applied_to_all {
+                        repos.tombstones().delete(t.id).await?;
+                        retired.push(t.id.get());
+                    }
+                }
+
+                Ok::<_, Error>(retired)
+            })
+            .await
+            .expect("retry forever");
+
+        if !retired.is_empty() {
+            info!(
+                table_id = table_id.get(),
+                partition_id = partition_info.partition_id.get(),
+                ?retired,
+                "retired tombstones applied to all files",
+            );
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use std::{fmt::Display, sync::Mutex};
+
+use async_trait::async_trait;
+use data_types::{ParquetFile, ParquetFileId, PartitionId};
+
+use crate::partition_info::PartitionInfo;
+
+use super::TombstonesSink;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ProcessedHistoryEntry {
+    pub partition_id: PartitionId,
+    pub created: Vec<ParquetFileId>,
+}
+
+#[derive(Debug, Default)]
+pub struct MockTombstonesSink {
+    history: Mutex<Vec<ProcessedHistoryEntry>>,
+}
+
+impl MockTombstonesSink {
+    #[allow(dead_code)] // not used anywhere
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[allow(dead_code)] // not used anywhere
+    pub fn history(&self) -> Vec<ProcessedHistoryEntry> {
+        self.history.lock().expect("not poisoned").clone()
+    }
+}
+
+impl Display for MockTombstonesSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "mock")
+    }
+}
+
+#[async_trait]
+impl TombstonesSink for MockTombstonesSink {
+    async fn processed(&self, partition_info: &PartitionInfo, created: &[ParquetFile]) {
+        self.history
+            .lock()
+            .expect("not poisoned")
+            .push(ProcessedHistoryEntry {
+                partition_id: partition_info.partition_id,
+                created: created.iter().map(|f| f.id).collect(),
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iox_tests::ParquetFileBuilder;
+
+    use crate::test_utils::PartitionInfoBuilder;
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(MockTombstonesSink::new().to_string(), "mock");
+    }
+
+    #[tokio::test]
+    async fn test_processed() {
+        let sink = MockTombstonesSink::new();
+        let p_1 = PartitionInfoBuilder::new().with_partition_id(1).build();
+        let p_2 = PartitionInfoBuilder::new().with_partition_id(2).build();
+        let f_1 = ParquetFileBuilder::new(1).build();
+        let f_2 = ParquetFileBuilder::new(2).build();
+
+        sink.processed(&p_1, &[f_1.clone(), f_2]).await;
+        sink.processed(&p_2, &[f_1]).await;
+
+        assert_eq!(
+            sink.history(),
+            vec![
+                ProcessedHistoryEntry {
+                    partition_id: PartitionId::new(1),
+                    created: vec![ParquetFileId::new(1), ParquetFileId::new(2)],
+                },
+                ProcessedHistoryEntry {
+                    partition_id: PartitionId::new(2),
+                    created: vec![ParquetFileId::new(1)],
+                },
+            ]
+        );
+    }
+}
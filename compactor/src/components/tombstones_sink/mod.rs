@@ -0,0 +1,21 @@
+use std::fmt::{Debug, Display};
+
+use async_trait::async_trait;
+use data_types::ParquetFile;
+
+use crate::partition_info::PartitionInfo;
+
+pub mod catalog;
+pub mod mock;
+
+/// Records the tombstones that compaction applied to the files it created, and removes the
+/// tombstones that are no longer needed.
+#[async_trait]
+pub trait TombstonesSink: Debug + Display + Send + Sync {
+    /// Record that the tombstones of `partition_info` that apply to the `created` files were
+    /// applied to their rows, then delete the tombstones of the table that have been applied to
+    /// all of its files.
+    ///
+    /// This method performs retries.
+    async fn processed(&self, partition_info: &PartitionInfo, created: &[ParquetFile]);
+}
@@ -0,0 +1,77 @@
+use std::{collections::HashMap, fmt::Display};
+
+use async_trait::async_trait;
+use data_types::{TableId, Tombstone};
+
+use super::TombstonesSource;
+
+#[derive(Debug)]
+pub struct MockTombstonesSource {
+    tombstones: HashMap<TableId, Vec<Tombstone>>,
+}
+
+impl MockTombstonesSource {
+    #[allow(dead_code)] // not used anywhere
+    pub fn new(tombstones: HashMap<TableId, Vec<Tombstone>>) -> Self {
+        Self { tombstones }
+    }
+}
+
+impl Display for MockTombstonesSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "mock")
+    }
+}
+
+#[async_trait]
+impl TombstonesSource for MockTombstonesSource {
+    async fn fetch(&self, table: TableId) -> Vec<Tombstone> {
+        self.tombstones.get(&table).cloned().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use data_types::{Timestamp, TombstoneId};
+
+    use super::*;
+
+    fn tombstone(id: i64, table_id: i64) -> Tombstone {
+        Tombstone {
+            id: TombstoneId::new(id),
+            table_id: TableId::new(table_id),
+            min_time: Timestamp::new(1),
+            max_time: Timestamp::new(10),
+            serialized_predicate: String::from(r#""tag1"='A'"#),
+            created_at: Timestamp::new(0),
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            MockTombstonesSource::new(HashMap::default()).to_string(),
+            "mock",
+        )
+    }
+
+    #[tokio::test]
+    async fn test_fetch() {
+        let t_1 = tombstone(1, 1);
+        let t_2 = tombstone(2, 1);
+
+        let tombstones = HashMap::from([(TableId::new(1), vec![t_1.clone(), t_2.clone()])]);
+        let source = MockTombstonesSource::new(tombstones);
+
+        assert_eq!(
+            source.fetch(TableId::new(1)).await,
+            vec![t_1.clone(), t_2.clone()],
+        );
+
+        // fetching does not drain
+        assert_eq!(source.fetch(TableId::new(1)).await, vec![t_1, t_2],);
+
+        // unknown table => empty result
+        assert_eq!(source.fetch(TableId::new(2)).await, vec![],);
+    }
+}
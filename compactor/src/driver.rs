@@ -353,6 +353,12 @@ async fn execute_branch(
     )
     .await;
 
+    // Record the tombstones applied to the created files, and retire those no file needs anymore
+    components
+        .tombstones_sink
+        .processed(&partition_info, &created_files)
+        .await;
+
     // Report to `timeout_with_progress_checking` that some progress has been made; stop
     // if sending this signal fails because something has gone terribly wrong for the other
     // end of the channel to not be listening anymore.
@@ -225,7 +225,7 @@ pub use components::{
 };
 pub use driver::compact;
 pub use error::DynError;
-pub use partition_info::PartitionInfo;
+pub use partition_info::{PartitionInfo, PartitionTombstone};
 pub use plan_ir::PlanIR;
 pub use round_info::RoundInfo;
 
@@ -2,7 +2,10 @@
 
 use std::sync::Arc;
 
-use data_types::{NamespaceId, PartitionId, PartitionKey, Table, TableSchema};
+use data_types::{
+    DeletePredicate, NamespaceId, PartitionId, PartitionKey, Table, TableSchema, Timestamp,
+    Tombstone,
+};
 use schema::sort::SortKey;
 
 /// Information about the Partition being compacted
@@ -28,6 +31,30 @@ pub struct PartitionInfo {
 
     /// partition_key
     pub partition_key: PartitionKey,
+
+    /// Tombstones of the table, applied to the files of this partition that were created before
+    /// them.
+    pub tombstones: Vec<PartitionTombstone>,
+}
+
+/// A tombstone of the table being compacted, and its parsed predicate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartitionTombstone {
+    /// The tombstone
+    pub tombstone: Tombstone,
+
+    /// The delete predicate of the tombstone
+    pub delete_predicate: Arc<DeletePredicate>,
+}
+
+impl PartitionTombstone {
+    /// Returns true if this tombstone deletes rows of a file with the given
+    /// `max_l0_created_at`.
+    ///
+    /// See [`Tombstone::applies_to`].
+    pub fn applies_to(&self, max_l0_created_at: Timestamp) -> bool {
+        self.tombstone.applies_to(max_l0_created_at)
+    }
 }
 
 impl PartitionInfo {
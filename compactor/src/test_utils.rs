@@ -26,10 +26,13 @@ impl PartitionInfoBuilder {
                     id: TableId::new(3),
                     namespace_id,
                     name: String::from("table"),
+                    partition_template: None,
+                    deleted_at: None,
                 }),
                 table_schema: Arc::new(TableSchema::new(table_id)),
                 sort_key: None,
                 partition_key: PartitionKey::from("key"),
+                tombstones: vec![],
             },
         }
     }
@@ -46,6 +49,7 @@ impl PartitionInfoBuilder {
                 name: i.to_string(),
                 column_type: ColumnType::I64,
                 table_id: self.inner.table.id,
+                deleted_at: None,
             })
             .collect();
 
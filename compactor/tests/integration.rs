@@ -1,6 +1,6 @@
 use arrow_util::assert_batches_sorted_eq;
 use compactor_test_utils::{format_files, list_object_store, TestSetup, TestSetupBuilder};
-use data_types::{CompactionLevel, ParquetFile, PartitionId};
+use data_types::{CompactionLevel, ParquetFile, PartitionId, Timestamp};
 use iox_tests::TestParquetFileBuilder;
 use test_helpers::{assert_contains, tracing::TracingCapture};
 
@@ -183,6 +183,90 @@ async fn test_compact_target_level() {
     );
 }
 
+#[tokio::test]
+async fn test_compact_with_tombstone() {
+    test_helpers::maybe_start_logging();
+
+    // Create a test setup with 6 files
+    let setup = TestSetup::builder()
+        .await
+        .with_files()
+        .await
+        // Ensure we have enough resource to compact the files
+        .with_max_num_files_per_plan(10)
+        .with_min_num_l1_files_to_compact(2)
+        .build()
+        .await;
+
+    // delete all rows with tag1=VT
+    let tombstone = setup
+        .catalog
+        .catalog()
+        .repositories()
+        .await
+        .tombstones()
+        .create(
+            setup.table.table.id,
+            Timestamp::new(0),
+            Timestamp::new(1_000_000),
+            r#""tag1"='VT'"#,
+        )
+        .await
+        .unwrap();
+
+    // compact
+    setup.run_compact().await;
+
+    // verify the deleted rows are gone, but rows without tag1 are kept
+    let mut batches = vec![];
+    for file in setup.list_by_table_not_to_delete().await {
+        batches.extend(setup.read_parquet_file(file).await);
+    }
+    assert_batches_sorted_eq!(
+        [
+            "+-----------+------+------+------+-----------------------------+",
+            "| field_int | tag1 | tag2 | tag3 | time                        |",
+            "+-----------+------+------+------+-----------------------------+",
+            "| 1500      | WA   |      |      | 1970-01-01T00:00:00.000008Z |",
+            "| 1601      |      | PA   | 15   | 1970-01-01T00:00:00.000030Z |",
+            "| 210       |      | OH   | 21   | 1970-01-01T00:00:00.000136Z |",
+            "| 22        |      | OH   | 21   | 1970-01-01T00:00:00.000036Z |",
+            "| 270       | UT   |      |      | 1970-01-01T00:00:00.000025Z |",
+            "| 70        | UT   |      |      | 1970-01-01T00:00:00.000020Z |",
+            "| 99        | OR   |      |      | 1970-01-01T00:00:00.000012Z |",
+            "+-----------+------+------+------+-----------------------------+",
+        ],
+        &batches
+    );
+
+    // the tombstone is recorded as applied to the files created by compaction, but is too
+    // recent to be removed
+    let mut repos = setup.catalog.catalog().repositories().await;
+    let processed = repos
+        .tombstones()
+        .list_processed_by_table_id(setup.table.table.id)
+        .await
+        .unwrap();
+    assert!(!processed.is_empty());
+    let file_ids = setup
+        .list_by_table_not_to_delete()
+        .await
+        .into_iter()
+        .map(|f| f.id)
+        .collect::<Vec<_>>();
+    for p in processed {
+        assert_eq!(p.tombstone_id, tombstone.id);
+        assert!(file_ids.contains(&p.parquet_file_id));
+    }
+
+    let tombstones = repos
+        .tombstones()
+        .list_by_table_id(setup.table.table.id)
+        .await
+        .unwrap();
+    assert_eq!(tombstones, vec![tombstone]);
+}
+
 #[tokio::test]
 async fn test_compact_large_overlapes() {
     test_helpers::maybe_start_logging();
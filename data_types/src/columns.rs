@@ -1,6 +1,6 @@
 //! Types having to do with columns.
 
-use super::{ChunkId, TableId};
+use super::{ChunkId, TableId, Timestamp};
 use influxdb_line_protocol::FieldValue;
 use schema::{builder::SchemaBuilder, InfluxColumnType, InfluxFieldType, Schema};
 use sqlx::postgres::PgHasArrayType;
@@ -145,6 +145,8 @@ pub struct Column {
     pub name: String,
     /// the logical type of the column
     pub column_type: ColumnType,
+    /// When this column was marked for deletion.
+    pub deleted_at: Option<Timestamp>,
 }
 
 impl Column {
@@ -283,6 +283,27 @@ impl std::fmt::Display for ParquetFileId {
     }
 }
 
+/// Unique ID for a `Tombstone`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, sqlx::Type)]
+#[sqlx(transparent)]
+pub struct TombstoneId(i64);
+
+#[allow(missing_docs)]
+impl TombstoneId {
+    pub fn new(v: i64) -> Self {
+        Self(v)
+    }
+    pub fn get(&self) -> i64 {
+        self.0
+    }
+}
+
+impl std::fmt::Display for TombstoneId {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 /// Data object for a namespace
 #[derive(Debug, Clone, Eq, PartialEq, sqlx::FromRow)]
 pub struct Namespace {
@@ -297,8 +318,16 @@ pub struct Namespace {
     pub max_tables: i32,
     /// The maximum number of columns per table in this namespace
     pub max_columns_per_table: i32,
+    /// The maximum number of queries that can run concurrently against this namespace
+    pub max_concurrent_queries: Option<i32>,
+    /// The maximum memory in bytes a single query against this namespace can reserve
+    pub max_query_memory_bytes: Option<i64>,
+    /// The maximum memory in bytes all queries against this namespace can reserve together
+    pub max_total_query_memory_bytes: Option<i64>,
     /// When this file was marked for deletion.
     pub deleted_at: Option<Timestamp>,
+    /// The partition template to use for writes in this namespace, if overridden.
+    pub partition_template: Option<NamespacePartitionTemplateOverride>,
 }
 
 /// Schema collection for a namespace. This is an in-memory object useful for a schema
@@ -313,6 +342,8 @@ pub struct NamespaceSchema {
     pub max_columns_per_table: usize,
     /// The maximum number of tables permitted in this namespace.
     pub max_tables: usize,
+    /// The limits on queries against this namespace.
+    pub query_limits: NamespaceQueryLimits,
     /// The retention period in ns.
     /// None represents infinite duration (i.e. never drop data).
     pub retention_period_ns: Option<i64>,
@@ -320,27 +351,53 @@ pub struct NamespaceSchema {
     pub partition_template: Option<Arc<NamespacePartitionTemplateOverride>>,
 }
 
+/// Limits on the queries run against a namespace, enforced by the querier on top of its own
+/// limits.
+///
+/// A limit of `None` leaves the namespace bound by the querier-wide limits only.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NamespaceQueryLimits {
+    /// The maximum number of queries that can run concurrently against the namespace.
+    pub max_concurrent_queries: Option<usize>,
+    /// The maximum memory in bytes a single query can reserve.
+    pub max_query_memory_bytes: Option<usize>,
+    /// The maximum memory in bytes all queries against the namespace can reserve together.
+    pub max_total_query_memory_bytes: Option<usize>,
+}
+
+impl From<&Namespace> for NamespaceQueryLimits {
+    fn from(namespace: &Namespace) -> Self {
+        Self {
+            max_concurrent_queries: namespace.max_concurrent_queries.map(|v| v as usize),
+            max_query_memory_bytes: namespace.max_query_memory_bytes.map(|v| v as usize),
+            max_total_query_memory_bytes: namespace
+                .max_total_query_memory_bytes
+                .map(|v| v as usize),
+        }
+    }
+}
+
 impl NamespaceSchema {
     /// Start a new `NamespaceSchema` with empty `tables` but the rest of the information populated
     /// from the given `Namespace`.
     pub fn new_empty_from(namespace: &Namespace) -> Self {
-        let &Namespace {
+        let Namespace {
             id,
             retention_period_ns,
             max_tables,
             max_columns_per_table,
+            partition_template,
             ..
         } = namespace;
 
         Self {
-            id,
+            id: *id,
             tables: BTreeMap::new(),
-            max_columns_per_table: max_columns_per_table as usize,
-            max_tables: max_tables as usize,
-            retention_period_ns,
-
-            // TODO: Store and retrieve PartitionTemplate from the database
-            partition_template: None,
+            max_columns_per_table: *max_columns_per_table as usize,
+            max_tables: *max_tables as usize,
+            query_limits: NamespaceQueryLimits::from(namespace),
+            retention_period_ns: *retention_period_ns,
+            partition_template: partition_template.clone().map(Arc::new),
         }
     }
 }
@@ -366,6 +423,10 @@ pub struct Table {
     pub namespace_id: NamespaceId,
     /// The name of the table, which is unique within the associated namespace
     pub name: String,
+    /// The partition template to use for writes to this table, if overridden.
+    pub partition_template: Option<TablePartitionTemplateOverride>,
+    /// When this table was marked for deletion.
+    pub deleted_at: Option<Timestamp>,
 }
 
 /// Column definitions for a table
@@ -395,8 +456,7 @@ impl TableSchema {
     pub fn new_empty_from(table: &Table) -> Self {
         Self {
             id: table.id,
-            // TODO: Store and retrieve PartitionTemplate from the database
-            partition_template: None,
+            partition_template: table.partition_template.clone().map(Arc::new),
             columns: ColumnsByName::new([]),
         }
     }
@@ -413,6 +473,7 @@ impl TableSchema {
             name,
             column_type,
             table_id,
+            deleted_at: _,
         } = col;
 
         assert_eq!(table_id, self.id);
@@ -623,6 +684,50 @@ pub struct SkippedCompaction {
     pub limit_num_files_first_in_partition: i64,
 }
 
+/// A delete request recorded in the catalog.
+///
+/// A tombstone removes the rows of the table with a timestamp within
+/// `[min_time, max_time]` (both ends inclusive) that match
+/// `serialized_predicate`, and that were written before the tombstone was
+/// created. Rows written after `created_at` are not affected.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, sqlx::FromRow)]
+pub struct Tombstone {
+    /// the id of the tombstone
+    pub id: TombstoneId,
+    /// the table the delete applies to
+    pub table_id: TableId,
+    /// the inclusive lower bound of the deleted time range
+    pub min_time: Timestamp,
+    /// the inclusive upper bound of the deleted time range
+    pub max_time: Timestamp,
+    /// the tag predicate, as produced by [`DeletePredicate::expr_sql_string`]
+    pub serialized_predicate: String,
+    /// when the delete was requested
+    pub created_at: Timestamp,
+}
+
+impl Tombstone {
+    /// Returns true if this tombstone removes rows from a parquet file with the
+    /// given `max_l0_created_at`.
+    ///
+    /// Only files containing data persisted before the delete was requested
+    /// are affected. Data persisted afterwards was either written after the
+    /// delete, or had the delete applied by the ingester that buffered it.
+    pub fn applies_to(&self, max_l0_created_at: Timestamp) -> bool {
+        max_l0_created_at < self.created_at
+    }
+}
+
+/// Records that the rows deleted by a [`Tombstone`] have been removed from a
+/// parquet file, so the tombstone no longer needs to be applied to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, sqlx::FromRow)]
+pub struct ProcessedTombstone {
+    /// the id of the tombstone
+    pub tombstone_id: TombstoneId,
+    /// the id of the parquet file the tombstone was applied to
+    pub parquet_file_id: ParquetFileId,
+}
+
 /// Data for a parquet file reference that has been inserted in the catalog.
 #[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
 pub struct ParquetFile {
@@ -2648,6 +2753,7 @@ mod tests {
                     table_id: TableId::new(2),
                     name: String::from("foo"),
                     column_type: ColumnType::Bool,
+                    deleted_at: None,
                 }]
                 .into_iter(),
             ),
@@ -2662,6 +2768,7 @@ mod tests {
             tables: BTreeMap::from([]),
             max_columns_per_table: 4,
             max_tables: 42,
+            query_limits: Default::default(),
             retention_period_ns: None,
             partition_template: None,
         };
@@ -2677,6 +2784,7 @@ mod tests {
             )]),
             max_columns_per_table: 4,
             max_tables: 42,
+            query_limits: Default::default(),
             retention_period_ns: None,
             partition_template: None,
         };
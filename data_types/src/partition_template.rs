@@ -1,4 +1,6 @@
 use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
 use std::sync::Arc;
 
 /// A partition template specified by a namespace record.
@@ -10,6 +12,11 @@ impl NamespacePartitionTemplateOverride {
     pub fn new(partition_template: PartitionTemplate) -> Self {
         Self(partition_template)
     }
+
+    /// The partition template of this override.
+    pub fn partition_template(&self) -> &PartitionTemplate {
+        &self.0
+    }
 }
 
 /// A partition template specified by a table record.
@@ -21,8 +28,73 @@ impl TablePartitionTemplateOverride {
     pub fn new(partition_template: PartitionTemplate) -> Self {
         Self(partition_template)
     }
+
+    /// The partition template of this override.
+    pub fn partition_template(&self) -> &PartitionTemplate {
+        &self.0
+    }
 }
 
+/// A table created without an explicit partition template inherits the template of its
+/// namespace at creation time, so that later changes to the namespace do not change how existing
+/// tables are partitioned.
+impl From<&NamespacePartitionTemplateOverride> for TablePartitionTemplateOverride {
+    fn from(namespace: &NamespacePartitionTemplateOverride) -> Self {
+        Self(namespace.0.clone())
+    }
+}
+
+/// Implement the sqlx traits for a partition template override, storing the template as JSON.
+macro_rules! impl_sqlx_json {
+    ($t:ty) => {
+        impl<DB> sqlx::Type<DB> for $t
+        where
+            DB: sqlx::Database,
+            Json<PartitionTemplate>: sqlx::Type<DB>,
+        {
+            fn type_info() -> DB::TypeInfo {
+                <Json<PartitionTemplate> as sqlx::Type<DB>>::type_info()
+            }
+
+            fn compatible(ty: &DB::TypeInfo) -> bool {
+                <Json<PartitionTemplate> as sqlx::Type<DB>>::compatible(ty)
+            }
+        }
+
+        impl<'q, DB> sqlx::Encode<'q, DB> for $t
+        where
+            DB: sqlx::Database,
+            Json<PartitionTemplate>: sqlx::Encode<'q, DB>,
+        {
+            fn encode_by_ref(
+                &self,
+                buf: &mut <DB as sqlx::database::HasArguments<'q>>::ArgumentBuffer,
+            ) -> sqlx::encode::IsNull {
+                <Json<PartitionTemplate> as sqlx::Encode<'q, DB>>::encode_by_ref(
+                    &Json(self.0.clone()),
+                    buf,
+                )
+            }
+        }
+
+        impl<'r, DB> sqlx::Decode<'r, DB> for $t
+        where
+            DB: sqlx::Database,
+            Json<PartitionTemplate>: sqlx::Decode<'r, DB>,
+        {
+            fn decode(
+                value: <DB as sqlx::database::HasValueRef<'r>>::ValueRef,
+            ) -> Result<Self, sqlx::error::BoxDynError> {
+                <Json<PartitionTemplate> as sqlx::Decode<'r, DB>>::decode(value)
+                    .map(|Json(partition_template)| Self(partition_template))
+            }
+        }
+    };
+}
+
+impl_sqlx_json!(NamespacePartitionTemplateOverride);
+impl_sqlx_json!(TablePartitionTemplateOverride);
+
 /// A partition template specified as the default to be used in the absence of any overrides.
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
 pub struct DefaultPartitionTemplate(&'static PartitionTemplate);
@@ -44,7 +116,7 @@ pub static PARTITION_BY_DAY: Lazy<PartitionTemplate> = Lazy::new(|| PartitionTem
 ///
 /// The key is constructed in order of the template parts; thus ordering changes what partition key
 /// is generated.
-#[derive(Debug, Eq, PartialEq, Clone)]
+#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
 #[allow(missing_docs)]
 pub struct PartitionTemplate {
     pub parts: Vec<TemplatePart>,
@@ -68,7 +140,7 @@ impl PartitionTemplate {
 
 /// `TemplatePart` specifies what part of a row should be used to compute this
 /// part of a partition key.
-#[derive(Debug, Eq, PartialEq, Clone)]
+#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
 pub enum TemplatePart {
     /// The value in a named column
     Column(String),
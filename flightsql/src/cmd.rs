@@ -2,6 +2,10 @@
 
 use std::fmt::Display;
 
+use arrow::{
+    ipc::{reader::StreamReader, writer::StreamWriter},
+    record_batch::RecordBatch,
+};
 use arrow_flight::sql::{
     ActionClosePreparedStatementRequest, ActionCreatePreparedStatementRequest, Any,
     CommandGetCatalogs, CommandGetCrossReference, CommandGetDbSchemas, CommandGetExportedKeys,
@@ -9,23 +13,31 @@ use arrow_flight::sql::{
     CommandGetTables, CommandGetXdbcTypeInfo, CommandPreparedStatementQuery, CommandStatementQuery,
 };
 use bytes::Bytes;
+use datafusion::{error::DataFusionError, scalar::ScalarValue};
 use prost::Message;
 use snafu::ResultExt;
 
 use crate::error::*;
 
 /// Represents a prepared statement "handle". IOx passes all state
-/// required to run the prepared statement back and forth to the
-/// client, so any querier instance can run it
+/// required to run the prepared statement, including the parameter
+/// values bound to it with `DoPut`, back and forth to the client, so
+/// any querier instance can run it.
 #[derive(Debug, Clone, PartialEq)]
 pub struct PreparedStatementHandle {
     /// The raw SQL query text
     query: String,
+
+    /// Values bound to the placeholders (`$1`, `$2`, ...) of the query
+    params: Vec<ScalarValue>,
 }
 
 impl PreparedStatementHandle {
     pub fn new(query: String) -> Self {
-        Self { query }
+        Self {
+            query,
+            params: vec![],
+        }
     }
 
     /// return the query
@@ -33,16 +45,43 @@ impl PreparedStatementHandle {
         self.query.as_ref()
     }
 
+    /// return the values bound to the placeholders of the query
+    pub fn params(&self) -> &[ScalarValue] {
+        &self.params
+    }
+
+    /// Bind `params` to the placeholders of the query
+    pub fn with_params(self, params: Vec<ScalarValue>) -> Self {
+        Self { params, ..self }
+    }
+
     fn try_decode(handle: Bytes) -> Result<Self> {
-        // Note: in IOx  handles are the entire decoded query
-        // It will likely need to get more sophisticated as part of
-        // https://github.com/influxdata/influxdb_iox/issues/6699
-        let query = String::from_utf8(handle.to_vec()).context(InvalidHandleSnafu)?;
-        Ok(Self { query })
+        match handle.split_first() {
+            Some((&ENCODED_HANDLE_MARKER, encoded)) => {
+                let EncodedHandle { query, params } =
+                    EncodedHandle::decode(encoded).context(InvalidEncodedHandleSnafu)?;
+                let params = decode_params(params).context(InvalidHandleParamsSnafu)?;
+                Ok(Self { query, params })
+            }
+            // Handles issued by older versions of IOx are the entire
+            // query text
+            _ => {
+                let query = String::from_utf8(handle.to_vec()).context(InvalidHandleSnafu)?;
+                Ok(Self {
+                    query,
+                    params: vec![],
+                })
+            }
+        }
     }
 
-    fn encode(self) -> Bytes {
-        Bytes::from(self.query.into_bytes())
+    fn encode(self) -> Result<Bytes> {
+        let Self { query, params } = self;
+        let params = encode_params(&params).context(InvalidHandleParamsSnafu)?;
+
+        let mut buf = vec![ENCODED_HANDLE_MARKER];
+        buf.extend(EncodedHandle { query, params }.encode_to_vec());
+        Ok(buf.into())
     }
 }
 
@@ -53,9 +92,86 @@ impl Display for PreparedStatementHandle {
 }
 
 /// Encode a PreparedStatementHandle as Bytes
-impl From<PreparedStatementHandle> for Bytes {
-    fn from(value: PreparedStatementHandle) -> Self {
-        Bytes::from(value.query.into_bytes())
+impl TryFrom<PreparedStatementHandle> for Bytes {
+    type Error = Error;
+
+    fn try_from(value: PreparedStatementHandle) -> Result<Self> {
+        value.encode()
+    }
+}
+
+/// The first byte of an [`EncodedHandle`] sent to the client.
+///
+/// Handles issued by older versions of IOx are the raw UTF-8 query
+/// text, which never contains this byte.
+const ENCODED_HANDLE_MARKER: u8 = 0xFF;
+
+/// The form of a [`PreparedStatementHandle`] sent to the client
+#[derive(Clone, PartialEq, Message)]
+struct EncodedHandle {
+    #[prost(string, tag = "1")]
+    query: String,
+
+    /// The bound parameter values, as a single row Arrow IPC stream
+    /// with a column per parameter. Empty if no values are bound.
+    #[prost(bytes = "bytes", tag = "2")]
+    params: Bytes,
+}
+
+/// Encode `params` as a single row Arrow IPC stream
+fn encode_params(params: &[ScalarValue]) -> Result<Bytes, DataFusionError> {
+    if params.is_empty() {
+        return Ok(Bytes::new());
+    }
+
+    let batch = RecordBatch::try_from_iter(
+        params
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (format!("${}", i + 1), v.to_array())),
+    )?;
+
+    let mut writer = StreamWriter::try_new(vec![], &batch.schema())?;
+    writer.write(&batch)?;
+    Ok(writer.into_inner()?.into())
+}
+
+/// Decode the parameter values encoded by [`encode_params`]
+fn decode_params(params: Bytes) -> Result<Vec<ScalarValue>, DataFusionError> {
+    if params.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let mut params = vec![];
+    for batch in StreamReader::try_new(std::io::Cursor::new(params), None)? {
+        let batch = batch?;
+        for row in 0..batch.num_rows() {
+            for array in batch.columns() {
+                params.push(ScalarValue::try_from_array(array, row)?);
+            }
+        }
+    }
+    Ok(params)
+}
+
+/// The `app_metadata` of the `PutResult` returned by `DoPut` requests
+/// that bind parameter values to a prepared statement.
+///
+/// This mirrors the `DoPutPreparedStatementResult` message of the
+/// FlightSQL specification: as the bound values are part of the
+/// handle, clients must use the returned handle to run the statement.
+#[derive(Clone, PartialEq, Message)]
+pub struct DoPutPreparedStatementResult {
+    #[prost(bytes = "bytes", optional, tag = "1")]
+    pub prepared_statement_handle: Option<Bytes>,
+}
+
+impl DoPutPreparedStatementResult {
+    /// Create the result of binding parameter values to `handle`
+    pub fn try_new(handle: PreparedStatementHandle) -> Result<Self> {
+        Ok(Self {
+            prepared_statement_handle: Some(handle.encode()?),
+        })
     }
 }
 
@@ -305,7 +421,7 @@ impl FlightSQLCommand {
         let msg = match self {
             FlightSQLCommand::CommandStatementQuery(cmd) => Any::pack(&cmd),
             FlightSQLCommand::CommandPreparedStatementQuery(handle) => {
-                let prepared_statement_handle = handle.encode();
+                let prepared_statement_handle = handle.encode()?;
                 let cmd = CommandPreparedStatementQuery {
                     prepared_statement_handle,
                 };
@@ -323,7 +439,7 @@ impl FlightSQLCommand {
             FlightSQLCommand::CommandGetXdbcTypeInfo(cmd) => Any::pack(&cmd),
             FlightSQLCommand::ActionCreatePreparedStatementRequest(cmd) => Any::pack(&cmd),
             FlightSQLCommand::ActionClosePreparedStatementRequest(handle) => {
-                let prepared_statement_handle = handle.encode();
+                let prepared_statement_handle = handle.encode()?;
                 Any::pack(&ActionClosePreparedStatementRequest {
                     prepared_statement_handle,
                 })
@@ -19,6 +19,12 @@ pub enum Error {
     #[snafu(display("Invalid PreparedStatement handle (invalid UTF-8:) {}", source))]
     InvalidHandle { source: FromUtf8Error },
 
+    #[snafu(display("Invalid PreparedStatement handle: {}", source))]
+    InvalidEncodedHandle { source: DecodeError },
+
+    #[snafu(display("Invalid PreparedStatement parameters: {}", source))]
+    InvalidHandleParams { source: DataFusionError },
+
     #[snafu(display("{}", source))]
     #[snafu(context(false))]
     Flight { source: FlightError },
@@ -8,6 +8,6 @@ mod planner;
 mod sql_info;
 mod xdbc_type_info;
 
-pub use cmd::{FlightSQLCommand, PreparedStatementHandle};
+pub use cmd::{DoPutPreparedStatementResult, FlightSQLCommand, PreparedStatementHandle};
 pub use error::{Error, Result};
 pub use planner::FlightSQLPlanner;
@@ -58,7 +58,7 @@ impl FlightSQLPlanner {
                 get_schema_for_query(&query, ctx).await
             }
             FlightSQLCommand::CommandPreparedStatementQuery(handle) => {
-                get_schema_for_query(handle.query(), ctx).await
+                get_schema_for_plan(plan_prepared_statement(&handle, ctx).await?)
             }
             FlightSQLCommand::CommandGetSqlInfo(CommandGetSqlInfo { .. }) => {
                 encode_schema(iox_sql_info_list().schema())
@@ -117,7 +117,8 @@ impl FlightSQLPlanner {
             FlightSQLCommand::CommandPreparedStatementQuery(handle) => {
                 let query = handle.query();
                 debug!(%query, "Planning FlightSQL prepared query");
-                Ok(ctx.sql_to_physical_plan(query).await?)
+                let plan = plan_prepared_statement(&handle, ctx).await?;
+                Ok(ctx.create_physical_plan(&plan).await?)
             }
             FlightSQLCommand::CommandGetSqlInfo(CommandGetSqlInfo { info }) => {
                 debug!("Planning GetSqlInfo query");
@@ -283,7 +284,7 @@ impl FlightSQLPlanner {
                 let handle = PreparedStatementHandle::new(query);
 
                 let result = ActionCreatePreparedStatementResult {
-                    prepared_statement_handle: Bytes::from(handle),
+                    prepared_statement_handle: Bytes::try_from(handle)?,
                     dataset_schema,
                     parameter_schema: Bytes::from(parameter_schema),
                 };
@@ -314,6 +315,21 @@ async fn get_schema_for_query(query: &str, ctx: &IOxSessionContext) -> Result<By
     get_schema_for_plan(ctx.sql_to_logical_plan(query).await?)
 }
 
+/// Return the logical plan for the query of a prepared statement,
+/// with the parameter values bound to it substituted for its
+/// placeholders
+async fn plan_prepared_statement(
+    handle: &PreparedStatementHandle,
+    ctx: &IOxSessionContext,
+) -> Result<LogicalPlan> {
+    let plan = ctx.sql_to_logical_plan(handle.query()).await?;
+    if handle.params().is_empty() {
+        Ok(plan)
+    } else {
+        Ok(plan.with_param_values(handle.params().to_vec())?)
+    }
+}
+
 /// Return the schema for the specified logical plan
 ///
 /// returns: IPC encoded (schema_bytes) for this query
@@ -18,6 +18,7 @@
 #![allow(clippy::missing_docs_in_private_items)]
 
 use crate::{
+    namespace::purger as ns_purger,
     objectstore::{checker as os_checker, deleter as os_deleter, lister as os_lister},
     parquetfile::deleter as pf_deleter,
     retention::flagger as retention_flagger,
@@ -33,6 +34,8 @@ use std::{fmt::Debug, sync::Arc};
 use tokio::{select, sync::mpsc};
 use tokio_util::sync::CancellationToken;
 
+/// Logic for permanently removing soft-deleted namespaces
+mod namespace;
 /// Logic for listing, checking and deleting files in object storage
 mod objectstore;
 /// Logic for deleting parquet files from the catalog
@@ -55,6 +58,7 @@ pub struct GarbageCollector {
     os_deleter: tokio::task::JoinHandle<Result<(), os_deleter::Error>>,
     pf_deleter: tokio::task::JoinHandle<Result<(), pf_deleter::Error>>,
     retention_flagger: tokio::task::JoinHandle<Result<(), retention_flagger::Error>>,
+    ns_purger: tokio::task::JoinHandle<Result<(), ns_purger::Error>>,
 }
 
 impl Debug for GarbageCollector {
@@ -79,6 +83,9 @@ impl GarbageCollector {
             objectstore_sleep_interval_minutes = %sub_config.objectstore_sleep_interval_minutes,
             parquetfile_sleep_interval_minutes = %sub_config.parquetfile_sleep_interval_minutes,
             retention_sleep_interval_minutes = %sub_config.retention_sleep_interval_minutes,
+            table_delete_cutoff = %format_duration(sub_config.table_delete_cutoff).to_string(),
+            namespace_delete_cutoff = %format_duration(sub_config.namespace_delete_cutoff).to_string(),
+            namespace_purge_sleep_interval_minutes = %sub_config.namespace_purge_sleep_interval_minutes,
             "GarbageCollector starting"
         );
 
@@ -96,6 +103,9 @@ impl GarbageCollector {
         let (tx1, rx1) = mpsc::channel(BUFFER_SIZE);
         let (tx2, rx2) = mpsc::channel(BUFFER_SIZE);
 
+        // The namespace purger also sends the files of purged namespaces to the deleter.
+        let ns_purger_tx = tx2.clone();
+
         let sdt = shutdown.clone();
         let osa = Arc::clone(&object_store);
 
@@ -146,11 +156,24 @@ impl GarbageCollector {
         // flag_for_delete_by_retention() on the catalog then sleeps.
         let retention_flagger = tokio::spawn(retention_flagger::perform(
             shutdown.clone(),
-            catalog,
+            Arc::clone(&catalog),
             sub_config.retention_sleep_interval_minutes,
+            sub_config.table_delete_cutoff,
             sub_config.dry_run,
         ));
 
+        // Initialise the namespace purger, which is just one thread that removes namespaces
+        // soft-deleted before the cutoff from the catalog, hands their parquet files to the
+        // object store deleter, then sleeps.
+        let ns_purger = tokio::spawn(ns_purger::perform(
+            shutdown.clone(),
+            catalog,
+            sub_config.namespace_delete_cutoff,
+            sub_config.namespace_purge_sleep_interval_minutes,
+            sub_config.dry_run,
+            ns_purger_tx,
+        ));
+
         Ok(Self {
             shutdown,
             os_lister,
@@ -158,6 +181,7 @@ impl GarbageCollector {
             os_deleter,
             pf_deleter,
             retention_flagger,
+            ns_purger,
         })
     }
 
@@ -177,17 +201,20 @@ impl GarbageCollector {
             os_deleter,
             pf_deleter,
             retention_flagger,
+            ns_purger,
             shutdown: _,
         } = self;
 
-        let (os_lister, os_checker, os_deleter, pf_deleter, retention_flagger) = futures::join!(
+        let (os_lister, os_checker, os_deleter, pf_deleter, retention_flagger, ns_purger) = futures::join!(
             os_lister,
             os_checker,
             os_deleter,
             pf_deleter,
-            retention_flagger
+            retention_flagger,
+            ns_purger
         );
 
+        ns_purger.context(NamespacePurgerPanicSnafu)??;
         retention_flagger.context(ParquetFileDeleterPanicSnafu)??;
         pf_deleter.context(ParquetFileDeleterPanicSnafu)??;
         os_deleter.context(ObjectStoreDeleterPanicSnafu)??;
@@ -254,6 +281,12 @@ pub enum Error {
     ParquetFileRetentionFlagger { source: retention_flagger::Error },
     #[snafu(display("The parquet file retention flagger task panicked"))]
     ParquetFileRetentionFlaggerPanic { source: tokio::task::JoinError },
+
+    #[snafu(display("The namespace purger task failed"))]
+    #[snafu(context(false))]
+    NamespacePurger { source: ns_purger::Error },
+    #[snafu(display("The namespace purger task panicked"))]
+    NamespacePurgerPanic { source: tokio::task::JoinError },
 }
 
 #[allow(missing_docs)]
@@ -0,0 +1,2 @@
+/// Logic for permanently removing soft-deleted namespaces
+pub(crate) mod purger;
@@ -0,0 +1,228 @@
+use data_types::Timestamp;
+use iox_catalog::interface::{Catalog, SoftDeletedRows};
+use object_store::ObjectMeta;
+use observability_deps::tracing::*;
+use parquet_file::ParquetFilePath;
+use snafu::prelude::*;
+use std::{sync::Arc, time::Duration};
+use tokio::{select, sync::mpsc, time::sleep};
+use tokio_util::sync::CancellationToken;
+
+pub(crate) async fn perform(
+    shutdown: CancellationToken,
+    catalog: Arc<dyn Catalog>,
+    cutoff: Duration,
+    sleep_interval_minutes: u64,
+    dry_run: bool,
+    deleter: mpsc::Sender<ObjectMeta>,
+) -> Result<()> {
+    loop {
+        if !dry_run {
+            let older_than = Timestamp::from(catalog.time_provider().now() - cutoff);
+            let purged = purge_namespaces(&*catalog, older_than, &deleter).await?;
+            info!(purged_count = %purged, "purged soft-deleted namespaces");
+        } else {
+            debug!("dry run enabled for namespace purger");
+        }
+
+        select! {
+            _ = shutdown.cancelled() => {
+                break
+            },
+            _ = sleep(Duration::from_secs(60 * sleep_interval_minutes)) => (),
+        }
+    }
+    Ok(())
+}
+
+/// Permanently remove all namespaces soft-deleted before `older_than` from the
+/// catalog, and send the objects of their parquet files to the object store
+/// `deleter`. Returns the number of namespaces purged.
+async fn purge_namespaces(
+    catalog: &dyn Catalog,
+    older_than: Timestamp,
+    deleter: &mpsc::Sender<ObjectMeta>,
+) -> Result<usize> {
+    let mut repos = catalog.repositories().await;
+
+    let namespaces = repos
+        .namespaces()
+        .list(SoftDeletedRows::OnlyDeleted)
+        .await
+        .context(ListingSnafu)?;
+
+    let mut purged = 0;
+    for namespace in namespaces
+        .into_iter()
+        .filter(|n| matches!(n.deleted_at, Some(deleted_at) if deleted_at < older_than))
+    {
+        // The parquet file records are removed along with the namespace, so
+        // collect the files, including those already flagged for deletion,
+        // first.
+        let mut files = vec![];
+        let tables = repos
+            .tables()
+            .list_by_namespace_id(namespace.id)
+            .await
+            .context(ListingSnafu)?;
+        for table in tables {
+            files.extend(
+                repos
+                    .parquet_files()
+                    .list_by_table(table.id)
+                    .await
+                    .context(ListingSnafu)?,
+            );
+        }
+
+        // The namespace may have been restored in the meantime, in which case
+        // it is left alone.
+        if !repos
+            .namespaces()
+            .purge(namespace.id, older_than)
+            .await
+            .context(PurgingSnafu)?
+        {
+            continue;
+        }
+
+        info!(
+            namespace_id = %namespace.id,
+            namespace_name = %namespace.name,
+            file_count = %files.len(),
+            "purged soft-deleted namespace"
+        );
+        purged += 1;
+
+        for file in files {
+            let item = ObjectMeta {
+                location: ParquetFilePath::from(&file).object_store_path(),
+                // the deleter does not care about the "last modified" field
+                last_modified: Default::default(),
+                size: file.file_size_bytes as usize,
+            };
+            deleter.send(item).await.context(DeleterExitedSnafu)?;
+        }
+    }
+
+    Ok(purged)
+}
+
+#[derive(Debug, Snafu)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[snafu(display("Failed to list soft-deleted namespaces and their parquet files"))]
+    Listing {
+        source: iox_catalog::interface::Error,
+    },
+
+    #[snafu(display("Failed to purge soft-deleted namespace"))]
+    Purging {
+        source: iox_catalog::interface::Error,
+    },
+
+    #[snafu(display("The deleter task exited unexpectedly"))]
+    DeleterExited {
+        source: tokio::sync::mpsc::error::SendError<ObjectMeta>,
+    },
+}
+
+pub(crate) type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use data_types::{ColumnId, ColumnSet, CompactionLevel, ParquetFileParams};
+    use iox_catalog::mem::MemCatalog;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn purge_soft_deleted_namespace() {
+        let metric_registry = Arc::new(metric::Registry::new());
+        let catalog = Arc::new(MemCatalog::new(Arc::clone(&metric_registry)));
+        let mut repos = catalog.repositories().await;
+        let namespace = repos
+            .namespaces()
+            .create("namespace_purge_test", None, None)
+            .await
+            .unwrap();
+        let table = repos
+            .tables()
+            .create_or_get("test_table", namespace.id)
+            .await
+            .unwrap();
+        let partition = repos
+            .partitions()
+            .create_or_get("one".into(), table.id)
+            .await
+            .unwrap();
+        let parquet_file = repos
+            .parquet_files()
+            .create(ParquetFileParams {
+                namespace_id: namespace.id,
+                table_id: partition.table_id,
+                partition_id: partition.id,
+                object_store_id: Uuid::new_v4(),
+                min_time: Timestamp::new(1),
+                max_time: Timestamp::new(10),
+                file_size_bytes: 1337,
+                row_count: 0,
+                compaction_level: CompactionLevel::Initial,
+                created_at: Timestamp::new(1),
+                column_set: ColumnSet::new([ColumnId::new(1)]),
+                max_l0_created_at: Timestamp::new(1),
+            })
+            .await
+            .unwrap();
+
+        repos
+            .namespaces()
+            .soft_delete("namespace_purge_test")
+            .await
+            .unwrap();
+        let deleted_at = repos
+            .namespaces()
+            .get_by_id(namespace.id, SoftDeletedRows::OnlyDeleted)
+            .await
+            .unwrap()
+            .unwrap()
+            .deleted_at
+            .unwrap();
+        drop(repos);
+
+        let (tx, mut rx) = mpsc::channel(10);
+
+        // Within the cutoff, nothing is purged
+        let purged = purge_namespaces(&*catalog, deleted_at, &tx).await.unwrap();
+        assert_eq!(purged, 0);
+        assert!(rx.try_recv().is_err());
+
+        let purged = purge_namespaces(&*catalog, deleted_at + 1, &tx)
+            .await
+            .unwrap();
+        assert_eq!(purged, 1);
+
+        // The parquet file is sent to the object store deleter ...
+        let item = rx.try_recv().unwrap();
+        assert_eq!(
+            item.location,
+            ParquetFilePath::from(&parquet_file).object_store_path()
+        );
+        assert!(rx.try_recv().is_err());
+
+        // ... and the namespace is gone from the catalog
+        let mut repos = catalog.repositories().await;
+        assert!(repos
+            .namespaces()
+            .get_by_id(namespace.id, SoftDeletedRows::AllRows)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(repos
+            .parquet_files()
+            .get_by_object_store_id(parquet_file.object_store_id)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
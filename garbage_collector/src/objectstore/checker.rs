@@ -157,7 +157,7 @@ mod tests {
         let mut repos = catalog.repositories().await;
         let namespace = repos
             .namespaces()
-            .create("namespace_parquet_file_test", None)
+            .create("namespace_parquet_file_test", None, None)
             .await
             .unwrap();
         let table = repos
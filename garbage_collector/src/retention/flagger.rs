@@ -1,3 +1,4 @@
+use data_types::Timestamp;
 use iox_catalog::interface::Catalog;
 use observability_deps::tracing::*;
 use snafu::prelude::*;
@@ -9,6 +10,7 @@ pub(crate) async fn perform(
     shutdown: CancellationToken,
     catalog: Arc<dyn Catalog>,
     sleep_interval_minutes: u64,
+    table_delete_cutoff: Duration,
     dry_run: bool,
 ) -> Result<()> {
     loop {
@@ -21,6 +23,10 @@ pub(crate) async fn perform(
                 .await
                 .context(FlaggingSnafu)?;
             info!(flagged_count = %flagged.len(), "iox_catalog::flag_for_delete_by_retention()");
+
+            let older_than = Timestamp::from(catalog.time_provider().now() - table_delete_cutoff);
+            let flagged = flag_soft_deleted_tables(&*catalog, older_than).await?;
+            info!(flagged_count = %flagged, "flagged parquet files of soft-deleted tables");
         } else {
             debug!("dry run enabled for parquet retention flagger");
         };
@@ -35,6 +41,37 @@ pub(crate) async fn perform(
     Ok(())
 }
 
+/// Flag the parquet files of all tables soft-deleted before `older_than` for
+/// deletion, returning the number of files flagged.
+async fn flag_soft_deleted_tables(catalog: &dyn Catalog, older_than: Timestamp) -> Result<usize> {
+    let mut repos = catalog.repositories().await;
+
+    let tables = repos.tables().list().await.context(FlaggingTablesSnafu)?;
+
+    let mut flagged = 0;
+    for table in tables
+        .into_iter()
+        .filter(|t| matches!(t.deleted_at, Some(deleted_at) if deleted_at < older_than))
+    {
+        let files = repos
+            .parquet_files()
+            .list_by_table_not_to_delete(table.id)
+            .await
+            .context(FlaggingTablesSnafu)?;
+
+        for file in files {
+            repos
+                .parquet_files()
+                .flag_for_delete(file.id)
+                .await
+                .context(FlaggingTablesSnafu)?;
+            flagged += 1;
+        }
+    }
+
+    Ok(flagged)
+}
+
 #[derive(Debug, Snafu)]
 #[allow(missing_docs)]
 pub enum Error {
@@ -42,6 +79,11 @@ pub enum Error {
     Flagging {
         source: iox_catalog::interface::Error,
     },
+
+    #[snafu(display("Failed to flag parquet files of soft-deleted tables for deletion"))]
+    FlaggingTables {
+        source: iox_catalog::interface::Error,
+    },
 }
 
 pub(crate) type Result<T, E = Error> = std::result::Result<T, E>;
@@ -118,6 +118,19 @@ pub mod influxdata {
             }
         }
 
+        pub mod partition_template {
+            pub mod v1 {
+                include!(concat!(
+                    env!("OUT_DIR"),
+                    "/influxdata.iox.partition_template.v1.rs"
+                ));
+                include!(concat!(
+                    env!("OUT_DIR"),
+                    "/influxdata.iox.partition_template.v1.serde.rs"
+                ));
+            }
+        }
+
         pub mod predicate {
             pub mod v1 {
                 include!(concat!(env!("OUT_DIR"), "/influxdata.iox.predicate.v1.rs"));
@@ -167,6 +180,16 @@ pub mod influxdata {
             }
         }
 
+        pub mod table {
+            pub mod v1 {
+                include!(concat!(env!("OUT_DIR"), "/influxdata.iox.table.v1.rs"));
+                include!(concat!(
+                    env!("OUT_DIR"),
+                    "/influxdata.iox.table.v1.serde.rs"
+                ));
+            }
+        }
+
         pub mod wal {
             pub mod v1 {
                 include!(concat!(env!("OUT_DIR"), "/influxdata.iox.wal.v1.rs"));
@@ -245,6 +268,8 @@ pub mod compactor;
 pub mod delete_predicate;
 #[cfg(any(feature = "data_types_conversions", test))]
 pub mod ingester;
+#[cfg(any(feature = "data_types_conversions", test))]
+pub mod partition_template;
 
 pub use prost::{DecodeError, EncodeError};
 
@@ -0,0 +1,156 @@
+//! Conversions between the protobuf and [`data_types`] representations of partition templates.
+
+use crate::google::{FieldViolation, FromRepeatedField, NonEmptyString};
+use crate::influxdata::iox::partition_template::v1 as proto;
+use chrono::format::{Item, StrftimeItems};
+use data_types::{
+    NamespacePartitionTemplateOverride, PartitionTemplate, TablePartitionTemplateOverride,
+    TemplatePart,
+};
+
+impl TryFrom<proto::PartitionTemplate> for PartitionTemplate {
+    type Error = FieldViolation;
+
+    fn try_from(value: proto::PartitionTemplate) -> Result<Self, Self::Error> {
+        if value.parts.is_empty() {
+            return Err(FieldViolation {
+                field: "parts".to_string(),
+                description: "a partition template must have at least one part".to_string(),
+            });
+        }
+
+        Ok(Self {
+            parts: value.parts.repeated("parts")?,
+        })
+    }
+}
+
+impl From<PartitionTemplate> for proto::PartitionTemplate {
+    fn from(value: PartitionTemplate) -> Self {
+        Self {
+            parts: value.parts.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<proto::TemplatePart> for TemplatePart {
+    type Error = FieldViolation;
+
+    fn try_from(value: proto::TemplatePart) -> Result<Self, Self::Error> {
+        Ok(match value.part {
+            Some(proto::template_part::Part::ColumnValue(column)) => {
+                Self::Column(column.non_empty("column_value")?)
+            }
+            Some(proto::template_part::Part::TimeFormat(format)) => {
+                let format = format.non_empty("time_format")?;
+                // Reject formats chrono cannot render, rather than failing every write later
+                if StrftimeItems::new(&format).any(|item| matches!(item, Item::Error)) {
+                    return Err(FieldViolation {
+                        field: "time_format".to_string(),
+                        description: format!("invalid strftime format: {format:?}"),
+                    });
+                }
+                Self::TimeFormat(format)
+            }
+            None => return Err(FieldViolation::required("part")),
+        })
+    }
+}
+
+impl From<TemplatePart> for proto::TemplatePart {
+    fn from(value: TemplatePart) -> Self {
+        let part = match value {
+            TemplatePart::Column(column) => proto::template_part::Part::ColumnValue(column),
+            TemplatePart::TimeFormat(format) => proto::template_part::Part::TimeFormat(format),
+        };
+
+        Self { part: Some(part) }
+    }
+}
+
+impl TryFrom<proto::PartitionTemplate> for NamespacePartitionTemplateOverride {
+    type Error = FieldViolation;
+
+    fn try_from(value: proto::PartitionTemplate) -> Result<Self, Self::Error> {
+        Ok(Self::new(value.try_into()?))
+    }
+}
+
+impl From<&NamespacePartitionTemplateOverride> for proto::PartitionTemplate {
+    fn from(value: &NamespacePartitionTemplateOverride) -> Self {
+        value.partition_template().clone().into()
+    }
+}
+
+impl TryFrom<proto::PartitionTemplate> for TablePartitionTemplateOverride {
+    type Error = FieldViolation;
+
+    fn try_from(value: proto::PartitionTemplate) -> Result<Self, Self::Error> {
+        Ok(Self::new(value.try_into()?))
+    }
+}
+
+impl From<&TablePartitionTemplateOverride> for proto::PartitionTemplate {
+    fn from(value: &TablePartitionTemplateOverride) -> Self {
+        value.partition_template().clone().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let template = PartitionTemplate {
+            parts: vec![
+                TemplatePart::Column("region".to_string()),
+                TemplatePart::TimeFormat("%Y-%m-%d %H".to_string()),
+            ],
+        };
+
+        let serialized: proto::PartitionTemplate = template.clone().into();
+        let deserialized: PartitionTemplate = serialized.try_into().unwrap();
+        assert_eq!(template, deserialized);
+    }
+
+    #[test]
+    fn test_no_parts() {
+        let err = PartitionTemplate::try_from(proto::PartitionTemplate { parts: vec![] })
+            .expect_err("empty template should be rejected");
+        assert_eq!(err.field, "parts");
+    }
+
+    #[test]
+    fn test_empty_part() {
+        let err = PartitionTemplate::try_from(proto::PartitionTemplate {
+            parts: vec![proto::TemplatePart { part: None }],
+        })
+        .expect_err("missing part should be rejected");
+        assert_eq!(err.field, "parts.0.part");
+
+        let err = PartitionTemplate::try_from(proto::PartitionTemplate {
+            parts: vec![
+                proto::TemplatePart {
+                    part: Some(proto::template_part::Part::TimeFormat("%Y".to_string())),
+                },
+                proto::TemplatePart {
+                    part: Some(proto::template_part::Part::ColumnValue(String::new())),
+                },
+            ],
+        })
+        .expect_err("empty column name should be rejected");
+        assert_eq!(err.field, "parts.1.column_value");
+    }
+
+    #[test]
+    fn test_invalid_time_format() {
+        let err = PartitionTemplate::try_from(proto::PartitionTemplate {
+            parts: vec![proto::TemplatePart {
+                part: Some(proto::template_part::Part::TimeFormat("%Y-%Q".to_string())),
+            }],
+        })
+        .expect_err("invalid time format should be rejected");
+        assert_eq!(err.field, "parts.0.time_format");
+    }
+}
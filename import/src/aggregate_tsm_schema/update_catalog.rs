@@ -87,7 +87,7 @@ async fn create_namespace<R>(name: &str, repos: &mut R) -> Result<Namespace, Upd
 where
     R: RepoCollection + ?Sized,
 {
-    match repos.namespaces().create(name, None).await {
+    match repos.namespaces().create(name, None, None).await {
         Ok(ns) => Ok(ns),
         Err(iox_catalog::interface::Error::NameExists { .. }) => {
             // presumably it got created in the meantime?
@@ -428,7 +428,7 @@ mod tests {
         // create namespace, table and columns for weather measurement
         let namespace = txn
             .namespaces()
-            .create("1234_5678", None)
+            .create("1234_5678", None, None)
             .await
             .expect("namespace created");
         let mut table = txn
@@ -520,7 +520,7 @@ mod tests {
         // create namespace, table and columns for weather measurement
         let namespace = txn
             .namespaces()
-            .create("1234_5678", None)
+            .create("1234_5678", None, None)
             .await
             .expect("namespace created");
         let mut table = txn
@@ -585,7 +585,7 @@ mod tests {
         // create namespace, table and columns for weather measurement
         let namespace = txn
             .namespaces()
-            .create("1234_5678", None)
+            .create("1234_5678", None, None)
             .await
             .expect("namespace created");
         let mut table = txn
@@ -0,0 +1,36 @@
+use influxdb_iox_client::{connection::Connection, flight};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Error cancelling query: {0}")]
+    Cancel(#[from] influxdb_iox_client::flight::Error),
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Cancel a running query
+#[derive(Debug, clap::Parser)]
+pub struct Config {
+    /// The IOx namespace the query runs against
+    #[clap(action)]
+    namespace: String,
+
+    /// The id of the query, as listed in the `system.queries` table
+    #[clap(action)]
+    query_id: String,
+}
+
+pub async fn command(connection: Connection, config: Config) -> Result<()> {
+    let mut client = flight::Client::new(connection);
+
+    let Config {
+        namespace,
+        query_id,
+    } = config;
+
+    client.cancel_query(namespace, query_id.clone()).await?;
+    println!("Cancelled query {query_id}");
+
+    Ok(())
+}
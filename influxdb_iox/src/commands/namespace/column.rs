@@ -0,0 +1,59 @@
+use influxdb_iox_client::connection::Connection;
+
+use crate::commands::namespace::Result;
+
+/// Soft-delete or restore a column of a table
+#[derive(Debug, clap::Parser)]
+pub struct Config {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, clap::Parser)]
+enum Command {
+    /// Soft-delete a column. The time column cannot be deleted
+    Delete(ColumnConfig),
+
+    /// Restore a soft-deleted column
+    Undelete(ColumnConfig),
+}
+
+#[derive(Debug, clap::Parser)]
+struct ColumnConfig {
+    /// The namespace the table belongs to
+    #[clap(action)]
+    namespace: String,
+
+    /// The name of the table the column belongs to
+    #[clap(action)]
+    table: String,
+
+    /// The name of the column
+    #[clap(action)]
+    column: String,
+}
+
+pub async fn command(connection: Connection, config: Config) -> Result<()> {
+    let mut client = influxdb_iox_client::namespace::Client::new(connection);
+
+    match config.command {
+        Command::Delete(ColumnConfig {
+            namespace,
+            table,
+            column,
+        }) => {
+            client.delete_column(&namespace, &table, &column).await?;
+            println!("Deleted column {column:?} of table {table:?} in namespace {namespace:?}");
+        }
+        Command::Undelete(ColumnConfig {
+            namespace,
+            table,
+            column,
+        }) => {
+            client.undelete_column(&namespace, &table, &column).await?;
+            println!("Undeleted column {column:?} of table {table:?} in namespace {namespace:?}");
+        }
+    }
+
+    Ok(())
+}
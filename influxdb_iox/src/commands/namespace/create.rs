@@ -1,4 +1,4 @@
-use influxdb_iox_client::connection::Connection;
+use influxdb_iox_client::{connection::Connection, namespace::generated_types::PartitionTemplate};
 
 use crate::commands::namespace::Result;
 
@@ -19,12 +19,24 @@ pub struct Config {
         default_value = "0"
     )]
     retention_hours: u32,
+
+    /// Partition template to use for writes to this namespace, as JSON.
+    /// If not specified, data is partitioned by day.
+    ///
+    /// Example: '{"parts": [{"columnValue": "region"}, {"timeFormat": "%Y-%m-%d %H"}]}'
+    #[clap(action, long = "partition-template", value_parser = parse_partition_template)]
+    partition_template: Option<PartitionTemplate>,
+}
+
+fn parse_partition_template(s: &str) -> Result<PartitionTemplate, serde_json::Error> {
+    serde_json::from_str(s)
 }
 
 pub async fn command(connection: Connection, config: Config) -> Result<()> {
     let Config {
         namespace,
         retention_hours,
+        partition_template,
     } = config;
 
     let mut client = influxdb_iox_client::namespace::Client::new(connection);
@@ -37,7 +49,9 @@ pub async fn command(connection: Connection, config: Config) -> Result<()> {
         // internally
         Some(retention_hours as i64 * 60 * 60 * 1_000_000_000)
     };
-    let namespace = client.create_namespace(&namespace, retention).await?;
+    let namespace = client
+        .create_namespace(&namespace, retention, partition_template)
+        .await?;
     println!("{}", serde_json::to_string_pretty(&namespace)?);
 
     Ok(())
@@ -3,9 +3,12 @@
 use influxdb_iox_client::{connection::Connection, namespace};
 use thiserror::Error;
 
+mod column;
 mod create;
 mod delete;
 mod retention;
+mod table;
+mod undelete;
 mod update_limit;
 
 #[allow(clippy::enum_variant_names)]
@@ -44,6 +47,15 @@ enum Command {
 
     /// Delete a namespace
     Delete(delete::Config),
+
+    /// Restore a deleted namespace, if it has not been removed by the garbage collector yet
+    Undelete(undelete::Config),
+
+    /// Delete or restore a table of a namespace
+    Table(table::Config),
+
+    /// Delete or restore a column of a table
+    Column(column::Config),
 }
 
 pub async fn command(connection: Connection, config: Config) -> Result<()> {
@@ -64,6 +76,15 @@ pub async fn command(connection: Connection, config: Config) -> Result<()> {
         }
         Command::Delete(config) => {
             delete::command(connection, config).await?;
+        }
+        Command::Undelete(config) => {
+            undelete::command(connection, config).await?;
+        }
+        Command::Table(config) => {
+            table::command(connection, config).await?;
+        }
+        Command::Column(config) => {
+            column::command(connection, config).await?;
         } // Deliberately not adding _ => so the compiler will direct people here to impl new
           // commands
     }
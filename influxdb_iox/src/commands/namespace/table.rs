@@ -0,0 +1,48 @@
+use influxdb_iox_client::connection::Connection;
+
+use crate::commands::namespace::Result;
+
+/// Soft-delete or restore a table of a namespace
+#[derive(Debug, clap::Parser)]
+pub struct Config {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, clap::Parser)]
+enum Command {
+    /// Soft-delete a table. Its data is removed by the garbage collector after
+    /// the configured cutoff, until which the table can be restored
+    Delete(TableConfig),
+
+    /// Restore a soft-deleted table
+    Undelete(TableConfig),
+}
+
+#[derive(Debug, clap::Parser)]
+struct TableConfig {
+    /// The namespace the table belongs to
+    #[clap(action)]
+    namespace: String,
+
+    /// The name of the table
+    #[clap(action)]
+    table: String,
+}
+
+pub async fn command(connection: Connection, config: Config) -> Result<()> {
+    let mut client = influxdb_iox_client::namespace::Client::new(connection);
+
+    match config.command {
+        Command::Delete(TableConfig { namespace, table }) => {
+            client.delete_table(&namespace, &table).await?;
+            println!("Deleted table {table:?} of namespace {namespace:?}");
+        }
+        Command::Undelete(TableConfig { namespace, table }) => {
+            client.undelete_table(&namespace, &table).await?;
+            println!("Undeleted table {table:?} of namespace {namespace:?}");
+        }
+    }
+
+    Ok(())
+}
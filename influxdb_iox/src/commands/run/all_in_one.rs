@@ -349,6 +349,29 @@ pub struct Config {
         action
     )]
     pub exec_mem_pool_bytes: usize,
+
+    /// Periodically write the metrics of all services to the namespace named
+    /// by `--self-monitoring-namespace`, at this interval in seconds.
+    ///
+    /// The metrics are written by the router, which shares a single metric
+    /// registry with the other services in this mode.
+    ///
+    /// Self-monitoring is disabled if unset.
+    #[clap(
+        long = "self-monitoring-interval-seconds",
+        env = "INFLUXDB_IOX_SELF_MONITORING_INTERVAL_SECONDS",
+        action
+    )]
+    pub self_monitoring_interval_seconds: Option<u64>,
+
+    /// The namespace self-monitoring metrics are written to.
+    #[clap(
+        long = "self-monitoring-namespace",
+        env = "INFLUXDB_IOX_SELF_MONITORING_NAMESPACE",
+        default_value = "_monitoring",
+        action
+    )]
+    pub self_monitoring_namespace: String,
 }
 
 impl Config {
@@ -378,6 +401,8 @@ impl Config {
             querier_max_concurrent_queries,
             exec_mem_pool_bytes,
             single_tenant_deployment,
+            self_monitoring_interval_seconds,
+            self_monitoring_namespace,
         } = self;
 
         // Determine where to store files (wal and possibly catalog
@@ -464,6 +489,8 @@ impl Config {
             persist_queue_depth,
             persist_hot_partition_cost,
             rpc_write_max_incoming_bytes: 1024 * 1024 * 1024, // 1GiB
+            replica_addresses: vec![],
+            replication_source: None,
         };
 
         let router_config = RouterConfig {
@@ -475,7 +502,12 @@ impl Config {
             namespace_autocreation_enabled: true,
             rpc_write_timeout_seconds: Duration::new(3, 0),
             rpc_write_replicas: None,
+            rpc_write_partition_affinity: false,
             rpc_write_max_outgoing_bytes: ingester_config.rpc_write_max_incoming_bytes,
+            self_monitoring_interval_seconds: self_monitoring_interval_seconds
+                .map(Duration::from_secs),
+            self_monitoring_namespace,
+            namespace_cache_refresh_interval_seconds: Duration::from_secs(60),
         };
 
         // create a CompactorConfig for the all in one server based on
@@ -513,10 +545,19 @@ impl Config {
             ingester_addresses,
             ram_pool_metadata_bytes: querier_ram_pool_metadata_bytes,
             ram_pool_data_bytes: querier_ram_pool_data_bytes,
+            disk_cache_dir: None,
+            disk_pool_data_bytes: 0,
+            cache_object_ranges: false,
             max_concurrent_queries: querier_max_concurrent_queries,
+            max_query_runtime: None,
+            namespace_max_query_runtime: Default::default(),
             exec_mem_pool_bytes,
             ingester_circuit_breaker_threshold: u64::MAX, // never for all-in-one-mode
             datafusion_config: Default::default(),
+            query_log_output: Default::default(),
+            query_log_file: None,
+            query_log_file_max_bytes: 0,
+            query_log_file_max_files: 0,
         };
 
         SpecializedConfig {
@@ -628,6 +669,8 @@ pub async fn command(config: Config) -> Result<()> {
         &ingester_config,
         Arc::clone(&exec),
         parquet_store_real.clone(),
+        // The services share `metrics`, which the router self-monitors.
+        None,
     )
     .await
     .expect("failed to start ingester");
@@ -651,6 +694,7 @@ pub async fn command(config: Config) -> Result<()> {
         Arc::clone(&exec),
         Arc::clone(&time_provider),
         compactor_config,
+        None,
     )
     .await;
 
@@ -663,6 +707,7 @@ pub async fn command(config: Config) -> Result<()> {
         exec,
         time_provider,
         querier_config,
+        self_monitoring_config: None,
     })
     .await?;
 
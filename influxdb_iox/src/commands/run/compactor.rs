@@ -4,7 +4,7 @@ use super::main;
 use crate::process_info::setup_metric_registry;
 use clap_blocks::{
     catalog_dsn::CatalogDsnConfig, compactor::CompactorConfig, object_store::make_object_store,
-    run_config::RunConfig,
+    run_config::RunConfig, self_monitoring::SelfMonitoringConfig,
 };
 use compactor::object_store::metrics::MetricsStore;
 use iox_query::exec::{Executor, ExecutorConfig};
@@ -64,6 +64,9 @@ pub struct Config {
 
     #[clap(flatten)]
     pub(crate) compactor_config: CompactorConfig,
+
+    #[clap(flatten)]
+    pub(crate) self_monitoring_config: SelfMonitoringConfig,
 }
 
 pub async fn command(config: Config) -> Result<(), Error> {
@@ -126,6 +129,7 @@ pub async fn command(config: Config) -> Result<(), Error> {
         exec,
         time_provider,
         config.compactor_config,
+        Some(&config.self_monitoring_config),
     )
     .await;
 
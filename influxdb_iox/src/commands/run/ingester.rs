@@ -4,7 +4,7 @@ use super::main;
 use crate::process_info::{setup_metric_registry, USIZE_MAX};
 use clap_blocks::{
     catalog_dsn::CatalogDsnConfig, ingester::IngesterConfig, object_store::make_object_store,
-    run_config::RunConfig,
+    run_config::RunConfig, self_monitoring::SelfMonitoringConfig,
 };
 use iox_query::exec::Executor;
 use iox_time::{SystemProvider, TimeProvider};
@@ -65,6 +65,9 @@ pub struct Config {
     #[clap(flatten)]
     pub(crate) ingester_config: IngesterConfig,
 
+    #[clap(flatten)]
+    pub(crate) self_monitoring_config: SelfMonitoringConfig,
+
     /// Specify the size of the thread-pool for query execution, and the
     /// separate compaction thread-pool.
     #[clap(
@@ -122,6 +125,7 @@ pub async fn command(config: Config) -> Result<()> {
         &config.ingester_config,
         exec,
         ParquetStorage::new(object_store, StorageId::from("iox")),
+        Some(&config.self_monitoring_config),
     )
     .await?;
 
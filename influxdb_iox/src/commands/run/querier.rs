@@ -5,7 +5,7 @@ use crate::process_info::setup_metric_registry;
 use super::main;
 use clap_blocks::{
     catalog_dsn::CatalogDsnConfig, object_store::make_object_store, querier::QuerierConfig,
-    run_config::RunConfig,
+    run_config::RunConfig, self_monitoring::SelfMonitoringConfig,
 };
 use iox_query::exec::Executor;
 use iox_time::{SystemProvider, TimeProvider};
@@ -68,6 +68,9 @@ pub struct Config {
 
     #[clap(flatten)]
     pub(crate) querier_config: QuerierConfig,
+
+    #[clap(flatten)]
+    pub(crate) self_monitoring_config: SelfMonitoringConfig,
 }
 
 pub async fn command(config: Config) -> Result<(), Error> {
@@ -114,6 +117,7 @@ pub async fn command(config: Config) -> Result<(), Error> {
         exec,
         time_provider,
         querier_config: config.querier_config,
+        self_monitoring_config: Some(config.self_monitoring_config),
     })
     .await?;
 
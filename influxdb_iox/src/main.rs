@@ -33,6 +33,7 @@ use std::{
 use tokio::runtime::Runtime;
 
 mod commands {
+    pub mod cancel_query;
     pub mod catalog;
     pub mod debug;
     pub mod import;
@@ -209,6 +210,9 @@ enum Command {
     /// Query the ingester only
     QueryIngester(commands::query_ingester::Config),
 
+    /// Cancel a running query
+    CancelQuery(commands::cancel_query::Config),
+
     /// Commands related to the bulk ingest of data
     Import(commands::import::Config),
 
@@ -373,6 +377,14 @@ fn main() -> Result<(), std::io::Error> {
                     std::process::exit(ReturnCode::Failure as _)
                 }
             }
+            Some(Command::CancelQuery(config)) => {
+                let _tracing_guard = handle_init_logs(init_simple_logs(log_verbose_count));
+                let connection = connection(grpc_host).await;
+                if let Err(e) = commands::cancel_query::command(connection, config).await {
+                    eprintln!("{e}");
+                    std::process::exit(ReturnCode::Failure as _)
+                }
+            }
             Some(Command::Import(config)) => {
                 let _tracing_guard = handle_init_logs(init_simple_logs(log_verbose_count));
                 if let Err(e) = commands::import::command(config).await {
@@ -25,7 +25,7 @@ async fn influxql_returns_error() {
                 query: "SHOW TAG KEYS ON foo".into(),
                 expected_error_code: tonic::Code::InvalidArgument,
                 expected_message:
-                    "Error while planning query: This feature is not implemented: SHOW TAG KEYS ON <database>"
+                    "Error while planning query: Error during planning: database not found: foo"
                         .into(),
             },
             Step::InfluxQLExpectingError {
@@ -75,6 +75,55 @@ async fn influxql_select_returns_results() {
     .await
 }
 
+#[tokio::test]
+async fn influxql_delete_removes_ingester_data() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let table_name = "the_table";
+
+    // Set up the cluster  ====================================
+    // Data is never persisted, so it is only buffered by the ingester.
+    let mut cluster = MiniCluster::create_shared_never_persist(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::WriteLineProtocol(format!(
+                "{table_name},tag1=A,tag2=B val=42i 123456\n\
+                 {table_name},tag1=B,tag2=C val=43i 123457"
+            )),
+            Step::Custom(Box::new(move |state: &mut StepTestState| {
+                async move {
+                    let cluster = state.cluster();
+                    let (batches, _schema) = try_run_influxql(
+                        format!("DELETE FROM {table_name} WHERE tag1 = 'A'"),
+                        cluster.namespace(),
+                        cluster.querier().querier_grpc_connection(),
+                        None,
+                    )
+                    .await
+                    .unwrap();
+                    assert!(batches.is_empty());
+                }
+                .boxed()
+            })),
+            Step::InfluxQLQuery {
+                query: format!("select tag1, val from {table_name}"),
+                expected: vec![
+                    "+------------------+--------------------------------+------+-----+",
+                    "| iox::measurement | time                           | tag1 | val |",
+                    "+------------------+--------------------------------+------+-----+",
+                    "| the_table        | 1970-01-01T00:00:00.000123457Z | B    | 43  |",
+                    "+------------------+--------------------------------+------+-----+",
+                ],
+            },
+        ],
+    )
+    .run()
+    .await
+}
+
 #[tokio::test]
 async fn authz() {
     test_helpers::maybe_start_logging();
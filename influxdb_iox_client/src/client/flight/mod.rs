@@ -2,8 +2,10 @@
 
 use std::{pin::Pin, task::Poll};
 
-use ::generated_types::influxdata::iox::querier::v1::{read_info::QueryType, ReadInfo};
-use futures_util::{Stream, StreamExt};
+use ::generated_types::influxdata::iox::querier::v1::{
+    read_info::QueryType, CancelQueryRequest, ReadInfo,
+};
+use futures_util::{Stream, StreamExt, TryStreamExt};
 use prost::Message;
 use thiserror::Error;
 use tonic::metadata::{MetadataKey, MetadataMap, MetadataValue};
@@ -15,7 +17,9 @@ use arrow::{
 
 use rand::Rng;
 
-use arrow_flight::{decode::FlightRecordBatchStream, error::FlightError, FlightClient, Ticket};
+use arrow_flight::{
+    decode::FlightRecordBatchStream, error::FlightError, Action, FlightClient, Ticket,
+};
 
 use crate::connection::Connection;
 
@@ -27,6 +31,9 @@ pub mod generated_types {
     };
 }
 
+/// The type of the Flight `DoAction` request that cancels a query.
+const CANCEL_QUERY_ACTION: &str = "CancelQuery";
+
 /// Error responses when querying an IOx namespace using the IOx Flight API.
 #[derive(Debug, Error)]
 pub enum Error {
@@ -200,6 +207,7 @@ impl Client {
             sql_query: sql_query.into(),
             query_type: QueryType::Sql.into(),
             flightsql_command: vec![],
+            params: Default::default(),
         };
 
         self.do_get_with_read_info(request).await
@@ -217,11 +225,37 @@ impl Client {
             sql_query: influxql_query.into(),
             query_type: QueryType::InfluxQl.into(),
             flightsql_command: vec![],
+            params: Default::default(),
         };
 
         self.do_get_with_read_info(request).await
     }
 
+    /// Cancel the running query with the given id, as listed in the
+    /// `system.queries` table of the namespace.
+    pub async fn cancel_query(
+        &mut self,
+        namespace_name: impl Into<String> + Send,
+        query_id: impl Into<String> + Send,
+    ) -> Result<(), Error> {
+        let request = CancelQueryRequest {
+            namespace_name: namespace_name.into(),
+            query_id: query_id.into(),
+        };
+        let action = Action {
+            r#type: CANCEL_QUERY_ACTION.to_string(),
+            body: request.encode_to_vec().into(),
+        };
+
+        // the response does not contain any information
+        self.inner
+            .do_action(action)
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        Ok(())
+    }
+
     /// Perform a lower level client read with the `ReadInfo`
     async fn do_get_with_read_info(
         &mut self,
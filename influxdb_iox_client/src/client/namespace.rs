@@ -10,6 +10,9 @@ pub mod generated_types {
     pub use generated_types::influxdata::iox::namespace::v1::{
         update_namespace_service_protection_limit_request::LimitUpdate, *,
     };
+    pub use generated_types::influxdata::iox::partition_template::v1::{
+        template_part, PartitionTemplate, TemplatePart,
+    };
 }
 
 /// A basic client for working with Namespaces.
@@ -40,16 +43,21 @@ impl Client {
     /// drop data), and 0 is also mapped to `None` on the server side.
     ///
     /// Negative retention periods are rejected, returning an error.
+    ///
+    /// `partition_template` overrides the default (daily) partitioning scheme
+    /// for writes to this namespace. Templates without any parts are rejected.
     pub async fn create_namespace(
         &mut self,
         namespace: &str,
         retention_period_ns: Option<i64>,
+        partition_template: Option<PartitionTemplate>,
     ) -> Result<Namespace, Error> {
         let response = self
             .inner
             .create_namespace(CreateNamespaceRequest {
                 name: namespace.to_string(),
                 retention_period_ns,
+                partition_template,
             })
             .await?;
 
@@ -84,7 +92,8 @@ impl Client {
     /// `limit_update` is the new service limit protection limit to set
     /// on the namespace.
     ///
-    /// Zero-valued limits are rejected, returning an error.
+    /// Zero-valued table and column limits are rejected, returning an error, while zero-valued
+    /// query limits remove the limit.
     pub async fn update_namespace_service_protection_limit(
         &mut self,
         namespace: &str,
@@ -113,4 +122,76 @@ impl Client {
 
         Ok(())
     }
+
+    /// Restore a deleted namespace
+    pub async fn undelete_namespace(&mut self, namespace: &str) -> Result<Namespace, Error> {
+        let response = self
+            .inner
+            .undelete_namespace(UndeleteNamespaceRequest {
+                name: namespace.to_string(),
+            })
+            .await?;
+
+        Ok(response.into_inner().namespace.unwrap_field("namespace")?)
+    }
+
+    /// Soft-delete a table of a namespace
+    pub async fn delete_table(&mut self, namespace: &str, table: &str) -> Result<(), Error> {
+        self.inner
+            .delete_table(DeleteTableRequest {
+                namespace: namespace.to_string(),
+                table: table.to_string(),
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    /// Restore a soft-deleted table of a namespace
+    pub async fn undelete_table(&mut self, namespace: &str, table: &str) -> Result<(), Error> {
+        self.inner
+            .undelete_table(UndeleteTableRequest {
+                namespace: namespace.to_string(),
+                table: table.to_string(),
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    /// Soft-delete a column of a table
+    pub async fn delete_column(
+        &mut self,
+        namespace: &str,
+        table: &str,
+        column: &str,
+    ) -> Result<(), Error> {
+        self.inner
+            .delete_column(DeleteColumnRequest {
+                namespace: namespace.to_string(),
+                table: table.to_string(),
+                column: column.to_string(),
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    /// Restore a soft-deleted column of a table
+    pub async fn undelete_column(
+        &mut self,
+        namespace: &str,
+        table: &str,
+        column: &str,
+    ) -> Result<(), Error> {
+        self.inner
+            .undelete_column(UndeleteColumnRequest {
+                namespace: namespace.to_string(),
+                table: table.to_string(),
+                column: column.to_string(),
+            })
+            .await?;
+
+        Ok(())
+    }
 }
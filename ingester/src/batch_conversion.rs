@@ -0,0 +1,103 @@
+//! Conversion of buffered [`RecordBatch`] back into a [`MutableBatch`].
+
+use std::sync::Arc;
+
+use arrow::{
+    array::{as_boolean_array, as_primitive_array, as_string_array, Array, ArrayRef},
+    compute::cast,
+    datatypes::{DataType, Float64Type, Int64Type, TimestampNanosecondType, UInt64Type},
+    error::ArrowError,
+    record_batch::RecordBatch,
+    util::bit_util,
+};
+use mutable_batch::{writer::Writer, MutableBatch};
+use schema::{InfluxColumnType, InfluxFieldType, Schema};
+use thiserror::Error;
+
+/// Errors converting the buffered data of a partition into a [`MutableBatch`].
+#[derive(Debug, Error)]
+pub(crate) enum ConversionError {
+    /// The buffered data does not have a valid IOx schema.
+    #[error("invalid buffer schema: {0}")]
+    Schema(#[from] schema::Error),
+
+    /// A tag column could not be read.
+    #[error("failed to read tag column: {0}")]
+    Arrow(#[from] ArrowError),
+
+    /// The data could not be written to the [`MutableBatch`].
+    #[error("failed to write buffered data: {0}")]
+    Write(#[from] mutable_batch::writer::Error),
+}
+
+/// Convert the buffered `batches` of a partition back into a single
+/// [`MutableBatch`], preserving their order.
+pub(crate) fn to_mutable_batch(
+    batches: &[Arc<RecordBatch>],
+) -> Result<MutableBatch, ConversionError> {
+    let mut mb = MutableBatch::new();
+
+    for batch in batches {
+        let schema = Schema::try_from(batch.schema())?;
+
+        let mut writer = Writer::new(&mut mb, batch.num_rows());
+        for (idx, (column_type, field)) in schema.iter().enumerate() {
+            let array = batch.column(idx);
+            let name = field.name();
+            let mask = valid_mask(array);
+            let mask = mask.as_deref();
+
+            match column_type {
+                InfluxColumnType::Tag => {
+                    let values = cast(array, &DataType::Utf8)?;
+                    writer.write_tag(name, mask, as_string_array(&values).iter().flatten())
+                }
+                InfluxColumnType::Field(InfluxFieldType::Float) => writer.write_f64(
+                    name,
+                    mask,
+                    as_primitive_array::<Float64Type>(array).iter().flatten(),
+                ),
+                InfluxColumnType::Field(InfluxFieldType::Integer) => writer.write_i64(
+                    name,
+                    mask,
+                    as_primitive_array::<Int64Type>(array).iter().flatten(),
+                ),
+                InfluxColumnType::Field(InfluxFieldType::UInteger) => writer.write_u64(
+                    name,
+                    mask,
+                    as_primitive_array::<UInt64Type>(array).iter().flatten(),
+                ),
+                InfluxColumnType::Field(InfluxFieldType::String) => {
+                    writer.write_string(name, mask, as_string_array(array).iter().flatten())
+                }
+                InfluxColumnType::Field(InfluxFieldType::Boolean) => {
+                    writer.write_bool(name, mask, as_boolean_array(array).iter().flatten())
+                }
+                InfluxColumnType::Timestamp => writer.write_time(
+                    name,
+                    as_primitive_array::<TimestampNanosecondType>(array)
+                        .values()
+                        .iter()
+                        .copied(),
+                ),
+            }?;
+        }
+        writer.commit();
+    }
+
+    Ok(mb)
+}
+
+/// Return the validity bitmap of `array` in the format expected by
+/// [`Writer`], or [`None`] if it contains no nulls.
+fn valid_mask(array: &ArrayRef) -> Option<Vec<u8>> {
+    if array.null_count() == 0 {
+        return None;
+    }
+
+    let mut mask = vec![0; bit_util::ceil(array.len(), 8)];
+    for idx in (0..array.len()).filter(|&idx| array.is_valid(idx)) {
+        bit_util::set_bit(&mut mask, idx);
+    }
+    Some(mask)
+}
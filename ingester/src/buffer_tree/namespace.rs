@@ -8,7 +8,8 @@ use async_trait::async_trait;
 use data_types::{NamespaceId, TableId};
 use dml::DmlOperation;
 use metric::U64Counter;
-use observability_deps::tracing::warn;
+use observability_deps::tracing::debug;
+use predicate::Predicate;
 use trace::span::Span;
 
 use super::{
@@ -20,7 +21,10 @@ use crate::{
     arcmap::ArcMap,
     deferred_load::DeferredLoad,
     dml_sink::DmlSink,
-    query::{response::QueryResponse, tracing::QueryExecTracing, QueryError, QueryExec},
+    query::{
+        filter::PredicateFilter, response::QueryResponse, tracing::QueryExecTracing, QueryError,
+        QueryExec,
+    },
 };
 
 /// The string name / identifier of a Namespace.
@@ -77,6 +81,9 @@ pub(crate) struct NamespaceData<O> {
     partition_provider: Arc<dyn PartitionProvider>,
 
     post_write_observer: Arc<O>,
+
+    /// The query predicate evaluator shared by all tables in this namespace.
+    predicate_filter: PredicateFilter,
 }
 
 impl<O> NamespaceData<O> {
@@ -104,6 +111,7 @@ impl<O> NamespaceData<O> {
             table_count,
             partition_provider,
             post_write_observer,
+            predicate_filter: PredicateFilter::new(metrics),
         }
     }
 
@@ -155,10 +163,15 @@ where
                         Arc::new(TableData::new(
                             table_id,
                             Arc::new(self.table_name_resolver.for_table(table_id)),
+                            Arc::new(
+                                self.table_name_resolver
+                                    .partition_template_for_table(table_id),
+                            ),
                             self.namespace_id,
                             Arc::clone(&self.namespace_name),
                             Arc::clone(&self.partition_provider),
                             Arc::clone(&self.post_write_observer),
+                            self.predicate_filter.clone(),
                         ))
                     });
 
@@ -168,14 +181,24 @@ where
                 }
             }
             DmlOperation::Delete(delete) => {
-                // Deprecated delete support:
-                // https://github.com/influxdata/influxdb_iox/issues/5825
-                warn!(
+                let predicate = Arc::new(delete.predicate().clone());
+
+                // A delete without a table name applies to all tables.
+                for table_data in self.tables() {
+                    if let Some(table_name) = delete.table_name() {
+                        if table_data.table_name().get().await != *table_name {
+                            continue;
+                        }
+                    }
+                    table_data.apply_delete(&predicate);
+                }
+
+                debug!(
                     namespace_name=%self.namespace_name,
                     namespace_id=%self.namespace_id,
                     table_name=?delete.table_name(),
-                    sequence_number=?delete.meta().sequence(),
-                    "discarding unsupported delete op"
+                    %sequence_number,
+                    "applied delete op"
                 );
             }
         }
@@ -196,6 +219,7 @@ where
         namespace_id: NamespaceId,
         table_id: TableId,
         columns: Vec<String>,
+        predicate: Option<Predicate>,
         span: Option<Span>,
     ) -> Result<Self::Response, QueryError> {
         assert_eq!(
@@ -212,7 +236,7 @@ where
         // a tracing delegate to emit a child span.
         Ok(QueryResponse::new(
             QueryExecTracing::new(inner, "table")
-                .query_exec(namespace_id, table_id, columns, span)
+                .query_exec(namespace_id, table_id, columns, predicate, span)
                 .await?,
         ))
     }
@@ -222,6 +246,11 @@ where
 mod tests {
     use std::sync::Arc;
 
+    use data_types::{
+        DeleteExpr, DeletePredicate, NonEmptyString, Op, Scalar, SequenceNumber, TimestampRange,
+    };
+    use dml::{DmlDelete, DmlMeta};
+    use iox_time::Time;
     use metric::{Attributes, Metric};
 
     use super::*;
@@ -303,4 +332,81 @@ mod tests {
             &***ARBITRARY_NAMESPACE_NAME
         );
     }
+
+    #[tokio::test]
+    async fn test_namespace_apply_delete() {
+        let metrics = Arc::new(metric::Registry::default());
+
+        let partition_provider = Arc::new(
+            MockPartitionProvider::default().with_partition(PartitionDataBuilder::new().build()),
+        );
+
+        let ns = NamespaceData::new(
+            ARBITRARY_NAMESPACE_ID,
+            defer_namespace_name_1_ms(),
+            Arc::clone(&*ARBITRARY_TABLE_NAME_PROVIDER),
+            partition_provider,
+            Arc::new(MockPostWriteObserver::default()),
+            &metrics,
+        );
+
+        ns.apply(DmlOperation::Write(make_write_op(
+            &ARBITRARY_PARTITION_KEY,
+            ARBITRARY_NAMESPACE_ID,
+            &ARBITRARY_TABLE_NAME,
+            ARBITRARY_TABLE_ID,
+            0,
+            &format!(
+                "{table},city=Medford temp=55 22\n{table},city=Boston temp=42 23",
+                table = &*ARBITRARY_TABLE_NAME
+            ),
+        )))
+        .await
+        .expect("buffer op should succeed");
+
+        let delete = |table_name: Option<&str>, city: &str, sequence_number: i64| {
+            DmlOperation::Delete(DmlDelete::new(
+                ARBITRARY_NAMESPACE_ID,
+                DeletePredicate {
+                    range: TimestampRange::new(0, 100),
+                    exprs: vec![DeleteExpr::new(
+                        "city".to_string(),
+                        Op::Eq,
+                        Scalar::String(city.to_string()),
+                    )],
+                },
+                table_name.and_then(NonEmptyString::new),
+                DmlMeta::sequenced(SequenceNumber::new(sequence_number), Time::MAX, None, 42),
+            ))
+        };
+
+        let buffered_rows = || {
+            ns.table(ARBITRARY_TABLE_ID)
+                .expect("table must exist")
+                .partitions()
+                .into_iter()
+                .filter_map(|p| p.lock().get_query_data())
+                .flat_map(|data| data.record_batches().to_vec())
+                .map(|batch| batch.num_rows())
+                .sum::<usize>()
+        };
+        assert_eq!(buffered_rows(), 2);
+
+        // A delete for another table does not affect this table.
+        ns.apply(delete(Some("platanos"), "Medford", 1))
+            .await
+            .expect("delete op should succeed");
+        assert_eq!(buffered_rows(), 2);
+
+        ns.apply(delete(Some(&***ARBITRARY_TABLE_NAME), "Medford", 2))
+            .await
+            .expect("delete op should succeed");
+        assert_eq!(buffered_rows(), 1);
+
+        // A delete without a table name applies to all tables.
+        ns.apply(delete(None, "Boston", 3))
+            .await
+            .expect("delete op should succeed");
+        assert_eq!(buffered_rows(), 0);
+    }
 }
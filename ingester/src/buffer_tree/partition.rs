@@ -1,10 +1,13 @@
 //! Partition level data buffer structures.
 
-use std::{collections::VecDeque, sync::Arc};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+};
 
 use data_types::{
-    sequence_number_set::SequenceNumberSet, NamespaceId, PartitionId, PartitionKey, SequenceNumber,
-    TableId,
+    sequence_number_set::{intersect, SequenceNumberSet},
+    DeletePredicate, NamespaceId, PartitionId, PartitionKey, SequenceNumber, TableId,
 };
 use mutable_batch::MutableBatch;
 use observability_deps::tracing::*;
@@ -12,12 +15,14 @@ use schema::sort::SortKey;
 
 use self::{
     buffer::{traits::Queryable, BufferState, DataBuffer, Persisting},
+    delete::retain_undeleted,
     persisting::{BatchIdent, PersistingData},
 };
 use super::{namespace::NamespaceName, table::TableName};
 use crate::{deferred_load::DeferredLoad, query_adaptor::QueryAdaptor};
 
 mod buffer;
+mod delete;
 pub(crate) mod persisting;
 pub(crate) mod resolver;
 
@@ -84,6 +89,13 @@ pub struct PartitionData {
     /// persisting with a unique, opaque identifier.
     persisting: VecDeque<(BatchIdent, BufferState<Persisting>)>,
 
+    /// The deletes applied to each of the [`Self::persisting`] batches after
+    /// they were marked as persisting.
+    ///
+    /// Persisting data is immutable, so these deletes are applied when the
+    /// data is read, and before it is persisted.
+    persisting_deletes: HashMap<BatchIdent, Vec<Arc<DeletePredicate>>>,
+
     /// The number of persist operations started over the lifetime of this
     /// [`PartitionData`].
     started_persistence_count: BatchIdent,
@@ -91,6 +103,12 @@ pub struct PartitionData {
     /// The number of persist operations completed over the lifetime of this
     /// [`PartitionData`].
     completed_persistence_count: u64,
+
+    /// The [`SequenceNumber`] of writes in [`Self::buffer`] that the upstream
+    /// ingesters replicating to this instance have reported as persisted.
+    ///
+    /// See [`Self::mark_replica_persisted()`].
+    replica_persisted: SequenceNumberSet,
 }
 
 impl PartitionData {
@@ -115,8 +133,10 @@ impl PartitionData {
             table_name,
             buffer: DataBuffer::default(),
             persisting: VecDeque::with_capacity(1),
+            persisting_deletes: HashMap::default(),
             started_persistence_count: BatchIdent::default(),
             completed_persistence_count: 0,
+            replica_persisted: SequenceNumberSet::default(),
         }
     }
 
@@ -141,6 +161,82 @@ impl PartitionData {
         Ok(())
     }
 
+    /// Remove the rows matching `predicate` from the data buffered in this
+    /// partition, including any data that is currently persisting.
+    pub(crate) fn apply_delete(&mut self, predicate: Arc<DeletePredicate>) {
+        self.buffer.apply_delete(std::slice::from_ref(&predicate));
+
+        for (ident, _) in &self.persisting {
+            self.persisting_deletes
+                .entry(*ident)
+                .or_default()
+                .push(Arc::clone(&predicate));
+        }
+
+        debug!(
+            namespace_id = %self.namespace_id,
+            table_id = %self.table_id,
+            table_name = %self.table_name,
+            partition_id = %self.partition_id,
+            partition_key = %self.partition_key,
+            ?predicate,
+            "applied delete"
+        );
+    }
+
+    /// Return the set of [`SequenceNumber`] of all writes to this partition that
+    /// are not yet persisted - both persisting and buffered.
+    pub(crate) fn sequence_number_set(&self) -> SequenceNumberSet {
+        let mut set = self.buffer.sequence_number_set().clone();
+        for (_, p) in &self.persisting {
+            set.add_set(p.sequence_number_set());
+        }
+        set
+    }
+
+    /// Discard the buffered data of this partition once the upstream ingesters
+    /// replicating writes to this instance have persisted all of it.
+    ///
+    /// `persisted` contains the local [`SequenceNumber`] of replicated writes,
+    /// as resolved by the [`ReplicaSequenceMap`]. Upstream ingesters may
+    /// persist the writes held in this buffer across several persist
+    /// operations, so `persisted` is accumulated across calls. Once every
+    /// [`SequenceNumber`] in the buffer has been reported, the buffered data is
+    /// dropped and this method returns true. A buffer containing any write
+    /// that was not replicated from an upstream ingester is never discarded.
+    ///
+    /// Data that is currently persisting from this partition is unaffected.
+    ///
+    /// [`ReplicaSequenceMap`]: crate::replication::sequence_map::ReplicaSequenceMap
+    pub(crate) fn mark_replica_persisted(&mut self, persisted: &SequenceNumberSet) -> bool {
+        let buffered = self.buffer.sequence_number_set();
+
+        // Only retain the values that refer to writes in the current buffer -
+        // anything else has already been persisted or discarded.
+        let mut observed = std::mem::take(&mut self.replica_persisted);
+        observed.add_set(persisted);
+        let observed = intersect(&observed, buffered);
+
+        if buffered.is_empty() || observed.len() != buffered.len() {
+            self.replica_persisted = observed;
+            return false;
+        }
+
+        self.buffer = DataBuffer::default();
+
+        debug!(
+            namespace_id = %self.namespace_id,
+            table_id = %self.table_id,
+            table_name = %self.table_name,
+            partition_id = %self.partition_id,
+            partition_key = %self.partition_key,
+            n_writes = observed.len(),
+            "discarded buffer persisted by upstream ingester"
+        );
+
+        true
+    }
+
     /// Return an estimated cost of persisting the data buffered in this
     /// [`PartitionData`].
     pub(crate) fn persist_cost_estimate(&self) -> usize {
@@ -161,7 +257,10 @@ impl PartitionData {
         let data = self
             .persisting
             .iter()
-            .flat_map(|(_, b)| b.get_query_data())
+            .flat_map(|(ident, b)| match self.persisting_deletes.get(ident) {
+                Some(deletes) => retain_undeleted(&b.get_query_data(), deletes),
+                None => b.get_query_data(),
+            })
             .chain(buffered_data)
             .collect::<Vec<_>>();
 
@@ -183,8 +282,9 @@ impl PartitionData {
         //
         // `data` MUST contain at least one row, or the constructor panics. This
         // is upheld by the FSM, which ensures only non-empty snapshots /
-        // RecordBatch are generated. Because `data` contains at least one
-        // RecordBatch, this invariant holds.
+        // RecordBatch are generated, and by the application of deletes, which
+        // omits any RecordBatch with no remaining rows. Because `data` contains
+        // at least one RecordBatch, this invariant holds.
         Some(QueryAdaptor::new(self.partition_id, data))
     }
 
@@ -235,6 +335,24 @@ impl PartitionData {
         Some(data)
     }
 
+    /// Return the data in `batch` that was not deleted after it was marked as
+    /// persisting, or [`None`] if all of it was deleted.
+    pub(crate) fn persisting_data_without_deletes(
+        &self,
+        batch: &PersistingData,
+    ) -> Option<QueryAdaptor> {
+        let Some(deletes) = self.persisting_deletes.get(&batch.batch_ident()) else {
+            return Some(batch.query_adaptor());
+        };
+
+        let data = retain_undeleted(batch.record_batches(), deletes);
+        if data.is_empty() {
+            return None;
+        }
+
+        Some(QueryAdaptor::new(self.partition_id, data))
+    }
+
     /// Mark this partition as having completed persistence of the specified
     /// `batch`.
     ///
@@ -256,6 +374,7 @@ impl PartitionData {
         // for batch iteration during queries.
         let (old_ident, fsm) = self.persisting.remove(idx).unwrap();
         assert_eq!(old_ident, batch.batch_ident());
+        self.persisting_deletes.remove(&old_ident);
 
         self.completed_persistence_count += 1;
 
@@ -337,6 +456,7 @@ mod tests {
     use arrow_util::assert_batches_eq;
     use assert_matches::assert_matches;
     use backoff::BackoffConfig;
+    use data_types::{DeleteExpr, Op, Scalar, TimestampRange};
     use datafusion::{
         physical_expr::PhysicalSortExpr,
         physical_plan::{expressions::col, memory::MemoryExec, ExecutionPlan},
@@ -530,6 +650,96 @@ mod tests {
         }
     }
 
+    // Apply deletes to both the buffered and the persisting data, ensuring
+    // the deleted rows are no longer readable or persisted, while the
+    // sequence numbers of the deleted writes are retained.
+    #[tokio::test]
+    async fn test_apply_delete() {
+        fn delete_city(city: &str) -> Arc<DeletePredicate> {
+            Arc::new(DeletePredicate {
+                range: TimestampRange::new(0, 100),
+                exprs: vec![DeleteExpr::new(
+                    "city".to_string(),
+                    Op::Eq,
+                    Scalar::String(city.to_string()),
+                )],
+            })
+        }
+
+        fn num_rows(data: &QueryAdaptor) -> usize {
+            data.record_batches().iter().map(|b| b.num_rows()).sum()
+        }
+
+        let mut p = PartitionDataBuilder::new().build();
+
+        let mb = lp_to_mutable_batch(
+            "bananas,city=London people=2 10\nbananas,city=Madrid people=4 20",
+        )
+        .1;
+        p.buffer_write(mb, SequenceNumber::new(1))
+            .expect("write should succeed");
+        let persisting_data = p.mark_persisting().expect("must contain existing data");
+
+        let mb = lp_to_mutable_batch(
+            "bananas,city=London people=3 30\nbananas,city=Paris people=5 40",
+        )
+        .1;
+        p.buffer_write(mb, SequenceNumber::new(2))
+            .expect("write should succeed");
+
+        p.apply_delete(delete_city("London"));
+
+        let data = p.get_query_data().expect("must have data");
+        assert_batches_eq!(
+            [
+                "+--------+--------+--------------------------------+",
+                "| city   | people | time                           |",
+                "+--------+--------+--------------------------------+",
+                "| Madrid | 4.0    | 1970-01-01T00:00:00.000000020Z |",
+                "| Paris  | 5.0    | 1970-01-01T00:00:00.000000040Z |",
+                "+--------+--------+--------------------------------+",
+            ],
+            &*data
+                .record_batches()
+                .iter()
+                .map(Deref::deref)
+                .cloned()
+                .collect::<Vec<_>>()
+        );
+
+        // The persisting data excludes the deleted rows.
+        let persisted = p
+            .persisting_data_without_deletes(&persisting_data)
+            .expect("must have data");
+        assert_eq!(num_rows(&persisted), 1);
+
+        // Deleting all the persisting rows leaves nothing to persist.
+        p.apply_delete(delete_city("Madrid"));
+        assert!(p.persisting_data_without_deletes(&persisting_data).is_none());
+
+        // Deleting all the buffered rows leaves the sequence numbers in place.
+        p.apply_delete(delete_city("Paris"));
+        assert!(p.get_query_data().is_none());
+        assert_eq!(
+            p.sequence_number_set(),
+            [1, 2]
+                .into_iter()
+                .map(SequenceNumber::new)
+                .collect::<SequenceNumberSet>()
+        );
+
+        // Completing the persist releases the deletes applied to it.
+        let set = p.mark_persisted(persisting_data);
+        assert!(set.contains(SequenceNumber::new(1)));
+        assert!(p.persisting_deletes.is_empty());
+
+        // Deletes do not apply to data buffered after them.
+        let mb = lp_to_mutable_batch("bananas,city=London people=6 50").1;
+        p.buffer_write(mb, SequenceNumber::new(3))
+            .expect("write should succeed");
+        assert_eq!(num_rows(&p.get_query_data().expect("must have data")), 1);
+    }
+
     // Ensure the ordering of snapshots & persisting data is preserved such that
     // updates resolve correctly, and batch identifiers are correctly allocated
     // and validated in mark_persisted() calls which return the correct
@@ -1028,6 +1238,41 @@ mod tests {
         assert!(p.mark_persisting().is_none());
     }
 
+    // Ensure buffered data is only discarded once the upstream ingester has
+    // reported all of the writes within it as persisted, across one or more
+    // notifications.
+    #[tokio::test]
+    async fn test_mark_replica_persisted() {
+        let mut p = PartitionDataBuilder::new().build();
+
+        // Nothing is buffered, so nothing is discarded.
+        assert!(!p.mark_replica_persisted(&SequenceNumberSet::from_iter([SequenceNumber::new(1)])));
+
+        let mb = lp_to_mutable_batch(r#"bananas,city=London people=2,pigeons="millions" 10"#).1;
+        p.buffer_write(mb, SequenceNumber::new(1))
+            .expect("write should succeed");
+        let mb = lp_to_mutable_batch(r#"bananas,city=Madrid people=4,pigeons="none" 20"#).1;
+        p.buffer_write(mb, SequenceNumber::new(2))
+            .expect("write should succeed");
+
+        assert_eq!(
+            p.sequence_number_set(),
+            SequenceNumberSet::from_iter([SequenceNumber::new(1), SequenceNumber::new(2)])
+        );
+
+        // Only part of the buffer has been persisted upstream.
+        assert!(!p.mark_replica_persisted(&SequenceNumberSet::from_iter([
+            SequenceNumber::new(1),
+            SequenceNumber::new(42),
+        ])));
+        assert!(p.get_query_data().is_some());
+
+        // The remainder is persisted in a subsequent operation.
+        assert!(p.mark_replica_persisted(&SequenceNumberSet::from_iter([SequenceNumber::new(2)])));
+        assert!(p.get_query_data().is_none());
+        assert!(p.sequence_number_set().is_empty());
+    }
+
     // Ensure an empty PartitionData does not panic due to constructing an empty
     // QueryAdaptor.
     #[tokio::test]
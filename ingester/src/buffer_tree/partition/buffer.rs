@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use arrow::record_batch::RecordBatch;
-use data_types::SequenceNumber;
+use data_types::{sequence_number_set::SequenceNumberSet, DeletePredicate, SequenceNumber};
 use mutable_batch::MutableBatch;
 
 mod always_some;
@@ -55,6 +55,23 @@ impl DataBuffer {
         })
     }
 
+    /// Remove the buffered rows matching any of `predicates`.
+    pub(crate) fn apply_delete(&mut self, predicates: &[Arc<DeletePredicate>]) {
+        self.0.mutate(|fsm| match fsm {
+            FsmState::Buffering(mut b) => {
+                b.apply_delete(predicates);
+                (FsmState::Buffering(b), ())
+            }
+        })
+    }
+
+    /// Return the set of [`SequenceNumber`] applied to this buffer.
+    pub(crate) fn sequence_number_set(&self) -> &SequenceNumberSet {
+        match self.0.get() {
+            FsmState::Buffering(b) => b.sequence_number_set(),
+        }
+    }
+
     pub(crate) fn persist_cost_estimate(&self) -> usize {
         match self.0.get() {
             FsmState::Buffering(b) => b.persist_cost_estimate(),
@@ -1,9 +1,13 @@
 use std::sync::Arc;
 
 use arrow::record_batch::RecordBatch;
+use data_types::DeletePredicate;
 use mutable_batch::MutableBatch;
+use observability_deps::tracing::*;
 use schema::Projection;
 
+use crate::{batch_conversion::to_mutable_batch, buffer_tree::partition::delete::retain_undeleted};
+
 /// A [`Buffer`] is an internal mutable buffer wrapper over a [`MutableBatch`]
 /// for the [`BufferState`] FSM.
 ///
@@ -47,6 +51,43 @@ impl Buffer {
         ))
     }
 
+    /// Remove the buffered rows matching any of `predicates`.
+    ///
+    /// The buffer becomes empty if all the buffered rows are deleted.
+    ///
+    /// # Panics
+    ///
+    /// If converting the buffered data to or from a [`RecordBatch`] fails,
+    /// this method panics.
+    pub(super) fn apply_delete(&mut self, predicates: &[Arc<DeletePredicate>]) {
+        let Some(buffer) = self.buffer.as_ref() else {
+            return;
+        };
+
+        let data = Arc::new(
+            buffer
+                .to_arrow(Projection::All)
+                .expect("failed to snapshot buffer data"),
+        );
+        let retained = retain_undeleted(&[Arc::clone(&data)], predicates);
+
+        match retained.as_slice() {
+            [] => self.buffer = None,
+            [batch] if Arc::ptr_eq(batch, &data) => {
+                // No rows were deleted.
+            }
+            batches => {
+                let n_rows = buffer.rows();
+                let mb = to_mutable_batch(batches).expect("failed to rebuild buffer data");
+                debug!(
+                    n_deleted = n_rows - mb.rows(),
+                    "applied delete to buffered data"
+                );
+                self.buffer = Some(mb);
+            }
+        }
+    }
+
     pub(super) fn is_empty(&self) -> bool {
         self.buffer.is_none()
     }
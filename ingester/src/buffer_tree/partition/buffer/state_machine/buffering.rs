@@ -3,6 +3,7 @@
 use std::sync::Arc;
 
 use arrow::record_batch::RecordBatch;
+use data_types::DeletePredicate;
 use mutable_batch::MutableBatch;
 use schema::Projection;
 
@@ -80,6 +81,16 @@ impl BufferState<Buffering> {
     pub(crate) fn persist_cost_estimate(&self) -> usize {
         self.state.buffer.persist_cost_estimate()
     }
+
+    /// Remove the buffered rows matching any of `predicates`.
+    ///
+    /// The [`SequenceNumber`] of the writes applied to this buffer are
+    /// retained, even if all of their rows are deleted.
+    ///
+    /// [`SequenceNumber`]: data_types::SequenceNumber
+    pub(crate) fn apply_delete(&mut self, predicates: &[Arc<DeletePredicate>]) {
+        self.state.buffer.apply_delete(predicates)
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,95 @@
+//! Application of [`DeletePredicate`] to buffered partition data.
+
+use std::sync::Arc;
+
+use arrow::record_batch::RecordBatch;
+use data_types::DeletePredicate;
+use observability_deps::tracing::*;
+use predicate::delete_predicate::negated_delete_expr;
+
+use crate::query::filter::filter_batch;
+
+/// Return the rows of `batches` that are not deleted by any of `predicates`,
+/// preserving their order.
+///
+/// Batches with no remaining rows are omitted from the result.
+///
+/// If a delete cannot be evaluated against a batch, the rows of that batch are
+/// retained and a warning is logged.
+pub(super) fn retain_undeleted(
+    batches: &[Arc<RecordBatch>],
+    predicates: &[Arc<DeletePredicate>],
+) -> Vec<Arc<RecordBatch>> {
+    let Some(expr) = negated_delete_expr(predicates) else {
+        return batches.to_vec();
+    };
+
+    batches
+        .iter()
+        .map(|batch| match filter_batch(batch, &expr) {
+            Ok(filtered) if filtered.num_rows() == batch.num_rows() => Arc::clone(batch),
+            Ok(filtered) => Arc::new(filtered),
+            Err(e) => {
+                warn!(error=%e, %expr, "failed to apply delete to buffered data");
+                Arc::clone(batch)
+            }
+        })
+        .filter(|batch| batch.num_rows() > 0)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_util::assert_batches_eq;
+    use data_types::{DeleteExpr, Op, Scalar, TimestampRange};
+    use mutable_batch_lp::test_helpers::lp_to_mutable_batch;
+    use schema::Projection;
+
+    use super::*;
+
+    fn batch(lp: &str) -> Arc<RecordBatch> {
+        Arc::new(
+            lp_to_mutable_batch(lp)
+                .1
+                .to_arrow(Projection::All)
+                .expect("valid batch"),
+        )
+    }
+
+    #[test]
+    fn test_retain_undeleted() {
+        let batches = [
+            batch("bananas,city=London people=2 10\nbananas,city=Madrid people=4 20"),
+            batch("bananas,city=London people=3 30\nbananas people=5 40"),
+            batch("bananas,city=London people=6 50"),
+        ];
+
+        // No deletes retain all the data.
+        assert_eq!(retain_undeleted(&batches, &[]), batches);
+
+        // The time range of a delete is inclusive at both ends, and rows
+        // without the tag are not deleted.
+        let pred = Arc::new(DeletePredicate {
+            range: TimestampRange::new(10, 50),
+            exprs: vec![DeleteExpr::new(
+                "city".to_string(),
+                Op::Eq,
+                Scalar::String("London".to_string()),
+            )],
+        });
+
+        let got = retain_undeleted(&batches, &[pred]);
+        assert_eq!(got.len(), 2);
+        assert_batches_eq!(
+            [
+                "+--------+--------+--------------------------------+",
+                "| city   | people | time                           |",
+                "+--------+--------+--------------------------------+",
+                "| Madrid | 4.0    | 1970-01-01T00:00:00.000000020Z |",
+                "|        | 5.0    | 1970-01-01T00:00:00.000000040Z |",
+                "+--------+--------+--------------------------------+",
+            ],
+            &got.iter().map(|b| (**b).clone()).collect::<Vec<_>>()
+        );
+    }
+}
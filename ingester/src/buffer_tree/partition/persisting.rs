@@ -5,7 +5,7 @@ use crate::query_adaptor::QueryAdaptor;
 /// An opaque generational identifier of a buffer in a [`PartitionData`].
 ///
 /// [`PartitionData`]: super::PartitionData
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
 pub(super) struct BatchIdent(u64);
 
 impl BatchIdent {
@@ -116,7 +116,7 @@ mod tests {
             let mut repos = catalog.repositories().await;
             let ns = repos
                 .namespaces()
-                .create(TABLE_NAME, None)
+                .create(TABLE_NAME, None, None)
                 .await
                 .unwrap();
 
@@ -5,6 +5,7 @@ use data_types::{NamespaceId, TableId};
 use dml::DmlOperation;
 use metric::U64Counter;
 use parking_lot::Mutex;
+use predicate::Predicate;
 use trace::span::Span;
 
 use super::{
@@ -201,6 +202,7 @@ where
         namespace_id: NamespaceId,
         table_id: TableId,
         columns: Vec<String>,
+        predicate: Option<Predicate>,
         span: Option<Span>,
     ) -> Result<Self::Response, QueryError> {
         // Extract the namespace if it exists.
@@ -211,7 +213,7 @@ where
         // Delegate query execution to the namespace, wrapping the execution in
         // a tracing delegate to emit a child span.
         QueryExecTracing::new(inner, "namespace")
-            .query_exec(namespace_id, table_id, columns, span)
+            .query_exec(namespace_id, table_id, columns, predicate, span)
             .await
     }
 }
@@ -357,7 +359,7 @@ mod tests {
 
                     // Execute the query against ARBITRARY_NAMESPACE_ID and ARBITRARY_TABLE_ID
                     let batches = buf
-                        .query_exec(ARBITRARY_NAMESPACE_ID, ARBITRARY_TABLE_ID, vec![], None)
+                        .query_exec(ARBITRARY_NAMESPACE_ID, ARBITRARY_TABLE_ID, vec![], None, None)
                         .await
                         .expect("query should succeed")
                         .into_record_batches()
@@ -804,7 +806,13 @@ mod tests {
 
         // Query the empty tree
         let err = buf
-            .query_exec(ARBITRARY_NAMESPACE_ID, ARBITRARY_TABLE_ID, vec![], None)
+            .query_exec(
+                ARBITRARY_NAMESPACE_ID,
+                ARBITRARY_TABLE_ID,
+                vec![],
+                None,
+                None,
+            )
             .await
             .expect_err("query should fail");
         assert_matches!(err, QueryError::NamespaceNotFound(ns) => {
@@ -828,7 +836,13 @@ mod tests {
 
         // Ensure an unknown table errors
         let err = buf
-            .query_exec(ARBITRARY_NAMESPACE_ID, TableId::new(1234), vec![], None)
+            .query_exec(
+                ARBITRARY_NAMESPACE_ID,
+                TableId::new(1234),
+                vec![],
+                None,
+                None,
+            )
             .await
             .expect_err("query should fail");
         assert_matches!(err, QueryError::TableNotFound(ns, t) => {
@@ -837,9 +851,15 @@ mod tests {
         });
 
         // Ensure a valid namespace / table does not error
-        buf.query_exec(ARBITRARY_NAMESPACE_ID, ARBITRARY_TABLE_ID, vec![], None)
-            .await
-            .expect("namespace / table should exist");
+        buf.query_exec(
+            ARBITRARY_NAMESPACE_ID,
+            ARBITRARY_TABLE_ID,
+            vec![],
+            None,
+            None,
+        )
+        .await
+        .expect("namespace / table should exist");
     }
 
     /// This test asserts the read consistency properties defined in the
@@ -905,7 +925,13 @@ mod tests {
         // Execute a query of the buffer tree, generating the result stream, but
         // DO NOT consume it.
         let stream = buf
-            .query_exec(ARBITRARY_NAMESPACE_ID, ARBITRARY_TABLE_ID, vec![], None)
+            .query_exec(
+                ARBITRARY_NAMESPACE_ID,
+                ARBITRARY_TABLE_ID,
+                vec![],
+                None,
+                None,
+            )
             .await
             .expect("query should succeed")
             .into_partition_stream();
@@ -5,10 +5,14 @@ pub(crate) mod name_resolver;
 use std::{fmt::Debug, sync::Arc};
 
 use async_trait::async_trait;
-use data_types::{NamespaceId, PartitionKey, SequenceNumber, TableId};
+use data_types::{
+    DefaultPartitionTemplate, DeletePredicate, NamespaceId, PartitionKey, PartitionTemplate, SequenceNumber,
+    TableId, TablePartitionTemplateOverride,
+};
 use datafusion_util::MemoryStream;
 use mutable_batch::MutableBatch;
 use parking_lot::Mutex;
+use predicate::Predicate;
 use schema::Projection;
 use trace::span::{Span, SpanRecorder};
 
@@ -21,7 +25,8 @@ use crate::{
     arcmap::ArcMap,
     deferred_load::DeferredLoad,
     query::{
-        partition_response::PartitionResponse, response::PartitionStream, QueryError, QueryExec,
+        filter::PredicateFilter, partition_response::PartitionResponse, response::PartitionStream,
+        QueryError, QueryExec,
     },
 };
 
@@ -72,6 +77,10 @@ pub(crate) struct TableData<O> {
     table_id: TableId,
     table_name: Arc<DeferredLoad<TableName>>,
 
+    /// The partition template override of this table, if any, used to
+    /// interpret the partition keys of its partitions.
+    partition_template: Arc<DeferredLoad<Option<TablePartitionTemplateOverride>>>,
+
     /// The catalog ID of the namespace this table is being populated from.
     namespace_id: NamespaceId,
     namespace_name: Arc<DeferredLoad<NamespaceName>>,
@@ -84,6 +93,9 @@ pub(crate) struct TableData<O> {
     partition_data: ArcMap<PartitionKey, Mutex<PartitionData>>,
 
     post_write_observer: Arc<O>,
+
+    /// Applies the query predicate to the data of each partition.
+    predicate_filter: PredicateFilter,
 }
 
 impl<O> TableData<O> {
@@ -95,19 +107,23 @@ impl<O> TableData<O> {
     pub(super) fn new(
         table_id: TableId,
         table_name: Arc<DeferredLoad<TableName>>,
+        partition_template: Arc<DeferredLoad<Option<TablePartitionTemplateOverride>>>,
         namespace_id: NamespaceId,
         namespace_name: Arc<DeferredLoad<NamespaceName>>,
         partition_provider: Arc<dyn PartitionProvider>,
         post_write_observer: Arc<O>,
+        predicate_filter: PredicateFilter,
     ) -> Self {
         Self {
             table_id,
             table_name,
+            partition_template,
             namespace_id,
             namespace_name,
             partition_data: Default::default(),
             partition_provider,
             post_write_observer,
+            predicate_filter,
         }
     }
 
@@ -142,6 +158,14 @@ impl<O> TableData<O> {
     pub(crate) fn namespace_id(&self) -> NamespaceId {
         self.namespace_id
     }
+
+    /// Remove the rows matching `predicate` from the data buffered in all
+    /// partitions of this table.
+    pub(super) fn apply_delete(&self, predicate: &Arc<DeletePredicate>) {
+        for p in self.partitions() {
+            p.lock().apply_delete(Arc::clone(predicate));
+        }
+    }
 }
 
 impl<O> TableData<O>
@@ -204,6 +228,7 @@ where
         namespace_id: NamespaceId,
         table_id: TableId,
         columns: Vec<String>,
+        predicate: Option<Predicate>,
         span: Option<Span>,
     ) -> Result<Self::Response, QueryError> {
         assert_eq!(self.table_id, table_id, "buffer tree index inconsistency");
@@ -212,18 +237,54 @@ where
             "buffer tree index inconsistency"
         );
 
+        // Resolve the template the partition keys of this table were derived
+        // from, if the predicate may be used to prune partitions by key.
+        let partition_template = match &predicate {
+            Some(predicate) if predicate.range.is_some() => {
+                let table_template = self.partition_template.get().await.map(Arc::new);
+                Some(
+                    PartitionTemplate::determine_precedence(
+                        table_template.as_ref(),
+                        None,
+                        &DefaultPartitionTemplate::default(),
+                    )
+                    .clone(),
+                )
+            }
+            _ => None,
+        };
+
         // Gather the partition data from all of the partitions in this table.
         let span = SpanRecorder::new(span);
+        let predicate_filter = self.predicate_filter.clone();
         let partitions = self.partitions().into_iter().map(move |p| {
             let mut span = span.child("partition read");
 
             let (id, completed_persistence_count, data) = {
                 let mut p = p.lock();
-                (
-                    p.partition_id(),
-                    p.completed_persistence_count(),
-                    p.get_query_data(),
-                )
+
+                // Skip reading the data of a partition that the predicate
+                // proves contains no matching rows.
+                let data = match (&predicate, &partition_template) {
+                    (Some(predicate), Some(partition_template))
+                        if predicate_filter.prune_partition_key(
+                            p.partition_key(),
+                            partition_template,
+                            predicate,
+                        ) =>
+                    {
+                        None
+                    }
+                    _ => p.get_query_data(),
+                };
+
+                (p.partition_id(), p.completed_persistence_count(), data)
+            };
+
+            // Filter the data using the predicate, if any.
+            let data = match &predicate {
+                Some(predicate) => data.and_then(|data| predicate_filter.filter(data, predicate)),
+                None => data,
             };
 
             let ret = match data {
@@ -258,6 +319,9 @@ where
 mod tests {
     use std::sync::Arc;
 
+    use data_types::PartitionId;
+    use datafusion::prelude::{col, lit};
+    use futures::StreamExt;
     use mutable_batch_lp::lines_to_batches;
 
     use super::*;
@@ -266,10 +330,11 @@ mod tests {
             partition::resolver::mock::MockPartitionProvider,
             post_write::mock::MockPostWriteObserver,
         },
+        query::response::QueryResponse,
         test_util::{
-            defer_namespace_name_1_sec, defer_table_name_1_sec, PartitionDataBuilder,
-            ARBITRARY_NAMESPACE_ID, ARBITRARY_PARTITION_KEY, ARBITRARY_TABLE_ID,
-            ARBITRARY_TABLE_NAME,
+            defer_namespace_name_1_sec, defer_partition_template_1_sec, defer_table_name_1_sec,
+            PartitionDataBuilder, ARBITRARY_NAMESPACE_ID, ARBITRARY_PARTITION_KEY,
+            ARBITRARY_TABLE_ID, ARBITRARY_TABLE_NAME,
         },
     };
 
@@ -283,10 +348,12 @@ mod tests {
         let table = TableData::new(
             ARBITRARY_TABLE_ID,
             defer_table_name_1_sec(),
+            defer_partition_template_1_sec(),
             ARBITRARY_NAMESPACE_ID,
             defer_namespace_name_1_sec(),
             partition_provider,
             Arc::new(MockPostWriteObserver::default()),
+            PredicateFilter::new(&metric::Registry::default()),
         );
 
         let batch = lines_to_batches(
@@ -313,4 +380,95 @@ mod tests {
         // Referencing the partition should succeed
         assert!(table.partition_data.get(&ARBITRARY_PARTITION_KEY).is_some());
     }
+
+    /// A query predicate prunes partitions by partition key, and filters the
+    /// rows of the remaining partitions.
+    #[tokio::test]
+    async fn test_query_predicate() {
+        let day_1 = PartitionKey::from("1970-01-01");
+        let day_2 = PartitionKey::from("1970-01-02");
+
+        let partition_provider = Arc::new(
+            MockPartitionProvider::default()
+                .with_partition(
+                    PartitionDataBuilder::new()
+                        .with_partition_id(PartitionId::new(1))
+                        .with_partition_key(day_1.clone())
+                        .build(),
+                )
+                .with_partition(
+                    PartitionDataBuilder::new()
+                        .with_partition_id(PartitionId::new(2))
+                        .with_partition_key(day_2.clone())
+                        .build(),
+                ),
+        );
+
+        let table = TableData::new(
+            ARBITRARY_TABLE_ID,
+            defer_table_name_1_sec(),
+            defer_partition_template_1_sec(),
+            ARBITRARY_NAMESPACE_ID,
+            defer_namespace_name_1_sec(),
+            partition_provider,
+            Arc::new(MockPostWriteObserver::default()),
+            PredicateFilter::new(&metric::Registry::default()),
+        );
+
+        for (key, lines) in [
+            (&day_1, vec!["host=a v=1 10", "host=b v=2 20"]),
+            (&day_2, vec!["host=a v=3 86400000000010"]),
+        ] {
+            let lp = lines
+                .into_iter()
+                .map(|l| format!("{},{l}", &*ARBITRARY_TABLE_NAME))
+                .collect::<Vec<_>>()
+                .join("\n");
+            let batch = lines_to_batches(&lp, 0)
+                .unwrap()
+                .remove(&***ARBITRARY_TABLE_NAME)
+                .unwrap();
+            table
+                .buffer_table_write(SequenceNumber::new(42), batch, key.clone())
+                .await
+                .expect("buffer op should succeed");
+        }
+
+        let predicate = Predicate::new()
+            .with_range(0, 100)
+            .with_expr(col("host").eq(lit("a")));
+        let stream = table
+            .query_exec(
+                ARBITRARY_NAMESPACE_ID,
+                ARBITRARY_TABLE_ID,
+                vec![],
+                Some(predicate),
+                None,
+            )
+            .await
+            .expect("query should succeed");
+
+        let mut partitions = QueryResponse::new(stream)
+            .into_partition_stream()
+            .collect::<Vec<_>>()
+            .await;
+        partitions.sort_unstable_by_key(|p| p.id());
+        assert_eq!(partitions.len(), 2);
+
+        // The second partition is outside of the time range, and is pruned.
+        let p2 = partitions.pop().unwrap();
+        assert_eq!(p2.id(), PartitionId::new(2));
+        assert!(p2.into_record_batch_stream().is_none());
+
+        // The first partition contains only the matching row.
+        let p1 = partitions.pop().unwrap();
+        assert_eq!(p1.id(), PartitionId::new(1));
+        let batches = datafusion::physical_plan::common::collect(
+            p1.into_record_batch_stream()
+                .expect("partition should have data"),
+        )
+        .await
+        .expect("failed to collect batches");
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 1);
+    }
 }
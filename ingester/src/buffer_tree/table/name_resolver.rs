@@ -1,7 +1,7 @@
 use std::{sync::Arc, time::Duration};
 
 use backoff::{Backoff, BackoffConfig};
-use data_types::TableId;
+use data_types::{TableId, TablePartitionTemplateOverride};
 use iox_catalog::interface::Catalog;
 
 use super::TableName;
@@ -11,6 +11,14 @@ use crate::deferred_load::DeferredLoad;
 /// [`TableName`] of the specified [`TableId`].
 pub(crate) trait TableNameProvider: Send + Sync + std::fmt::Debug {
     fn for_table(&self, id: TableId) -> DeferredLoad<TableName>;
+
+    /// Return a [`DeferredLoad`] of the partition template override of the
+    /// specified [`TableId`], or [`None`] if the table uses the default
+    /// template.
+    fn partition_template_for_table(
+        &self,
+        id: TableId,
+    ) -> DeferredLoad<Option<TablePartitionTemplateOverride>>;
 }
 
 #[derive(Debug)]
@@ -59,6 +67,35 @@ impl TableNameResolver {
             .await
             .expect("retry forever")
     }
+
+    /// Fetch the partition template override from the [`Catalog`] for
+    /// specified `table_id`, retrying endlessly when errors occur.
+    ///
+    /// Tables inherit the template of their namespace when they are created,
+    /// so the table record alone determines how its writes are partitioned.
+    pub(crate) async fn fetch_partition_template(
+        table_id: TableId,
+        catalog: Arc<dyn Catalog>,
+        backoff_config: BackoffConfig,
+    ) -> Option<TablePartitionTemplateOverride> {
+        Backoff::new(&backoff_config)
+            .retry_all_errors("fetch table partition template", || async {
+                let t = catalog
+                    .repositories()
+                    .await
+                    .tables()
+                    .get_by_id(table_id)
+                    .await?
+                    .unwrap_or_else(|| {
+                        panic!("resolving partition template for non-existent table id {table_id}")
+                    })
+                    .partition_template;
+
+                Result::<_, iox_catalog::interface::Error>::Ok(t)
+            })
+            .await
+            .expect("retry forever")
+    }
 }
 
 impl TableNameProvider for TableNameResolver {
@@ -68,6 +105,20 @@ impl TableNameProvider for TableNameResolver {
             Self::fetch(id, Arc::clone(&self.catalog), self.backoff_config.clone()),
         )
     }
+
+    fn partition_template_for_table(
+        &self,
+        id: TableId,
+    ) -> DeferredLoad<Option<TablePartitionTemplateOverride>> {
+        DeferredLoad::new(
+            self.max_smear,
+            Self::fetch_partition_template(
+                id,
+                Arc::clone(&self.catalog),
+                self.backoff_config.clone(),
+            ),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -77,11 +128,15 @@ pub(crate) mod mock {
     #[derive(Debug)]
     pub(crate) struct MockTableNameProvider {
         name: TableName,
+        partition_template: Option<TablePartitionTemplateOverride>,
     }
 
     impl MockTableNameProvider {
         pub(crate) fn new(name: impl Into<TableName>) -> Self {
-            Self { name: name.into() }
+            Self {
+                name: name.into(),
+                partition_template: None,
+            }
         }
     }
 
@@ -96,6 +151,14 @@ pub(crate) mod mock {
             let name = self.name.clone();
             DeferredLoad::new(Duration::from_secs(1), async { name })
         }
+
+        fn partition_template_for_table(
+            &self,
+            _id: TableId,
+        ) -> DeferredLoad<Option<TablePartitionTemplateOverride>> {
+            let partition_template = self.partition_template.clone();
+            DeferredLoad::new(Duration::from_secs(1), async { partition_template })
+        }
     }
 }
 
@@ -133,5 +196,12 @@ mod tests {
             .with_timeout_panic(Duration::from_secs(5))
             .await;
         assert_eq!(&**got, TABLE_NAME);
+
+        let got = fetcher
+            .partition_template_for_table(table_id)
+            .get()
+            .with_timeout_panic(Duration::from_secs(5))
+            .await;
+        assert_eq!(got, None);
     }
 }
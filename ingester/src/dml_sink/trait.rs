@@ -21,6 +21,11 @@ pub enum DmlError {
     /// retrying indefinitely.
     #[error("buffer apply request timeout")]
     ApplyTimeout,
+
+    /// The operation could not be replicated to one or more replica
+    /// ingesters.
+    #[error("replication failure: {0}")]
+    Replication(String),
 }
 
 /// A [`DmlSink`] handles [`DmlOperation`] instances in some abstract way.
@@ -12,7 +12,15 @@ use backoff::BackoffConfig;
 use futures::{future::Shared, Future, FutureExt};
 use generated_types::influxdata::iox::{
     catalog::v1::catalog_service_server::CatalogService,
-    ingester::v1::{persist_service_server::PersistService, write_service_server::WriteService},
+    delete::v1::delete_service_server::DeleteService,
+    ingester::v1::{
+        partition_buffer_service_client::PartitionBufferServiceClient,
+        partition_buffer_service_server::PartitionBufferService,
+        persist_service_server::PersistService,
+        replication_service_client::ReplicationServiceClient,
+        replication_service_server::ReplicationService, write_service_server::WriteService,
+        GetPartitionBuffersRequest,
+    },
 };
 use iox_catalog::interface::Catalog;
 use iox_query::exec::Executor;
@@ -21,6 +29,7 @@ use parquet_file::storage::ParquetStorage;
 use thiserror::Error;
 use tokio::sync::oneshot;
 use tokio_util::sync::CancellationToken;
+use tonic::transport::Endpoint;
 use wal::Wal;
 
 use crate::{
@@ -35,14 +44,15 @@ use crate::{
     dml_sink::{instrumentation::DmlSinkInstrumentation, tracing::DmlSinkTracing},
     ingest_state::IngestState,
     ingester_id::IngesterId,
-    persist::{
-        completion_observer::NopObserver, handle::PersistHandle,
-        hot_partitions::HotPartitionPersister,
-    },
+    persist::{handle::PersistHandle, hot_partitions::HotPartitionPersister},
     query::{
         exec_instrumentation::QueryExecInstrumentation,
         result_instrumentation::QueryResultInstrumentation, tracing::QueryExecTracing,
     },
+    replication::{
+        bootstrap::bootstrap, observer::ReplicationObserver, sequence_map::ReplicaSequenceMap,
+        sink::ReplicationSink,
+    },
     server::grpc::GrpcDelegate,
     timestamp_oracle::TimestampOracle,
     wal::{rotate_task::periodic_rotation, wal_sink::WalSink},
@@ -64,10 +74,16 @@ pub trait IngesterRpcInterface: Send + Sync + std::fmt::Debug {
     type CatalogHandler: CatalogService;
     /// The type of the [`WriteService`] implementation.
     type WriteHandler: WriteService;
+    /// The type of the [`DeleteService`] implementation.
+    type DeleteHandler: DeleteService;
     /// The type of the [`PersistService`] implementation.
     type PersistHandler: PersistService;
     /// The type of the [`FlightService`] implementation.
     type FlightHandler: FlightService;
+    /// The type of the [`PartitionBufferService`] implementation.
+    type PartitionBufferHandler: PartitionBufferService;
+    /// The type of the [`ReplicationService`] implementation.
+    type ReplicationHandler: ReplicationService;
 
     /// Acquire an opaque handle to the Ingester's [`CatalogService`] RPC
     /// handler implementation.
@@ -77,6 +93,10 @@ pub trait IngesterRpcInterface: Send + Sync + std::fmt::Debug {
     /// handler implementation.
     fn write_service(&self) -> Self::WriteHandler;
 
+    /// Acquire an opaque handle to the Ingester's [`DeleteService`] RPC
+    /// handler implementation, applying deletes to the buffered data.
+    fn delete_service(&self) -> Self::DeleteHandler;
+
     /// Acquire an opaque handle to the Ingester's [`PersistService`] RPC
     /// handler implementation.
     fn persist_service(&self) -> Self::PersistHandler;
@@ -85,6 +105,16 @@ pub trait IngesterRpcInterface: Send + Sync + std::fmt::Debug {
     /// [`FlightService`] RPC handler implementation, allowing at most
     /// `max_simultaneous_requests` queries to be running at any one time.
     fn query_service(&self, max_simultaneous_requests: usize) -> Self::FlightHandler;
+
+    /// Acquire an opaque handle to the Ingester's [`PartitionBufferService`]
+    /// RPC handler implementation, used by replicas to initialise their
+    /// buffers.
+    fn partition_buffer_service(&self) -> Self::PartitionBufferHandler;
+
+    /// Acquire an opaque handle to the Ingester's [`ReplicationService`] RPC
+    /// handler implementation, accepting writes replicated from another
+    /// ingester.
+    fn replication_service(&self) -> Self::ReplicationHandler;
 }
 
 /// A RAII guard to clean up `ingester` instance resources when dropped.
@@ -143,6 +173,18 @@ pub enum InitError {
     /// An error replaying the entries in the WAL.
     #[error(transparent)]
     WalReplay(Box<dyn std::error::Error>),
+
+    /// A replica or replication source address is not a valid URI.
+    #[error("invalid replication address {0}: {1}")]
+    InvalidReplicationAddress(String, tonic::transport::Error),
+
+    /// The replication source could not be connected to.
+    #[error("failed to connect to replication source: {0}")]
+    ReplicationSourceConnect(tonic::transport::Error),
+
+    /// An error initialising the buffer from the replication source.
+    #[error("failed to bootstrap from replication source: {0}")]
+    Bootstrap(Box<dyn std::error::Error>),
 }
 
 /// Initialise a new `ingester` instance, returning the gRPC service handler
@@ -216,6 +258,22 @@ pub enum InitError {
 /// Decreasing this value increases the frequency of persist operations, and
 /// usually decreases the size of the resulting parquet files.
 ///
+/// ## Replication
+///
+/// Every write applied by this ingester is pushed to each ingester in
+/// `replica_addresses` before it is acknowledged, and the replicas are notified
+/// as the data is persisted so that they can drop their copy of it. If a
+/// replica is unavailable, writes fail until it recovers.
+///
+/// If `replication_source` is specified, this ingester acts as a replica of
+/// the ingester at that address: after the WAL is replayed, the data buffered
+/// by the source ingester is fetched and applied to the local buffer before
+/// this function returns. Any error during this initialisation is fatal.
+///
+/// A replica persists any replicated data that is not reported as persisted by
+/// its source, such as when the source ingester stops unexpectedly. The
+/// resulting duplicate data is deduplicated at query and compaction time.
+///
 /// [`MutableBatch::size_data()`]: mutable_batch::MutableBatch::size_data
 #[allow(clippy::too_many_arguments)]
 pub async fn new<F>(
@@ -229,6 +287,8 @@ pub async fn new<F>(
     persist_queue_depth: usize,
     persist_hot_partition_cost: usize,
     object_store: ParquetStorage,
+    replica_addresses: Vec<String>,
+    replication_source: Option<String>,
     shutdown: F,
 ) -> Result<IngesterGuard<impl IngesterRpcInterface>, InitError>
 where
@@ -237,6 +297,17 @@ where
     // Initialise a random ID for this ingester instance.
     let ingester_id = IngesterId::new();
 
+    // Initialise the (lazily connected) clients of the replicas of this
+    // ingester, if any.
+    let replicas = replica_addresses
+        .into_iter()
+        .map(|addr| {
+            Ok(Arc::new(ReplicationServiceClient::new(
+                endpoint(addr)?.connect_lazy(),
+            )))
+        })
+        .collect::<Result<Vec<_>, InitError>>()?;
+
     // Initialise the deferred namespace name resolver.
     let namespace_name_provider: Arc<dyn NamespaceNameProvider> =
         Arc::new(NamespaceNameResolver::new(
@@ -278,6 +349,10 @@ where
     );
     let partition_provider: Arc<dyn PartitionProvider> = Arc::new(partition_provider);
 
+    // Initialise the mapping of the sequence numbers assigned by the ingesters
+    // this instance replicates, if any, to those assigned locally.
+    let replica_sequence_map = Arc::new(ReplicaSequenceMap::default());
+
     // Initialise the ingest pause signal, used to propagate error conditions
     // between subsystems such that they cause an error to be returned in the
     // write path.
@@ -292,7 +367,11 @@ where
         persist_executor,
         object_store,
         Arc::clone(&catalog),
-        NopObserver::default(),
+        ReplicationObserver::new(
+            replicas.clone(),
+            ingester_id,
+            Arc::clone(&replica_sequence_map),
+        ),
         &metrics,
     );
     let persist_handle = Arc::new(persist_handle);
@@ -329,8 +408,50 @@ where
             .await
             .map_err(|e| InitError::WalReplay(e.into()))?;
 
+    // Restore the highest sequence number from the WAL files, and default to 0
+    // if there were no files to replay.
+    //
+    // This means sequence numbers are reused across different instances of an
+    // ingester, but they are only used for internal ordering of operations at
+    // runtime.
+    let timestamp = Arc::new(TimestampOracle::new(
+        max_sequence_number
+            .map(|v| u64::try_from(v.get()).expect("sequence number overflow"))
+            .unwrap_or(0),
+    ));
+
+    // Initialise the buffer from the ingester this instance replicates, if
+    // any.
+    if let Some(addr) = replication_source {
+        let channel = endpoint(addr.clone())?
+            .connect()
+            .await
+            .map_err(InitError::ReplicationSourceConnect)?;
+
+        let partitions = PartitionBufferServiceClient::new(channel)
+            .max_decoding_message_size(usize::MAX)
+            .get_partition_buffers(GetPartitionBuffersRequest {})
+            .await
+            .map_err(|e| InitError::Bootstrap(e.into()))?
+            .into_inner();
+
+        let n_partitions = bootstrap(partitions, &buffer, &timestamp, &replica_sequence_map)
+            .await
+            .map_err(|e| InitError::Bootstrap(e.into()))?;
+
+        info!(
+            n_partitions,
+            replication_source = %addr,
+            "initialised buffer from replication source"
+        );
+    }
+
     // Build the chain of DmlSink that forms the write path.
-    let write_path = DmlSinkInstrumentation::new(
+    //
+    // Writes replicated from another ingester are applied to this chain
+    // directly, while writes from the router are additionally pushed to the
+    // replicas of this ingester (if any).
+    let write_path = Arc::new(DmlSinkInstrumentation::new(
         "write_apply",
         DmlSinkTracing::new(
             DmlSinkTracing::new(
@@ -347,7 +468,8 @@ where
             "write_apply",
         ),
         &metrics,
-    );
+    ));
+    let rpc_write_path = ReplicationSink::new(Arc::clone(&write_path), replicas, ingester_id);
 
     // And the chain of QueryExec that forms the read path.
     let read_path = QueryResultInstrumentation::new(Arc::clone(&buffer), &metrics);
@@ -365,18 +487,6 @@ where
         Arc::clone(&persist_handle),
     ));
 
-    // Restore the highest sequence number from the WAL files, and default to 0
-    // if there were no files to replay.
-    //
-    // This means sequence numbers are reused across different instances of an
-    // ingester, but they are only used for internal ordering of operations at
-    // runtime.
-    let timestamp = Arc::new(TimestampOracle::new(
-        max_sequence_number
-            .map(|v| u64::try_from(v.get()).expect("sequence number overflow"))
-            .unwrap_or(0),
-    ));
-
     let (shutdown_tx, shutdown_rx) = oneshot::channel();
     let shutdown_task = tokio::spawn(graceful_shutdown_handler(
         shutdown,
@@ -389,7 +499,9 @@ where
 
     Ok(IngesterGuard {
         rpc: GrpcDelegate::new(
-            Arc::new(write_path),
+            Arc::new(rpc_write_path),
+            write_path,
+            replica_sequence_map,
             Arc::new(read_path),
             timestamp,
             ingest_state,
@@ -404,3 +516,8 @@ where
         shutdown_complete: shutdown_rx.shared(),
     })
 }
+
+/// Parse `addr` into a gRPC [`Endpoint`].
+fn endpoint(addr: String) -> Result<Endpoint, InitError> {
+    Endpoint::from_shared(addr.clone()).map_err(|e| InitError::InvalidReplicationAddress(addr, e))
+}
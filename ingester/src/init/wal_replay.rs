@@ -1,6 +1,9 @@
-use data_types::{NamespaceId, PartitionKey, SequenceNumber, TableId};
-use dml::{DmlMeta, DmlOperation, DmlWrite};
-use generated_types::influxdata::iox::wal::v1::sequenced_wal_op::Op;
+use data_types::{NamespaceId, NonEmptyString, PartitionKey, SequenceNumber, TableId};
+use dml::{DmlDelete, DmlMeta, DmlOperation, DmlWrite};
+use generated_types::{
+    google::{FieldViolation, FromOptionalField},
+    influxdata::iox::wal::v1::sequenced_wal_op::Op,
+};
 use metric::U64Counter;
 use mutable_batch_pb::decode::decode_database_batch;
 use observability_deps::tracing::*;
@@ -29,6 +32,10 @@ pub enum WalReplayError {
     #[error("failed converting wal entry to dml operation: {0}")]
     MapToDml(#[from] mutable_batch_pb::decode::Error),
 
+    /// An error converting the WAL delete entry into a [`DmlOperation`].
+    #[error("failed converting wal delete entry to dml operation: {0}")]
+    MapToDelete(#[from] FieldViolation),
+
     /// A failure to apply a [`DmlOperation`] from the WAL to the in-memory
     /// [`BufferTree`].
     ///
@@ -212,40 +219,45 @@ where
 
             max_sequence = max_sequence.max(Some(sequence_number));
 
-            let op = match op {
-                Op::Write(w) => w,
-                Op::Delete(_) => unreachable!(),
-                Op::Persist(_) => unreachable!(),
-            };
-
             debug!(?op, sequence_number = sequence_number.get(), "apply wal op");
 
-            // Reconstruct the DML operation
-            let batches = decode_database_batch(&op)?;
-            let namespace_id = NamespaceId::new(op.database_id);
-            let partition_key = PartitionKey::from(op.partition_key);
-
-            let op = DmlWrite::new(
-                namespace_id,
-                batches
-                    .into_iter()
-                    .map(|(k, v)| (TableId::new(k), v))
-                    .collect(),
-                partition_key,
-                // The tracing context should be propagated over the RPC boundary.
-                DmlMeta::sequenced(
-                    sequence_number,
-                    iox_time::Time::MAX, // TODO: remove this from DmlMeta
-                    // TODO: A tracing context should be added for WAL replay.
-                    None,
-                    42, // TODO: remove this from DmlMeta
-                ),
+            // The tracing context should be propagated over the RPC boundary.
+            let meta = DmlMeta::sequenced(
+                sequence_number,
+                iox_time::Time::MAX, // TODO: remove this from DmlMeta
+                // TODO: A tracing context should be added for WAL replay.
+                None,
+                42, // TODO: remove this from DmlMeta
             );
 
+            // Reconstruct the DML operation
+            let op = match op {
+                Op::Write(w) => {
+                    let batches = decode_database_batch(&w)?;
+                    let namespace_id = NamespaceId::new(w.database_id);
+                    let partition_key = PartitionKey::from(w.partition_key);
+
+                    DmlOperation::Write(DmlWrite::new(
+                        namespace_id,
+                        batches
+                            .into_iter()
+                            .map(|(k, v)| (TableId::new(k), v))
+                            .collect(),
+                        partition_key,
+                        meta,
+                    ))
+                }
+                Op::Delete(d) => DmlOperation::Delete(DmlDelete::new(
+                    NamespaceId::new(d.database_id),
+                    d.predicate.required("predicate")?,
+                    NonEmptyString::new(d.table_name),
+                    meta,
+                )),
+                Op::Persist(_) => unreachable!(),
+            };
+
             // Apply the operation to the provided DML sink
-            sink.apply(DmlOperation::Write(op))
-                .await
-                .map_err(Into::<DmlError>::into)?;
+            sink.apply(op).await.map_err(Into::<DmlError>::into)?;
 
             op_count_metric.inc(1);
         }
@@ -258,6 +270,7 @@ mod tests {
 
     use assert_matches::assert_matches;
     use async_trait::async_trait;
+    use data_types::{DeleteExpr, DeletePredicate, Op as DeleteOp, Scalar, TimestampRange};
     use metric::{Attributes, Metric};
     use parking_lot::Mutex;
     use wal::Wal;
@@ -456,4 +469,81 @@ mod tests {
             .fetch();
         assert_eq!(ops, 3);
     }
+
+    #[tokio::test]
+    async fn test_replay_delete() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let write = make_write_op(
+            &ARBITRARY_PARTITION_KEY,
+            ARBITRARY_NAMESPACE_ID,
+            &ARBITRARY_TABLE_NAME,
+            ARBITRARY_TABLE_ID,
+            24,
+            &format!(
+                r#"{},region=Madrid temp=35 4242424242"#,
+                &*ARBITRARY_TABLE_NAME
+            ),
+        );
+        let delete = DmlDelete::new(
+            ARBITRARY_NAMESPACE_ID,
+            DeletePredicate {
+                range: TimestampRange::new(1, 4242424242),
+                exprs: vec![DeleteExpr::new(
+                    "region".to_string(),
+                    DeleteOp::Eq,
+                    Scalar::String("Madrid".to_string()),
+                )],
+            },
+            NonEmptyString::new(&***ARBITRARY_TABLE_NAME),
+            DmlMeta::sequenced(SequenceNumber::new(25), iox_time::Time::MAX, None, 42),
+        );
+
+        // Write the ops, and rotate the file so that it is replayed.
+        {
+            let inner = Arc::new(MockDmlSink::default().with_apply_return(vec![Ok(()), Ok(())]));
+            let wal = Wal::new(dir.path())
+                .await
+                .expect("failed to initialise WAL");
+
+            let wal_sink = WalSink::new(Arc::clone(&inner), Arc::clone(&wal));
+            wal_sink
+                .apply(DmlOperation::Write(write.clone()))
+                .await
+                .expect("wal should not error");
+            wal_sink
+                .apply(DmlOperation::Delete(delete.clone()))
+                .await
+                .expect("wal should not error");
+
+            wal.rotate().expect("failed to rotate WAL file");
+        }
+
+        let wal = Wal::new(dir.path())
+            .await
+            .expect("failed to initialise WAL");
+
+        let persist = Arc::new(MockPersistQueue::default());
+        let mock_iter = MockIter {
+            sink: MockDmlSink::default().with_apply_return(vec![Ok(()), Ok(())]),
+            partitions: vec![],
+        };
+
+        let metrics = metric::Registry::default();
+        let max_sequence_number = replay(&wal, &mock_iter, Arc::clone(&persist), &metrics)
+            .await
+            .expect("failed to replay WAL");
+
+        assert_eq!(max_sequence_number, Some(SequenceNumber::new(25)));
+
+        // The delete is replayed after the write it applies to.
+        let ops = mock_iter.sink.get_calls();
+        assert_matches!(
+            &*ops,
+            &[DmlOperation::Write(ref w), DmlOperation::Delete(ref d)] => {
+                assert_dml_writes_eq(w.clone(), write);
+                assert_eq!(*d, delete);
+            }
+        );
+    }
 }
@@ -249,12 +249,14 @@ maybe_pub!(mod persist);
 maybe_pub!(mod partition_iter);
 maybe_pub!(mod wal);
 mod arcmap;
+mod batch_conversion;
 mod cancellation_safe;
 mod deferred_load;
 mod ingest_state;
 mod ingester_id;
 mod query;
 mod query_adaptor;
+mod replication;
 pub(crate) mod server;
 mod timestamp_oracle;
 
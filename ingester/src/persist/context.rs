@@ -19,6 +19,7 @@ use crate::{
     },
     deferred_load::DeferredLoad,
     persist::completion_observer::CompletedPersist,
+    query_adaptor::QueryAdaptor,
 };
 
 use super::completion_observer::PersistCompletionObserver;
@@ -217,8 +218,14 @@ impl Context {
     // Call [`PartitionData::mark_complete`] to finalise the persistence job,
     // emit a log for the user, and notify the observer of this persistence
     // task, if any.
-    pub(super) async fn mark_complete<O>(self, object_store_id: Uuid, completion_observer: &O)
-    where
+    //
+    // `object_store_id` is [`None`] if all the data was deleted before it was
+    // persisted, and no file was uploaded.
+    pub(super) async fn mark_complete<O>(
+        self,
+        object_store_id: Option<Uuid>,
+        completion_observer: &O,
+    ) where
         O: PersistCompletionObserver,
     {
         // Mark the partition as having completed persistence, causing it to
@@ -245,7 +252,7 @@ impl Context {
         let now = Instant::now();
 
         info!(
-            %object_store_id,
+            ?object_store_id,
             namespace_id = %self.namespace_id,
             namespace_name = %self.namespace_name,
             table_id = %self.table_id,
@@ -276,8 +283,12 @@ impl Context {
         &self.sort_key
     }
 
-    pub(super) fn data(&self) -> &PersistingData {
-        &self.data
+    /// Return the data to be persisted, excluding the rows deleted since it
+    /// was marked as persisting, or [`None`] if all of it was deleted.
+    pub(super) fn data_without_deletes(&self) -> Option<QueryAdaptor> {
+        self.partition
+            .lock()
+            .persisting_data_without_deletes(&self.data)
     }
 
     pub(super) fn namespace_id(&self) -> NamespaceId {
@@ -15,7 +15,7 @@ mod tests {
     use std::{sync::Arc, time::Duration};
 
     use assert_matches::assert_matches;
-    use data_types::{CompactionLevel, ParquetFile};
+    use data_types::{CompactionLevel, DeletePredicate, ParquetFile, TimestampRange};
     use dml::DmlOperation;
     use futures::TryStreamExt;
     use iox_catalog::{
@@ -295,6 +295,81 @@ mod tests {
         )
     }
 
+    /// Persisting data that was entirely deleted after it was marked as
+    /// persisting completes without uploading a file.
+    #[tokio::test]
+    async fn test_persist_integration_deleted() {
+        maybe_start_logging();
+
+        let object_storage: Arc<dyn ObjectStore> = Arc::new(InMemory::default());
+        let storage = ParquetStorage::new(Arc::clone(&object_storage), StorageId::from("iox"));
+        let metrics = Arc::new(metric::Registry::default());
+        let catalog: Arc<dyn Catalog> = Arc::new(MemCatalog::new(Arc::clone(&metrics)));
+        let ingest_state = Arc::new(IngestState::default());
+        let completion_observer = Arc::new(MockCompletionObserver::default());
+
+        let handle = PersistHandle::new(
+            1,
+            2,
+            Arc::clone(&ingest_state),
+            Arc::new(Executor::new_testing()),
+            storage,
+            Arc::clone(&catalog),
+            Arc::clone(&completion_observer),
+            &metrics,
+        );
+
+        let partition = partition_with_write(Arc::clone(&catalog)).await;
+        let partition_id = partition.lock().partition_id();
+
+        let data = partition
+            .lock()
+            .mark_persisting()
+            .expect("partition with write should transition to persisting");
+
+        // Delete all the persisting data before the persist job runs.
+        partition
+            .lock()
+            .apply_delete(Arc::new(DeletePredicate {
+                range: TimestampRange::new(0, i64::MAX),
+                exprs: vec![],
+            }));
+
+        handle
+            .enqueue(Arc::clone(&partition), data)
+            .await
+            .with_timeout(Duration::from_secs(10))
+            .await
+            .expect("timeout waiting for completion notification")
+            .expect("worker task failed");
+
+        // The persist completes, releasing the persisted writes.
+        assert_matches!(&completion_observer.calls().as_slice(), &[n] => {
+            assert_eq!(n.partition_id(), partition_id);
+            assert_eq!(n.sequence_numbers().len(), 1);
+        });
+        assert_eq!(partition.lock().completed_persistence_count(), 1);
+
+        // But no file is added to the catalog or object storage.
+        let files = catalog
+            .repositories()
+            .await
+            .parquet_files()
+            .list_by_partition_not_to_delete(partition_id)
+            .await
+            .expect("query for parquet files failed");
+        assert!(files.is_empty());
+
+        let objects = object_storage
+            .list(None)
+            .await
+            .expect("listing object storage failed")
+            .try_collect::<Vec<_>>()
+            .await
+            .expect("failed to list object store files");
+        assert!(objects.is_empty());
+    }
+
     /// An integration test covering concurrent catalog sort key updates,
     /// discovered at persist time.
     #[tokio::test]
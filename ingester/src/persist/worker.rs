@@ -5,7 +5,7 @@ use backoff::Backoff;
 use data_types::{CompactionLevel, ParquetFileParams};
 use iox_catalog::interface::{get_table_columns_by_id, CasFailure, Catalog};
 use iox_query::exec::Executor;
-use iox_time::{SystemProvider, TimeProvider};
+use iox_time::{SystemProvider, Time, TimeProvider};
 use metric::DurationHistogram;
 use observability_deps::tracing::{debug, info, warn};
 use parquet_file::{metadata::IoxMetadata, storage::ParquetStorage};
@@ -13,7 +13,7 @@ use schema::sort::SortKey;
 use tokio::{sync::mpsc, time::Instant};
 use uuid::Uuid;
 
-use crate::persist::compact::compact_persisting_batch;
+use crate::{persist::compact::compact_persisting_batch, query_adaptor::QueryAdaptor};
 
 use super::{
     compact::CompactedStream,
@@ -134,8 +134,12 @@ pub(super) async fn run_task<O>(
             };
         };
 
-        // Make the newly uploaded parquet file visible to other nodes.
-        let object_store_id = update_catalog_parquet(&ctx, &worker_state, parquet_table_data).await;
+        // Make the newly uploaded parquet file visible to other nodes, if the
+        // data was not entirely deleted before it could be persisted.
+        let object_store_id = match parquet_table_data {
+            Some(v) => Some(update_catalog_parquet(&ctx, &worker_state, v).await),
+            None => None,
+        };
 
         // And finally mark the persist job as complete and notify any
         // observers.
@@ -160,17 +164,43 @@ pub(super) async fn run_task<O>(
 ///
 /// See <https://github.com/influxdata/influxdb_iox/issues/6439>.
 ///
+/// Deletes applied to the [`PersistingData`] after it was marked as persisting
+/// are removed from it before it is compacted. If all of the data was deleted,
+/// no file is uploaded and [`None`] is returned.
+///
 /// [`PersistingData`]:
 ///     crate::buffer_tree::partition::persisting::PersistingData
 async fn compact_and_upload<O>(
     ctx: &mut Context,
     worker_state: &SharedWorkerState<O>,
-) -> Result<ParquetFileParams, PersistError>
+) -> Result<Option<ParquetFileParams>, PersistError>
 where
     O: Send + Sync,
 {
-    let compacted = compact(ctx, worker_state).await;
-    let (sort_key_update, parquet_table_data) = upload(ctx, worker_state, compacted).await;
+    // The creation time of the file MUST be read before the deletes applied to
+    // the data are - a delete applied after this point is then newer than the
+    // file, and is applied to it by queriers and the compactor.
+    let time_now = SystemProvider::new().now();
+
+    let data = match ctx.data_without_deletes() {
+        Some(v) => v,
+        None => {
+            debug!(
+                namespace_id = %ctx.namespace_id(),
+                namespace_name = %ctx.namespace_name(),
+                table_id = %ctx.table_id(),
+                table_name = %ctx.table_name(),
+                partition_id = %ctx.partition_id(),
+                partition_key = %ctx.partition_key(),
+                "persisting data entirely deleted, skipping upload"
+            );
+            return Ok(None);
+        }
+    };
+
+    let compacted = compact(ctx, worker_state, data).await;
+    let (sort_key_update, parquet_table_data) =
+        upload(ctx, worker_state, compacted, time_now).await;
 
     if let Some(update) = sort_key_update {
         update_catalog_sort_key(
@@ -182,12 +212,16 @@ where
         .await?
     }
 
-    Ok(parquet_table_data)
+    Ok(Some(parquet_table_data))
 }
 
-/// Compact the data in `ctx` using sorted by the sort key returned from
+/// Compact `data` from `ctx` using sorted by the sort key returned from
 /// [`Context::sort_key()`].
-async fn compact<O>(ctx: &Context, worker_state: &SharedWorkerState<O>) -> CompactedStream
+async fn compact<O>(
+    ctx: &Context,
+    worker_state: &SharedWorkerState<O>,
+    data: QueryAdaptor,
+) -> CompactedStream
 where
     O: Send + Sync,
 {
@@ -204,7 +238,7 @@ where
         "compacting partition"
     );
 
-    assert!(!ctx.data().record_batches().is_empty());
+    assert!(!data.record_batches().is_empty());
 
     // Run a compaction sort the data and resolve any duplicate values.
     //
@@ -214,18 +248,19 @@ where
         &worker_state.exec,
         sort_key,
         ctx.table_name().get().await,
-        ctx.data().query_adaptor(),
+        data,
     )
     .await
     .expect("unable to compact persisting batch")
 }
 
-/// Upload the compacted data in `compacted`, returning the new sort key value
-/// and parquet metadata to be upserted into the catalog.
+/// Upload the compacted data in `compacted`, created at `time_now`, returning
+/// the new sort key value and parquet metadata to be upserted into the catalog.
 async fn upload<O>(
     ctx: &Context,
     worker_state: &SharedWorkerState<O>,
     compacted: CompactedStream,
+    time_now: Time,
 ) -> (Option<SortKey>, ParquetFileParams)
 where
     O: Send + Sync,
@@ -253,7 +288,6 @@ where
     );
 
     // Construct the metadata for this parquet file.
-    let time_now = SystemProvider::new().now();
     let iox_metadata = IoxMetadata {
         object_store_id,
         creation_timestamp: time_now,
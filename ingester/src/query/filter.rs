@@ -0,0 +1,355 @@
+//! Pruning and filtering of buffered partition data using a query
+//! [`Predicate`].
+//!
+//! Evaluating the query predicate in the ingester reduces the amount of data
+//! streamed to the querier. This is an optimisation only - the querier applies
+//! the predicate to the returned data again, so any data that cannot be proven
+//! not to match the predicate is returned as-is.
+
+use std::sync::Arc;
+
+use arrow::record_batch::RecordBatch;
+use data_types::{PartitionKey, PartitionTemplate, TimestampRange, PARTITION_BY_DAY};
+use datafusion::{
+    common::{tree_node::TreeNode, ToDFSchema},
+    error::DataFusionError,
+    logical_expr::Expr,
+    optimizer::simplify_expressions::{ExprSimplifier, SimplifyContext},
+    physical_expr::execution_props::ExecutionProps,
+};
+use datafusion_util::{batch_filter, create_physical_expr_from_schema};
+use iox_query::{pruning::prune_summaries, util::MissingColumnsToNull, QueryChunkMeta};
+use iox_time::Time;
+use metric::U64Counter;
+use observability_deps::tracing::debug;
+use predicate::Predicate;
+use schema::Schema;
+
+use crate::query_adaptor::QueryAdaptor;
+
+/// The length of time covered by a partition key of the default (daily)
+/// partition template.
+const DAY_NANOS: i64 = 24 * 60 * 60 * 1_000_000_000;
+
+/// Applies a query [`Predicate`] to buffered partition data, recording the
+/// amount of data excluded from query responses.
+#[derive(Debug, Clone)]
+pub(crate) struct PredicateFilter {
+    partitions_pruned: U64Counter,
+    rows_pruned: U64Counter,
+}
+
+impl PredicateFilter {
+    pub(crate) fn new(metrics: &metric::Registry) -> Self {
+        let partitions_pruned = metrics
+            .register_metric::<U64Counter>(
+                "ingester_query_partitions_pruned",
+                "number of buffered partitions excluded from query responses by the query predicate",
+            )
+            .recorder(&[]);
+        let rows_pruned = metrics
+            .register_metric::<U64Counter>(
+                "ingester_query_rows_pruned",
+                "number of buffered rows excluded from query responses by the query predicate",
+            )
+            .recorder(&[]);
+
+        Self {
+            partitions_pruned,
+            rows_pruned,
+        }
+    }
+
+    /// Returns true if the partition identified by `partition_key` can be
+    /// proven to contain no rows matching the time range of `predicate`,
+    /// without reading the partition data.
+    ///
+    /// `partition_template` is the template the router derived
+    /// `partition_key` from. Only keys of the default (daily) template are
+    /// known to cover a single time range - partitions of tables with any
+    /// other template are never pruned by key.
+    pub(crate) fn prune_partition_key(
+        &self,
+        partition_key: &PartitionKey,
+        partition_template: &PartitionTemplate,
+        predicate: &Predicate,
+    ) -> bool {
+        let (Some(range), Some(key_range)) = (
+            predicate.range,
+            partition_key_range(partition_key, partition_template),
+        ) else {
+            return false;
+        };
+
+        let prune = range.end() <= key_range.start() || key_range.end() <= range.start();
+        if prune {
+            debug!(%partition_key, %predicate, "pruned partition by partition key");
+            self.partitions_pruned.inc(1);
+        }
+        prune
+    }
+
+    /// Apply `predicate` to `data`, returning only the rows that may match it,
+    /// or [`None`] if no rows match.
+    pub(crate) fn filter(&self, data: QueryAdaptor, predicate: &Predicate) -> Option<QueryAdaptor> {
+        let total_rows = num_rows(data.record_batches());
+
+        // Prune the entire partition if the statistics of the data show no
+        // rows can match.
+        if let Ok(keep) = prune_summaries(data.schema(), &[data.summary()], predicate) {
+            if !keep[0] {
+                debug!(partition_id=%data.partition_id(), %predicate, "pruned partition by statistics");
+                self.partitions_pruned.inc(1);
+                self.rows_pruned.inc(total_rows as u64);
+                return None;
+            }
+        }
+
+        let Some(expr) = predicate.filter_expr() else {
+            return Some(data);
+        };
+
+        let batches = data
+            .record_batches()
+            .iter()
+            .map(|batch| match filter_batch(batch, &expr) {
+                Ok(filtered) => Arc::new(filtered),
+                Err(e) => {
+                    // The querier filters the response, so this is not fatal.
+                    debug!(error=%e, %expr, "failed to apply query predicate to batch");
+                    Arc::clone(batch)
+                }
+            })
+            .filter(|batch| batch.num_rows() > 0)
+            .collect::<Vec<_>>();
+
+        self.rows_pruned
+            .inc((total_rows - num_rows(&batches)) as u64);
+
+        if batches.is_empty() {
+            self.partitions_pruned.inc(1);
+            return None;
+        }
+
+        Some(QueryAdaptor::new(data.partition_id(), batches))
+    }
+}
+
+fn num_rows(batches: &[Arc<RecordBatch>]) -> usize {
+    batches.iter().map(|b| b.num_rows()).sum()
+}
+
+/// Returns the range of timestamps covered by `partition_key` if it was
+/// derived from the default partition template, and so is in the
+/// `YYYY-MM-DD` form.
+fn partition_key_range(
+    partition_key: &PartitionKey,
+    partition_template: &PartitionTemplate,
+) -> Option<TimestampRange> {
+    if partition_template != &*PARTITION_BY_DAY {
+        return None;
+    }
+
+    let key = partition_key.to_string();
+    if key.len() != "YYYY-MM-DD".len() {
+        return None;
+    }
+
+    let start = Time::from_rfc3339(&format!("{key}T00:00:00Z"))
+        .ok()?
+        .timestamp_nanos();
+
+    Some(TimestampRange::new(start, start.checked_add(DAY_NANOS)?))
+}
+
+/// Return the rows of `batch` that match `expr`.
+///
+/// As in the querier, columns referenced by `expr` that do not exist in
+/// `batch` are treated as NULL.
+pub(crate) fn filter_batch(
+    batch: &RecordBatch,
+    expr: &Expr,
+) -> Result<RecordBatch, DataFusionError> {
+    let schema =
+        Schema::try_from(batch.schema()).map_err(|e| DataFusionError::External(Box::new(e)))?;
+    let expr = expr
+        .clone()
+        .rewrite(&mut MissingColumnsToNull::new(&schema))?;
+
+    let arrow_schema = batch.schema();
+    let df_schema = Arc::clone(&arrow_schema).to_dfschema_ref()?;
+
+    // Apply type coercion to ensure the types of the expression match the
+    // batch.
+    let props = ExecutionProps::new();
+    let simplifier =
+        ExprSimplifier::new(SimplifyContext::new(&props).with_schema(Arc::clone(&df_schema)));
+    let expr = simplifier.coerce(expr, df_schema)?;
+
+    let physical = create_physical_expr_from_schema(&props, &expr, &arrow_schema)?;
+    batch_filter(batch, &physical)
+}
+
+#[cfg(test)]
+mod tests {
+    use data_types::{PartitionId, TemplatePart};
+    use datafusion::{
+        assert_batches_sorted_eq,
+        prelude::{col, lit},
+    };
+    use metric::{Attributes, Metric};
+    use mutable_batch_lp::lines_to_batches;
+    use schema::Projection;
+
+    use super::*;
+
+    fn query_adaptor(lp: &str) -> QueryAdaptor {
+        let batch = lines_to_batches(lp, 0)
+            .unwrap()
+            .remove("bananas")
+            .unwrap()
+            .to_arrow(Projection::All)
+            .unwrap();
+        QueryAdaptor::new(PartitionId::new(1), vec![Arc::new(batch)])
+    }
+
+    fn counter(metrics: &metric::Registry, name: &'static str) -> u64 {
+        metrics
+            .get_instrument::<Metric<U64Counter>>(name)
+            .expect("failed to read metric")
+            .get_observer(&Attributes::from([]))
+            .expect("failed to get observer")
+            .fetch()
+    }
+
+    #[test]
+    fn test_partition_key_range() {
+        let template = &PARTITION_BY_DAY;
+        assert_eq!(
+            partition_key_range(&PartitionKey::from("1970-01-02"), template),
+            Some(TimestampRange::new(DAY_NANOS, 2 * DAY_NANOS))
+        );
+        assert_eq!(
+            partition_key_range(&PartitionKey::from("platanos"), template),
+            None
+        );
+        assert_eq!(
+            partition_key_range(&PartitionKey::from("1970-13-02"), template),
+            None
+        );
+        assert_eq!(
+            partition_key_range(&PartitionKey::from("1970-01-02 00:00:00"), template),
+            None
+        );
+
+        // Keys of any other template may look like a day, but are not one.
+        let template = PartitionTemplate {
+            parts: vec![TemplatePart::TimeFormat("%Y-%d-%m".to_string())],
+        };
+        assert_eq!(
+            partition_key_range(&PartitionKey::from("1970-02-01"), &template),
+            None
+        );
+        let template = PartitionTemplate {
+            parts: vec![TemplatePart::Column("region".to_string())],
+        };
+        assert_eq!(
+            partition_key_range(&PartitionKey::from("1970-01-02"), &template),
+            None
+        );
+    }
+
+    #[test]
+    fn test_prune_partition_key() {
+        let metrics = metric::Registry::default();
+        let filter = PredicateFilter::new(&metrics);
+        let key = PartitionKey::from("1970-01-02");
+        let template = &PARTITION_BY_DAY;
+
+        // Overlapping the day is not pruned.
+        let predicate = Predicate::new().with_range(DAY_NANOS - 1, DAY_NANOS + 1);
+        assert!(!filter.prune_partition_key(&key, template, &predicate));
+        let predicate = Predicate::new().with_range(2 * DAY_NANOS - 1, 3 * DAY_NANOS);
+        assert!(!filter.prune_partition_key(&key, template, &predicate));
+
+        // No time range, or a partition key that is not a day, is not pruned.
+        assert!(!filter.prune_partition_key(&key, template, &Predicate::new()));
+        let predicate = Predicate::new().with_range(0, 1);
+        assert!(!filter.prune_partition_key(&PartitionKey::from("platanos"), template, &predicate));
+
+        // Nor is a key of a custom template.
+        let custom = PartitionTemplate {
+            parts: vec![TemplatePart::TimeFormat("%Y-%d-%m".to_string())],
+        };
+        assert!(!filter.prune_partition_key(&key, &custom, &predicate));
+
+        // Ranges ending before, or starting after the day are pruned.
+        assert!(filter.prune_partition_key(
+            &key,
+            template,
+            &Predicate::new().with_range(0, DAY_NANOS)
+        ));
+        assert!(filter.prune_partition_key(
+            &key,
+            template,
+            &Predicate::new().with_range(2 * DAY_NANOS, 3 * DAY_NANOS)
+        ));
+
+        assert_eq!(counter(&metrics, "ingester_query_partitions_pruned"), 2);
+    }
+
+    #[test]
+    fn test_filter() {
+        let metrics = metric::Registry::default();
+        let filter = PredicateFilter::new(&metrics);
+        let data = query_adaptor(
+            "\
+            bananas,host=a v=1 10\n\
+            bananas,host=b v=2 20\n\
+            bananas,host=a v=3 30\n\
+            ",
+        );
+
+        // A predicate matching a subset of rows.
+        let predicate = Predicate::new()
+            .with_range(0, 25)
+            .with_expr(col("host").eq(lit("a")));
+        let got = filter
+            .filter(data.clone(), &predicate)
+            .expect("should match rows");
+        assert_batches_sorted_eq!(
+            [
+                "+------+-------------------------------+-----+",
+                "| host | time                          | v   |",
+                "+------+-------------------------------+-----+",
+                "| a    | 1970-01-01T00:00:00.000000010 | 1.0 |",
+                "+------+-------------------------------+-----+",
+            ],
+            &got.record_batches()
+                .iter()
+                .map(|b| (**b).clone())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(counter(&metrics, "ingester_query_rows_pruned"), 2);
+        assert_eq!(counter(&metrics, "ingester_query_partitions_pruned"), 0);
+
+        // A predicate referencing a column not in the data treats it as NULL.
+        let predicate = Predicate::new().with_expr(col("region").eq(lit("west")));
+        assert!(filter.filter(data.clone(), &predicate).is_none());
+        assert_eq!(counter(&metrics, "ingester_query_rows_pruned"), 5);
+        assert_eq!(counter(&metrics, "ingester_query_partitions_pruned"), 1);
+
+        // A time range outside of the data is pruned by statistics.
+        let predicate = Predicate::new().with_range(100, 200);
+        assert!(filter.filter(data.clone(), &predicate).is_none());
+        assert_eq!(counter(&metrics, "ingester_query_rows_pruned"), 8);
+        assert_eq!(counter(&metrics, "ingester_query_partitions_pruned"), 2);
+
+        // An empty predicate returns all rows.
+        let got = filter
+            .filter(data, &Predicate::new())
+            .expect("should match rows");
+        assert_eq!(num_rows(got.record_batches()), 3);
+        assert_eq!(counter(&metrics, "ingester_query_rows_pruned"), 8);
+    }
+}
@@ -1,6 +1,7 @@
 use async_trait::async_trait;
 use data_types::{NamespaceId, TableId};
 use parking_lot::Mutex;
+use predicate::Predicate;
 use trace::span::Span;
 
 use super::{response::QueryResponse, QueryError, QueryExec};
@@ -26,6 +27,7 @@ impl QueryExec for MockQueryExec {
         _namespace_id: NamespaceId,
         _table_id: TableId,
         _columns: Vec<String>,
+        _predicate: Option<Predicate>,
         _span: Option<Span>,
     ) -> Result<Self::Response, QueryError> {
         self.response
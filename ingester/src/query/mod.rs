@@ -7,6 +7,9 @@ pub(crate) use r#trait::*;
 pub(crate) mod partition_response;
 pub(crate) mod response;
 
+// Predicate evaluation
+pub(crate) mod filter;
+
 // Instrumentation
 pub(crate) mod exec_instrumentation;
 pub(crate) mod result_instrumentation;
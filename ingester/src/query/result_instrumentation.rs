@@ -65,6 +65,7 @@ use iox_time::{SystemProvider, Time, TimeProvider};
 use metric::{DurationHistogram, Metric, U64Histogram, U64HistogramOptions};
 use observability_deps::tracing::debug;
 use pin_project::{pin_project, pinned_drop};
+use predicate::Predicate;
 use trace::span::Span;
 
 use crate::query::{
@@ -217,13 +218,14 @@ where
         namespace_id: NamespaceId,
         table_id: TableId,
         columns: Vec<String>,
+        predicate: Option<Predicate>,
         span: Option<Span>,
     ) -> Result<Self::Response, QueryError> {
         let started_at = self.time_provider.now();
 
         let stream = self
             .inner
-            .query_exec(namespace_id, table_id, columns, span)
+            .query_exec(namespace_id, table_id, columns, predicate, span)
             .await?;
 
         let stream = QueryMetricContext::new(
@@ -551,7 +553,7 @@ mod tests {
             .with_time_provider(Arc::clone(&mock_time));
 
         let response = layer
-            .query_exec(NAMESPACE_ID, TABLE_ID, vec![], None)
+            .query_exec(NAMESPACE_ID, TABLE_ID, vec![], None, None)
             .await
             .expect("query should succeed");
 
@@ -650,7 +652,7 @@ mod tests {
             .with_time_provider(Arc::clone(&mock_time));
 
         let response = layer
-            .query_exec(NAMESPACE_ID, TABLE_ID, vec![], None)
+            .query_exec(NAMESPACE_ID, TABLE_ID, vec![], None, None)
             .await
             .expect("query should succeed");
 
@@ -748,7 +750,7 @@ mod tests {
             .with_time_provider(Arc::clone(&mock_time));
 
         let response = layer
-            .query_exec(NAMESPACE_ID, TABLE_ID, vec![], None)
+            .query_exec(NAMESPACE_ID, TABLE_ID, vec![], None, None)
             .await
             .expect("query should succeed");
 
@@ -842,7 +844,7 @@ mod tests {
             .with_time_provider(Arc::clone(&mock_time));
 
         let response = layer
-            .query_exec(NAMESPACE_ID, TABLE_ID, vec![], None)
+            .query_exec(NAMESPACE_ID, TABLE_ID, vec![], None, None)
             .await
             .expect("query should succeed");
 
@@ -942,7 +944,7 @@ mod tests {
             .with_time_provider(Arc::clone(&mock_time));
 
         let response = layer
-            .query_exec(NAMESPACE_ID, TABLE_ID, vec![], None)
+            .query_exec(NAMESPACE_ID, TABLE_ID, vec![], None, None)
             .await
             .expect("query should succeed");
 
@@ -1049,7 +1051,7 @@ mod tests {
             .with_time_provider(Arc::clone(&mock_time));
 
         let response = layer
-            .query_exec(NAMESPACE_ID, TABLE_ID, vec![], None)
+            .query_exec(NAMESPACE_ID, TABLE_ID, vec![], None, None)
             .await
             .expect("query should succeed");
 
@@ -2,6 +2,7 @@ use std::borrow::Cow;
 
 use async_trait::async_trait;
 use data_types::{NamespaceId, TableId};
+use predicate::Predicate;
 use trace::span::{Span, SpanRecorder};
 
 use super::QueryExec;
@@ -41,13 +42,20 @@ where
         namespace_id: NamespaceId,
         table_id: TableId,
         columns: Vec<String>,
+        predicate: Option<Predicate>,
         span: Option<Span>,
     ) -> Result<Self::Response, QueryError> {
         let mut recorder = SpanRecorder::new(span).child(self.name.clone());
 
         match self
             .inner
-            .query_exec(namespace_id, table_id, columns, recorder.span().cloned())
+            .query_exec(
+                namespace_id,
+                table_id,
+                columns,
+                predicate,
+                recorder.span().cloned(),
+            )
             .await
         {
             Ok(v) => {
@@ -110,6 +118,7 @@ mod tests {
                 NamespaceId::new(42),
                 TableId::new(24),
                 vec![],
+                None,
                 Some(span.child("root span")),
             )
             .await
@@ -133,6 +142,7 @@ mod tests {
                 NamespaceId::new(42),
                 TableId::new(24),
                 vec![],
+                None,
                 Some(span.child("root span")),
             )
             .await
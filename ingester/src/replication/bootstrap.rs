@@ -0,0 +1,281 @@
+//! Initialisation of a replica's buffer from the ingester it replicates.
+
+use data_types::{sequence_number_set::SequenceNumberSet, NamespaceId, PartitionKey, TableId};
+use dml::{DmlMeta, DmlOperation, DmlWrite};
+use futures::{Stream, StreamExt};
+use generated_types::influxdata::iox::ingester::v1::GetPartitionBuffersResponse;
+use mutable_batch_pb::decode::decode_database_batch;
+use observability_deps::tracing::*;
+use thiserror::Error;
+
+use super::sequence_map::ReplicaSequenceMap;
+use crate::{
+    buffer_tree::{post_write::PostWriteObserver, BufferTree},
+    dml_sink::DmlSink,
+    timestamp_oracle::TimestampOracle,
+};
+
+/// Errors returned when initialising a buffer from another ingester.
+#[derive(Debug, Error)]
+pub enum BootstrapError {
+    /// The upstream ingester returned an error.
+    #[error("failed to fetch partition buffers: {0}")]
+    Rpc(#[from] tonic::Status),
+
+    /// A partition buffer did not contain a data payload.
+    #[error("partition buffer does not contain a payload")]
+    NoPayload,
+
+    /// The serialised partition data could not be read.
+    #[error("failed to decode partition buffer: {0}")]
+    Decode(#[from] mutable_batch_pb::decode::Error),
+
+    /// The serialised set of sequence numbers could not be read.
+    #[error("{0}")]
+    SequenceNumberSet(String),
+
+    /// A partition buffer contained no sequence numbers.
+    #[error("partition buffer does not contain any sequence numbers")]
+    NoSequenceNumbers,
+
+    /// The partition data could not be buffered.
+    #[error("failed to buffer partition data: {0}")]
+    Buffer(#[from] mutable_batch::Error),
+}
+
+/// Apply each partition buffer yielded by `partitions` to `buffer`, returning
+/// the number of partitions initialised.
+///
+/// Each partition buffer is written to `buffer` as a single write carrying a
+/// [`SequenceNumber`] from `timestamp`, and the upstream [`SequenceNumber`] of
+/// the writes it contains are mapped to it in `sequence_map` so that persist
+/// notifications from the upstream ingester can be matched to it.
+///
+/// Partition buffers are not committed to the write-ahead log; a replica
+/// bootstraps again from its upstream ingester each time it starts.
+///
+/// [`SequenceNumber`]: data_types::SequenceNumber
+pub(crate) async fn bootstrap<S, O>(
+    partitions: S,
+    buffer: &BufferTree<O>,
+    timestamp: &TimestampOracle,
+    sequence_map: &ReplicaSequenceMap,
+) -> Result<usize, BootstrapError>
+where
+    S: Stream<Item = Result<GetPartitionBuffersResponse, tonic::Status>> + Send,
+    O: PostWriteObserver,
+{
+    let mut partitions = std::pin::pin!(partitions);
+    let mut n_partitions = 0;
+
+    while let Some(partition) = partitions.next().await {
+        let partition = partition?;
+
+        let sequence_numbers =
+            SequenceNumberSet::try_from(partition.croaring_sequence_number_bitmap.as_slice())
+                .map_err(BootstrapError::SequenceNumberSet)?;
+        if sequence_numbers.is_empty() {
+            return Err(BootstrapError::NoSequenceNumbers);
+        }
+
+        let payload = partition.payload.ok_or(BootstrapError::NoPayload)?;
+        let batches = decode_database_batch(&payload)?;
+        let namespace_id = NamespaceId::new(partition.namespace_id);
+        let table_id = TableId::new(partition.table_id);
+        let partition_key = PartitionKey::from(payload.partition_key);
+
+        debug!(
+            %namespace_id,
+            %table_id,
+            partition_id = partition.partition_id,
+            %partition_key,
+            upstream_ingester = %partition.ingester_uuid,
+            "bootstrapping partition buffer"
+        );
+
+        let sequence_number = timestamp.next();
+        let op = DmlWrite::new(
+            namespace_id,
+            batches
+                .into_iter()
+                .map(|(k, v)| (TableId::new(k), v))
+                .collect(),
+            partition_key,
+            DmlMeta::sequenced(
+                sequence_number,
+                iox_time::Time::MAX, // TODO: remove this from DmlMeta
+                None,
+                42, // TODO: remove this from DmlMeta
+            ),
+        );
+        buffer.apply(DmlOperation::Write(op)).await?;
+
+        // Record the full set of upstream writes the buffered data represents.
+        sequence_map.insert(
+            &partition.ingester_uuid,
+            table_id,
+            &sequence_numbers,
+            sequence_number,
+        );
+
+        n_partitions += 1;
+    }
+
+    Ok(n_partitions)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow_util::assert_batches_eq;
+    use assert_matches::assert_matches;
+    use mutable_batch_pb::encode::encode_write;
+
+    use data_types::SequenceNumber;
+
+    use super::*;
+    use crate::{
+        buffer_tree::{
+            namespace::name_resolver::mock::MockNamespaceNameProvider,
+            partition::resolver::mock::MockPartitionProvider,
+            post_write::mock::MockPostWriteObserver,
+        },
+        test_util::{
+            make_write_op, PartitionDataBuilder, ARBITRARY_NAMESPACE_ID, ARBITRARY_NAMESPACE_NAME,
+            ARBITRARY_PARTITION_ID, ARBITRARY_PARTITION_KEY, ARBITRARY_TABLE_ID,
+            ARBITRARY_TABLE_NAME, ARBITRARY_TABLE_NAME_PROVIDER,
+        },
+    };
+
+    fn new_buffer() -> BufferTree<MockPostWriteObserver> {
+        BufferTree::new(
+            Arc::new(MockNamespaceNameProvider::new(&**ARBITRARY_NAMESPACE_NAME)),
+            Arc::clone(&*ARBITRARY_TABLE_NAME_PROVIDER),
+            Arc::new(
+                MockPartitionProvider::default()
+                    .with_partition(PartitionDataBuilder::new().build()),
+            ),
+            Arc::new(MockPostWriteObserver::default()),
+            Arc::new(metric::Registry::default()),
+        )
+    }
+
+    fn partition_buffer(sequence_numbers: &[i64]) -> GetPartitionBuffersResponse {
+        let op = make_write_op(
+            &ARBITRARY_PARTITION_KEY,
+            ARBITRARY_NAMESPACE_ID,
+            &ARBITRARY_TABLE_NAME,
+            ARBITRARY_TABLE_ID,
+            0,
+            "bananas,city=London people=2 10\nbananas,city=Madrid people=4 20",
+        );
+
+        GetPartitionBuffersResponse {
+            ingester_uuid: "bananas".to_string(),
+            namespace_id: ARBITRARY_NAMESPACE_ID.get(),
+            table_id: ARBITRARY_TABLE_ID.get(),
+            partition_id: ARBITRARY_PARTITION_ID.get(),
+            croaring_sequence_number_bitmap: sequence_numbers
+                .iter()
+                .map(|v| SequenceNumber::new(*v))
+                .collect::<SequenceNumberSet>()
+                .to_bytes(),
+            payload: Some(encode_write(ARBITRARY_NAMESPACE_ID.get(), &op)),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bootstrap() {
+        let buffer = new_buffer();
+        let sequence_map = ReplicaSequenceMap::default();
+
+        let n = bootstrap(
+            futures::stream::iter([Ok(partition_buffer(&[1, 2, 5]))]),
+            &buffer,
+            &TimestampOracle::new(41),
+            &sequence_map,
+        )
+        .await
+        .expect("bootstrap should succeed");
+        assert_eq!(n, 1);
+
+        let partitions = buffer.partitions().collect::<Vec<_>>();
+        assert_matches!(partitions.as_slice(), [p] => {
+            let mut p = p.lock();
+            assert_eq!(p.partition_id(), ARBITRARY_PARTITION_ID);
+            assert_eq!(
+                p.sequence_number_set(),
+                SequenceNumberSet::from_iter([SequenceNumber::new(42)])
+            );
+
+            let data = p.get_query_data().expect("must contain data");
+            assert_batches_eq!(
+                [
+                    "+--------+--------+--------------------------------+",
+                    "| city   | people | time                           |",
+                    "+--------+--------+--------------------------------+",
+                    "| London | 2.0    | 1970-01-01T00:00:00.000000010Z |",
+                    "| Madrid | 4.0    | 1970-01-01T00:00:00.000000020Z |",
+                    "+--------+--------+--------------------------------+",
+                ],
+                &data
+                    .record_batches()
+                    .iter()
+                    .map(|v| (**v).clone())
+                    .collect::<Vec<_>>()
+            );
+        });
+
+        // The buffered write is only persisted once all of the upstream
+        // writes it contains are.
+        let set = |v: &[i64]| -> SequenceNumberSet {
+            v.iter().map(|v| SequenceNumber::new(*v)).collect()
+        };
+        assert!(sequence_map
+            .mark_persisted("bananas", ARBITRARY_TABLE_ID, &set(&[1, 2]))
+            .is_empty());
+        assert_eq!(
+            sequence_map.mark_persisted("bananas", ARBITRARY_TABLE_ID, &set(&[5])),
+            set(&[42])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bootstrap_errors() {
+        let buffer = new_buffer();
+        let timestamp = TimestampOracle::new(0);
+        let sequence_map = ReplicaSequenceMap::default();
+
+        let got = bootstrap(
+            futures::stream::iter([Err(tonic::Status::unavailable("bananas"))]),
+            &buffer,
+            &timestamp,
+            &sequence_map,
+        )
+        .await;
+        assert_matches!(got, Err(BootstrapError::Rpc(_)));
+
+        let got = bootstrap(
+            futures::stream::iter([Ok(partition_buffer(&[]))]),
+            &buffer,
+            &timestamp,
+            &sequence_map,
+        )
+        .await;
+        assert_matches!(got, Err(BootstrapError::NoSequenceNumbers));
+
+        let mut no_payload = partition_buffer(&[1]);
+        no_payload.payload = None;
+        let got = bootstrap(
+            futures::stream::iter([Ok(no_payload)]),
+            &buffer,
+            &timestamp,
+            &sequence_map,
+        )
+        .await;
+        assert_matches!(got, Err(BootstrapError::NoPayload));
+
+        assert_eq!(buffer.partitions().count(), 0);
+    }
+}
@@ -0,0 +1,103 @@
+//! An abstraction over the RPC client used to push events to a replica.
+
+use std::{fmt::Debug, sync::Arc};
+
+use async_trait::async_trait;
+use generated_types::influxdata::iox::ingester::v1::{
+    replication_service_client::ReplicationServiceClient, PersistCompleteRequest, ReplicateRequest,
+};
+use tonic::transport::Channel;
+
+/// An abstract RPC client that pushes replication events to a replica
+/// ingester.
+#[async_trait]
+pub(crate) trait ReplicationClient: Send + Sync + Debug {
+    /// Push the write in `req` to the replica and wait for it to be applied.
+    async fn replicate(&self, req: ReplicateRequest) -> Result<(), tonic::Status>;
+
+    /// Notify the replica of the persist operation described by `req`.
+    async fn persist_complete(&self, req: PersistCompleteRequest) -> Result<(), tonic::Status>;
+}
+
+/// An implementation of [`ReplicationClient`] for the tonic gRPC client.
+#[async_trait]
+impl ReplicationClient for ReplicationServiceClient<Channel> {
+    async fn replicate(&self, req: ReplicateRequest) -> Result<(), tonic::Status> {
+        ReplicationServiceClient::replicate(&mut self.clone(), req).await?;
+        Ok(())
+    }
+
+    async fn persist_complete(&self, req: PersistCompleteRequest) -> Result<(), tonic::Status> {
+        ReplicationServiceClient::persist_complete(&mut self.clone(), req).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<T> ReplicationClient for Arc<T>
+where
+    T: ReplicationClient,
+{
+    async fn replicate(&self, req: ReplicateRequest) -> Result<(), tonic::Status> {
+        (**self).replicate(req).await
+    }
+
+    async fn persist_complete(&self, req: PersistCompleteRequest) -> Result<(), tonic::Status> {
+        (**self).persist_complete(req).await
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod mock {
+    use std::collections::VecDeque;
+
+    use parking_lot::Mutex;
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct State {
+        replicate_calls: Vec<ReplicateRequest>,
+        persist_complete_calls: Vec<PersistCompleteRequest>,
+        ret: VecDeque<Result<(), tonic::Status>>,
+    }
+
+    /// A mock [`ReplicationClient`] that records the requests it receives.
+    ///
+    /// Calls return the values configured with [`Self::with_ret()`] in order,
+    /// and [`Ok`] once they are exhausted.
+    #[derive(Debug, Default)]
+    pub(crate) struct MockReplicationClient {
+        state: Mutex<State>,
+    }
+
+    impl MockReplicationClient {
+        pub(crate) fn with_ret(self, ret: impl Into<VecDeque<Result<(), tonic::Status>>>) -> Self {
+            self.state.lock().ret = ret.into();
+            self
+        }
+
+        pub(crate) fn replicate_calls(&self) -> Vec<ReplicateRequest> {
+            self.state.lock().replicate_calls.clone()
+        }
+
+        pub(crate) fn persist_complete_calls(&self) -> Vec<PersistCompleteRequest> {
+            self.state.lock().persist_complete_calls.clone()
+        }
+    }
+
+    #[async_trait]
+    impl ReplicationClient for MockReplicationClient {
+        async fn replicate(&self, req: ReplicateRequest) -> Result<(), tonic::Status> {
+            let mut state = self.state.lock();
+            state.replicate_calls.push(req);
+            state.ret.pop_front().unwrap_or(Ok(()))
+        }
+
+        async fn persist_complete(&self, req: PersistCompleteRequest) -> Result<(), tonic::Status> {
+            let mut state = self.state.lock();
+            state.persist_complete_calls.push(req);
+            state.ret.pop_front().unwrap_or(Ok(()))
+        }
+    }
+}
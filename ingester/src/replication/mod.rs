@@ -0,0 +1,22 @@
+//! Replication of buffered writes between ingester instances.
+//!
+//! An ingester configured with one or more replicas pushes every write it
+//! applies to each of them using a [`ReplicationSink`], and notifies them of
+//! completed persist operations using a [`ReplicationObserver`] so that the
+//! replicas can release the persisted data from their buffers.
+//!
+//! A replica initialises its buffer from the ingester it replicates at
+//! startup using [`bootstrap()`], before accepting replicated writes. Writes
+//! received from an upstream ingester are buffered with a locally assigned
+//! sequence number, tracked by the [`ReplicaSequenceMap`].
+//!
+//! [`ReplicationSink`]: sink::ReplicationSink
+//! [`ReplicationObserver`]: observer::ReplicationObserver
+//! [`bootstrap()`]: bootstrap::bootstrap
+//! [`ReplicaSequenceMap`]: sequence_map::ReplicaSequenceMap
+
+pub(crate) mod bootstrap;
+pub(crate) mod client;
+pub(crate) mod observer;
+pub(crate) mod sequence_map;
+pub(crate) mod sink;
@@ -0,0 +1,176 @@
+//! A [`PersistCompletionObserver`] that notifies replica ingesters of
+//! completed persist operations.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::future::join_all;
+use generated_types::influxdata::iox::ingester::v1::PersistCompleteRequest;
+use observability_deps::tracing::*;
+
+use super::{client::ReplicationClient, sequence_map::ReplicaSequenceMap};
+use crate::{
+    ingester_id::IngesterId,
+    persist::completion_observer::{CompletedPersist, PersistCompletionObserver},
+};
+
+/// A [`PersistCompletionObserver`] that sends a [`PersistCompleteRequest`] to
+/// every configured replica when a persist operation completes.
+///
+/// Notification failures are logged and otherwise ignored - a replica that
+/// misses a notification retains the data until it persists it itself, which
+/// results in duplicate (but deduplicated) data rather than data loss.
+///
+/// Persisted writes that were replicated to this ingester are removed from the
+/// [`ReplicaSequenceMap`], as notifications from their upstream ingester no
+/// longer refer to buffered data.
+#[derive(Debug)]
+pub(crate) struct ReplicationObserver<C> {
+    replicas: Vec<C>,
+    ingester_id: IngesterId,
+    sequence_map: Arc<ReplicaSequenceMap>,
+}
+
+impl<C> ReplicationObserver<C> {
+    /// Initialise a new [`ReplicationObserver`] notifying `replicas` of
+    /// completed persist operations, identifying itself as `ingester_id`.
+    pub(crate) fn new(
+        replicas: Vec<C>,
+        ingester_id: IngesterId,
+        sequence_map: Arc<ReplicaSequenceMap>,
+    ) -> Self {
+        Self {
+            replicas,
+            ingester_id,
+            sequence_map,
+        }
+    }
+}
+
+#[async_trait]
+impl<C> PersistCompletionObserver for ReplicationObserver<C>
+where
+    C: ReplicationClient,
+{
+    async fn persist_complete(&self, note: Arc<CompletedPersist>) {
+        self.sequence_map
+            .forget(note.table_id(), note.sequence_numbers());
+
+        if self.replicas.is_empty() {
+            return;
+        }
+
+        let req = PersistCompleteRequest {
+            ingester_uuid: self.ingester_id.to_string(),
+            namespace_id: note.namespace_id().get(),
+            table_id: note.table_id().get(),
+            partition_id: note.partition_id().get(),
+            croaring_sequence_number_bitmap: note.sequence_numbers().to_bytes(),
+        };
+
+        let results = join_all(
+            self.replicas
+                .iter()
+                .map(|r| r.persist_complete(req.clone())),
+        )
+        .await;
+        for e in results.into_iter().filter_map(Result::err) {
+            warn!(
+                error=%e,
+                namespace_id = %note.namespace_id(),
+                table_id = %note.table_id(),
+                partition_id = %note.partition_id(),
+                "failed to notify replica of persist completion"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use data_types::{
+        sequence_number_set::SequenceNumberSet, NamespaceId, PartitionId, SequenceNumber, TableId,
+    };
+
+    use super::*;
+    use crate::replication::client::mock::MockReplicationClient;
+
+    #[tokio::test]
+    async fn test_notify_replicas() {
+        let replicas = vec![
+            Arc::new(
+                MockReplicationClient::default()
+                    .with_ret([Err(tonic::Status::unavailable("replica down"))]),
+            ),
+            Arc::new(MockReplicationClient::default()),
+        ];
+        let ingester_id = IngesterId::new();
+        let observer = ReplicationObserver::new(
+            replicas.clone(),
+            ingester_id,
+            Arc::new(ReplicaSequenceMap::default()),
+        );
+
+        let set = SequenceNumberSet::from_iter([SequenceNumber::new(1), SequenceNumber::new(3)]);
+        observer
+            .persist_complete(Arc::new(CompletedPersist::new(
+                NamespaceId::new(1),
+                TableId::new(2),
+                PartitionId::new(3),
+                set.clone(),
+            )))
+            .await;
+
+        // Both replicas are notified, regardless of the failure of the first.
+        for replica in &replicas {
+            let calls = replica.persist_complete_calls();
+            assert_eq!(calls.len(), 1);
+
+            let req = &calls[0];
+            assert_eq!(req.ingester_uuid, ingester_id.to_string());
+            assert_eq!(req.namespace_id, 1);
+            assert_eq!(req.table_id, 2);
+            assert_eq!(req.partition_id, 3);
+            assert_eq!(
+                SequenceNumberSet::try_from(req.croaring_sequence_number_bitmap.as_slice())
+                    .expect("valid bitmap"),
+                set
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_forget_replicated_writes() {
+        let sequence_map = Arc::new(ReplicaSequenceMap::default());
+        sequence_map.insert(
+            "upstream",
+            TableId::new(2),
+            &SequenceNumberSet::from_iter([SequenceNumber::new(1)]),
+            SequenceNumber::new(3),
+        );
+
+        let observer = ReplicationObserver::<Arc<MockReplicationClient>>::new(
+            vec![],
+            IngesterId::new(),
+            Arc::clone(&sequence_map),
+        );
+        observer
+            .persist_complete(Arc::new(CompletedPersist::new(
+                NamespaceId::new(1),
+                TableId::new(2),
+                PartitionId::new(3),
+                SequenceNumberSet::from_iter([SequenceNumber::new(3)]),
+            )))
+            .await;
+
+        // The upstream write was persisted locally, so a later notification
+        // from the upstream ingester resolves to nothing.
+        assert!(sequence_map
+            .mark_persisted(
+                "upstream",
+                TableId::new(2),
+                &SequenceNumberSet::from_iter([SequenceNumber::new(1)])
+            )
+            .is_empty());
+    }
+}
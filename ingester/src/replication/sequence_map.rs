@@ -0,0 +1,202 @@
+//! Translation of the [`SequenceNumber`] assigned to replicated writes by
+//! their upstream ingester into those assigned by this ingester.
+
+use std::collections::HashMap;
+
+use data_types::{sequence_number_set::SequenceNumberSet, SequenceNumber, TableId};
+use parking_lot::Mutex;
+
+/// Maps the [`SequenceNumber`] each upstream ingester assigned to a replicated
+/// write to the [`SequenceNumber`] this ingester applied it with.
+///
+/// Each ingester assigns [`SequenceNumber`] values from its own, independent
+/// sequence, so values assigned by an upstream ingester may collide with those
+/// assigned by this ingester, or by another upstream ingester. Replicated
+/// writes are therefore buffered with a locally assigned [`SequenceNumber`],
+/// and persist notifications from an upstream ingester are translated into the
+/// local sequence before they are applied to the buffer.
+///
+/// A single local write may contain the data of many upstream writes (such as
+/// when a partition buffer is bootstrapped from an upstream ingester), in which
+/// case it is only reported as persisted once all of them have been persisted
+/// upstream.
+///
+/// A write may contain the data of several tables, which the upstream ingester
+/// persists (and notifies this ingester of) independently, so writes are
+/// mapped per table.
+#[derive(Debug, Default)]
+pub(crate) struct ReplicaSequenceMap {
+    /// The mappings of each upstream ingester, keyed by its UUID and the
+    /// table the writes are for.
+    upstreams: Mutex<HashMap<(String, TableId), Upstream>>,
+}
+
+#[derive(Debug, Default)]
+struct Upstream {
+    /// The local [`SequenceNumber`] of each upstream [`SequenceNumber`].
+    local: HashMap<SequenceNumber, SequenceNumber>,
+
+    /// The upstream [`SequenceNumber`] values that have not yet been reported
+    /// as persisted, for each local [`SequenceNumber`].
+    pending: HashMap<SequenceNumber, SequenceNumberSet>,
+}
+
+impl ReplicaSequenceMap {
+    /// Record that the writes to `table_id` that `upstream` assigned the
+    /// [`SequenceNumber`] values in `sequence_numbers` to were applied locally
+    /// as `local`.
+    pub(crate) fn insert(
+        &self,
+        upstream: &str,
+        table_id: TableId,
+        sequence_numbers: &SequenceNumberSet,
+        local: SequenceNumber,
+    ) {
+        let mut upstreams = self.upstreams.lock();
+        let u = upstreams
+            .entry((upstream.to_string(), table_id))
+            .or_default();
+
+        for n in sequence_numbers.iter() {
+            u.local.insert(n, local);
+        }
+        u.pending
+            .entry(local)
+            .or_default()
+            .add_set(sequence_numbers);
+    }
+
+    /// Record the writes to `table_id` that `upstream` assigned the
+    /// [`SequenceNumber`] values in `persisted` to as persisted, returning the
+    /// set of local [`SequenceNumber`] for which all upstream writes to
+    /// `table_id` are now persisted.
+    ///
+    /// Values that are not mapped to a local [`SequenceNumber`] are ignored.
+    pub(crate) fn mark_persisted(
+        &self,
+        upstream: &str,
+        table_id: TableId,
+        persisted: &SequenceNumberSet,
+    ) -> SequenceNumberSet {
+        let mut upstreams = self.upstreams.lock();
+        let mut out = SequenceNumberSet::default();
+
+        let key = (upstream.to_string(), table_id);
+        let Some(u) = upstreams.get_mut(&key) else {
+            return out;
+        };
+
+        for n in persisted.iter() {
+            let Some(local) = u.local.remove(&n) else {
+                continue;
+            };
+            if let Some(pending) = u.pending.get_mut(&local) {
+                pending.remove(n);
+                if pending.is_empty() {
+                    u.pending.remove(&local);
+                    out.add(local);
+                }
+            }
+        }
+
+        if u.local.is_empty() {
+            upstreams.remove(&key);
+        }
+
+        out
+    }
+
+    /// Remove the mappings of the writes to `table_id` with the local
+    /// [`SequenceNumber`] values in `set`, such as once this ingester has
+    /// persisted them itself.
+    pub(crate) fn forget(&self, table_id: TableId, set: &SequenceNumberSet) {
+        let mut upstreams = self.upstreams.lock();
+
+        upstreams.retain(|(_, t), u| {
+            if *t != table_id {
+                return true;
+            }
+            for local in set.iter() {
+                if let Some(pending) = u.pending.remove(&local) {
+                    for n in pending.iter() {
+                        u.local.remove(&n);
+                    }
+                }
+            }
+            !u.local.is_empty()
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TABLE_ID: TableId = TableId::new(1);
+
+    fn set(values: &[i64]) -> SequenceNumberSet {
+        values.iter().map(|v| SequenceNumber::new(*v)).collect()
+    }
+
+    #[test]
+    fn test_mark_persisted() {
+        let map = ReplicaSequenceMap::default();
+
+        map.insert("a", TABLE_ID, &set(&[1]), SequenceNumber::new(10));
+        map.insert("a", TABLE_ID, &set(&[2, 3]), SequenceNumber::new(11));
+
+        // The same upstream sequence numbers from another upstream ingester
+        // map to different local writes.
+        map.insert("b", TABLE_ID, &set(&[1]), SequenceNumber::new(12));
+
+        // Unknown upstream ingesters and sequence numbers are ignored.
+        assert!(map.mark_persisted("c", TABLE_ID, &set(&[1])).is_empty());
+        assert!(map.mark_persisted("a", TABLE_ID, &set(&[42])).is_empty());
+
+        // A local write is only persisted once all of the upstream writes it
+        // contains are.
+        assert_eq!(map.mark_persisted("a", TABLE_ID, &set(&[1, 2])), set(&[10]));
+        assert_eq!(map.mark_persisted("a", TABLE_ID, &set(&[3])), set(&[11]));
+
+        // Reporting a value again has no effect.
+        assert!(map.mark_persisted("a", TABLE_ID, &set(&[1])).is_empty());
+
+        assert_eq!(map.mark_persisted("b", TABLE_ID, &set(&[1])), set(&[12]));
+        assert!(map.upstreams.lock().is_empty());
+    }
+
+    #[test]
+    fn test_mark_persisted_per_table() {
+        let map = ReplicaSequenceMap::default();
+        let other = TableId::new(2);
+
+        // A single write to two tables.
+        map.insert("a", TABLE_ID, &set(&[1]), SequenceNumber::new(10));
+        map.insert("a", other, &set(&[1]), SequenceNumber::new(10));
+
+        // Each table is persisted, and reported, independently.
+        assert_eq!(map.mark_persisted("a", TABLE_ID, &set(&[1])), set(&[10]));
+        assert_eq!(map.mark_persisted("a", other, &set(&[1])), set(&[10]));
+    }
+
+    #[test]
+    fn test_forget() {
+        let map = ReplicaSequenceMap::default();
+
+        map.insert("a", TABLE_ID, &set(&[1, 2]), SequenceNumber::new(10));
+        map.insert("a", TABLE_ID, &set(&[3]), SequenceNumber::new(11));
+        map.insert("b", TABLE_ID, &set(&[1]), SequenceNumber::new(12));
+
+        // The same local write to another table.
+        let other = TableId::new(2);
+        map.insert("a", other, &set(&[1]), SequenceNumber::new(10));
+
+        map.forget(TABLE_ID, &set(&[10, 12]));
+
+        assert!(map.mark_persisted("a", TABLE_ID, &set(&[1, 2])).is_empty());
+        assert!(map.mark_persisted("b", TABLE_ID, &set(&[1])).is_empty());
+        assert_eq!(map.mark_persisted("a", TABLE_ID, &set(&[3])), set(&[11]));
+        assert_eq!(map.mark_persisted("a", other, &set(&[1])), set(&[10]));
+        assert!(map.upstreams.lock().is_empty());
+    }
+}
@@ -0,0 +1,235 @@
+//! A [`DmlSink`] decorator that replicates applied writes to replica
+//! ingesters.
+
+use async_trait::async_trait;
+use dml::DmlOperation;
+use futures::future::try_join_all;
+use generated_types::influxdata::iox::ingester::v1::ReplicateRequest;
+use mutable_batch_pb::encode::encode_write;
+
+use super::client::ReplicationClient;
+use crate::{
+    dml_sink::{DmlError, DmlSink},
+    ingester_id::IngesterId,
+};
+
+/// A [`DmlSink`] decorator that pushes each [`DmlOperation`] successfully
+/// applied to the inner [`DmlSink`] to every configured replica.
+///
+/// Only sequenced writes can be replicated - any other [`DmlOperation`] is
+/// rejected with an error before it is applied to the inner [`DmlSink`].
+///
+/// The write is only acknowledged once all replicas have applied it - a
+/// failure to replicate the write to any replica returns an error to the
+/// caller, even though the write has been applied locally. Retrying such a
+/// write is safe, as the duplicate rows are deduplicated at query & persist
+/// time.
+///
+/// If no replicas are configured, this decorator is a no-op.
+#[derive(Debug)]
+pub(crate) struct ReplicationSink<T, C> {
+    inner: T,
+    replicas: Vec<C>,
+    ingester_id: IngesterId,
+}
+
+impl<T, C> ReplicationSink<T, C> {
+    /// Initialise a new [`ReplicationSink`] that replicates writes applied to
+    /// `inner` to all of `replicas`, identifying itself as `ingester_id`.
+    pub(crate) fn new(inner: T, replicas: Vec<C>, ingester_id: IngesterId) -> Self {
+        Self {
+            inner,
+            replicas,
+            ingester_id,
+        }
+    }
+}
+
+#[async_trait]
+impl<T, C> DmlSink for ReplicationSink<T, C>
+where
+    T: DmlSink,
+    C: ReplicationClient,
+{
+    type Error = DmlError;
+
+    async fn apply(&self, op: DmlOperation) -> Result<(), Self::Error> {
+        if self.replicas.is_empty() {
+            return self.inner.apply(op).await.map_err(Into::into);
+        }
+
+        // Serialise the op before handing ownership of it to the inner sink,
+        // rejecting any op that cannot be replicated before it is applied.
+        let sequence_number = op
+            .meta()
+            .sequence()
+            .ok_or_else(|| DmlError::Replication("cannot replicate unsequenced op".to_string()))?;
+        let payload = match &op {
+            DmlOperation::Write(w) => encode_write(op.namespace_id().get(), w),
+            DmlOperation::Delete(_) => {
+                return Err(DmlError::Replication(
+                    "cannot replicate delete op".to_string(),
+                ))
+            }
+        };
+        let req = ReplicateRequest {
+            ingester_uuid: self.ingester_id.to_string(),
+            sequence_number: sequence_number.get(),
+            payload: Some(payload),
+        };
+
+        self.inner.apply(op).await.map_err(Into::into)?;
+
+        try_join_all(self.replicas.iter().map(|r| r.replicate(req.clone())))
+            .await
+            .map_err(|e| DmlError::Replication(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use assert_matches::assert_matches;
+    use data_types::{NamespaceId, PartitionKey, TableId};
+    use dml::{DmlMeta, DmlWrite};
+    use mutable_batch_lp::test_helpers::lp_to_mutable_batch;
+    use mutable_batch_pb::decode::decode_database_batch;
+
+    use super::*;
+    use crate::{
+        dml_sink::mock_sink::MockDmlSink, replication::client::mock::MockReplicationClient,
+        test_util::make_write_op,
+    };
+
+    const NAMESPACE_ID: NamespaceId = NamespaceId::new(42);
+    const TABLE_ID: TableId = TableId::new(24);
+    const TABLE_NAME: &str = "bananas";
+
+    fn arbitrary_op() -> DmlOperation {
+        DmlOperation::Write(make_write_op(
+            &PartitionKey::from("p1"),
+            NAMESPACE_ID,
+            TABLE_NAME,
+            TABLE_ID,
+            12,
+            r#"bananas,region=Madrid temp=35 4242424242"#,
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_replicate() {
+        let inner = Arc::new(MockDmlSink::default().with_apply_return([Ok(())]));
+        let replicas = vec![
+            Arc::new(MockReplicationClient::default()),
+            Arc::new(MockReplicationClient::default()),
+        ];
+        let ingester_id = IngesterId::new();
+
+        let sink = ReplicationSink::new(Arc::clone(&inner), replicas.clone(), ingester_id);
+        sink.apply(arbitrary_op())
+            .await
+            .expect("replication should succeed");
+
+        assert_eq!(inner.get_calls().len(), 1);
+
+        for replica in &replicas {
+            let calls = replica.replicate_calls();
+            assert_matches!(calls.as_slice(), [req] => {
+                assert_eq!(req.ingester_uuid, ingester_id.to_string());
+                assert_eq!(req.sequence_number, 12);
+
+                let payload = req.payload.as_ref().expect("must contain payload");
+                assert_eq!(payload.database_id, NAMESPACE_ID.get());
+                assert_eq!(payload.partition_key, "p1");
+
+                let batches = decode_database_batch(payload).expect("valid payload");
+                assert_eq!(batches[&TABLE_ID.get()].rows(), 1);
+            });
+        }
+    }
+
+    #[tokio::test]
+    async fn test_inner_error_not_replicated() {
+        let inner = Arc::new(
+            MockDmlSink::default().with_apply_return([Err(DmlError::Wal("bananas".to_string()))]),
+        );
+        let replica = Arc::new(MockReplicationClient::default());
+
+        let sink = ReplicationSink::new(inner, vec![Arc::clone(&replica)], IngesterId::new());
+        let err = sink
+            .apply(arbitrary_op())
+            .await
+            .expect_err("inner error should be returned");
+
+        assert_matches!(err, DmlError::Wal(_));
+        assert!(replica.replicate_calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_replica_error() {
+        let inner = Arc::new(MockDmlSink::default().with_apply_return([Ok(())]));
+        let replica = Arc::new(
+            MockReplicationClient::default()
+                .with_ret([Err(tonic::Status::unavailable("replica down"))]),
+        );
+
+        let sink = ReplicationSink::new(Arc::clone(&inner), vec![replica], IngesterId::new());
+        let err = sink
+            .apply(arbitrary_op())
+            .await
+            .expect_err("replication error should be returned");
+
+        assert_matches!(err, DmlError::Replication(e) => {
+            assert!(e.contains("replica down"));
+        });
+        assert_eq!(inner.get_calls().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_unsequenced_op_rejected() {
+        let inner = Arc::new(MockDmlSink::default().with_apply_return([Ok(())]));
+        let replica = Arc::new(MockReplicationClient::default());
+
+        let op = DmlOperation::Write(DmlWrite::new(
+            NAMESPACE_ID,
+            [(TABLE_ID, lp_to_mutable_batch("bananas temp=35 42").1)]
+                .into_iter()
+                .collect(),
+            PartitionKey::from("p1"),
+            DmlMeta::unsequenced(None),
+        ));
+
+        let sink = ReplicationSink::new(
+            Arc::clone(&inner),
+            vec![Arc::clone(&replica)],
+            IngesterId::new(),
+        );
+        let err = sink
+            .apply(op)
+            .await
+            .expect_err("unsequenced op should be rejected");
+
+        assert_matches!(err, DmlError::Replication(_));
+        assert!(inner.get_calls().is_empty());
+        assert!(replica.replicate_calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_no_replicas() {
+        let inner = Arc::new(MockDmlSink::default().with_apply_return([Ok(())]));
+
+        let sink = ReplicationSink::<_, Arc<MockReplicationClient>>::new(
+            Arc::clone(&inner),
+            vec![],
+            IngesterId::new(),
+        );
+        sink.apply(arbitrary_op())
+            .await
+            .expect("write should succeed");
+
+        assert_eq!(inner.get_calls().len(), 1);
+    }
+}
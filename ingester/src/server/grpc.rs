@@ -1,7 +1,10 @@
 //! gRPC service implementations for `ingester`.
 
+mod partition_buffer;
 mod persist;
 mod query;
+mod replication;
+mod rpc_delete;
 mod rpc_write;
 
 use std::{fmt::Debug, sync::Arc};
@@ -17,10 +20,14 @@ use crate::{
     partition_iter::PartitionIter,
     persist::queue::PersistQueue,
     query::{response::QueryResponse, QueryExec},
+    replication::sequence_map::ReplicaSequenceMap,
     timestamp_oracle::TimestampOracle,
 };
 
-use self::{persist::PersistHandler, rpc_write::RpcWrite};
+use self::{
+    partition_buffer::PartitionBufferHandler, persist::PersistHandler,
+    replication::ReplicationHandler, rpc_delete::RpcDelete, rpc_write::RpcWrite,
+};
 
 /// This type is responsible for injecting internal dependencies that SHOULD NOT
 /// leak outside of the ingester crate into public gRPC handlers.
@@ -28,8 +35,10 @@ use self::{persist::PersistHandler, rpc_write::RpcWrite};
 /// Configuration and external dependencies SHOULD be injected through the
 /// respective gRPC handler constructor method.
 #[derive(Debug)]
-pub(crate) struct GrpcDelegate<D, Q, T, P> {
+pub(crate) struct GrpcDelegate<D, R, Q, T, P> {
     dml_sink: Arc<D>,
+    replica_sink: Arc<R>,
+    replica_sequence_map: Arc<ReplicaSequenceMap>,
     query_exec: Arc<Q>,
     timestamp: Arc<TimestampOracle>,
     ingest_state: Arc<IngestState>,
@@ -40,17 +49,24 @@ pub(crate) struct GrpcDelegate<D, Q, T, P> {
     persist_handle: Arc<P>,
 }
 
-impl<D, Q, T, P> GrpcDelegate<D, Q, T, P>
+impl<D, R, Q, T, P> GrpcDelegate<D, R, Q, T, P>
 where
     D: DmlSink + 'static,
+    R: DmlSink + 'static,
     Q: QueryExec<Response = QueryResponse> + 'static,
     T: PartitionIter + Sync + 'static,
     P: PersistQueue + Sync + 'static,
 {
     /// Initialise a new [`GrpcDelegate`].
+    ///
+    /// Writes received from the router are applied to `dml_sink`, while writes
+    /// replicated from another ingester are applied to `replica_sink` and
+    /// recorded in `replica_sequence_map`.
     #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         dml_sink: Arc<D>,
+        replica_sink: Arc<R>,
+        replica_sequence_map: Arc<ReplicaSequenceMap>,
         query_exec: Arc<Q>,
         timestamp: Arc<TimestampOracle>,
         ingest_state: Arc<IngestState>,
@@ -62,6 +78,8 @@ where
     ) -> Self {
         Self {
             dml_sink,
+            replica_sink,
+            replica_sequence_map,
             query_exec,
             timestamp,
             ingest_state,
@@ -76,17 +94,21 @@ where
 
 /// Implement the type-erasure trait to hide internal types from crate-external
 /// callers.
-impl<D, Q, T, P> IngesterRpcInterface for GrpcDelegate<D, Q, T, P>
+impl<D, R, Q, T, P> IngesterRpcInterface for GrpcDelegate<D, R, Q, T, P>
 where
     D: DmlSink + 'static,
+    R: DmlSink + 'static,
     Q: QueryExec<Response = QueryResponse> + 'static,
     T: PartitionIter + Sync + 'static,
     P: PersistQueue + Sync + 'static,
 {
     type CatalogHandler = CatalogService;
     type WriteHandler = RpcWrite<Arc<D>>;
+    type DeleteHandler = RpcDelete<Arc<D>>;
     type PersistHandler = PersistHandler<Arc<T>, Arc<P>>;
     type FlightHandler = query::FlightService<Arc<Q>>;
+    type PartitionBufferHandler = PartitionBufferHandler<Arc<T>>;
+    type ReplicationHandler = ReplicationHandler<Arc<R>, Arc<T>>;
 
     /// Acquire a [`CatalogService`] gRPC service implementation.
     ///
@@ -106,6 +128,17 @@ where
         )
     }
 
+    /// Return a [`DeleteService`] gRPC implementation.
+    ///
+    /// [`DeleteService`]: generated_types::influxdata::iox::delete::v1::delete_service_server::DeleteService.
+    fn delete_service(&self) -> Self::DeleteHandler {
+        RpcDelete::new(
+            Arc::clone(&self.dml_sink),
+            Arc::clone(&self.timestamp),
+            Arc::clone(&self.ingest_state),
+        )
+    }
+
     /// Return a [`PersistService`] gRPC implementation.
     ///
     /// [`PersistService`]: generated_types::influxdata::iox::ingester::v1::persist_service_server::PersistService.
@@ -117,6 +150,26 @@ where
         )
     }
 
+    /// Return a [`PartitionBufferService`] gRPC implementation.
+    ///
+    /// [`PartitionBufferService`]: generated_types::influxdata::iox::ingester::v1::partition_buffer_service_server::PartitionBufferService.
+    fn partition_buffer_service(&self) -> Self::PartitionBufferHandler {
+        PartitionBufferHandler::new(Arc::clone(&self.buffer), self.ingester_id)
+    }
+
+    /// Return a [`ReplicationService`] gRPC implementation.
+    ///
+    /// [`ReplicationService`]: generated_types::influxdata::iox::ingester::v1::replication_service_server::ReplicationService.
+    fn replication_service(&self) -> Self::ReplicationHandler {
+        ReplicationHandler::new(
+            Arc::clone(&self.replica_sink),
+            Arc::clone(&self.buffer),
+            Arc::clone(&self.timestamp),
+            Arc::clone(&self.replica_sequence_map),
+            Arc::clone(&self.ingest_state),
+        )
+    }
+
     /// Return an Arrow [`FlightService`] gRPC implementation.
     ///
     /// [`FlightService`]: arrow_flight::flight_service_server::FlightService
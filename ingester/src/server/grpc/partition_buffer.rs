@@ -0,0 +1,210 @@
+use std::pin::Pin;
+
+use futures::Stream;
+use generated_types::influxdata::{
+    iox::ingester::v1::{
+        partition_buffer_service_server::PartitionBufferService, GetPartitionBuffersRequest,
+        GetPartitionBuffersResponse,
+    },
+    pbdata::v1::DatabaseBatch,
+};
+use mutable_batch_pb::encode::encode_batch;
+use observability_deps::tracing::*;
+use parking_lot::Mutex;
+use tonic::{Request, Response};
+
+use crate::{
+    batch_conversion::to_mutable_batch, buffer_tree::partition::PartitionData,
+    ingester_id::IngesterId, partition_iter::PartitionIter,
+};
+
+/// A gRPC [`PartitionBufferService`] handler.
+///
+/// This handler streams a snapshot of the data buffered in each partition of
+/// this ingester, allowing a replica to initialise its own buffer from it.
+///
+/// Each partition is read atomically, but partitions are read one at a time as
+/// the caller consumes the response - writes applied concurrently to the
+/// request may or may not be included in the response.
+#[derive(Debug)]
+pub(crate) struct PartitionBufferHandler<T> {
+    buffer: T,
+    ingester_id: IngesterId,
+}
+
+impl<T> PartitionBufferHandler<T> {
+    pub(crate) fn new(buffer: T, ingester_id: IngesterId) -> Self {
+        Self {
+            buffer,
+            ingester_id,
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl<T> PartitionBufferService for PartitionBufferHandler<T>
+where
+    T: PartitionIter + Sync + 'static,
+{
+    type GetPartitionBuffersStream = Pin<
+        Box<dyn Stream<Item = Result<GetPartitionBuffersResponse, tonic::Status>> + Send + 'static>,
+    >;
+
+    /// Stream the buffered data of every partition in this ingester.
+    async fn get_partition_buffers(
+        &self,
+        _request: Request<GetPartitionBuffersRequest>,
+    ) -> Result<Response<Self::GetPartitionBuffersStream>, tonic::Status> {
+        let ingester_uuid = self.ingester_id.to_string();
+
+        // Partitions are serialised lazily, as the response is consumed.
+        let partitions = self
+            .buffer
+            .partition_iter()
+            .filter_map(move |p| snapshot_partition(&p, &ingester_uuid).transpose());
+
+        Ok(Response::new(Box::pin(futures::stream::iter(partitions))))
+    }
+}
+
+/// Serialise the data buffered in `partition`, returning [`None`] if it
+/// contains no data.
+fn snapshot_partition(
+    partition: &Mutex<PartitionData>,
+    ingester_uuid: &str,
+) -> Result<Option<GetPartitionBuffersResponse>, tonic::Status> {
+    // Read the data and the set of writes it contains under the same lock so
+    // that they describe the same snapshot of the partition.
+    let (namespace_id, table_id, partition_id, partition_key, data, sequence_numbers) = {
+        let mut p = partition.lock();
+        let data = match p.get_query_data() {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+        (
+            p.namespace_id(),
+            p.table_id(),
+            p.partition_id(),
+            p.partition_key().clone(),
+            data,
+            p.sequence_number_set(),
+        )
+    };
+
+    let batch = to_mutable_batch(data.record_batches()).map_err(|e| {
+        error!(
+            error=%e,
+            %namespace_id,
+            %table_id,
+            %partition_id,
+            "failed to serialise partition buffer"
+        );
+        tonic::Status::internal(e.to_string())
+    })?;
+
+    Ok(Some(GetPartitionBuffersResponse {
+        ingester_uuid: ingester_uuid.to_string(),
+        namespace_id: namespace_id.get(),
+        table_id: table_id.get(),
+        partition_id: partition_id.get(),
+        croaring_sequence_number_bitmap: sequence_numbers.to_bytes(),
+        payload: Some(DatabaseBatch {
+            database_id: namespace_id.get(),
+            partition_key: partition_key.to_string(),
+            table_batches: vec![encode_batch(table_id.get(), &batch)],
+        }),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_util::assert_batches_eq;
+    use assert_matches::assert_matches;
+    use data_types::{sequence_number_set::SequenceNumberSet, PartitionId, SequenceNumber};
+    use futures::TryStreamExt;
+    use mutable_batch_lp::test_helpers::lp_to_mutable_batch;
+    use mutable_batch_pb::decode::decode_database_batch;
+    use schema::Projection;
+
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::test_util::{
+        PartitionDataBuilder, ARBITRARY_NAMESPACE_ID, ARBITRARY_PARTITION_ID,
+        ARBITRARY_PARTITION_KEY, ARBITRARY_TABLE_ID,
+    };
+
+    #[tokio::test]
+    async fn test_get_partition_buffers() {
+        let mut p = PartitionDataBuilder::new().build();
+
+        // Write data to the partition and mark it as persisting, so that the
+        // snapshot contains both persisting and buffered data.
+        let mb =
+            lp_to_mutable_batch(r#"bananas,city=London people=2,pigeons="millions",good=true 10"#)
+                .1;
+        p.buffer_write(mb, SequenceNumber::new(1))
+            .expect("write should succeed");
+        let _persisting = p.mark_persisting().expect("must contain data");
+
+        let mb = lp_to_mutable_batch(
+            r#"bananas,city=Madrid,country=Spain people=4,count=5i,ucount=6u 20"#,
+        )
+        .1;
+        p.buffer_write(mb, SequenceNumber::new(2))
+            .expect("write should succeed");
+
+        // A partition without any data is not included in the response.
+        let empty = PartitionDataBuilder::new()
+            .with_partition_id(PartitionId::new(42))
+            .build();
+
+        let ingester_id = IngesterId::new();
+        let handler = PartitionBufferHandler::new(
+            vec![Arc::new(Mutex::new(p)), Arc::new(Mutex::new(empty))],
+            ingester_id,
+        );
+
+        let got = handler
+            .get_partition_buffers(Request::new(GetPartitionBuffersRequest {}))
+            .await
+            .expect("request should succeed")
+            .into_inner()
+            .try_collect::<Vec<_>>()
+            .await
+            .expect("stream should succeed");
+
+        assert_matches!(got.as_slice(), [resp] => {
+            assert_eq!(resp.ingester_uuid, ingester_id.to_string());
+            assert_eq!(resp.namespace_id, ARBITRARY_NAMESPACE_ID.get());
+            assert_eq!(resp.table_id, ARBITRARY_TABLE_ID.get());
+            assert_eq!(resp.partition_id, ARBITRARY_PARTITION_ID.get());
+            assert_eq!(
+                SequenceNumberSet::try_from(resp.croaring_sequence_number_bitmap.as_slice())
+                    .expect("valid bitmap"),
+                [1, 2].into_iter().map(SequenceNumber::new).collect::<SequenceNumberSet>()
+            );
+
+            let payload = resp.payload.as_ref().expect("must contain payload");
+            assert_eq!(payload.database_id, ARBITRARY_NAMESPACE_ID.get());
+            assert_eq!(payload.partition_key, ARBITRARY_PARTITION_KEY.to_string());
+
+            let batches = decode_database_batch(payload).expect("valid payload");
+            let batch = batches[&ARBITRARY_TABLE_ID.get()]
+                .to_arrow(Projection::All)
+                .expect("valid batch");
+
+            assert_batches_eq!(
+                [
+                    "+--------+-------+---------+------+--------+----------+--------------------------------+--------+",
+                    "| city   | count | country | good | people | pigeons  | time                           | ucount |",
+                    "+--------+-------+---------+------+--------+----------+--------------------------------+--------+",
+                    "| London |       |         | true | 2.0    | millions | 1970-01-01T00:00:00.000000010Z |        |",
+                    "| Madrid | 5     | Spain   |      | 4.0    |          | 1970-01-01T00:00:00.000000020Z | 6      |",
+                    "+--------+-------+---------+------+--------+----------+--------------------------------+--------+",
+                ],
+                &[batch]
+            );
+        });
+    }
+}
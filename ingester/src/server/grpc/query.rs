@@ -9,9 +9,10 @@ use arrow_flight::{
 use data_types::{NamespaceId, PartitionId, TableId};
 use flatbuffers::FlatBufferBuilder;
 use futures::{Stream, StreamExt, TryStreamExt};
-use generated_types::influxdata::iox::ingester::v1 as proto;
+use generated_types::{google::FieldViolation, influxdata::iox::ingester::v1 as proto};
 use metric::U64Counter;
 use observability_deps::tracing::*;
+use predicate::Predicate;
 use prost::Message;
 use thiserror::Error;
 use tokio::sync::{Semaphore, TryAcquireError};
@@ -39,6 +40,11 @@ enum Error {
     #[error("invalid flight ticket: {0}")]
     InvalidTicket(#[from] prost::DecodeError),
 
+    /// The [`proto::Predicate`] within the query request cannot be converted
+    /// into a [`Predicate`].
+    #[error("invalid query predicate: {0}")]
+    InvalidPredicate(#[from] FieldViolation),
+
     /// The number of simultaneous queries being executed has been reached.
     #[error("simultaneous query limit exceeded")]
     RequestLimit,
@@ -67,6 +73,10 @@ impl From<Error> for tonic::Status {
                 debug!(error=%e, "invalid flight query ticket");
                 Code::InvalidArgument
             }
+            Error::InvalidPredicate(_) => {
+                debug!(error=%e, "invalid flight query predicate");
+                Code::InvalidArgument
+            }
             Error::RequestLimit => {
                 warn!("simultaneous query limit exceeded");
                 Code::ResourceExhausted
@@ -172,14 +182,16 @@ where
         let namespace_id = NamespaceId::new(request.namespace_id);
         let table_id = TableId::new(request.table_id);
 
-        // Predicate pushdown is part of the API, but not implemented.
-        if let Some(p) = request.predicate {
-            debug!(predicate=?p, "ignoring query predicate (unsupported)");
-        }
+        // Decode the predicate used to prune and filter the buffered data.
+        let predicate = request
+            .predicate
+            .map(Predicate::try_from)
+            .transpose()
+            .map_err(Error::from)?;
 
         let response = match self
             .query_handler
-            .query_exec(namespace_id, table_id, request.columns, span)
+            .query_exec(namespace_id, table_id, request.columns, predicate, span)
             .await
         {
             Ok(v) => v,
@@ -363,4 +375,35 @@ mod tests {
             }
         }
     }
+
+    #[tokio::test]
+    async fn invalid_predicate() {
+        let flight = FlightService::new(
+            MockQueryExec::default(),
+            IngesterId::new(),
+            100,
+            &metric::Registry::default(),
+        );
+
+        // A predicate containing an expression that cannot be decoded.
+        let request = proto::IngesterQueryRequest {
+            namespace_id: 42,
+            table_id: 24,
+            columns: vec![],
+            predicate: Some(proto::Predicate {
+                exprs: vec![b"bananas".to_vec()],
+                ..Default::default()
+            }),
+        };
+
+        let req = tonic::Request::new(Ticket {
+            ticket: request.encode_to_vec().into(),
+        });
+        match flight.do_get(req).await {
+            Ok(_) => panic!("expected error because of invalid predicate"),
+            Err(s) => {
+                assert_eq!(s.code(), Code::InvalidArgument);
+            }
+        }
+    }
 }
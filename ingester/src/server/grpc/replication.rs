@@ -0,0 +1,504 @@
+use std::{collections::HashMap, sync::Arc};
+
+use data_types::{
+    sequence_number_set::SequenceNumberSet, NamespaceId, PartitionId, PartitionKey, SequenceNumber,
+    TableId,
+};
+use dml::{DmlMeta, DmlOperation, DmlWrite};
+use generated_types::influxdata::iox::ingester::v1::{
+    self as proto, replication_service_server::ReplicationService,
+};
+use mutable_batch_pb::decode::decode_database_batch;
+use observability_deps::tracing::*;
+use thiserror::Error;
+use tonic::{Code, Request, Response};
+
+use crate::{
+    dml_sink::{DmlError, DmlSink},
+    ingest_state::{IngestState, IngestStateError},
+    partition_iter::PartitionIter,
+    replication::sequence_map::ReplicaSequenceMap,
+    timestamp_oracle::TimestampOracle,
+};
+
+/// Errors returned when handling a replication request.
+#[derive(Debug, Error)]
+enum ReplicationError {
+    /// The replicate request did not contain a write payload.
+    #[error("replicate request does not contain a payload")]
+    NoPayload,
+
+    /// The write payload contains no tables.
+    #[error("replicate request does not contain any table data")]
+    NoTables,
+
+    /// The serialised write payload could not be read.
+    #[error(transparent)]
+    Decode(mutable_batch_pb::decode::Error),
+
+    /// The serialised set of persisted sequence numbers could not be read.
+    #[error("{0}")]
+    SequenceNumberSet(String),
+
+    /// The ingester's [`IngestState`] returns [`IngestStateError`] instances if
+    /// set by a subsystem. See [`IngestState`] for documentation.
+    #[error(transparent)]
+    SystemState(IngestStateError),
+}
+
+impl From<ReplicationError> for tonic::Status {
+    fn from(e: ReplicationError) -> Self {
+        let code = match e {
+            ReplicationError::Decode(_)
+            | ReplicationError::NoPayload
+            | ReplicationError::NoTables
+            | ReplicationError::SequenceNumberSet(_) => Code::InvalidArgument,
+            ReplicationError::SystemState(IngestStateError::PersistSaturated) => {
+                Code::ResourceExhausted
+            }
+            ReplicationError::SystemState(IngestStateError::GracefulStop) => {
+                Code::FailedPrecondition
+            }
+        };
+
+        Self::new(code, e.to_string())
+    }
+}
+
+/// A gRPC [`ReplicationService`] handler.
+///
+/// This handler applies writes replicated from an upstream ingester to the
+/// provided [`DmlSink`] with a [`SequenceNumber`] from the local
+/// [`TimestampOracle`], recording the [`SequenceNumber`] assigned by the
+/// upstream ingester in the [`ReplicaSequenceMap`]. Persist notifications from
+/// the upstream ingester are resolved to the local [`SequenceNumber`] of the
+/// writes they refer to, causing the corresponding buffered data to be
+/// discarded, see [`PartitionData::mark_replica_persisted()`].
+///
+/// [`PartitionData::mark_replica_persisted()`]:
+///     crate::buffer_tree::partition::PartitionData::mark_replica_persisted()
+#[derive(Debug)]
+pub(crate) struct ReplicationHandler<D, T> {
+    sink: D,
+    buffer: T,
+    timestamp: Arc<TimestampOracle>,
+    sequence_map: Arc<ReplicaSequenceMap>,
+    ingest_state: Arc<IngestState>,
+}
+
+impl<D, T> ReplicationHandler<D, T> {
+    /// Instantiate a new [`ReplicationHandler`] that applies replicated writes
+    /// to `sink`, and discards persisted data from the partitions in `buffer`.
+    pub(crate) fn new(
+        sink: D,
+        buffer: T,
+        timestamp: Arc<TimestampOracle>,
+        sequence_map: Arc<ReplicaSequenceMap>,
+        ingest_state: Arc<IngestState>,
+    ) -> Self {
+        Self {
+            sink,
+            buffer,
+            timestamp,
+            sequence_map,
+            ingest_state,
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl<D, T> ReplicationService for ReplicationHandler<D, T>
+where
+    D: DmlSink + 'static,
+    T: PartitionIter + Sync + 'static,
+{
+    /// Apply a write replicated from the upstream ingester.
+    async fn replicate(
+        &self,
+        request: Request<proto::ReplicateRequest>,
+    ) -> Result<Response<proto::ReplicateResponse>, tonic::Status> {
+        // Replicated writes are subject to the same backpressure as writes
+        // from the router.
+        self.ingest_state
+            .read()
+            .map_err(ReplicationError::SystemState)?;
+
+        let request = request.into_inner();
+        let payload = request.payload.ok_or(ReplicationError::NoPayload)?;
+
+        let batches = decode_database_batch(&payload).map_err(ReplicationError::Decode)?;
+        let num_tables = batches.len();
+        let namespace_id = NamespaceId::new(payload.database_id);
+        let partition_key = PartitionKey::from(payload.partition_key);
+        let upstream_sequence_number = SequenceNumber::new(request.sequence_number);
+
+        // Never attempt to create a DmlWrite with no tables - doing so causes a
+        // panic.
+        if num_tables == 0 {
+            return Err(ReplicationError::NoTables)?;
+        }
+
+        trace!(
+            upstream_ingester = %request.ingester_uuid,
+            num_tables,
+            %namespace_id,
+            %partition_key,
+            upstream_sequence_number = upstream_sequence_number.get(),
+            "received replicated write"
+        );
+
+        // Sequence numbers assigned by the upstream ingester may collide with
+        // those assigned locally, so the write is applied with a local one.
+        let sequence_number = self.timestamp.next();
+
+        let batches = batches
+            .into_iter()
+            .map(|(k, v)| (TableId::new(k), v))
+            .collect::<HashMap<_, _>>();
+        let table_ids = batches.keys().copied().collect::<Vec<_>>();
+
+        let op = DmlWrite::new(
+            namespace_id,
+            batches,
+            partition_key,
+            DmlMeta::sequenced(
+                sequence_number,
+                iox_time::Time::MAX, // TODO: remove this from DmlMeta
+                None,
+                42, // TODO: remove this from DmlMeta
+            ),
+        );
+
+        if let Err(e) = self.sink.apply(DmlOperation::Write(op)).await {
+            let e: DmlError = e.into();
+            error!(error=%e, "failed to apply replicated DML op");
+            return Err(e.into());
+        }
+
+        let upstream_set = SequenceNumberSet::from_iter([upstream_sequence_number]);
+        for table_id in table_ids {
+            self.sequence_map.insert(
+                &request.ingester_uuid,
+                table_id,
+                &upstream_set,
+                sequence_number,
+            );
+        }
+
+        Ok(Response::new(proto::ReplicateResponse {}))
+    }
+
+    /// Discard buffered data persisted by the upstream ingester.
+    async fn persist_complete(
+        &self,
+        request: Request<proto::PersistCompleteRequest>,
+    ) -> Result<Response<proto::PersistCompleteResponse>, tonic::Status> {
+        let request = request.into_inner();
+
+        let persisted =
+            SequenceNumberSet::try_from(request.croaring_sequence_number_bitmap.as_slice())
+                .map_err(ReplicationError::SequenceNumberSet)?;
+        let namespace_id = NamespaceId::new(request.namespace_id);
+        let table_id = TableId::new(request.table_id);
+        let partition_id = PartitionId::new(request.partition_id);
+
+        // Resolve the writes of this upstream ingester to the local writes
+        // that are now entirely persisted.
+        let local = self
+            .sequence_map
+            .mark_persisted(&request.ingester_uuid, table_id, &persisted);
+
+        let partition = self.buffer.partition_iter().find(|p| {
+            let p = p.lock();
+            p.partition_id() == partition_id
+                && p.table_id() == table_id
+                && p.namespace_id() == namespace_id
+        });
+
+        let discarded = partition
+            .map(|p| p.lock().mark_replica_persisted(&local))
+            .unwrap_or_default();
+
+        debug!(
+            upstream_ingester = %request.ingester_uuid,
+            %namespace_id,
+            %table_id,
+            %partition_id,
+            n_writes = persisted.len(),
+            discarded,
+            "received persist notification"
+        );
+
+        Ok(Response::new(proto::PersistCompleteResponse {}))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use mutable_batch_lp::test_helpers::lp_to_mutable_batch;
+    use mutable_batch_pb::encode::encode_write;
+    use parking_lot::Mutex;
+
+    use super::*;
+    use crate::{
+        buffer_tree::partition::PartitionData,
+        dml_sink::mock_sink::MockDmlSink,
+        test_util::{
+            make_write_op, PartitionDataBuilder, ARBITRARY_NAMESPACE_ID, ARBITRARY_PARTITION_ID,
+            ARBITRARY_PARTITION_KEY, ARBITRARY_TABLE_ID,
+        },
+    };
+
+    fn replicate_request(sequence_number: i64) -> proto::ReplicateRequest {
+        let op = make_write_op(
+            &ARBITRARY_PARTITION_KEY,
+            ARBITRARY_NAMESPACE_ID,
+            "bananas",
+            ARBITRARY_TABLE_ID,
+            0,
+            "bananas,city=London people=2 10",
+        );
+
+        proto::ReplicateRequest {
+            ingester_uuid: "upstream".to_string(),
+            sequence_number,
+            payload: Some(encode_write(ARBITRARY_NAMESPACE_ID.get(), &op)),
+        }
+    }
+
+    fn new_handler(
+        sink: Arc<MockDmlSink>,
+        partitions: Vec<Arc<Mutex<PartitionData>>>,
+        sequence_map: Arc<ReplicaSequenceMap>,
+    ) -> (
+        ReplicationHandler<Arc<MockDmlSink>, Vec<Arc<Mutex<PartitionData>>>>,
+        Arc<IngestState>,
+    ) {
+        let ingest_state = Arc::new(IngestState::default());
+        let handler = ReplicationHandler::new(
+            sink,
+            partitions,
+            Arc::new(TimestampOracle::new(0)),
+            sequence_map,
+            Arc::clone(&ingest_state),
+        );
+        (handler, ingest_state)
+    }
+
+    fn set(values: &[i64]) -> SequenceNumberSet {
+        values.iter().map(|v| SequenceNumber::new(*v)).collect()
+    }
+
+    #[tokio::test]
+    async fn test_replicate() {
+        let sink = Arc::new(MockDmlSink::default().with_apply_return([Ok(()), Ok(())]));
+        let sequence_map = Arc::new(ReplicaSequenceMap::default());
+        let (handler, _) = new_handler(Arc::clone(&sink), vec![], Arc::clone(&sequence_map));
+
+        handler
+            .replicate(Request::new(replicate_request(42)))
+            .await
+            .expect("replicate should succeed");
+
+        // The same upstream sequence number from another upstream ingester is
+        // a different write.
+        let mut request = replicate_request(42);
+        request.ingester_uuid = "other".to_string();
+        handler
+            .replicate(Request::new(request))
+            .await
+            .expect("replicate should succeed");
+
+        // The writes are applied with local sequence numbers.
+        assert_matches!(
+            sink.get_calls().as_slice(),
+            [DmlOperation::Write(w1), DmlOperation::Write(w2)] => {
+                assert_eq!(w1.namespace_id(), ARBITRARY_NAMESPACE_ID);
+                assert_eq!(w1.table_count(), 1);
+                assert_eq!(*w1.partition_key(), *ARBITRARY_PARTITION_KEY);
+                assert_eq!(w1.meta().sequence(), Some(SequenceNumber::new(1)));
+                assert_eq!(w2.meta().sequence(), Some(SequenceNumber::new(2)));
+            }
+        );
+
+        // And mapped from the upstream sequence numbers.
+        assert_eq!(
+            sequence_map.mark_persisted("upstream", ARBITRARY_TABLE_ID, &set(&[42])),
+            set(&[1])
+        );
+        assert_eq!(
+            sequence_map.mark_persisted("other", ARBITRARY_TABLE_ID, &set(&[42])),
+            set(&[2])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_replicate_errors() {
+        let sink = Arc::new(
+            MockDmlSink::default().with_apply_return([Err(DmlError::Wal("broken".to_string()))]),
+        );
+        let sequence_map = Arc::new(ReplicaSequenceMap::default());
+        let (handler, ingest_state) =
+            new_handler(Arc::clone(&sink), vec![], Arc::clone(&sequence_map));
+
+        let err = handler
+            .replicate(Request::new(proto::ReplicateRequest {
+                ingester_uuid: "upstream".to_string(),
+                sequence_number: 1,
+                payload: None,
+            }))
+            .await
+            .expect_err("request without payload should fail");
+        assert_eq!(err.code(), Code::InvalidArgument);
+
+        let err = handler
+            .replicate(Request::new(replicate_request(1)))
+            .await
+            .expect_err("sink error should be returned");
+        assert_eq!(err.code(), Code::Internal);
+
+        ingest_state.set(IngestStateError::PersistSaturated);
+        let err = handler
+            .replicate(Request::new(replicate_request(2)))
+            .await
+            .expect_err("write should be rejected");
+        assert_eq!(err.code(), Code::ResourceExhausted);
+
+        // Only the write that passed validation reached the sink, and as it
+        // was not applied it is not mapped.
+        assert_eq!(sink.get_calls().len(), 1);
+        assert!(sequence_map
+            .mark_persisted("upstream", ARBITRARY_TABLE_ID, &set(&[1]))
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_persist_complete() {
+        // Two writes replicated from different upstream ingesters that both
+        // assigned them the upstream sequence number 1.
+        let sequence_map = Arc::new(ReplicaSequenceMap::default());
+        sequence_map.insert(
+            "upstream",
+            ARBITRARY_TABLE_ID,
+            &set(&[1]),
+            SequenceNumber::new(1),
+        );
+        sequence_map.insert(
+            "other",
+            ARBITRARY_TABLE_ID,
+            &set(&[1]),
+            SequenceNumber::new(2),
+        );
+
+        let mut p = PartitionDataBuilder::new().build();
+        for (n, lp) in [
+            (1, "bananas,city=London people=2 10"),
+            (2, "bananas,city=Madrid people=4 20"),
+        ] {
+            p.buffer_write(lp_to_mutable_batch(lp).1, SequenceNumber::new(n))
+                .expect("write should succeed");
+        }
+        let p = Arc::new(Mutex::new(p));
+
+        let (handler, _) = new_handler(
+            Arc::new(MockDmlSink::default()),
+            vec![Arc::clone(&p)],
+            sequence_map,
+        );
+
+        let request = |upstream: &str, partition_id: PartitionId, sequence_numbers: &[i64]| {
+            Request::new(proto::PersistCompleteRequest {
+                ingester_uuid: upstream.to_string(),
+                namespace_id: ARBITRARY_NAMESPACE_ID.get(),
+                table_id: ARBITRARY_TABLE_ID.get(),
+                partition_id: partition_id.get(),
+                croaring_sequence_number_bitmap: set(sequence_numbers).to_bytes(),
+            })
+        };
+
+        // A notification for an unknown partition is ignored.
+        handler
+            .persist_complete(request("upstream", PartitionId::new(1234), &[1]))
+            .await
+            .expect("notification should succeed");
+        assert!(p.lock().get_query_data().is_some());
+
+        // A notification for sequence numbers this ingester did not receive
+        // from the upstream ingester is ignored.
+        handler
+            .persist_complete(request("unknown", ARBITRARY_PARTITION_ID, &[1, 2]))
+            .await
+            .expect("notification should succeed");
+        assert!(p.lock().get_query_data().is_some());
+
+        // Persisting part of the buffer does not discard it.
+        handler
+            .persist_complete(request("other", ARBITRARY_PARTITION_ID, &[1]))
+            .await
+            .expect("notification should succeed");
+        assert!(p.lock().get_query_data().is_some());
+
+        handler
+            .persist_complete(request("upstream", ARBITRARY_PARTITION_ID, &[1]))
+            .await
+            .expect("notification should succeed");
+        assert!(p.lock().get_query_data().is_none());
+
+        let err = handler
+            .persist_complete(Request::new(proto::PersistCompleteRequest {
+                ingester_uuid: "upstream".to_string(),
+                namespace_id: ARBITRARY_NAMESPACE_ID.get(),
+                table_id: ARBITRARY_TABLE_ID.get(),
+                partition_id: ARBITRARY_PARTITION_ID.get(),
+                croaring_sequence_number_bitmap: b"bananas".to_vec(),
+            }))
+            .await
+            .expect_err("invalid bitmap should be rejected");
+        assert_eq!(err.code(), Code::InvalidArgument);
+    }
+
+    // Ensure a persist notification from an upstream ingester never discards
+    // data written to this ingester directly.
+    #[tokio::test]
+    async fn test_persist_complete_retains_local_writes() {
+        let sequence_map = Arc::new(ReplicaSequenceMap::default());
+        sequence_map.insert(
+            "upstream",
+            ARBITRARY_TABLE_ID,
+            &set(&[1]),
+            SequenceNumber::new(1),
+        );
+
+        // Local sequence number 2 is a write received from the router, with
+        // the same value as a write of the upstream ingester.
+        let mut p = PartitionDataBuilder::new().build();
+        for (n, lp) in [
+            (1, "bananas,city=London people=2 10"),
+            (2, "bananas,city=Madrid people=4 20"),
+        ] {
+            p.buffer_write(lp_to_mutable_batch(lp).1, SequenceNumber::new(n))
+                .expect("write should succeed");
+        }
+        let p = Arc::new(Mutex::new(p));
+
+        let (handler, _) = new_handler(
+            Arc::new(MockDmlSink::default()),
+            vec![Arc::clone(&p)],
+            sequence_map,
+        );
+
+        handler
+            .persist_complete(Request::new(proto::PersistCompleteRequest {
+                ingester_uuid: "upstream".to_string(),
+                namespace_id: ARBITRARY_NAMESPACE_ID.get(),
+                table_id: ARBITRARY_TABLE_ID.get(),
+                partition_id: ARBITRARY_PARTITION_ID.get(),
+                croaring_sequence_number_bitmap: set(&[1, 2]).to_bytes(),
+            }))
+            .await
+            .expect("notification should succeed");
+
+        assert!(p.lock().get_query_data().is_some());
+    }
+}
@@ -0,0 +1,220 @@
+use std::sync::Arc;
+
+use data_types::{NamespaceId, NonEmptyString};
+use dml::{DmlDelete, DmlMeta, DmlOperation};
+use generated_types::{
+    google::{FieldViolation, FromOptionalField},
+    influxdata::iox::delete::v1::{self as proto, delete_service_server::DeleteService},
+};
+use observability_deps::tracing::*;
+use thiserror::Error;
+use tonic::{Code, Request, Response};
+
+use crate::{
+    dml_sink::DmlSink,
+    ingest_state::{IngestState, IngestStateError},
+    timestamp_oracle::TimestampOracle,
+};
+
+/// A list of error states when handling an RPC delete request.
+#[derive(Debug, Error)]
+enum RpcError {
+    /// The RPC delete request did not contain a delete payload.
+    #[error("rpc delete request does not contain a payload")]
+    NoPayload,
+
+    /// The delete predicate could not be read.
+    #[error(transparent)]
+    Predicate(FieldViolation),
+
+    /// The ingester's [`IngestState`] returns [`IngestStateError`] instances if
+    /// set by a subsystem. See [`IngestState`] for documentation.
+    #[error(transparent)]
+    SystemState(IngestStateError),
+}
+
+impl From<RpcError> for tonic::Status {
+    fn from(e: RpcError) -> Self {
+        let code = match e {
+            RpcError::NoPayload | RpcError::Predicate(_) => Code::InvalidArgument,
+            RpcError::SystemState(IngestStateError::PersistSaturated) => Code::ResourceExhausted,
+            RpcError::SystemState(IngestStateError::GracefulStop) => Code::FailedPrecondition,
+        };
+
+        Self::new(code, e.to_string())
+    }
+}
+
+/// A gRPC [`DeleteService`] handler.
+///
+/// This handler accepts deletes from an upstream, and applies them to the data
+/// buffered in the provided [`DmlSink`].
+#[derive(Debug)]
+pub(crate) struct RpcDelete<T> {
+    sink: T,
+    timestamp: Arc<TimestampOracle>,
+    ingest_state: Arc<IngestState>,
+}
+
+impl<T> RpcDelete<T> {
+    /// Instantiate a new [`RpcDelete`] that pushes [`DmlOperation`] instances
+    /// into `sink`.
+    pub(crate) fn new(
+        sink: T,
+        timestamp: Arc<TimestampOracle>,
+        ingest_state: Arc<IngestState>,
+    ) -> Self {
+        Self {
+            sink,
+            timestamp,
+            ingest_state,
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl<T> DeleteService for RpcDelete<T>
+where
+    T: DmlSink + 'static,
+{
+    /// Handle an RPC delete request.
+    async fn delete(
+        &self,
+        request: Request<proto::DeleteRequest>,
+    ) -> Result<Response<proto::DeleteResponse>, tonic::Status> {
+        // Deletes are rejected for the same reasons as writes - see the
+        // WriteService handler.
+        self.ingest_state.read().map_err(RpcError::SystemState)?;
+
+        let payload = request.into_inner().payload.ok_or(RpcError::NoPayload)?;
+
+        let namespace_id = NamespaceId::new(payload.database_id);
+        let predicate = payload
+            .predicate
+            .required("predicate")
+            .map_err(RpcError::Predicate)?;
+
+        // An empty table name applies the delete to all tables.
+        let table_name = NonEmptyString::new(payload.table_name);
+
+        debug!(%namespace_id, ?table_name, ?predicate, "received rpc delete");
+
+        let op = DmlDelete::new(
+            namespace_id,
+            predicate,
+            table_name,
+            DmlMeta::sequenced(
+                self.timestamp.next(),
+                iox_time::Time::MAX, // TODO: remove this from DmlMeta
+                None,
+                42, // TODO: remove this from DmlMeta
+            ),
+        );
+
+        // Apply the delete to the in-memory buffer.
+        if let Err(e) = self.sink.apply(DmlOperation::Delete(op)).await {
+            error!(error=%e, "failed to apply DML delete op");
+            return Err(e.into());
+        }
+
+        Ok(Response::new(proto::DeleteResponse {}))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use data_types::{DeletePredicate, TimestampRange};
+
+    use super::*;
+    use crate::dml_sink::mock_sink::MockDmlSink;
+
+    const NAMESPACE_ID: NamespaceId = NamespaceId::new(42);
+
+    fn handler(mock: &Arc<MockDmlSink>) -> RpcDelete<Arc<MockDmlSink>> {
+        RpcDelete::new(
+            Arc::clone(mock),
+            Arc::new(TimestampOracle::new(0)),
+            Arc::new(IngestState::default()),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_rpc_delete_apply_ok() {
+        let mock = Arc::new(MockDmlSink::default().with_apply_return(vec![Ok(())]));
+
+        let predicate = DeletePredicate {
+            range: TimestampRange::new(1, 2),
+            exprs: vec![],
+        };
+
+        handler(&mock)
+            .delete(Request::new(proto::DeleteRequest {
+                payload: Some(proto::DeletePayload {
+                    database_id: NAMESPACE_ID.get(),
+                    table_name: "bananas".to_string(),
+                    predicate: Some(predicate.clone().into()),
+                }),
+            }))
+            .await
+            .expect("delete should succeed");
+
+        assert_matches!(mock.get_calls().as_slice(), [DmlOperation::Delete(d)] => {
+            assert_eq!(d.namespace_id(), NAMESPACE_ID);
+            assert_eq!(d.table_name(), Some("bananas"));
+            assert_eq!(*d.predicate(), predicate);
+            assert_eq!(d.meta().sequence().unwrap().get(), 1);
+        });
+    }
+
+    #[tokio::test]
+    async fn test_rpc_delete_all_tables() {
+        let mock = Arc::new(MockDmlSink::default().with_apply_return(vec![Ok(())]));
+
+        handler(&mock)
+            .delete(Request::new(proto::DeleteRequest {
+                payload: Some(proto::DeletePayload {
+                    database_id: NAMESPACE_ID.get(),
+                    table_name: String::new(),
+                    predicate: Some(
+                        DeletePredicate {
+                            range: TimestampRange::new(1, 2),
+                            exprs: vec![],
+                        }
+                        .into(),
+                    ),
+                }),
+            }))
+            .await
+            .expect("delete should succeed");
+
+        assert_matches!(mock.get_calls().as_slice(), [DmlOperation::Delete(d)] => {
+            assert_eq!(d.table_name(), None);
+        });
+    }
+
+    #[tokio::test]
+    async fn test_rpc_delete_invalid() {
+        let mock = Arc::new(MockDmlSink::default());
+
+        let err = handler(&mock)
+            .delete(Request::new(proto::DeleteRequest { payload: None }))
+            .await
+            .expect_err("delete without payload should fail");
+        assert_eq!(err.code(), Code::InvalidArgument);
+
+        let err = handler(&mock)
+            .delete(Request::new(proto::DeleteRequest {
+                payload: Some(proto::DeletePayload {
+                    database_id: NAMESPACE_ID.get(),
+                    table_name: "bananas".to_string(),
+                    predicate: None,
+                }),
+            }))
+            .await
+            .expect_err("delete without predicate should fail");
+        assert_eq!(err.code(), Code::InvalidArgument);
+
+        assert!(mock.get_calls().is_empty());
+    }
+}
@@ -1,6 +1,9 @@
 use std::{collections::BTreeMap, sync::Arc, time::Duration};
 
-use data_types::{NamespaceId, Partition, PartitionId, PartitionKey, SequenceNumber, TableId};
+use data_types::{
+    NamespaceId, Partition, PartitionId, PartitionKey, SequenceNumber, TableId,
+    TablePartitionTemplateOverride,
+};
 use dml::{DmlMeta, DmlWrite};
 use iox_catalog::interface::Catalog;
 use lazy_static::lazy_static;
@@ -45,6 +48,11 @@ pub(crate) fn defer_table_name_1_sec() -> Arc<DeferredLoad<TableName>> {
     }))
 }
 
+pub(crate) fn defer_partition_template_1_sec(
+) -> Arc<DeferredLoad<Option<TablePartitionTemplateOverride>>> {
+    Arc::new(DeferredLoad::new(Duration::from_secs(1), async { None }))
+}
+
 lazy_static! {
     pub(crate) static ref ARBITRARY_PARTITION_KEY: PartitionKey =
         PartitionKey::from(ARBITRARY_PARTITION_KEY_STR);
@@ -298,7 +306,12 @@ pub(crate) async fn populate_catalog(
     table: &str,
 ) -> (NamespaceId, TableId) {
     let mut c = catalog.repositories().await;
-    let ns_id = c.namespaces().create(namespace, None).await.unwrap().id;
+    let ns_id = c
+        .namespaces()
+        .create(namespace, None, None)
+        .await
+        .unwrap()
+        .id;
     let table_id = c.tables().create_or_get(table, ns_id).await.unwrap().id;
 
     (ns_id, table_id)
@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 use dml::DmlOperation;
-use generated_types::influxdata::iox::wal::v1::sequenced_wal_op::Op;
+use generated_types::influxdata::iox::{delete::v1::DeletePayload, wal::v1::sequenced_wal_op::Op};
 use mutable_batch_pb::encode::encode_write;
 use std::{sync::Arc, time::Duration};
 use tokio::sync::watch::Receiver;
@@ -108,7 +108,11 @@ impl WalAppender for Arc<wal::Wal> {
 
         let wal_op = match op {
             DmlOperation::Write(w) => Op::Write(encode_write(namespace_id.get(), w)),
-            DmlOperation::Delete(_) => unreachable!(),
+            DmlOperation::Delete(d) => Op::Delete(DeletePayload {
+                database_id: namespace_id.get(),
+                table_name: d.table_name().unwrap_or_default().to_string(),
+                predicate: Some(d.predicate().clone().into()),
+            }),
         };
 
         self.write_op(SequencedWalOp {
@@ -147,6 +147,8 @@ impl TestContextBuilder {
             max_persist_queue_depth,
             persist_hot_partition_cost,
             storage.clone(),
+            vec![],
+            None,
             shutdown_rx.map(|v| v.expect("shutdown sender dropped without calling shutdown")),
         )
         .await
@@ -207,7 +209,7 @@ where
             .repositories()
             .await
             .namespaces()
-            .create(name, None)
+            .create(name, None, None)
             .await
             .expect("failed to create test namespace");
 
@@ -220,6 +222,7 @@ where
                         tables: Default::default(),
                         max_columns_per_table: iox_catalog::DEFAULT_MAX_COLUMNS_PER_TABLE as usize,
                         max_tables: iox_catalog::DEFAULT_MAX_TABLES as usize,
+                        query_limits: Default::default(),
                         retention_period_ns,
                         partition_template: None,
                     },
@@ -0,0 +1,13 @@
+-- Add optional partition template overrides to the "namespace" and
+-- "table_name" tables, stored as the JSON encoding of the template.
+--
+-- A NULL template means the default partition template is used.
+ALTER TABLE
+    namespace
+ADD
+    COLUMN partition_template JSONB DEFAULT NULL;
+
+ALTER TABLE
+    table_name
+ADD
+    COLUMN partition_template JSONB DEFAULT NULL;
@@ -0,0 +1,18 @@
+-- Add optional query service protection limits to the "namespace" table,
+-- enforced by the querier.
+--
+-- A NULL limit means the namespace is only bound by the querier-wide limits.
+ALTER TABLE
+    namespace
+ADD
+    COLUMN max_concurrent_queries INT DEFAULT NULL;
+
+ALTER TABLE
+    namespace
+ADD
+    COLUMN max_query_memory_bytes BIGINT DEFAULT NULL;
+
+ALTER TABLE
+    namespace
+ADD
+    COLUMN max_total_query_memory_bytes BIGINT DEFAULT NULL;
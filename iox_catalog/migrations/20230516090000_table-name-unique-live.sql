@@ -0,0 +1,11 @@
+-- Only require the names of tables that have not been soft-deleted to be
+-- unique within a namespace, so that writing to a dropped measurement
+-- creates a new table.
+ALTER TABLE
+    table_name
+DROP
+    CONSTRAINT table_name_unique;
+
+CREATE UNIQUE INDEX table_name_unique ON table_name (namespace_id, name)
+WHERE
+    deleted_at IS NULL;
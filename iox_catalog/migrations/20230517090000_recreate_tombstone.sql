@@ -0,0 +1,34 @@
+-- Replace the shard-sequenced "tombstone" table with one recording deletes
+-- received through the router's delete API, and the "processed_tombstone"
+-- table that tracked it with one recording the parquet files each delete has
+-- been applied to by the compactor. The old tables have been unused since the
+-- write buffer was removed, and their rows cannot be applied without a shard
+-- sequence, so they are discarded.
+DROP TABLE IF EXISTS processed_tombstone;
+DROP TABLE IF EXISTS tombstone;
+
+CREATE TABLE tombstone (
+    id BIGINT GENERATED ALWAYS AS IDENTITY,
+    table_id BIGINT NOT NULL,
+    min_time BIGINT NOT NULL,
+    max_time BIGINT NOT NULL,
+    serialized_predicate TEXT NOT NULL,
+    created_at BIGINT NOT NULL,
+    PRIMARY KEY (id),
+    CONSTRAINT tombstone_table_id_fkey
+        FOREIGN KEY (table_id) REFERENCES table_name (id) ON DELETE CASCADE
+);
+
+CREATE INDEX tombstone_table_id_idx ON tombstone (table_id);
+
+CREATE TABLE processed_tombstone (
+    tombstone_id BIGINT NOT NULL,
+    parquet_file_id BIGINT NOT NULL,
+    PRIMARY KEY (tombstone_id, parquet_file_id),
+    CONSTRAINT processed_tombstone_tombstone_id_fkey
+        FOREIGN KEY (tombstone_id) REFERENCES tombstone (id) ON DELETE CASCADE,
+    CONSTRAINT processed_tombstone_parquet_file_id_fkey
+        FOREIGN KEY (parquet_file_id) REFERENCES parquet_file (id) ON DELETE CASCADE
+);
+
+CREATE INDEX processed_tombstone_parquet_file_id_idx ON processed_tombstone (parquet_file_id);
@@ -0,0 +1,8 @@
+-- Add a soft-deletion timestamp to the "table_name" table, set when a
+-- measurement is dropped.
+ALTER TABLE
+    table_name
+ADD
+    COLUMN deleted_at numeric DEFAULT NULL;
+
+CREATE INDEX table_name_deleted_at_idx ON table_name (deleted_at);
@@ -0,0 +1,8 @@
+-- Add a soft-deletion timestamp to the "column_name" table, set when a
+-- column is deleted.
+ALTER TABLE
+    column_name
+ADD
+    COLUMN deleted_at numeric DEFAULT NULL;
+
+CREATE INDEX column_name_deleted_at_idx ON column_name (deleted_at);
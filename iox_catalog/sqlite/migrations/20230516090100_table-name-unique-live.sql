@@ -0,0 +1,39 @@
+-- Only require the names of tables that have not been soft-deleted to be
+-- unique within a namespace, so that writing to a dropped measurement
+-- creates a new table.
+--
+-- SQLite cannot drop the constraint, so the table is rebuilt. The old table
+-- is renamed with legacy_alter_table enabled to leave the foreign keys
+-- referencing "table_name" untouched, and it is dropped once the rows have
+-- been copied, which does not cascade to the rows referencing them.
+PRAGMA legacy_alter_table = ON;
+
+DROP INDEX table_name_namespace_idx;
+DROP INDEX table_name_deleted_at_idx;
+
+ALTER TABLE table_name RENAME TO table_name_old;
+
+create table table_name
+(
+    id                 INTEGER
+        constraint table_name_pkey
+            primary key autoincrement,
+    namespace_id       numeric not null
+        references namespace
+            on delete cascade,
+    name               varchar not null,
+    partition_template TEXT    DEFAULT NULL,
+    deleted_at         numeric DEFAULT NULL
+);
+
+INSERT INTO table_name (id, namespace_id, name, partition_template, deleted_at)
+SELECT id, namespace_id, name, partition_template, deleted_at FROM table_name_old;
+
+DROP TABLE table_name_old;
+
+PRAGMA legacy_alter_table = OFF;
+
+create index table_name_namespace_idx on table_name (namespace_id);
+create index table_name_deleted_at_idx on table_name (deleted_at);
+create unique index table_name_unique on table_name (namespace_id, name)
+    where deleted_at is null;
@@ -0,0 +1,38 @@
+-- Replace the shard-sequenced "tombstone" table with one recording deletes
+-- received through the router's delete API, and the "processed_tombstone"
+-- table that tracked it with one recording the parquet files each delete has
+-- been applied to by the compactor. The old tables have been unused since the
+-- write buffer was removed, and their rows cannot be applied without a shard
+-- sequence, so they are discarded.
+DROP TABLE IF EXISTS processed_tombstone;
+DROP TABLE IF EXISTS tombstone;
+
+create table if not exists tombstone
+(
+    id                   INTEGER
+        constraint tombstone_pkey
+            primary key autoincrement,
+    table_id             numeric not null
+        references table_name
+            on delete cascade,
+    min_time             numeric not null,
+    max_time             numeric not null,
+    serialized_predicate text    not null,
+    created_at           numeric not null
+);
+
+create index if not exists tombstone_table_id_idx on tombstone (table_id);
+
+create table if not exists processed_tombstone
+(
+    tombstone_id    INTEGER not null
+        references tombstone
+            on delete cascade,
+    parquet_file_id numeric not null
+        references parquet_file
+            on delete cascade,
+    primary key (tombstone_id, parquet_file_id)
+);
+
+create index if not exists processed_tombstone_parquet_file_id_idx
+    on processed_tombstone (parquet_file_id);
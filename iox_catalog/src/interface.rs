@@ -2,9 +2,10 @@
 
 use async_trait::async_trait;
 use data_types::{
-    Column, ColumnType, ColumnsByName, CompactionLevel, Namespace, NamespaceId, NamespaceSchema,
-    ParquetFile, ParquetFileId, ParquetFileParams, Partition, PartitionId, PartitionKey,
-    SkippedCompaction, Table, TableId, TableSchema, Timestamp,
+    Column, ColumnId, ColumnType, ColumnsByName, CompactionLevel, Namespace, NamespaceId,
+    NamespacePartitionTemplateOverride, NamespaceSchema, ParquetFile, ParquetFileId,
+    ParquetFileParams, Partition, PartitionId, PartitionKey, ProcessedTombstone, SkippedCompaction,
+    Table, TableId, TablePartitionTemplateOverride, TableSchema, Timestamp, Tombstone, TombstoneId,
 };
 use iox_time::TimeProvider;
 use snafu::{OptionExt, Snafu};
@@ -64,12 +65,24 @@ pub enum Error {
     #[snafu(display("namespace {} not found", id))]
     NamespaceNotFoundById { id: NamespaceId },
 
+    #[snafu(display("namespace {} is not deleted", name))]
+    NamespaceNotDeleted { name: String },
+
     #[snafu(display("table {} not found", id))]
     TableNotFound { id: TableId },
 
+    #[snafu(display("column {} not found", id))]
+    ColumnNotFound { id: ColumnId },
+
+    #[snafu(display("column {} has been deleted", name))]
+    ColumnDeleted { name: String },
+
     #[snafu(display("partition {} not found", id))]
     PartitionNotFound { id: PartitionId },
 
+    #[snafu(display("tombstone {} not found", id))]
+    TombstoneNotFound { id: TombstoneId },
+
     #[snafu(display(
         "couldn't create column {} in table {}; limit reached on namespace",
         column_name,
@@ -133,6 +146,21 @@ pub enum Error {
 
     #[snafu(display("could not delete namespace: {source}"))]
     CouldNotDeleteNamespace { source: sqlx::Error },
+
+    #[snafu(display("could not purge namespace: {source}"))]
+    CouldNotPurgeNamespace { source: sqlx::Error },
+
+    #[snafu(display("could not delete table: {source}"))]
+    CouldNotDeleteTable { source: sqlx::Error },
+
+    #[snafu(display("could not undelete table: {source}"))]
+    CouldNotUndeleteTable { source: sqlx::Error },
+
+    #[snafu(display("could not delete column: {source}"))]
+    CouldNotDeleteColumn { source: sqlx::Error },
+
+    #[snafu(display("could not undelete column: {source}"))]
+    CouldNotUndeleteColumn { source: sqlx::Error },
 }
 
 /// A specialized `Error` for Catalog errors
@@ -299,6 +327,9 @@ pub trait RepoCollection: Send + Sync + Debug {
 
     /// Repository for [Parquet files](data_types::ParquetFile).
     fn parquet_files(&mut self) -> &mut dyn ParquetFileRepo;
+
+    /// Repository for [tombstones](data_types::Tombstone).
+    fn tombstones(&mut self) -> &mut dyn TombstoneRepo;
 }
 
 /// Functions for working with namespaces in the catalog
@@ -306,8 +337,14 @@ pub trait RepoCollection: Send + Sync + Debug {
 pub trait NamespaceRepo: Send + Sync {
     /// Creates the namespace in the catalog. If one by the same name already exists, an
     /// error is returned.
-    /// Specify `None` for `retention_period_ns` to get infinite retention.
-    async fn create(&mut self, name: &str, retention_period_ns: Option<i64>) -> Result<Namespace>;
+    /// Specify `None` for `partition_template` to use the default partition template, and `None`
+    /// for `retention_period_ns` to get infinite retention.
+    async fn create(
+        &mut self,
+        name: &str,
+        partition_template: Option<NamespacePartitionTemplateOverride>,
+        retention_period_ns: Option<i64>,
+    ) -> Result<Namespace>;
 
     /// Update retention period for a namespace
     async fn update_retention_period(
@@ -336,23 +373,76 @@ pub trait NamespaceRepo: Send + Sync {
     /// Soft-delete a namespace by name
     async fn soft_delete(&mut self, name: &str) -> Result<()>;
 
+    /// Restore a soft-deleted namespace by name.
+    ///
+    /// Returns [`Error::NamespaceNotDeleted`] if the namespace exists but is not soft-deleted.
+    async fn undelete(&mut self, name: &str) -> Result<Namespace>;
+
+    /// Permanently delete the namespace `id` along with all of its tables, columns, partitions,
+    /// tombstones and parquet file records, if it was soft-deleted before `older_than`.
+    ///
+    /// Returns `false` if the namespace does not exist, is not soft-deleted, or was soft-deleted
+    /// too recently. The objects of its parquet files are NOT removed from the object store.
+    async fn purge(&mut self, id: NamespaceId, older_than: Timestamp) -> Result<bool>;
+
     /// Update the limit on the number of tables that can exist per namespace.
     async fn update_table_limit(&mut self, name: &str, new_max: i32) -> Result<Namespace>;
 
     /// Update the limit on the number of columns that can exist per table in a given namespace.
     async fn update_column_limit(&mut self, name: &str, new_max: i32) -> Result<Namespace>;
+
+    /// Update the limit on the number of queries that can run concurrently against a namespace,
+    /// removing it if `None`.
+    async fn update_concurrent_query_limit(
+        &mut self,
+        name: &str,
+        new_max: Option<i32>,
+    ) -> Result<Namespace>;
+
+    /// Update the limit on the memory a single query against a namespace can reserve, removing it
+    /// if `None`.
+    async fn update_query_memory_limit(
+        &mut self,
+        name: &str,
+        new_max: Option<i64>,
+    ) -> Result<Namespace>;
+
+    /// Update the limit on the memory all queries against a namespace can reserve together,
+    /// removing it if `None`.
+    async fn update_total_query_memory_limit(
+        &mut self,
+        name: &str,
+        new_max: Option<i64>,
+    ) -> Result<Namespace>;
 }
 
 /// Functions for working with tables in the catalog
 #[async_trait]
 pub trait TableRepo: Send + Sync {
     /// Creates the table in the catalog or get the existing record by name.
+    ///
+    /// A newly created table inherits the partition template of its namespace.
+    /// Soft-deleted tables are never returned, and a new table is created in
+    /// place of one.
     async fn create_or_get(&mut self, name: &str, namespace_id: NamespaceId) -> Result<Table>;
 
+    /// Creates the table in the catalog with the given `partition_template`, or the partition
+    /// template of its namespace if `None`. If a table by the same name that has not been
+    /// soft-deleted already exists in the namespace, an error is returned.
+    async fn create(
+        &mut self,
+        name: &str,
+        partition_template: Option<TablePartitionTemplateOverride>,
+        namespace_id: NamespaceId,
+    ) -> Result<Table>;
+
     /// get table by ID
     async fn get_by_id(&mut self, table_id: TableId) -> Result<Option<Table>>;
 
     /// get table by namespace ID and name
+    ///
+    /// If every table of that name has been soft-deleted, the most recently
+    /// created one is returned.
     async fn get_by_namespace_and_name(
         &mut self,
         namespace_id: NamespaceId,
@@ -364,6 +454,20 @@ pub trait TableRepo: Send + Sync {
 
     /// List all tables.
     async fn list(&mut self) -> Result<Vec<Table>>;
+
+    /// Soft-delete a table, excluding it from namespace schemas and allowing
+    /// its parquet files to be reclaimed by the garbage collector.
+    ///
+    /// A soft-deleted table does not count towards the table limit of its
+    /// namespace.
+    async fn soft_delete(&mut self, table_id: TableId) -> Result<()>;
+
+    /// Restore a soft-deleted table.
+    ///
+    /// Fails with [`Error::NameExists`] if a table of the same name has been
+    /// created since it was deleted. The table limit of the namespace is not
+    /// checked.
+    async fn undelete(&mut self, table_id: TableId) -> Result<()>;
 }
 
 /// Functions for working with columns in the catalog
@@ -402,6 +506,17 @@ pub trait ColumnRepo: Send + Sync {
 
     /// List all columns.
     async fn list(&mut self) -> Result<Vec<Column>>;
+
+    /// Soft-delete a column, excluding it from namespace schemas.
+    ///
+    /// A soft-deleted column does not count towards the column limit of its
+    /// table.
+    async fn soft_delete(&mut self, column_id: ColumnId) -> Result<()>;
+
+    /// Restore a soft-deleted column.
+    ///
+    /// The column limit of the table is not checked.
+    async fn undelete(&mut self, column_id: ColumnId) -> Result<()>;
 }
 
 /// Functions for working with IOx partitions in the catalog. These are how IOx splits up
@@ -544,7 +659,50 @@ pub trait ParquetFileRepo: Send + Sync {
     ) -> Result<Option<ParquetFile>>;
 }
 
+/// Functions for working with delete tombstones in the catalog
+#[async_trait]
+pub trait TombstoneRepo: Send + Sync {
+    /// Record a delete of the rows in `table_id` with a timestamp in
+    /// `[min_time, max_time]` (both ends inclusive) that match
+    /// `serialized_predicate`.
+    async fn create(
+        &mut self,
+        table_id: TableId,
+        min_time: Timestamp,
+        max_time: Timestamp,
+        serialized_predicate: &str,
+    ) -> Result<Tombstone>;
+
+    /// List all tombstones for the given table, ordered by ID.
+    async fn list_by_table_id(&mut self, table_id: TableId) -> Result<Vec<Tombstone>>;
+
+    /// List all tombstones for tables within the given namespace, ordered by ID.
+    async fn list_by_namespace_id(&mut self, namespace_id: NamespaceId) -> Result<Vec<Tombstone>>;
+
+    /// Record that the rows deleted by `tombstone_id` have been removed from
+    /// `parquet_file_id`. Recording the same pair again has no effect.
+    async fn create_processed(
+        &mut self,
+        tombstone_id: TombstoneId,
+        parquet_file_id: ParquetFileId,
+    ) -> Result<()>;
+
+    /// List the processed tombstones of the given table, ordered by tombstone
+    /// ID and parquet file ID.
+    async fn list_processed_by_table_id(
+        &mut self,
+        table_id: TableId,
+    ) -> Result<Vec<ProcessedTombstone>>;
+
+    /// Delete the tombstone with the given ID, and the records of the parquet
+    /// files it was applied to.
+    async fn delete(&mut self, id: TombstoneId) -> Result<()>;
+}
+
 /// Gets the namespace schema including all tables and columns.
+///
+/// Soft-deleted tables and columns are excluded from the schema if `deleted`
+/// is [`SoftDeletedRows::ExcludeDeleted`], and included otherwise.
 pub async fn get_schema_by_id<R>(
     id: NamespaceId,
     repos: &mut R,
@@ -559,10 +717,13 @@ where
         .await?
         .context(NamespaceNotFoundByIdSnafu { id })?;
 
-    get_schema_internal(namespace, repos).await
+    get_schema_internal(namespace, repos, deleted).await
 }
 
 /// Gets the namespace schema including all tables and columns.
+///
+/// Soft-deleted tables and columns are excluded from the schema if `deleted`
+/// is [`SoftDeletedRows::ExcludeDeleted`], and included otherwise.
 pub async fn get_schema_by_name<R>(
     name: &str,
     repos: &mut R,
@@ -577,10 +738,14 @@ where
         .await?
         .context(NamespaceNotFoundByNameSnafu { name })?;
 
-    get_schema_internal(namespace, repos).await
+    get_schema_internal(namespace, repos, deleted).await
 }
 
-async fn get_schema_internal<R>(namespace: Namespace, repos: &mut R) -> Result<NamespaceSchema>
+async fn get_schema_internal<R>(
+    namespace: Namespace,
+    repos: &mut R,
+    deleted: SoftDeletedRows,
+) -> Result<NamespaceSchema>
 where
     R: RepoCollection + ?Sized,
 {
@@ -588,19 +753,31 @@ where
     let columns = repos.columns().list_by_namespace_id(namespace.id).await?;
     let tables = repos.tables().list_by_namespace_id(namespace.id).await?;
 
+    let include_deleted = !matches!(deleted, SoftDeletedRows::ExcludeDeleted);
+
     let mut namespace = NamespaceSchema::new_empty_from(&namespace);
 
     let mut table_id_to_schema = BTreeMap::new();
-    for t in tables {
+    for t in tables
+        .into_iter()
+        .filter(|t| include_deleted || t.deleted_at.is_none())
+    {
         let table_schema = TableSchema::new_empty_from(&t);
         table_id_to_schema.insert(t.id, (t.name, table_schema));
     }
 
-    for c in columns {
-        let (_, t) = table_id_to_schema.get_mut(&c.table_id).unwrap();
-        t.add_column(c);
+    for c in columns
+        .into_iter()
+        .filter(|c| include_deleted || c.deleted_at.is_none())
+    {
+        // The columns of excluded tables are skipped.
+        if let Some((_, t)) = table_id_to_schema.get_mut(&c.table_id) {
+            t.add_column(c);
+        }
     }
 
+    // A table created in place of a deleted table of the same name has a
+    // greater ID, and so replaces it when deleted tables are included.
     for (_, (table_name, schema)) in table_id_to_schema {
         namespace.tables.insert(table_name, schema);
     }
@@ -627,7 +804,8 @@ where
 ///
 /// # Soft Deletion
 ///
-/// No schemas for soft-deleted namespaces are returned.
+/// No schemas for soft-deleted namespaces are returned, and soft-deleted
+/// tables and columns are excluded from the schemas that are.
 pub async fn list_schemas(
     catalog: &dyn Catalog,
 ) -> Result<impl Iterator<Item = (Namespace, NamespaceSchema)>> {
@@ -690,6 +868,9 @@ pub async fn list_schemas(
     for column in columns {
         // Resolve the table this column references
         let table = tables.get(&column.table_id).expect("no table for column");
+        if table.deleted_at.is_some() || column.deleted_at.is_some() {
+            continue;
+        }
 
         let table_schema = joined
             // Find or create a record in the joined <NamespaceId, Tables> map
@@ -707,21 +888,23 @@ pub async fn list_schemas(
     drop(tables);
 
     // Convert the Namespace instances into NamespaceSchema instances.
+    //
+    // Namespaces that have no tables/columns (and therefore have no entry in
+    // "joined") are included with an empty set of tables, so that callers
+    // replacing a previously listed schema observe all of its tables having
+    // been deleted.
     let iter = namespaces
         .await
         .expect("namespace list task panicked")?
         .into_iter()
-        // Ignore any namespaces that did not exist when the "columns" snapshot
-        // was created, or have no tables/columns (and therefore have no entry
-        // in "joined").
-        .filter_map(move |v| {
+        .map(move |v| {
             // The catalog call explicitly asked for no soft deleted records.
             assert!(v.deleted_at.is_none());
 
             let mut ns = NamespaceSchema::new_empty_from(&v);
 
-            ns.tables = joined.remove(&v.id)?;
-            Some((v, ns))
+            ns.tables = joined.remove(&v.id).unwrap_or_default();
+            (v, ns)
         });
 
     Ok(iter)
@@ -734,7 +917,7 @@ pub(crate) mod test_helpers {
     use super::*;
     use ::test_helpers::{assert_contains, tracing::TracingCapture};
     use assert_matches::assert_matches;
-    use data_types::{ColumnId, ColumnSet, CompactionLevel};
+    use data_types::{ColumnId, ColumnSet, CompactionLevel, PartitionTemplate, TemplatePart};
     use futures::Future;
     use metric::{Attributes, DurationHistogram, Metric};
     use std::{collections::BTreeSet, ops::DerefMut, sync::Arc, time::Duration};
@@ -746,6 +929,7 @@ pub(crate) mod test_helpers {
     {
         test_setup(clean_state().await).await;
         test_namespace_soft_deletion(clean_state().await).await;
+        test_namespace_undelete_and_purge(clean_state().await).await;
         test_partitions_new_file_between(clean_state().await).await;
         test_column(clean_state().await).await;
         test_partition(clean_state().await).await;
@@ -757,6 +941,8 @@ pub(crate) mod test_helpers {
         test_txn_drop(clean_state().await).await;
         test_list_schemas(clean_state().await).await;
         test_list_schemas_soft_deleted_rows(clean_state().await).await;
+        test_table_soft_deletion(clean_state().await).await;
+        test_column_soft_deletion(clean_state().await).await;
         test_delete_namespace(clean_state().await).await;
 
         let catalog = clean_state().await;
@@ -767,6 +953,10 @@ pub(crate) mod test_helpers {
         test_table(Arc::clone(&catalog)).await;
         assert_metric_hit(&catalog.metrics(), "table_create_or_get");
 
+        let catalog = clean_state().await;
+        test_table_partition_template(Arc::clone(&catalog)).await;
+        assert_metric_hit(&catalog.metrics(), "table_create");
+
         let catalog = clean_state().await;
         test_column(Arc::clone(&catalog)).await;
         assert_metric_hit(&catalog.metrics(), "column_create_or_get");
@@ -778,6 +968,10 @@ pub(crate) mod test_helpers {
         let catalog = clean_state().await;
         test_parquet_file(Arc::clone(&catalog)).await;
         assert_metric_hit(&catalog.metrics(), "parquet_create");
+
+        let catalog = clean_state().await;
+        test_tombstone(Arc::clone(&catalog)).await;
+        assert_metric_hit(&catalog.metrics(), "tombstone_create");
     }
 
     async fn test_setup(catalog: Arc<dyn Catalog>) {
@@ -790,7 +984,7 @@ pub(crate) mod test_helpers {
         let namespace_name = "test_namespace";
         let namespace = repos
             .namespaces()
-            .create(namespace_name, None)
+            .create(namespace_name, None, None)
             .await
             .unwrap();
         assert!(namespace.id > NamespaceId::new(0));
@@ -802,8 +996,11 @@ pub(crate) mod test_helpers {
             namespace.max_columns_per_table,
             DEFAULT_MAX_COLUMNS_PER_TABLE
         );
+        assert!(namespace.max_concurrent_queries.is_none());
+        assert!(namespace.max_query_memory_bytes.is_none());
+        assert!(namespace.max_total_query_memory_bytes.is_none());
 
-        let conflict = repos.namespaces().create(namespace_name, None).await;
+        let conflict = repos.namespaces().create(namespace_name, None, None).await;
         assert!(matches!(
             conflict.unwrap_err(),
             Error::NameExists { name: _ }
@@ -842,7 +1039,7 @@ pub(crate) mod test_helpers {
         let namespace2_name = "test_namespace2";
         let namespace2 = repos
             .namespaces()
-            .create(namespace2_name, None)
+            .create(namespace2_name, None, None)
             .await
             .unwrap();
         let mut namespaces = repos
@@ -869,6 +1066,60 @@ pub(crate) mod test_helpers {
             .expect("namespace should be updateable");
         assert_eq!(NEW_COLUMN_LIMIT, modified.max_columns_per_table);
 
+        let modified = repos
+            .namespaces()
+            .update_concurrent_query_limit(namespace_name, Some(5))
+            .await
+            .expect("namespace should be updateable");
+        assert_eq!(modified.max_concurrent_queries, Some(5));
+
+        const NEW_QUERY_MEMORY_LIMIT: i64 = 5 * 1024 * 1024 * 1024;
+        let modified = repos
+            .namespaces()
+            .update_query_memory_limit(namespace_name, Some(NEW_QUERY_MEMORY_LIMIT))
+            .await
+            .expect("namespace should be updateable");
+        assert_eq!(
+            modified.max_query_memory_bytes,
+            Some(NEW_QUERY_MEMORY_LIMIT)
+        );
+
+        let modified = repos
+            .namespaces()
+            .update_total_query_memory_limit(namespace_name, Some(2 * NEW_QUERY_MEMORY_LIMIT))
+            .await
+            .expect("namespace should be updateable");
+        assert_eq!(
+            modified.max_total_query_memory_bytes,
+            Some(2 * NEW_QUERY_MEMORY_LIMIT)
+        );
+        assert_eq!(modified.max_concurrent_queries, Some(5));
+        assert_eq!(
+            modified.max_query_memory_bytes,
+            Some(NEW_QUERY_MEMORY_LIMIT)
+        );
+
+        let modified = repos
+            .namespaces()
+            .update_concurrent_query_limit(namespace_name, None)
+            .await
+            .expect("namespace should be updateable");
+        assert!(modified.max_concurrent_queries.is_none());
+
+        let modified = repos
+            .namespaces()
+            .update_query_memory_limit(namespace_name, None)
+            .await
+            .expect("namespace should be updateable");
+        assert!(modified.max_query_memory_bytes.is_none());
+
+        let modified = repos
+            .namespaces()
+            .update_total_query_memory_limit(namespace_name, None)
+            .await
+            .expect("namespace should be updateable");
+        assert!(modified.max_total_query_memory_bytes.is_none());
+
         const NEW_RETENTION_PERIOD_NS: i64 = 5 * 60 * 60 * 1000 * 1000 * 1000;
         let modified = repos
             .namespaces()
@@ -891,7 +1142,7 @@ pub(crate) mod test_helpers {
         let namespace3_name = "test_namespace3";
         let namespace3 = repos
             .namespaces()
-            .create(namespace3_name, None)
+            .create(namespace3_name, None, None)
             .await
             .expect("namespace with NULL retention should be created");
         assert!(namespace3.retention_period_ns.is_none());
@@ -900,7 +1151,7 @@ pub(crate) mod test_helpers {
         let namespace4_name = "test_namespace4";
         let namespace4 = repos
             .namespaces()
-            .create(namespace4_name, Some(NEW_RETENTION_PERIOD_NS))
+            .create(namespace4_name, None, Some(NEW_RETENTION_PERIOD_NS))
             .await
             .expect("namespace with 5-hour retention should be created");
         assert_eq!(
@@ -947,8 +1198,16 @@ pub(crate) mod test_helpers {
     async fn test_namespace_soft_deletion(catalog: Arc<dyn Catalog>) {
         let mut repos = catalog.repositories().await;
 
-        let deleted_ns = repos.namespaces().create("deleted-ns", None).await.unwrap();
-        let active_ns = repos.namespaces().create("active-ns", None).await.unwrap();
+        let deleted_ns = repos
+            .namespaces()
+            .create("deleted-ns", None, None)
+            .await
+            .unwrap();
+        let active_ns = repos
+            .namespaces()
+            .create("active-ns", None, None)
+            .await
+            .unwrap();
 
         // Mark "deleted-ns" as soft-deleted.
         repos.namespaces().soft_delete("deleted-ns").await.unwrap();
@@ -1085,6 +1344,180 @@ pub(crate) mod test_helpers {
         assert_string_set_eq(got, ["active-ns"]);
     }
 
+    async fn test_namespace_undelete_and_purge(catalog: Arc<dyn Catalog>) {
+        let mut repos = catalog.repositories().await;
+
+        let namespace = repos
+            .namespaces()
+            .create("purged-ns", None, None)
+            .await
+            .unwrap();
+        let other_namespace = repos
+            .namespaces()
+            .create("other-ns", None, None)
+            .await
+            .unwrap();
+
+        let mut parquet_files = vec![];
+        for ns in [&namespace, &other_namespace] {
+            let table = repos
+                .tables()
+                .create_or_get("test_table", ns.id)
+                .await
+                .unwrap();
+            repos
+                .columns()
+                .create_or_get("time", table.id, ColumnType::Time)
+                .await
+                .unwrap();
+            let partition = repos
+                .partitions()
+                .create_or_get("one".into(), table.id)
+                .await
+                .unwrap();
+            let parquet_file = repos
+                .parquet_files()
+                .create(ParquetFileParams {
+                    namespace_id: ns.id,
+                    table_id: table.id,
+                    partition_id: partition.id,
+                    object_store_id: Uuid::new_v4(),
+                    min_time: Timestamp::new(1),
+                    max_time: Timestamp::new(10),
+                    file_size_bytes: 1337,
+                    row_count: 0,
+                    compaction_level: CompactionLevel::Initial,
+                    created_at: Timestamp::new(1),
+                    column_set: ColumnSet::new([ColumnId::new(1)]),
+                    max_l0_created_at: Timestamp::new(1),
+                })
+                .await
+                .unwrap();
+            parquet_files.push(parquet_file);
+        }
+
+        // A namespace that is not soft-deleted is never purged
+        let now = Timestamp::from(catalog.time_provider().now());
+        let purged = repos
+            .namespaces()
+            .purge(namespace.id, now + 1)
+            .await
+            .unwrap();
+        assert!(!purged);
+
+        // Soft-deleted namespaces can be restored
+        repos.namespaces().soft_delete("purged-ns").await.unwrap();
+        let undeleted = repos.namespaces().undelete("purged-ns").await.unwrap();
+        assert_eq!(undeleted.id, namespace.id);
+        assert!(undeleted.deleted_at.is_none());
+        assert!(repos
+            .namespaces()
+            .get_by_name("purged-ns", SoftDeletedRows::ExcludeDeleted)
+            .await
+            .unwrap()
+            .is_some());
+
+        let err = repos.namespaces().undelete("missing-ns").await.unwrap_err();
+        assert_matches!(err, Error::NamespaceNotFoundByName { .. });
+
+        // Undeleting a namespace that is not deleted fails, and leaves it untouched
+        let err = repos.namespaces().undelete("purged-ns").await.unwrap_err();
+        assert_matches!(err, Error::NamespaceNotDeleted { name } => {
+            assert_eq!(name, "purged-ns");
+        });
+        let err = repos.namespaces().undelete("other-ns").await.unwrap_err();
+        assert_matches!(err, Error::NamespaceNotDeleted { .. });
+        assert!(repos
+            .namespaces()
+            .get_by_name("other-ns", SoftDeletedRows::ExcludeDeleted)
+            .await
+            .unwrap()
+            .is_some());
+
+        repos.namespaces().soft_delete("purged-ns").await.unwrap();
+        let deleted_at = repos
+            .namespaces()
+            .get_by_id(namespace.id, SoftDeletedRows::OnlyDeleted)
+            .await
+            .unwrap()
+            .expect("namespace should be soft-deleted")
+            .deleted_at
+            .unwrap();
+
+        // A namespace deleted after the cutoff is not purged
+        let purged = repos
+            .namespaces()
+            .purge(namespace.id, deleted_at)
+            .await
+            .unwrap();
+        assert!(!purged);
+
+        let purged = repos
+            .namespaces()
+            .purge(namespace.id, deleted_at + 1)
+            .await
+            .unwrap();
+        assert!(purged);
+
+        // The namespace and everything in it are gone
+        assert!(repos
+            .namespaces()
+            .get_by_id(namespace.id, SoftDeletedRows::AllRows)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(repos
+            .tables()
+            .list_by_namespace_id(namespace.id)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(repos
+            .columns()
+            .list_by_namespace_id(namespace.id)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(repos
+            .parquet_files()
+            .get_by_object_store_id(parquet_files[0].object_store_id)
+            .await
+            .unwrap()
+            .is_none());
+
+        // ... while the other namespace is untouched
+        assert_eq!(
+            repos
+                .tables()
+                .list_by_namespace_id(other_namespace.id)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(repos
+            .parquet_files()
+            .get_by_object_store_id(parquet_files[1].object_store_id)
+            .await
+            .unwrap()
+            .is_some());
+
+        // Purging is not repeated
+        let purged = repos
+            .namespaces()
+            .purge(namespace.id, deleted_at + 1)
+            .await
+            .unwrap();
+        assert!(!purged);
+
+        // And the name can be reused
+        repos
+            .namespaces()
+            .create("purged-ns", None, None)
+            .await
+            .unwrap();
+    }
+
     // Assert the set of strings "a" is equal to the set "b", tolerating
     // duplicates.
     #[track_caller]
@@ -1104,7 +1537,7 @@ pub(crate) mod test_helpers {
         let mut repos = catalog.repositories().await;
         let namespace = repos
             .namespaces()
-            .create("namespace_table_test", None)
+            .create("namespace_table_test", None, None)
             .await
             .unwrap();
 
@@ -1139,7 +1572,7 @@ pub(crate) mod test_helpers {
         assert_eq!(vec![t.clone()], tables);
 
         // test we can create a table of the same name in a different namespace
-        let namespace2 = repos.namespaces().create("two", None).await.unwrap();
+        let namespace2 = repos.namespaces().create("two", None, None).await.unwrap();
         assert_ne!(namespace, namespace2);
         let test_table = repos
             .tables()
@@ -1221,23 +1654,175 @@ pub(crate) mod test_helpers {
             }
         ));
 
-        repos
+        repos
+            .namespaces()
+            .soft_delete("namespace_table_test")
+            .await
+            .expect("delete namespace should succeed");
+        repos
+            .namespaces()
+            .soft_delete("two")
+            .await
+            .expect("delete namespace should succeed");
+    }
+
+    async fn test_table_partition_template(catalog: Arc<dyn Catalog>) {
+        let mut repos = catalog.repositories().await;
+
+        let namespace_template = PartitionTemplate {
+            parts: vec![
+                TemplatePart::Column("region".to_string()),
+                TemplatePart::TimeFormat("%Y".to_string()),
+            ],
+        };
+        let table_template = PartitionTemplate {
+            parts: vec![TemplatePart::Column("host".to_string())],
+        };
+
+        // The namespace template round-trips through the catalog
+        let namespace = repos
+            .namespaces()
+            .create(
+                "namespace_template_test",
+                Some(NamespacePartitionTemplateOverride::new(
+                    namespace_template.clone(),
+                )),
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            namespace
+                .partition_template
+                .as_ref()
+                .map(|t| t.partition_template()),
+            Some(&namespace_template)
+        );
+        let got = repos
+            .namespaces()
+            .get_by_id(namespace.id, SoftDeletedRows::ExcludeDeleted)
+            .await
+            .unwrap()
+            .expect("namespace should exist");
+        assert_eq!(got, namespace);
+
+        // A namespace without a template has no override
+        let plain = repos
+            .namespaces()
+            .create("namespace_no_template_test", None, None)
+            .await
+            .unwrap();
+        assert_eq!(plain.partition_template, None);
+
+        // Tables created implicitly inherit the namespace template
+        let inherited = repos
+            .tables()
+            .create_or_get("inherited", namespace.id)
+            .await
+            .unwrap();
+        assert_eq!(
+            inherited
+                .partition_template
+                .as_ref()
+                .map(|t| t.partition_template()),
+            Some(&namespace_template)
+        );
+        let plain_table = repos
+            .tables()
+            .create_or_get("inherited", plain.id)
+            .await
+            .unwrap();
+        assert_eq!(plain_table.partition_template, None);
+
+        // An explicitly created table without a template also inherits it
+        let explicit_inherited = repos
+            .tables()
+            .create("explicit_inherited", None, namespace.id)
+            .await
+            .unwrap();
+        assert_eq!(
+            explicit_inherited.partition_template,
+            inherited.partition_template
+        );
+
+        // An explicit table template takes precedence over the namespace template
+        let overridden = repos
+            .tables()
+            .create(
+                "overridden",
+                Some(TablePartitionTemplateOverride::new(table_template.clone())),
+                namespace.id,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            overridden
+                .partition_template
+                .as_ref()
+                .map(|t| t.partition_template()),
+            Some(&table_template)
+        );
+        let got = repos
+            .tables()
+            .get_by_namespace_and_name(namespace.id, "overridden")
+            .await
+            .unwrap()
+            .expect("table should exist");
+        assert_eq!(got, overridden);
+
+        // Explicitly creating a table that already exists is an error
+        let err = repos
+            .tables()
+            .create("overridden", None, namespace.id)
+            .await
+            .expect_err("duplicate table should be rejected");
+        assert_matches!(err, Error::NameExists { .. });
+
+        // Explicitly creating a table in an unknown namespace is an error
+        repos
+            .tables()
+            .create("bananas", None, NamespaceId::new(i64::MAX))
+            .await
+            .expect_err("table in unknown namespace should be rejected");
+
+        // The table limit applies to explicitly created tables
+        let latest = repos
             .namespaces()
-            .soft_delete("namespace_table_test")
+            .update_table_limit("namespace_template_test", 3)
             .await
-            .expect("delete namespace should succeed");
-        repos
-            .namespaces()
-            .soft_delete("two")
+            .expect("namespace should be updateable");
+        let err = repos
+            .tables()
+            .create("definitely_unique", None, latest.id)
             .await
-            .expect("delete namespace should succeed");
+            .expect_err("should error with table create limit error");
+        assert_matches!(err, Error::TableCreateLimitError { .. });
+
+        // The templates are carried into the namespace schema
+        let schema = get_schema_by_id(namespace.id, repos.as_mut(), SoftDeletedRows::AllRows)
+            .await
+            .unwrap();
+        assert_eq!(
+            schema
+                .partition_template
+                .as_ref()
+                .map(|t| t.partition_template()),
+            Some(&namespace_template)
+        );
+        assert_eq!(
+            schema.tables["overridden"]
+                .partition_template
+                .as_ref()
+                .map(|t| t.partition_template()),
+            Some(&table_template)
+        );
     }
 
     async fn test_column(catalog: Arc<dyn Catalog>) {
         let mut repos = catalog.repositories().await;
         let namespace = repos
             .namespaces()
-            .create("namespace_column_test", None)
+            .create("namespace_column_test", None, None)
             .await
             .unwrap();
         let table = repos
@@ -1370,7 +1955,7 @@ pub(crate) mod test_helpers {
         let mut repos = catalog.repositories().await;
         let namespace = repos
             .namespaces()
-            .create("namespace_partition_test", None)
+            .create("namespace_partition_test", None, None)
             .await
             .unwrap();
         let table = repos
@@ -1652,7 +2237,7 @@ pub(crate) mod test_helpers {
         let mut repos = catalog.repositories().await;
         let namespace = repos
             .namespaces()
-            .create("namespace_parquet_file_test", None)
+            .create("namespace_parquet_file_test", None, None)
             .await
             .unwrap();
         let table = repos
@@ -1837,7 +2422,7 @@ pub(crate) mod test_helpers {
         // test list_by_namespace_not_to_delete
         let namespace2 = repos
             .namespaces()
-            .create("namespace_parquet_file_test1", None)
+            .create("namespace_parquet_file_test1", None, None)
             .await
             .unwrap();
         let table2 = repos
@@ -2060,12 +2645,12 @@ pub(crate) mod test_helpers {
         let mut repos = catalog.repositories().await;
         let namespace_1 = repos
             .namespaces()
-            .create("retention_broken_1", None)
+            .create("retention_broken_1", None, None)
             .await
             .unwrap();
         let namespace_2 = repos
             .namespaces()
-            .create("retention_broken_2", Some(1))
+            .create("retention_broken_2", None, Some(1))
             .await
             .unwrap();
         let table_1 = repos
@@ -2140,7 +2725,7 @@ pub(crate) mod test_helpers {
         let mut repos = catalog.repositories().await;
         let namespace = repos
             .namespaces()
-            .create("test_partitions_new_file_between", None)
+            .create("test_partitions_new_file_between", None, None)
             .await
             .unwrap();
         let table = repos
@@ -2509,6 +3094,7 @@ pub(crate) mod test_helpers {
             .create(
                 "namespace_parquet_file_test_list_by_partiton_not_to_delete",
                 None,
+                None,
             )
             .await
             .unwrap();
@@ -2616,7 +3202,7 @@ pub(crate) mod test_helpers {
         let mut repos = catalog.repositories().await;
         let namespace = repos
             .namespaces()
-            .create("namespace_update_to_compaction_level_1_test", None)
+            .create("namespace_update_to_compaction_level_1_test", None, None)
             .await
             .unwrap();
         let table = repos
@@ -2702,7 +3288,7 @@ pub(crate) mod test_helpers {
         let mut repos = catalog.repositories().await;
         let namespace_1 = repos
             .namespaces()
-            .create("namespace_test_delete_namespace_1", None)
+            .create("namespace_test_delete_namespace_1", None, None)
             .await
             .unwrap();
         let table_1 = repos
@@ -2757,7 +3343,7 @@ pub(crate) mod test_helpers {
         // it, let's create another so we can ensure that doesn't get deleted.
         let namespace_2 = repos
             .namespaces()
-            .create("namespace_test_delete_namespace_2", None)
+            .create("namespace_test_delete_namespace_2", None, None)
             .await
             .unwrap();
         let table_2 = repos
@@ -2943,7 +3529,7 @@ pub(crate) mod test_helpers {
 
             let mut txn = catalog_captured.start_transaction().await.unwrap();
             txn.namespaces()
-                .create("test_txn_isolation", None)
+                .create("test_txn_isolation", None, None)
                 .await
                 .unwrap();
 
@@ -2980,7 +3566,7 @@ pub(crate) mod test_helpers {
         let capture = TracingCapture::new();
         let mut txn = catalog.start_transaction().await.unwrap();
         txn.namespaces()
-            .create("test_txn_drop", None)
+            .create("test_txn_drop", None, None)
             .await
             .unwrap();
         drop(txn);
@@ -3009,7 +3595,7 @@ pub(crate) mod test_helpers {
     where
         R: RepoCollection + ?Sized,
     {
-        let namespace = repos.namespaces().create(namespace_name, None).await;
+        let namespace = repos.namespaces().create(namespace_name, None, None).await;
 
         let namespace = match namespace {
             Ok(v) => v,
@@ -3096,6 +3682,480 @@ pub(crate) mod test_helpers {
         assert!(!got.contains(&ns2), "{:#?}\n\n do not want{:#?}", got, &ns2);
     }
 
+    async fn test_table_soft_deletion(catalog: Arc<dyn Catalog>) {
+        let mut repos = catalog.repositories().await;
+
+        let (namespace, schema) = populate_namespace(
+            repos.deref_mut(),
+            "ns_table_soft_delete",
+            "cpu,tag=1 field=1i\nmem,tag=1 field=1.0",
+        )
+        .await;
+        let cpu_id = schema.tables.get("cpu").unwrap().id;
+
+        repos
+            .tables()
+            .soft_delete(cpu_id)
+            .await
+            .expect("failed to soft delete table");
+
+        // The table record is retained, and marked as deleted.
+        let cpu = repos.tables().get_by_id(cpu_id).await.unwrap().unwrap();
+        assert!(cpu.deleted_at.is_some());
+
+        // The table is excluded from the namespace schema.
+        let got = get_schema_by_id(
+            namespace.id,
+            repos.deref_mut(),
+            SoftDeletedRows::ExcludeDeleted,
+        )
+        .await
+        .unwrap();
+        assert_eq!(got.tables.keys().collect::<Vec<_>>(), vec!["mem"]);
+
+        // Otherwise the in-mem catalog deadlocks.... (but not postgres)
+        drop(repos);
+
+        let got = list_schemas(&*catalog)
+            .await
+            .expect("should be able to list the schemas")
+            .find(|(ns, _)| ns.id == namespace.id)
+            .map(|(_, schema)| schema)
+            .unwrap();
+        assert_eq!(got.tables.keys().collect::<Vec<_>>(), vec!["mem"]);
+
+        let mut repos = catalog.repositories().await;
+
+        // The table is still included when asking for all rows, so that the
+        // remaining data can be processed.
+        let got = get_schema_by_id(namespace.id, repos.deref_mut(), SoftDeletedRows::AllRows)
+            .await
+            .unwrap();
+        assert_eq!(got.tables.keys().collect::<Vec<_>>(), vec!["cpu", "mem"]);
+
+        // The deleted table does not count towards the table limit.
+        repos
+            .namespaces()
+            .update_table_limit(&namespace.name, 2)
+            .await
+            .unwrap();
+        repos
+            .tables()
+            .create_or_get("disk", namespace.id)
+            .await
+            .expect("deleted table should not count towards the limit");
+
+        // Restoring the table makes it visible again.
+        repos
+            .tables()
+            .undelete(cpu_id)
+            .await
+            .expect("failed to undelete table");
+        let cpu = repos.tables().get_by_id(cpu_id).await.unwrap().unwrap();
+        assert!(cpu.deleted_at.is_none());
+        let got = get_schema_by_id(
+            namespace.id,
+            repos.deref_mut(),
+            SoftDeletedRows::ExcludeDeleted,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            got.tables.keys().collect::<Vec<_>>(),
+            vec!["cpu", "disk", "mem"]
+        );
+
+        // A write to a deleted table creates a new table of the same name.
+        repos
+            .tables()
+            .soft_delete(cpu_id)
+            .await
+            .expect("failed to soft delete table");
+        repos
+            .namespaces()
+            .update_table_limit(&namespace.name, 3)
+            .await
+            .unwrap();
+        let batches = mutable_batch_lp::lines_to_batches("cpu,tag=1 field=2i", 42).unwrap();
+        let got = validate_or_insert_schema(
+            batches.iter().map(|(table, batch)| (table.as_str(), batch)),
+            &NamespaceSchema::new_empty_from(&namespace),
+            repos.deref_mut(),
+        )
+        .await
+        .expect("write to a deleted table should succeed")
+        .expect("schema should be updated");
+        let new_cpu_id = got.tables.get("cpu").unwrap().id;
+        assert_ne!(new_cpu_id, cpu_id);
+
+        // The new table is returned when looking it up by name.
+        let cpu = repos
+            .tables()
+            .get_by_namespace_and_name(namespace.id, "cpu")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(cpu.id, new_cpu_id);
+        assert!(cpu.deleted_at.is_none());
+
+        // The deleted table cannot be restored while the new table exists.
+        let err = repos
+            .tables()
+            .undelete(cpu_id)
+            .await
+            .expect_err("undelete should fail");
+        assert_matches!(err, Error::NameExists { name } if name == "cpu");
+
+        // Unless the new table is also deleted, in which case the most
+        // recently created table is returned by name.
+        repos
+            .tables()
+            .soft_delete(new_cpu_id)
+            .await
+            .expect("failed to soft delete table");
+        let cpu = repos
+            .tables()
+            .get_by_namespace_and_name(namespace.id, "cpu")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(cpu.id, new_cpu_id);
+        repos
+            .tables()
+            .undelete(cpu_id)
+            .await
+            .expect("failed to undelete table");
+        let cpu = repos
+            .tables()
+            .get_by_namespace_and_name(namespace.id, "cpu")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(cpu.id, cpu_id);
+    }
+
+    async fn test_column_soft_deletion(catalog: Arc<dyn Catalog>) {
+        let mut repos = catalog.repositories().await;
+
+        let (namespace, schema) = populate_namespace(
+            repos.deref_mut(),
+            "ns_column_soft_delete",
+            "cpu,tag=1 field=1i,typo=1i",
+        )
+        .await;
+        let cpu = schema.tables.get("cpu").unwrap();
+        let typo_id = cpu.columns.get("typo").unwrap().id;
+
+        repos
+            .columns()
+            .soft_delete(typo_id)
+            .await
+            .expect("failed to soft delete column");
+
+        // The column record is retained, and marked as deleted.
+        let typo = repos
+            .columns()
+            .list_by_table_id(cpu.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|c| c.id == typo_id)
+            .unwrap();
+        assert!(typo.deleted_at.is_some());
+
+        // The column is excluded from the namespace schema, unless asking for
+        // all rows.
+        let got = get_schema_by_id(
+            namespace.id,
+            repos.deref_mut(),
+            SoftDeletedRows::ExcludeDeleted,
+        )
+        .await
+        .unwrap();
+        let got = got.tables.get("cpu").unwrap();
+        assert!(!got.columns.contains_column_name("typo"));
+        assert!(got.columns.contains_column_name("field"));
+
+        let got = get_schema_by_id(namespace.id, repos.deref_mut(), SoftDeletedRows::AllRows)
+            .await
+            .unwrap();
+        assert!(got
+            .tables
+            .get("cpu")
+            .unwrap()
+            .columns
+            .contains_column_name("typo"));
+
+        // Writes to the deleted column are rejected.
+        let batches = mutable_batch_lp::lines_to_batches("cpu typo=2i", 42).unwrap();
+        let err = validate_or_insert_schema(
+            batches.iter().map(|(table, batch)| (table.as_str(), batch)),
+            &NamespaceSchema::new_empty_from(&namespace),
+            repos.deref_mut(),
+        )
+        .await
+        .expect_err("write to a deleted column should fail");
+        assert_matches!(err.err(), Error::ColumnDeleted { name } if name == "typo");
+
+        // The deleted column does not count towards the column limit, which
+        // leaves room for one more column alongside tag, field and time.
+        repos
+            .namespaces()
+            .update_column_limit(&namespace.name, 4)
+            .await
+            .unwrap();
+        repos
+            .columns()
+            .create_or_get("fixed", cpu.id, ColumnType::I64)
+            .await
+            .expect("deleted column should not count towards the limit");
+
+        // Otherwise the in-mem catalog deadlocks.... (but not postgres)
+        drop(repos);
+
+        let got = list_schemas(&*catalog)
+            .await
+            .expect("should be able to list the schemas")
+            .find(|(ns, _)| ns.id == namespace.id)
+            .map(|(_, schema)| schema)
+            .unwrap();
+        assert!(!got
+            .tables
+            .get("cpu")
+            .unwrap()
+            .columns
+            .contains_column_name("typo"));
+
+        // Restoring the column makes it visible again.
+        let mut repos = catalog.repositories().await;
+        repos
+            .columns()
+            .undelete(typo_id)
+            .await
+            .expect("failed to undelete column");
+        let got = get_schema_by_id(
+            namespace.id,
+            repos.deref_mut(),
+            SoftDeletedRows::ExcludeDeleted,
+        )
+        .await
+        .unwrap();
+        assert!(got
+            .tables
+            .get("cpu")
+            .unwrap()
+            .columns
+            .contains_column_name("typo"));
+    }
+
+    async fn test_tombstone(catalog: Arc<dyn Catalog>) {
+        let mut repos = catalog.repositories().await;
+        let namespace = repos
+            .namespaces()
+            .create("namespace_tombstone_test", None, None)
+            .await
+            .unwrap();
+        let table = repos
+            .tables()
+            .create_or_get("test_table", namespace.id)
+            .await
+            .unwrap();
+        let other_table = repos
+            .tables()
+            .create_or_get("other", namespace.id)
+            .await
+            .unwrap();
+        let other_namespace = repos
+            .namespaces()
+            .create("namespace_tombstone_test_other", None, None)
+            .await
+            .unwrap();
+        let other_namespace_table = repos
+            .tables()
+            .create_or_get("test_table", other_namespace.id)
+            .await
+            .unwrap();
+
+        let t1 = repos
+            .tombstones()
+            .create(
+                table.id,
+                Timestamp::new(1),
+                Timestamp::new(10),
+                r#""city"='Boston'"#,
+            )
+            .await
+            .unwrap();
+        assert_eq!(t1.table_id, table.id);
+        assert_eq!(t1.min_time, Timestamp::new(1));
+        assert_eq!(t1.max_time, Timestamp::new(10));
+        assert_eq!(t1.serialized_predicate, r#""city"='Boston'"#);
+
+        let t2 = repos
+            .tombstones()
+            .create(table.id, Timestamp::new(5), Timestamp::new(20), "")
+            .await
+            .unwrap();
+        let t3 = repos
+            .tombstones()
+            .create(other_table.id, Timestamp::new(1), Timestamp::new(2), "")
+            .await
+            .unwrap();
+        let t4 = repos
+            .tombstones()
+            .create(
+                other_namespace_table.id,
+                Timestamp::new(1),
+                Timestamp::new(2),
+                "",
+            )
+            .await
+            .unwrap();
+        assert_ne!(t1.id, t2.id);
+
+        let listed = repos.tombstones().list_by_table_id(table.id).await.unwrap();
+        assert_eq!(listed, vec![t1.clone(), t2.clone()]);
+
+        let listed = repos
+            .tombstones()
+            .list_by_namespace_id(namespace.id)
+            .await
+            .unwrap();
+        assert_eq!(listed, vec![t1.clone(), t2.clone(), t3]);
+
+        let listed = repos
+            .tombstones()
+            .list_by_namespace_id(other_namespace.id)
+            .await
+            .unwrap();
+        assert_eq!(listed, vec![t4.clone()]);
+
+        let listed = repos
+            .tombstones()
+            .list_by_table_id(TableId::new(i64::MAX))
+            .await
+            .unwrap();
+        assert!(listed.is_empty());
+
+        // A tombstone cannot reference a table that does not exist.
+        repos
+            .tombstones()
+            .create(
+                TableId::new(i64::MAX),
+                Timestamp::new(1),
+                Timestamp::new(2),
+                "",
+            )
+            .await
+            .expect_err("tombstone for unknown table should fail");
+
+        // Record the parquet files the tombstones were applied to.
+        let partition = repos
+            .partitions()
+            .create_or_get("one".into(), table.id)
+            .await
+            .unwrap();
+        let file = repos
+            .parquet_files()
+            .create(ParquetFileParams {
+                namespace_id: namespace.id,
+                table_id: table.id,
+                partition_id: partition.id,
+                object_store_id: Uuid::new_v4(),
+                min_time: Timestamp::new(1),
+                max_time: Timestamp::new(10),
+                file_size_bytes: 1337,
+                row_count: 0,
+                compaction_level: CompactionLevel::Initial,
+                created_at: Timestamp::new(1),
+                column_set: ColumnSet::new([ColumnId::new(1)]),
+                max_l0_created_at: Timestamp::new(1),
+            })
+            .await
+            .unwrap();
+
+        let listed = repos
+            .tombstones()
+            .list_processed_by_table_id(table.id)
+            .await
+            .unwrap();
+        assert!(listed.is_empty());
+
+        repos
+            .tombstones()
+            .create_processed(t2.id, file.id)
+            .await
+            .unwrap();
+        repos
+            .tombstones()
+            .create_processed(t1.id, file.id)
+            .await
+            .unwrap();
+        // Recording the same pair again has no effect.
+        repos
+            .tombstones()
+            .create_processed(t1.id, file.id)
+            .await
+            .unwrap();
+
+        let listed = repos
+            .tombstones()
+            .list_processed_by_table_id(table.id)
+            .await
+            .unwrap();
+        assert_eq!(
+            listed,
+            vec![
+                ProcessedTombstone {
+                    tombstone_id: t1.id,
+                    parquet_file_id: file.id,
+                },
+                ProcessedTombstone {
+                    tombstone_id: t2.id,
+                    parquet_file_id: file.id,
+                },
+            ]
+        );
+        let listed = repos
+            .tombstones()
+            .list_processed_by_table_id(other_table.id)
+            .await
+            .unwrap();
+        assert!(listed.is_empty());
+
+        // A processed tombstone cannot reference a parquet file that does not exist.
+        repos
+            .tombstones()
+            .create_processed(t1.id, ParquetFileId::new(i64::MAX))
+            .await
+            .expect_err("processed tombstone for unknown file should fail");
+
+        // Deleting a tombstone removes its processed records.
+        repos.tombstones().delete(t1.id).await.unwrap();
+        let listed = repos.tombstones().list_by_table_id(table.id).await.unwrap();
+        assert_eq!(listed, vec![t2.clone()]);
+        let listed = repos
+            .tombstones()
+            .list_processed_by_table_id(table.id)
+            .await
+            .unwrap();
+        assert_eq!(
+            listed,
+            vec![ProcessedTombstone {
+                tombstone_id: t2.id,
+                parquet_file_id: file.id,
+            }]
+        );
+
+        // IDs are not reused once a tombstone is deleted.
+        let t5 = repos
+            .tombstones()
+            .create(table.id, Timestamp::new(1), Timestamp::new(2), "")
+            .await
+            .unwrap();
+        assert!(t5.id > t4.id);
+    }
+
     fn assert_metric_hit(metrics: &metric::Registry, name: &'static str) {
         let histogram = metrics
             .get_instrument::<Metric<DurationHistogram>>("catalog_op_duration")
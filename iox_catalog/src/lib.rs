@@ -13,7 +13,9 @@
     clippy::dbg_macro
 )]
 
-use crate::interface::{ColumnTypeMismatchSnafu, Error, RepoCollection, Result};
+use crate::interface::{
+    ColumnDeletedSnafu, ColumnTypeMismatchSnafu, Error, RepoCollection, Result,
+};
 use data_types::{ColumnType, NamespaceSchema, TableSchema};
 use mutable_batch::MutableBatch;
 use std::{borrow::Cow, collections::HashMap};
@@ -114,11 +116,13 @@ where
             //
             // Attempt to create the table in the catalog, or load an existing
             // table from the catalog to populate the cache.
-            let mut table = repos
-                .tables()
-                .create_or_get(table_name, schema.id)
-                .await
-                .map(|t| TableSchema::new_empty_from(&t))?;
+            //
+            // A soft-deleted table of the same name is not returned, and a
+            // new table is created in its place, as InfluxDB does for writes
+            // to a dropped measurement.
+            let table = repos.tables().create_or_get(table_name, schema.id).await?;
+
+            let mut table = TableSchema::new_empty_from(&table);
 
             // Always add a time column to all new tables.
             let time_col = repos
@@ -180,10 +184,16 @@ where
     }
 
     if !column_batch.is_empty() {
-        repos
+        let columns = repos
             .columns()
             .create_or_get_many_unchecked(table.id, column_batch)
-            .await?
+            .await?;
+
+        if let Some(c) = columns.iter().find(|c| c.deleted_at.is_some()) {
+            return ColumnDeletedSnafu { name: &c.name }.fail();
+        }
+
+        columns
             .into_iter()
             .for_each(|c| table.to_mut().add_column(c));
     }
@@ -238,7 +248,7 @@ mod tests {
 
                     let namespace = txn
                         .namespaces()
-                        .create(NAMESPACE_NAME, None)
+                        .create(NAMESPACE_NAME, None, None)
                         .await
                         .unwrap();
 
@@ -5,16 +5,17 @@ use crate::{
     interface::{
         sealed::TransactionFinalize, CasFailure, Catalog, ColumnRepo, ColumnTypeMismatchSnafu,
         Error, NamespaceRepo, ParquetFileRepo, PartitionRepo, RepoCollection, Result,
-        SoftDeletedRows, TableRepo, Transaction, MAX_PARQUET_FILES_SELECTED_ONCE,
+        SoftDeletedRows, TableRepo, TombstoneRepo, Transaction, MAX_PARQUET_FILES_SELECTED_ONCE,
     },
     metrics::MetricDecorator,
     DEFAULT_MAX_COLUMNS_PER_TABLE, DEFAULT_MAX_TABLES,
 };
 use async_trait::async_trait;
 use data_types::{
-    Column, ColumnId, ColumnType, CompactionLevel, Namespace, NamespaceId, ParquetFile,
-    ParquetFileId, ParquetFileParams, Partition, PartitionId, PartitionKey, SkippedCompaction,
-    Table, TableId, Timestamp,
+    Column, ColumnId, ColumnType, CompactionLevel, Namespace, NamespaceId,
+    NamespacePartitionTemplateOverride, ParquetFile, ParquetFileId, ParquetFileParams, Partition,
+    PartitionId, PartitionKey, ProcessedTombstone, SkippedCompaction, Table, TableId,
+    TablePartitionTemplateOverride, Timestamp, Tombstone, TombstoneId,
 };
 use iox_time::{SystemProvider, TimeProvider};
 use observability_deps::tracing::warn;
@@ -68,6 +69,9 @@ struct MemCollections {
     partitions: Vec<Partition>,
     skipped_compactions: Vec<SkippedCompaction>,
     parquet_files: Vec<ParquetFile>,
+    tombstones: Vec<Tombstone>,
+    processed_tombstones: Vec<ProcessedTombstone>,
+    last_tombstone_id: i64,
 }
 
 #[derive(Debug)]
@@ -213,11 +217,20 @@ impl RepoCollection for MemTxn {
     fn parquet_files(&mut self) -> &mut dyn ParquetFileRepo {
         self
     }
+
+    fn tombstones(&mut self) -> &mut dyn TombstoneRepo {
+        self
+    }
 }
 
 #[async_trait]
 impl NamespaceRepo for MemTxn {
-    async fn create(&mut self, name: &str, retention_period_ns: Option<i64>) -> Result<Namespace> {
+    async fn create(
+        &mut self,
+        name: &str,
+        partition_template: Option<NamespacePartitionTemplateOverride>,
+        retention_period_ns: Option<i64>,
+    ) -> Result<Namespace> {
         let stage = self.stage();
 
         if stage.namespaces.iter().any(|n| n.name == name) {
@@ -231,8 +244,12 @@ impl NamespaceRepo for MemTxn {
             name: name.to_string(),
             max_tables: DEFAULT_MAX_TABLES,
             max_columns_per_table: DEFAULT_MAX_COLUMNS_PER_TABLE,
+            max_concurrent_queries: None,
+            max_query_memory_bytes: None,
+            max_total_query_memory_bytes: None,
             retention_period_ns,
             deleted_at: None,
+            partition_template,
         };
         stage.namespaces.push(namespace);
         Ok(stage.namespaces.last().unwrap().clone())
@@ -287,6 +304,67 @@ impl NamespaceRepo for MemTxn {
         }
     }
 
+    async fn undelete(&mut self, name: &str) -> Result<Namespace> {
+        let stage = self.stage();
+        match stage.namespaces.iter_mut().find(|n| n.name == name) {
+            Some(n) if n.deleted_at.is_none() => Err(Error::NamespaceNotDeleted {
+                name: name.to_string(),
+            }),
+            Some(n) => {
+                n.deleted_at = None;
+                Ok(n.clone())
+            }
+            None => Err(Error::NamespaceNotFoundByName {
+                name: name.to_string(),
+            }),
+        }
+    }
+
+    async fn purge(&mut self, id: NamespaceId, older_than: Timestamp) -> Result<bool> {
+        let stage = self.stage();
+
+        let Some(idx) = stage
+            .namespaces
+            .iter()
+            .position(|n| n.id == id && matches!(n.deleted_at, Some(t) if t < older_than))
+        else {
+            return Ok(false);
+        };
+        stage.namespaces.remove(idx);
+
+        let table_ids: HashSet<_> = stage
+            .tables
+            .iter()
+            .filter(|t| t.namespace_id == id)
+            .map(|t| t.id)
+            .collect();
+        let partition_ids: HashSet<_> = stage
+            .partitions
+            .iter()
+            .filter(|p| table_ids.contains(&p.table_id))
+            .map(|p| p.id)
+            .collect();
+
+        stage.tables.retain(|t| t.namespace_id != id);
+        stage.columns.retain(|c| !table_ids.contains(&c.table_id));
+        stage
+            .partitions
+            .retain(|p| !table_ids.contains(&p.table_id));
+        stage
+            .skipped_compactions
+            .retain(|s| !partition_ids.contains(&s.partition_id));
+        stage.parquet_files.retain(|f| f.namespace_id != id);
+        stage
+            .tombstones
+            .retain(|t| !table_ids.contains(&t.table_id));
+        let tombstone_ids: HashSet<_> = stage.tombstones.iter().map(|t| t.id).collect();
+        stage
+            .processed_tombstones
+            .retain(|p| tombstone_ids.contains(&p.tombstone_id));
+
+        Ok(true)
+    }
+
     async fn update_table_limit(&mut self, name: &str, new_max: i32) -> Result<Namespace> {
         let stage = self.stage();
         match stage.namespaces.iter_mut().find(|n| n.name == name) {
@@ -313,6 +391,57 @@ impl NamespaceRepo for MemTxn {
         }
     }
 
+    async fn update_concurrent_query_limit(
+        &mut self,
+        name: &str,
+        new_max: Option<i32>,
+    ) -> Result<Namespace> {
+        let stage = self.stage();
+        match stage.namespaces.iter_mut().find(|n| n.name == name) {
+            Some(n) => {
+                n.max_concurrent_queries = new_max;
+                Ok(n.clone())
+            }
+            None => Err(Error::NamespaceNotFoundByName {
+                name: name.to_string(),
+            }),
+        }
+    }
+
+    async fn update_query_memory_limit(
+        &mut self,
+        name: &str,
+        new_max: Option<i64>,
+    ) -> Result<Namespace> {
+        let stage = self.stage();
+        match stage.namespaces.iter_mut().find(|n| n.name == name) {
+            Some(n) => {
+                n.max_query_memory_bytes = new_max;
+                Ok(n.clone())
+            }
+            None => Err(Error::NamespaceNotFoundByName {
+                name: name.to_string(),
+            }),
+        }
+    }
+
+    async fn update_total_query_memory_limit(
+        &mut self,
+        name: &str,
+        new_max: Option<i64>,
+    ) -> Result<Namespace> {
+        let stage = self.stage();
+        match stage.namespaces.iter_mut().find(|n| n.name == name) {
+            Some(n) => {
+                n.max_total_query_memory_bytes = new_max;
+                Ok(n.clone())
+            }
+            None => Err(Error::NamespaceNotFoundByName {
+                name: name.to_string(),
+            }),
+        }
+    }
+
     async fn update_retention_period(
         &mut self,
         name: &str,
@@ -338,7 +467,7 @@ impl TableRepo for MemTxn {
 
         // this block is just to ensure the mem impl correctly creates TableCreateLimitError in
         // tests, we don't care about any of the errors it is discarding
-        stage
+        let namespace_partition_template = stage
             .namespaces
             .iter()
             .find(|n| n.id == namespace_id)
@@ -353,7 +482,7 @@ impl TableRepo for MemTxn {
                 let tables_count = stage
                     .tables
                     .iter()
-                    .filter(|t| t.namespace_id == namespace_id)
+                    .filter(|t| t.namespace_id == namespace_id && t.deleted_at.is_none())
                     .count();
                 if tables_count >= max_tables.try_into().unwrap() {
                     return Err(Error::TableCreateLimitError {
@@ -361,20 +490,21 @@ impl TableRepo for MemTxn {
                         namespace_id,
                     });
                 }
-                Ok(())
+                Ok(n.partition_template)
             })?;
 
-        let table = match stage
-            .tables
-            .iter()
-            .find(|t| t.name == name && t.namespace_id == namespace_id)
-        {
+        // A soft-deleted table of the same name is replaced by a new table.
+        let is_live =
+            |t: &&Table| t.name == name && t.namespace_id == namespace_id && t.deleted_at.is_none();
+        let table = match stage.tables.iter().find(is_live) {
             Some(t) => t,
             None => {
                 let table = Table {
                     id: TableId::new(stage.tables.len() as i64 + 1),
                     namespace_id,
                     name: name.to_string(),
+                    partition_template: namespace_partition_template.as_ref().map(Into::into),
+                    deleted_at: None,
                 };
                 stage.tables.push(table);
                 stage.tables.last().unwrap()
@@ -384,6 +514,55 @@ impl TableRepo for MemTxn {
         Ok(table.clone())
     }
 
+    async fn create(
+        &mut self,
+        name: &str,
+        partition_template: Option<TablePartitionTemplateOverride>,
+        namespace_id: NamespaceId,
+    ) -> Result<Table> {
+        let stage = self.stage();
+
+        let namespace = stage
+            .namespaces
+            .iter()
+            .find(|n| n.id == namespace_id)
+            .ok_or(Error::NamespaceNotFoundById { id: namespace_id })?;
+
+        let tables_count = stage
+            .tables
+            .iter()
+            .filter(|t| t.namespace_id == namespace_id && t.deleted_at.is_none())
+            .count();
+        if tables_count >= namespace.max_tables.try_into().unwrap() {
+            return Err(Error::TableCreateLimitError {
+                table_name: name.to_string(),
+                namespace_id,
+            });
+        }
+
+        if stage
+            .tables
+            .iter()
+            .any(|t| t.name == name && t.namespace_id == namespace_id && t.deleted_at.is_none())
+        {
+            return Err(Error::NameExists {
+                name: name.to_string(),
+            });
+        }
+
+        let table = Table {
+            id: TableId::new(stage.tables.len() as i64 + 1),
+            namespace_id,
+            name: name.to_string(),
+            partition_template: partition_template
+                .or_else(|| namespace.partition_template.as_ref().map(Into::into)),
+            deleted_at: None,
+        };
+        stage.tables.push(table);
+
+        Ok(stage.tables.last().unwrap().clone())
+    }
+
     async fn get_by_id(&mut self, table_id: TableId) -> Result<Option<Table>> {
         let stage = self.stage();
 
@@ -400,7 +579,8 @@ impl TableRepo for MemTxn {
         Ok(stage
             .tables
             .iter()
-            .find(|t| t.namespace_id == namespace_id && t.name == name)
+            .filter(|t| t.namespace_id == namespace_id && t.name == name)
+            .max_by_key(|t| (t.deleted_at.is_none(), t.id))
             .cloned())
     }
 
@@ -420,6 +600,48 @@ impl TableRepo for MemTxn {
         let stage = self.stage();
         Ok(stage.tables.clone())
     }
+
+    async fn soft_delete(&mut self, table_id: TableId) -> Result<()> {
+        let timestamp = self.time_provider.now();
+        let stage = self.stage();
+        match stage.tables.iter_mut().find(|t| t.id == table_id) {
+            Some(t) => {
+                t.deleted_at = Some(Timestamp::from(timestamp));
+                Ok(())
+            }
+            None => Err(Error::TableNotFound { id: table_id }),
+        }
+    }
+
+    async fn undelete(&mut self, table_id: TableId) -> Result<()> {
+        let stage = self.stage();
+        let table = stage
+            .tables
+            .iter()
+            .find(|t| t.id == table_id)
+            .ok_or(Error::TableNotFound { id: table_id })?;
+
+        // Fails if a table of the same name has been created since this one
+        // was deleted.
+        if stage.tables.iter().any(|t| {
+            t.id != table_id
+                && t.namespace_id == table.namespace_id
+                && t.name == table.name
+                && t.deleted_at.is_none()
+        }) {
+            return Err(Error::NameExists {
+                name: table.name.clone(),
+            });
+        }
+
+        let table = stage
+            .tables
+            .iter_mut()
+            .find(|t| t.id == table_id)
+            .expect("table exists");
+        table.deleted_at = None;
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -456,7 +678,7 @@ impl ColumnRepo for MemTxn {
                         let columns_count = stage
                             .columns
                             .iter()
-                            .filter(|t| t.table_id == table_id)
+                            .filter(|c| c.table_id == table_id && c.deleted_at.is_none())
                             .count();
                         if columns_count >= max_columns_per_table.try_into().unwrap() {
                             return Err(Error::ColumnCreateLimitError {
@@ -491,6 +713,7 @@ impl ColumnRepo for MemTxn {
                     table_id,
                     name: name.to_string(),
                     column_type,
+                    deleted_at: None,
                 };
                 stage.columns.push(column);
                 stage.columns.last().unwrap()
@@ -536,6 +759,7 @@ impl ColumnRepo for MemTxn {
                             table_id,
                             name: column_name.to_string(),
                             column_type,
+                            deleted_at: None,
                         };
                         stage.columns.push(new_column);
                         Ok(stage.columns.last().unwrap().clone())
@@ -583,6 +807,29 @@ impl ColumnRepo for MemTxn {
         let stage = self.stage();
         Ok(stage.columns.clone())
     }
+
+    async fn soft_delete(&mut self, column_id: ColumnId) -> Result<()> {
+        let timestamp = self.time_provider.now();
+        let stage = self.stage();
+        match stage.columns.iter_mut().find(|c| c.id == column_id) {
+            Some(c) => {
+                c.deleted_at = Some(Timestamp::from(timestamp));
+                Ok(())
+            }
+            None => Err(Error::ColumnNotFound { id: column_id }),
+        }
+    }
+
+    async fn undelete(&mut self, column_id: ColumnId) -> Result<()> {
+        let stage = self.stage();
+        match stage.columns.iter_mut().find(|c| c.id == column_id) {
+            Some(c) => {
+                c.deleted_at = None;
+                Ok(())
+            }
+            None => Err(Error::ColumnNotFound { id: column_id }),
+        }
+    }
 }
 
 #[async_trait]
@@ -902,6 +1149,11 @@ impl ParquetFileRepo for MemTxn {
         );
 
         stage.parquet_files = keep;
+        stage.processed_tombstones.retain(|p| {
+            !delete
+                .iter()
+                .any(|f: &ParquetFile| f.id == p.parquet_file_id)
+        });
 
         let delete = delete
             .into_iter()
@@ -977,6 +1229,126 @@ impl ParquetFileRepo for MemTxn {
     }
 }
 
+#[async_trait]
+impl TombstoneRepo for MemTxn {
+    async fn create(
+        &mut self,
+        table_id: TableId,
+        min_time: Timestamp,
+        max_time: Timestamp,
+        serialized_predicate: &str,
+    ) -> Result<Tombstone> {
+        let created_at = Timestamp::from(self.time_provider.now());
+        let stage = self.stage();
+
+        if !stage.tables.iter().any(|t| t.id == table_id) {
+            return Err(Error::TableNotFound { id: table_id });
+        }
+
+        let tombstone = Tombstone {
+            id: TombstoneId::new(stage.last_tombstone_id + 1),
+            table_id,
+            min_time,
+            max_time,
+            serialized_predicate: serialized_predicate.to_string(),
+            created_at,
+        };
+        stage.last_tombstone_id = tombstone.id.get();
+        stage.tombstones.push(tombstone.clone());
+
+        Ok(tombstone)
+    }
+
+    async fn list_by_table_id(&mut self, table_id: TableId) -> Result<Vec<Tombstone>> {
+        let stage = self.stage();
+
+        Ok(stage
+            .tombstones
+            .iter()
+            .filter(|t| t.table_id == table_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn list_by_namespace_id(&mut self, namespace_id: NamespaceId) -> Result<Vec<Tombstone>> {
+        let stage = self.stage();
+
+        let table_ids: HashSet<_> = stage
+            .tables
+            .iter()
+            .filter(|t| t.namespace_id == namespace_id)
+            .map(|t| t.id)
+            .collect();
+
+        Ok(stage
+            .tombstones
+            .iter()
+            .filter(|t| table_ids.contains(&t.table_id))
+            .cloned()
+            .collect())
+    }
+
+    async fn create_processed(
+        &mut self,
+        tombstone_id: TombstoneId,
+        parquet_file_id: ParquetFileId,
+    ) -> Result<()> {
+        let stage = self.stage();
+
+        if !stage.tombstones.iter().any(|t| t.id == tombstone_id) {
+            return Err(Error::TombstoneNotFound { id: tombstone_id });
+        }
+        if !stage.parquet_files.iter().any(|f| f.id == parquet_file_id) {
+            return Err(Error::ParquetRecordNotFound {
+                id: parquet_file_id,
+            });
+        }
+
+        let processed = ProcessedTombstone {
+            tombstone_id,
+            parquet_file_id,
+        };
+        if !stage.processed_tombstones.contains(&processed) {
+            stage.processed_tombstones.push(processed);
+        }
+
+        Ok(())
+    }
+
+    async fn list_processed_by_table_id(
+        &mut self,
+        table_id: TableId,
+    ) -> Result<Vec<ProcessedTombstone>> {
+        let stage = self.stage();
+
+        let tombstone_ids: HashSet<_> = stage
+            .tombstones
+            .iter()
+            .filter(|t| t.table_id == table_id)
+            .map(|t| t.id)
+            .collect();
+
+        let mut processed: Vec<_> = stage
+            .processed_tombstones
+            .iter()
+            .filter(|p| tombstone_ids.contains(&p.tombstone_id))
+            .copied()
+            .collect();
+        processed.sort();
+
+        Ok(processed)
+    }
+
+    async fn delete(&mut self, id: TombstoneId) -> Result<()> {
+        let stage = self.stage();
+
+        stage.tombstones.retain(|t| t.id != id);
+        stage.processed_tombstones.retain(|p| p.tombstone_id != id);
+
+        Ok(())
+    }
+}
+
 fn filter_namespace_soft_delete<'a>(
     v: impl IntoIterator<Item = &'a Namespace>,
     deleted: SoftDeletedRows,
@@ -2,13 +2,14 @@
 
 use crate::interface::{
     sealed::TransactionFinalize, CasFailure, ColumnRepo, NamespaceRepo, ParquetFileRepo,
-    PartitionRepo, RepoCollection, Result, SoftDeletedRows, TableRepo,
+    PartitionRepo, RepoCollection, Result, SoftDeletedRows, TableRepo, TombstoneRepo,
 };
 use async_trait::async_trait;
 use data_types::{
-    Column, ColumnType, CompactionLevel, Namespace, NamespaceId, ParquetFile, ParquetFileId,
-    ParquetFileParams, Partition, PartitionId, PartitionKey, SkippedCompaction, Table, TableId,
-    Timestamp,
+    Column, ColumnId, ColumnType, CompactionLevel, Namespace, NamespaceId,
+    NamespacePartitionTemplateOverride, ParquetFile, ParquetFileId, ParquetFileParams, Partition,
+    PartitionId, PartitionKey, ProcessedTombstone, SkippedCompaction, Table, TableId,
+    TablePartitionTemplateOverride, Timestamp, Tombstone, TombstoneId,
 };
 use iox_time::{SystemProvider, TimeProvider};
 use metric::{DurationHistogram, Metric};
@@ -41,7 +42,13 @@ impl<T> MetricDecorator<T> {
 
 impl<T, P> RepoCollection for MetricDecorator<T, P>
 where
-    T: NamespaceRepo + TableRepo + ColumnRepo + PartitionRepo + ParquetFileRepo + Debug,
+    T: NamespaceRepo
+        + TableRepo
+        + ColumnRepo
+        + PartitionRepo
+        + ParquetFileRepo
+        + TombstoneRepo
+        + Debug,
     P: TimeProvider,
 {
     fn namespaces(&mut self) -> &mut dyn NamespaceRepo {
@@ -63,6 +70,10 @@ where
     fn parquet_files(&mut self) -> &mut dyn ParquetFileRepo {
         self
     }
+
+    fn tombstones(&mut self) -> &mut dyn TombstoneRepo {
+        self
+    }
 }
 
 #[async_trait]
@@ -145,14 +156,19 @@ macro_rules! decorate {
 decorate!(
     impl_trait = NamespaceRepo,
     methods = [
-        "namespace_create" = create(&mut self, name: &str, retention_period_ns: Option<i64>) -> Result<Namespace>;
+        "namespace_create" = create(&mut self, name: &str, partition_template: Option<NamespacePartitionTemplateOverride>, retention_period_ns: Option<i64>) -> Result<Namespace>;
         "namespace_update_retention_period" = update_retention_period(&mut self, name: &str, retention_period_ns: Option<i64>) -> Result<Namespace>;
         "namespace_list" = list(&mut self, deleted: SoftDeletedRows) -> Result<Vec<Namespace>>;
         "namespace_get_by_id" = get_by_id(&mut self, id: NamespaceId, deleted: SoftDeletedRows) -> Result<Option<Namespace>>;
         "namespace_get_by_name" = get_by_name(&mut self, name: &str, deleted: SoftDeletedRows) -> Result<Option<Namespace>>;
         "namespace_soft_delete" = soft_delete(&mut self, name: &str) -> Result<()>;
+        "namespace_undelete" = undelete(&mut self, name: &str) -> Result<Namespace>;
+        "namespace_purge" = purge(&mut self, id: NamespaceId, older_than: Timestamp) -> Result<bool>;
         "namespace_update_table_limit" = update_table_limit(&mut self, name: &str, new_max: i32) -> Result<Namespace>;
         "namespace_update_column_limit" = update_column_limit(&mut self, name: &str, new_max: i32) -> Result<Namespace>;
+        "namespace_update_concurrent_query_limit" = update_concurrent_query_limit(&mut self, name: &str, new_max: Option<i32>) -> Result<Namespace>;
+        "namespace_update_query_memory_limit" = update_query_memory_limit(&mut self, name: &str, new_max: Option<i64>) -> Result<Namespace>;
+        "namespace_update_total_query_memory_limit" = update_total_query_memory_limit(&mut self, name: &str, new_max: Option<i64>) -> Result<Namespace>;
     ]
 );
 
@@ -160,10 +176,13 @@ decorate!(
     impl_trait = TableRepo,
     methods = [
         "table_create_or_get" = create_or_get(&mut self, name: &str, namespace_id: NamespaceId) -> Result<Table>;
+        "table_create" = create(&mut self, name: &str, partition_template: Option<TablePartitionTemplateOverride>, namespace_id: NamespaceId) -> Result<Table>;
         "table_get_by_id" = get_by_id(&mut self, table_id: TableId) -> Result<Option<Table>>;
         "table_get_by_namespace_and_name" = get_by_namespace_and_name(&mut self, namespace_id: NamespaceId, name: &str) -> Result<Option<Table>>;
         "table_list_by_namespace_id" = list_by_namespace_id(&mut self, namespace_id: NamespaceId) -> Result<Vec<Table>>;
         "table_list" = list(&mut self) -> Result<Vec<Table>>;
+        "table_soft_delete" = soft_delete(&mut self, table_id: TableId) -> Result<()>;
+        "table_undelete" = undelete(&mut self, table_id: TableId) -> Result<()>;
     ]
 );
 
@@ -175,6 +194,8 @@ decorate!(
         "column_list_by_table_id" = list_by_table_id(&mut self, table_id: TableId) -> Result<Vec<Column>>;
         "column_create_or_get_many_unchecked" = create_or_get_many_unchecked(&mut self, table_id: TableId, columns: HashMap<&str, ColumnType>) -> Result<Vec<Column>>;
         "column_list" = list(&mut self) -> Result<Vec<Column>>;
+        "column_soft_delete" = soft_delete(&mut self, column_id: ColumnId) -> Result<()>;
+        "column_undelete" = undelete(&mut self, column_id: ColumnId) -> Result<()>;
     ]
 );
 
@@ -212,3 +233,15 @@ decorate!(
         "parquet_get_by_object_store_id" = get_by_object_store_id(&mut self, object_store_id: Uuid) -> Result<Option<ParquetFile>>;
     ]
 );
+
+decorate!(
+    impl_trait = TombstoneRepo,
+    methods = [
+        "tombstone_create" = create(&mut self, table_id: TableId, min_time: Timestamp, max_time: Timestamp, serialized_predicate: &str) -> Result<Tombstone>;
+        "tombstone_list_by_table_id" = list_by_table_id(&mut self, table_id: TableId) -> Result<Vec<Tombstone>>;
+        "tombstone_list_by_namespace_id" = list_by_namespace_id(&mut self, namespace_id: NamespaceId) -> Result<Vec<Tombstone>>;
+        "tombstone_create_processed" = create_processed(&mut self, tombstone_id: TombstoneId, parquet_file_id: ParquetFileId) -> Result<()>;
+        "tombstone_list_processed_by_table_id" = list_processed_by_table_id(&mut self, table_id: TableId) -> Result<Vec<ProcessedTombstone>>;
+        "tombstone_delete" = delete(&mut self, id: TombstoneId) -> Result<()>;
+    ]
+);
@@ -4,7 +4,7 @@ use crate::{
     interface::{
         self, sealed::TransactionFinalize, CasFailure, Catalog, ColumnRepo,
         ColumnTypeMismatchSnafu, Error, NamespaceRepo, ParquetFileRepo, PartitionRepo,
-        RepoCollection, Result, SoftDeletedRows, TableRepo, Transaction,
+        RepoCollection, Result, SoftDeletedRows, TableRepo, TombstoneRepo, Transaction,
         MAX_PARQUET_FILES_SELECTED_ONCE,
     },
     kafkaless_transition::{
@@ -16,9 +16,10 @@ use crate::{
 };
 use async_trait::async_trait;
 use data_types::{
-    Column, ColumnType, CompactionLevel, Namespace, NamespaceId, ParquetFile, ParquetFileId,
-    ParquetFileParams, Partition, PartitionId, PartitionKey, SkippedCompaction, Table, TableId,
-    Timestamp,
+    Column, ColumnId, ColumnType, CompactionLevel, Namespace, NamespaceId,
+    NamespacePartitionTemplateOverride, ParquetFile, ParquetFileId, ParquetFileParams, Partition,
+    PartitionId, PartitionKey, ProcessedTombstone, SkippedCompaction, Table, TableId,
+    TablePartitionTemplateOverride, Timestamp, Tombstone, TombstoneId,
 };
 use iox_time::{SystemProvider, TimeProvider};
 use observability_deps::tracing::{debug, info, warn};
@@ -574,23 +575,33 @@ impl RepoCollection for PostgresTxn {
     fn parquet_files(&mut self) -> &mut dyn ParquetFileRepo {
         self
     }
+
+    fn tombstones(&mut self) -> &mut dyn TombstoneRepo {
+        self
+    }
 }
 
 #[async_trait]
 impl NamespaceRepo for PostgresTxn {
-    async fn create(&mut self, name: &str, retention_period_ns: Option<i64>) -> Result<Namespace> {
+    async fn create(
+        &mut self,
+        name: &str,
+        partition_template: Option<NamespacePartitionTemplateOverride>,
+        retention_period_ns: Option<i64>,
+    ) -> Result<Namespace> {
         let rec = sqlx::query_as::<_, Namespace>(
             r#"
-                INSERT INTO namespace ( name, topic_id, query_pool_id, retention_period_ns, max_tables )
-                VALUES ( $1, $2, $3, $4, $5 )
-                RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at;
+                INSERT INTO namespace ( name, topic_id, query_pool_id, retention_period_ns, max_tables, partition_template )
+                VALUES ( $1, $2, $3, $4, $5, $6 )
+                RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, max_concurrent_queries, max_query_memory_bytes, max_total_query_memory_bytes, deleted_at, partition_template;
             "#,
         )
         .bind(name) // $1
         .bind(SHARED_TOPIC_ID) // $2
         .bind(SHARED_QUERY_POOL_ID) // $3
         .bind(retention_period_ns) // $4
-        .bind(DEFAULT_MAX_TABLES); // $5
+        .bind(DEFAULT_MAX_TABLES) // $5
+        .bind(partition_template); // $6
 
         let rec = rec.fetch_one(&mut self.inner).await.map_err(|e| {
             if is_unique_violation(&e) {
@@ -615,7 +626,7 @@ impl NamespaceRepo for PostgresTxn {
         let rec = sqlx::query_as::<_, Namespace>(
             format!(
                 r#"
-SELECT id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at
+SELECT id, name, retention_period_ns, max_tables, max_columns_per_table, max_concurrent_queries, max_query_memory_bytes, max_total_query_memory_bytes, deleted_at, partition_template
 FROM namespace
 WHERE {v};
                 "#,
@@ -638,7 +649,7 @@ WHERE {v};
         let rec = sqlx::query_as::<_, Namespace>(
             format!(
                 r#"
-SELECT id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at
+SELECT id, name, retention_period_ns, max_tables, max_columns_per_table, max_concurrent_queries, max_query_memory_bytes, max_total_query_memory_bytes, deleted_at, partition_template
 FROM namespace
 WHERE id=$1 AND {v};
                 "#,
@@ -667,7 +678,7 @@ WHERE id=$1 AND {v};
         let rec = sqlx::query_as::<_, Namespace>(
             format!(
                 r#"
-SELECT id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at
+SELECT id, name, retention_period_ns, max_tables, max_columns_per_table, max_concurrent_queries, max_query_memory_bytes, max_total_query_memory_bytes, deleted_at, partition_template
 FROM namespace
 WHERE name=$1 AND {v};
                 "#,
@@ -701,13 +712,56 @@ WHERE name=$1 AND {v};
             .map(|_| ())
     }
 
+    async fn undelete(&mut self, name: &str) -> Result<Namespace> {
+        let rec = sqlx::query_as::<_, Namespace>(
+            r#"
+UPDATE namespace
+SET deleted_at = NULL
+WHERE name = $1 AND deleted_at IS NOT NULL
+RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, max_concurrent_queries, max_query_memory_bytes, max_total_query_memory_bytes, deleted_at, partition_template;
+        "#,
+        )
+        .bind(name) // $1
+        .fetch_one(&mut self.inner)
+        .await;
+
+        match rec {
+            Ok(namespace) => Ok(namespace),
+            // Distinguish a namespace that is not deleted from one that does not exist.
+            Err(sqlx::Error::RowNotFound) => {
+                match self.get_by_name(name, SoftDeletedRows::AllRows).await? {
+                    Some(_) => Err(Error::NamespaceNotDeleted {
+                        name: name.to_string(),
+                    }),
+                    None => Err(Error::NamespaceNotFoundByName {
+                        name: name.to_string(),
+                    }),
+                }
+            }
+            Err(e) => Err(Error::SqlxError { source: e }),
+        }
+    }
+
+    async fn purge(&mut self, id: NamespaceId, older_than: Timestamp) -> Result<bool> {
+        // the tables, columns, partitions, tombstones and parquet files of the namespace are
+        // removed by the cascading foreign keys
+        let result = sqlx::query(r#"DELETE FROM namespace WHERE id = $1 AND deleted_at < $2;"#)
+            .bind(id) // $1
+            .bind(older_than) // $2
+            .execute(&mut self.inner)
+            .await
+            .context(interface::CouldNotPurgeNamespaceSnafu)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
     async fn update_table_limit(&mut self, name: &str, new_max: i32) -> Result<Namespace> {
         let rec = sqlx::query_as::<_, Namespace>(
             r#"
 UPDATE namespace
 SET max_tables = $1
 WHERE name = $2
-RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at;
+RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, max_concurrent_queries, max_query_memory_bytes, max_total_query_memory_bytes, deleted_at, partition_template;
         "#,
         )
         .bind(new_max)
@@ -731,7 +785,91 @@ RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, dele
 UPDATE namespace
 SET max_columns_per_table = $1
 WHERE name = $2
-RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at;
+RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, max_concurrent_queries, max_query_memory_bytes, max_total_query_memory_bytes, deleted_at, partition_template;
+        "#,
+        )
+        .bind(new_max)
+        .bind(name)
+        .fetch_one(&mut self.inner)
+        .await;
+
+        let namespace = rec.map_err(|e| match e {
+            sqlx::Error::RowNotFound => Error::NamespaceNotFoundByName {
+                name: name.to_string(),
+            },
+            _ => Error::SqlxError { source: e },
+        })?;
+
+        Ok(namespace)
+    }
+
+    async fn update_concurrent_query_limit(
+        &mut self,
+        name: &str,
+        new_max: Option<i32>,
+    ) -> Result<Namespace> {
+        let rec = sqlx::query_as::<_, Namespace>(
+            r#"
+UPDATE namespace
+SET max_concurrent_queries = $1
+WHERE name = $2
+RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, max_concurrent_queries, max_query_memory_bytes, max_total_query_memory_bytes, deleted_at, partition_template;
+        "#,
+        )
+        .bind(new_max)
+        .bind(name)
+        .fetch_one(&mut self.inner)
+        .await;
+
+        let namespace = rec.map_err(|e| match e {
+            sqlx::Error::RowNotFound => Error::NamespaceNotFoundByName {
+                name: name.to_string(),
+            },
+            _ => Error::SqlxError { source: e },
+        })?;
+
+        Ok(namespace)
+    }
+
+    async fn update_query_memory_limit(
+        &mut self,
+        name: &str,
+        new_max: Option<i64>,
+    ) -> Result<Namespace> {
+        let rec = sqlx::query_as::<_, Namespace>(
+            r#"
+UPDATE namespace
+SET max_query_memory_bytes = $1
+WHERE name = $2
+RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, max_concurrent_queries, max_query_memory_bytes, max_total_query_memory_bytes, deleted_at, partition_template;
+        "#,
+        )
+        .bind(new_max)
+        .bind(name)
+        .fetch_one(&mut self.inner)
+        .await;
+
+        let namespace = rec.map_err(|e| match e {
+            sqlx::Error::RowNotFound => Error::NamespaceNotFoundByName {
+                name: name.to_string(),
+            },
+            _ => Error::SqlxError { source: e },
+        })?;
+
+        Ok(namespace)
+    }
+
+    async fn update_total_query_memory_limit(
+        &mut self,
+        name: &str,
+        new_max: Option<i64>,
+    ) -> Result<Namespace> {
+        let rec = sqlx::query_as::<_, Namespace>(
+            r#"
+UPDATE namespace
+SET max_total_query_memory_bytes = $1
+WHERE name = $2
+RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, max_concurrent_queries, max_query_memory_bytes, max_total_query_memory_bytes, deleted_at, partition_template;
         "#,
         )
         .bind(new_max)
@@ -759,7 +897,7 @@ RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, dele
 UPDATE namespace
 SET retention_period_ns = $1
 WHERE name = $2
-RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, deleted_at;
+RETURNING id, name, retention_period_ns, max_tables, max_columns_per_table, max_concurrent_queries, max_query_memory_bytes, max_total_query_memory_bytes, deleted_at, partition_template;
         "#,
         )
         .bind(retention_period_ns) // $1
@@ -790,16 +928,20 @@ impl TableRepo for PostgresTxn {
         // By using SELECT rather than VALUES it will insert zero rows if it finds a null in the
         // subquery, i.e. if count >= max_tables. fetch_one() will return a RowNotFound error if
         // nothing was inserted. Not pretty!
+        //
+        // The new table inherits the partition template of its namespace.
         let rec = sqlx::query_as::<_, Table>(
             r#"
-INSERT INTO table_name ( name, namespace_id )
-SELECT $1, id FROM (
-    SELECT namespace.id AS id, max_tables, COUNT(table_name.*) AS count
-    FROM namespace LEFT JOIN table_name ON namespace.id = table_name.namespace_id
+INSERT INTO table_name ( name, namespace_id, partition_template )
+SELECT $1, id, partition_template FROM (
+    SELECT namespace.id AS id, namespace.partition_template AS partition_template, max_tables,
+        COUNT(table_name.*) AS count
+    FROM namespace LEFT JOIN table_name
+        ON namespace.id = table_name.namespace_id AND table_name.deleted_at IS NULL
     WHERE namespace.id = $2
-    GROUP BY namespace.max_tables, table_name.namespace_id, namespace.id
+    GROUP BY namespace.max_tables, namespace.partition_template, table_name.namespace_id, namespace.id
 ) AS get_count WHERE count < max_tables
-ON CONFLICT ON CONSTRAINT table_name_unique
+ON CONFLICT (namespace_id, name) WHERE deleted_at IS NULL
 DO UPDATE SET name = table_name.name
 RETURNING *;
         "#,
@@ -825,6 +967,53 @@ RETURNING *;
         Ok(rec)
     }
 
+    async fn create(
+        &mut self,
+        name: &str,
+        partition_template: Option<TablePartitionTemplateOverride>,
+        namespace_id: NamespaceId,
+    ) -> Result<Table> {
+        // See `create_or_get` for the table limit check.
+        let rec = sqlx::query_as::<_, Table>(
+            r#"
+INSERT INTO table_name ( name, namespace_id, partition_template )
+SELECT $1, id, COALESCE($3, partition_template) FROM (
+    SELECT namespace.id AS id, namespace.partition_template AS partition_template, max_tables,
+        COUNT(table_name.*) AS count
+    FROM namespace LEFT JOIN table_name
+        ON namespace.id = table_name.namespace_id AND table_name.deleted_at IS NULL
+    WHERE namespace.id = $2
+    GROUP BY namespace.max_tables, namespace.partition_template, table_name.namespace_id, namespace.id
+) AS get_count WHERE count < max_tables
+RETURNING *;
+        "#,
+        )
+        .bind(name) // $1
+        .bind(namespace_id) // $2
+        .bind(partition_template) // $3
+        .fetch_one(&mut self.inner)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => Error::TableCreateLimitError {
+                table_name: name.to_string(),
+                namespace_id,
+            },
+            _ => {
+                if is_unique_violation(&e) {
+                    Error::NameExists {
+                        name: name.to_string(),
+                    }
+                } else if is_fk_violation(&e) {
+                    Error::ForeignKeyViolation { source: e }
+                } else {
+                    Error::SqlxError { source: e }
+                }
+            }
+        })?;
+
+        Ok(rec)
+    }
+
     async fn get_by_id(&mut self, table_id: TableId) -> Result<Option<Table>> {
         let rec = sqlx::query_as::<_, Table>(
             r#"
@@ -855,7 +1044,9 @@ WHERE id = $1;
             r#"
 SELECT *
 FROM table_name
-WHERE namespace_id = $1 AND name = $2;
+WHERE namespace_id = $1 AND name = $2
+ORDER BY deleted_at IS NULL DESC, id DESC
+LIMIT 1;
             "#,
         )
         .bind(namespace_id) // $1
@@ -896,6 +1087,40 @@ WHERE namespace_id = $1;
 
         Ok(rec)
     }
+
+    async fn soft_delete(&mut self, table_id: TableId) -> Result<()> {
+        let flagged_at = Timestamp::from(self.time_provider.now());
+
+        sqlx::query(r#"UPDATE table_name SET deleted_at=$1 WHERE id = $2;"#)
+            .bind(flagged_at) // $1
+            .bind(table_id) // $2
+            .execute(&mut self.inner)
+            .await
+            .context(interface::CouldNotDeleteTableSnafu)
+            .map(|_| ())
+    }
+
+    async fn undelete(&mut self, table_id: TableId) -> Result<()> {
+        let table = self
+            .get_by_id(table_id)
+            .await?
+            .ok_or(Error::TableNotFound { id: table_id })?;
+
+        // Fails if a table of the same name has been created since this one
+        // was deleted.
+        sqlx::query(r#"UPDATE table_name SET deleted_at=NULL WHERE id = $1;"#)
+            .bind(table_id) // $1
+            .execute(&mut self.inner)
+            .await
+            .map_err(|e| {
+                if is_unique_violation(&e) {
+                    Error::NameExists { name: table.name }
+                } else {
+                    Error::CouldNotUndeleteTable { source: e }
+                }
+            })
+            .map(|_| ())
+    }
 }
 
 #[async_trait]
@@ -912,7 +1137,8 @@ INSERT INTO column_name ( name, table_id, column_type )
 SELECT $1, table_id, $3 FROM (
     SELECT max_columns_per_table, namespace.id, table_name.id as table_id, COUNT(column_name.*) AS count
     FROM namespace LEFT JOIN table_name ON namespace.id = table_name.namespace_id
-                   LEFT JOIN column_name ON table_name.id = column_name.table_id
+                   LEFT JOIN column_name
+                       ON table_name.id = column_name.table_id AND column_name.deleted_at IS NULL
     WHERE table_name.id = $2
     GROUP BY namespace.max_columns_per_table, namespace.id, table_name.id
 ) AS get_count WHERE count < max_columns_per_table
@@ -991,6 +1217,27 @@ WHERE table_id = $1;
         Ok(rec)
     }
 
+    async fn soft_delete(&mut self, column_id: ColumnId) -> Result<()> {
+        let flagged_at = Timestamp::from(self.time_provider.now());
+
+        sqlx::query(r#"UPDATE column_name SET deleted_at=$1 WHERE id = $2;"#)
+            .bind(flagged_at) // $1
+            .bind(column_id) // $2
+            .execute(&mut self.inner)
+            .await
+            .context(interface::CouldNotDeleteColumnSnafu)
+            .map(|_| ())
+    }
+
+    async fn undelete(&mut self, column_id: ColumnId) -> Result<()> {
+        sqlx::query(r#"UPDATE column_name SET deleted_at=NULL WHERE id = $1;"#)
+            .bind(column_id) // $1
+            .execute(&mut self.inner)
+            .await
+            .context(interface::CouldNotUndeleteColumnSnafu)
+            .map(|_| ())
+    }
+
     async fn create_or_get_many_unchecked(
         &mut self,
         table_id: TableId,
@@ -1598,6 +1845,136 @@ WHERE object_store_id = $1;
     }
 }
 
+#[async_trait]
+impl TombstoneRepo for PostgresTxn {
+    async fn create(
+        &mut self,
+        table_id: TableId,
+        min_time: Timestamp,
+        max_time: Timestamp,
+        serialized_predicate: &str,
+    ) -> Result<Tombstone> {
+        let created_at = Timestamp::from(self.time_provider.now());
+
+        sqlx::query_as::<_, Tombstone>(
+            r#"
+INSERT INTO tombstone ( table_id, min_time, max_time, serialized_predicate, created_at )
+VALUES ( $1, $2, $3, $4, $5 )
+RETURNING id, table_id, min_time, max_time, serialized_predicate, created_at;
+        "#,
+        )
+        .bind(table_id) // $1
+        .bind(min_time) // $2
+        .bind(max_time) // $3
+        .bind(serialized_predicate) // $4
+        .bind(created_at) // $5
+        .fetch_one(&mut self.inner)
+        .await
+        .map_err(|e| {
+            if is_fk_violation(&e) {
+                Error::ForeignKeyViolation { source: e }
+            } else {
+                Error::SqlxError { source: e }
+            }
+        })
+    }
+
+    async fn list_by_table_id(&mut self, table_id: TableId) -> Result<Vec<Tombstone>> {
+        sqlx::query_as::<_, Tombstone>(
+            r#"
+SELECT id, table_id, min_time, max_time, serialized_predicate, created_at
+FROM tombstone
+WHERE table_id = $1
+ORDER BY id;
+             "#,
+        )
+        .bind(table_id) // $1
+        .fetch_all(&mut self.inner)
+        .await
+        .map_err(|e| Error::SqlxError { source: e })
+    }
+
+    async fn list_by_namespace_id(&mut self, namespace_id: NamespaceId) -> Result<Vec<Tombstone>> {
+        sqlx::query_as::<_, Tombstone>(
+            r#"
+SELECT tombstone.id, tombstone.table_id, tombstone.min_time, tombstone.max_time,
+       tombstone.serialized_predicate, tombstone.created_at
+FROM tombstone
+INNER JOIN table_name on table_name.id = tombstone.table_id
+WHERE table_name.namespace_id = $1
+ORDER BY tombstone.id;
+             "#,
+        )
+        .bind(namespace_id) // $1
+        .fetch_all(&mut self.inner)
+        .await
+        .map_err(|e| Error::SqlxError { source: e })
+    }
+
+    async fn create_processed(
+        &mut self,
+        tombstone_id: TombstoneId,
+        parquet_file_id: ParquetFileId,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+INSERT INTO processed_tombstone ( tombstone_id, parquet_file_id )
+VALUES ( $1, $2 )
+ON CONFLICT DO NOTHING;
+        "#,
+        )
+        .bind(tombstone_id) // $1
+        .bind(parquet_file_id) // $2
+        .execute(&mut self.inner)
+        .await
+        .map_err(|e| {
+            if is_fk_violation(&e) {
+                Error::ForeignKeyViolation { source: e }
+            } else {
+                Error::SqlxError { source: e }
+            }
+        })?;
+
+        Ok(())
+    }
+
+    async fn list_processed_by_table_id(
+        &mut self,
+        table_id: TableId,
+    ) -> Result<Vec<ProcessedTombstone>> {
+        sqlx::query_as::<_, ProcessedTombstone>(
+            r#"
+SELECT processed_tombstone.tombstone_id, processed_tombstone.parquet_file_id
+FROM processed_tombstone
+INNER JOIN tombstone on tombstone.id = processed_tombstone.tombstone_id
+WHERE tombstone.table_id = $1
+ORDER BY processed_tombstone.tombstone_id, processed_tombstone.parquet_file_id;
+             "#,
+        )
+        .bind(table_id) // $1
+        .fetch_all(&mut self.inner)
+        .await
+        .map_err(|e| Error::SqlxError { source: e })
+    }
+
+    async fn delete(&mut self, id: TombstoneId) -> Result<()> {
+        // The records of the files the tombstone was applied to are removed
+        // by the cascading foreign key.
+        sqlx::query(
+            r#"
+DELETE FROM tombstone
+WHERE id = $1;
+        "#,
+        )
+        .bind(id) // $1
+        .execute(&mut self.inner)
+        .await
+        .map_err(|e| Error::SqlxError { source: e })?;
+
+        Ok(())
+    }
+}
+
 /// The error code returned by Postgres for a unique constraint violation.
 ///
 /// See <https://www.postgresql.org/docs/9.2/errcodes-appendix.html>
@@ -1822,7 +2199,7 @@ mod tests {
             .repositories()
             .await
             .namespaces()
-            .create("ns4", None)
+            .create("ns4", None, None)
             .await
             .expect("namespace create failed")
             .id;
@@ -1960,7 +2337,7 @@ mod tests {
                         .repositories()
                         .await
                         .namespaces()
-                        .create("ns4", None)
+                        .create("ns4", None, None)
                         .await
                         .expect("namespace create failed")
                         .id;
@@ -2123,7 +2500,7 @@ mod tests {
             .repositories()
             .await
             .namespaces()
-            .create("ns4", None)
+            .create("ns4", None, None)
             .await
             .expect("namespace create failed")
             .id;
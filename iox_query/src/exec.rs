@@ -5,6 +5,7 @@ pub(crate) mod context;
 pub mod field;
 pub mod fieldlist;
 pub mod gapfill;
+pub mod memory_tracker;
 mod non_null_checker;
 mod query_tracing;
 mod schema_pivot;
@@ -23,6 +24,7 @@ use datafusion::{
     self,
     execution::{
         disk_manager::DiskManagerConfig,
+        memory_pool::MemoryPool,
         runtime_env::{RuntimeConfig, RuntimeEnv},
     },
     logical_expr::{expr_rewriter::normalize_col, Extension},
@@ -207,6 +209,11 @@ impl Executor {
         self.new_execution_config(executor_type).build()
     }
 
+    /// The DataFusion memory pool shared by all executions.
+    pub fn memory_pool(&self) -> Arc<dyn MemoryPool> {
+        Arc::clone(&self.runtime.memory_pool)
+    }
+
     /// Return the execution pool  of the specified type
     pub fn executor(&self, executor_type: ExecutorType) -> &DedicatedExecutor {
         match executor_type {
@@ -12,6 +12,7 @@ use crate::{
     config::IoxConfigExt,
     exec::{
         fieldlist::{FieldList, IntoFieldList},
+        memory_tracker::{LimitedMemoryPool, TrackedMemoryPool},
         non_null_checker::NonNullCheckerExec,
         query_tracing::TracedStream,
         schema_pivot::{SchemaPivotExec, SchemaPivotNode},
@@ -174,6 +175,9 @@ pub struct IOxSessionConfig {
     /// Shared DataFusion runtime
     runtime: Arc<RuntimeEnv>,
 
+    /// Pool from which the memory of this query is reserved
+    memory_pool: Arc<dyn MemoryPool>,
+
     /// Default catalog
     default_catalog: Option<Arc<dyn CatalogProvider>>,
 
@@ -195,10 +199,13 @@ impl IOxSessionConfig {
             .extensions
             .insert(IoxConfigExt::default());
 
+        let memory_pool = Arc::clone(&runtime.memory_pool);
+
         Self {
             exec,
             session_config,
             runtime,
+            memory_pool,
             default_catalog: None,
             span_ctx: None,
         }
@@ -225,6 +232,43 @@ impl IOxSessionConfig {
         Self { span_ctx, ..self }
     }
 
+    /// Attach an extension to the DataFusion session of this query.
+    ///
+    /// Extensions can be retrieved by the table providers and execution plans of the query from
+    /// the [`SessionConfig`].
+    pub fn with_extension<T>(self, ext: Arc<T>) -> Self
+    where
+        T: Send + Sync + 'static,
+    {
+        Self {
+            session_config: self.session_config.with_extension(ext),
+            ..self
+        }
+    }
+
+    /// Reserve the memory of this query from `memory_pool` instead of the pool of the executor.
+    ///
+    /// `memory_pool` should forward its reservations to the pool of the executor (see
+    /// [`Executor::memory_pool`]), so that the limit of the executor still applies.
+    ///
+    /// [`Executor::memory_pool`]: crate::exec::Executor::memory_pool
+    pub fn with_memory_pool(self, memory_pool: Arc<dyn MemoryPool>) -> Self {
+        Self {
+            memory_pool,
+            ..self
+        }
+    }
+
+    /// Fail the query once it reserves more than `limit` bytes, on top of the limit of its
+    /// memory pool.
+    pub fn with_memory_limit(self, limit: usize) -> Self {
+        let memory_pool = Arc::new(LimitedMemoryPool::new(self.memory_pool, limit));
+        Self {
+            memory_pool,
+            ..self
+        }
+    }
+
     /// Set DataFusion [config option].
     ///
     /// May be used to set [IOx-specific] option as well.
@@ -255,7 +299,15 @@ impl IOxSessionConfig {
             .session_config
             .with_extension(Arc::new(recorder.span().cloned()));
 
-        let state = SessionState::with_config_rt(session_config, self.runtime)
+        // track the memory used by this query, while still drawing from the shared pool
+        let memory_tracker = Arc::new(TrackedMemoryPool::new(self.memory_pool));
+        let runtime = Arc::new(RuntimeEnv {
+            memory_pool: Arc::clone(&memory_tracker) as _,
+            disk_manager: Arc::clone(&self.runtime.disk_manager),
+            object_store_registry: Arc::clone(&self.runtime.object_store_registry),
+        });
+
+        let state = SessionState::with_config_rt(session_config, runtime)
             .with_query_planner(Arc::new(IOxQueryPlanner {}));
         let state = register_iox_physical_optimizers(state);
         let state = register_iox_logical_optimizers(state);
@@ -267,7 +319,7 @@ impl IOxSessionConfig {
             inner.register_catalog(DEFAULT_CATALOG, default_catalog);
         }
 
-        IOxSessionContext::new(inner, self.exec, recorder)
+        IOxSessionContext::new(inner, self.exec, recorder, memory_tracker)
     }
 }
 
@@ -296,6 +348,9 @@ pub struct IOxSessionContext {
 
     /// Span context from which to create spans for this query
     recorder: SpanRecorder,
+
+    /// Memory reserved by this query from the DataFusion memory pool
+    memory_tracker: Arc<TrackedMemoryPool>,
 }
 
 impl fmt::Debug for IOxSessionContext {
@@ -314,10 +369,16 @@ impl IOxSessionContext {
     /// This is identical to [`Default::default`] but we do NOT implement [`Default`] to make the creation of untracked
     /// contexts more explicit.
     pub fn with_testing() -> Self {
+        let inner = SessionContext::default();
+        let memory_tracker = Arc::new(TrackedMemoryPool::new(Arc::clone(
+            &inner.runtime_env().memory_pool,
+        )));
+
         Self {
-            inner: SessionContext::default(),
+            inner,
             exec: DedicatedExecutor::new_testing(),
             recorder: SpanRecorder::default(),
+            memory_tracker,
         }
     }
 
@@ -326,11 +387,13 @@ impl IOxSessionContext {
         inner: SessionContext,
         exec: DedicatedExecutor,
         recorder: SpanRecorder,
+        memory_tracker: Arc<TrackedMemoryPool>,
     ) -> Self {
         Self {
             inner,
             exec,
             recorder,
+            memory_tracker,
         }
     }
 
@@ -662,6 +725,7 @@ impl IOxSessionContext {
             self.inner.clone(),
             self.exec.clone(),
             self.recorder.child(name),
+            Arc::clone(&self.memory_tracker),
         )
     }
 
@@ -685,6 +749,11 @@ impl IOxSessionContext {
         self.recorder.child_span(name)
     }
 
+    /// Tracker of the memory this query reserves from the DataFusion memory pool.
+    pub fn memory_tracker(&self) -> &Arc<TrackedMemoryPool> {
+        &self.memory_tracker
+    }
+
     /// Number of currently active tasks.
     pub fn tasks(&self) -> usize {
         self.exec.tasks()
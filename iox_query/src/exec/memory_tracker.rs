@@ -0,0 +1,202 @@
+//! Per-query accounting and limits of DataFusion memory usage.
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use datafusion::{
+    error::{DataFusionError, Result},
+    execution::memory_pool::{MemoryConsumer, MemoryPool, MemoryReservation},
+};
+
+/// A [`MemoryPool`] that forwards all reservations to a shared pool while keeping track of the
+/// memory reserved through it.
+///
+/// Each query runs with its own [`TrackedMemoryPool`] wrapping the pool of the executor, so
+/// that the memory limit is still enforced across all queries while the peak usage of a
+/// single query can be reported.
+#[derive(Debug)]
+pub struct TrackedMemoryPool {
+    inner: Arc<dyn MemoryPool>,
+    reserved: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+impl TrackedMemoryPool {
+    /// Track reservations made through this pool, forwarding them to `inner`.
+    pub fn new(inner: Arc<dyn MemoryPool>) -> Self {
+        Self {
+            inner,
+            reserved: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+        }
+    }
+
+    /// The largest number of bytes reserved through this pool at any one time.
+    pub fn peak(&self) -> usize {
+        self.peak.load(Ordering::Relaxed)
+    }
+
+    fn add(&self, additional: usize) {
+        let reserved = self.reserved.fetch_add(additional, Ordering::Relaxed) + additional;
+        self.peak.fetch_max(reserved, Ordering::Relaxed);
+    }
+}
+
+impl MemoryPool for TrackedMemoryPool {
+    fn register(&self, consumer: &MemoryConsumer) {
+        self.inner.register(consumer)
+    }
+
+    fn unregister(&self, consumer: &MemoryConsumer) {
+        self.inner.unregister(consumer)
+    }
+
+    fn grow(&self, reservation: &MemoryReservation, additional: usize) {
+        self.inner.grow(reservation, additional);
+        self.add(additional);
+    }
+
+    fn shrink(&self, reservation: &MemoryReservation, shrink: usize) {
+        self.inner.shrink(reservation, shrink);
+        self.reserved.fetch_sub(shrink, Ordering::Relaxed);
+    }
+
+    fn try_grow(&self, reservation: &MemoryReservation, additional: usize) -> Result<()> {
+        self.inner.try_grow(reservation, additional)?;
+        self.add(additional);
+        Ok(())
+    }
+
+    fn reserved(&self) -> usize {
+        self.inner.reserved()
+    }
+}
+
+/// A [`MemoryPool`] that forwards all reservations to a shared pool, failing reservations that
+/// would take the memory reserved through it above a limit.
+///
+/// This bounds a subset of the users of the shared pool, e.g. a single query or all queries
+/// against one namespace, without lowering the limit of the shared pool for everyone else.
+#[derive(Debug)]
+pub struct LimitedMemoryPool {
+    inner: Arc<dyn MemoryPool>,
+    limit: usize,
+    reserved: AtomicUsize,
+}
+
+impl LimitedMemoryPool {
+    /// Allow at most `limit` bytes to be reserved from `inner` through this pool.
+    pub fn new(inner: Arc<dyn MemoryPool>, limit: usize) -> Self {
+        Self {
+            inner,
+            limit,
+            reserved: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl MemoryPool for LimitedMemoryPool {
+    fn register(&self, consumer: &MemoryConsumer) {
+        self.inner.register(consumer)
+    }
+
+    fn unregister(&self, consumer: &MemoryConsumer) {
+        self.inner.unregister(consumer)
+    }
+
+    fn grow(&self, reservation: &MemoryReservation, additional: usize) {
+        self.inner.grow(reservation, additional);
+        self.reserved.fetch_add(additional, Ordering::Relaxed);
+    }
+
+    fn shrink(&self, reservation: &MemoryReservation, shrink: usize) {
+        self.inner.shrink(reservation, shrink);
+        self.reserved.fetch_sub(shrink, Ordering::Relaxed);
+    }
+
+    fn try_grow(&self, reservation: &MemoryReservation, additional: usize) -> Result<()> {
+        self.reserved
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |reserved| {
+                reserved
+                    .checked_add(additional)
+                    .filter(|&new_reserved| new_reserved <= self.limit)
+            })
+            .map_err(|reserved| {
+                DataFusionError::ResourcesExhausted(format!(
+                    "Failed to allocate additional {additional} bytes with {reserved} bytes \
+                     already allocated - memory limit is {} bytes",
+                    self.limit
+                ))
+            })?;
+
+        if let Err(e) = self.inner.try_grow(reservation, additional) {
+            self.reserved.fetch_sub(additional, Ordering::Relaxed);
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    fn reserved(&self) -> usize {
+        self.reserved.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::execution::memory_pool::GreedyMemoryPool;
+
+    use super::*;
+
+    #[test]
+    fn test_peak() {
+        let shared: Arc<dyn MemoryPool> = Arc::new(GreedyMemoryPool::new(100));
+        let tracker = Arc::new(TrackedMemoryPool::new(Arc::clone(&shared)));
+        let pool: Arc<dyn MemoryPool> = Arc::clone(&tracker) as _;
+
+        let mut reservation = MemoryConsumer::new("test").register(&pool);
+        reservation.try_grow(30).unwrap();
+        reservation.shrink(20);
+        reservation.grow(5);
+        assert_eq!(shared.reserved(), 15);
+        assert_eq!(tracker.peak(), 30);
+
+        // the limit of the shared pool still applies
+        reservation.try_grow(90).unwrap_err();
+        assert_eq!(tracker.peak(), 30);
+
+        drop(reservation);
+        assert_eq!(shared.reserved(), 0);
+        assert_eq!(tracker.peak(), 30);
+    }
+
+    #[test]
+    fn test_limit() {
+        let shared: Arc<dyn MemoryPool> = Arc::new(GreedyMemoryPool::new(100));
+        let limited: Arc<dyn MemoryPool> =
+            Arc::new(LimitedMemoryPool::new(Arc::clone(&shared), 50));
+
+        let mut reservation = MemoryConsumer::new("test").register(&limited);
+        reservation.try_grow(30).unwrap();
+        let err = reservation.try_grow(30).unwrap_err();
+        assert!(matches!(err, DataFusionError::ResourcesExhausted(_)));
+        assert_eq!(limited.reserved(), 30);
+        assert_eq!(shared.reserved(), 30);
+
+        // the limit of the shared pool still applies
+        let mut other = MemoryConsumer::new("other").register(&shared);
+        other.try_grow(60).unwrap();
+        reservation.try_grow(20).unwrap_err();
+        assert_eq!(limited.reserved(), 30);
+
+        reservation.shrink(30);
+        reservation.try_grow(40).unwrap();
+        assert_eq!(limited.reserved(), 40);
+        assert_eq!(shared.reserved(), 100);
+
+        drop(reservation);
+        assert_eq!(limited.reserved(), 0);
+        assert_eq!(shared.reserved(), 60);
+    }
+}
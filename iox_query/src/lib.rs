@@ -16,8 +16,13 @@ use arrow::{
 };
 use async_trait::async_trait;
 use data_types::{ChunkId, ChunkOrder, DeletePredicate, InfluxDbType, PartitionId, TableSummary};
-use datafusion::{error::DataFusionError, prelude::SessionContext};
+use datafusion::{
+    error::DataFusionError,
+    physical_plan::{ExecutionPlan, SendableRecordBatchStream},
+    prelude::SessionContext,
+};
 use exec::{stringset::StringSet, IOxSessionContext};
+use futures::{future::BoxFuture, stream::BoxStream};
 use hashbrown::HashMap;
 use observability_deps::tracing::{debug, trace};
 use once_cell::sync::Lazy;
@@ -27,21 +32,33 @@ use schema::{
     sort::{SortKey, SortKeyBuilder},
     Projection, Schema, TIME_COLUMN_NAME,
 };
-use std::{any::Any, collections::BTreeSet, fmt::Debug, iter::FromIterator, sync::Arc};
+use std::{
+    any::Any,
+    collections::BTreeSet,
+    fmt::{Debug, Display},
+    iter::FromIterator,
+    sync::Arc,
+    time::Instant,
+};
 
 pub mod config;
 pub mod exec;
 pub mod frontend;
 pub mod logical_optimizer;
+pub mod params;
 pub mod physical_optimizer;
 pub mod plan;
 pub mod provider;
 pub mod pruning;
+pub mod query_cancellation;
+pub mod query_stats;
 pub mod statistics;
 pub mod util;
 
 pub use frontend::common::ScanPlanBuilder;
+pub use query_cancellation::{CancelQueryError, QueryAbortedError, QueryCancellation};
 pub use query_functions::group_by::{Aggregate, WindowDuration};
+pub use query_stats::{QueryResultCounter, QueryStats};
 
 /// The name of the virtual column that represents the chunk order.
 pub const CHUNK_ORDER_COLUMN_NAME: &str = "__chunk_order";
@@ -106,31 +123,102 @@ pub trait QueryChunkMeta {
 /// a `QueryNamespace`. It is used to trigger side-effects (such as query timing)
 /// on query completion.
 ///
+/// The code running the query reports its progress and resource usage to the
+/// token, which passes them on as [`QueryStats`] when it is dropped.
 pub struct QueryCompletedToken {
     /// If this query completed successfully
     success: bool,
 
+    /// When the query was recorded, used to time planning.
+    start: Instant,
+
+    /// Statistics reported so far.
+    stats: QueryStats,
+
+    /// Rows and bytes returned so far.
+    result_counter: QueryResultCounter,
+
+    /// Aborts the execution of this query.
+    cancellation: QueryCancellation,
+
     /// Function invoked when the token is dropped. It is passed the
-    /// vaue of `self.success`
-    f: Option<Box<dyn FnOnce(bool) + Send>>,
+    /// vaue of `self.success` and the collected statistics.
+    f: Option<Box<dyn FnOnce(bool, QueryStats) + Send>>,
 }
 
 impl Debug for QueryCompletedToken {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("QueryCompletedToken")
             .field("success", &self.success)
+            .field("stats", &self.stats)
             .finish()
     }
 }
 
 impl QueryCompletedToken {
-    pub fn new(f: impl FnOnce(bool) + Send + 'static) -> Self {
+    pub fn new(f: impl FnOnce(bool, QueryStats) + Send + 'static) -> Self {
         Self {
             success: false,
+            start: Instant::now(),
+            stats: QueryStats::default(),
+            result_counter: QueryResultCounter::default(),
+            cancellation: QueryCancellation::default(),
             f: Some(Box::new(f)),
         }
     }
 
+    /// Abort the execution of this query through `cancellation`.
+    pub fn with_cancellation(self, cancellation: QueryCancellation) -> Self {
+        Self {
+            cancellation,
+            ..self
+        }
+    }
+
+    /// Wrap the results `stream` of this query so that it fails once the query is cancelled or
+    /// exceeds its maximum runtime, see [`QueryCancellation`].
+    pub fn abortable(&self, stream: SendableRecordBatchStream) -> SendableRecordBatchStream {
+        self.cancellation.abortable(self.start, stream)
+    }
+
+    /// Like [`abortable`](Self::abortable), but for results that are not record batches. The
+    /// stream fails with the error returned by `on_abort`.
+    pub fn abortable_stream<T, E>(
+        &self,
+        stream: BoxStream<'static, Result<T, E>>,
+        on_abort: fn(QueryAbortedError) -> E,
+    ) -> BoxStream<'static, Result<T, E>>
+    where
+        T: Send + 'static,
+        E: Send + 'static,
+    {
+        self.cancellation
+            .abortable_stream(self.start, stream, on_abort)
+    }
+
+    /// Future that resolves once this query is cancelled or exceeds its maximum runtime.
+    ///
+    /// Work that does not produce a results stream can race against it to be aborted.
+    pub fn aborted(&self) -> BoxFuture<'static, QueryAbortedError> {
+        self.cancellation.aborted(self.start)
+    }
+
+    /// Record that planning of this query completed with the physical `plan`.
+    pub fn set_planned(&mut self, plan: &dyn ExecutionPlan) {
+        self.stats.planning_duration = Some(self.start.elapsed());
+        self.stats.add_plan(plan);
+    }
+
+    /// Record that this query failed with `error`.
+    pub fn set_error(&mut self, error: impl Display) {
+        self.stats.error = Some(error.to_string());
+    }
+
+    /// Counter for the results returned by this query.
+    pub fn result_counter(&self) -> QueryResultCounter {
+        self.result_counter.clone()
+    }
+
     /// Record that this query completed successfully
     pub fn set_success(&mut self) {
         self.success = true;
@@ -140,7 +228,11 @@ impl QueryCompletedToken {
 impl Drop for QueryCompletedToken {
     fn drop(&mut self) {
         if let Some(f) = self.f.take() {
-            (f)(self.success)
+            let mut stats = std::mem::take(&mut self.stats);
+            stats.rows_returned = self.result_counter.rows();
+            stats.bytes_returned = self.result_counter.bytes();
+
+            (f)(self.success, stats)
         }
     }
 }
@@ -180,6 +272,11 @@ pub trait QueryNamespace: QueryNamespaceMeta + Debug + Send + Sync {
         query_text: QueryText,
     ) -> QueryCompletedToken;
 
+    /// Cancel the running query with the given id.
+    ///
+    /// Ids are assigned to the queries recorded through [`record_query`](Self::record_query).
+    fn cancel_query(&self, query_id: &str) -> Result<(), CancelQueryError>;
+
     /// Upcast to [`QueryNamespaceMeta`].
     ///
     /// This is required until <https://github.com/rust-lang/rust/issues/65991> is fixed.
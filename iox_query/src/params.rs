@@ -0,0 +1,93 @@
+//! Values for the named parameters of a query
+
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+};
+
+/// A set of values for the named parameters of a query, keyed by name.
+pub type StatementParams = HashMap<String, StatementParam>;
+
+/// The value bound to a named query parameter.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StatementParam {
+    /// A boolean value.
+    Boolean(bool),
+    /// A signed 64-bit integer value.
+    Integer(i64),
+    /// An unsigned 64-bit integer value.
+    Unsigned(u64),
+    /// A 64-bit floating point value.
+    Float(f64),
+    /// A UTF-8 string value.
+    String(String),
+}
+
+impl Display for StatementParam {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Boolean(v) => Display::fmt(v, f),
+            Self::Integer(v) => Display::fmt(v, f),
+            Self::Unsigned(v) => Display::fmt(v, f),
+            Self::Float(v) => Display::fmt(v, f),
+            Self::String(v) => write!(f, "'{v}'"),
+        }
+    }
+}
+
+impl From<bool> for StatementParam {
+    fn from(v: bool) -> Self {
+        Self::Boolean(v)
+    }
+}
+
+impl From<i64> for StatementParam {
+    fn from(v: i64) -> Self {
+        Self::Integer(v)
+    }
+}
+
+impl From<u64> for StatementParam {
+    fn from(v: u64) -> Self {
+        Self::Unsigned(v)
+    }
+}
+
+impl From<f64> for StatementParam {
+    fn from(v: f64) -> Self {
+        Self::Float(v)
+    }
+}
+
+impl From<&str> for StatementParam {
+    fn from(v: &str) -> Self {
+        Self::String(v.to_string())
+    }
+}
+
+impl From<String> for StatementParam {
+    fn from(v: String) -> Self {
+        Self::String(v)
+    }
+}
+
+impl TryFrom<serde_json::Value> for StatementParam {
+    /// The value, if it is not a boolean, number or string.
+    type Error = serde_json::Value;
+
+    fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {
+        match value {
+            serde_json::Value::Bool(v) => Ok(Self::Boolean(v)),
+            serde_json::Value::Number(v) => Ok(if let Some(v) = v.as_i64() {
+                Self::Integer(v)
+            } else if let Some(v) = v.as_u64() {
+                Self::Unsigned(v)
+            } else {
+                // a JSON number is always representable as an f64
+                Self::Float(v.as_f64().unwrap_or_default())
+            }),
+            serde_json::Value::String(v) => Ok(Self::String(v)),
+            _ => Err(value),
+        }
+    }
+}
@@ -2,7 +2,6 @@
 
 use async_trait::async_trait;
 use data_types::DeletePredicate;
-use hashbrown::HashMap;
 use std::{collections::HashSet, sync::Arc};
 
 use arrow::{
@@ -17,12 +16,12 @@ use datafusion::{
     optimizer::utils::{conjunction, split_conjunction},
     physical_plan::{
         expressions::col as physical_col, filter::FilterExec, projection::ProjectionExec,
-        sorts::sort::SortExec, ExecutionPlan,
+        sorts::sort::SortExec, union::UnionExec, ExecutionPlan,
     },
     prelude::Expr,
 };
 use observability_deps::tracing::trace;
-use predicate::Predicate;
+use predicate::{delete_predicate::negated_delete_expr, Predicate};
 use schema::{sort::SortKey, Schema};
 
 use crate::{
@@ -227,36 +226,64 @@ impl TableProvider for ChunkTableProvider {
         let pk = self.iox_schema().primary_key();
         let dedup_sort_key = SortKey::from_columns(pk.iter().copied());
 
-        let mut chunks_by_delete_predicates =
-            HashMap::<&[Arc<DeletePredicate>], Vec<Arc<dyn QueryChunk>>>::new();
+        // Group the chunks by their delete predicates, in the order they are first seen so that plans are stable.
+        let mut chunks_by_delete_predicates: Vec<(
+            &[Arc<DeletePredicate>],
+            Vec<Arc<dyn QueryChunk>>,
+        )> = vec![];
         for chunk in &self.chunks {
-            chunks_by_delete_predicates
-                .entry(chunk.delete_predicates())
-                .or_default()
-                .push(Arc::clone(chunk));
+            let delete_predicates = chunk.delete_predicates();
+            match chunks_by_delete_predicates
+                .iter_mut()
+                .find(|(preds, _)| *preds == delete_predicates)
+            {
+                Some((_, chunks)) => chunks.push(Arc::clone(chunk)),
+                None => {
+                    chunks_by_delete_predicates.push((delete_predicates, vec![Arc::clone(chunk)]))
+                }
+            }
         }
 
-        let (delete_predicates, chunks) = match chunks_by_delete_predicates.len() {
-            0 => (&[] as _, vec![]),
-            1 => chunks_by_delete_predicates
+        let target_partitions = ctx.config().target_partitions();
+        let (plan, negated_del_expr_val) = if chunks_by_delete_predicates.len() <= 1 {
+            let (delete_predicates, chunks) = chunks_by_delete_predicates
                 .into_iter()
                 .next()
-                .expect("checked len"),
-            n => {
-                return Err(DataFusionError::External(
-                    format!("expected at most 1 delete predicate set, got {n}").into(),
-                ));
-            }
-        };
+                .unwrap_or((&[] as _, vec![]));
 
-        // Create data stream from chunk data. This is the most simple data stream possible and contains duplicates and
-        // has no filters at all.
-        let plan = chunks_to_physical_nodes(
-            &schema_with_chunk_order,
-            None,
-            chunks,
-            ctx.config().target_partitions(),
-        );
+            // Create data stream from chunk data. This is the most simple data stream possible and contains duplicates
+            // and has no filters at all.
+            let plan =
+                chunks_to_physical_nodes(&schema_with_chunk_order, None, chunks, target_partitions);
+
+            (plan, negated_delete_expr(delete_predicates))
+        } else {
+            // Chunks are subject to different delete predicates (e.g. a delete only applies to the data persisted
+            // before it), so the rows of each set of chunks are deleted before the data is de-duplicated.
+            let plans = chunks_by_delete_predicates
+                .into_iter()
+                .map(|(delete_predicates, chunks)| {
+                    let plan = chunks_to_physical_nodes(
+                        &schema_with_chunk_order,
+                        None,
+                        chunks,
+                        target_partitions,
+                    );
+                    match negated_delete_expr(delete_predicates) {
+                        Some(expr) => Ok(Arc::new(FilterExec::try_new(
+                            df_physical_expr(plan.as_ref(), expr)?,
+                            plan,
+                        )?) as Arc<dyn ExecutionPlan>),
+                        None => Ok(plan),
+                    }
+                })
+                .collect::<DataFusionResult<Vec<_>>>()?;
+
+            (
+                Arc::new(UnionExec::new(plans)) as Arc<dyn ExecutionPlan>,
+                None,
+            )
+        };
 
         // De-dup before doing anything else, because all logical expressions act on de-duplicated data.
         let plan = if self.deduplication {
@@ -266,13 +293,6 @@ impl TableProvider for ChunkTableProvider {
             plan
         };
 
-        // Convert delete predicates to DF
-        let del_preds: Vec<Arc<Predicate>> = delete_predicates
-            .iter()
-            .map(|pred| Arc::new(pred.as_ref().clone().into()))
-            .collect();
-        let negated_del_expr_val = Predicate::negated_expr(&del_preds[..]);
-
         // Filter as early as possible (AFTER de-dup!). Predicate pushdown will eventually push down parts of this.
         let plan = if let Some(expr) = filters
             .iter()
@@ -811,10 +831,22 @@ mod test {
             .build()
             .unwrap();
 
-        let err = provider.scan(&state, None, &[], None).await.unwrap_err();
-        assert_eq!(
-            err.to_string(),
-            "External error: expected at most 1 delete predicate set, got 2"
+        // The rows of each chunk are deleted before the data is de-duplicated
+        let plan = provider.scan(&state, None, &[], None).await.unwrap();
+        insta::assert_yaml_snapshot!(
+            format_execution_plan(&plan),
+            @r###"
+        ---
+        - " ProjectionExec: expr=[field@0 as field, tag1@1 as tag1, tag2@2 as tag2, time@3 as time]"
+        - "   DeduplicateExec: [tag1@1 ASC,tag2@2 ASC,time@3 ASC]"
+        - "     UnionExec"
+        - "       FilterExec: time@3 < -9223372036854775808 OR time@3 > 100"
+        - "         UnionExec"
+        - "           RecordBatchesExec: batches_groups=1 batches=0 total_rows=0"
+        - "       FilterExec: time@3 < -9223372036854775808 OR time@3 > 200"
+        - "         UnionExec"
+        - "           ParquetExec: file_groups={1 group: [[2.parquet]]}, projection=[field, tag1, tag2, time, __chunk_order], output_ordering=[__chunk_order@4 ASC]"
+        "###
         );
     }
 }
@@ -6,7 +6,10 @@ use crate::{
 };
 use arrow::datatypes::{DataType, Fields, Schema as ArrowSchema, SchemaRef};
 use datafusion::{
-    datasource::{listing::PartitionedFile, object_store::ObjectStoreUrl},
+    datasource::{
+        listing::{FileRange, PartitionedFile},
+        object_store::ObjectStoreUrl,
+    },
     physical_expr::PhysicalSortExpr,
     physical_plan::{
         empty::EmptyExec,
@@ -38,7 +41,7 @@ pub struct PartitionedFileExt {
 #[derive(Debug)]
 struct ParquetChunkList {
     object_store_url: ObjectStoreUrl,
-    chunks: Vec<(ObjectMeta, Arc<dyn QueryChunk>)>,
+    chunks: Vec<(ObjectMeta, Option<FileRange>, Arc<dyn QueryChunk>)>,
     /// Sort key to place on the ParquetExec, validated to be
     /// compatible with all chunk sort keys
     sort_key: Option<SortKey>,
@@ -53,21 +56,27 @@ impl ParquetChunkList {
         object_store_url: ObjectStoreUrl,
         chunk: &Arc<dyn QueryChunk>,
         meta: ObjectMeta,
+        range: Option<FileRange>,
         output_sort_key: Option<&SortKey>,
     ) -> Self {
         let sort_key = combine_sort_key(output_sort_key.cloned(), chunk.sort_key(), chunk.schema());
 
         Self {
             object_store_url,
-            chunks: vec![(meta, Arc::clone(chunk))],
+            chunks: vec![(meta, range, Arc::clone(chunk))],
             sort_key,
         }
     }
 
     /// Add the parquet file the list of files to be scanned, updating
     /// the sort key as necessary.
-    fn add_parquet_file(&mut self, chunk: &Arc<dyn QueryChunk>, meta: ObjectMeta) {
-        self.chunks.push((meta, Arc::clone(chunk)));
+    fn add_parquet_file(
+        &mut self,
+        chunk: &Arc<dyn QueryChunk>,
+        meta: ObjectMeta,
+        range: Option<FileRange>,
+    ) {
+        self.chunks.push((meta, range, Arc::clone(chunk)));
 
         self.sort_key = combine_sort_key(self.sort_key.take(), chunk.sort_key(), chunk.schema());
     }
@@ -159,8 +168,11 @@ pub fn chunks_to_physical_nodes(
                 let url_str = parquet_input.object_store_url.as_str().to_owned();
                 match parquet_chunks.entry(url_str) {
                     Entry::Occupied(mut o) => {
-                        o.get_mut()
-                            .add_parquet_file(chunk, parquet_input.object_meta);
+                        o.get_mut().add_parquet_file(
+                            chunk,
+                            parquet_input.object_meta,
+                            parquet_input.range,
+                        );
                     }
                     Entry::Vacant(v) => {
                         // better have some instead of no sort information at all
@@ -169,6 +181,7 @@ pub fn chunks_to_physical_nodes(
                             parquet_input.object_store_url,
                             chunk,
                             parquet_input.object_meta,
+                            parquet_input.range,
                             output_sort_key,
                         ));
                     }
@@ -196,25 +209,25 @@ pub fn chunks_to_physical_nodes(
         } = chunk_list;
 
         // ensure that chunks are actually ordered by chunk order
-        chunks.sort_by_key(|(_meta, c)| c.order());
+        chunks.sort_by_key(|(_meta, _range, c)| c.order());
 
         let num_rows = chunks
             .iter()
-            .map(|(_meta, c)| c.summary().total_count() as usize)
+            .map(|(_meta, _range, c)| c.summary().total_count() as usize)
             .sum::<usize>();
         let chunk_order_min = chunks
             .iter()
-            .map(|(_meta, c)| c.order().get())
+            .map(|(_meta, _range, c)| c.order().get())
             .min()
             .expect("at least one chunk");
         let chunk_order_max = chunks
             .iter()
-            .map(|(_meta, c)| c.order().get())
+            .map(|(_meta, _range, c)| c.order().get())
             .max()
             .expect("at least one chunk");
 
         let file_groups = distribute(
-            chunks.into_iter().map(|(object_meta, chunk)| {
+            chunks.into_iter().map(|(object_meta, range, chunk)| {
                 let partition_values = if has_chunk_order_col {
                     vec![ScalarValue::from(chunk.order().get())]
                 } else {
@@ -223,7 +236,7 @@ pub fn chunks_to_physical_nodes(
                 PartitionedFile {
                     object_meta,
                     partition_values,
-                    range: None,
+                    range,
                     extensions: Some(Arc::new(PartitionedFileExt {
                         chunk,
                         output_sort_key_memo: output_sort_key.cloned(),
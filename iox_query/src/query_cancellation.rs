@@ -0,0 +1,226 @@
+//! Cancellation of running queries, either on request or once they exceed a maximum runtime.
+
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use datafusion::{
+    error::DataFusionError,
+    physical_plan::{stream::RecordBatchStreamAdapter, SendableRecordBatchStream},
+};
+use futures::{future::BoxFuture, stream::BoxStream, FutureExt, Stream, StreamExt};
+use snafu::Snafu;
+use tokio_util::sync::CancellationToken;
+
+/// Error returned by the results stream of an aborted query.
+#[derive(Debug, Snafu, PartialEq, Eq)]
+pub enum QueryAbortedError {
+    #[snafu(display("Query was cancelled"))]
+    Cancelled,
+
+    #[snafu(display("Query exceeded the maximum runtime of {max_runtime:?}"))]
+    Timeout { max_runtime: Duration },
+}
+
+/// Error cancelling a query through [`QueryNamespace::cancel_query`].
+///
+/// [`QueryNamespace::cancel_query`]: crate::QueryNamespace::cancel_query
+#[derive(Debug, Snafu, PartialEq, Eq)]
+#[snafu(visibility(pub))]
+pub enum CancelQueryError {
+    #[snafu(display("Invalid query id '{query_id}'"))]
+    InvalidId { query_id: String },
+
+    #[snafu(display("Query '{query_id}' not found"))]
+    NotFound { query_id: String },
+
+    #[snafu(display("Query '{query_id}' has already completed"))]
+    Completed { query_id: String },
+}
+
+/// Aborts the execution of a query once it is cancelled through a [`CancellationToken`] or runs
+/// for longer than a maximum runtime.
+#[derive(Debug, Clone, Default)]
+pub struct QueryCancellation {
+    token: CancellationToken,
+    max_runtime: Option<Duration>,
+}
+
+impl QueryCancellation {
+    /// Abort the query once `token` is cancelled or it has run for `max_runtime`.
+    pub fn new(token: CancellationToken, max_runtime: Option<Duration>) -> Self {
+        Self { token, max_runtime }
+    }
+
+    /// Future that resolves once the query started at `start` is aborted.
+    pub fn aborted(&self, start: Instant) -> BoxFuture<'static, QueryAbortedError> {
+        let token = self.token.clone();
+        let max_runtime = self.max_runtime;
+
+        let timeout = async move {
+            match max_runtime {
+                Some(max_runtime) => {
+                    tokio::time::sleep_until(tokio::time::Instant::from_std(start) + max_runtime)
+                        .await;
+                    TimeoutSnafu { max_runtime }.build()
+                }
+                None => futures::future::pending().await,
+            }
+        };
+        async move {
+            tokio::select! {
+                _ = token.cancelled() => CancelledSnafu.build(),
+                e = timeout => e,
+            }
+        }
+        .boxed()
+    }
+
+    /// Wrap the results `stream` of a query started at `start` so that it fails with a
+    /// [`QueryAbortedError`] once the query is aborted.
+    ///
+    /// The wrapped stream is dropped on abort, which stops the execution of its plan.
+    pub fn abortable(
+        &self,
+        start: Instant,
+        stream: SendableRecordBatchStream,
+    ) -> SendableRecordBatchStream {
+        let schema = stream.schema();
+        Box::pin(RecordBatchStreamAdapter::new(
+            schema,
+            self.abortable_stream(start, stream.boxed(), |e| {
+                DataFusionError::External(Box::new(e))
+            }),
+        ))
+    }
+
+    /// Wrap the results `stream` of a query started at `start` so that it fails with the error
+    /// returned by `on_abort` once the query is aborted.
+    ///
+    /// Like [`abortable`](Self::abortable), but for results that are not record batches.
+    pub fn abortable_stream<T, E>(
+        &self,
+        start: Instant,
+        stream: BoxStream<'static, Result<T, E>>,
+        on_abort: fn(QueryAbortedError) -> E,
+    ) -> BoxStream<'static, Result<T, E>>
+    where
+        T: Send + 'static,
+        E: Send + 'static,
+    {
+        AbortableStream {
+            inner: Some(stream),
+            aborted: self.aborted(start),
+            on_abort,
+        }
+        .boxed()
+    }
+}
+
+struct AbortableStream<T, E> {
+    /// The results, `None` once the query was aborted.
+    inner: Option<BoxStream<'static, Result<T, E>>>,
+    aborted: BoxFuture<'static, QueryAbortedError>,
+    on_abort: fn(QueryAbortedError) -> E,
+}
+
+impl<T, E> Stream for AbortableStream<T, E> {
+    type Item = Result<T, E>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.inner.is_none() {
+            return Poll::Ready(None);
+        }
+
+        if let Poll::Ready(e) = self.aborted.poll_unpin(cx) {
+            self.inner = None;
+            return Poll::Ready(Some(Err((self.on_abort)(e))));
+        }
+
+        self.inner
+            .as_mut()
+            .expect("checked above")
+            .poll_next_unpin(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::{datatypes::Schema, record_batch::RecordBatch};
+    use futures::TryStreamExt;
+
+    use super::*;
+
+    fn pending_stream() -> SendableRecordBatchStream {
+        Box::pin(RecordBatchStreamAdapter::new(
+            Arc::new(Schema::empty()),
+            futures::stream::pending(),
+        ))
+    }
+
+    async fn abort_error(stream: SendableRecordBatchStream) -> QueryAbortedError {
+        let e = stream.try_collect::<Vec<_>>().await.unwrap_err();
+        match e {
+            DataFusionError::External(e) => *e.downcast::<QueryAbortedError>().unwrap(),
+            e => panic!("unexpected error: {e}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cancel() {
+        let token = CancellationToken::new();
+        let cancellation = QueryCancellation::new(token.clone(), None);
+        let stream = cancellation.abortable(Instant::now(), pending_stream());
+
+        token.cancel();
+        assert_eq!(abort_error(stream).await, QueryAbortedError::Cancelled);
+    }
+
+    #[tokio::test]
+    async fn test_timeout() {
+        let max_runtime = Duration::from_millis(10);
+        let cancellation = QueryCancellation::new(CancellationToken::new(), Some(max_runtime));
+        let stream = cancellation.abortable(Instant::now(), pending_stream());
+
+        assert_eq!(
+            abort_error(stream).await,
+            QueryAbortedError::Timeout { max_runtime }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_not_aborted() {
+        let cancellation =
+            QueryCancellation::new(CancellationToken::new(), Some(Duration::from_secs(3600)));
+        let stream = cancellation.abortable(
+            Instant::now(),
+            Box::pin(RecordBatchStreamAdapter::new(
+                Arc::new(Schema::empty()),
+                futures::stream::iter([Ok(RecordBatch::new_empty(Arc::new(Schema::empty())))]),
+            )),
+        );
+
+        assert_eq!(stream.try_collect::<Vec<_>>().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_abortable_stream() {
+        let token = CancellationToken::new();
+        let cancellation = QueryCancellation::new(token.clone(), None);
+        let stream = cancellation.abortable_stream(
+            Instant::now(),
+            futures::stream::pending::<Result<(), String>>().boxed(),
+            |e| e.to_string(),
+        );
+
+        token.cancel();
+        assert_eq!(
+            stream.collect::<Vec<_>>().await,
+            vec![Err(String::from("Query was cancelled"))]
+        );
+    }
+}
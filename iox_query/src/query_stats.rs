@@ -0,0 +1,125 @@
+//! Resource accounting of individual queries, reported through a [`QueryCompletedToken`].
+//!
+//! [`QueryCompletedToken`]: crate::QueryCompletedToken
+
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use arrow::record_batch::RecordBatch;
+use datafusion::physical_plan::{
+    file_format::ParquetExec, visit_execution_plan, ExecutionPlan, ExecutionPlanVisitor,
+};
+
+/// Statistics of a query, collected while it is planned and executed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryStats {
+    /// Time spent planning the query, if planning completed.
+    pub planning_duration: Option<Duration>,
+
+    /// Number of rows returned to the client.
+    pub rows_returned: u64,
+
+    /// In-memory size of the record batches returned to the client, in bytes.
+    pub bytes_returned: u64,
+
+    /// Number of parquet files scanned by the physical plan.
+    pub parquet_files: u64,
+
+    /// Total size of the parquet files scanned by the physical plan, in bytes.
+    pub parquet_bytes: u64,
+
+    /// The error the query failed with, if any.
+    pub error: Option<String>,
+}
+
+impl QueryStats {
+    /// Add the parquet files scanned by `plan` to these statistics.
+    pub fn add_plan(&mut self, plan: &dyn ExecutionPlan) {
+        let mut visitor = ParquetFilesVisitor::default();
+        // the visitor never fails
+        visit_execution_plan(plan, &mut visitor).ok();
+
+        self.parquet_files += visitor.files;
+        self.parquet_bytes += visitor.bytes;
+    }
+}
+
+#[derive(Debug, Default)]
+struct ParquetFilesVisitor {
+    files: u64,
+    bytes: u64,
+}
+
+impl ExecutionPlanVisitor for ParquetFilesVisitor {
+    type Error = std::convert::Infallible;
+
+    fn pre_visit(&mut self, plan: &dyn ExecutionPlan) -> Result<bool, Self::Error> {
+        if let Some(parquet_exec) = plan.as_any().downcast_ref::<ParquetExec>() {
+            for file in parquet_exec.base_config().file_groups.iter().flatten() {
+                self.files += 1;
+                self.bytes += file.object_meta.size as u64;
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// Counts the rows and bytes returned by a query.
+///
+/// Obtained from [`QueryCompletedToken::result_counter`] so that the results can be counted by
+/// the stream that returns them while the token is owned elsewhere.
+///
+/// [`QueryCompletedToken::result_counter`]: crate::QueryCompletedToken::result_counter
+#[derive(Debug, Clone, Default)]
+pub struct QueryResultCounter {
+    rows: Arc<AtomicU64>,
+    bytes: Arc<AtomicU64>,
+}
+
+impl QueryResultCounter {
+    /// Count `batch` as returned to the client.
+    pub fn record(&self, batch: &RecordBatch) {
+        self.rows
+            .fetch_add(batch.num_rows() as u64, Ordering::Relaxed);
+        self.bytes
+            .fetch_add(batch.get_array_memory_size() as u64, Ordering::Relaxed);
+    }
+
+    /// The number of rows counted so far.
+    pub fn rows(&self) -> u64 {
+        self.rows.load(Ordering::Relaxed)
+    }
+
+    /// The number of bytes counted so far.
+    pub fn bytes(&self) -> u64 {
+        self.bytes.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::{ArrayRef, Int64Array};
+
+    use super::*;
+
+    #[test]
+    fn test_result_counter() {
+        let counter = QueryResultCounter::default();
+        let batch = RecordBatch::try_from_iter([(
+            "a",
+            Arc::new(Int64Array::from(vec![1, 2, 3])) as ArrayRef,
+        )])
+        .unwrap();
+
+        counter.clone().record(&batch);
+        counter.record(&batch);
+
+        assert_eq!(counter.rows(), 6);
+        assert_eq!(counter.bytes(), 2 * batch.get_array_memory_size() as u64);
+    }
+}
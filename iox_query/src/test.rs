@@ -7,8 +7,8 @@ use crate::{
         stringset::{StringSet, StringSetRef},
         ExecutionContextProvider, Executor, ExecutorType, IOxSessionContext,
     },
-    Predicate, PredicateMatch, QueryChunk, QueryChunkData, QueryChunkMeta, QueryCompletedToken,
-    QueryNamespace, QueryText,
+    CancelQueryError, Predicate, PredicateMatch, QueryCancellation, QueryChunk, QueryChunkData,
+    QueryChunkMeta, QueryCompletedToken, QueryNamespace, QueryText,
 };
 use arrow::array::{BooleanArray, Float64Array};
 use arrow::datatypes::SchemaRef;
@@ -42,7 +42,17 @@ use schema::{
     builder::SchemaBuilder, merge::SchemaMerger, sort::SortKey, InfluxColumnType, Projection,
     Schema, TIME_COLUMN_NAME,
 };
-use std::{any::Any, collections::BTreeMap, fmt, num::NonZeroU64, sync::Arc};
+use std::{
+    any::Any,
+    collections::BTreeMap,
+    fmt,
+    num::NonZeroU64,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+use tokio_util::sync::CancellationToken;
 use trace::ctx::SpanContext;
 
 #[derive(Debug)]
@@ -58,6 +68,12 @@ pub struct TestDatabase {
 
     /// The predicate passed to the most recent call to `chunks()`
     chunks_predicate: Mutex<Predicate>,
+
+    /// Cancellation tokens of the recorded queries, indexed by query id
+    queries: Mutex<Vec<CancellationToken>>,
+
+    /// Cancel queries as soon as they are recorded
+    cancel_new_queries: AtomicBool,
 }
 
 impl TestDatabase {
@@ -67,6 +83,8 @@ impl TestDatabase {
             partitions: Default::default(),
             column_names: Default::default(),
             chunks_predicate: Default::default(),
+            queries: Default::default(),
+            cancel_new_queries: Default::default(),
         }
     }
 
@@ -107,6 +125,11 @@ impl TestDatabase {
 
         *Arc::clone(&self.column_names).lock() = Some(column_names)
     }
+
+    /// Cancel every query recorded from now on, as if `cancel_query` was called while it runs
+    pub fn cancel_new_queries(&self) {
+        self.cancel_new_queries.store(true, Ordering::SeqCst);
+    }
 }
 
 #[async_trait]
@@ -148,7 +171,25 @@ impl QueryNamespace for TestDatabase {
         _query_type: &str,
         _query_text: QueryText,
     ) -> QueryCompletedToken {
-        QueryCompletedToken::new(|_| {})
+        let token = CancellationToken::new();
+        if self.cancel_new_queries.load(Ordering::SeqCst) {
+            token.cancel();
+        }
+        self.queries.lock().push(token.clone());
+
+        QueryCompletedToken::new(|_, _| {}).with_cancellation(QueryCancellation::new(token, None))
+    }
+
+    fn cancel_query(&self, query_id: &str) -> Result<(), CancelQueryError> {
+        let token = query_id
+            .parse::<usize>()
+            .ok()
+            .and_then(|id| self.queries.lock().get(id).cloned())
+            .ok_or_else(|| CancelQueryError::NotFound {
+                query_id: query_id.to_string(),
+            })?;
+        token.cancel();
+        Ok(())
     }
 
     fn as_meta(&self) -> &dyn QueryNamespaceMeta {
@@ -470,6 +511,7 @@ impl TestChunk {
                     last_modified: Default::default(),
                     size: 1,
                 },
+                range: None,
             }),
             ..self
         }
@@ -0,0 +1,92 @@
+//! Access to the databases an InfluxQL query may reference.
+
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use data_types::DeletePredicate;
+use datafusion::catalog::catalog::CatalogProvider;
+use datafusion::error::Result;
+
+pub use crate::plan::{Database, DEFAULT_RETENTION_POLICY};
+
+/// The separator between the database and retention policy names of a
+/// namespace name.
+const NAMESPACE_RP_SEPARATOR: char = '/';
+
+/// Provides the InfluxQL planner with the databases, or namespaces, visible
+/// to a query.
+///
+/// Statements such as `SHOW DATABASES`, or those that reference another
+/// database using an `ON <database>` clause or a fully-qualified measurement
+/// name, are planned using this trait.
+#[async_trait]
+pub trait DatabaseProvider: Debug + Send + Sync {
+    /// The name of the database the query runs against if one is
+    /// not specified, or `None` if the request did not specify one.
+    fn default_database(&self) -> Option<&str>;
+
+    /// Returns the databases the caller is authorised to read.
+    async fn databases(&self) -> Result<Vec<Database>>;
+
+    /// Returns the catalog of the database `name`, or `None` if it does not
+    /// exist or the caller is not authorised to read it.
+    async fn catalog(&self, name: &str) -> Result<Option<Arc<dyn CatalogProvider>>>;
+
+    /// Drops the measurement `name` from `database`, along with all of its data.
+    async fn drop_measurement(&self, database: &str, name: &str) -> Result<()>;
+
+    /// Deletes the rows of `measurement` in `database` that match `predicate`.
+    async fn delete(
+        &self,
+        database: &str,
+        measurement: &str,
+        predicate: &DeletePredicate,
+    ) -> Result<()>;
+}
+
+/// Returns the name of the namespace for the `database` and
+/// `retention_policy`, using the same mapping as the InfluxDB 1.x
+/// write and query APIs.
+pub fn namespace_name(database: &str, retention_policy: Option<&str>) -> String {
+    match retention_policy {
+        None => database.to_string(),
+        Some(rp) if rp.is_empty() || rp == DEFAULT_RETENTION_POLICY => database.to_string(),
+        Some(rp) => format!("{database}{NAMESPACE_RP_SEPARATOR}{rp}"),
+    }
+}
+
+/// Returns the database and retention policy names of the namespace `name`,
+/// reversing [`namespace_name`].
+pub fn split_namespace_name(name: &str) -> (&str, Option<&str>) {
+    match name.split_once(NAMESPACE_RP_SEPARATOR) {
+        Some((database, rp)) => (database, Some(rp)),
+        None => (name, None),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_namespace_name() {
+        assert_eq!(namespace_name("telegraf", None), "telegraf");
+        assert_eq!(namespace_name("telegraf", Some("")), "telegraf");
+        assert_eq!(namespace_name("telegraf", Some("autogen")), "telegraf");
+        assert_eq!(namespace_name("telegraf", Some("1w")), "telegraf/1w");
+    }
+
+    #[test]
+    fn test_split_namespace_name() {
+        assert_eq!(split_namespace_name("telegraf"), ("telegraf", None));
+        assert_eq!(
+            split_namespace_name("telegraf/1w"),
+            ("telegraf", Some("1w"))
+        );
+        assert_eq!(
+            split_namespace_name(&namespace_name("telegraf", Some("1w"))),
+            ("telegraf", Some("1w"))
+        );
+    }
+}
@@ -1 +1,3 @@
+pub mod database;
+mod modify;
 pub mod planner;
@@ -0,0 +1,260 @@
+//! Physical plans for InfluxQL statements that modify a database.
+
+use std::any::Any;
+use std::fmt;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use arrow::datatypes::{Schema, SchemaRef};
+use data_types::DeletePredicate;
+use datafusion::common::Statistics;
+use datafusion::error::Result;
+use datafusion::execution::context::TaskContext;
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{ExecutionPlan, Partitioning, SendableRecordBatchStream};
+use futures::StreamExt;
+
+use crate::frontend::database::DatabaseProvider;
+
+/// A change to a database made by an InfluxQL statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Modification {
+    /// Drop the measurement `name` from `database`.
+    DropMeasurement { database: String, name: String },
+
+    /// Delete the rows of each of `measurements` in `database` that match
+    /// `predicate`.
+    Delete {
+        database: String,
+        measurements: Vec<String>,
+        predicate: DeletePredicate,
+    },
+}
+
+impl Modification {
+    async fn apply(&self, databases: &dyn DatabaseProvider) -> Result<()> {
+        match self {
+            Self::DropMeasurement { database, name } => {
+                databases.drop_measurement(database, name).await
+            }
+            Self::Delete {
+                database,
+                measurements,
+                predicate,
+            } => {
+                for measurement in measurements {
+                    databases.delete(database, measurement, predicate).await?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A physical operator that applies a [`Modification`] to a database when
+/// it is executed, and produces no rows.
+///
+/// Planning a statement such as `DROP MEASUREMENT` must not change the
+/// database, so that a plan may be inspected, for example by `EXPLAIN`,
+/// or discarded without side effects.
+pub(crate) struct ModifyDatabaseExec {
+    databases: Arc<dyn DatabaseProvider>,
+    modification: Modification,
+    schema: SchemaRef,
+}
+
+impl ModifyDatabaseExec {
+    pub(crate) fn new(databases: Arc<dyn DatabaseProvider>, modification: Modification) -> Self {
+        Self {
+            databases,
+            modification,
+            schema: Arc::new(Schema::empty()),
+        }
+    }
+}
+
+impl Debug for ModifyDatabaseExec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ModifyDatabaseExec: {:?}", self.modification)
+    }
+}
+
+impl ExecutionPlan for ModifyDatabaseExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(self)
+    }
+
+    fn execute(
+        &self,
+        _partition: usize,
+        _context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let databases = Arc::clone(&self.databases);
+        let modification = self.modification.clone();
+
+        // The modification is only applied once the stream is polled, and
+        // any error is returned as the only item of the stream.
+        let stream =
+            futures::stream::once(async move { modification.apply(databases.as_ref()).await })
+                .filter_map(|res| async move { res.err().map(Err) });
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            Arc::clone(&self.schema),
+            stream,
+        )))
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use async_trait::async_trait;
+    use data_types::TimestampRange;
+    use datafusion::catalog::catalog::CatalogProvider;
+    use datafusion::error::DataFusionError;
+    use datafusion::physical_plan::common::collect;
+    use datafusion::prelude::SessionContext;
+    use std::sync::Mutex;
+
+    use crate::frontend::database::Database;
+
+    /// Records the modifications applied to it.
+    #[derive(Debug, Default)]
+    struct MockDatabases {
+        calls: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl DatabaseProvider for MockDatabases {
+        fn default_database(&self) -> Option<&str> {
+            Some("db0")
+        }
+
+        async fn databases(&self) -> Result<Vec<Database>> {
+            Ok(vec![])
+        }
+
+        async fn catalog(&self, _name: &str) -> Result<Option<Arc<dyn CatalogProvider>>> {
+            Ok(None)
+        }
+
+        async fn drop_measurement(&self, database: &str, name: &str) -> Result<()> {
+            if name == "missing" {
+                return Err(DataFusionError::Plan(format!(
+                    "measurement not found: {name}"
+                )));
+            }
+            self.calls
+                .lock()
+                .unwrap()
+                .push(format!("drop {database}.{name}"));
+            Ok(())
+        }
+
+        async fn delete(
+            &self,
+            database: &str,
+            measurement: &str,
+            _predicate: &DeletePredicate,
+        ) -> Result<()> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(format!("delete {database}.{measurement}"));
+            Ok(())
+        }
+    }
+
+    fn execute(exec: &ModifyDatabaseExec) -> Result<()> {
+        let stream = exec.execute(0, SessionContext::new().task_ctx())?;
+        let batches = futures::executor::block_on(collect(stream))?;
+        assert!(batches.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_applied_on_execution() {
+        let databases = Arc::new(MockDatabases::default());
+        let exec = ModifyDatabaseExec::new(
+            Arc::clone(&databases) as _,
+            Modification::Delete {
+                database: "db0".into(),
+                measurements: vec!["cpu".into(), "mem".into()],
+                predicate: DeletePredicate {
+                    range: TimestampRange::new(0, 10),
+                    exprs: vec![],
+                },
+            },
+        );
+
+        // Nothing is applied until the plan is executed.
+        let stream = exec.execute(0, SessionContext::new().task_ctx()).unwrap();
+        assert!(databases.calls.lock().unwrap().is_empty());
+
+        let batches = futures::executor::block_on(collect(stream)).unwrap();
+        assert!(batches.is_empty());
+        assert_eq!(
+            *databases.calls.lock().unwrap(),
+            ["delete db0.cpu", "delete db0.mem"]
+        );
+
+        let exec = ModifyDatabaseExec::new(
+            Arc::clone(&databases) as _,
+            Modification::DropMeasurement {
+                database: "db0".into(),
+                name: "cpu".into(),
+            },
+        );
+        execute(&exec).unwrap();
+        assert_eq!(
+            *databases.calls.lock().unwrap(),
+            ["delete db0.cpu", "delete db0.mem", "drop db0.cpu"]
+        );
+    }
+
+    #[test]
+    fn test_error_on_execution() {
+        let databases = Arc::new(MockDatabases::default());
+        let exec = ModifyDatabaseExec::new(
+            Arc::clone(&databases) as _,
+            Modification::DropMeasurement {
+                database: "db0".into(),
+                name: "missing".into(),
+            },
+        );
+
+        let err = execute(&exec).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: measurement not found: missing"
+        );
+    }
+}
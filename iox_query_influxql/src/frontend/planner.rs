@@ -1,6 +1,8 @@
 use arrow::datatypes::SchemaRef;
+use influxdb_influxql_parser::show::OnClause;
 use influxdb_influxql_parser::show_field_keys::ShowFieldKeysStatement;
-use influxdb_influxql_parser::show_measurements::ShowMeasurementsStatement;
+use influxdb_influxql_parser::show_measurements::{ExtendedOnClause, ShowMeasurementsStatement};
+use influxdb_influxql_parser::show_retention_policies::ShowRetentionPoliciesStatement;
 use influxdb_influxql_parser::show_tag_keys::ShowTagKeysStatement;
 use influxdb_influxql_parser::show_tag_values::ShowTagValuesStatement;
 use std::any::Any;
@@ -10,7 +12,10 @@ use std::fmt::Debug;
 use std::ops::Deref;
 use std::sync::Arc;
 
-use crate::plan::{parse_regex, InfluxQLToLogicalPlan, SchemaProvider};
+use crate::frontend::database::{namespace_name, split_namespace_name, DatabaseProvider};
+use crate::frontend::modify::{Modification, ModifyDatabaseExec};
+use crate::plan::{delete_predicate, parse_regex, Database, InfluxQLToLogicalPlan, SchemaProvider};
+use datafusion::catalog::schema::MemorySchemaProvider;
 use datafusion::common::Statistics;
 use datafusion::datasource::provider_as_source;
 use datafusion::execution::context::{SessionState, TaskContext};
@@ -21,17 +26,24 @@ use datafusion::{
     error::{DataFusionError, Result},
     physical_plan::ExecutionPlan,
 };
-use influxdb_influxql_parser::common::MeasurementName;
+use influxdb_influxql_parser::common::{MeasurementName, QualifiedMeasurementName};
+use influxdb_influxql_parser::delete::DeleteStatement;
+use influxdb_influxql_parser::expression::Expr;
+use influxdb_influxql_parser::literal::Literal;
 use influxdb_influxql_parser::parse_statements;
 use influxdb_influxql_parser::statement::Statement;
 use influxdb_influxql_parser::visit::{Visitable, Visitor};
+use influxdb_influxql_parser::visit_mut::{VisitableMut, VisitorMut};
 use iox_query::exec::IOxSessionContext;
+use iox_query::params::{StatementParam, StatementParams};
+use itertools::Itertools;
 use observability_deps::tracing::debug;
 use schema::Schema;
 
 struct ContextSchemaProvider<'a> {
     state: &'a SessionState,
     tables: HashMap<String, (Arc<dyn TableSource>, Schema)>,
+    databases: Vec<Database>,
 }
 
 impl<'a> SchemaProvider for ContextSchemaProvider<'a> {
@@ -61,6 +73,10 @@ impl<'a> SchemaProvider for ContextSchemaProvider<'a> {
     fn table_schema(&self, name: &str) -> Option<Schema> {
         self.tables.get(name).map(|(_, s)| s.clone())
     }
+
+    fn databases(&self) -> &[Database] {
+        &self.databases
+    }
 }
 
 /// A physical operator that overrides the `schema` API,
@@ -129,15 +145,37 @@ impl InfluxQLQueryPlanner {
 
     /// Plan an InfluxQL query against the catalogs registered with `ctx`, and return a
     /// DataFusion physical execution plan that runs on the query executor.
+    ///
+    /// Each bind parameter of the query, such as `$host`, is replaced by the value of
+    /// the same name in `params`. Databases other than the one registered with `ctx`
+    /// are resolved using `databases`.
+    ///
+    /// Statements that modify a database, such as `DROP MEASUREMENT`, are applied
+    /// using `databases` when the returned plan is executed.
     pub async fn query(
         &self,
         query: &str,
+        params: &StatementParams,
+        databases: Arc<dyn DatabaseProvider>,
         ctx: &IOxSessionContext,
     ) -> Result<Arc<dyn ExecutionPlan>> {
         debug!(text=%query, "planning InfluxQL query");
 
-        let statement = self.query_to_statement(query)?;
-        let logical_plan = self.statement_to_plan(statement, ctx).await?;
+        let mut statement = self.query_to_statement(query)?;
+        bind_parameters(&mut statement, params)?;
+
+        if is_modification(&statement) {
+            let database = databases
+                .default_database()
+                .ok_or_else(database_name_required)?;
+            let names = default_table_names(ctx)?;
+            let modification = statement_to_modification(&statement, database, &names)?;
+            return Ok(Arc::new(ModifyDatabaseExec::new(databases, modification)));
+        }
+
+        let logical_plan = self
+            .statement_to_plan(statement, databases.as_ref(), ctx)
+            .await?;
 
         let input = ctx.create_physical_plan(&logical_plan).await?;
 
@@ -157,35 +195,60 @@ impl InfluxQLQueryPlanner {
 
     async fn statement_to_plan(
         &self,
-        statement: Statement,
+        mut statement: Statement,
+        databases: &dyn DatabaseProvider,
         ctx: &IOxSessionContext,
     ) -> Result<LogicalPlan> {
         use std::collections::hash_map::Entry;
 
+        let default_database = databases.default_database();
         let session_cfg = ctx.inner().copied_config();
         let cfg = session_cfg.options();
-        let schema = ctx
-            .inner()
-            .catalog(&cfg.catalog.default_catalog)
-            .ok_or_else(|| {
-                DataFusionError::Plan(format!(
-                    "failed to resolve catalog: {}",
-                    cfg.catalog.default_catalog
-                ))
-            })?
-            .schema(&cfg.catalog.default_schema)
-            .ok_or_else(|| {
+        let catalog = match resolve_namespace(&mut statement, default_database)? {
+            Some(name) if Some(name.as_str()) != default_database => {
+                match databases.catalog(&name).await? {
+                    Some(catalog) => Some(catalog),
+                    None => return Err(namespace_not_found(databases, &name).await),
+                }
+            }
+            _ if default_database.is_some() => Some(
+                ctx.inner()
+                    .catalog(&cfg.catalog.default_catalog)
+                    .ok_or_else(|| {
+                        DataFusionError::Plan(format!(
+                            "failed to resolve catalog: {}",
+                            cfg.catalog.default_catalog
+                        ))
+                    })?,
+            ),
+            // Without a default database, only statements that do not read
+            // from a database can be planned.
+            _ if !requires_database(&statement) => None,
+            _ => return Err(database_name_required()),
+        };
+        let schema = match catalog {
+            Some(catalog) => catalog.schema(&cfg.catalog.default_schema).ok_or_else(|| {
                 DataFusionError::Plan(format!(
                     "failed to resolve schema: {}",
                     cfg.catalog.default_schema
                 ))
-            })?;
+            })?,
+            None => Arc::new(MemorySchemaProvider::new()) as _,
+        };
         let names = schema.table_names();
         let query_tables = find_all_measurements(&statement, &names)?;
 
+        let databases = match statement {
+            Statement::ShowDatabases(_) | Statement::ShowRetentionPolicies(_) => {
+                databases.databases().await?
+            }
+            _ => vec![],
+        };
+
         let mut sp = ContextSchemaProvider {
             state: &ctx.inner().state(),
             tables: HashMap::with_capacity(query_tables.len()),
+            databases,
         };
 
         for table_name in &query_tables {
@@ -220,6 +283,252 @@ impl InfluxQLQueryPlanner {
     }
 }
 
+/// Returns true unless `stmt` only lists the databases and their
+/// retention policies, and so can be planned without a database.
+fn requires_database(stmt: &Statement) -> bool {
+    !matches!(
+        stmt,
+        Statement::ShowDatabases(_) | Statement::ShowRetentionPolicies(_)
+    )
+}
+
+/// The error returned when a statement requires a database, but does
+/// not name one and the query has no default database.
+fn database_name_required() -> DataFusionError {
+    DataFusionError::Plan("database name required".to_string())
+}
+
+/// Returns the name of the namespace the statement `stmt` refers to
+/// using an `ON <database>` clause or fully-qualified measurement
+/// names, if any.
+///
+/// Returns an error if `stmt` refers to more than one namespace, or
+/// names a retention policy without a database.
+pub fn statement_namespace(stmt: &Statement) -> Result<Option<String>> {
+    resolve_namespace(&mut stmt.clone(), None)
+}
+
+/// Returns true if `stmt` modifies a database rather than querying it.
+fn is_modification(stmt: &Statement) -> bool {
+    matches!(stmt, Statement::DropMeasurement(_) | Statement::Delete(_))
+}
+
+/// Returns the names of the tables in the default schema of the default
+/// catalog registered with `ctx`.
+fn default_table_names(ctx: &IOxSessionContext) -> Result<Vec<String>> {
+    let session_cfg = ctx.inner().copied_config();
+    let cfg = session_cfg.options();
+    let catalog = ctx
+        .inner()
+        .catalog(&cfg.catalog.default_catalog)
+        .ok_or_else(|| {
+            DataFusionError::Plan(format!(
+                "failed to resolve catalog: {}",
+                cfg.catalog.default_catalog
+            ))
+        })?;
+    let schema = catalog.schema(&cfg.catalog.default_schema).ok_or_else(|| {
+        DataFusionError::Plan(format!(
+            "failed to resolve schema: {}",
+            cfg.catalog.default_schema
+        ))
+    })?;
+    Ok(schema.table_names())
+}
+
+/// Returns the [`Modification`] of `database` made by the statement `stmt`,
+/// where `tables` are the names of the measurements in `database`.
+fn statement_to_modification(
+    stmt: &Statement,
+    database: &str,
+    tables: &[String],
+) -> Result<Modification> {
+    match stmt {
+        Statement::DropMeasurement(dm) => Ok(Modification::DropMeasurement {
+            database: database.to_string(),
+            name: dm.name.as_str().to_string(),
+        }),
+        Statement::Delete(ds) => {
+            let condition = match ds.as_ref() {
+                DeleteStatement::FromWhere { condition, .. } => condition.as_deref(),
+                DeleteStatement::Where(condition) => Some(condition.deref()),
+            };
+            let predicate = delete_predicate(condition)?;
+            let measurements = find_all_measurements(stmt, tables)?
+                .into_iter()
+                .sorted()
+                .collect();
+            Ok(Modification::Delete {
+                database: database.to_string(),
+                measurements,
+                predicate,
+            })
+        }
+        _ => Err(DataFusionError::Internal(format!(
+            "statement does not modify a database: {stmt}"
+        ))),
+    }
+}
+
+/// Replace each bind parameter of `stmt` with a literal of the value
+/// of the same name in `params`.
+///
+/// Returns an error if `params` has no value for a bind parameter.
+fn bind_parameters(stmt: &mut Statement, params: &StatementParams) -> Result<()> {
+    struct Binder<'a>(&'a StatementParams);
+    impl<'a> VisitorMut for Binder<'a> {
+        type Error = DataFusionError;
+
+        fn post_visit_expr(&mut self, expr: &mut Expr) -> Result<(), Self::Error> {
+            if let Expr::BindParameter(name) = expr {
+                let value = self.0.get(name.as_str()).ok_or_else(|| {
+                    DataFusionError::Plan(format!("missing parameter: {}", name.as_str()))
+                })?;
+
+                *expr = Expr::Literal(match value {
+                    StatementParam::Boolean(v) => Literal::Boolean(*v),
+                    StatementParam::Integer(v) => Literal::Integer(*v),
+                    StatementParam::Unsigned(v) => Literal::Unsigned(*v),
+                    StatementParam::Float(v) => Literal::Float(*v),
+                    StatementParam::String(v) => Literal::String(v.clone()),
+                });
+            }
+
+            Ok(())
+        }
+    }
+
+    stmt.accept(&mut Binder(params))
+}
+
+/// Returns the error for a query that refers to the namespace `name`, which
+/// does not exist, naming the retention policy if its database exists.
+async fn namespace_not_found(databases: &dyn DatabaseProvider, name: &str) -> DataFusionError {
+    match split_namespace_name(name) {
+        (database, Some(rp)) if matches!(databases.catalog(database).await, Ok(Some(_))) => {
+            DataFusionError::Plan(format!("retention policy not found: {rp}"))
+        }
+        (database, _) => DataFusionError::Plan(format!("database not found: {database}")),
+    }
+}
+
+/// Remove the database and retention policy qualifiers of `stmt`, such as the
+/// `ON <database>` clause or `FROM <database>.<retention policy>.<measurement>`,
+/// and return the name of the namespace they refer to, if any. A
+/// `SHOW RETENTION POLICIES` statement without an `ON` clause is updated to refer
+/// to `default_database`, if any.
+///
+/// Returns an error if `stmt` refers to more than one namespace, or names a
+/// retention policy without a database when there is no `default_database`.
+fn resolve_namespace(
+    stmt: &mut Statement,
+    default_database: Option<&str>,
+) -> Result<Option<String>> {
+    struct Resolver<'a> {
+        default_database: Option<&'a str>,
+        namespace: Option<String>,
+    }
+
+    impl<'a> Resolver<'a> {
+        fn add(&mut self, database: Option<&str>, retention_policy: Option<&str>) -> Result<()> {
+            let database = database
+                .or(self.default_database)
+                .ok_or_else(database_name_required)?;
+            let name = namespace_name(database, retention_policy);
+            match &self.namespace {
+                Some(existing) if *existing != name => Err(DataFusionError::NotImplemented(
+                    "queries that reference more than one database".to_string(),
+                )),
+                _ => {
+                    self.namespace = Some(name);
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    impl<'a> VisitorMut for Resolver<'a> {
+        type Error = DataFusionError;
+
+        fn post_visit_show_measurements_statement(
+            &mut self,
+            n: &mut ShowMeasurementsStatement,
+        ) -> Result<(), Self::Error> {
+            match n.on.take() {
+                None => Ok(()),
+                Some(ExtendedOnClause::Database(db)) => self.add(Some(db.as_str()), None),
+                Some(ExtendedOnClause::DatabaseRetentionPolicy(db, rp)) => {
+                    self.add(Some(db.as_str()), Some(rp.as_str()))
+                }
+                Some(on) => Err(DataFusionError::NotImplemented(format!(
+                    "SHOW MEASUREMENTS {on}"
+                ))),
+            }
+        }
+
+        fn post_visit_show_retention_policies_statement(
+            &mut self,
+            n: &mut ShowRetentionPoliciesStatement,
+        ) -> Result<(), Self::Error> {
+            if let (None, Some(database)) = (&n.database, self.default_database) {
+                n.database = Some(OnClause::new(database.into()));
+            }
+            Ok(())
+        }
+
+        fn post_visit_show_tag_keys_statement(
+            &mut self,
+            n: &mut ShowTagKeysStatement,
+        ) -> Result<(), Self::Error> {
+            match n.database.take() {
+                Some(db) => self.add(Some(db.as_str()), None),
+                None => Ok(()),
+            }
+        }
+
+        fn post_visit_show_tag_values_statement(
+            &mut self,
+            n: &mut ShowTagValuesStatement,
+        ) -> Result<(), Self::Error> {
+            match n.database.take() {
+                Some(db) => self.add(Some(db.as_str()), None),
+                None => Ok(()),
+            }
+        }
+
+        fn post_visit_show_field_keys_statement(
+            &mut self,
+            n: &mut ShowFieldKeysStatement,
+        ) -> Result<(), Self::Error> {
+            match n.database.take() {
+                Some(db) => self.add(Some(db.as_str()), None),
+                None => Ok(()),
+            }
+        }
+
+        fn post_visit_qualified_measurement_name(
+            &mut self,
+            n: &mut QualifiedMeasurementName,
+        ) -> Result<(), Self::Error> {
+            if n.database.is_none() && n.retention_policy.is_none() {
+                return Ok(());
+            }
+            let (db, rp) = (n.database.take(), n.retention_policy.take());
+            self.add(
+                db.as_deref().map(String::as_str),
+                rp.as_deref().map(String::as_str),
+            )
+        }
+    }
+
+    let mut resolver = Resolver {
+        default_database,
+        namespace: None,
+    };
+    stmt.accept(&mut resolver)?;
+    Ok(resolver.namespace)
+}
+
 fn find_all_measurements(stmt: &Statement, tables: &[String]) -> Result<HashSet<String>> {
     struct Matcher<'a>(&'a mut HashSet<String>, &'a [String]);
     impl<'a> Visitor for Matcher<'a> {
@@ -294,6 +603,14 @@ fn find_all_measurements(stmt: &Statement, tables: &[String]) -> Result<HashSet<
 
             Ok(self)
         }
+
+        fn post_visit_delete_statement(self, ds: &DeleteStatement) -> Result<Self, Self::Error> {
+            if let DeleteStatement::Where(_) = ds {
+                self.0.extend(self.1.iter().cloned());
+            }
+
+            Ok(self)
+        }
     }
 
     let mut m = HashSet::new();
@@ -306,6 +623,7 @@ fn find_all_measurements(stmt: &Statement, tables: &[String]) -> Result<HashSet<
 #[cfg(test)]
 mod test {
     use super::*;
+    use assert_matches::assert_matches;
     use itertools::Itertools;
     use test_helpers::assert_error;
 
@@ -324,6 +642,200 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_bind_parameters() {
+        fn bind(q: &str, params: &StatementParams) -> Result<String> {
+            let p = InfluxQLQueryPlanner::new();
+            let mut s = p.query_to_statement(q).unwrap();
+            bind_parameters(&mut s, params)?;
+            Ok(s.to_string())
+        }
+
+        let params = StatementParams::from([
+            ("host".to_string(), StatementParam::from("server01")),
+            (
+                "start".to_string(),
+                StatementParam::from("2022-10-31T02:00:00Z"),
+            ),
+            ("n".to_string(), StatementParam::from(10_i64)),
+            ("u".to_string(), StatementParam::from(5_u64)),
+            ("f".to_string(), StatementParam::from(0.5)),
+            ("b".to_string(), StatementParam::from(true)),
+        ]);
+
+        assert_eq!(
+            bind(
+                "SELECT usage_idle FROM cpu WHERE host = $host AND time >= $start",
+                &params
+            )
+            .unwrap(),
+            "SELECT usage_idle FROM cpu WHERE host = 'server01' AND time >= '2022-10-31T02:00:00Z'"
+        );
+        assert_eq!(
+            bind(
+                "SELECT usage_idle * $f + $n FROM cpu WHERE b = $b OR u > $u",
+                &params
+            )
+            .unwrap(),
+            "SELECT usage_idle * 0.5 + 10 FROM cpu WHERE b = true OR u > 5"
+        );
+        // quoted parameter names
+        assert_eq!(
+            bind(
+                r#"SELECT usage_idle FROM cpu WHERE host = $"host""#,
+                &params
+            )
+            .unwrap(),
+            "SELECT usage_idle FROM cpu WHERE host = 'server01'"
+        );
+        // parameters in a subquery
+        assert_eq!(
+            bind(
+                "SELECT max(v) FROM (SELECT usage_idle AS v FROM cpu WHERE host = $host)",
+                &params
+            )
+            .unwrap(),
+            "SELECT max(v) FROM (SELECT usage_idle AS v FROM cpu WHERE host = 'server01')"
+        );
+
+        // Fallible
+
+        assert_error!(
+            bind("SELECT usage_idle FROM cpu WHERE host = $missing", &params),
+            DataFusionError::Plan(ref s) if s == "missing parameter: missing"
+        );
+    }
+
+    #[test]
+    fn test_resolve_namespace() {
+        fn resolve(q: &str) -> Result<(Option<String>, String)> {
+            let p = InfluxQLQueryPlanner::new();
+            let mut s = p.query_to_statement(q).unwrap();
+            let ns = resolve_namespace(&mut s, Some("db0"))?;
+            Ok((ns, s.to_string()))
+        }
+
+        // no database references
+        assert_eq!(
+            resolve("SELECT usage_idle FROM cpu").unwrap(),
+            (None, "SELECT usage_idle FROM cpu".into())
+        );
+
+        // qualified measurement names
+        assert_eq!(
+            resolve("SELECT usage_idle FROM db1.autogen.cpu").unwrap(),
+            (Some("db1".into()), "SELECT usage_idle FROM cpu".into())
+        );
+        assert_eq!(
+            resolve("SELECT usage_idle FROM db1..cpu, db1..mem").unwrap(),
+            (Some("db1".into()), "SELECT usage_idle FROM cpu, mem".into())
+        );
+        assert_eq!(
+            resolve("SELECT usage_idle FROM db1.one_week.cpu").unwrap(),
+            (
+                Some("db1/one_week".into()),
+                "SELECT usage_idle FROM cpu".into()
+            )
+        );
+        assert_eq!(
+            resolve("SELECT usage_idle FROM autogen.cpu").unwrap(),
+            (Some("db0".into()), "SELECT usage_idle FROM cpu".into())
+        );
+        assert_eq!(
+            resolve("SELECT max(v) FROM (SELECT usage_idle AS v FROM db1..cpu)").unwrap(),
+            (
+                Some("db1".into()),
+                "SELECT max(v) FROM (SELECT usage_idle AS v FROM cpu)".into()
+            )
+        );
+
+        // ON clauses
+        assert_eq!(
+            resolve("SHOW MEASUREMENTS ON db1").unwrap(),
+            (Some("db1".into()), "SHOW MEASUREMENTS".into())
+        );
+        assert_eq!(
+            resolve("SHOW MEASUREMENTS ON db1.autogen").unwrap(),
+            (Some("db1".into()), "SHOW MEASUREMENTS".into())
+        );
+        assert_eq!(
+            resolve("SHOW TAG KEYS ON db1 FROM cpu").unwrap(),
+            (Some("db1".into()), "SHOW TAG KEYS FROM cpu".into())
+        );
+        assert_eq!(
+            resolve("SHOW TAG VALUES ON db1 WITH KEY = host").unwrap(),
+            (Some("db1".into()), "SHOW TAG VALUES WITH KEY = host".into())
+        );
+        assert_eq!(
+            resolve("SHOW FIELD KEYS ON db1 FROM db1..cpu").unwrap(),
+            (Some("db1".into()), "SHOW FIELD KEYS FROM cpu".into())
+        );
+
+        // SHOW RETENTION POLICIES defaults to the current database
+        assert_eq!(
+            resolve("SHOW RETENTION POLICIES").unwrap(),
+            (None, "SHOW RETENTION POLICIES ON db0".into())
+        );
+        assert_eq!(
+            resolve("SHOW RETENTION POLICIES ON db1").unwrap(),
+            (None, "SHOW RETENTION POLICIES ON db1".into())
+        );
+
+        // Fallible
+
+        assert_error!(
+            resolve("SELECT usage_idle FROM db1..cpu, db2..cpu"),
+            DataFusionError::NotImplemented(ref s) if s == "queries that reference more than one database"
+        );
+        assert_error!(
+            resolve("SHOW FIELD KEYS ON db1 FROM db2..cpu"),
+            DataFusionError::NotImplemented(ref s) if s == "queries that reference more than one database"
+        );
+        assert_error!(
+            resolve("SHOW MEASUREMENTS ON *"),
+            DataFusionError::NotImplemented(ref s) if s == "SHOW MEASUREMENTS ON *"
+        );
+    }
+
+    #[test]
+    fn test_statement_namespace() {
+        fn namespace(q: &str) -> Result<Option<String>> {
+            let p = InfluxQLQueryPlanner::new();
+            statement_namespace(&p.query_to_statement(q).unwrap())
+        }
+
+        assert_eq!(namespace("SELECT usage_idle FROM cpu").unwrap(), None);
+        assert_eq!(namespace("SHOW DATABASES").unwrap(), None);
+        assert_eq!(namespace("SHOW RETENTION POLICIES").unwrap(), None);
+        assert_eq!(
+            namespace("SELECT usage_idle FROM db1.one_week.cpu").unwrap(),
+            Some("db1/one_week".into())
+        );
+        assert_eq!(
+            namespace("SHOW MEASUREMENTS ON db1").unwrap(),
+            Some("db1".into())
+        );
+
+        // A retention policy requires a database
+        assert_error!(
+            namespace("SELECT usage_idle FROM autogen.cpu"),
+            DataFusionError::Plan(ref s) if s == "database name required"
+        );
+    }
+
+    #[test]
+    fn test_requires_database() {
+        let requires = |q: &str| {
+            let p = InfluxQLQueryPlanner::new();
+            requires_database(&p.query_to_statement(q).unwrap())
+        };
+
+        assert!(!requires("SHOW DATABASES"));
+        assert!(!requires("SHOW RETENTION POLICIES ON db1"));
+        assert!(requires("SHOW MEASUREMENTS"));
+        assert!(requires("SELECT usage_idle FROM cpu"));
+    }
+
     #[test]
     fn test_find_all_measurements() {
         fn find(q: &str) -> Vec<String> {
@@ -381,10 +893,58 @@ mod test {
         assert_eq!(find("SHOW TAG KEYS"), vec!["bar", "foo", "foobar"]);
         assert_eq!(find("SHOW TAG KEYS FROM /^foo/"), vec!["foo", "foobar"]);
 
+        // Find all measurements in `DELETE`
+        assert_eq!(find("DELETE FROM foo"), vec!["foo"]);
+        assert_eq!(
+            find("DELETE FROM /^foo/ WHERE time < 10"),
+            vec!["foo", "foobar"]
+        );
+        assert_eq!(find("DELETE WHERE time < 10"), vec!["bar", "foo", "foobar"]);
+
         // Finds no measurements
         assert!(find("SELECT * FROM none").is_empty());
         assert!(find("SELECT * FROM (SELECT * FROM none)").is_empty());
         assert!(find("SELECT * FROM /^l/").is_empty());
         assert!(find("SELECT * FROM (SELECT * FROM /^l/)").is_empty());
     }
+
+    #[test]
+    fn test_statement_to_modification() {
+        fn modification(q: &str) -> Result<Modification> {
+            let p = InfluxQLQueryPlanner::new();
+            let s = p.query_to_statement(q).unwrap();
+            let tables = vec!["foo".into(), "bar".into(), "foobar".into()];
+            statement_to_modification(&s, "db0", &tables)
+        }
+
+        assert_eq!(
+            modification("DROP MEASUREMENT foo").unwrap(),
+            Modification::DropMeasurement {
+                database: "db0".into(),
+                name: "foo".into()
+            }
+        );
+
+        // Measurements are deleted in a stable order
+        assert_matches!(
+            modification("DELETE WHERE time < 10").unwrap(),
+            Modification::Delete { database, measurements, .. } => {
+                assert_eq!(database, "db0");
+                assert_eq!(measurements, ["bar", "foo", "foobar"]);
+            }
+        );
+        assert_matches!(
+            modification("DELETE FROM /^foo/").unwrap(),
+            Modification::Delete { measurements, .. } => {
+                assert_eq!(measurements, ["foo", "foobar"]);
+            }
+        );
+
+        // Fallible
+
+        assert_error!(
+            modification("SELECT * FROM foo"),
+            DataFusionError::Internal(ref s) if s == "statement does not modify a database: SELECT * FROM foo"
+        );
+    }
 }
@@ -0,0 +1,201 @@
+//! Conversion of the `WHERE` clause of a `DELETE` statement to a [`DeletePredicate`].
+
+use crate::plan::error;
+use crate::plan::planner_time_range_expression::time_range_to_df_expr;
+use data_types::{DeleteExpr, DeletePredicate, Op, Scalar, TimestampRange};
+use datafusion::common::{DFSchema, Result, ScalarValue};
+use datafusion::execution::context::ExecutionProps;
+use datafusion::logical_expr::Expr as DFExpr;
+use datafusion::optimizer::simplify_expressions::{ExprSimplifier, SimplifyContext};
+use influxdb_influxql_parser::expression::{
+    ConditionalBinary, ConditionalExpression, ConditionalOperator, Expr, VarRef,
+};
+use influxdb_influxql_parser::literal::Literal;
+use std::sync::Arc;
+
+/// Returns the [`DeletePredicate`] for the `condition` of a `DELETE` statement,
+/// or a predicate matching all rows if there is no condition.
+///
+/// A delete predicate is a conjunction of comparisons, so `condition` must be
+/// one or more `AND`ed expressions that either restrict `time`, or compare a
+/// tag or field to a literal using the `=` or `!=` operators.
+pub fn delete_predicate(condition: Option<&ConditionalExpression>) -> Result<DeletePredicate> {
+    let mut builder = Builder {
+        start: i64::MIN,
+        end: i64::MAX,
+        exprs: vec![],
+    };
+
+    if let Some(condition) = condition {
+        builder.add_condition(condition)?;
+    }
+
+    Ok(DeletePredicate {
+        range: TimestampRange::new(builder.start, builder.end),
+        exprs: builder.exprs,
+    })
+}
+
+struct Builder {
+    /// The inclusive lower bound of the time range.
+    start: i64,
+    /// The exclusive upper bound of the time range.
+    end: i64,
+    exprs: Vec<DeleteExpr>,
+}
+
+impl Builder {
+    fn add_condition(&mut self, cond: &ConditionalExpression) -> Result<()> {
+        match cond {
+            ConditionalExpression::Grouped(cond) => self.add_condition(cond),
+            ConditionalExpression::Binary(ConditionalBinary {
+                lhs,
+                op: ConditionalOperator::And,
+                rhs,
+            }) => {
+                self.add_condition(lhs)?;
+                self.add_condition(rhs)
+            }
+            ConditionalExpression::Binary(ConditionalBinary { lhs, op, rhs }) => {
+                match (lhs.expr(), rhs.expr()) {
+                    (Some(Expr::VarRef(VarRef { name, .. })), Some(rhs))
+                        if name.eq_ignore_ascii_case("time") =>
+                    {
+                        self.add_time_range(*op, rhs)
+                    }
+                    (Some(Expr::VarRef(VarRef { name, .. })), Some(Expr::Literal(lit))) => {
+                        self.add_expr(name, *op, lit)
+                    }
+                    _ => error::not_implemented(format!(
+                        "DELETE with condition \"{cond}\", expected a comparison of a tag or field to a literal"
+                    )),
+                }
+            }
+            ConditionalExpression::Expr(_) => error::query(format!(
+                "invalid DELETE condition \"{cond}\", expected a conditional expression"
+            )),
+        }
+    }
+
+    fn add_time_range(&mut self, op: ConditionalOperator, expr: &Expr) -> Result<()> {
+        let ts = eval_timestamp(expr)?;
+        match op {
+            ConditionalOperator::Eq => {
+                self.start = self.start.max(ts);
+                self.end = self.end.min(ts.saturating_add(1));
+            }
+            ConditionalOperator::Lt => self.end = self.end.min(ts),
+            ConditionalOperator::LtEq => self.end = self.end.min(ts.saturating_add(1)),
+            ConditionalOperator::Gt => self.start = self.start.max(ts.saturating_add(1)),
+            ConditionalOperator::GtEq => self.start = self.start.max(ts),
+            _ => return error::not_implemented(format!("DELETE with time {op} condition")),
+        }
+        Ok(())
+    }
+
+    fn add_expr(&mut self, column: &str, op: ConditionalOperator, lit: &Literal) -> Result<()> {
+        let op = match op {
+            ConditionalOperator::Eq => Op::Eq,
+            ConditionalOperator::NotEq => Op::Ne,
+            _ => return error::not_implemented(format!("DELETE with {op} condition")),
+        };
+        let scalar = match lit {
+            Literal::String(v) => Scalar::String(v.clone()),
+            Literal::Integer(v) => Scalar::I64(*v),
+            Literal::Float(v) => Scalar::F64((*v).into()),
+            Literal::Boolean(v) => Scalar::Bool(*v),
+            _ => {
+                return error::not_implemented(format!(
+                    "DELETE with a {lit} literal, expected a string, integer, float or boolean"
+                ))
+            }
+        };
+        self.exprs
+            .push(DeleteExpr::new(column.to_string(), op, scalar));
+        Ok(())
+    }
+}
+
+/// Evaluates the time-range expression `expr`, such as `now() - 1h`, to a
+/// timestamp in nanoseconds.
+fn eval_timestamp(expr: &Expr) -> Result<i64> {
+    let df_expr = time_range_to_df_expr(expr, None)?;
+
+    let props = ExecutionProps::new();
+    let simplifier =
+        ExprSimplifier::new(SimplifyContext::new(&props).with_schema(Arc::new(DFSchema::empty())));
+    match simplifier.simplify(df_expr)? {
+        DFExpr::Literal(ScalarValue::TimestampNanosecond(Some(v), _)) => Ok(v),
+        _ => error::query(format!("invalid time range expression \"{expr}\"")),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use assert_matches::assert_matches;
+    use data_types::{MAX_NANO_TIME, MIN_NANO_TIME};
+    use influxdb_influxql_parser::delete::DeleteStatement;
+    use influxdb_influxql_parser::parse_statements;
+    use influxdb_influxql_parser::statement::Statement;
+
+    fn predicate(cond: &str) -> Result<DeletePredicate> {
+        let statement = parse_statements(&format!("DELETE FROM cpu WHERE {cond}"))
+            .unwrap()
+            .pop()
+            .unwrap();
+        let Statement::Delete(delete) = statement else { panic!("expected DELETE") };
+        let DeleteStatement::FromWhere { condition, .. } = *delete else { panic!("expected FROM") };
+        delete_predicate(condition.as_deref())
+    }
+
+    #[test]
+    fn test_delete_predicate() {
+        // No condition matches all rows
+        let got = delete_predicate(None).unwrap();
+        assert!(got.range.contains_all());
+        assert!(got.exprs.is_empty());
+
+        let got = predicate("host = 'a' AND region != 'west'").unwrap();
+        assert!(got.range.contains_all());
+        assert_eq!(
+            got.exprs,
+            vec![
+                DeleteExpr::new("host".into(), Op::Eq, Scalar::String("a".into())),
+                DeleteExpr::new("region".into(), Op::Ne, Scalar::String("west".into())),
+            ]
+        );
+
+        let got = predicate("time >= 10 AND (time < 20 AND host = 'a')").unwrap();
+        assert_eq!(got.range, TimestampRange::new(10, 20));
+        assert_eq!(
+            got.exprs,
+            vec![DeleteExpr::new(
+                "host".into(),
+                Op::Eq,
+                Scalar::String("a".into())
+            )]
+        );
+
+        let got = predicate("time > 10 AND time <= 20").unwrap();
+        assert_eq!(got.range, TimestampRange::new(11, 21));
+
+        let got = predicate("time = '2004-04-09T02:33:45Z'").unwrap();
+        assert_eq!(
+            got.range,
+            TimestampRange::new(1081478025000000000, 1081478025000000001)
+        );
+
+        // Relative to now()
+        let got = predicate("time < now() - 1h").unwrap();
+        assert_eq!(got.range.start(), MIN_NANO_TIME);
+        assert!(got.range.end() < MAX_NANO_TIME);
+
+        // Fallible
+
+        assert_matches!(predicate("host = 'a' OR host = 'b'"), Err(_));
+        assert_matches!(predicate("host =~ /a/"), Err(_));
+        assert_matches!(predicate("usage > 10"), Err(_));
+        assert_matches!(predicate("time != 10"), Err(_));
+    }
+}
@@ -7,7 +7,7 @@ use influxdb_influxql_parser::common::{
 };
 use influxdb_influxql_parser::select::{
     Field, FieldList, FillClause, FromMeasurementClause, GroupByClause, MeasurementSelection,
-    SelectStatement, TimeZoneClause,
+    SLimitClause, SOffsetClause, SelectStatement, TimeZoneClause,
 };
 
 /// Represents a validated and normalized top-level [`SelectStatement]`.
@@ -51,6 +51,12 @@ pub(super) struct Select {
     /// A value to specify an offset to start retrieving rows.
     pub(super) offset: Option<OffsetClause>,
 
+    /// A value to restrict the number of series returned.
+    pub(super) series_limit: Option<SLimitClause>,
+
+    /// A value to specify an offset to start retrieving series.
+    pub(super) series_offset: Option<SOffsetClause>,
+
     /// The timezone for the query, specified as [`tz('<time zone>')`][time_zone_clause].
     ///
     /// [time_zone_clause]: https://docs.influxdata.com/influxdb/v1.8/query_language/explore-data/#the-time-zone-clause
@@ -85,8 +91,8 @@ impl From<Select> for SelectStatement {
             order_by: value.order_by,
             limit: value.limit,
             offset: value.offset,
-            series_limit: None,
-            series_offset: None,
+            series_limit: value.series_limit,
+            series_offset: value.series_offset,
             timezone: value.timezone.map(TimeZoneClause::new),
         }
     }
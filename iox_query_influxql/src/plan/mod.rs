@@ -1,3 +1,4 @@
+mod delete_predicate;
 mod error;
 mod expr_type_evaluator;
 mod field;
@@ -9,10 +10,13 @@ mod planner_time_range_expression;
 mod rewriter;
 mod test_utils;
 mod timestamp;
+mod udaf;
+mod udf;
 mod util;
 mod util_copy;
 mod var_ref;
 
+pub use delete_predicate::delete_predicate;
 pub use planner::InfluxQLToLogicalPlan;
-pub use planner::SchemaProvider;
+pub use planner::{Database, SchemaProvider, DEFAULT_RETENTION_POLICY};
 pub(crate) use util::parse_regex;
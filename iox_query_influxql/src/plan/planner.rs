@@ -2,7 +2,9 @@ mod select;
 
 use crate::plan::ir::{DataSource, Select, SelectQuery};
 use crate::plan::planner::select::{
-    check_exprs_satisfy_columns, fields_to_exprs_no_nulls, make_tag_key_column_meta, plan_with_sort,
+    check_exprs_satisfy_columns, fields_to_exprs_no_nulls, from_table_names,
+    make_tag_key_column_meta, plan_with_sort, select_may_precede_time_range,
+    select_restrict_to_table,
 };
 use crate::plan::planner_time_range_expression::{
     duration_expr_to_nanoseconds, expr_to_df_interval_dt, time_range_to_df_expr,
@@ -12,15 +14,16 @@ use crate::plan::rewriter::{
 };
 use crate::plan::util::{binary_operator_to_df_operator, rebase_expr, Schemas};
 use crate::plan::var_ref::{data_type_to_var_ref_data_type, var_ref_data_type_to_data_type};
-use crate::plan::{error, planner_rewrite_expression};
-use arrow::array::{StringBuilder, StringDictionaryBuilder};
+use crate::plan::{error, planner_rewrite_expression, udf, util_copy};
+use arrow::array::{BooleanArray, Int64Array, StringArray, StringBuilder, StringDictionaryBuilder};
 use arrow::datatypes::{DataType, Field as ArrowField, Int32Type, Schema as ArrowSchema};
 use arrow::record_batch::RecordBatch;
 use chrono_tz::Tz;
 use datafusion::catalog::TableReference;
+use datafusion::common::tree_node::{TreeNode, VisitRecursion};
 use datafusion::common::{DFSchema, DFSchemaRef, Result, ScalarValue, ToDFSchema};
 use datafusion::datasource::{provider_as_source, MemTable};
-use datafusion::logical_expr::expr_rewriter::normalize_col;
+use datafusion::logical_expr::expr_rewriter::{normalize_col, normalize_cols};
 use datafusion::logical_expr::logical_plan::builder::project;
 use datafusion::logical_expr::logical_plan::Analyze;
 use datafusion::logical_expr::utils::{expr_as_column_expr, find_aggregate_exprs};
@@ -31,7 +34,8 @@ use datafusion::logical_expr::{
     LogicalPlan, LogicalPlanBuilder, Operator, PlanType, ScalarUDF, TableSource, ToStringifiedPlan,
     WindowFrame, WindowFrameBound, WindowFrameUnits,
 };
-use datafusion::prelude::{cast, sum, when, Column};
+use datafusion::optimizer::utils::disjunction;
+use datafusion::prelude::{cast, max, min, sum, when, Column};
 use datafusion_util::{lit_dict, AsExpr};
 use generated_types::influxdata::iox::querier::v1::InfluxQlMetadata;
 use influxdb_influxql_parser::common::{LimitClause, OffsetClause, OrderByClause};
@@ -44,11 +48,12 @@ use influxdb_influxql_parser::expression::{
 use influxdb_influxql_parser::functions::{
     is_aggregate_function, is_now_function, is_scalar_math_function,
 };
-use influxdb_influxql_parser::select::{FillClause, GroupByClause};
+use influxdb_influxql_parser::select::{FillClause, GroupByClause, SLimitClause, SOffsetClause};
 use influxdb_influxql_parser::show_field_keys::ShowFieldKeysStatement;
 use influxdb_influxql_parser::show_measurements::{
     ShowMeasurementsStatement, WithMeasurementClause,
 };
+use influxdb_influxql_parser::show_retention_policies::ShowRetentionPoliciesStatement;
 use influxdb_influxql_parser::show_tag_keys::ShowTagKeysStatement;
 use influxdb_influxql_parser::show_tag_values::{ShowTagValuesStatement, WithKeyClause};
 use influxdb_influxql_parser::simple_from_clause::ShowFromClause;
@@ -67,7 +72,10 @@ use itertools::Itertools;
 use observability_deps::tracing::debug;
 use query_functions::{
     clean_non_meta_escapes,
-    selectors::{selector_first, selector_last, selector_max, selector_min},
+    selectors::{
+        mode, selector_bottom, selector_first, selector_last, selector_max, selector_min,
+        selector_percentile, selector_sample, selector_top,
+    },
 };
 use schema::{
     InfluxColumnType, InfluxFieldType, Schema, INFLUXQL_MEASUREMENT_COLUMN_NAME,
@@ -79,6 +87,7 @@ use std::iter;
 use std::ops::{Bound, ControlFlow, Deref, Range};
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use super::parse_regex;
 
@@ -107,6 +116,21 @@ pub trait SchemaProvider {
 
     /// Get the schema for the specified `table`.
     fn table_schema(&self, name: &str) -> Option<Schema>;
+
+    /// The databases visible to the query, used to plan `SHOW DATABASES`
+    /// and `SHOW RETENTION POLICIES` statements.
+    fn databases(&self) -> &[Database];
+}
+
+/// A database, or namespace, visible to an InfluxQL query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Database {
+    /// The name of the database.
+    pub name: String,
+
+    /// The retention period of the database, or `None` if data is
+    /// retained indefinitely.
+    pub retention_period: Option<Duration>,
 }
 
 /// Informs the planner which rules should be applied when transforming
@@ -149,6 +173,13 @@ impl<'a> Context<'a> {
         }
     }
 
+    fn with_is_subquery(&self, is_subquery: bool) -> Self {
+        Self {
+            is_subquery,
+            ..*self
+        }
+    }
+
     fn with_scope(&self, scope: ExprScope) -> Self {
         Self { scope, ..*self }
     }
@@ -172,7 +203,9 @@ impl<'a> Context<'a> {
     fn is_aggregate(&self) -> bool {
         matches!(
             self.info.projection_type,
-            ProjectionType::Aggregate | ProjectionType::Selector { .. }
+            ProjectionType::Aggregate
+                | ProjectionType::Selector { .. }
+                | ProjectionType::TopBottomSelector
         )
     }
 
@@ -202,12 +235,12 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             Statement::Select(select) => {
                 self.select_statement_to_plan(&self.rewrite_select_statement(*select)?)
             }
-            Statement::ShowDatabases(_) => error::not_implemented("SHOW DATABASES"),
+            Statement::ShowDatabases(_) => self.show_databases_to_plan(),
             Statement::ShowMeasurements(show_measurements) => {
                 self.show_measurements_to_plan(*show_measurements)
             }
-            Statement::ShowRetentionPolicies(_) => {
-                error::not_implemented("SHOW RETENTION POLICIES")
+            Statement::ShowRetentionPolicies(show_retention_policies) => {
+                self.show_retention_policies_to_plan(*show_retention_policies)
             }
             Statement::ShowTagKeys(show_tag_keys) => self.show_tag_keys_to_plan(*show_tag_keys),
             Statement::ShowTagValues(show_tag_values) => {
@@ -272,9 +305,19 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
 
     /// Create a [`LogicalPlan`] from the specified InfluxQL `SELECT` statement.
     fn select_statement_to_plan(&self, query: &SelectQuery) -> Result<LogicalPlan> {
-        let select = &query.select;
+        self.select_to_plan(&query.select, query.has_multiple_measurements, false)
+    }
 
+    /// Create a [`LogicalPlan`] from the specified [`Select`], which is either the
+    /// top-level `SELECT` statement or a subquery of a `FROM` clause.
+    fn select_to_plan(
+        &self,
+        select: &Select,
+        has_multiple_measurements: bool,
+        is_subquery: bool,
+    ) -> Result<LogicalPlan> {
         let ctx = Context::new(select_statement_info(select)?)
+            .with_is_subquery(is_subquery)
             .with_timezone(select.timezone)
             .with_group_by_fill(select);
 
@@ -338,8 +381,23 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
 
         fields.extend(fields_no_time.iter().cloned());
 
+        // A subquery is planned for each of the tables it selects from, so that the rows of
+        // each measurement are aggregated separately by the outer query.
+        let from = select
+            .from
+            .iter()
+            .flat_map(|ds| match ds {
+                DataSource::Table(_) => vec![ds.clone()],
+                DataSource::Subquery(q) => from_table_names(q)
+                    .into_iter()
+                    .filter_map(|table_name| select_restrict_to_table(q, table_name))
+                    .map(|q| DataSource::Subquery(Box::new(q)))
+                    .collect(),
+            })
+            .collect::<Vec<_>>();
+
         let plan = {
-            let mut iter = select.from.iter();
+            let mut iter = from.iter();
             let plan = match iter.next() {
                 Some(ds) => self.project_select(&ctx, ds, select, &fields, &group_by_tag_set),
                 None => {
@@ -370,17 +428,23 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             })?
         };
 
-        let plan = plan_with_metadata(
-            plan,
-            &InfluxQlMetadata {
-                measurement_column_index: MEASUREMENT_COLUMN_INDEX,
-                tag_key_columns: make_tag_key_column_meta(
-                    &fields,
-                    &group_by_tag_set,
-                    &is_projected,
-                ),
-            },
-        )?;
+        // The metadata describes the schema of the results, which is only relevant
+        // to the top-level query.
+        let plan = if ctx.is_subquery {
+            plan
+        } else {
+            plan_with_metadata(
+                plan,
+                &InfluxQlMetadata {
+                    measurement_column_index: MEASUREMENT_COLUMN_INDEX,
+                    tag_key_columns: make_tag_key_column_meta(
+                        &fields,
+                        &group_by_tag_set,
+                        &is_projected,
+                    ),
+                },
+            )?
+        };
 
         // the sort planner node must refer to the time column using
         // the alias that was specified
@@ -399,10 +463,17 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             false,
         );
 
+        let plan = self.slimit(
+            plan,
+            select.series_offset,
+            select.series_limit,
+            &group_by_tag_set,
+        )?;
+
         let plan = plan_with_sort(
             plan,
             vec![time_sort_expr.clone()],
-            query.has_multiple_measurements,
+            has_multiple_measurements,
             &group_by_tag_set,
             &projection_tag_set,
         )?;
@@ -412,7 +483,7 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             select.offset,
             select.limit,
             vec![time_sort_expr],
-            query.has_multiple_measurements,
+            has_multiple_measurements,
             &group_by_tag_set,
             &projection_tag_set,
         )?;
@@ -432,7 +503,17 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
 
         let schemas = Schemas::new(plan.schema())?;
 
-        let plan = self.plan_where_clause(ctx, &select.condition, plan, &schemas)?;
+        let plan = match ds {
+            DataSource::Table(_) => {
+                self.plan_where_clause(ctx, &select.condition, plan, &schemas)?
+            }
+            DataSource::Subquery(q) if select_may_precede_time_range(q)? => {
+                self.plan_subquery_where_clause(ctx, &select.condition, plan, &schemas)?
+            }
+            DataSource::Subquery(_) => {
+                self.plan_where_clause(ctx, &select.condition, plan, &schemas)?
+            }
+        };
 
         // Transform InfluxQL AST field expressions to a list of DataFusion expressions.
         let mut select_exprs = self.field_list_to_exprs(ctx, &plan, fields, &schemas)?;
@@ -464,10 +545,21 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         let (plan, select_exprs_post_aggr) =
             self.select_aggregate(ctx, ds, plan, fields, select_exprs, group_by_tag_set)?;
 
+        let (plan, select_exprs_post_window) =
+            self.select_window(plan, fields, select_exprs_post_aggr, group_by_tag_set)?;
+
+        let (plan, select_exprs_post_hw) = self.select_holt_winters(
+            ctx,
+            plan,
+            fields,
+            select_exprs_post_window,
+            group_by_tag_set,
+        )?;
+
         // Wrap the plan in a `LogicalPlan::Projection` from the select expressions
         project(
             plan,
-            proj.into_iter().chain(select_exprs_post_aggr.into_iter()),
+            proj.into_iter().chain(select_exprs_post_hw.into_iter()),
         )
     }
 
@@ -501,6 +593,24 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         // table.
         let aggr_exprs = find_aggregate_exprs(&select_exprs);
 
+        // The `top`, `bottom` and `sample` selectors produce a list of points for each
+        // group, which are expanded to a row per point once the input is aggregated.
+        let multi_row_selector = aggr_exprs
+            .iter()
+            .find(|e| is_multi_row_selector(e))
+            .cloned();
+        if multi_row_selector.is_some() && aggr_exprs.len() > 1 {
+            return error::not_implemented(
+                "sample combined with other aggregate or selector functions",
+            );
+        }
+
+        if let (ProjectionType::TopBottomSelector, Some(selector)) =
+            (&ctx.info.projection_type, &multi_row_selector)
+        {
+            project_top_bottom_tags(fields, &mut select_exprs, selector)?;
+        }
+
         // This block identifies the time column index and updates the time expression
         // based on the semantics of the projection.
         let time_column_index = {
@@ -590,6 +700,19 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             vec![]
         };
 
+        // The `top`, `bottom` and `sample` selectors project the time of each selected
+        // point, including when the points are grouped by time.
+        if let Some(selector) = &multi_row_selector {
+            let Expr::Alias(_, alias) = &select_exprs[time_column_index] else {
+                return error::internal("time column is not an alias")
+            };
+            select_exprs[time_column_index] = Expr::GetIndexedField(GetIndexedField {
+                expr: Box::new(selector.clone()),
+                key: ScalarValue::Utf8(Some("time".to_owned())),
+            })
+            .alias(alias.clone());
+        }
+
         if aggr_exprs.is_empty() && aggr_group_by_exprs.is_empty() {
             // If there are no aggregate expressions in the projection, because
             // they all referred to non-existent columns in the table, and there
@@ -603,6 +726,19 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             .aggregate(aggr_group_by_exprs.clone(), aggr_exprs.clone())?
             .build()?;
 
+        let plan = if let Some(selector) = &multi_row_selector {
+            let points = expr_as_column_expr(selector, &plan)?;
+            let Expr::Column(points_column) = &points else {
+                return error::internal("expected column expression")
+            };
+            LogicalPlanBuilder::from(plan)
+                .unnest_column(points_column.clone())?
+                .filter(points.is_not_null())?
+                .build()?
+        } else {
+            plan
+        };
+
         let fill_option = ctx.fill();
 
         // Wrap the plan in a GapFill operator if the statement specifies a `GROUP BY TIME` clause and
@@ -615,6 +751,7 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         //
         let plan = if ctx.group_by.and_then(|gb| gb.time_dimension()).is_some()
             && fill_option != FillClause::None
+            && multi_row_selector.is_none()
         {
             let args = match select_exprs[time_column_index].clone().unalias() {
                 Expr::ScalarFunction {
@@ -702,6 +839,239 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         Ok((plan, select_exprs_post_aggr))
     }
 
+    /// Generate a plan that evaluates the window-like functions of the projection, such as
+    /// `difference` or `derivative`, replacing the stand-in function calls of `select_exprs`
+    /// with references to the output columns of the window operator.
+    ///
+    /// The input is either the raw field values of the measurement or the output of the
+    /// aggregate operator, for calls such as `difference(mean(usage))`.
+    fn select_window(
+        &self,
+        input: LogicalPlan,
+        fields: &[Field],
+        select_exprs: Vec<Expr>,
+        group_by_tag_set: &[&str],
+    ) -> Result<(LogicalPlan, Vec<Expr>)> {
+        // Find a list of unique window-like function calls from the projection.
+        let mut udf_exprs = Vec::<Expr>::new();
+        for expr in &select_exprs {
+            expr.apply(&mut |expr| {
+                Ok(match expr {
+                    Expr::ScalarUDF { fun, .. }
+                        if udf::WindowFunction::try_from_scalar_udf(fun).is_some() =>
+                    {
+                        if !udf_exprs.contains(expr) {
+                            udf_exprs.push(expr.clone())
+                        }
+                        VisitRecursion::Skip
+                    }
+                    _ => VisitRecursion::Continue,
+                })
+            })?;
+        }
+
+        if udf_exprs.is_empty() {
+            return Ok((input, select_exprs));
+        }
+
+        let Some(time_column_index) = find_time_column_index(fields) else {
+            return error::internal("unable to find time column")
+        };
+        let time_expr = select_exprs[time_column_index].clone().unalias();
+
+        // Construct the window expressions, which are evaluated as:
+        //
+        // FUNC(value, time[, arg]) OVER (
+        //   PARTITION BY [group_by_tag_set]
+        //   ORDER BY time ASC
+        //   ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW
+        // )
+        let partition_by =
+            fields_to_exprs_no_nulls(input.schema(), group_by_tag_set).collect::<Vec<_>>();
+        let window_exprs = udf_exprs
+            .iter()
+            .map(|expr| {
+                let Expr::ScalarUDF { fun, args } = expr else {
+                    unreachable!("expected stand-in function")
+                };
+                let Some(func) = udf::WindowFunction::try_from_scalar_udf(fun) else {
+                    unreachable!("expected window-like function")
+                };
+
+                let mut args = args.clone();
+                args.insert(1, time_expr.clone());
+
+                Ok(Expr::WindowFunction(WindowFunction {
+                    fun: window_function::WindowFunction::AggregateUDF(func.udaf()?),
+                    args,
+                    partition_by: partition_by.clone(),
+                    order_by: vec![time_expr.clone().sort(true, false)],
+                    window_frame: WindowFrame {
+                        units: WindowFrameUnits::Rows,
+                        start_bound: WindowFrameBound::Preceding(ScalarValue::Null),
+                        end_bound: WindowFrameBound::CurrentRow,
+                    },
+                }))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // Normalize the window expressions, so that they can be resolved to the
+        // output columns of the window operator.
+        let window_exprs = normalize_cols(window_exprs, &input)?;
+
+        let plan = LogicalPlanBuilder::from(input)
+            .window(window_exprs.clone())?
+            .build()?;
+
+        // Rewrite the projection, so that the stand-in function calls refer to the
+        // output columns of the window operator.
+        let select_exprs_post_window = select_exprs
+            .iter()
+            .map(|expr| {
+                util_copy::clone_with_replacement(expr, &|nested_expr| {
+                    udf_exprs
+                        .iter()
+                        .position(|e| e == nested_expr)
+                        .map(|i| expr_as_column_expr(&window_exprs[i], &plan))
+                        .transpose()
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // InfluxQL does not emit a row when a window-like function produces no value,
+        // such as the first row of `difference`, unless the projection includes other
+        // aggregate or selector functions.
+        let plan = if fields
+            .iter()
+            .filter(|f| is_aggregate_field(f))
+            .all(is_window_field)
+        {
+            let predicate = disjunction(
+                window_exprs
+                    .iter()
+                    .map(|expr| Ok(expr_as_column_expr(expr, &plan)?.is_not_null()))
+                    .collect::<Result<Vec<_>>>()?,
+            )
+            .ok_or_else(|| error::map::internal("expected window expressions"))?;
+
+            LogicalPlanBuilder::from(plan).filter(predicate)?.build()?
+        } else {
+            plan
+        };
+
+        Ok((plan, select_exprs_post_window))
+    }
+
+    /// Generate a plan that evaluates the `holt_winters` or `holt_winters_with_fit` function
+    /// of the projection, replacing the `time` column and the stand-in function call of
+    /// `select_exprs` with the predicted points.
+    ///
+    /// The input is the output of the aggregate operator, which is grouped by
+    /// the `GROUP BY TIME` interval, and the plan is evaluated as:
+    ///
+    /// ```text
+    /// Unnest(HOLT_WINTERS(value, time, N, S, interval) GROUP BY [group_by_tag_set])
+    /// ```
+    fn select_holt_winters(
+        &self,
+        ctx: &Context<'_>,
+        input: LogicalPlan,
+        fields: &[Field],
+        mut select_exprs: Vec<Expr>,
+        group_by_tag_set: &[&str],
+    ) -> Result<(LogicalPlan, Vec<Expr>)> {
+        let mut udf_exprs = Vec::<Expr>::new();
+        for expr in &select_exprs {
+            expr.apply(&mut |expr| {
+                Ok(match expr {
+                    Expr::ScalarUDF { fun, .. }
+                        if udf::HoltWinters::try_from_scalar_udf(fun).is_some() =>
+                    {
+                        if !udf_exprs.contains(expr) {
+                            udf_exprs.push(expr.clone())
+                        }
+                        VisitRecursion::Skip
+                    }
+                    _ => VisitRecursion::Continue,
+                })
+            })?;
+        }
+
+        let udf_expr = match udf_exprs.len() {
+            0 => return Ok((input, select_exprs)),
+            1 if fields.iter().filter(|f| is_aggregate_field(f)).count() == 1 => {
+                udf_exprs.remove(0)
+            }
+            _ => {
+                return error::not_implemented(
+                    "holt_winters combined with other aggregate or selector functions",
+                )
+            }
+        };
+
+        let Expr::ScalarUDF { fun, args } = &udf_expr else {
+            unreachable!("expected stand-in function")
+        };
+        let Some(func) = udf::HoltWinters::try_from_scalar_udf(fun) else {
+            unreachable!("expected holt_winters function")
+        };
+
+        // The predicted points are spaced by the GROUP BY TIME interval, which is
+        // required by `select_statement_info`.
+        let Some(dim) = ctx.group_by.and_then(|gb| gb.time_dimension()) else {
+            return error::internal("expected GROUP BY TIME interval")
+        };
+        let interval = duration_expr_to_nanoseconds(&dim.interval)?;
+
+        let Some(time_column_index) = find_time_column_index(fields) else {
+            return error::internal("unable to find time column")
+        };
+        let Expr::Alias(time_expr, alias) = &select_exprs[time_column_index] else {
+            return error::internal("time column is not an alias")
+        };
+
+        let mut args = args.clone();
+        args.insert(1, time_expr.as_ref().clone());
+        args.push(lit(interval));
+        let aggr_expr = normalize_col(func.udaf()?.call(args), &input)?;
+
+        let group_by_exprs =
+            fields_to_exprs_no_nulls(input.schema(), group_by_tag_set).collect::<Vec<_>>();
+
+        let plan = LogicalPlanBuilder::from(input)
+            .aggregate(group_by_exprs, vec![aggr_expr.clone()])?
+            .build()?;
+
+        // Expand the list of predicted points to a row per point.
+        let points = expr_as_column_expr(&aggr_expr, &plan)?;
+        let Expr::Column(points_column) = &points else {
+            return error::internal("expected column expression")
+        };
+        let plan = LogicalPlanBuilder::from(plan)
+            .unnest_column(points_column.clone())?
+            .filter(points.clone().is_not_null())?
+            .build()?;
+
+        let point_field = |name: &str| {
+            Expr::GetIndexedField(GetIndexedField {
+                expr: Box::new(points.clone()),
+                key: ScalarValue::Utf8(Some(name.to_owned())),
+            })
+        };
+
+        select_exprs[time_column_index] = point_field("time").alias(alias);
+        let select_exprs_post_hw = select_exprs
+            .iter()
+            .map(|expr| {
+                util_copy::clone_with_replacement(expr, &|nested_expr| {
+                    Ok((nested_expr == &udf_expr).then(|| point_field("value")))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok((plan, select_exprs_post_hw))
+    }
+
     /// Generate a plan that partitions the input data into groups, first omitting a specified
     /// number of rows, followed by restricting the quantity of rows within each group.
     ///
@@ -793,30 +1163,8 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                 .transpose()
                 .map_err(|_| error::map::query("offset out of range".to_owned()))?;
 
-            // a reference to the ROW_NUMBER column.
-            let row_alias = IOX_ROW_ALIAS.as_expr();
-
-            let row_filter_expr = match (limit, offset) {
-                // WHERE "iox::row" BETWEEN OFFSET + 1 AND OFFSET + LIMIT
-                (Some(limit), Some(offset)) => {
-                    let low = offset + 1;
-                    let high = offset + limit;
-
-                    Expr::Between(Between {
-                        expr: Box::new(row_alias),
-                        negated: false,
-                        low: Box::new(lit(low)),
-                        high: Box::new(lit(high)),
-                    })
-                }
-
-                // WHERE "iox::row" <= LIMIT
-                (Some(limit), None) => row_alias.lt_eq(lit(limit)),
-
-                // WHERE "iox::row" > OFFSET
-                (None, Some(offset)) => row_alias.gt(lit(offset)),
-                (None, None) => unreachable!("limit and offset cannot not be None"),
-            };
+            // Filter using a reference to the ROW_NUMBER column.
+            let row_filter_expr = limit_offset_filter_expr(IOX_ROW_ALIAS.as_expr(), limit, offset);
 
             let plan = LogicalPlanBuilder::from(plan)
                 // Filter by the LIMIT and OFFSET clause
@@ -837,6 +1185,81 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         }
     }
 
+    /// Wrap the `input` plan such that it only produces the series selected by the
+    /// `SLIMIT` and `SOFFSET` clauses.
+    ///
+    /// A series is identified by the values of the `group_by_tag_set`, and series are
+    /// numbered within each measurement in ascending order of their tag values, which
+    /// is the order of the series keys in InfluxDB 1.x. If the query does not group by
+    /// any tags, each measurement is a single series.
+    fn slimit(
+        &self,
+        input: LogicalPlan,
+        series_offset: Option<SOffsetClause>,
+        series_limit: Option<SLimitClause>,
+        group_by_tag_set: &[&str],
+    ) -> Result<LogicalPlan> {
+        if series_offset.is_none() && series_limit.is_none() {
+            return Ok(input);
+        }
+
+        // The name of the DENSE_RANK window expression
+        const IOX_SERIES_ALIAS: &str = "iox::series";
+
+        // Construct a DENSE_RANK window expression, such that each row of a series is
+        // assigned the same number:
+        //
+        // DENSE_RANK() OVER (
+        //   PARTITION BY [iox::measurement]
+        //   ORDER BY [group_by_tag_set]
+        // ) AS iox::series
+        //
+        // An empty tag value sorts first in a series key, so NULL tag values are ordered first.
+        let order_by = fields_to_exprs_no_nulls(input.schema(), group_by_tag_set)
+            .map(|expr| expr.sort(true, true))
+            .collect::<Vec<_>>();
+        let window_frame = WindowFrame::new(!order_by.is_empty());
+        let window_func_exprs = vec![Expr::WindowFunction(WindowFunction {
+            fun: window_function::WindowFunction::BuiltInWindowFunction(
+                BuiltInWindowFunction::DenseRank,
+            ),
+            args: vec![],
+            partition_by: vec![INFLUXQL_MEASUREMENT_COLUMN_NAME.as_expr()],
+            order_by,
+            window_frame,
+        })
+        .alias(IOX_SERIES_ALIAS)];
+
+        // Prepare new projection.
+        let proj_exprs = input
+            .schema()
+            .fields()
+            .iter()
+            .map(|expr| Expr::Column(expr.unqualified_column()))
+            .collect::<Vec<_>>();
+
+        let series_limit = series_limit
+            .map(|v| <u64 as TryInto<i64>>::try_into(*v))
+            .transpose()
+            .map_err(|_| error::map::query("series limit out of range"))?;
+        let series_offset = series_offset
+            .map(|v| <u64 as TryInto<i64>>::try_into(*v))
+            .transpose()
+            .map_err(|_| error::map::query("series offset out of range"))?;
+
+        LogicalPlanBuilder::from(input)
+            .window(window_func_exprs)?
+            // Filter by the SLIMIT and SOFFSET clause
+            .filter(limit_offset_filter_expr(
+                IOX_SERIES_ALIAS.as_expr(),
+                series_limit,
+                series_offset,
+            ))?
+            // Project the output without the IOX_SERIES_ALIAS column
+            .project(proj_exprs)?
+            .build()
+    }
+
     /// Map the InfluxQL `SELECT` projection list into a list of DataFusion expressions.
     fn field_list_to_exprs(
         &self,
@@ -997,7 +1420,8 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                     },
                 })
             }
-            IQLExpr::BindParameter(_) => error::not_implemented("parameter"),
+            // Bind parameters are substituted with their values prior to planning.
+            IQLExpr::BindParameter(_) => error::internal("unexpected bind parameter"),
             IQLExpr::Literal(val) => match val {
                 Literal::Integer(v) => Ok(lit(*v)),
                 Literal::Unsigned(v) => Ok(lit(*v)),
@@ -1008,7 +1432,9 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                     Some(v.timestamp()),
                     None,
                 ))),
-                Literal::Duration(_) => error::not_implemented("duration literal"),
+                // A duration outside of a time range expression evaluates to an
+                // integer number of nanoseconds.
+                Literal::Duration(v) => Ok(lit(**v)),
                 Literal::Regex(re) => match ctx.scope {
                     // a regular expression in a projection list is unexpected,
                     // as it should have been expanded by the rewriter.
@@ -1047,14 +1473,18 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
             return self.scalar_math_func_to_df_expr(ctx, call, schemas);
         }
 
+        if is_now_function(&call.name) {
+            return if call.args.is_empty() {
+                Ok(now())
+            } else {
+                error::query("invalid number of arguments for now, expected 0")
+            };
+        }
+
         match ctx.scope {
             ExprScope::Where => {
-                if is_now_function(&call.name) {
-                    error::not_implemented("now")
-                } else {
-                    let name = &call.name;
-                    error::query(format!("invalid function call in condition: {name}"))
-                }
+                let name = &call.name;
+                error::query(format!("invalid function call in condition: {name}"))
             }
             ExprScope::Projection => self.function_to_df_expr(ctx, call, schemas),
         }
@@ -1137,6 +1567,214 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                     key: ScalarValue::Utf8(Some("value".to_owned())),
                 }))
             }
+            "mode" => {
+                let expr = self.expr_to_df_expr(ctx, &args[0], schemas)?;
+                if let Expr::Literal(ScalarValue::Null) = expr {
+                    return Ok(expr);
+                }
+
+                check_arg_count("mode", args, 1)?;
+                Ok(mode().call(vec![expr, "time".as_expr()]))
+            }
+            "spread" => {
+                let expr = self.expr_to_df_expr(ctx, &args[0], schemas)?;
+                if let Expr::Literal(ScalarValue::Null) = expr {
+                    return Ok(expr);
+                }
+
+                check_arg_count("spread", args, 1)?;
+                Ok(binary_expr(max(expr.clone()), Operator::Minus, min(expr)))
+            }
+            "percentile" => {
+                let expr = self.expr_to_df_expr(ctx, &args[0], schemas)?;
+                if let Expr::Literal(ScalarValue::Null) = expr {
+                    return Ok(expr);
+                }
+
+                check_arg_count("percentile", args, 2)?;
+                let percentile = match &args[1] {
+                    IQLExpr::Literal(Literal::Integer(v)) => *v as f64,
+                    IQLExpr::Literal(Literal::Float(v)) => *v,
+                    _ => return error::query("expected number for percentile()"),
+                };
+
+                Ok(Expr::GetIndexedField(GetIndexedField {
+                    expr: Box::new(selector_percentile().call(vec![
+                        expr,
+                        "time".as_expr(),
+                        lit(percentile),
+                    ])),
+                    key: ScalarValue::Utf8(Some("value".to_owned())),
+                }))
+            }
+            name @ ("top" | "bottom" | "sample") => {
+                let expr = self.expr_to_df_expr(ctx, &args[0], schemas)?;
+                if let Expr::Literal(ScalarValue::Null) = expr {
+                    return Ok(expr);
+                }
+
+                let Some((IQLExpr::Literal(Literal::Integer(n)), rest)) = args[1..].split_last() else {
+                    return error::query(format!("expected integer as last argument for {name}"))
+                };
+
+                let mut df_args = vec![expr, "time".as_expr(), lit(*n)];
+                let selector = match name {
+                    "top" => selector_top(),
+                    "bottom" => selector_bottom(),
+                    _ => selector_sample(),
+                };
+
+                // The tags of `top` and `bottom` are passed as additional arguments,
+                // which are projected by the fields added by the rewriter.
+                for arg in rest {
+                    match arg {
+                        IQLExpr::VarRef(VarRef {
+                            data_type: Some(VarRefDataType::Tag) | None,
+                            ..
+                        }) => df_args.push(self.expr_to_df_expr(ctx, arg, schemas)?),
+                        _ => return error::not_implemented(format!("{name} with field arguments")),
+                    }
+                }
+
+                Ok(Expr::GetIndexedField(GetIndexedField {
+                    expr: Box::new(selector.call(df_args)),
+                    key: ScalarValue::Utf8(Some("value".to_owned())),
+                }))
+            }
+            name @ ("difference" | "non_negative_difference" | "cumulative_sum") => {
+                let expr = self.expr_to_df_expr(ctx, &args[0], schemas)?;
+                if let Expr::Literal(ScalarValue::Null) = expr {
+                    return Ok(expr);
+                }
+
+                check_arg_count(name, args, 1)?;
+                window_function_call(name, vec![expr])
+            }
+            name @ ("derivative" | "non_negative_derivative" | "elapsed") => {
+                let expr = self.expr_to_df_expr(ctx, &args[0], schemas)?;
+                if let Expr::Literal(ScalarValue::Null) = expr {
+                    return Ok(expr);
+                }
+
+                // The unit defaults to the GROUP BY TIME interval, or one second, for
+                // derivative, and to one nanosecond for elapsed.
+                let unit = match args.get(1) {
+                    Some(IQLExpr::Literal(Literal::Duration(d))) => **d,
+                    Some(_) => {
+                        return error::query(format!(
+                            "second argument to {name} must be a duration"
+                        ))
+                    }
+                    None if name == "elapsed" => 1,
+                    None => match ctx.group_by.and_then(|gb| gb.time_dimension()) {
+                        Some(dim) => duration_expr_to_nanoseconds(&dim.interval)?,
+                        None => 1_000_000_000,
+                    },
+                };
+
+                // A zero unit divides by zero and a negative unit flips the sign of the result.
+                if unit <= 0 {
+                    return error::query(format!(
+                        "duration argument must be positive, got {}",
+                        influxdb_influxql_parser::literal::Duration::from(unit)
+                    ));
+                }
+
+                window_function_call(name, vec![expr, lit(unit)])
+            }
+            "moving_average" => {
+                let expr = self.expr_to_df_expr(ctx, &args[0], schemas)?;
+                if let Expr::Literal(ScalarValue::Null) = expr {
+                    return Ok(expr);
+                }
+
+                check_arg_count("moving_average", args, 2)?;
+                let n = match &args[1] {
+                    IQLExpr::Literal(Literal::Integer(n)) if *n > 0 => *n,
+                    _ => {
+                        return error::query(
+                            "second argument for moving_average must be a positive integer",
+                        )
+                    }
+                };
+
+                window_function_call("moving_average", vec![expr, lit(n)])
+            }
+            name @ ("exponential_moving_average"
+            | "double_exponential_moving_average"
+            | "triple_exponential_moving_average"
+            | "relative_strength_index"
+            | "triple_exponential_derivative"
+            | "kaufmans_efficiency_ratio"
+            | "kaufmans_adaptive_moving_average"
+            | "chande_momentum_oscillator") => {
+                let expr = self.expr_to_df_expr(ctx, &args[0], schemas)?;
+                if let Expr::Literal(ScalarValue::Null) = expr {
+                    return Ok(expr);
+                }
+
+                let period = match args.get(1) {
+                    Some(IQLExpr::Literal(Literal::Integer(n))) if *n > 0 => *n,
+                    _ => {
+                        return error::query(format!(
+                            "second argument for {name} must be a positive integer"
+                        ))
+                    }
+                };
+
+                // A hold period of -1 is replaced by the number of values required
+                // to warm up the indicator.
+                let hold = match args.get(2) {
+                    Some(IQLExpr::Literal(Literal::Integer(n))) => *n,
+                    Some(_) => {
+                        return error::query(format!(
+                            "third argument for {name} must be an integer"
+                        ))
+                    }
+                    None => -1,
+                };
+
+                let mut df_args = vec![expr, lit(period), lit(hold)];
+                if !name.starts_with("kaufmans_") {
+                    let warmup = match args.get(3) {
+                        Some(IQLExpr::Literal(Literal::String(s))) => s.as_str(),
+                        Some(_) => {
+                            return error::query(format!(
+                                "fourth argument for {name} must be a string"
+                            ))
+                        }
+                        None if name == "chande_momentum_oscillator" => "none",
+                        None => "exponential",
+                    };
+                    df_args.push(lit(warmup));
+                }
+
+                window_function_call(name, df_args)
+            }
+            name @ ("holt_winters" | "holt_winters_with_fit") => {
+                let expr = self.expr_to_df_expr(ctx, &args[0], schemas)?;
+                if let Expr::Literal(ScalarValue::Null) = expr {
+                    return Ok(expr);
+                }
+
+                check_arg_count(name, args, 3)?;
+                let (n, s) = match (&args[1], &args[2]) {
+                    (
+                        IQLExpr::Literal(Literal::Integer(n)),
+                        IQLExpr::Literal(Literal::Integer(s)),
+                    ) => (*n, *s),
+                    _ => {
+                        return error::query(format!(
+                            "N and S arguments for {name} must be integers"
+                        ))
+                    }
+                };
+
+                let Some(func) = udf::HoltWinters::try_from_name(name) else {
+                    return error::internal(format!("expected holt_winters function, got {name}"))
+                };
+                Ok(func.call(vec![expr, lit(n), lit(s)]))
+            }
             _ => error::query(format!("Invalid function '{name}'")),
         }
     }
@@ -1176,6 +1814,12 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         expr: &Binary,
         schemas: &Schemas,
     ) -> Result<Expr> {
+        // Arithmetic with now() is a time expression, which is reduced
+        // to a timestamp using the same rules as a time range.
+        if has_now_call(expr) {
+            return time_range_to_df_expr(&IQLExpr::Binary(expr.clone()), ctx.tz);
+        }
+
         Ok(binary_expr(
             self.expr_to_df_expr(ctx, &expr.lhs, schemas)?,
             binary_operator_to_df_operator(expr.op),
@@ -1210,6 +1854,33 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         }
     }
 
+    /// Plan the `WHERE` clause of a query that selects from a subquery that may
+    /// produce rows timestamped before the lower bound of the time range, such
+    /// as the start of a `GROUP BY` interval.
+    ///
+    /// Only the upper bound of the time range is applied, as the time range was
+    /// inherited by the subquery. The upper bound is retained to bound the gap
+    /// filling of the outer query.
+    fn plan_subquery_where_clause(
+        &self,
+        ctx: &Context<'_>,
+        condition: &Option<WhereClause>,
+        plan: LogicalPlan,
+        schemas: &Schemas,
+    ) -> Result<LogicalPlan> {
+        let Some(where_clause) = condition else {
+            return Ok(plan)
+        };
+
+        let filter_expr =
+            self.conditional_to_df_expr(&ctx.with_scope(ExprScope::Where), where_clause, schemas)?;
+        let filter_expr =
+            planner_rewrite_expression::remove_non_upper_bound_time_range_exprs(filter_expr);
+        let filter_expr =
+            planner_rewrite_expression::rewrite_conditional_expr(filter_expr, schemas)?;
+        LogicalPlanBuilder::from(plan).filter(filter_expr)?.build()
+    }
+
     /// Generate a list of logical plans for each of the tables references in the `FROM`
     /// clause.
     fn plan_from_data_source(&self, ds: &DataSource) -> Result<(LogicalPlan, Vec<Expr>)> {
@@ -1219,11 +1890,20 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                 let source = self.s.get_table_provider(table_name)?;
                 let table_ref = TableReference::bare(table_name.to_owned());
                 Ok((
-                    LogicalPlanBuilder::scan(table_ref, source, None)?.build()?,
+                    LogicalPlanBuilder::scan(table_ref, source, None)?.build()?,
+                    vec![lit_dict(table_name).alias(INFLUXQL_MEASUREMENT_COLUMN_NAME)],
+                ))
+            }
+            DataSource::Subquery(select) => {
+                // `select_to_plan` restricts each subquery to a single table
+                let Some(table_name) = from_table_names(select).into_iter().exactly_one().ok() else {
+                    return error::internal("expected subquery to select from a single table")
+                };
+                Ok((
+                    self.select_to_plan(select, false, true)?,
                     vec![lit_dict(table_name).alias(INFLUXQL_MEASUREMENT_COLUMN_NAME)],
                 ))
             }
-            DataSource::Subquery(_) => error::not_implemented("subquery in FROM clause"),
         }
     }
 
@@ -1244,6 +1924,10 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
     }
 
     /// Expand tables from `FROM` clause in metadata queries.
+    ///
+    /// Database and retention policy qualifiers are removed from the
+    /// statement before it is planned, when the namespace it runs against
+    /// is resolved.
     fn expand_show_from_clause(&self, from: Option<ShowFromClause>) -> Result<Vec<String>> {
         match from {
             None => {
@@ -1260,12 +1944,6 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                 let all_tables = self.s.table_names().into_iter().collect::<HashSet<_>>();
                 let mut out = HashSet::new();
                 for qualified_name in from.iter() {
-                    if qualified_name.database.is_some() {
-                        return error::not_implemented("database name in from clause");
-                    }
-                    if qualified_name.retention_policy.is_some() {
-                        return error::not_implemented("retention policy in from clause");
-                    }
                     match &qualified_name.name {
                         MeasurementName::Name(name) => {
                             let name = name.as_str();
@@ -1296,18 +1974,6 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         with_measurement: Option<WithMeasurementClause>,
     ) -> Result<Vec<String>> {
         match with_measurement {
-            Some(
-                WithMeasurementClause::Equals(qualified_name)
-                | WithMeasurementClause::Regex(qualified_name),
-            ) if qualified_name.database.is_some() => {
-                error::not_implemented("database name in from clause")
-            }
-            Some(
-                WithMeasurementClause::Equals(qualified_name)
-                | WithMeasurementClause::Regex(qualified_name),
-            ) if qualified_name.retention_policy.is_some() => {
-                error::not_implemented("retention policy in from clause")
-            }
             Some(WithMeasurementClause::Equals(qualified_name)) => match qualified_name.name {
                 MeasurementName::Name(n) => {
                     let names = self.s.table_names();
@@ -1349,11 +2015,6 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
     }
 
     fn show_tag_keys_to_plan(&self, show_tag_keys: ShowTagKeysStatement) -> Result<LogicalPlan> {
-        if show_tag_keys.database.is_some() {
-            // How do we handle this? Do we need to perform cross-namespace queries here?
-            return error::not_implemented("SHOW TAG KEYS ON <database>");
-        }
-
         let tag_key_col = "tagKey";
         let output_schema = Arc::new(ArrowSchema::new(vec![
             ArrowField::new(
@@ -1516,11 +2177,6 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         &self,
         show_field_keys: ShowFieldKeysStatement,
     ) -> Result<LogicalPlan> {
-        if show_field_keys.database.is_some() {
-            // How do we handle this? Do we need to perform cross-namespace queries here?
-            return error::not_implemented("SHOW FIELD KEYS ON <database>");
-        }
-
         let field_key_col = "fieldKey";
         let output_schema = Arc::new(ArrowSchema::new(vec![
             ArrowField::new(INFLUXQL_MEASUREMENT_COLUMN_NAME, DataType::Utf8, false),
@@ -1594,11 +2250,6 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         &self,
         show_tag_values: ShowTagValuesStatement,
     ) -> Result<LogicalPlan> {
-        if show_tag_values.database.is_some() {
-            // How do we handle this? Do we need to perform cross-namespace queries here?
-            return error::not_implemented("SHOW TAG VALUES ON <database>");
-        }
-
         let key_col = "key";
         let value_col = "value";
         let output_schema = Arc::new(ArrowSchema::new(vec![
@@ -1700,11 +2351,6 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         &self,
         show_measurements: ShowMeasurementsStatement,
     ) -> Result<LogicalPlan> {
-        if show_measurements.on.is_some() {
-            // How do we handle this? Do we need to perform cross-namespace queries here?
-            return error::not_implemented("SHOW MEASUREMENTS ON <database>");
-        }
-
         let tables = self.expand_with_measurement_clause(show_measurements.with_measurement)?;
 
         let name_col = "name";
@@ -1814,6 +2460,106 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
         Ok(plan)
     }
 
+    fn show_databases_to_plan(&self) -> Result<LogicalPlan> {
+        let output_schema = Arc::new(ArrowSchema::new(vec![
+            ArrowField::new(INFLUXQL_MEASUREMENT_COLUMN_NAME, DataType::Utf8, false),
+            ArrowField::new("name", DataType::Utf8, false),
+        ]));
+
+        let mut measurement_names_builder = StringBuilder::new();
+        let mut name_builder = StringBuilder::new();
+        for db in self
+            .s
+            .databases()
+            .iter()
+            .sorted_by(|a, b| a.name.cmp(&b.name))
+        {
+            measurement_names_builder.append_value("databases");
+            name_builder.append_value(&db.name);
+        }
+
+        let plan = LogicalPlanBuilder::scan(
+            "databases",
+            provider_as_source(Arc::new(MemTable::try_new(
+                Arc::clone(&output_schema),
+                vec![vec![RecordBatch::try_new(
+                    Arc::clone(&output_schema),
+                    vec![
+                        Arc::new(measurement_names_builder.finish()),
+                        Arc::new(name_builder.finish()),
+                    ],
+                )?]],
+            )?)),
+            None,
+        )?
+        .build()?;
+
+        plan_with_metadata(
+            plan,
+            &InfluxQlMetadata {
+                measurement_column_index: MEASUREMENT_COLUMN_INDEX,
+                tag_key_columns: vec![],
+            },
+        )
+    }
+
+    fn show_retention_policies_to_plan(
+        &self,
+        show_retention_policies: ShowRetentionPoliciesStatement,
+    ) -> Result<LogicalPlan> {
+        let Some(name) = show_retention_policies.database else {
+            return error::query("database name required");
+        };
+        let Some(db) = self.s.databases().iter().find(|db| db.name == name.as_str()) else {
+            return error::query(format!("database not found: {}", name.as_str()));
+        };
+
+        let output_schema = Arc::new(ArrowSchema::new(vec![
+            ArrowField::new(INFLUXQL_MEASUREMENT_COLUMN_NAME, DataType::Utf8, false),
+            ArrowField::new("name", DataType::Utf8, false),
+            ArrowField::new("duration", DataType::Utf8, false),
+            ArrowField::new("shardGroupDuration", DataType::Utf8, false),
+            ArrowField::new("replicaN", DataType::Int64, false),
+            ArrowField::new("default", DataType::Boolean, false),
+        ]));
+
+        // A namespace has a single retention period, which is presented
+        // as the default retention policy of the database.
+        let batch = RecordBatch::try_new(
+            Arc::clone(&output_schema),
+            vec![
+                Arc::new(StringArray::from(vec![""])),
+                Arc::new(StringArray::from(vec![DEFAULT_RETENTION_POLICY])),
+                Arc::new(StringArray::from(vec![format_duration(
+                    db.retention_period.unwrap_or_default(),
+                )])),
+                Arc::new(StringArray::from(vec![format_duration(
+                    shard_group_duration(db.retention_period),
+                )])),
+                Arc::new(Int64Array::from(vec![1])),
+                Arc::new(BooleanArray::from(vec![true])),
+            ],
+        )?;
+
+        let plan = LogicalPlanBuilder::scan(
+            "retention_policies",
+            provider_as_source(Arc::new(MemTable::try_new(
+                Arc::clone(&output_schema),
+                vec![vec![batch]],
+            )?)),
+            None,
+        )?
+        .build()?;
+
+        plan_with_metadata(
+            plan,
+            &InfluxQlMetadata {
+                measurement_column_index: MEASUREMENT_COLUMN_INDEX,
+                tag_key_columns: vec![],
+            },
+        )
+    }
+
     fn metadata_cutoff(&self) -> MetadataCutoff {
         self.iox_ctx
             .inner()
@@ -1828,6 +2574,34 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
     }
 }
 
+/// Returns a filter expression that selects the values of `expr`, which numbers
+/// rows from 1, within the range specified by `limit` and `offset`.
+fn limit_offset_filter_expr(expr: Expr, limit: Option<i64>, offset: Option<i64>) -> Expr {
+    match (limit, offset) {
+        // WHERE expr BETWEEN OFFSET + 1 AND OFFSET + LIMIT
+        (Some(limit), Some(offset)) => {
+            let low = offset + 1;
+            let high = offset + limit;
+
+            Expr::Between(Between {
+                expr: Box::new(expr),
+                negated: false,
+                low: Box::new(lit(low)),
+                high: Box::new(lit(high)),
+            })
+        }
+
+        // WHERE expr <= LIMIT
+        (Some(limit), None) => expr.lt_eq(lit(limit)),
+
+        // WHERE expr > OFFSET
+        (None, Some(offset)) => expr.gt(lit(offset)),
+
+        // Every row is selected
+        (None, None) => lit(true),
+    }
+}
+
 /// Returns a [`LogicalPlan`] that performs gap-filling for the `input` plan.
 ///
 /// # Arguments
@@ -1912,6 +2686,34 @@ fn build_gap_fill_node(
     }))
 }
 
+/// The retention policy of every database, as InfluxQL databases map to
+/// namespaces, which have a single retention period.
+pub const DEFAULT_RETENTION_POLICY: &str = "autogen";
+
+/// Returns the shard group duration InfluxDB 1.x would choose for a
+/// retention policy with the specified `retention_period`.
+fn shard_group_duration(retention_period: Option<Duration>) -> Duration {
+    const HOUR: u64 = 60 * 60;
+    const DAY: u64 = 24 * HOUR;
+
+    match retention_period.map(|d| d.as_secs()) {
+        Some(secs) if secs < 2 * DAY => Duration::from_secs(HOUR),
+        Some(secs) if secs < 180 * DAY => Duration::from_secs(DAY),
+        _ => Duration::from_secs(7 * DAY),
+    }
+}
+
+/// Formats `d` the same as the Go `time.Duration` type, which is how
+/// InfluxDB 1.x presents retention policy durations. A zero duration
+/// represents an infinite retention period.
+fn format_duration(d: Duration) -> String {
+    let secs = d.as_secs();
+    if secs == 0 {
+        return "0s".to_string();
+    }
+    format!("{}h{}m{}s", secs / 3600, secs % 3600 / 60, secs % 60)
+}
+
 /// Adds [`InfluxQlMetadata`] to the `plan`.
 fn plan_with_metadata(plan: LogicalPlan, metadata: &InfluxQlMetadata) -> Result<LogicalPlan> {
     fn make_schema(schema: DFSchemaRef, metadata: &InfluxQlMetadata) -> Result<DFSchemaRef> {
@@ -2039,6 +2841,89 @@ fn is_aggregate_field(f: &Field) -> bool {
     .is_break()
 }
 
+/// Returns `true` if either operand of the binary expression `expr` contains a
+/// call to `now()`.
+fn has_now_call(expr: &Binary) -> bool {
+    [&expr.lhs, &expr.rhs].into_iter().any(|e| {
+        walk_expr(e, &mut |e| match e {
+            IQLExpr::Call(Call { name, .. }) if is_now_function(name) => ControlFlow::Break(()),
+            _ => ControlFlow::Continue(()),
+        })
+        .is_break()
+    })
+}
+
+/// Returns a call to the stand-in function of the window-like function `name`,
+/// which is replaced by a window expression by `select_window`.
+fn window_function_call(name: &str, args: Vec<Expr>) -> Result<Expr> {
+    udf::WindowFunction::try_from_name(name)
+        .map(|func| func.call(args))
+        .ok_or_else(|| error::map::internal(format!("expected window-like function, got {name}")))
+}
+
+/// Returns `true` if `expr` is a call to a selector that produces a list of points
+/// for each group, such as `top`.
+fn is_multi_row_selector(expr: &Expr) -> bool {
+    matches!(
+        expr,
+        Expr::AggregateUDF { fun, .. }
+            if matches!(fun.name.as_str(), "selector_top" | "selector_bottom" | "selector_sample")
+    )
+}
+
+/// Replace the projection of the tags that are arguments of the `top` or `bottom`
+/// function, which are added to the field list by the rewriter, with the values
+/// of the tags of the points returned by `selector`.
+fn project_top_bottom_tags(
+    fields: &[Field],
+    select_exprs: &mut [Expr],
+    selector: &Expr,
+) -> Result<()> {
+    let Some(tags) = fields.iter().find_map(|f| match &f.expr {
+        IQLExpr::Call(Call { name, args }) if name == "top" || name == "bottom" => {
+            Some(&args[1..args.len() - 1])
+        }
+        _ => None,
+    }) else {
+        return Ok(())
+    };
+
+    for (f, expr) in fields.iter().zip(select_exprs.iter_mut()) {
+        let IQLExpr::VarRef(VarRef { name, .. }) = &f.expr else {
+            continue
+        };
+        let Some(i) = tags.iter().position(
+            |t| matches!(t, IQLExpr::VarRef(VarRef { name: tag, .. }) if tag == name),
+        ) else {
+            continue
+        };
+        let Expr::Alias(_, alias) = expr else {
+            return error::internal("expected aliased expression")
+        };
+        let alias = alias.clone();
+
+        *expr = Expr::GetIndexedField(GetIndexedField {
+            expr: Box::new(selector.clone()),
+            key: ScalarValue::Utf8(Some(format!("aux{i}"))),
+        })
+        .alias(alias);
+    }
+
+    Ok(())
+}
+
+/// Returns `true` if the field contains a call to a window-like function,
+/// such as `difference`.
+fn is_window_field(f: &Field) -> bool {
+    walk_expr(&f.expr, &mut |e| match e {
+        IQLExpr::Call(Call { name, .. }) if udf::WindowFunction::try_from_name(name).is_some() => {
+            ControlFlow::Break(())
+        }
+        _ => ControlFlow::Continue(()),
+    })
+    .is_break()
+}
+
 /// Find all the columns where the resolved data type
 /// is a tag or is [`None`], which is unknown.
 fn find_tag_and_unknown_columns(fields: &[Field]) -> impl Iterator<Item = &str> {
@@ -2250,13 +3135,54 @@ mod test {
         assert_snapshot!(plan("CREATE DATABASE foo"), @"This feature is not implemented: CREATE DATABASE");
         assert_snapshot!(plan("DELETE FROM foo"), @"This feature is not implemented: DELETE");
         assert_snapshot!(plan("DROP MEASUREMENT foo"), @"This feature is not implemented: DROP MEASUREMENT");
-        assert_snapshot!(plan("SHOW DATABASES"), @"This feature is not implemented: SHOW DATABASES");
-        assert_snapshot!(plan("SHOW RETENTION POLICIES"), @"This feature is not implemented: SHOW RETENTION POLICIES");
     }
 
     mod metadata_queries {
         use super::*;
 
+        #[test]
+        fn test_show_databases() {
+            assert_snapshot!(plan("SHOW DATABASES"), @"TableScan: databases [iox::measurement:Utf8, name:Utf8]");
+        }
+
+        #[test]
+        fn test_show_retention_policies() {
+            assert_snapshot!(plan("SHOW RETENTION POLICIES ON db0"), @"TableScan: retention_policies [iox::measurement:Utf8, name:Utf8, duration:Utf8, shardGroupDuration:Utf8, replicaN:Int64, default:Boolean]");
+
+            // Fallible
+
+            assert_snapshot!(plan("SHOW RETENTION POLICIES"), @"Error during planning: database name required");
+            assert_snapshot!(plan("SHOW RETENTION POLICIES ON foo"), @"Error during planning: database not found: foo");
+        }
+
+        #[test]
+        fn test_format_duration() {
+            assert_eq!(format_duration(Duration::ZERO), "0s");
+            assert_eq!(format_duration(Duration::from_secs(90)), "0h1m30s");
+            assert_eq!(
+                format_duration(Duration::from_secs(7 * 24 * 60 * 60)),
+                "168h0m0s"
+            );
+        }
+
+        #[test]
+        fn test_shard_group_duration() {
+            const DAY: u64 = 24 * 60 * 60;
+            assert_eq!(shard_group_duration(None), Duration::from_secs(7 * DAY));
+            assert_eq!(
+                shard_group_duration(Some(Duration::from_secs(DAY))),
+                Duration::from_secs(60 * 60)
+            );
+            assert_eq!(
+                shard_group_duration(Some(Duration::from_secs(30 * DAY))),
+                Duration::from_secs(DAY)
+            );
+            assert_eq!(
+                shard_group_duration(Some(Duration::from_secs(365 * DAY))),
+                Duration::from_secs(7 * DAY)
+            );
+        }
+
         #[test]
         fn test_show_field_keys() {
             assert_snapshot!(plan("SHOW FIELD KEYS"), @"TableScan: field_keys [iox::measurement:Utf8, fieldKey:Utf8, fieldType:Utf8]");
@@ -2724,6 +3650,241 @@ mod test {
             }
         }
 
+        /// Validate the window-like functions, such as `difference`, are evaluated by
+        /// a window operator over the raw or aggregated values.
+        #[test]
+        fn test_window_functions() {
+            // raw values, excluding rows without a value
+            assert_snapshot!(plan("SELECT DIFFERENCE(usage_idle) FROM cpu"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), difference:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, AggregateUDF { name: "difference", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None)]), Exact([Int64, Timestamp(Nanosecond, None)]), Exact([UInt64, Timestamp(Nanosecond, None)])]), volatility: Immutable }, fun: "<FUNC>" }(cpu.usage_idle,cpu.time) ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS difference [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), difference:Float64;N]
+                Filter: AggregateUDF { name: "difference", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None)]), Exact([Int64, Timestamp(Nanosecond, None)]), Exact([UInt64, Timestamp(Nanosecond, None)])]), volatility: Immutable }, fun: "<FUNC>" }(cpu.usage_idle,cpu.time) ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, AggregateUDF { name: "difference", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None)]), Exact([Int64, Timestamp(Nanosecond, None)]), Exact([UInt64, Timestamp(Nanosecond, None)])]), volatility: Immutable }, fun: "<FUNC>" }(cpu.usage_idle,cpu.time) ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW:Float64;N]
+                  WindowAggr: windowExpr=[[AggregateUDF { name: "difference", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None)]), Exact([Int64, Timestamp(Nanosecond, None)]), Exact([UInt64, Timestamp(Nanosecond, None)])]), volatility: Immutable }, fun: "<FUNC>" }(cpu.usage_idle, cpu.time) ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW]] [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, AggregateUDF { name: "difference", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None)]), Exact([Int64, Timestamp(Nanosecond, None)]), Exact([UInt64, Timestamp(Nanosecond, None)])]), volatility: Immutable }, fun: "<FUNC>" }(cpu.usage_idle,cpu.time) ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW:Float64;N]
+                    TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // partitioned by the GROUP BY tags
+            assert_snapshot!(plan("SELECT CUMULATIVE_SUM(usage_idle) FROM cpu GROUP BY cpu"), @r###"
+            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, cumulative_sum:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.cpu AS cpu, AggregateUDF { name: "cumulative_sum", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None)]), Exact([Int64, Timestamp(Nanosecond, None)]), Exact([UInt64, Timestamp(Nanosecond, None)])]), volatility: Immutable }, fun: "<FUNC>" }(cpu.usage_idle,cpu.time) PARTITION BY [cpu.cpu] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS cumulative_sum [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, cumulative_sum:Float64;N]
+                Filter: AggregateUDF { name: "cumulative_sum", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None)]), Exact([Int64, Timestamp(Nanosecond, None)]), Exact([UInt64, Timestamp(Nanosecond, None)])]), volatility: Immutable }, fun: "<FUNC>" }(cpu.usage_idle,cpu.time) PARTITION BY [cpu.cpu] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, AggregateUDF { name: "cumulative_sum", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None)]), Exact([Int64, Timestamp(Nanosecond, None)]), Exact([UInt64, Timestamp(Nanosecond, None)])]), volatility: Immutable }, fun: "<FUNC>" }(cpu.usage_idle,cpu.time) PARTITION BY [cpu.cpu] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW:Float64;N]
+                  WindowAggr: windowExpr=[[AggregateUDF { name: "cumulative_sum", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None)]), Exact([Int64, Timestamp(Nanosecond, None)]), Exact([UInt64, Timestamp(Nanosecond, None)])]), volatility: Immutable }, fun: "<FUNC>" }(cpu.usage_idle, cpu.time) PARTITION BY [cpu.cpu] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW]] [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, AggregateUDF { name: "cumulative_sum", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None)]), Exact([Int64, Timestamp(Nanosecond, None)]), Exact([UInt64, Timestamp(Nanosecond, None)])]), volatility: Immutable }, fun: "<FUNC>" }(cpu.usage_idle,cpu.time) PARTITION BY [cpu.cpu] ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW:Float64;N]
+                    TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // the unit of derivative defaults to the GROUP BY TIME interval
+            assert_snapshot!(plan("SELECT DERIVATIVE(MEAN(usage_idle)) FROM cpu GROUP BY TIME(10s)"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, derivative:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, AggregateUDF { name: "derivative", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None), Int64]), Exact([Int64, Timestamp(Nanosecond, None), Int64]), Exact([UInt64, Timestamp(Nanosecond, None), Int64])]), volatility: Immutable }, fun: "<FUNC>" }(AVG(cpu.usage_idle),time,Int64(10000000000)) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS derivative [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, derivative:Float64;N]
+                Filter: AggregateUDF { name: "derivative", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None), Int64]), Exact([Int64, Timestamp(Nanosecond, None), Int64]), Exact([UInt64, Timestamp(Nanosecond, None), Int64])]), volatility: Immutable }, fun: "<FUNC>" }(AVG(cpu.usage_idle),time,Int64(10000000000)) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW IS NOT NULL [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N, AggregateUDF { name: "derivative", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None), Int64]), Exact([Int64, Timestamp(Nanosecond, None), Int64]), Exact([UInt64, Timestamp(Nanosecond, None), Int64])]), volatility: Immutable }, fun: "<FUNC>" }(AVG(cpu.usage_idle),time,Int64(10000000000)) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW:Float64;N]
+                  WindowAggr: windowExpr=[[AggregateUDF { name: "derivative", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None), Int64]), Exact([Int64, Timestamp(Nanosecond, None), Int64]), Exact([UInt64, Timestamp(Nanosecond, None), Int64])]), volatility: Immutable }, fun: "<FUNC>" }(AVG(cpu.usage_idle), time, Int64(10000000000)) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW]] [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N, AggregateUDF { name: "derivative", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None), Int64]), Exact([Int64, Timestamp(Nanosecond, None), Int64]), Exact([UInt64, Timestamp(Nanosecond, None), Int64])]), volatility: Immutable }, fun: "<FUNC>" }(AVG(cpu.usage_idle),time,Int64(10000000000)) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW:Float64;N]
+                    GapFill: groupBy=[[time]], aggr=[[AVG(cpu.usage_idle)]], time_column=time, stride=IntervalMonthDayNano("10000000000"), range=Unbounded..Excluded(now()) [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N]
+                      Aggregate: groupBy=[[datebin(IntervalMonthDayNano("10000000000"), cpu.time, TimestampNanosecond(0, None)) AS time]], aggr=[[AVG(cpu.usage_idle)]] [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N]
+                        TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // otherwise one second
+            assert_snapshot!(plan("SELECT DERIVATIVE(usage_idle) FROM cpu"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), derivative:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, AggregateUDF { name: "derivative", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None), Int64]), Exact([Int64, Timestamp(Nanosecond, None), Int64]), Exact([UInt64, Timestamp(Nanosecond, None), Int64])]), volatility: Immutable }, fun: "<FUNC>" }(cpu.usage_idle,cpu.time,Int64(1000000000)) ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS derivative [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), derivative:Float64;N]
+                Filter: AggregateUDF { name: "derivative", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None), Int64]), Exact([Int64, Timestamp(Nanosecond, None), Int64]), Exact([UInt64, Timestamp(Nanosecond, None), Int64])]), volatility: Immutable }, fun: "<FUNC>" }(cpu.usage_idle,cpu.time,Int64(1000000000)) ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, AggregateUDF { name: "derivative", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None), Int64]), Exact([Int64, Timestamp(Nanosecond, None), Int64]), Exact([UInt64, Timestamp(Nanosecond, None), Int64])]), volatility: Immutable }, fun: "<FUNC>" }(cpu.usage_idle,cpu.time,Int64(1000000000)) ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW:Float64;N]
+                  WindowAggr: windowExpr=[[AggregateUDF { name: "derivative", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None), Int64]), Exact([Int64, Timestamp(Nanosecond, None), Int64]), Exact([UInt64, Timestamp(Nanosecond, None), Int64])]), volatility: Immutable }, fun: "<FUNC>" }(cpu.usage_idle, cpu.time, Int64(1000000000)) ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW]] [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, AggregateUDF { name: "derivative", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None), Int64]), Exact([Int64, Timestamp(Nanosecond, None), Int64]), Exact([UInt64, Timestamp(Nanosecond, None), Int64])]), volatility: Immutable }, fun: "<FUNC>" }(cpu.usage_idle,cpu.time,Int64(1000000000)) ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW:Float64;N]
+                    TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // rows are not filtered when projecting other aggregates
+            assert_snapshot!(plan("SELECT DIFFERENCE(MEAN(usage_idle)), MEAN(usage_idle) FROM cpu GROUP BY TIME(10s)"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, difference:Float64;N, mean:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, AggregateUDF { name: "difference", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None)]), Exact([Int64, Timestamp(Nanosecond, None)]), Exact([UInt64, Timestamp(Nanosecond, None)])]), volatility: Immutable }, fun: "<FUNC>" }(AVG(cpu.usage_idle),time) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS difference, AVG(cpu.usage_idle) AS mean [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, difference:Float64;N, mean:Float64;N]
+                WindowAggr: windowExpr=[[AggregateUDF { name: "difference", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None)]), Exact([Int64, Timestamp(Nanosecond, None)]), Exact([UInt64, Timestamp(Nanosecond, None)])]), volatility: Immutable }, fun: "<FUNC>" }(AVG(cpu.usage_idle), time) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW]] [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N, AggregateUDF { name: "difference", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None)]), Exact([Int64, Timestamp(Nanosecond, None)]), Exact([UInt64, Timestamp(Nanosecond, None)])]), volatility: Immutable }, fun: "<FUNC>" }(AVG(cpu.usage_idle),time) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW:Float64;N]
+                  GapFill: groupBy=[[time]], aggr=[[AVG(cpu.usage_idle)]], time_column=time, stride=IntervalMonthDayNano("10000000000"), range=Unbounded..Excluded(now()) [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N]
+                    Aggregate: groupBy=[[datebin(IntervalMonthDayNano("10000000000"), cpu.time, TimestampNanosecond(0, None)) AS time]], aggr=[[AVG(cpu.usage_idle)]] [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N]
+                      TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // Fallible
+
+            assert_snapshot!(plan("SELECT DIFFERENCE(usage_idle), MEAN(usage_idle) FROM cpu"), @"Error during planning: mixing window functions with aggregate or selector functions requires a GROUP BY interval");
+
+            // the unit of derivative and elapsed must be positive
+            assert_snapshot!(plan("SELECT DERIVATIVE(usage_idle, 0s) FROM cpu"), @"Error during planning: duration argument must be positive, got 0s");
+            assert_snapshot!(plan("SELECT DERIVATIVE(usage_idle, -5s) FROM cpu"), @"Error during planning: duration argument must be positive, got -5s");
+            assert_snapshot!(plan("SELECT NON_NEGATIVE_DERIVATIVE(usage_idle, -5s) FROM cpu"), @"Error during planning: duration argument must be positive, got -5s");
+            assert_snapshot!(plan("SELECT ELAPSED(usage_idle, 0s) FROM cpu"), @"Error during planning: duration argument must be positive, got 0s");
+            assert_snapshot!(plan("SELECT ELAPSED(usage_idle, -5s) FROM cpu"), @"Error during planning: duration argument must be positive, got -5s");
+        }
+
+        #[test]
+        fn test_technical_analysis_functions() {
+            // the hold period defaults to -1 and the warmup type to exponential
+            assert_snapshot!(plan("SELECT EXPONENTIAL_MOVING_AVERAGE(usage_idle, 5) FROM cpu"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), exponential_moving_average:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, AggregateUDF { name: "exponential_moving_average", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None), Int64, Int64, Utf8]), Exact([Int64, Timestamp(Nanosecond, None), Int64, Int64, Utf8]), Exact([UInt64, Timestamp(Nanosecond, None), Int64, Int64, Utf8])]), volatility: Immutable }, fun: "<FUNC>" }(cpu.usage_idle,cpu.time,Int64(5),Int64(-1),Utf8("exponential")) ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS exponential_moving_average [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), exponential_moving_average:Float64;N]
+                Filter: AggregateUDF { name: "exponential_moving_average", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None), Int64, Int64, Utf8]), Exact([Int64, Timestamp(Nanosecond, None), Int64, Int64, Utf8]), Exact([UInt64, Timestamp(Nanosecond, None), Int64, Int64, Utf8])]), volatility: Immutable }, fun: "<FUNC>" }(cpu.usage_idle,cpu.time,Int64(5),Int64(-1),Utf8("exponential")) ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, AggregateUDF { name: "exponential_moving_average", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None), Int64, Int64, Utf8]), Exact([Int64, Timestamp(Nanosecond, None), Int64, Int64, Utf8]), Exact([UInt64, Timestamp(Nanosecond, None), Int64, Int64, Utf8])]), volatility: Immutable }, fun: "<FUNC>" }(cpu.usage_idle,cpu.time,Int64(5),Int64(-1),Utf8("exponential")) ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW:Float64;N]
+                  WindowAggr: windowExpr=[[AggregateUDF { name: "exponential_moving_average", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None), Int64, Int64, Utf8]), Exact([Int64, Timestamp(Nanosecond, None), Int64, Int64, Utf8]), Exact([UInt64, Timestamp(Nanosecond, None), Int64, Int64, Utf8])]), volatility: Immutable }, fun: "<FUNC>" }(cpu.usage_idle, cpu.time, Int64(5), Int64(-1), Utf8("exponential")) ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW]] [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, AggregateUDF { name: "exponential_moving_average", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None), Int64, Int64, Utf8]), Exact([Int64, Timestamp(Nanosecond, None), Int64, Int64, Utf8]), Exact([UInt64, Timestamp(Nanosecond, None), Int64, Int64, Utf8])]), volatility: Immutable }, fun: "<FUNC>" }(cpu.usage_idle,cpu.time,Int64(5),Int64(-1),Utf8("exponential")) ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW:Float64;N]
+                    TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // kaufmans functions have no warmup type
+            assert_snapshot!(plan("SELECT KAUFMANS_EFFICIENCY_RATIO(MEAN(usage_idle), 3, 2) FROM cpu GROUP BY TIME(10s)"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, kaufmans_efficiency_ratio:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, AggregateUDF { name: "kaufmans_efficiency_ratio", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None), Int64, Int64]), Exact([Int64, Timestamp(Nanosecond, None), Int64, Int64]), Exact([UInt64, Timestamp(Nanosecond, None), Int64, Int64])]), volatility: Immutable }, fun: "<FUNC>" }(AVG(cpu.usage_idle),time,Int64(3),Int64(2)) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS kaufmans_efficiency_ratio [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, kaufmans_efficiency_ratio:Float64;N]
+                Filter: AggregateUDF { name: "kaufmans_efficiency_ratio", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None), Int64, Int64]), Exact([Int64, Timestamp(Nanosecond, None), Int64, Int64]), Exact([UInt64, Timestamp(Nanosecond, None), Int64, Int64])]), volatility: Immutable }, fun: "<FUNC>" }(AVG(cpu.usage_idle),time,Int64(3),Int64(2)) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW IS NOT NULL [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N, AggregateUDF { name: "kaufmans_efficiency_ratio", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None), Int64, Int64]), Exact([Int64, Timestamp(Nanosecond, None), Int64, Int64]), Exact([UInt64, Timestamp(Nanosecond, None), Int64, Int64])]), volatility: Immutable }, fun: "<FUNC>" }(AVG(cpu.usage_idle),time,Int64(3),Int64(2)) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW:Float64;N]
+                  WindowAggr: windowExpr=[[AggregateUDF { name: "kaufmans_efficiency_ratio", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None), Int64, Int64]), Exact([Int64, Timestamp(Nanosecond, None), Int64, Int64]), Exact([UInt64, Timestamp(Nanosecond, None), Int64, Int64])]), volatility: Immutable }, fun: "<FUNC>" }(AVG(cpu.usage_idle), time, Int64(3), Int64(2)) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW]] [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N, AggregateUDF { name: "kaufmans_efficiency_ratio", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None), Int64, Int64]), Exact([Int64, Timestamp(Nanosecond, None), Int64, Int64]), Exact([UInt64, Timestamp(Nanosecond, None), Int64, Int64])]), volatility: Immutable }, fun: "<FUNC>" }(AVG(cpu.usage_idle),time,Int64(3),Int64(2)) ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW:Float64;N]
+                    GapFill: groupBy=[[time]], aggr=[[AVG(cpu.usage_idle)]], time_column=time, stride=IntervalMonthDayNano("10000000000"), range=Unbounded..Excluded(now()) [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N]
+                      Aggregate: groupBy=[[datebin(IntervalMonthDayNano("10000000000"), cpu.time, TimestampNanosecond(0, None)) AS time]], aggr=[[AVG(cpu.usage_idle)]] [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N]
+                        TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // chande_momentum_oscillator has no warmup by default
+            assert_snapshot!(plan("SELECT CHANDE_MOMENTUM_OSCILLATOR(usage_idle, 5) FROM cpu"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), chande_momentum_oscillator:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, AggregateUDF { name: "chande_momentum_oscillator", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None), Int64, Int64, Utf8]), Exact([Int64, Timestamp(Nanosecond, None), Int64, Int64, Utf8]), Exact([UInt64, Timestamp(Nanosecond, None), Int64, Int64, Utf8])]), volatility: Immutable }, fun: "<FUNC>" }(cpu.usage_idle,cpu.time,Int64(5),Int64(-1),Utf8("none")) ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS chande_momentum_oscillator [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), chande_momentum_oscillator:Float64;N]
+                Filter: AggregateUDF { name: "chande_momentum_oscillator", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None), Int64, Int64, Utf8]), Exact([Int64, Timestamp(Nanosecond, None), Int64, Int64, Utf8]), Exact([UInt64, Timestamp(Nanosecond, None), Int64, Int64, Utf8])]), volatility: Immutable }, fun: "<FUNC>" }(cpu.usage_idle,cpu.time,Int64(5),Int64(-1),Utf8("none")) ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, AggregateUDF { name: "chande_momentum_oscillator", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None), Int64, Int64, Utf8]), Exact([Int64, Timestamp(Nanosecond, None), Int64, Int64, Utf8]), Exact([UInt64, Timestamp(Nanosecond, None), Int64, Int64, Utf8])]), volatility: Immutable }, fun: "<FUNC>" }(cpu.usage_idle,cpu.time,Int64(5),Int64(-1),Utf8("none")) ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW:Float64;N]
+                  WindowAggr: windowExpr=[[AggregateUDF { name: "chande_momentum_oscillator", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None), Int64, Int64, Utf8]), Exact([Int64, Timestamp(Nanosecond, None), Int64, Int64, Utf8]), Exact([UInt64, Timestamp(Nanosecond, None), Int64, Int64, Utf8])]), volatility: Immutable }, fun: "<FUNC>" }(cpu.usage_idle, cpu.time, Int64(5), Int64(-1), Utf8("none")) ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW]] [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N, AggregateUDF { name: "chande_momentum_oscillator", signature: Signature { type_signature: OneOf([Exact([Float64, Timestamp(Nanosecond, None), Int64, Int64, Utf8]), Exact([Int64, Timestamp(Nanosecond, None), Int64, Int64, Utf8]), Exact([UInt64, Timestamp(Nanosecond, None), Int64, Int64, Utf8])]), volatility: Immutable }, fun: "<FUNC>" }(cpu.usage_idle,cpu.time,Int64(5),Int64(-1),Utf8("none")) ORDER BY [cpu.time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW:Float64;N]
+                    TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+        }
+
+        #[test]
+        fn test_holt_winters() {
+            assert_snapshot!(plan("SELECT HOLT_WINTERS(MEAN(usage_idle), 10, 4) FROM cpu WHERE time >= '2022-10-31T02:00:00Z' AND time < '2022-10-31T03:00:00Z' GROUP BY TIME(1m), cpu"), @r###"
+            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, cpu:Dictionary(Int32, Utf8);N, holt_winters:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, (holt_winters(AVG(cpu.usage_idle),time,Int64(10),Int64(4),Int64(60000000000)))[time] AS time, cpu.cpu AS cpu, (holt_winters(AVG(cpu.usage_idle),time,Int64(10),Int64(4),Int64(60000000000)))[value] AS holt_winters [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, cpu:Dictionary(Int32, Utf8);N, holt_winters:Float64;N]
+                Filter: holt_winters(AVG(cpu.usage_idle),time,Int64(10),Int64(4),Int64(60000000000)) IS NOT NULL [cpu:Dictionary(Int32, Utf8);N, holt_winters(AVG(cpu.usage_idle),time,Int64(10),Int64(4),Int64(60000000000)):Struct([Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "value", data_type: Float64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }]);N]
+                  Unnest: holt_winters(AVG(cpu.usage_idle),time,Int64(10),Int64(4),Int64(60000000000)) [cpu:Dictionary(Int32, Utf8);N, holt_winters(AVG(cpu.usage_idle),time,Int64(10),Int64(4),Int64(60000000000)):Struct([Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "value", data_type: Float64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }]);N]
+                    Aggregate: groupBy=[[cpu.cpu]], aggr=[[holt_winters(AVG(cpu.usage_idle), time, Int64(10), Int64(4), Int64(60000000000))]] [cpu:Dictionary(Int32, Utf8);N, holt_winters(AVG(cpu.usage_idle),time,Int64(10),Int64(4),Int64(60000000000)):List(Field { name: "item", data_type: Struct([Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "value", data_type: Float64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }]), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} });N]
+                      GapFill: groupBy=[[time, cpu.cpu]], aggr=[[AVG(cpu.usage_idle)]], time_column=time, stride=IntervalMonthDayNano("60000000000"), range=Included(TimestampNanosecond(1667181600000000000, None))..Excluded(TimestampNanosecond(1667185200000000000, None)) [time:Timestamp(Nanosecond, None);N, cpu:Dictionary(Int32, Utf8);N, AVG(cpu.usage_idle):Float64;N]
+                        Aggregate: groupBy=[[datebin(IntervalMonthDayNano("60000000000"), cpu.time, TimestampNanosecond(0, None)) AS time, cpu.cpu]], aggr=[[AVG(cpu.usage_idle)]] [time:Timestamp(Nanosecond, None);N, cpu:Dictionary(Int32, Utf8);N, AVG(cpu.usage_idle):Float64;N]
+                          Filter: cpu.time >= TimestampNanosecond(1667181600000000000, None) AND cpu.time < TimestampNanosecond(1667185200000000000, None) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                            TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // Fallible
+
+            assert_snapshot!(plan("SELECT HOLT_WINTERS(MEAN(usage_idle), 10, 4), MEAN(usage_idle) FROM cpu GROUP BY TIME(1m)"), @"This feature is not implemented: holt_winters combined with other aggregate or selector functions");
+        }
+
+        #[test]
+        fn test_top_bottom_sample() {
+            // the selected points are unnested
+            assert_snapshot!(plan("SELECT TOP(usage_idle, 3) FROM cpu"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, top:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, (selector_top(cpu.usage_idle,cpu.time,Int64(3)))[time] AS time, (selector_top(cpu.usage_idle,cpu.time,Int64(3)))[value] AS top [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, top:Float64;N]
+                Filter: selector_top(cpu.usage_idle,cpu.time,Int64(3)) IS NOT NULL [selector_top(cpu.usage_idle,cpu.time,Int64(3)):Struct([Field { name: "value", data_type: Float64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }]);N]
+                  Unnest: selector_top(cpu.usage_idle,cpu.time,Int64(3)) [selector_top(cpu.usage_idle,cpu.time,Int64(3)):Struct([Field { name: "value", data_type: Float64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }]);N]
+                    Aggregate: groupBy=[[]], aggr=[[selector_top(cpu.usage_idle, cpu.time, Int64(3))]] [selector_top(cpu.usage_idle,cpu.time,Int64(3)):List(Field { name: "item", data_type: Struct([Field { name: "value", data_type: Float64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }]), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} });N]
+                      TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // tag arguments are projected from the selected points
+            assert_snapshot!(plan("SELECT BOTTOM(usage_idle, cpu, 3) FROM cpu"), @r###"
+            Sort: time ASC NULLS LAST, cpu ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, bottom:Float64;N, cpu:Dictionary(Int32, Utf8);N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, (selector_bottom(cpu.usage_idle,cpu.time,Int64(3),cpu.cpu))[time] AS time, (selector_bottom(cpu.usage_idle,cpu.time,Int64(3),cpu.cpu))[value] AS bottom, (selector_bottom(cpu.usage_idle,cpu.time,Int64(3),cpu.cpu))[aux0] AS cpu [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, bottom:Float64;N, cpu:Dictionary(Int32, Utf8);N]
+                Filter: selector_bottom(cpu.usage_idle,cpu.time,Int64(3),cpu.cpu) IS NOT NULL [selector_bottom(cpu.usage_idle,cpu.time,Int64(3),cpu.cpu):Struct([Field { name: "value", data_type: Float64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "aux0", data_type: Dictionary(Int32, Utf8), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }]);N]
+                  Unnest: selector_bottom(cpu.usage_idle,cpu.time,Int64(3),cpu.cpu) [selector_bottom(cpu.usage_idle,cpu.time,Int64(3),cpu.cpu):Struct([Field { name: "value", data_type: Float64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "aux0", data_type: Dictionary(Int32, Utf8), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }]);N]
+                    Aggregate: groupBy=[[]], aggr=[[selector_bottom(cpu.usage_idle, cpu.time, Int64(3), cpu.cpu)]] [selector_bottom(cpu.usage_idle,cpu.time,Int64(3),cpu.cpu):List(Field { name: "item", data_type: Struct([Field { name: "value", data_type: Float64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "aux0", data_type: Dictionary(Int32, Utf8), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }]), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} });N]
+                      TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // the time of each point is projected, rather than the start of the window
+            assert_snapshot!(plan("SELECT SAMPLE(usage_idle, 2) FROM cpu GROUP BY TIME(10s)"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, sample:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, (selector_sample(cpu.usage_idle,cpu.time,Int64(2)))[time] AS time, (selector_sample(cpu.usage_idle,cpu.time,Int64(2)))[value] AS sample [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, sample:Float64;N]
+                Filter: selector_sample(cpu.usage_idle,cpu.time,Int64(2)) IS NOT NULL [time:Timestamp(Nanosecond, None);N, selector_sample(cpu.usage_idle,cpu.time,Int64(2)):Struct([Field { name: "value", data_type: Float64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }]);N]
+                  Unnest: selector_sample(cpu.usage_idle,cpu.time,Int64(2)) [time:Timestamp(Nanosecond, None);N, selector_sample(cpu.usage_idle,cpu.time,Int64(2)):Struct([Field { name: "value", data_type: Float64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }]);N]
+                    Aggregate: groupBy=[[datebin(IntervalMonthDayNano("10000000000"), cpu.time, TimestampNanosecond(0, None)) AS time]], aggr=[[selector_sample(cpu.usage_idle, cpu.time, Int64(2))]] [time:Timestamp(Nanosecond, None);N, selector_sample(cpu.usage_idle,cpu.time,Int64(2)):List(Field { name: "item", data_type: Struct([Field { name: "value", data_type: Float64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }]), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} });N]
+                      TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // Fallible
+
+            assert_snapshot!(plan("SELECT TOP(usage_idle, usage_system, 3) FROM cpu"), @"This feature is not implemented: top with field arguments");
+            assert_snapshot!(plan("SELECT SAMPLE(usage_idle, 2), MEAN(usage_idle) FROM cpu"), @"This feature is not implemented: sample combined with other aggregate or selector functions");
+        }
+
+        #[test]
+        fn test_percentile_mode_spread() {
+            assert_snapshot!(plan("SELECT PERCENTILE(usage_idle, 90) FROM cpu"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, percentile:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, (selector_percentile(cpu.usage_idle,cpu.time,Float64(90)))[time] AS time, (selector_percentile(cpu.usage_idle,cpu.time,Float64(90)))[value] AS percentile [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, percentile:Float64;N]
+                Aggregate: groupBy=[[]], aggr=[[selector_percentile(cpu.usage_idle, cpu.time, Float64(90))]] [selector_percentile(cpu.usage_idle,cpu.time,Float64(90)):Struct([Field { name: "value", data_type: Float64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }]);N]
+                  TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+            assert_snapshot!(plan("SELECT MODE(usage_idle) FROM cpu"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), mode:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, TimestampNanosecond(0, None) AS time, mode(cpu.usage_idle,cpu.time) AS mode [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), mode:Float64;N]
+                Aggregate: groupBy=[[]], aggr=[[mode(cpu.usage_idle, cpu.time)]] [mode(cpu.usage_idle,cpu.time):Float64;N]
+                  TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+            assert_snapshot!(plan("SELECT SPREAD(usage_idle) FROM cpu"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), spread:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, TimestampNanosecond(0, None) AS time, MAX(cpu.usage_idle) - MIN(cpu.usage_idle) AS spread [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), spread:Float64;N]
+                Aggregate: groupBy=[[]], aggr=[[MAX(cpu.usage_idle), MIN(cpu.usage_idle)]] [MAX(cpu.usage_idle):Float64;N, MIN(cpu.usage_idle):Float64;N]
+                  TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+        }
+
+        #[test]
+        fn test_subqueries() {
+            // the outer query aggregates the results of the subquery
+            assert_snapshot!(plan("SELECT MAX(m) FROM (SELECT MEAN(usage_idle) AS m FROM cpu GROUP BY TIME(1m), host) GROUP BY TIME(10m)"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, max:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, (selector_max(m,time))[value] AS max [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, max:Float64;N]
+                GapFill: groupBy=[[time]], aggr=[[selector_max(m,time)]], time_column=time, stride=IntervalMonthDayNano("600000000000"), range=Unbounded..Excluded(now()) [time:Timestamp(Nanosecond, None);N, selector_max(m,time):Struct([Field { name: "value", data_type: Float64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }]);N]
+                  Aggregate: groupBy=[[datebin(IntervalMonthDayNano("600000000000"), time, TimestampNanosecond(0, None)) AS time]], aggr=[[selector_max(m, time)]] [time:Timestamp(Nanosecond, None);N, selector_max(m,time):Struct([Field { name: "value", data_type: Float64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }, Field { name: "time", data_type: Timestamp(Nanosecond, None), nullable: true, dict_id: 0, dict_is_ordered: false, metadata: {} }]);N]
+                    Sort: host ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, host:Dictionary(Int32, Utf8);N, m:Float64;N]
+                      Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, cpu.host AS host, AVG(cpu.usage_idle) AS m [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, host:Dictionary(Int32, Utf8);N, m:Float64;N]
+                        GapFill: groupBy=[[time, cpu.host]], aggr=[[AVG(cpu.usage_idle)]], time_column=time, stride=IntervalMonthDayNano("60000000000"), range=Unbounded..Excluded(now()) [time:Timestamp(Nanosecond, None);N, host:Dictionary(Int32, Utf8);N, AVG(cpu.usage_idle):Float64;N]
+                          Aggregate: groupBy=[[datebin(IntervalMonthDayNano("60000000000"), cpu.time, TimestampNanosecond(0, None)) AS time, cpu.host]], aggr=[[AVG(cpu.usage_idle)]] [time:Timestamp(Nanosecond, None);N, host:Dictionary(Int32, Utf8);N, AVG(cpu.usage_idle):Float64;N]
+                            TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // the subquery inherits the time range, and as its rows are not grouped by time,
+            // the outer query applies the entire time range
+            assert_snapshot!(plan("SELECT usage_idle FROM (SELECT usage_idle FROM cpu) WHERE time >= '2022-10-31T02:00:00Z' AND time < '2022-10-31T03:00:00Z' AND usage_idle > 5.5"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time AS time, usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+                Filter: time >= TimestampNanosecond(1667181600000000000, None) AND time < TimestampNanosecond(1667185200000000000, None) AND usage_idle > Float64(5.5) [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+                  Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+                    Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+                      Filter: cpu.time >= TimestampNanosecond(1667181600000000000, None) AND cpu.time < TimestampNanosecond(1667185200000000000, None) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                        TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // the subquery inherits the time range, whereas the outer query only applies
+            // the upper bound, as the rows of the subquery are timestamped at the start of
+            // each GROUP BY interval, which may precede the lower bound
+            assert_snapshot!(plan("SELECT MEAN(m) FROM (SELECT MEAN(usage_idle) AS m FROM cpu GROUP BY TIME(1m)) WHERE time >= '2022-10-31T02:00:00Z' AND time < '2022-10-31T03:00:00Z'"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), mean:Float64;N]
+              Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, TimestampNanosecond(0, None) AS time, AVG(m) AS mean [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), mean:Float64;N]
+                Aggregate: groupBy=[[]], aggr=[[AVG(m)]] [AVG(m):Float64;N]
+                  Filter: time < TimestampNanosecond(1667185200000000000, None) [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, m:Float64;N]
+                    Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, m:Float64;N]
+                      Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, time, AVG(cpu.usage_idle) AS m [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None);N, m:Float64;N]
+                        GapFill: groupBy=[[time]], aggr=[[AVG(cpu.usage_idle)]], time_column=time, stride=IntervalMonthDayNano("60000000000"), range=Included(TimestampNanosecond(1667181600000000000, None))..Excluded(TimestampNanosecond(1667185200000000000, None)) [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N]
+                          Aggregate: groupBy=[[datebin(IntervalMonthDayNano("60000000000"), cpu.time, TimestampNanosecond(0, None)) AS time]], aggr=[[AVG(cpu.usage_idle)]] [time:Timestamp(Nanosecond, None);N, AVG(cpu.usage_idle):Float64;N]
+                            Filter: cpu.time >= TimestampNanosecond(1667181600000000000, None) AND cpu.time < TimestampNanosecond(1667185200000000000, None) [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+                              TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // the subquery is planned for each measurement
+            assert_snapshot!(plan("SELECT COUNT(bytes_free), COUNT(bytes_read) FROM (SELECT bytes_free, bytes_read FROM disk, diskio)"), @r###"
+            Sort: iox::measurement ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), count:Int64;N, count_1:Int64;N]
+              Union [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), count:Int64;N, count_1:Int64;N]
+                Projection: Dictionary(Int32, Utf8("disk")) AS iox::measurement, TimestampNanosecond(0, None) AS time, COUNT(bytes_free) AS count, COUNT(bytes_read) AS count_1 [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), count:Int64;N, count_1:Int64;N]
+                  Aggregate: groupBy=[[]], aggr=[[COUNT(bytes_free), COUNT(bytes_read)]] [COUNT(bytes_free):Int64;N, COUNT(bytes_read):Int64;N]
+                    Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), bytes_free:Int64;N, bytes_read:Int64;N]
+                      Projection: Dictionary(Int32, Utf8("disk")) AS iox::measurement, disk.time AS time, disk.bytes_free AS bytes_free, CAST(NULL AS Int64) AS bytes_read [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), bytes_free:Int64;N, bytes_read:Int64;N]
+                        TableScan: disk [bytes_free:Int64;N, bytes_used:Int64;N, device:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None)]
+                Projection: Dictionary(Int32, Utf8("diskio")) AS iox::measurement, TimestampNanosecond(0, None) AS time, COUNT(bytes_free) AS count, COUNT(bytes_read) AS count_1 [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), count:Int64;N, count_1:Int64;N]
+                  Aggregate: groupBy=[[]], aggr=[[COUNT(bytes_free), COUNT(bytes_read)]] [COUNT(bytes_free):Int64;N, COUNT(bytes_read):Int64;N]
+                    Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), bytes_free:Int64;N, bytes_read:Int64;N]
+                      Projection: Dictionary(Int32, Utf8("diskio")) AS iox::measurement, diskio.time AS time, CAST(NULL AS Int64) AS bytes_free, diskio.bytes_read AS bytes_read [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), bytes_free:Int64;N, bytes_read:Int64;N]
+                        TableScan: diskio [bytes_read:Int64;N, bytes_written:Int64;N, host:Dictionary(Int32, Utf8);N, is_local:Boolean;N, read_utilization:Float64;N, region:Dictionary(Int32, Utf8);N, status:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), write_utilization:Float64;N]
+            "###);
+        }
+
         /// Test InfluxQL-specific behaviour of scalar functions that differ
         /// from DataFusion
         #[test]
@@ -3497,6 +4658,33 @@ mod test {
             "###);
         }
 
+        #[test]
+        fn test_now_and_duration_in_projection() {
+            assert_snapshot!(plan("SELECT f64_field, now() - 1h AS t FROM data"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), f64_field:Float64;N, t:Timestamp(Nanosecond, Some("+00:00"));N]
+              Projection: Dictionary(Int32, Utf8("data")) AS iox::measurement, data.time AS time, data.f64_field AS f64_field, now() - IntervalMonthDayNano("3600000000000") AS t [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), f64_field:Float64;N, t:Timestamp(Nanosecond, Some("+00:00"));N]
+                TableScan: data [TIME:Boolean;N, bar:Dictionary(Int32, Utf8);N, bool_field:Boolean;N, f64_field:Float64;N, foo:Dictionary(Int32, Utf8);N, i64_field:Int64;N, mixedCase:Float64;N, str_field:Utf8;N, time:Timestamp(Nanosecond, None), with space:Float64;N]
+            "###);
+            assert_snapshot!(plan("SELECT f64_field, now() AS t FROM data"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), f64_field:Float64;N, t:Timestamp(Nanosecond, Some("+00:00"));N]
+              Projection: Dictionary(Int32, Utf8("data")) AS iox::measurement, data.time AS time, data.f64_field AS f64_field, now() AS t [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), f64_field:Float64;N, t:Timestamp(Nanosecond, Some("+00:00"));N]
+                TableScan: data [TIME:Boolean;N, bar:Dictionary(Int32, Utf8);N, bool_field:Boolean;N, f64_field:Float64;N, foo:Dictionary(Int32, Utf8);N, i64_field:Int64;N, mixedCase:Float64;N, str_field:Utf8;N, time:Timestamp(Nanosecond, None), with space:Float64;N]
+            "###);
+
+            // a duration is an integer number of nanoseconds outside of a time expression
+            assert_snapshot!(plan("SELECT i64_field + 1s FROM data"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), i64_field:Int64;N]
+              Projection: Dictionary(Int32, Utf8("data")) AS iox::measurement, data.time AS time, data.i64_field + Int64(1000000000) AS i64_field [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), i64_field:Int64;N]
+                TableScan: data [TIME:Boolean;N, bar:Dictionary(Int32, Utf8);N, bool_field:Boolean;N, f64_field:Float64;N, foo:Dictionary(Int32, Utf8);N, i64_field:Int64;N, mixedCase:Float64;N, str_field:Utf8;N, time:Timestamp(Nanosecond, None), with space:Float64;N]
+            "###);
+            assert_snapshot!(plan("SELECT f64_field FROM data WHERE i64_field < 1s"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), f64_field:Float64;N]
+              Projection: Dictionary(Int32, Utf8("data")) AS iox::measurement, data.time AS time, data.f64_field AS f64_field [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), f64_field:Float64;N]
+                Filter: data.i64_field < Int64(1000000000) [TIME:Boolean;N, bar:Dictionary(Int32, Utf8);N, bool_field:Boolean;N, f64_field:Float64;N, foo:Dictionary(Int32, Utf8);N, i64_field:Int64;N, mixedCase:Float64;N, str_field:Utf8;N, time:Timestamp(Nanosecond, None), with space:Float64;N]
+                  TableScan: data [TIME:Boolean;N, bar:Dictionary(Int32, Utf8);N, bool_field:Boolean;N, f64_field:Float64;N, foo:Dictionary(Int32, Utf8);N, i64_field:Int64;N, mixedCase:Float64;N, str_field:Utf8;N, time:Timestamp(Nanosecond, None), with space:Float64;N]
+            "###);
+        }
+
         #[test]
         fn test_select_single_measurement_group_by() {
             // Sort should be cpu, time
@@ -3660,6 +4848,58 @@ mod test {
             "###);
         }
 
+        #[test]
+        fn test_select_group_by_slimit_soffset() {
+            assert_snapshot!(plan("SELECT usage_idle FROM cpu GROUP BY cpu SLIMIT 1"), @r###"
+            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+              Projection: iox::measurement, time, cpu, usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                Filter: iox::series <= Int64(1) [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                  WindowAggr: windowExpr=[[DENSE_RANK() PARTITION BY [iox::measurement] ORDER BY [cpu ASC NULLS FIRST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS iox::series]] [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                    Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.cpu AS cpu, cpu.usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                      TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+            assert_snapshot!(plan("SELECT usage_idle FROM cpu GROUP BY cpu SOFFSET 1"), @r###"
+            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+              Projection: iox::measurement, time, cpu, usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                Filter: iox::series > Int64(1) [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                  WindowAggr: windowExpr=[[DENSE_RANK() PARTITION BY [iox::measurement] ORDER BY [cpu ASC NULLS FIRST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS iox::series]] [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                    Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.cpu AS cpu, cpu.usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                      TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+            assert_snapshot!(plan("SELECT usage_idle FROM cpu GROUP BY cpu SLIMIT 2 SOFFSET 1"), @r###"
+            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+              Projection: iox::measurement, time, cpu, usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                Filter: iox::series BETWEEN Int64(2) AND Int64(3) [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                  WindowAggr: windowExpr=[[DENSE_RANK() PARTITION BY [iox::measurement] ORDER BY [cpu ASC NULLS FIRST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS iox::series]] [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                    Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.cpu AS cpu, cpu.usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                      TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // applied independently of LIMIT, which limits the rows of each series
+            assert_snapshot!(plan("SELECT usage_idle FROM cpu GROUP BY cpu LIMIT 1 SLIMIT 1"), @r###"
+            Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+              Projection: iox::measurement, time, cpu, usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                Filter: iox::row <= Int64(1) [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::row:UInt64;N]
+                  WindowAggr: windowExpr=[[ROW_NUMBER() PARTITION BY [iox::measurement, cpu] ORDER BY [time ASC NULLS LAST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS iox::row]] [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::row:UInt64;N]
+                    Sort: cpu ASC NULLS LAST, time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                      Projection: iox::measurement, time, cpu, usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                        Filter: iox::series <= Int64(1) [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                          WindowAggr: windowExpr=[[DENSE_RANK() PARTITION BY [iox::measurement] ORDER BY [cpu ASC NULLS FIRST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS iox::series]] [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N, iox::series:UInt64;N]
+                            Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.cpu AS cpu, cpu.usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), cpu:Dictionary(Int32, Utf8);N, usage_idle:Float64;N]
+                              TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+
+            // each measurement is a single series without a GROUP BY
+            assert_snapshot!(plan("SELECT usage_idle FROM cpu SOFFSET 1"), @r###"
+            Sort: time ASC NULLS LAST [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+              Projection: iox::measurement, time, usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+                Filter: iox::series > Int64(1) [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N, iox::series:UInt64;N]
+                  WindowAggr: windowExpr=[[DENSE_RANK() PARTITION BY [iox::measurement] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS iox::series]] [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N, iox::series:UInt64;N]
+                    Projection: Dictionary(Int32, Utf8("cpu")) AS iox::measurement, cpu.time AS time, cpu.usage_idle AS usage_idle [iox::measurement:Dictionary(Int32, Utf8), time:Timestamp(Nanosecond, None), usage_idle:Float64;N]
+                      TableScan: cpu [cpu:Dictionary(Int32, Utf8);N, host:Dictionary(Int32, Utf8);N, region:Dictionary(Int32, Utf8);N, time:Timestamp(Nanosecond, None), usage_idle:Float64;N, usage_system:Float64;N, usage_user:Float64;N]
+            "###);
+        }
+
         // The following is an outline of additional scenarios to develop
         // as the planner learns more features.
         // This is not an exhaustive list and is expected to grow as the
@@ -1,4 +1,6 @@
 use crate::plan::error;
+use crate::plan::ir::{DataSource, Select};
+use crate::plan::rewriter::{select_statement_info, ProjectionType};
 use arrow::datatypes::DataType;
 use datafusion::common::{DFSchemaRef, Result};
 use datafusion::logical_expr::utils::find_column_exprs;
@@ -8,7 +10,7 @@ use generated_types::influxdata::iox::querier::v1::influx_ql_metadata::TagKeyCol
 use influxdb_influxql_parser::expression::{Expr as IQLExpr, VarRef, VarRefDataType};
 use influxdb_influxql_parser::select::Field;
 use schema::INFLUXQL_MEASUREMENT_COLUMN_NAME;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::ops::Deref;
 
 /// Determines that all [`Expr::Column`] references in `exprs` refer to a
@@ -137,3 +139,71 @@ pub(super) fn fields_to_exprs_no_nulls<'a>(
         })
         .map(|f| f.as_expr())
 }
+
+/// Returns the names of the tables of the `FROM` clause of `select`, including
+/// those of any subqueries.
+pub(super) fn from_table_names(select: &Select) -> BTreeSet<&str> {
+    let mut names = BTreeSet::new();
+    for ds in &select.from {
+        match ds {
+            DataSource::Table(name) => {
+                names.insert(name.as_str());
+            }
+            DataSource::Subquery(q) => names.extend(from_table_names(q)),
+        }
+    }
+    names
+}
+
+/// Returns a copy of `select`, where the `FROM` clause and those of any subqueries
+/// are restricted to the table `table_name`, or `None` if `select` does not select
+/// from `table_name`.
+pub(super) fn select_restrict_to_table(select: &Select, table_name: &str) -> Option<Select> {
+    let from = select
+        .from
+        .iter()
+        .filter_map(|ds| match ds {
+            DataSource::Table(name) if name == table_name => Some(ds.clone()),
+            DataSource::Table(_) => None,
+            DataSource::Subquery(q) => {
+                select_restrict_to_table(q, table_name).map(|q| DataSource::Subquery(Box::new(q)))
+            }
+        })
+        .collect::<Vec<_>>();
+
+    (!from.is_empty()).then(|| Select {
+        from,
+        ..select.clone()
+    })
+}
+
+/// Returns `true` if `select`, or any of its subqueries, may produce rows
+/// timestamped before the lower bound of the time range of its `WHERE` clause.
+///
+/// This is the case for rows that are timestamped at the start of a `GROUP BY`
+/// time interval, or at the Unix epoch for aggregates without an interval.
+pub(super) fn select_may_precede_time_range(select: &Select) -> Result<bool> {
+    let has_group_by_time = select
+        .group_by
+        .as_ref()
+        .and_then(|gb| gb.time_dimension())
+        .is_some();
+
+    if has_group_by_time
+        || matches!(
+            select_statement_info(select)?.projection_type,
+            ProjectionType::Aggregate | ProjectionType::RawDistinct
+        )
+    {
+        return Ok(true);
+    }
+
+    for ds in &select.from {
+        if let DataSource::Subquery(q) = ds {
+            if select_may_precede_time_range(q)? {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
@@ -375,6 +375,47 @@ fn rewrite_time_range_exprs(expr: Expr) -> Result<Expr> {
     })
 }
 
+/// Remove the time range expressions from `expr` that do not form an upper bound,
+/// such as `time >= now() - 5m` or `time = '2004-04-09T12:00:00Z'`, whereas
+/// `time < now()` is retained. Time range expressions are identified using the same
+/// rules as [`rewrite_time_range_exprs`]. If no expressions remain, the result is `true`.
+pub(super) fn remove_non_upper_bound_time_range_exprs(expr: Expr) -> Expr {
+    fn is_upper_bound(expr: &Expr) -> bool {
+        use Operator::*;
+        match expr {
+            Expr::BinaryExpr(BinaryExpr {
+                left,
+                op: Lt | LtEq,
+                ..
+            }) => is_time_column(left),
+            Expr::BinaryExpr(BinaryExpr {
+                op: Gt | GtEq,
+                right,
+                ..
+            }) => is_time_column(right),
+            _ => false,
+        }
+    }
+
+    fn remove(expr: Expr) -> Option<Expr> {
+        match expr {
+            Expr::BinaryExpr(BinaryExpr {
+                left,
+                op: op @ (Operator::And | Operator::Or),
+                right,
+            }) => match (remove(*left), remove(*right)) {
+                (Some(left), Some(right)) => Some(binary_expr(left, op, right)),
+                (Some(expr), None) | (None, Some(expr)) => Some(expr),
+                (None, None) => None,
+            },
+            expr if is_time_range(&expr) && !is_upper_bound(&expr) => None,
+            expr => Some(expr),
+        }
+    }
+
+    remove(expr).unwrap_or_else(|| lit(true))
+}
+
 /// Returns `true` if `expr` refers to the `time` column.
 fn is_time_column(expr: &Expr) -> bool {
     matches!(expr, Expr::Column(Column{ name, .. }) if name == "time")
@@ -946,6 +987,39 @@ mod test {
         assert_eq!(rewrite(expr), "Boolean(false)");
     }
 
+    #[test]
+    fn test_remove_non_upper_bound_time_range_exprs() {
+        let remove = |expr| remove_non_upper_bound_time_range_exprs(expr).to_string();
+
+        let expr = "time"
+            .as_expr()
+            .gt_eq(lit_timestamp_nano(0))
+            .and("cpu".as_expr().eq(lit("cpu0")))
+            .and("time".as_expr().lt(now()));
+        assert_eq!(remove(expr), r#"cpu = Utf8("cpu0") AND time < now()"#);
+
+        let expr = "time".as_expr().gt_eq(lit_timestamp_nano(0)).and(
+            "cpu"
+                .as_expr()
+                .eq(lit("cpu0"))
+                .or("cpu".as_expr().eq(lit("cpu1"))),
+        );
+        assert_eq!(remove(expr), r#"cpu = Utf8("cpu0") OR cpu = Utf8("cpu1")"#);
+
+        // upper bound with the time column on the right hand side
+        let expr = lit_timestamp_nano(0)
+            .lt("time".as_expr())
+            .and(now().gt_eq("time".as_expr()));
+        assert_eq!(remove(expr), "now() >= time");
+
+        // only lower bound and equality expressions
+        let expr = "time"
+            .as_expr()
+            .gt_eq(lit_timestamp_nano(0))
+            .and("time".as_expr().eq(now()));
+        assert_eq!(remove(expr), "Boolean(true)");
+    }
+
     /// Tests which validate that division is coalesced to `0`, to handle division by zero,
     /// which normally returns a `NULL`, but presents as `0` for InfluxQL.
     ///
@@ -4,12 +4,15 @@ use crate::plan::field_mapper::{field_and_dimensions, FieldTypeMap, TagSet};
 use crate::plan::ir::{DataSource, Select, SelectQuery};
 use crate::plan::{error, util, SchemaProvider};
 use datafusion::common::{DataFusionError, Result};
-use influxdb_influxql_parser::common::{MeasurementName, QualifiedMeasurementName};
-use influxdb_influxql_parser::expression::walk::{walk_expr, walk_expr_mut};
+use influxdb_influxql_parser::common::{MeasurementName, QualifiedMeasurementName, WhereClause};
+use influxdb_influxql_parser::expression::walk::{
+    walk_expr, walk_expr_mut, walk_expression, Expression,
+};
 use influxdb_influxql_parser::expression::{
-    AsVarRefExpr, Call, Expr, VarRef, VarRefDataType, WildcardType,
+    AsVarRefExpr, Call, ConditionalBinary, ConditionalExpression, ConditionalOperator, Expr,
+    VarRef, VarRefDataType, WildcardType,
 };
-use influxdb_influxql_parser::functions::is_scalar_math_function;
+use influxdb_influxql_parser::functions::{is_now_function, is_scalar_math_function};
 use influxdb_influxql_parser::identifier::Identifier;
 use influxdb_influxql_parser::literal::Literal;
 use influxdb_influxql_parser::select::{
@@ -27,8 +30,7 @@ pub(super) fn rewrite_statement(
 ) -> Result<SelectQuery> {
     let mut select = map_select(s, q)?;
     from_drop_empty(s, &mut select);
-    field_list_normalize_time(&mut select);
-    field_list_rewrite_aliases(&mut select.fields)?;
+    rewrite_select(&mut select, false)?;
 
     let has_multiple_measurements = has_multiple_measurements(&select);
 
@@ -38,6 +40,24 @@ pub(super) fn rewrite_statement(
     })
 }
 
+/// Normalize the projection list of `stmt` and recursively rewrite any subqueries of the
+/// `FROM` clause, after they inherit the time range and dimensions of `stmt`.
+fn rewrite_select(stmt: &mut Select, is_subquery: bool) -> Result<()> {
+    field_list_normalize_time(stmt, is_subquery);
+    field_list_expand_top_bottom(stmt);
+    field_list_rewrite_aliases(&mut stmt.fields)?;
+    from_subquery_inherit(stmt)?;
+
+    for q in stmt.from.iter_mut().filter_map(|ds| match ds {
+        DataSource::Subquery(q) => Some(q),
+        _ => None,
+    }) {
+        rewrite_select(q, true)?;
+    }
+
+    Ok(())
+}
+
 /// Determines if s projects more than a single unique table
 fn has_multiple_measurements(s: &Select) -> bool {
     let mut data_sources = vec![s.from.as_slice()];
@@ -66,8 +86,6 @@ fn has_multiple_measurements(s: &Select) -> bool {
 ///
 /// The goal is that `Select` will eventually be used by the InfluxQL planner.
 pub(super) fn map_select(s: &dyn SchemaProvider, stmt: &SelectStatement) -> Result<Select> {
-    check_features(stmt)?;
-
     let mut sel = Select {
         fields: vec![],
         from: vec![],
@@ -77,6 +95,8 @@ pub(super) fn map_select(s: &dyn SchemaProvider, stmt: &SelectStatement) -> Resu
         order_by: stmt.order_by,
         limit: stmt.limit,
         offset: stmt.offset,
+        series_limit: stmt.series_limit,
+        series_offset: stmt.series_offset,
         timezone: stmt.timezone.map(|v| *v),
     };
     from_expand_wildcards(s, stmt, &mut sel)?;
@@ -85,70 +105,173 @@ pub(super) fn map_select(s: &dyn SchemaProvider, stmt: &SelectStatement) -> Resu
     Ok(sel)
 }
 
-/// Asserts that the `SELECT` statement does not use any unimplemented features.
-///
-/// The list of unimplemented or unsupported features are listed below.
+/// Ensure the time field is added to all projections,
+/// and is moved to the first position, which is a requirement
+/// for InfluxQL compatibility.
+fn field_list_normalize_time(stmt: &mut Select, is_subquery: bool) {
+    if let Some(f) = match stmt
+        .fields
+        .iter()
+        .find_position(
+            |f| matches!(&f.expr, Expr::VarRef(VarRef { name, .. }) if name.deref() == "time"),
+        )
+        .map(|(i, _)| i)
+    {
+        Some(0) => None,
+        Some(idx) => Some(stmt.fields.remove(idx)),
+        None => Some(Field {
+            expr: "time".to_var_ref_expr(),
+            alias: None,
+        }),
+    } {
+        stmt.fields.insert(0, f)
+    }
+
+    let f = &mut stmt.fields[0];
+
+    // time aliases in subqueries is ignored
+    if f.alias.is_none() || is_subquery {
+        f.alias = Some("time".into())
+    }
+
+    if let Expr::VarRef(VarRef {
+        ref mut data_type, ..
+    }) = f.expr
+    {
+        *data_type = Some(VarRefDataType::Timestamp);
+    }
+}
+
+/// Add a field after each call to the `top` or `bottom` functions for each of the
+/// tags or fields passed as arguments, such that `SELECT top(usage, host, 3)` is
+/// rewritten to `SELECT top(usage, host, 3), host`, in accordance with the
+/// [original implementation].
 ///
-/// # `SLIMIT` and `SOFFSET`
+/// [original implementation]: https://github.com/influxdata/influxdb/blob/98361e207349a3643bcc332d54b009818fe7585f/query/compile.go
+fn field_list_expand_top_bottom(stmt: &mut Select) {
+    let fields = std::mem::take(&mut stmt.fields);
+    for f in fields {
+        let args = match &f.expr {
+            Expr::Call(Call { name, args }) if name == "top" || name == "bottom" => args
+                .get(1..args.len().saturating_sub(1))
+                .unwrap_or_default()
+                .to_vec(),
+            _ => vec![],
+        };
+
+        stmt.fields.push(f);
+        stmt.fields
+            .extend(args.into_iter().map(|expr| Field { expr, alias: None }));
+    }
+}
+
+/// Propagate the time range and `GROUP BY` dimensions of `stmt` to the subqueries of
+/// the `FROM` clause, per the [original implementation]:
 ///
-/// * `SLIMIT` and `SOFFSET` don't work as expected per issue [#7571]
-/// * This issue [is noted](https://docs.influxdata.com/influxdb/v1.8/query_language/explore-data/#the-slimit-clause) in our official documentation
+/// * the time range of the `WHERE` clause is combined with the condition of the subquery,
+/// * the tags of the `GROUP BY` clause are added to the `GROUP BY` clause of the subquery, and
+/// * a subquery that aggregates its input without a `GROUP BY` interval inherits the interval.
 ///
-/// [#7571]: https://github.com/influxdata/influxdb/issues/7571
-fn check_features(stmt: &SelectStatement) -> Result<()> {
-    if stmt.series_limit.is_some() || stmt.series_offset.is_some() {
-        return error::not_implemented("SLIMIT or SOFFSET");
-    }
+/// [original implementation]: https://github.com/influxdata/influxdb/blob/98361e207349a3643bcc332d54b009818fe7585f/query/subquery.go
+fn from_subquery_inherit(stmt: &mut Select) -> Result<()> {
+    let time_range = stmt
+        .condition
+        .as_ref()
+        .and_then(|cond| find_time_range_condition(cond));
 
-    Ok(())
-}
+    for q in stmt.from.iter_mut().filter_map(|ds| match ds {
+        DataSource::Subquery(q) => Some(q),
+        _ => None,
+    }) {
+        if let Some(time_range) = &time_range {
+            q.condition = Some(WhereClause::new(match q.condition.take() {
+                Some(cond) => ConditionalExpression::Binary(ConditionalBinary {
+                    lhs: Box::new(ConditionalExpression::Grouped(Box::new((*cond).clone()))),
+                    op: ConditionalOperator::And,
+                    rhs: Box::new(time_range.clone()),
+                }),
+                None => time_range.clone(),
+            }));
+        }
+
+        let Some(group_by) = &stmt.group_by else {
+            continue
+        };
 
-/// Ensure the time field is added to all projections,
-/// and is moved to the first position, which is a requirement
-/// for InfluxQL compatibility.
-fn field_list_normalize_time(stmt: &mut Select) {
-    fn normalize_time(stmt: &mut Select, is_subquery: bool) {
-        if let Some(f) = match stmt
-            .fields
-            .iter()
-            .find_position(
-                |f| matches!(&f.expr, Expr::VarRef(VarRef { name, .. }) if name.deref() == "time"),
-            )
-            .map(|(i, _)| i)
-        {
-            Some(0) => None,
-            Some(idx) => Some(stmt.fields.remove(idx)),
-            None => Some(Field {
-                expr: "time".to_var_ref_expr(),
-                alias: None,
-            }),
-        } {
-            stmt.fields.insert(0, f)
+        let mut dimensions = q
+            .group_by
+            .as_ref()
+            .map(|gb| gb.to_vec())
+            .unwrap_or_default();
+
+        if let Some(time_dimension) = group_by.time_dimension() {
+            let has_time_dimension = dimensions.iter().any(|d| matches!(d, Dimension::Time(_)));
+            if !has_time_dimension
+                && matches!(
+                    select_statement_info(q)?.projection_type,
+                    ProjectionType::Aggregate
+                        | ProjectionType::Selector { .. }
+                        | ProjectionType::TopBottomSelector
+                )
+            {
+                dimensions.push(Dimension::Time(time_dimension.clone()));
+            }
         }
 
-        let f = &mut stmt.fields[0];
-
-        // time aliases in subqueries is ignored
-        if f.alias.is_none() || is_subquery {
-            f.alias = Some("time".into())
+        for tag in group_by.tags() {
+            if !dimensions
+                .iter()
+                .any(|d| matches!(d, Dimension::Tag(ident) if ident == tag))
+            {
+                dimensions.push(Dimension::Tag(tag.clone()));
+            }
         }
 
-        if let Expr::VarRef(VarRef {
-            ref mut data_type, ..
-        }) = f.expr
-        {
-            *data_type = Some(VarRefDataType::Timestamp);
+        if !dimensions.is_empty() {
+            q.group_by = Some(GroupByClause::new(dimensions));
         }
     }
 
-    normalize_time(stmt, false);
+    Ok(())
+}
 
-    for stmt in stmt.from.iter_mut().filter_map(|ms| match ms {
-        DataSource::Subquery(stmt) => Some(stmt),
-        _ => None,
-    }) {
-        normalize_time(stmt, true)
+/// Returns the conjunction of the expressions of `cond` that compare the `time` column,
+/// or `None` if there are none.
+fn find_time_range_condition(cond: &ConditionalExpression) -> Option<ConditionalExpression> {
+    fn is_time_var_ref(cond: &ConditionalExpression) -> bool {
+        matches!(cond, ConditionalExpression::Expr(expr) if matches!(&**expr, Expr::VarRef(VarRef { name, .. }) if name.eq_ignore_ascii_case("time")))
     }
+
+    let mut time_ranges = Vec::new();
+    let _ = walk_expression::<()>(cond, &mut |e| {
+        if let Expression::Conditional(
+            cond @ ConditionalExpression::Binary(ConditionalBinary {
+                lhs,
+                op:
+                    ConditionalOperator::Eq
+                    | ConditionalOperator::NotEq
+                    | ConditionalOperator::Lt
+                    | ConditionalOperator::LtEq
+                    | ConditionalOperator::Gt
+                    | ConditionalOperator::GtEq,
+                rhs,
+            }),
+        ) = e
+        {
+            if is_time_var_ref(lhs) || is_time_var_ref(rhs) {
+                time_ranges.push(cond.clone());
+            }
+        }
+        ControlFlow::Continue(())
+    });
+
+    time_ranges.into_iter().reduce(|lhs, rhs| {
+        ConditionalExpression::Binary(ConditionalBinary {
+            lhs: Box::new(lhs),
+            op: ConditionalOperator::And,
+            rhs: Box::new(rhs),
+        })
+    })
 }
 
 /// Recursively expand the `from` clause of `stmt` and any subqueries.
@@ -725,9 +848,13 @@ struct FieldChecker {
     /// `true` when the projection contains a `DISTINCT` function or unary `DISTINCT` operator.
     has_distinct: bool,
 
-    /// Accumulator for the number of aggregate or window expressions for the statement.
+    /// Accumulator for the number of aggregate expressions for the statement.
     aggregate_count: usize,
 
+    /// Accumulator for the number of window-like expressions, such as `difference`,
+    /// for the statement.
+    window_count: usize,
+
     /// Accumulator for the number of selector expressions for the statement.
     selector_count: usize,
 }
@@ -783,7 +910,7 @@ impl FieldChecker {
 
         // Validate we are using a selector or raw query if non-aggregate fields are projected.
         if self.has_non_aggregate_fields {
-            if self.aggregate_count > 0 {
+            if self.aggregate_count > 0 || self.window_count > 0 {
                 return error::query("mixing aggregate and non-aggregate columns is not supported");
             } else if self.selector_count > 1 {
                 return error::query(
@@ -801,6 +928,15 @@ impl FieldChecker {
             Ok(ProjectionType::Aggregate)
         } else if self.has_distinct {
             Ok(ProjectionType::RawDistinct)
+        } else if self.window_count > 0 {
+            // Without a `GROUP BY time()`, window-like functions are applied to the raw
+            // values of a field, and produce a row for each input row.
+            if self.aggregate_count > 0 || self.selector_count > 0 {
+                return error::query(
+                    "mixing window functions with aggregate or selector functions requires a GROUP BY interval",
+                );
+            }
+            Ok(ProjectionType::Window)
         } else if self.selector_count == 1 && self.aggregate_count == 0 {
             Ok(ProjectionType::Selector {
                 has_fields: self.has_non_aggregate_fields,
@@ -814,7 +950,7 @@ impl FieldChecker {
 
     /// The total number of functions observed.
     fn function_count(&self) -> usize {
-        self.aggregate_count + self.selector_count
+        self.aggregate_count + self.selector_count + self.window_count
     }
 }
 
@@ -827,6 +963,8 @@ impl FieldChecker {
                 self.has_non_aggregate_fields = true;
                 Ok(())
             }
+            // now() is evaluated as a constant timestamp, like a literal
+            Expr::Call(c) if is_now_function(&c.name) => Ok(()),
             Expr::Call(c) if is_scalar_math_function(&c.name) => self.check_math_function(c),
             Expr::Call(c) => self.check_aggregate_function(c),
             Expr::Binary(b) => match (&*b.lhs, &*b.rhs) {
@@ -1036,7 +1174,7 @@ impl FieldChecker {
     }
 
     fn check_derivative(&mut self, name: &str, args: &[Expr]) -> Result<()> {
-        self.inc_aggregate_count();
+        self.inc_window_count();
 
         check_exp_args!(name, 1, 2, args);
         match args.get(1) {
@@ -1055,7 +1193,7 @@ impl FieldChecker {
     }
 
     fn check_elapsed(&mut self, name: &str, args: &[Expr]) -> Result<()> {
-        self.inc_aggregate_count();
+        self.inc_window_count();
         check_exp_args!(name, 1, 2, args);
 
         match args.get(1) {
@@ -1074,21 +1212,21 @@ impl FieldChecker {
     }
 
     fn check_difference(&mut self, name: &str, args: &[Expr]) -> Result<()> {
-        self.inc_aggregate_count();
+        self.inc_window_count();
         check_exp_args!(name, 1, args);
 
         self.check_nested_symbol(name, &args[0])
     }
 
     fn check_cumulative_sum(&mut self, args: &[Expr]) -> Result<()> {
-        self.inc_aggregate_count();
+        self.inc_window_count();
         check_exp_args!("cumulative_sum", 1, args);
 
         self.check_nested_symbol("cumulative_sum", &args[0])
     }
 
     fn check_moving_average(&mut self, args: &[Expr]) -> Result<()> {
-        self.inc_aggregate_count();
+        self.inc_window_count();
         check_exp_args!("moving_average", 2, args);
 
         let v = lit_integer!("moving_average", args, 1);
@@ -1102,7 +1240,7 @@ impl FieldChecker {
     }
 
     fn check_exponential_moving_average(&mut self, name: &str, args: &[Expr]) -> Result<()> {
-        self.inc_aggregate_count();
+        self.inc_window_count();
         check_exp_args!(name, 2, 4, args);
 
         let v = lit_integer!(name, args, 1);
@@ -1140,7 +1278,7 @@ impl FieldChecker {
     }
 
     fn check_kaufmans(&mut self, name: &str, args: &[Expr]) -> Result<()> {
-        self.inc_aggregate_count();
+        self.inc_window_count();
         check_exp_args!(name, 2, 3, args);
 
         let v = lit_integer!(name, args, 1);
@@ -1160,7 +1298,7 @@ impl FieldChecker {
     }
 
     fn check_chande_momentum_oscillator(&mut self, name: &str, args: &[Expr]) -> Result<()> {
-        self.inc_aggregate_count();
+        self.inc_window_count();
         check_exp_args!(name, 2, 4, args);
 
         let v = lit_integer!(name, args, 1);
@@ -1251,6 +1389,10 @@ impl FieldChecker {
         self.selector_count += 1
     }
 
+    fn inc_window_count(&mut self) {
+        self.window_count += 1
+    }
+
     fn check_nested_expr(&mut self, expr: &Expr) -> Result<()> {
         match expr {
             Expr::Call(c) if c.name == "distinct" => self.check_distinct(&c.args, true),
@@ -1294,6 +1436,9 @@ pub(crate) enum ProjectionType {
     /// A query that projects one or more aggregate functions or
     /// two or more selector functions.
     Aggregate,
+    /// A query that projects one or more window-like functions, such as
+    /// `difference`, of the raw field values.
+    Window,
     /// A query that projects a single selector function,
     /// such as `last` or `first`.
     Selector {
@@ -1412,6 +1557,32 @@ mod test {
 
         let info = select_statement_info(&parse_select("SELECT top(foo, 3) FROM cpu")).unwrap();
         assert_matches!(info.projection_type, ProjectionType::TopBottomSelector);
+
+        let info = select_statement_info(&parse_select("SELECT difference(foo) FROM cpu")).unwrap();
+        assert_matches!(info.projection_type, ProjectionType::Window);
+
+        let info = select_statement_info(&parse_select(
+            "SELECT exponential_moving_average(foo, 3) FROM cpu",
+        ))
+        .unwrap();
+        assert_matches!(info.projection_type, ProjectionType::Window);
+
+        let info = select_statement_info(&parse_select(
+            "SELECT holt_winters(mean(foo), 3, 0) FROM cpu GROUP BY TIME(10s)",
+        ))
+        .unwrap();
+        assert_matches!(info.projection_type, ProjectionType::Aggregate);
+
+        let info = select_statement_info(&parse_select(
+            "SELECT difference(mean(foo)), mean(foo) FROM cpu GROUP BY TIME(10s)",
+        ))
+        .unwrap();
+        assert_matches!(info.projection_type, ProjectionType::Aggregate);
+
+        // Fallible
+
+        assert_error!(select_statement_info(&parse_select("SELECT difference(foo), mean(foo) FROM cpu")), DataFusionError::Plan(ref s) if s == "mixing window functions with aggregate or selector functions requires a GROUP BY interval");
+        assert_error!(select_statement_info(&parse_select("SELECT difference(foo), bar FROM cpu")), DataFusionError::Plan(ref s) if s == "mixing aggregate and non-aggregate columns is not supported");
     }
 
     /// Verify all the aggregate, window-like and selector functions are handled
@@ -1904,20 +2075,6 @@ mod test {
                 err.to_string(),
                 "Error during planning: unable to use tag as wildcard in count()"
             );
-
-            let stmt = parse_select("SELECT usage_idle FROM cpu SLIMIT 1");
-            let err = rewrite_statement(&namespace, &stmt).unwrap_err();
-            assert_eq!(
-                err.to_string(),
-                "This feature is not implemented: SLIMIT or SOFFSET"
-            );
-
-            let stmt = parse_select("SELECT usage_idle FROM cpu SOFFSET 1");
-            let err = rewrite_statement(&namespace, &stmt).unwrap_err();
-            assert_eq!(
-                err.to_string(),
-                "This feature is not implemented: SLIMIT or SOFFSET"
-            );
         }
 
         /// Verify subqueries
@@ -1930,7 +2087,7 @@ mod test {
             let stmt = rewrite_statement(&namespace, &stmt).unwrap();
             assert_eq!(
                 stmt.to_string(),
-                "SELECT time::timestamp AS time, usage_idle::float AS usage_idle FROM (SELECT time::timestamp AS time, usage_idle::float FROM cpu)"
+                "SELECT time::timestamp AS time, usage_idle::float AS usage_idle FROM (SELECT time::timestamp AS time, usage_idle::float AS usage_idle FROM cpu)"
             );
 
             // Subquery, regex, match
@@ -1939,7 +2096,7 @@ mod test {
             let stmt = rewrite_statement(&namespace, &stmt).unwrap();
             assert_eq!(
                 stmt.to_string(),
-                "SELECT time::timestamp AS time, bytes_free::integer AS bytes_free FROM (SELECT time::timestamp AS time, bytes_free::integer, bytes_read::integer FROM disk, diskio)"
+                "SELECT time::timestamp AS time, bytes_free::integer AS bytes_free FROM (SELECT time::timestamp AS time, bytes_free::integer AS bytes_free, bytes_read::integer AS bytes_read FROM disk, diskio)"
             );
 
             // Subquery, exact, no match
@@ -1958,7 +2115,7 @@ mod test {
             let stmt = rewrite_statement(&namespace, &stmt).unwrap();
             assert_eq!(
                 stmt.to_string(),
-                "SELECT time::timestamp AS time, usage_system_usage_idle::float AS usage_system_usage_idle FROM (SELECT time::timestamp AS time, usage_system::float + usage_idle::float FROM cpu)"
+                "SELECT time::timestamp AS time, usage_system_usage_idle::float AS usage_system_usage_idle FROM (SELECT time::timestamp AS time, usage_system::float + usage_idle::float AS usage_system_usage_idle FROM cpu)"
             );
 
             // Subquery, no fields projected should be dropped
@@ -1975,13 +2132,51 @@ mod test {
             let stmt = rewrite_statement(&namespace, &stmt).unwrap();
             assert_eq!(
                 stmt.to_string(),
-                "SELECT time::timestamp AS time, cpu::tag AS cpu FROM (SELECT time::timestamp AS time, cpu::tag, usage_system::float FROM cpu)"
+                "SELECT time::timestamp AS time, cpu::tag AS cpu FROM (SELECT time::timestamp AS time, cpu::tag AS cpu, usage_system::float AS usage_system FROM cpu)"
             );
 
             // Outer FROM should be empty, as the subquery does not project any fields
             let stmt = parse_select("SELECT cpu FROM (SELECT cpu FROM cpu)");
             let stmt = rewrite_statement(&namespace, &stmt).unwrap();
             assert!(stmt.from.is_empty());
+
+            // Subquery inherits the time range of the outer query
+            let stmt = parse_select("SELECT usage_idle FROM (SELECT usage_idle FROM cpu WHERE host = 'a') WHERE time >= 0 AND usage_idle > 5");
+            let stmt = rewrite_statement(&namespace, &stmt).unwrap();
+            assert_eq!(
+                stmt.to_string(),
+                "SELECT time::timestamp AS time, usage_idle::float AS usage_idle FROM (SELECT time::timestamp AS time, usage_idle::float AS usage_idle FROM cpu WHERE (host = 'a') AND time >= 0) WHERE time >= 0 AND usage_idle > 5"
+            );
+
+            // Subquery inherits the tags of the outer GROUP BY clause
+            let stmt = parse_select(
+                "SELECT max(usage_idle) FROM (SELECT usage_idle FROM cpu GROUP BY cpu) GROUP BY host",
+            );
+            let stmt = rewrite_statement(&namespace, &stmt).unwrap();
+            assert_eq!(
+                stmt.to_string(),
+                "SELECT time::timestamp AS time, max(usage_idle::float) AS max FROM (SELECT time::timestamp AS time, usage_idle::float AS usage_idle FROM cpu GROUP BY cpu, host) GROUP BY host"
+            );
+
+            // Aggregate subquery inherits the outer GROUP BY interval
+            let stmt = parse_select(
+                "SELECT max(m) FROM (SELECT mean(usage_idle) AS m FROM cpu) GROUP BY TIME(10s)",
+            );
+            let stmt = rewrite_statement(&namespace, &stmt).unwrap();
+            assert_eq!(
+                stmt.to_string(),
+                "SELECT time::timestamp AS time, max(m::float) AS max FROM (SELECT time::timestamp AS time, mean(usage_idle::float) AS m FROM cpu GROUP BY TIME(10s)) GROUP BY TIME(10s)"
+            );
+
+            // Raw subquery does not inherit the outer GROUP BY interval
+            let stmt = parse_select(
+                "SELECT max(usage_idle) FROM (SELECT usage_idle FROM cpu) GROUP BY TIME(10s)",
+            );
+            let stmt = rewrite_statement(&namespace, &stmt).unwrap();
+            assert_eq!(
+                stmt.to_string(),
+                "SELECT time::timestamp AS time, max(usage_idle::float) AS max FROM (SELECT time::timestamp AS time, usage_idle::float AS usage_idle FROM cpu) GROUP BY TIME(10s)"
+            );
         }
 
         /// `DISTINCT` clause and `distinct` function
@@ -2078,6 +2273,33 @@ mod test {
                 "SELECT time::timestamp AS time, sum(field_f64::float) AS sum_field_f64, sum(field_i64::integer) AS sum_field_i64, sum(field_u64::unsigned) AS sum_field_u64, sum(shared_field0::float) AS sum_shared_field0 FROM temp_01"
             );
         }
+
+        /// The tag arguments of top and bottom are projected as additional fields
+        #[test]
+        fn projection_top_bottom() {
+            let namespace = MockSchemaProvider::default();
+
+            let stmt = parse_select("SELECT top(usage_idle, 2) FROM cpu");
+            let stmt = rewrite_statement(&namespace, &stmt).unwrap();
+            assert_eq!(
+                stmt.to_string(),
+                "SELECT time::timestamp AS time, top(usage_idle::float, 2) AS top FROM cpu"
+            );
+
+            let stmt = parse_select("SELECT top(usage_idle, cpu, 2) FROM cpu");
+            let stmt = rewrite_statement(&namespace, &stmt).unwrap();
+            assert_eq!(
+                stmt.to_string(),
+                "SELECT time::timestamp AS time, top(usage_idle::float, cpu::tag, 2) AS top, cpu::tag AS cpu FROM cpu"
+            );
+
+            let stmt = parse_select("SELECT bottom(usage_idle, host, cpu, 2) FROM cpu");
+            let stmt = rewrite_statement(&namespace, &stmt).unwrap();
+            assert_eq!(
+                stmt.to_string(),
+                "SELECT time::timestamp AS time, bottom(usage_idle::float, host::tag, cpu::tag, 2) AS bottom, host::tag AS host, cpu::tag AS cpu FROM cpu"
+            );
+        }
     }
 
     #[test]
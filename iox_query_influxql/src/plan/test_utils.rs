@@ -1,7 +1,7 @@
 //! APIs for testing.
 #![cfg(test)]
 
-use crate::plan::{error, SchemaProvider};
+use crate::plan::{error, Database, SchemaProvider};
 use datafusion::common::Result as DataFusionResult;
 use datafusion::datasource::empty::EmptyTable;
 use datafusion::datasource::provider_as_source;
@@ -136,12 +136,23 @@ pub(crate) mod database {
 
 pub(crate) struct MockSchemaProvider {
     tables: HashMap<String, (Arc<dyn TableSource>, Schema)>,
+    databases: Vec<Database>,
 }
 
 impl Default for MockSchemaProvider {
     fn default() -> Self {
         let mut res = Self {
             tables: HashMap::new(),
+            databases: vec![
+                Database {
+                    name: "db0".to_string(),
+                    retention_period: None,
+                },
+                Database {
+                    name: "db1".to_string(),
+                    retention_period: Some(std::time::Duration::from_secs(7 * 24 * 60 * 60)),
+                },
+            ],
         };
         res.add_schemas(database::schemas());
         res
@@ -190,4 +201,8 @@ impl SchemaProvider for MockSchemaProvider {
     fn table_schema(&self, name: &str) -> Option<Schema> {
         self.tables.get(name).map(|(_, s)| s.clone())
     }
+
+    fn databases(&self) -> &[Database] {
+        &self.databases
+    }
 }
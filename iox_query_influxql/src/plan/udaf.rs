@@ -0,0 +1,664 @@
+//! User-defined aggregate functions that implement the InfluxQL window-like
+//! functions, such as `difference` and `derivative`.
+//!
+//! The functions are evaluated by the DataFusion window operator, over a window
+//! frame that spans from the first row of each series to the current row, ordered
+//! by time. As the frame grows by a single row for each evaluation, the accumulators
+//! observe the rows of a series in order, and evaluate the result for the most
+//! recent row.
+//!
+//! All functions accept the value, followed by the timestamp of the row. Some
+//! functions accept an additional argument, such as the unit of `derivative` or
+//! the window size of `moving_average`.
+//!
+//! Consistent with InfluxQL, `NULL` values are skipped, such that the result of
+//! a function for a row with a `NULL` value is `NULL`, and the next row is computed
+//! using the last non-null value.
+use crate::plan::error;
+use arrow::array::{Array, ArrayRef, Int64Array, TimestampNanosecondArray};
+use arrow::datatypes::DataType;
+use datafusion::common::{Result, ScalarValue};
+use datafusion::logical_expr::{
+    Accumulator, AccumulatorFunctionImplementation, AggregateUDF, ReturnTypeFunction, Signature,
+    StateTypeFunction, TypeSignature, Volatility,
+};
+use once_cell::sync::Lazy;
+use schema::TIME_DATA_TYPE;
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+/// The numeric data types accepted by the window-like functions.
+const NUMERICS: &[DataType] = &[DataType::Float64, DataType::Int64, DataType::UInt64];
+
+/// Definition of the `difference` window function.
+pub(super) static DIFFERENCE: Lazy<Arc<AggregateUDF>> = Lazy::new(|| {
+    make_udaf(
+        "difference",
+        numeric_signature(&[]),
+        Arc::new(|args| Ok(Arc::new(args[0].clone()))),
+        Arc::new(|return_type| Ok(Box::new(DifferenceAccumulator::new(return_type, false)?))),
+    )
+});
+
+/// Definition of the `non_negative_difference` window function.
+pub(super) static NON_NEGATIVE_DIFFERENCE: Lazy<Arc<AggregateUDF>> = Lazy::new(|| {
+    make_udaf(
+        "non_negative_difference",
+        numeric_signature(&[]),
+        Arc::new(|args| Ok(Arc::new(args[0].clone()))),
+        Arc::new(|return_type| Ok(Box::new(DifferenceAccumulator::new(return_type, true)?))),
+    )
+});
+
+/// Definition of the `derivative` window function.
+pub(super) static DERIVATIVE: Lazy<Arc<AggregateUDF>> = Lazy::new(|| {
+    make_udaf(
+        "derivative",
+        numeric_signature(&[DataType::Int64]),
+        Arc::new(|_| Ok(Arc::new(DataType::Float64))),
+        Arc::new(|_| Ok(Box::new(DerivativeAccumulator::new(false)))),
+    )
+});
+
+/// Definition of the `non_negative_derivative` window function.
+pub(super) static NON_NEGATIVE_DERIVATIVE: Lazy<Arc<AggregateUDF>> = Lazy::new(|| {
+    make_udaf(
+        "non_negative_derivative",
+        numeric_signature(&[DataType::Int64]),
+        Arc::new(|_| Ok(Arc::new(DataType::Float64))),
+        Arc::new(|_| Ok(Box::new(DerivativeAccumulator::new(true)))),
+    )
+});
+
+/// Definition of the `moving_average` window function.
+pub(super) static MOVING_AVERAGE: Lazy<Arc<AggregateUDF>> = Lazy::new(|| {
+    make_udaf(
+        "moving_average",
+        numeric_signature(&[DataType::Int64]),
+        Arc::new(|_| Ok(Arc::new(DataType::Float64))),
+        Arc::new(|_| Ok(Box::<MovingAverageAccumulator>::default())),
+    )
+});
+
+/// Definition of the `cumulative_sum` window function.
+pub(super) static CUMULATIVE_SUM: Lazy<Arc<AggregateUDF>> = Lazy::new(|| {
+    make_udaf(
+        "cumulative_sum",
+        numeric_signature(&[]),
+        Arc::new(|args| Ok(Arc::new(args[0].clone()))),
+        Arc::new(|return_type| Ok(Box::new(CumulativeSumAccumulator::new(return_type)?))),
+    )
+});
+
+/// Definition of the `elapsed` window function.
+pub(super) static ELAPSED: Lazy<Arc<AggregateUDF>> = Lazy::new(|| {
+    // Unlike the other window functions, `elapsed` accepts fields of any type.
+    let signature = Signature::one_of(
+        NUMERICS
+            .iter()
+            .chain(&[DataType::Utf8, DataType::Boolean])
+            .map(|dt| TypeSignature::Exact(vec![dt.clone(), TIME_DATA_TYPE(), DataType::Int64]))
+            .collect(),
+        Volatility::Immutable,
+    );
+
+    make_udaf(
+        "elapsed",
+        signature,
+        Arc::new(|_| Ok(Arc::new(DataType::Int64))),
+        Arc::new(|_| Ok(Box::<ElapsedAccumulator>::default())),
+    )
+});
+
+/// Returns the signature of a window function that accepts a numeric value,
+/// a timestamp, followed by the `extra` arguments.
+fn numeric_signature(extra: &[DataType]) -> Signature {
+    Signature::one_of(
+        NUMERICS
+            .iter()
+            .map(|dt| {
+                let mut args = vec![dt.clone(), TIME_DATA_TYPE()];
+                args.extend_from_slice(extra);
+                TypeSignature::Exact(args)
+            })
+            .collect(),
+        Volatility::Immutable,
+    )
+}
+
+fn make_udaf(
+    name: &str,
+    signature: Signature,
+    return_type: ReturnTypeFunction,
+    accumulator: AccumulatorFunctionImplementation,
+) -> Arc<AggregateUDF> {
+    // The functions are only evaluated as window functions, and therefore
+    // never produce an intermediate state.
+    let state_type: StateTypeFunction = Arc::new(|_| Ok(Arc::new(vec![])));
+
+    Arc::new(AggregateUDF::new(
+        name,
+        &signature,
+        &return_type,
+        &accumulator,
+        &state_type,
+    ))
+}
+
+/// Returns the argument at `index` as an [`Int64Array`].
+fn int64_arg(values: &[ArrayRef], index: usize) -> Result<&Int64Array> {
+    values
+        .get(index)
+        .and_then(|arr| arr.as_any().downcast_ref::<Int64Array>())
+        .ok_or_else(|| error::map::internal(format!("expected Int64 argument at position {index}")))
+}
+
+/// Returns the timestamp argument, which is always the second argument.
+fn time_arg(values: &[ArrayRef]) -> Result<&TimestampNanosecondArray> {
+    values
+        .get(1)
+        .and_then(|arr| arr.as_any().downcast_ref::<TimestampNanosecondArray>())
+        .ok_or_else(|| error::map::internal("expected timestamp argument"))
+}
+
+/// Returns the numeric `value` as a [`f64`], or `None` if it is `NULL`.
+fn to_f64(value: &ScalarValue) -> Option<f64> {
+    match value {
+        ScalarValue::Float64(v) => *v,
+        ScalarValue::Int64(v) => v.map(|v| v as f64),
+        ScalarValue::UInt64(v) => v.map(|v| v as f64),
+        _ => None,
+    }
+}
+
+/// Returns `curr - prev`, or `NULL` if `non_negative` is `true` and the
+/// difference is negative.
+fn difference(curr: &ScalarValue, prev: &ScalarValue, non_negative: bool) -> Result<ScalarValue> {
+    Ok(match (curr, prev) {
+        (ScalarValue::Float64(Some(curr)), ScalarValue::Float64(Some(prev))) => {
+            let diff = curr - prev;
+            ScalarValue::Float64((!non_negative || diff >= 0.0).then_some(diff))
+        }
+        (ScalarValue::Int64(Some(curr)), ScalarValue::Int64(Some(prev))) => {
+            let diff = curr.wrapping_sub(*prev);
+            ScalarValue::Int64((!non_negative || diff >= 0).then_some(diff))
+        }
+        (ScalarValue::UInt64(Some(curr)), ScalarValue::UInt64(Some(prev))) => {
+            ScalarValue::UInt64((!non_negative || curr >= prev).then(|| curr.wrapping_sub(*prev)))
+        }
+        (curr, prev) => {
+            return error::internal(format!(
+                "unsupported arguments for difference: {curr:?}, {prev:?}"
+            ))
+        }
+    })
+}
+
+/// Returns `lhs + rhs`.
+fn add(lhs: &ScalarValue, rhs: &ScalarValue) -> Result<ScalarValue> {
+    Ok(match (lhs, rhs) {
+        (ScalarValue::Float64(Some(lhs)), ScalarValue::Float64(Some(rhs))) => {
+            ScalarValue::Float64(Some(lhs + rhs))
+        }
+        (ScalarValue::Int64(Some(lhs)), ScalarValue::Int64(Some(rhs))) => {
+            ScalarValue::Int64(Some(lhs.wrapping_add(*rhs)))
+        }
+        (ScalarValue::UInt64(Some(lhs)), ScalarValue::UInt64(Some(rhs))) => {
+            ScalarValue::UInt64(Some(lhs.wrapping_add(*rhs)))
+        }
+        (lhs, rhs) => {
+            return error::internal(format!(
+                "unsupported arguments for cumulative_sum: {lhs:?}, {rhs:?}"
+            ))
+        }
+    })
+}
+
+/// Implements the `difference` and `non_negative_difference` functions.
+#[derive(Debug)]
+struct DifferenceAccumulator {
+    non_negative: bool,
+    /// The `NULL` value of the return type.
+    null: ScalarValue,
+    /// The last non-null value and its timestamp.
+    prev: Option<(i64, ScalarValue)>,
+    result: ScalarValue,
+}
+
+impl DifferenceAccumulator {
+    fn new(return_type: &DataType, non_negative: bool) -> Result<Self> {
+        let null = ScalarValue::try_from(return_type)?;
+        Ok(Self {
+            non_negative,
+            result: null.clone(),
+            null,
+            prev: None,
+        })
+    }
+}
+
+impl Accumulator for DifferenceAccumulator {
+    fn state(&self) -> Result<Vec<ScalarValue>> {
+        error::internal("unexpected call to difference state")
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let times = time_arg(values)?;
+        for i in 0..values[0].len() {
+            let value = ScalarValue::try_from_array(&values[0], i)?;
+            if value.is_null() {
+                self.result = self.null.clone();
+                continue;
+            }
+
+            let time = times.value(i);
+            match &self.prev {
+                // Points that do not advance the time are discarded, per InfluxQL.
+                Some((prev_time, _)) if *prev_time == time => {
+                    self.result = self.null.clone();
+                    continue;
+                }
+                Some((_, prev)) => self.result = difference(&value, prev, self.non_negative)?,
+                None => self.result = self.null.clone(),
+            }
+            self.prev = Some((time, value));
+        }
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, _states: &[ArrayRef]) -> Result<()> {
+        error::internal("unexpected call to difference merge_batch")
+    }
+
+    fn evaluate(&self) -> Result<ScalarValue> {
+        Ok(self.result.clone())
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+    }
+}
+
+/// Implements the `derivative` and `non_negative_derivative` functions.
+///
+/// The derivative is the rate of change between subsequent non-null values,
+/// per the unit duration, specified in nanoseconds by the third argument.
+#[derive(Debug)]
+struct DerivativeAccumulator {
+    non_negative: bool,
+    /// The last non-null value and its timestamp.
+    prev: Option<(i64, f64)>,
+    result: Option<f64>,
+}
+
+impl DerivativeAccumulator {
+    fn new(non_negative: bool) -> Self {
+        Self {
+            non_negative,
+            prev: None,
+            result: None,
+        }
+    }
+}
+
+impl Accumulator for DerivativeAccumulator {
+    fn state(&self) -> Result<Vec<ScalarValue>> {
+        error::internal("unexpected call to derivative state")
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let times = time_arg(values)?;
+        let units = int64_arg(values, 2)?;
+        for i in 0..values[0].len() {
+            let Some(value) = to_f64(&ScalarValue::try_from_array(&values[0], i)?) else {
+                self.result = None;
+                continue;
+            };
+            let time = times.value(i);
+
+            match self.prev {
+                // Points that do not advance the time are discarded, per InfluxQL.
+                Some((prev_time, _)) if prev_time == time => {
+                    self.result = None;
+                    continue;
+                }
+                Some((prev_time, prev)) => {
+                    let unit = units.value(i);
+                    if unit <= 0 {
+                        return error::internal(format!("invalid derivative unit: {unit}"));
+                    }
+                    let diff = value - prev;
+                    let elapsed = (time - prev_time) as f64 / unit as f64;
+                    self.result = (!self.non_negative || diff >= 0.0).then_some(diff / elapsed);
+                }
+                None => self.result = None,
+            }
+            self.prev = Some((time, value));
+        }
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, _states: &[ArrayRef]) -> Result<()> {
+        error::internal("unexpected call to derivative merge_batch")
+    }
+
+    fn evaluate(&self) -> Result<ScalarValue> {
+        Ok(ScalarValue::Float64(self.result))
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+    }
+}
+
+/// Implements the `moving_average` function.
+///
+/// The result is the mean of the last `N` non-null values, where `N` is
+/// specified by the third argument, and is `NULL` until `N` values
+/// have been observed.
+#[derive(Debug, Default)]
+struct MovingAverageAccumulator {
+    /// The last `N` non-null values.
+    window: VecDeque<f64>,
+    sum: f64,
+    result: Option<f64>,
+}
+
+impl Accumulator for MovingAverageAccumulator {
+    fn state(&self) -> Result<Vec<ScalarValue>> {
+        error::internal("unexpected call to moving_average state")
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let sizes = int64_arg(values, 2)?;
+        for i in 0..values[0].len() {
+            let Some(value) = to_f64(&ScalarValue::try_from_array(&values[0], i)?) else {
+                self.result = None;
+                continue;
+            };
+            let n = sizes.value(i).max(1) as usize;
+
+            self.window.push_back(value);
+            self.sum += value;
+            while self.window.len() > n {
+                self.sum -= self.window.pop_front().unwrap_or_default();
+            }
+
+            self.result = (self.window.len() == n).then(|| self.sum / n as f64);
+        }
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, _states: &[ArrayRef]) -> Result<()> {
+        error::internal("unexpected call to moving_average merge_batch")
+    }
+
+    fn evaluate(&self) -> Result<ScalarValue> {
+        Ok(ScalarValue::Float64(self.result))
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self) + self.window.capacity() * std::mem::size_of::<f64>()
+    }
+}
+
+/// Implements the `cumulative_sum` function.
+#[derive(Debug)]
+struct CumulativeSumAccumulator {
+    /// The `NULL` value of the return type.
+    null: ScalarValue,
+    /// The sum of the non-null values observed so far.
+    sum: Option<ScalarValue>,
+    result: ScalarValue,
+}
+
+impl CumulativeSumAccumulator {
+    fn new(return_type: &DataType) -> Result<Self> {
+        let null = ScalarValue::try_from(return_type)?;
+        Ok(Self {
+            result: null.clone(),
+            null,
+            sum: None,
+        })
+    }
+}
+
+impl Accumulator for CumulativeSumAccumulator {
+    fn state(&self) -> Result<Vec<ScalarValue>> {
+        error::internal("unexpected call to cumulative_sum state")
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        for i in 0..values[0].len() {
+            let value = ScalarValue::try_from_array(&values[0], i)?;
+            if value.is_null() {
+                self.result = self.null.clone();
+                continue;
+            }
+
+            let sum = match &self.sum {
+                Some(sum) => add(sum, &value)?,
+                None => value,
+            };
+            self.result = sum.clone();
+            self.sum = Some(sum);
+        }
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, _states: &[ArrayRef]) -> Result<()> {
+        error::internal("unexpected call to cumulative_sum merge_batch")
+    }
+
+    fn evaluate(&self) -> Result<ScalarValue> {
+        Ok(self.result.clone())
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+    }
+}
+
+/// Implements the `elapsed` function.
+///
+/// The result is the time elapsed between subsequent non-null values, as
+/// a multiple of the unit duration, specified in nanoseconds by the third
+/// argument.
+#[derive(Debug, Default)]
+struct ElapsedAccumulator {
+    /// The timestamp of the last non-null value.
+    prev: Option<i64>,
+    result: Option<i64>,
+}
+
+impl Accumulator for ElapsedAccumulator {
+    fn state(&self) -> Result<Vec<ScalarValue>> {
+        error::internal("unexpected call to elapsed state")
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let times = time_arg(values)?;
+        let units = int64_arg(values, 2)?;
+        for i in 0..values[0].len() {
+            if values[0].is_null(i) {
+                self.result = None;
+                continue;
+            }
+            let unit = units.value(i);
+            if unit <= 0 {
+                return error::internal(format!("invalid elapsed unit: {unit}"));
+            }
+            let time = times.value(i);
+            self.result = self.prev.map(|prev| (time - prev) / unit);
+            self.prev = Some(time);
+        }
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, _states: &[ArrayRef]) -> Result<()> {
+        error::internal("unexpected call to elapsed merge_batch")
+    }
+
+    fn evaluate(&self) -> Result<ScalarValue> {
+        Ok(ScalarValue::Int64(self.result))
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use arrow::array::{Float64Array, UInt64Array};
+
+    /// Evaluate `acc` for each row of `values`, one row at a time, like
+    /// the DataFusion window operator.
+    fn evaluate_rows(mut acc: Box<dyn Accumulator>, values: &[ArrayRef]) -> Vec<ScalarValue> {
+        (0..values[0].len())
+            .map(|i| {
+                let row = values.iter().map(|arr| arr.slice(i, 1)).collect::<Vec<_>>();
+                acc.update_batch(&row).unwrap();
+                acc.evaluate().unwrap()
+            })
+            .collect()
+    }
+
+    fn f64_args(values: Vec<Option<f64>>, times: Vec<i64>, extra: Option<i64>) -> Vec<ArrayRef> {
+        let len = values.len();
+        let mut args: Vec<ArrayRef> = vec![
+            Arc::new(Float64Array::from(values)),
+            Arc::new(TimestampNanosecondArray::from(times)),
+        ];
+        if let Some(v) = extra {
+            args.push(Arc::new(Int64Array::from(vec![v; len])));
+        }
+        args
+    }
+
+    #[test]
+    fn test_difference() {
+        let args = f64_args(
+            vec![Some(1.0), Some(3.0), None, Some(2.0), Some(4.5)],
+            vec![1, 2, 3, 4, 5],
+            None,
+        );
+
+        let got = evaluate_rows(
+            Box::new(DifferenceAccumulator::new(&DataType::Float64, false).unwrap()),
+            &args,
+        );
+        assert_eq!(
+            got,
+            [None, Some(2.0), None, Some(-1.0), Some(2.5)]
+                .map(ScalarValue::Float64)
+                .to_vec()
+        );
+
+        let got = evaluate_rows(
+            Box::new(DifferenceAccumulator::new(&DataType::Float64, true).unwrap()),
+            &args,
+        );
+        assert_eq!(
+            got,
+            [None, Some(2.0), None, None, Some(2.5)]
+                .map(ScalarValue::Float64)
+                .to_vec()
+        );
+
+        // Unsigned values are not negative
+        let args: Vec<ArrayRef> = vec![
+            Arc::new(UInt64Array::from(vec![3, 1, 4])),
+            Arc::new(TimestampNanosecondArray::from(vec![1, 2, 3])),
+        ];
+        let got = evaluate_rows(
+            Box::new(DifferenceAccumulator::new(&DataType::UInt64, true).unwrap()),
+            &args,
+        );
+        assert_eq!(got, [None, None, Some(3)].map(ScalarValue::UInt64).to_vec());
+    }
+
+    #[test]
+    fn test_derivative() {
+        const SECOND: i64 = 1_000_000_000;
+
+        let args = f64_args(
+            vec![Some(1.0), Some(3.0), None, Some(2.0), Some(2.0)],
+            vec![0, SECOND, 2 * SECOND, 3 * SECOND, 3 * SECOND],
+            Some(SECOND),
+        );
+
+        let got = evaluate_rows(Box::new(DerivativeAccumulator::new(false)), &args);
+        assert_eq!(
+            got,
+            // The derivative of the 4th row is computed using the elapsed time
+            // since the last non-null value, and the last row is discarded, as
+            // it does not advance the time.
+            [None, Some(2.0), None, Some(-0.5), None]
+                .map(ScalarValue::Float64)
+                .to_vec()
+        );
+
+        let got = evaluate_rows(Box::new(DerivativeAccumulator::new(true)), &args);
+        assert_eq!(
+            got,
+            [None, Some(2.0), None, None, None]
+                .map(ScalarValue::Float64)
+                .to_vec()
+        );
+    }
+
+    #[test]
+    fn test_moving_average() {
+        let args = f64_args(
+            vec![Some(1.0), Some(3.0), None, Some(2.0), Some(4.0)],
+            vec![1, 2, 3, 4, 5],
+            Some(2),
+        );
+
+        let got = evaluate_rows(Box::<MovingAverageAccumulator>::default(), &args);
+        assert_eq!(
+            got,
+            [None, Some(2.0), None, Some(2.5), Some(3.0)]
+                .map(ScalarValue::Float64)
+                .to_vec()
+        );
+    }
+
+    #[test]
+    fn test_cumulative_sum() {
+        let args: Vec<ArrayRef> = vec![
+            Arc::new(Int64Array::from(vec![Some(1), Some(3), None, Some(-2)])),
+            Arc::new(TimestampNanosecondArray::from(vec![1, 2, 3, 4])),
+        ];
+
+        let got = evaluate_rows(
+            Box::new(CumulativeSumAccumulator::new(&DataType::Int64).unwrap()),
+            &args,
+        );
+        assert_eq!(
+            got,
+            [Some(1), Some(4), None, Some(2)]
+                .map(ScalarValue::Int64)
+                .to_vec()
+        );
+    }
+
+    #[test]
+    fn test_elapsed() {
+        let args = f64_args(
+            vec![Some(1.0), Some(3.0), None, Some(2.0)],
+            vec![0, 10, 20, 35],
+            Some(5),
+        );
+
+        let got = evaluate_rows(Box::<ElapsedAccumulator>::default(), &args);
+        assert_eq!(
+            got,
+            [None, Some(2), None, Some(5)]
+                .map(ScalarValue::Int64)
+                .to_vec()
+        );
+    }
+}
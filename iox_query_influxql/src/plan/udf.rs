@@ -0,0 +1,190 @@
+//! Stand-in scalar functions for the InfluxQL window-like functions, such as
+//! `difference` and `derivative`, and the `holt_winters` functions.
+//!
+//! The planner maps the projection of a `SELECT` statement to DataFusion expressions
+//! before planning any aggregates, and so the input of a window-like function,
+//! which is frequently an aggregate like `mean(usage)`, is not yet known. The calls
+//! are therefore represented by stand-in functions, which are replaced by window
+//! expressions, implemented in [`udaf`](super::udaf) and [`query_functions`], once
+//! the input is planned.
+//!
+//! The stand-in functions have no implementation, and must not exist in the final
+//! logical plan.
+use crate::plan::{error, udaf};
+use arrow::datatypes::DataType;
+use datafusion::common::Result;
+use datafusion::execution::FunctionRegistry;
+use datafusion::logical_expr::{
+    AggregateUDF, Expr, ReturnTypeFunction, ScalarFunctionImplementation, ScalarUDF, Signature,
+    Volatility,
+};
+use std::sync::Arc;
+
+/// The InfluxQL window-like functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum WindowFunction {
+    Difference,
+    NonNegativeDifference,
+    Derivative,
+    NonNegativeDerivative,
+    MovingAverage,
+    CumulativeSum,
+    Elapsed,
+    ExponentialMovingAverage,
+    DoubleExponentialMovingAverage,
+    TripleExponentialMovingAverage,
+    RelativeStrengthIndex,
+    TripleExponentialDerivative,
+    KaufmansEfficiencyRatio,
+    KaufmansAdaptiveMovingAverage,
+    ChandeMomentumOscillator,
+}
+
+impl WindowFunction {
+    /// Returns the window-like function named `name`, if any.
+    pub(super) fn try_from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "difference" => Self::Difference,
+            "non_negative_difference" => Self::NonNegativeDifference,
+            "derivative" => Self::Derivative,
+            "non_negative_derivative" => Self::NonNegativeDerivative,
+            "moving_average" => Self::MovingAverage,
+            "cumulative_sum" => Self::CumulativeSum,
+            "elapsed" => Self::Elapsed,
+            "exponential_moving_average" => Self::ExponentialMovingAverage,
+            "double_exponential_moving_average" => Self::DoubleExponentialMovingAverage,
+            "triple_exponential_moving_average" => Self::TripleExponentialMovingAverage,
+            "relative_strength_index" => Self::RelativeStrengthIndex,
+            "triple_exponential_derivative" => Self::TripleExponentialDerivative,
+            "kaufmans_efficiency_ratio" => Self::KaufmansEfficiencyRatio,
+            "kaufmans_adaptive_moving_average" => Self::KaufmansAdaptiveMovingAverage,
+            "chande_momentum_oscillator" => Self::ChandeMomentumOscillator,
+            _ => return None,
+        })
+    }
+
+    /// Returns the window-like function represented by the stand-in `udf`, if any.
+    pub(super) fn try_from_scalar_udf(udf: &ScalarUDF) -> Option<Self> {
+        Self::try_from_name(&udf.name)
+    }
+
+    /// The InfluxQL name of the function.
+    pub(super) fn name(&self) -> &'static str {
+        match self {
+            Self::Difference => "difference",
+            Self::NonNegativeDifference => "non_negative_difference",
+            Self::Derivative => "derivative",
+            Self::NonNegativeDerivative => "non_negative_derivative",
+            Self::MovingAverage => "moving_average",
+            Self::CumulativeSum => "cumulative_sum",
+            Self::Elapsed => "elapsed",
+            Self::ExponentialMovingAverage => "exponential_moving_average",
+            Self::DoubleExponentialMovingAverage => "double_exponential_moving_average",
+            Self::TripleExponentialMovingAverage => "triple_exponential_moving_average",
+            Self::RelativeStrengthIndex => "relative_strength_index",
+            Self::TripleExponentialDerivative => "triple_exponential_derivative",
+            Self::KaufmansEfficiencyRatio => "kaufmans_efficiency_ratio",
+            Self::KaufmansAdaptiveMovingAverage => "kaufmans_adaptive_moving_average",
+            Self::ChandeMomentumOscillator => "chande_momentum_oscillator",
+        }
+    }
+
+    /// Returns the aggregate function that implements the window-like function.
+    ///
+    /// The technical analysis functions are provided by the [`query_functions`]
+    /// registry, using the same name as the InfluxQL function.
+    pub(super) fn udaf(&self) -> Result<Arc<AggregateUDF>> {
+        Ok(Arc::clone(match self {
+            Self::Difference => &udaf::DIFFERENCE,
+            Self::NonNegativeDifference => &udaf::NON_NEGATIVE_DIFFERENCE,
+            Self::Derivative => &udaf::DERIVATIVE,
+            Self::NonNegativeDerivative => &udaf::NON_NEGATIVE_DERIVATIVE,
+            Self::MovingAverage => &udaf::MOVING_AVERAGE,
+            Self::CumulativeSum => &udaf::CUMULATIVE_SUM,
+            Self::Elapsed => &udaf::ELAPSED,
+            _ => return query_functions::registry().udaf(self.name()),
+        }))
+    }
+
+    /// Returns an expression that calls the stand-in function with `args`.
+    ///
+    /// The first argument is the input of the window-like function, followed by
+    /// any additional arguments, such as the unit of `derivative`, in nanoseconds.
+    pub(super) fn call(&self, args: Vec<Expr>) -> Expr {
+        let return_type: ReturnTypeFunction = match self {
+            Self::Difference | Self::NonNegativeDifference | Self::CumulativeSum => {
+                Arc::new(|args| Ok(Arc::new(args[0].clone())))
+            }
+            Self::Elapsed => Arc::new(|_| Ok(Arc::new(DataType::Int64))),
+            _ => Arc::new(|_| Ok(Arc::new(DataType::Float64))),
+        };
+
+        stand_in_call(self.name(), return_type, args)
+    }
+}
+
+/// The InfluxQL `holt_winters` and `holt_winters_with_fit` functions.
+///
+/// Unlike the window-like functions, these produce a set of rows for each group of
+/// the `GROUP BY` clause, which replace the rows of the aggregate input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum HoltWinters {
+    WithoutFit,
+    WithFit,
+}
+
+impl HoltWinters {
+    /// Returns the Holt-Winters function named `name`, if any.
+    pub(super) fn try_from_name(name: &str) -> Option<Self> {
+        match name {
+            "holt_winters" => Some(Self::WithoutFit),
+            "holt_winters_with_fit" => Some(Self::WithFit),
+            _ => None,
+        }
+    }
+
+    /// Returns the Holt-Winters function represented by the stand-in `udf`, if any.
+    pub(super) fn try_from_scalar_udf(udf: &ScalarUDF) -> Option<Self> {
+        Self::try_from_name(&udf.name)
+    }
+
+    /// The InfluxQL name of the function.
+    pub(super) fn name(&self) -> &'static str {
+        match self {
+            Self::WithoutFit => "holt_winters",
+            Self::WithFit => "holt_winters_with_fit",
+        }
+    }
+
+    /// Returns the aggregate function that implements the Holt-Winters function.
+    pub(super) fn udaf(&self) -> Result<Arc<AggregateUDF>> {
+        query_functions::registry().udaf(self.name())
+    }
+
+    /// Returns an expression that calls the stand-in function with `args`, which
+    /// are the aggregate input, followed by the `N` and `S` arguments.
+    pub(super) fn call(&self, args: Vec<Expr>) -> Expr {
+        stand_in_call(
+            self.name(),
+            Arc::new(|_| Ok(Arc::new(DataType::Float64))),
+            args,
+        )
+    }
+}
+
+/// Returns an expression that calls a stand-in function named `name` with `args`.
+fn stand_in_call(name: &'static str, return_type: ReturnTypeFunction, args: Vec<Expr>) -> Expr {
+    let fun: ScalarFunctionImplementation = Arc::new(move |_| {
+        error::internal(format!("{name} should not exist in the final logical plan"))
+    });
+
+    Expr::ScalarUDF {
+        fun: Arc::new(ScalarUDF::new(
+            name,
+            &Signature::variadic_any(Volatility::Immutable),
+            &return_type,
+            &fun,
+        )),
+        args,
+    }
+}
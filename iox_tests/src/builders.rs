@@ -121,6 +121,8 @@ impl TableBuilder {
                 id: TableId::new(id),
                 namespace_id: NamespaceId::new(0),
                 name: "table".to_string(),
+                partition_template: None,
+                deleted_at: None,
             },
         }
     }
@@ -145,7 +145,7 @@ impl TestCatalog {
         let mut repos = self.catalog.repositories().await;
         let namespace = repos
             .namespaces()
-            .create(name, retention_period_ns)
+            .create(name, None, retention_period_ns)
             .await
             .unwrap();
 
@@ -1,5 +1,6 @@
 pub mod http;
 pub mod rpc;
+pub mod self_monitoring;
 pub mod server_type;
 mod service;
 
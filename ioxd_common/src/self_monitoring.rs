@@ -0,0 +1,276 @@
+//! Periodic writes of the metrics of a server into an IOx namespace through
+//! the HTTP write API of a router.
+//!
+//! Routers export their own metrics through their write path. Ingesters,
+//! queriers and compactors have no write path of their own, so a
+//! [`SelfMonitor`] snapshots their [`metric::Registry`] to line protocol with
+//! the [`LineProtocolEncoder`] and writes it to the `/api/v2/write` endpoint
+//! of the router configured by [`SelfMonitoringConfig`].
+
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use clap_blocks::self_monitoring::SelfMonitoringConfig;
+use http::header::AUTHORIZATION;
+use metric::U64Counter;
+use metric_exporters::LineProtocolEncoder;
+use observability_deps::tracing::*;
+use snafu::{ResultExt, Snafu};
+use tokio::time::MissedTickBehavior;
+use tokio_util::sync::CancellationToken;
+
+/// Errors writing a snapshot of the metric registry.
+#[derive(Debug, Snafu)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[snafu(display("failed to send metric snapshot to {url}: {source}"))]
+    Send { url: String, source: reqwest::Error },
+
+    #[snafu(display("router at {url} rejected metric snapshot: {source}"))]
+    Rejected { url: String, source: reqwest::Error },
+}
+
+/// Writes a snapshot of a [`metric::Registry`] to a router at a fixed
+/// interval.
+#[derive(Debug)]
+pub struct SelfMonitor {
+    client: reqwest::Client,
+    url: String,
+    query: Vec<(&'static str, String)>,
+    token: Option<String>,
+    interval: Duration,
+    metrics: Arc<metric::Registry>,
+
+    write_errors: U64Counter,
+}
+
+impl SelfMonitor {
+    /// Initialise a [`SelfMonitor`] writing the contents of `metrics` as
+    /// configured by `config`.
+    ///
+    /// Returns [`None`] if self-monitoring is disabled.
+    pub fn new(config: &SelfMonitoringConfig, metrics: Arc<metric::Registry>) -> Option<Self> {
+        let interval = config.interval_seconds?;
+        let router_addr = config
+            .router_addr
+            .as_deref()
+            .expect("self-monitoring interval set without a router address");
+
+        let mut query = vec![("bucket", config.namespace.clone())];
+        if let Some(org) = &config.org {
+            query.push(("org", org.clone()));
+        }
+
+        let write_errors = metrics
+            .register_metric::<U64Counter>(
+                "self_monitoring_write_errors",
+                "number of metric snapshots that could not be written to the monitoring namespace",
+            )
+            .recorder(&[]);
+
+        Some(Self {
+            client: reqwest::Client::new(),
+            url: format!("{}/api/v2/write", router_addr.trim_end_matches('/')),
+            query,
+            token: config.token.clone(),
+            interval,
+            metrics,
+            write_errors,
+        })
+    }
+
+    /// Write a snapshot of the metric registry every configured interval
+    /// until `shutdown` is cancelled.
+    ///
+    /// Failed writes are logged and counted, and retried with the next
+    /// snapshot.
+    pub fn spawn(self, shutdown: CancellationToken) {
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = self.run() => {},
+                _ = shutdown.cancelled() => {},
+            }
+        });
+    }
+
+    async fn run(&self) {
+        let mut ticker = tokio::time::interval(self.interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+
+            if let Err(e) = self.write_snapshot().await {
+                warn!(error=%e, "failed to write metric snapshot");
+                self.write_errors.inc(1);
+            }
+        }
+    }
+
+    /// Write the current value of every metric in the registry to the
+    /// monitoring namespace.
+    pub async fn write_snapshot(&self) -> Result<(), Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system time before unix epoch")
+            .as_nanos() as i64;
+
+        let mut lp = Vec::new();
+        self.metrics
+            .report(&mut LineProtocolEncoder::new(&mut lp, now));
+        if lp.is_empty() {
+            return Ok(());
+        }
+
+        let mut request = self.client.post(&self.url).query(&self.query).body(lp);
+        if let Some(token) = &self.token {
+            request = request.header(AUTHORIZATION, format!("Token {token}"));
+        }
+
+        request
+            .send()
+            .await
+            .context(SendSnafu { url: &self.url })?
+            .error_for_status()
+            .context(RejectedSnafu { url: &self.url })?;
+
+        debug!(url=%self.url, "wrote metric snapshot");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{convert::Infallible, net::SocketAddr};
+
+    use clap::Parser;
+    use hyper::{
+        service::{make_service_fn, service_fn},
+        Body, Request, Response, Server, StatusCode,
+    };
+    use parking_lot::Mutex;
+
+    use super::*;
+
+    /// The method, URI, authorization header and body of a request.
+    type Captured = (String, String, Option<String>, String);
+
+    /// Serve requests on a local port, recording each one and responding
+    /// with `status`.
+    fn serve(status: StatusCode) -> (SocketAddr, Arc<Mutex<Vec<Captured>>>) {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let captured = Arc::clone(&requests);
+        let make_service = make_service_fn(move |_conn| {
+            let captured = Arc::clone(&captured);
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req: Request<Body>| {
+                    let captured = Arc::clone(&captured);
+                    async move {
+                        let method = req.method().to_string();
+                        let uri = req.uri().to_string();
+                        let authz = req
+                            .headers()
+                            .get(AUTHORIZATION)
+                            .map(|v| v.to_str().unwrap().to_string());
+                        let body = hyper::body::to_bytes(req.into_body()).await.unwrap();
+                        let body = String::from_utf8(body.to_vec()).unwrap();
+                        captured.lock().push((method, uri, authz, body));
+
+                        let mut response = Response::new(Body::empty());
+                        *response.status_mut() = status;
+                        Ok::<_, Infallible>(response)
+                    }
+                }))
+            }
+        });
+
+        let server = Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(make_service);
+        let addr = server.local_addr();
+        tokio::spawn(server);
+
+        (addr, requests)
+    }
+
+    fn monitor(addr: SocketAddr, args: &[&str], metrics: &Arc<metric::Registry>) -> SelfMonitor {
+        let router_addr = format!("http://{addr}/");
+        let config = SelfMonitoringConfig::try_parse_from(
+            [
+                "server",
+                "--self-monitoring-interval-seconds",
+                "10",
+                "--self-monitoring-router-addr",
+                router_addr.as_str(),
+            ]
+            .iter()
+            .chain(args),
+        )
+        .unwrap();
+
+        SelfMonitor::new(&config, Arc::clone(metrics)).expect("self-monitoring enabled")
+    }
+
+    #[test]
+    fn test_disabled() {
+        let config = SelfMonitoringConfig::try_parse_from(["server"]).unwrap();
+        assert!(SelfMonitor::new(&config, Default::default()).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_write_snapshot() {
+        let (addr, requests) = serve(StatusCode::NO_CONTENT);
+
+        let metrics = Arc::new(metric::Registry::default());
+        metrics
+            .register_metric::<U64Counter>("bananas", "a counter")
+            .recorder(&[("kind", "ripe")])
+            .inc(3);
+
+        monitor(
+            addr,
+            &[
+                "--self-monitoring-org",
+                "iox",
+                "--self-monitoring-token",
+                "s3cr3t",
+            ],
+            &metrics,
+        )
+        .write_snapshot()
+        .await
+        .expect("write should succeed");
+
+        let requests = requests.lock();
+        let [(method, uri, authz, body)] = requests.as_slice() else {
+            panic!("expected a single request, got {requests:?}");
+        };
+        assert_eq!(method, "POST");
+        assert_eq!(uri, "/api/v2/write?bucket=_monitoring&org=iox");
+        assert_eq!(authz.as_deref(), Some("Token s3cr3t"));
+
+        // Both the counter and the write error counter of the monitor are written.
+        assert!(body.contains("bananas,kind=ripe "), "{body}");
+        assert!(body.contains("self_monitoring_write_errors"), "{body}");
+    }
+
+    #[tokio::test]
+    async fn test_write_snapshot_rejected() {
+        let (addr, requests) = serve(StatusCode::NOT_FOUND);
+
+        let metrics = Arc::new(metric::Registry::default());
+        let err = monitor(addr, &[], &metrics)
+            .write_snapshot()
+            .await
+            .expect_err("write should fail");
+        assert!(matches!(err, Error::Rejected { .. }), "{err}");
+
+        let requests = requests.lock();
+        let [(_, uri, authz, _)] = requests.as_slice() else {
+            panic!("expected a single request, got {requests:?}");
+        };
+        assert_eq!(uri, "/api/v2/write?bucket=_monitoring");
+        assert_eq!(authz, &None);
+    }
+}
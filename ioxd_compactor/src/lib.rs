@@ -1,6 +1,9 @@
 use async_trait::async_trait;
 use backoff::BackoffConfig;
-use clap_blocks::compactor::{CompactionType, CompactorConfig};
+use clap_blocks::{
+    compactor::{CompactionType, CompactorConfig},
+    self_monitoring::SelfMonitoringConfig,
+};
 use compactor::{
     compactor::Compactor,
     config::{Config, PartitionsSourceConfig, ShardConfig},
@@ -14,6 +17,7 @@ use ioxd_common::{
     add_service,
     http::error::{HttpApiError, HttpApiErrorCode, HttpApiErrorSource},
     rpc::RpcBuilderInput,
+    self_monitoring::SelfMonitor,
     serve_builder,
     server_type::{CommonServerState, RpcError, ServerType},
     setup_builder,
@@ -30,6 +34,7 @@ use trace::TraceCollector;
 
 pub struct CompactorServerType {
     compactor: Compactor,
+    self_monitoring_shutdown: CancellationToken,
     metric_registry: Arc<Registry>,
     trace_collector: Option<Arc<dyn TraceCollector>>,
 }
@@ -43,11 +48,13 @@ impl std::fmt::Debug for CompactorServerType {
 impl CompactorServerType {
     pub fn new(
         compactor: Compactor,
+        self_monitoring_shutdown: CancellationToken,
         metric_registry: Arc<metric::Registry>,
         common_state: &CommonServerState,
     ) -> Self {
         Self {
             compactor,
+            self_monitoring_shutdown,
             metric_registry,
             trace_collector: common_state.trace_collector(),
         }
@@ -97,6 +104,7 @@ impl ServerType for CompactorServerType {
 
     fn shutdown(&self, frontend: CancellationToken) {
         frontend.cancel();
+        self.self_monitoring_shutdown.cancel();
         self.compactor.shutdown();
     }
 }
@@ -130,6 +138,9 @@ impl HttpApiErrorSource for IoxHttpError {
 }
 
 /// Instantiate a compactor server
+///
+/// If `self_monitoring_config` enables self-monitoring, the contents of
+/// `metric_registry` are periodically written to a router until shutdown.
 #[allow(clippy::too_many_arguments)]
 pub async fn create_compactor_server_type(
     common_state: &CommonServerState,
@@ -140,6 +151,7 @@ pub async fn create_compactor_server_type(
     exec: Arc<Executor>,
     time_provider: Arc<dyn TimeProvider>,
     compactor_config: CompactorConfig,
+    self_monitoring_config: Option<&SelfMonitoringConfig>,
 ) -> Arc<dyn ServerType> {
     let backoff_config = BackoffConfig::default();
 
@@ -221,8 +233,16 @@ pub async fn create_compactor_server_type(
         max_num_files_per_plan: compactor_config.max_num_files_per_plan,
     });
 
+    let self_monitoring_shutdown = CancellationToken::new();
+    if let Some(self_monitor) =
+        self_monitoring_config.and_then(|c| SelfMonitor::new(c, Arc::clone(&metric_registry)))
+    {
+        self_monitor.spawn(self_monitoring_shutdown.clone());
+    }
+
     Arc::new(CompactorServerType::new(
         compactor,
+        self_monitoring_shutdown,
         metric_registry,
         common_state,
     ))
@@ -1,11 +1,15 @@
 use arrow_flight::flight_service_server::FlightServiceServer;
 use async_trait::async_trait;
-use clap_blocks::ingester::IngesterConfig;
+use clap_blocks::{ingester::IngesterConfig, self_monitoring::SelfMonitoringConfig};
 use futures::FutureExt;
 use generated_types::influxdata::iox::{
     catalog::v1::catalog_service_server::CatalogServiceServer,
+    delete::v1::delete_service_server::DeleteServiceServer,
     ingester::v1::{
-        persist_service_server::PersistServiceServer, write_service_server::WriteServiceServer,
+        partition_buffer_service_server::PartitionBufferServiceServer,
+        persist_service_server::PersistServiceServer,
+        replication_service_server::ReplicationServiceServer,
+        write_service_server::WriteServiceServer,
     },
 };
 use hyper::{Body, Request, Response};
@@ -16,6 +20,7 @@ use ioxd_common::{
     add_service,
     http::error::{HttpApiError, HttpApiErrorCode, HttpApiErrorSource},
     rpc::RpcBuilderInput,
+    self_monitoring::SelfMonitor,
     serve_builder,
     server_type::{CommonServerState, RpcError, ServerType},
     setup_builder,
@@ -48,6 +53,7 @@ pub type Result<T, E = Error> = std::result::Result<T, E>;
 struct IngesterServerType<I: IngesterRpcInterface> {
     server: IngesterGuard<I>,
     shutdown: Mutex<Option<oneshot::Sender<CancellationToken>>>,
+    self_monitoring_shutdown: CancellationToken,
     metrics: Arc<Registry>,
     trace_collector: Option<Arc<dyn TraceCollector>>,
     max_simultaneous_queries: usize,
@@ -62,10 +68,12 @@ impl<I: IngesterRpcInterface> IngesterServerType<I> {
         max_simultaneous_queries: usize,
         max_incoming_msg_bytes: usize,
         shutdown: oneshot::Sender<CancellationToken>,
+        self_monitoring_shutdown: CancellationToken,
     ) -> Self {
         Self {
             server,
             shutdown: Mutex::new(Some(shutdown)),
+            self_monitoring_shutdown,
             metrics,
             trace_collector: common_state.trace_collector(),
             max_simultaneous_queries,
@@ -119,10 +127,24 @@ impl<I: IngesterRpcInterface + Sync + Send + Debug + 'static> ServerType for Ing
                 .max_decoding_message_size(self.max_incoming_msg_bytes)
                 .max_encoding_message_size(MAX_OUTGOING_MSG_BYTES)
         );
+        add_service!(
+            builder,
+            DeleteServiceServer::new(self.server.rpc().delete_service())
+        );
         add_service!(
             builder,
             PersistServiceServer::new(self.server.rpc().persist_service())
         );
+        add_service!(
+            builder,
+            ReplicationServiceServer::new(self.server.rpc().replication_service())
+                .max_decoding_message_size(self.max_incoming_msg_bytes)
+                .max_encoding_message_size(MAX_OUTGOING_MSG_BYTES)
+        );
+        add_service!(
+            builder,
+            PartitionBufferServiceServer::new(self.server.rpc().partition_buffer_service())
+        );
         add_service!(
             builder,
             FlightServiceServer::new(
@@ -142,6 +164,7 @@ impl<I: IngesterRpcInterface + Sync + Send + Debug + 'static> ServerType for Ing
     }
 
     fn shutdown(&self, frontend: CancellationToken) {
+        self.self_monitoring_shutdown.cancel();
         if let Some(c) = self
             .shutdown
             .lock()
@@ -184,6 +207,10 @@ impl HttpApiErrorSource for IoxHttpError {
 const PERSIST_BACKGROUND_FETCH_TIME: Duration = Duration::from_secs(30);
 
 /// Instantiate an ingester server type
+///
+/// If `self_monitoring_config` enables self-monitoring, the contents of
+/// `metrics` are periodically written to a router until shutdown.
+#[allow(clippy::too_many_arguments)]
 pub async fn create_ingester_server_type(
     common_state: &CommonServerState,
     catalog: Arc<dyn Catalog>,
@@ -191,9 +218,17 @@ pub async fn create_ingester_server_type(
     ingester_config: &IngesterConfig,
     exec: Arc<Executor>,
     object_store: ParquetStorage,
+    self_monitoring_config: Option<&SelfMonitoringConfig>,
 ) -> Result<Arc<dyn ServerType>> {
     let (shutdown_tx, shutdown_rx) = oneshot::channel();
 
+    let self_monitoring_shutdown = CancellationToken::new();
+    if let Some(self_monitor) =
+        self_monitoring_config.and_then(|c| SelfMonitor::new(c, Arc::clone(&metrics)))
+    {
+        self_monitor.spawn(self_monitoring_shutdown.clone());
+    }
+
     let grpc = ingester::new(
         catalog,
         Arc::clone(&metrics),
@@ -205,6 +240,15 @@ pub async fn create_ingester_server_type(
         ingester_config.persist_queue_depth,
         ingester_config.persist_hot_partition_cost,
         object_store,
+        ingester_config
+            .replica_addresses
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+        ingester_config
+            .replication_source
+            .as_ref()
+            .map(ToString::to_string),
         shutdown_rx.map(|v| v.expect("shutdown sender dropped without calling shutdown")),
     )
     .await?;
@@ -216,5 +260,6 @@ pub async fn create_ingester_server_type(
         ingester_config.concurrent_query_limit,
         ingester_config.rpc_write_max_incoming_bytes,
         shutdown_tx,
+        self_monitoring_shutdown,
     )))
 }
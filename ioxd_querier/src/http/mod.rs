@@ -0,0 +1,73 @@
+//! HTTP API of the querier.
+
+use std::sync::Arc;
+
+use authz::Authorizer;
+use hyper::{Body, Method, Request, Response};
+use ioxd_common::http::error::{HttpApiError, HttpApiErrorCode, HttpApiErrorSource};
+use service_common::QueryNamespaceProvider;
+use thiserror::Error;
+
+pub(crate) mod v1;
+
+/// Errors returned by the querier HTTP request handler.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// The requested path has no registered handler.
+    #[error("not found")]
+    NoHandler,
+
+    /// The request method is not supported by the handler of the path.
+    #[error("method not allowed")]
+    MethodNotAllowed,
+}
+
+impl Error {
+    fn error_code(&self) -> HttpApiErrorCode {
+        match self {
+            Self::NoHandler => HttpApiErrorCode::NotFound,
+            Self::MethodNotAllowed => HttpApiErrorCode::MethodNotAllowed,
+        }
+    }
+}
+
+impl HttpApiErrorSource for Error {
+    fn to_http_api_error(&self) -> HttpApiError {
+        HttpApiError::new(self.error_code(), self.to_string())
+    }
+}
+
+/// This type is responsible for servicing requests to the querier HTTP
+/// endpoint.
+///
+/// Requests to some paths may be handled externally by the caller - the IOx
+/// server runner framework takes care of implementing the health endpoint,
+/// metrics, pprof, etc.
+#[derive(Debug)]
+pub struct HttpDelegate<S> {
+    server: Arc<S>,
+    authz: Option<Arc<dyn Authorizer>>,
+}
+
+impl<S> HttpDelegate<S>
+where
+    S: QueryNamespaceProvider,
+{
+    /// Initialise a new [`HttpDelegate`] executing queries against the
+    /// namespaces of `server`.
+    pub fn new(server: Arc<S>, authz: Option<Arc<dyn Authorizer>>) -> Self {
+        Self { server, authz }
+    }
+
+    /// Routes `req` to the appropriate handler, if any, returning the handler
+    /// response.
+    pub async fn route(&self, req: Request<Body>) -> Result<Response<Body>, Error> {
+        match (req.method(), req.uri().path()) {
+            (&Method::GET | &Method::POST, "/query") => {
+                Ok(v1::query(&self.server, &self.authz, req).await)
+            }
+            (_, "/query") => Err(Error::MethodNotAllowed),
+            _ => Err(Error::NoHandler),
+        }
+    }
+}
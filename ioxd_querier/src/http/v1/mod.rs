@@ -0,0 +1,851 @@
+//! An InfluxDB 1.x compatible `/query` API, executing InfluxQL queries.
+//!
+//! See the [1.x query API] for a description of the request parameters and
+//! response format.
+//!
+//! [1.x query API]:
+//!     https://docs.influxdata.com/influxdb/v1.8/tools/api/#query-http-endpoint
+
+use std::sync::Arc;
+
+use authz::{
+    extract_token, http::AuthorizationHeaderExtension, Action, Authorizer, Permission, Resource,
+};
+use datafusion::{error::DataFusionError, physical_plan::ExecutionPlan};
+use futures::StreamExt;
+use hyper::{
+    body::{Bytes, HttpBody},
+    header::{ACCEPT, CONTENT_TYPE},
+    Body, Method, Request, Response, StatusCode,
+};
+use influxdb_influxql_parser::{parse_statements, statement::Statement, ParseError};
+use iox_query::{
+    exec::{ExecutionContextProvider, IOxSessionContext},
+    params::StatementParams,
+    QueryCompletedToken, QueryNamespace,
+};
+use observability_deps::tracing::{debug, info};
+use service_common::{
+    influxql::{statement_namespace, NamespaceDatabases},
+    planner::Planner,
+    QueryNamespaceProvider, QueryPermit,
+};
+use thiserror::Error;
+use tokio::sync::mpsc;
+use trace::{ctx::SpanContext, span::SpanExt};
+
+use self::{
+    params::{ParamsError, QueryParams},
+    response::{
+        Epoch, Format, QueryResponse, ResponseError, Series, SeriesWriter, StatementResult,
+    },
+};
+
+mod params;
+mod response;
+
+/// The maximum size of a form-encoded `POST /query` request body.
+const MAX_REQUEST_BODY_BYTES: usize = 1024 * 1024;
+
+/// Errors that fail a `/query` request as a whole.
+///
+/// Errors executing a single statement are returned in the statement result
+/// instead.
+#[derive(Debug, Error)]
+pub enum QueryError {
+    /// The request parameters are invalid.
+    #[error(transparent)]
+    Params(#[from] ParamsError),
+
+    /// The client disconnected while sending the request body.
+    #[error("client disconnected")]
+    ClientHangup(hyper::Error),
+
+    /// The client sent a request body that exceeds [`MAX_REQUEST_BODY_BYTES`].
+    #[error("max request size ({0} bytes) exceeded")]
+    RequestSizeExceeded(usize),
+
+    /// The request is not authorised to read the namespace.
+    #[error("authorization failed: {0}")]
+    Authz(authz::Error),
+
+    /// The query is not valid InfluxQL.
+    #[error("error parsing query: {0}")]
+    Parse(ParseError),
+}
+
+impl QueryError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::Params(_) | Self::ClientHangup(_) | Self::Parse(_) => StatusCode::BAD_REQUEST,
+            Self::RequestSizeExceeded(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::Authz(authz::Error::Forbidden) => StatusCode::FORBIDDEN,
+            Self::Authz(authz::Error::NoToken) => StatusCode::UNAUTHORIZED,
+            Self::Authz(authz::Error::Verification { .. }) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+/// Errors that end the execution of a single statement.
+#[derive(Debug, Error)]
+enum StatementError {
+    #[error(transparent)]
+    Query(#[from] DataFusionError),
+
+    #[error("database not found: {0}")]
+    DatabaseNotFound(String),
+
+    /// The request is not authorised to read the database named by the
+    /// statement.
+    #[error("authorization failed: {0}")]
+    Authz(authz::Error),
+
+    #[error(transparent)]
+    Response(#[from] ResponseError),
+
+    /// The response receiver has gone away, so there is no point continuing.
+    #[error("client disconnected")]
+    Hangup,
+}
+
+/// Handle a `GET` or `POST` request to the `/query` endpoint.
+///
+/// Errors are returned to the client as 1.x error responses, rather than IOx
+/// HTTP API errors, for compatibility with 1.x clients.
+pub(crate) async fn query<S>(
+    server: &Arc<S>,
+    authz: &Option<Arc<dyn Authorizer>>,
+    req: Request<Body>,
+) -> Response<Body>
+where
+    S: QueryNamespaceProvider,
+{
+    let accepts_csv = req
+        .headers()
+        .get(ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.contains("application/csv") || v.contains("text/csv"))
+        .unwrap_or_default();
+
+    match handle_query(server, authz, req, accepts_csv).await {
+        Ok(response) => response,
+        Err(e) => {
+            debug!(error=%e, "InfluxQL query request failed");
+            let format = if accepts_csv {
+                Format::Csv
+            } else {
+                Format::Json { pretty: false }
+            };
+            response(
+                e.status_code(),
+                format,
+                QueryResponse::error(e.to_string())
+                    .encode(format, None)
+                    .into(),
+            )
+        }
+    }
+}
+
+async fn handle_query<S>(
+    server: &Arc<S>,
+    authz: &Option<Arc<dyn Authorizer>>,
+    req: Request<Body>,
+    accepts_csv: bool,
+) -> Result<Response<Body>, QueryError>
+where
+    S: QueryNamespaceProvider,
+{
+    let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
+    let header_token = extract_token(
+        req.extensions()
+            .get::<AuthorizationHeaderExtension>()
+            .and_then(|v| v.as_ref()),
+    );
+
+    // Only form-encoded POST bodies carry parameters.
+    let is_form = req.method() == Method::POST
+        && req
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.starts_with("application/x-www-form-urlencoded"))
+            .unwrap_or_default();
+    let query_string = req.uri().query().map(ToOwned::to_owned);
+    let body = match is_form {
+        true => Some(read_body(req.into_body()).await?),
+        false => None,
+    };
+    let params = QueryParams::try_new(query_string.as_deref(), body.as_deref())?;
+
+    let token = header_token.or_else(|| params.password.clone().map(String::into_bytes));
+    if let Some(namespace) = &params.namespace {
+        let perms = [Permission::ResourceAction(
+            Resource::Database(namespace.clone()),
+            Action::Read,
+        )];
+        authz
+            .require_any_permission(token.clone(), &perms)
+            .await
+            .map_err(QueryError::Authz)?;
+    }
+
+    let statements = parse_statements(&params.query).map_err(QueryError::Parse)?;
+
+    let format = if accepts_csv {
+        Format::Csv
+    } else {
+        Format::Json {
+            pretty: params.pretty,
+        }
+    };
+
+    info!(
+        namespace_name=?params.namespace,
+        query=%params.query,
+        chunked=params.chunk_size.is_some(),
+        "InfluxQL query request",
+    );
+
+    let (tx, mut rx) = mpsc::channel(1);
+    tokio::spawn(run_statements(
+        Arc::clone(server),
+        authz.clone(),
+        token,
+        params.namespace.clone(),
+        span_ctx,
+        statements,
+        params.params.clone(),
+        params.epoch,
+        params.chunk_size,
+        tx,
+    ));
+
+    let epoch = params.epoch;
+    let body = match params.chunk_size {
+        Some(_) => {
+            // Stream each chunk to the client as soon as it is available.
+            // Dropping the receiver when the client disconnects stops the
+            // query execution.
+            let (mut sender, body) = Body::channel();
+            tokio::spawn(async move {
+                while let Some(result) = rx.recv().await {
+                    let chunk = QueryResponse::new(vec![result]).encode(format, epoch);
+                    if sender.send_data(Bytes::from(chunk)).await.is_err() {
+                        break;
+                    }
+                }
+            });
+            body
+        }
+        None => {
+            let mut results = vec![];
+            while let Some(result) = rx.recv().await {
+                results.push(result);
+            }
+            QueryResponse::new(results).encode(format, epoch).into()
+        }
+    };
+
+    Ok(response(StatusCode::OK, format, body))
+}
+
+/// Read a request body of at most [`MAX_REQUEST_BODY_BYTES`].
+async fn read_body(mut body: Body) -> Result<Vec<u8>, QueryError> {
+    let mut out = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk.map_err(QueryError::ClientHangup)?;
+        if out.len() + chunk.len() > MAX_REQUEST_BODY_BYTES {
+            return Err(QueryError::RequestSizeExceeded(MAX_REQUEST_BODY_BYTES));
+        }
+        out.extend_from_slice(&chunk);
+    }
+    Ok(out)
+}
+
+fn response(status: StatusCode, format: Format, body: Body) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header(CONTENT_TYPE, format.content_type())
+        .body(body)
+        .expect("static response parts are valid")
+}
+
+/// Execute `statements` in order, sending their results to `tx`.
+///
+/// The statements run against `namespace`, the database of the request, if
+/// any. The `params` are bound to the parameters of each statement. Execution
+/// stops at the first statement that fails, or when `tx` is closed.
+#[allow(clippy::too_many_arguments)]
+async fn run_statements<S>(
+    server: Arc<S>,
+    authz: Option<Arc<dyn Authorizer>>,
+    token: Option<Vec<u8>>,
+    namespace: Option<String>,
+    span_ctx: Option<SpanContext>,
+    statements: Vec<Statement>,
+    params: StatementParams,
+    epoch: Option<Epoch>,
+    chunk_size: Option<usize>,
+    tx: mpsc::Sender<StatementResult>,
+) where
+    S: QueryNamespaceProvider,
+{
+    for (statement_id, statement) in statements.into_iter().enumerate() {
+        // The planner accepts a single statement at a time.
+        let query = statement.to_string();
+
+        let mut sink = StatementSink {
+            statement_id,
+            tx: &tx,
+            chunked: chunk_size.is_some(),
+            series: vec![],
+        };
+        let res = match statement_context(
+            &server,
+            &authz,
+            &token,
+            namespace.as_deref(),
+            &statement,
+            &query,
+            span_ctx.clone(),
+        )
+        .await
+        {
+            Ok(StatementContext {
+                ctx,
+                mut query_completed_token,
+                databases,
+                _permit,
+            }) => match run_statement(
+                &ctx,
+                &query_completed_token,
+                &query,
+                params.clone(),
+                databases,
+                epoch,
+                chunk_size,
+                &mut sink,
+            )
+            .await
+            {
+                Ok(()) => {
+                    query_completed_token.set_success();
+                    sink.finish().await
+                }
+                Err(e) => Err(e),
+            },
+            Err(e) => Err(e),
+        };
+
+        match res {
+            Ok(()) => {}
+            Err(StatementError::Hangup) => return,
+            Err(e) => {
+                debug!(%statement_id, %query, error=%e, "InfluxQL statement failed");
+                let _ = tx
+                    .send(StatementResult::error(statement_id, e.to_string()))
+                    .await;
+                return;
+            }
+        }
+    }
+}
+
+/// The state a single statement executes with.
+struct StatementContext<S> {
+    ctx: IOxSessionContext,
+    query_completed_token: QueryCompletedToken,
+    databases: Arc<NamespaceDatabases<S>>,
+    /// Held until the statement completes.
+    _permit: Option<QueryPermit>,
+}
+
+/// Prepare the execution of `statement` against `namespace`, the database of
+/// the request or, if the request has none, the database the statement names.
+///
+/// Statements that name no database, such as `SHOW DATABASES`, run in a
+/// context not bound to a namespace and are not recorded in the query log.
+/// The planner rejects those that require a database.
+async fn statement_context<S>(
+    server: &Arc<S>,
+    authz: &Option<Arc<dyn Authorizer>>,
+    token: &Option<Vec<u8>>,
+    namespace: Option<&str>,
+    statement: &Statement,
+    query: &str,
+    span_ctx: Option<SpanContext>,
+) -> Result<StatementContext<S>, StatementError>
+where
+    S: QueryNamespaceProvider,
+{
+    // The namespace of the request is authorised before any statement runs.
+    let (namespace, authorized) = match namespace {
+        Some(namespace) => (Some(namespace.to_string()), true),
+        None => (statement_namespace(statement)?, false),
+    };
+    let databases = Arc::new(NamespaceDatabases::new(
+        Arc::clone(server),
+        authz.clone(),
+        token.clone(),
+        namespace.clone(),
+        span_ctx.clone(),
+    ));
+
+    let Some(namespace) = namespace else {
+        return Ok(StatementContext {
+            ctx: server.new_query_context(span_ctx),
+            query_completed_token: QueryCompletedToken::new(|_, _| {}),
+            databases,
+            _permit: None,
+        });
+    };
+
+    if !authorized {
+        let perms = [Permission::ResourceAction(
+            Resource::Database(namespace.clone()),
+            Action::Read,
+        )];
+        authz
+            .require_any_permission(token.clone(), &perms)
+            .await
+            .map_err(StatementError::Authz)?;
+    }
+
+    // The 1.x API reports a missing database as a statement error.
+    let db = server
+        .db(&namespace, span_ctx.child_span("get namespace"))
+        .await
+        .ok_or_else(|| StatementError::DatabaseNotFound(namespace.clone()))?;
+    let permit = server
+        .acquire_semaphore(
+            &namespace,
+            span_ctx.child_span("query rate limit semaphore"),
+        )
+        .await;
+
+    let ctx = db.new_query_context(span_ctx);
+    let query_completed_token = db.record_query(&ctx, "influxql", Box::new(query.to_string()));
+    Ok(StatementContext {
+        ctx,
+        query_completed_token,
+        databases,
+        _permit: Some(permit),
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_statement<S>(
+    ctx: &IOxSessionContext,
+    query_completed_token: &QueryCompletedToken,
+    query: &str,
+    params: StatementParams,
+    databases: Arc<NamespaceDatabases<S>>,
+    epoch: Option<Epoch>,
+    chunk_size: Option<usize>,
+    sink: &mut StatementSink<'_>,
+) -> Result<(), StatementError>
+where
+    S: QueryNamespaceProvider,
+{
+    let plan: Arc<dyn ExecutionPlan> = Planner::new(ctx).influxql(query, params, databases).await?;
+    let mut writer = SeriesWriter::try_new(&plan.schema(), epoch, chunk_size)?;
+
+    // Stop executing the statement once it is cancelled or exceeds the maximum runtime.
+    let mut stream = query_completed_token.abortable(ctx.execute_stream(plan).await?);
+    while let Some(batch) = stream.next().await {
+        for series in writer.write(&batch?)? {
+            sink.push(series).await?;
+        }
+    }
+    if let Some(series) = writer.finish() {
+        sink.push(series).await?;
+    }
+
+    Ok(())
+}
+
+/// Sends the series of a statement to the response.
+///
+/// Unchunked responses receive a single result holding every series. Chunked
+/// responses receive one result per series, all but the last of which are
+/// marked as partial.
+#[derive(Debug)]
+struct StatementSink<'a> {
+    statement_id: usize,
+    tx: &'a mpsc::Sender<StatementResult>,
+    chunked: bool,
+    /// The series not yet sent. When chunked, this holds at most one series
+    /// so that the last chunk of the statement can be identified.
+    series: Vec<Series>,
+}
+
+impl<'a> StatementSink<'a> {
+    async fn push(&mut self, series: Series) -> Result<(), StatementError> {
+        if self.chunked {
+            if let Some(prev) = self.series.pop() {
+                self.send(vec![prev], true).await?;
+            }
+        }
+        self.series.push(series);
+        Ok(())
+    }
+
+    async fn finish(mut self) -> Result<(), StatementError> {
+        let series = std::mem::take(&mut self.series);
+        self.send(series, false).await
+    }
+
+    async fn send(&self, series: Vec<Series>, partial: bool) -> Result<(), StatementError> {
+        self.tx
+            .send(StatementResult {
+                series,
+                partial,
+                ..StatementResult::new(self.statement_id)
+            })
+            .await
+            .map_err(|_| StatementError::Hangup)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use authz::Permission;
+    use iox_query::test::TestChunk;
+    use serde_json::{json, Value};
+    use service_common::test_util::TestDatabaseStore;
+
+    use super::*;
+
+    async fn test_server() -> Arc<TestDatabaseStore> {
+        let server = Arc::new(TestDatabaseStore::default());
+        let db = server.db_or_create("bananas").await;
+        db.add_chunk(
+            "my_partition_key",
+            Arc::new(
+                TestChunk::new("cpu")
+                    .with_tag_column("tag1")
+                    .with_i64_field_column("field_int")
+                    .with_time_column()
+                    .with_three_rows_of_data(),
+            ),
+        );
+        server
+    }
+
+    fn request(method: Method, uri: &str) -> Request<Body> {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    async fn body_string(response: Response<Body>) -> String {
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    async fn run(server: &Arc<TestDatabaseStore>, req: Request<Body>) -> (StatusCode, String) {
+        let response = query(server, &None, req).await;
+        let status = response.status();
+        (status, body_string(response).await)
+    }
+
+    async fn run_json(server: &Arc<TestDatabaseStore>, uri: &str) -> (StatusCode, Value) {
+        let (status, body) = run(server, request(Method::GET, uri)).await;
+        (status, serde_json::from_str(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_query() {
+        let server = test_server().await;
+
+        let (status, got) = run_json(
+            &server,
+            "/query?db=bananas&q=SELECT+field_int+FROM+cpu+GROUP+BY+tag1&epoch=s",
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            got,
+            json!({"results": [{
+                "statement_id": 0,
+                "series": [
+                    {"name": "cpu", "tags": {"tag1": "UT"}, "columns": ["time", "field_int"], "values": [[0, 70]]},
+                    {"name": "cpu", "tags": {"tag1": "VT"}, "columns": ["time", "field_int"], "values": [[0, 10]]},
+                    {"name": "cpu", "tags": {"tag1": "WA"}, "columns": ["time", "field_int"], "values": [[0, 1000]]},
+                ],
+            }]})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_query_rfc3339_time() {
+        let server = test_server().await;
+
+        let (status, got) = run_json(
+            &server,
+            "/query?db=bananas&q=SELECT+field_int+FROM+cpu+WHERE+tag1%3D%27WA%27",
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            got["results"][0]["series"][0]["values"],
+            json!([["1970-01-01T00:00:00.000008Z", 1000]])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_multiple_statements() {
+        let server = test_server().await;
+
+        let (status, got) = run_json(
+            &server,
+            "/query?db=bananas&q=SELECT+field_int+FROM+cpu+WHERE+tag1%3D%27WA%27%3BSELECT+count(field_int),field_int+FROM+cpu%3BSELECT+field_int+FROM+cpu",
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        let results = got["results"].as_array().unwrap();
+        assert_eq!(results.len(), 2, "execution stops at the first error");
+        assert_eq!(results[0]["statement_id"], 0);
+        assert_eq!(
+            results[0]["series"][0]["values"].as_array().unwrap().len(),
+            1
+        );
+        assert_eq!(results[1]["statement_id"], 1);
+        assert!(results[1]["series"].is_null());
+        assert_eq!(
+            results[1]["error"],
+            "Error during planning: mixing aggregate and non-aggregate columns is not supported"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cancelled() {
+        let server = test_server().await;
+        // Cancel the query while it runs, as a `CancelQuery` request would
+        server.db_or_create("bananas").await.cancel_new_queries();
+
+        let (status, got) =
+            run_json(&server, "/query?db=bananas&q=SELECT+field_int+FROM+cpu").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(got["results"][0]["series"].is_null());
+        assert_eq!(
+            got["results"][0]["error"],
+            "External error: Query was cancelled"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_post_form() {
+        let server = test_server().await;
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/query?db=bananas")
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from("q=SELECT%20field_int%20FROM%20cpu&epoch=ns"))
+            .unwrap();
+        let (status, body) = run(&server, req).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let got: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(
+            got["results"][0]["series"][0]["values"],
+            json!([[8000, 1000], [10000, 10], [20000, 70]])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_chunked() {
+        let server = test_server().await;
+
+        let (status, body) = run(
+            &server,
+            request(
+                Method::GET,
+                "/query?db=bananas&q=SELECT+field_int+FROM+cpu&epoch=ns&chunked=true&chunk_size=2",
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        let chunks = body
+            .lines()
+            .map(|l| serde_json::from_str::<Value>(l).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            chunks,
+            [
+                json!({"results": [{
+                    "statement_id": 0,
+                    "series": [{"name": "cpu", "columns": ["time", "field_int"], "values": [[8000, 1000], [10000, 10]], "partial": true}],
+                    "partial": true,
+                }]}),
+                json!({"results": [{
+                    "statement_id": 0,
+                    "series": [{"name": "cpu", "columns": ["time", "field_int"], "values": [[20000, 70]]}],
+                }]}),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_csv() {
+        let server = test_server().await;
+
+        let req = Request::builder()
+            .uri("/query?db=bananas&q=SELECT+field_int+FROM+cpu+GROUP+BY+tag1")
+            .header(ACCEPT, "application/csv")
+            .body(Body::empty())
+            .unwrap();
+        let response = query(&server, &None, req).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/csv");
+        assert_eq!(
+            body_string(response).await,
+            "name,tags,time,field_int\n\
+             cpu,tag1=UT,20000,70\n\
+             cpu,tag1=VT,10000,10\n\
+             cpu,tag1=WA,8000,1000\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_request_errors() {
+        let server = test_server().await;
+
+        let (status, got) = run_json(&server, "/query?db=bananas").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(got, json!({"error": "missing required parameter \"q\""}));
+
+        let (status, got) = run_json(&server, "/query?db=bananas&q=SELECT").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(got["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("error parsing query: "));
+
+        let (status, _) = run_json(&server, "/query?db=bananas&q=SELECT+1&epoch=d").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_without_database() {
+        let server = test_server().await;
+
+        let (status, got) = run_json(&server, "/query?q=SHOW+DATABASES").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            got["results"][0]["series"][0]["values"],
+            json!([["bananas"]])
+        );
+
+        // Fully-qualified statements run against the database they name
+        let (status, got) = run_json(
+            &server,
+            "/query?q=SELECT+field_int+FROM+bananas..cpu+WHERE+tag1%3D%27WA%27",
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            got["results"][0]["series"][0]["values"],
+            json!([["1970-01-01T00:00:00.000008Z", 1000]])
+        );
+
+        let (status, got) = run_json(&server, "/query?q=SELECT+field_int+FROM+platanos..cpu").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            got,
+            json!({"results": [{"statement_id": 0, "error": "database not found: platanos"}]})
+        );
+
+        // Other statements require a database
+        let (status, got) = run_json(
+            &server,
+            "/query?q=SHOW+DATABASES%3BSELECT+field_int+FROM+cpu",
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            got["results"][1],
+            json!({"statement_id": 1, "error": "Error during planning: database name required"})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_database_not_found() {
+        let server = test_server().await;
+
+        let (status, got) = run_json(&server, "/query?db=platanos&q=SELECT+*+FROM+cpu").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            got,
+            json!({"results": [{"statement_id": 0, "error": "database not found: platanos"}]})
+        );
+    }
+
+    #[derive(Debug)]
+    struct MockAuthorizer {}
+
+    #[async_trait::async_trait]
+    impl Authorizer for MockAuthorizer {
+        async fn permissions(
+            &self,
+            token: Option<Vec<u8>>,
+            perms: &[Permission],
+        ) -> Result<Vec<Permission>, authz::Error> {
+            match token.as_deref() {
+                Some(b"GOOD") => Ok(perms.to_vec()),
+                Some(b"BAD") => Ok(vec![]),
+                Some(b"UGLY") => Err(authz::Error::verification("test", "test error")),
+                Some(_) => panic!("unexpected token"),
+                None => Err(authz::Error::NoToken),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_authz() {
+        let server = test_server().await;
+        let authz: Option<Arc<dyn Authorizer>> = Some(Arc::new(MockAuthorizer {}));
+
+        async fn status(
+            server: &Arc<TestDatabaseStore>,
+            authz: &Option<Arc<dyn Authorizer>>,
+            uri: &str,
+            authorization: Option<&'static str>,
+        ) -> StatusCode {
+            let mut req = request(Method::GET, uri);
+            req.extensions_mut()
+                .insert(AuthorizationHeaderExtension::new(
+                    authorization.map(hyper::header::HeaderValue::from_static),
+                ));
+            query(server, authz, req).await.status()
+        }
+
+        let uri = "/query?db=bananas&q=SELECT+field_int+FROM+cpu";
+        assert_eq!(
+            status(&server, &authz, uri, None).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status(&server, &authz, uri, Some("Token GOOD")).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(&server, &authz, uri, Some("Token BAD")).await,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            status(&server, &authz, uri, Some("Token UGLY")).await,
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+        assert_eq!(
+            status(&server, &authz, &format!("{uri}&p=GOOD"), None).await,
+            StatusCode::OK
+        );
+    }
+}
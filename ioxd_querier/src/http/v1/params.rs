@@ -0,0 +1,331 @@
+//! Parameters of an InfluxDB 1.x `/query` request.
+
+use std::collections::HashMap;
+
+use iox_query::params::{StatementParam, StatementParams};
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::response::Epoch;
+
+/// The number of rows per chunk of a chunked response, if the request does not
+/// specify a valid `chunk_size`.
+const DEFAULT_CHUNK_SIZE: usize = 10_000;
+
+/// The separator between the database and retention policy names when mapping
+/// them to a namespace name.
+const V1_NAMESPACE_RP_SEPARATOR: char = '/';
+
+/// Errors returned when parsing the parameters of a `/query` request.
+#[derive(Debug, Error)]
+pub enum ParamsError {
+    /// The request contains no query.
+    #[error("missing required parameter \"q\"")]
+    MissingQuery,
+
+    /// The provided "db" or "rp" value contains the reserved `/` character.
+    #[error("db and rp cannot contain the reserved character '/'")]
+    ContainsRpSeparator,
+
+    /// The `epoch` parameter is not a known precision.
+    #[error("{0}")]
+    InvalidEpoch(String),
+
+    /// The `params` parameter is not a JSON object of boolean, number or
+    /// string values.
+    #[error("error parsing query parameters: {0}")]
+    InvalidParams(String),
+
+    /// The request parameters cannot be decoded.
+    #[error("failed to decode query parameters: {0}")]
+    Decode(#[from] serde::de::value::Error),
+}
+
+/// The raw parameters of a request, as found in either the URL query string or
+/// a form-encoded request body.
+#[derive(Debug, Default, Deserialize)]
+struct RawParams {
+    q: Option<String>,
+    db: Option<String>,
+    rp: Option<String>,
+    epoch: Option<String>,
+    pretty: Option<String>,
+    chunked: Option<String>,
+    chunk_size: Option<String>,
+    p: Option<String>,
+    params: Option<String>,
+}
+
+impl RawParams {
+    /// Fill any parameters missing from `self` with those of `other`.
+    fn or(self, other: Self) -> Self {
+        Self {
+            q: self.q.or(other.q),
+            db: self.db.or(other.db),
+            rp: self.rp.or(other.rp),
+            epoch: self.epoch.or(other.epoch),
+            pretty: self.pretty.or(other.pretty),
+            chunked: self.chunked.or(other.chunked),
+            chunk_size: self.chunk_size.or(other.chunk_size),
+            p: self.p.or(other.p),
+            params: self.params.or(other.params),
+        }
+    }
+}
+
+/// The parsed parameters of a `/query` request.
+#[derive(Debug, PartialEq)]
+pub(crate) struct QueryParams {
+    /// The InfluxQL query text, possibly containing multiple statements.
+    pub(crate) query: String,
+    /// The namespace mapped from the `db` and `rp` parameters, if the request
+    /// specifies a database.
+    pub(crate) namespace: Option<String>,
+    /// The precision of returned timestamps, or `None` for RFC3339 strings.
+    pub(crate) epoch: Option<Epoch>,
+    pub(crate) pretty: bool,
+    /// The maximum number of rows per chunk if the response is chunked.
+    pub(crate) chunk_size: Option<usize>,
+    /// The token passed as the `p` (password) parameter.
+    pub(crate) password: Option<String>,
+    /// The values bound to the named parameters of the query, passed as a
+    /// JSON object in the `params` parameter.
+    pub(crate) params: StatementParams,
+}
+
+impl QueryParams {
+    /// Parse the parameters of a request from its URL `query_string` and
+    /// form-encoded `body`.
+    ///
+    /// Parameters specified in the body take precedence over those in the
+    /// query string.
+    pub(crate) fn try_new(
+        query_string: Option<&str>,
+        body: Option<&[u8]>,
+    ) -> Result<Self, ParamsError> {
+        let from_query: RawParams = serde_urlencoded::from_str(query_string.unwrap_or_default())?;
+        let from_body: RawParams = serde_urlencoded::from_bytes(body.unwrap_or_default())?;
+        let raw = from_body.or(from_query);
+
+        let query = raw
+            .q
+            .filter(|q| !q.trim().is_empty())
+            .ok_or(ParamsError::MissingQuery)?;
+        // The database is optional, as statements such as SHOW DATABASES, or
+        // those with fully-qualified measurement names, do not need one.
+        let db = raw.db.filter(|db| !db.is_empty());
+        let rp = raw.rp.unwrap_or_default();
+        if db
+            .as_deref()
+            .map_or(false, |db| db.contains(V1_NAMESPACE_RP_SEPARATOR))
+            || rp.contains(V1_NAMESPACE_RP_SEPARATOR)
+        {
+            return Err(ParamsError::ContainsRpSeparator);
+        }
+
+        // Map the database and retention policy to a namespace the same way
+        // the router does for v1 writes.
+        let namespace = db.map(|db| {
+            if rp.is_empty() || rp.eq_ignore_ascii_case("autogen") {
+                db
+            } else {
+                format!("{db}{V1_NAMESPACE_RP_SEPARATOR}{rp}")
+            }
+        });
+
+        let epoch = raw
+            .epoch
+            .filter(|e| !e.is_empty())
+            .map(|e| e.parse())
+            .transpose()
+            .map_err(ParamsError::InvalidEpoch)?;
+
+        let chunk_size = (raw.chunked.as_deref() == Some("true")).then(|| {
+            raw.chunk_size
+                .and_then(|v| v.parse().ok())
+                .filter(|&v| v > 0)
+                .unwrap_or(DEFAULT_CHUNK_SIZE)
+        });
+
+        let params = match raw.params.filter(|p| !p.trim().is_empty()) {
+            Some(params) => parse_statement_params(&params)?,
+            None => StatementParams::default(),
+        };
+
+        Ok(Self {
+            query,
+            namespace,
+            epoch,
+            pretty: raw.pretty.as_deref() == Some("true"),
+            chunk_size,
+            password: raw.p.filter(|p| !p.is_empty()),
+            params,
+        })
+    }
+}
+
+/// Parse the JSON object of the `params` parameter, such as
+/// `{"host": "server01", "limit": 10}`.
+fn parse_statement_params(params: &str) -> Result<StatementParams, ParamsError> {
+    let params: HashMap<String, serde_json::Value> =
+        serde_json::from_str(params).map_err(|e| ParamsError::InvalidParams(e.to_string()))?;
+
+    params
+        .into_iter()
+        .map(|(name, value)| {
+            let value = StatementParam::try_from(value).map_err(|value| {
+                ParamsError::InvalidParams(format!("invalid value for parameter {name}: {value}"))
+            })?;
+            Ok((name, value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+
+    #[test]
+    fn test_query_string() {
+        let got = QueryParams::try_new(
+            Some("db=bananas&q=SELECT+*+FROM+cpu&epoch=ms&pretty=true&p=token"),
+            None,
+        )
+        .expect("valid params");
+
+        assert_eq!(
+            got,
+            QueryParams {
+                query: "SELECT * FROM cpu".to_string(),
+                namespace: Some("bananas".to_string()),
+                epoch: Some(Epoch::Millisecond),
+                pretty: true,
+                chunk_size: None,
+                password: Some("token".to_string()),
+                params: StatementParams::default(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_statement_params() {
+        let got = QueryParams::try_new(
+            Some("db=bananas&q=SELECT+*+FROM+cpu+WHERE+host+%3D+%24host"),
+            Some(b"params=%7B%22host%22%3A%22server01%22%2C%22n%22%3A10%2C%22f%22%3A0.5%2C%22b%22%3Atrue%7D"),
+        )
+        .expect("valid params");
+
+        assert_eq!(
+            got.params,
+            StatementParams::from([
+                ("host".to_string(), StatementParam::from("server01")),
+                ("n".to_string(), StatementParam::from(10_i64)),
+                ("f".to_string(), StatementParam::from(0.5)),
+                ("b".to_string(), StatementParam::from(true)),
+            ])
+        );
+
+        let params = |params: &str| {
+            QueryParams::try_new(
+                Some(&format!("db=bananas&q=SELECT+1&params={params}")),
+                None,
+            )
+            .map(|p| p.params)
+        };
+
+        assert!(params("").unwrap().is_empty());
+        assert_matches!(params("bananas"), Err(ParamsError::InvalidParams(_)));
+        assert_matches!(
+            params("%7B%22a%22%3Anull%7D"),
+            Err(ParamsError::InvalidParams(e)) => {
+                assert_eq!(e, "invalid value for parameter a: null");
+            }
+        );
+    }
+
+    #[test]
+    fn test_body_takes_precedence() {
+        let got = QueryParams::try_new(
+            Some("db=bananas&q=SHOW+MEASUREMENTS"),
+            Some(b"q=SELECT%20*%20FROM%20cpu&chunked=true"),
+        )
+        .expect("valid params");
+
+        assert_eq!(got.query, "SELECT * FROM cpu");
+        assert_eq!(got.namespace.as_deref(), Some("bananas"));
+        assert_eq!(got.chunk_size, Some(DEFAULT_CHUNK_SIZE));
+    }
+
+    #[test]
+    fn test_retention_policy() {
+        let namespace = |rp: &str| {
+            QueryParams::try_new(Some(&format!("db=bananas&q=SELECT+1&rp={rp}")), None)
+                .map(|p| p.namespace.unwrap())
+        };
+
+        assert_eq!(namespace("").unwrap(), "bananas");
+        assert_eq!(namespace("autogen").unwrap(), "bananas");
+        assert_eq!(namespace("AutoGen").unwrap(), "bananas");
+        assert_eq!(namespace("platanos").unwrap(), "bananas/platanos");
+        assert_matches!(namespace("a/b"), Err(ParamsError::ContainsRpSeparator));
+    }
+
+    #[test]
+    fn test_optional_database() {
+        let got = QueryParams::try_new(Some("q=SHOW+DATABASES"), None).expect("valid params");
+        assert_eq!(got.namespace, None);
+
+        // A retention policy without a database is ignored
+        let got =
+            QueryParams::try_new(Some("q=SHOW+DATABASES&rp=platanos"), None).expect("valid params");
+        assert_eq!(got.namespace, None);
+    }
+
+    #[test]
+    fn test_chunk_size() {
+        let chunk_size = |params: &str| {
+            QueryParams::try_new(Some(&format!("db=bananas&q=SELECT+1&{params}")), None)
+                .unwrap()
+                .chunk_size
+        };
+
+        assert_eq!(chunk_size("chunk_size=10"), None);
+        assert_eq!(chunk_size("chunked=true&chunk_size=10"), Some(10));
+        assert_eq!(
+            chunk_size("chunked=true&chunk_size=0"),
+            Some(DEFAULT_CHUNK_SIZE)
+        );
+        assert_eq!(
+            chunk_size("chunked=true&chunk_size=bananas"),
+            Some(DEFAULT_CHUNK_SIZE)
+        );
+    }
+
+    #[test]
+    fn test_missing_params() {
+        assert_matches!(
+            QueryParams::try_new(Some("db=bananas"), None),
+            Err(ParamsError::MissingQuery)
+        );
+        assert_matches!(
+            QueryParams::try_new(Some("db=bananas&q=+"), None),
+            Err(ParamsError::MissingQuery)
+        );
+        assert_matches!(
+            QueryParams::try_new(Some("db=a/b&q=SELECT+1"), None),
+            Err(ParamsError::ContainsRpSeparator)
+        );
+    }
+
+    #[test]
+    fn test_invalid_epoch() {
+        assert_matches!(
+            QueryParams::try_new(Some("db=bananas&q=SELECT+1&epoch=d"), None),
+            Err(ParamsError::InvalidEpoch(e)) => {
+                assert_eq!(e, "invalid epoch: d");
+            }
+        );
+    }
+}
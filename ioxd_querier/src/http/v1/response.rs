@@ -0,0 +1,749 @@
+//! Conversion of InfluxQL query output into the InfluxDB 1.x `/query` response
+//! format.
+
+use std::{collections::BTreeMap, io::Write, str::FromStr, sync::Arc};
+
+use arrow::{
+    array::{
+        Array, ArrayRef, BooleanArray, Float64Array, Int64Array, StringArray,
+        TimestampNanosecondArray, UInt64Array,
+    },
+    compute::cast,
+    datatypes::{DataType, SchemaRef, TimeUnit},
+    error::ArrowError,
+    record_batch::RecordBatch,
+    util::display::array_value_to_string,
+};
+use chrono::{TimeZone, Utc};
+use generated_types::influxdata::iox::querier::v1::InfluxQlMetadata;
+use serde::Serialize;
+use serde_json::Value;
+use thiserror::Error;
+
+/// Errors converting query output into the v1 response format.
+#[derive(Debug, Error)]
+pub enum ResponseError {
+    /// The output schema carries metadata that cannot be decoded.
+    #[error("invalid InfluxQL metadata: {0}")]
+    InvalidMetadata(#[from] serde_json::Error),
+
+    /// A column could not be converted.
+    #[error("error converting query output: {0}")]
+    Arrow(#[from] ArrowError),
+}
+
+/// The precision of the timestamps in a response, as specified by the `epoch`
+/// query parameter.
+///
+/// When no epoch is specified, JSON timestamps are rendered as RFC3339
+/// strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Epoch {
+    Nanosecond,
+    Microsecond,
+    Millisecond,
+    Second,
+    Minute,
+    Hour,
+}
+
+impl Epoch {
+    fn convert(&self, ns: i64) -> i64 {
+        match self {
+            Self::Nanosecond => ns,
+            Self::Microsecond => ns / 1_000,
+            Self::Millisecond => ns / 1_000_000,
+            Self::Second => ns / 1_000_000_000,
+            Self::Minute => ns / (60 * 1_000_000_000),
+            Self::Hour => ns / (60 * 60 * 1_000_000_000),
+        }
+    }
+}
+
+impl FromStr for Epoch {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "n" | "ns" => Self::Nanosecond,
+            "u" | "µ" => Self::Microsecond,
+            "ms" => Self::Millisecond,
+            "s" => Self::Second,
+            "m" => Self::Minute,
+            "h" => Self::Hour,
+            _ => return Err(format!("invalid epoch: {s}")),
+        })
+    }
+}
+
+/// The encoding of a response body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json { pretty: bool },
+    Csv,
+}
+
+impl Format {
+    /// The value of the `Content-Type` header of a response in this format.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Json { .. } => "application/json",
+            Self::Csv => "application/csv",
+        }
+    }
+}
+
+/// A single series of a statement result.
+///
+/// Rows sharing the same measurement and `GROUP BY` tag values form a series.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Series {
+    pub name: String,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub tags: BTreeMap<String, String>,
+    pub columns: Vec<String>,
+    pub values: Vec<Vec<Value>>,
+    /// Set when the rows of this series continue in the next chunk.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub partial: bool,
+}
+
+/// The result of a single statement.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatementResult {
+    pub statement_id: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub series: Vec<Series>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Set when the series of this statement continue in the next chunk.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub partial: bool,
+}
+
+impl StatementResult {
+    pub fn new(statement_id: usize) -> Self {
+        Self {
+            statement_id,
+            series: vec![],
+            error: None,
+            partial: false,
+        }
+    }
+
+    pub fn error(statement_id: usize, error: impl Into<String>) -> Self {
+        Self {
+            error: Some(error.into()),
+            ..Self::new(statement_id)
+        }
+    }
+}
+
+/// The top-level body of a `/query` response, or of a single chunk of a
+/// chunked response.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QueryResponse {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub results: Vec<StatementResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl QueryResponse {
+    pub fn new(results: Vec<StatementResult>) -> Self {
+        Self {
+            results,
+            error: None,
+        }
+    }
+
+    /// A response that failed as a whole, rather than a single statement.
+    pub fn error(error: impl Into<String>) -> Self {
+        Self {
+            results: vec![],
+            error: Some(error.into()),
+        }
+    }
+
+    /// Encode this response in the given format.
+    ///
+    /// JSON responses are terminated by a newline so that the chunks of a
+    /// chunked response are delimited.
+    pub fn encode(&self, format: Format, epoch: Option<Epoch>) -> Vec<u8> {
+        match format {
+            Format::Json { pretty } => {
+                let mut out = if pretty {
+                    serde_json::to_vec_pretty(self)
+                } else {
+                    serde_json::to_vec(self)
+                }
+                .expect("response serialisation is infallible");
+                out.push(b'\n');
+                out
+            }
+            Format::Csv => self.encode_csv(epoch),
+        }
+    }
+
+    /// Encode this response in the 1.x CSV format:
+    ///
+    /// ```text
+    /// name,tags,time,usage
+    /// cpu,host=a,1000,42.5
+    /// ```
+    ///
+    /// CSV timestamps are always integers, in nanoseconds if no epoch is given.
+    fn encode_csv(&self, epoch: Option<Epoch>) -> Vec<u8> {
+        let mut out = Vec::new();
+
+        if let Some(error) = &self.error {
+            write_csv_row(&mut out, ["error", error.as_str()]);
+            return out;
+        }
+
+        for result in &self.results {
+            if let Some(error) = &result.error {
+                write_csv_row(&mut out, ["error", error.as_str()]);
+                continue;
+            }
+
+            let mut columns: Option<&[String]> = None;
+            for series in &result.series {
+                if columns != Some(series.columns.as_slice()) {
+                    write_csv_row(
+                        &mut out,
+                        ["name", "tags"]
+                            .into_iter()
+                            .chain(series.columns.iter().map(String::as_str)),
+                    );
+                    columns = Some(series.columns.as_slice());
+                }
+
+                let tags = series
+                    .tags
+                    .iter()
+                    .map(|(k, v)| format!("{k}={v}"))
+                    .collect::<Vec<_>>()
+                    .join(",");
+
+                for row in &series.values {
+                    let values = row
+                        .iter()
+                        .zip(&series.columns)
+                        .map(|(v, col)| csv_value(v, col == "time", epoch))
+                        .collect::<Vec<_>>();
+                    write_csv_row(
+                        &mut out,
+                        [series.name.as_str(), tags.as_str()]
+                            .into_iter()
+                            .chain(values.iter().map(String::as_str)),
+                    );
+                }
+            }
+        }
+
+        out
+    }
+}
+
+/// Render a JSON cell as a CSV field.
+///
+/// RFC3339 timestamps are only produced when no epoch is requested, in which
+/// case CSV output uses nanoseconds.
+fn csv_value(v: &Value, is_time: bool, epoch: Option<Epoch>) -> String {
+    match v {
+        Value::Null => String::new(),
+        Value::String(s) if is_time && epoch.is_none() => chrono::DateTime::parse_from_rfc3339(s)
+            .map(|t| t.timestamp_nanos().to_string())
+            .unwrap_or_else(|_| s.clone()),
+        Value::String(s) => s.clone(),
+        v => v.to_string(),
+    }
+}
+
+fn write_csv_row<'a>(out: &mut Vec<u8>, fields: impl IntoIterator<Item = &'a str>) {
+    for (i, field) in fields.into_iter().enumerate() {
+        if i > 0 {
+            out.push(b',');
+        }
+        if field.contains([',', '"', '\n', '\r']) {
+            write!(out, "\"{}\"", field.replace('"', "\"\"")).expect("write to vec");
+        } else {
+            out.extend_from_slice(field.as_bytes());
+        }
+    }
+    out.push(b'\n');
+}
+
+/// Converts the [`RecordBatch`] stream of a single InfluxQL statement into
+/// [`Series`].
+///
+/// The InfluxQL planner sorts output by measurement and `GROUP BY` tag values,
+/// so a series is complete as soon as a row with a different group key is
+/// seen.
+#[derive(Debug)]
+pub struct SeriesWriter {
+    /// Index of the measurement name column, if the plan has InfluxQL
+    /// metadata.
+    measurement_index: Option<usize>,
+    /// The `GROUP BY` tag keys and the indexes of their columns.
+    tag_keys: Vec<(String, usize)>,
+    /// The indexes and names of the columns included in each series.
+    columns: Vec<(usize, String)>,
+    epoch: Option<Epoch>,
+    /// The maximum number of rows per series, if the response is chunked.
+    chunk_size: Option<usize>,
+    current: Option<Series>,
+}
+
+impl SeriesWriter {
+    pub fn try_new(
+        schema: &SchemaRef,
+        epoch: Option<Epoch>,
+        chunk_size: Option<usize>,
+    ) -> Result<Self, ResponseError> {
+        let md = schema
+            .metadata()
+            .get(schema::INFLUXQL_METADATA_KEY)
+            .map(|md| serde_json::from_str::<InfluxQlMetadata>(md))
+            .transpose()?;
+
+        let (measurement_index, tag_key_columns) = match md {
+            Some(md) => (
+                Some(md.measurement_column_index as usize),
+                md.tag_key_columns,
+            ),
+            None => (None, vec![]),
+        };
+
+        // Exclude the measurement column and any tag key columns that only
+        // appear in the `GROUP BY` clause.
+        let columns = schema
+            .fields()
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| {
+                Some(*i) != measurement_index
+                    && !tag_key_columns
+                        .iter()
+                        .any(|tk| tk.column_index as usize == *i && !tk.is_projected)
+            })
+            .map(|(i, f)| (i, f.name().clone()))
+            .collect();
+
+        let tag_keys = tag_key_columns
+            .into_iter()
+            .map(|tk| (tk.tag_key, tk.column_index as usize))
+            .collect();
+
+        Ok(Self {
+            measurement_index,
+            tag_keys,
+            columns,
+            epoch,
+            chunk_size,
+            current: None,
+        })
+    }
+
+    /// Add the rows of `batch`, returning any series that are complete.
+    pub fn write(&mut self, batch: &RecordBatch) -> Result<Vec<Series>, ResponseError> {
+        let measurement = self
+            .measurement_index
+            .map(|idx| string_column(batch.column(idx)))
+            .transpose()?;
+        let tag_values = self
+            .tag_keys
+            .iter()
+            .map(|(_, idx)| string_column(batch.column(*idx)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let columns = self
+            .columns
+            .iter()
+            .map(|(idx, _)| batch.column(*idx))
+            .collect::<Vec<_>>();
+
+        let mut completed = vec![];
+        for row in 0..batch.num_rows() {
+            let name = measurement
+                .as_ref()
+                .map(|m| string_value(m, row))
+                .unwrap_or_default();
+            let tags = self
+                .tag_keys
+                .iter()
+                .zip(&tag_values)
+                .map(|((key, _), values)| (key.clone(), string_value(values, row).to_string()))
+                .collect::<BTreeMap<_, _>>();
+
+            let same_series = matches!(&self.current, Some(s) if s.name == name && s.tags == tags);
+            if !same_series {
+                let next = self.new_series(name.to_string(), tags);
+                completed.extend(self.current.replace(next));
+            } else if self.chunk_size == self.current.as_ref().map(|s| s.values.len()) {
+                // The series continues in the next chunk.
+                let next = self.new_series(name.to_string(), tags);
+                let mut full = self.current.replace(next).expect("current series is set");
+                full.partial = true;
+                completed.push(full);
+            }
+
+            let values = columns
+                .iter()
+                .map(|col| self.value(col, row))
+                .collect::<Result<Vec<_>, _>>()?;
+            self.current
+                .as_mut()
+                .expect("current series is always set")
+                .values
+                .push(values);
+        }
+
+        Ok(completed)
+    }
+
+    /// Return the last, incomplete series, if any.
+    pub fn finish(self) -> Option<Series> {
+        self.current
+    }
+
+    fn new_series(&self, name: String, tags: BTreeMap<String, String>) -> Series {
+        Series {
+            name,
+            tags,
+            columns: self.columns.iter().map(|(_, name)| name.clone()).collect(),
+            values: vec![],
+            partial: false,
+        }
+    }
+
+    fn value(&self, array: &ArrayRef, row: usize) -> Result<Value, ResponseError> {
+        if array.is_null(row) {
+            return Ok(Value::Null);
+        }
+
+        let any = array.as_any();
+        Ok(match array.data_type() {
+            DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+                let ns = any
+                    .downcast_ref::<TimestampNanosecondArray>()
+                    .expect("timestamp array")
+                    .value(row);
+                match self.epoch {
+                    Some(epoch) => Value::from(epoch.convert(ns)),
+                    None => Value::from(format_rfc3339_nano(ns)),
+                }
+            }
+            DataType::Int64 => Value::from(
+                any.downcast_ref::<Int64Array>()
+                    .expect("int64 array")
+                    .value(row),
+            ),
+            DataType::UInt64 => Value::from(
+                any.downcast_ref::<UInt64Array>()
+                    .expect("uint64 array")
+                    .value(row),
+            ),
+            // NaN and infinite values have no JSON representation and are
+            // rendered as null.
+            DataType::Float64 => Value::from(
+                any.downcast_ref::<Float64Array>()
+                    .expect("float64 array")
+                    .value(row),
+            ),
+            DataType::Boolean => Value::from(
+                any.downcast_ref::<BooleanArray>()
+                    .expect("boolean array")
+                    .value(row),
+            ),
+            DataType::Utf8 | DataType::Dictionary(_, _) => {
+                Value::from(string_value(&string_column(array)?, row))
+            }
+            _ => Value::from(array_value_to_string(array, row)?),
+        })
+    }
+}
+
+/// Cast a measurement, tag or string column to a [`StringArray`].
+fn string_column(array: &ArrayRef) -> Result<Arc<StringArray>, ArrowError> {
+    let array = match array.data_type() {
+        DataType::Utf8 => Arc::clone(array),
+        _ => cast(array, &DataType::Utf8)?,
+    };
+    Ok(Arc::new(
+        array
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .expect("cast to utf8")
+            .clone(),
+    ))
+}
+
+/// The value of `row`, or an empty string if it is NULL.
+fn string_value(array: &StringArray, row: usize) -> &str {
+    if array.is_null(row) {
+        ""
+    } else {
+        array.value(row)
+    }
+}
+
+/// Format a nanosecond timestamp as an RFC3339 string with the fractional
+/// seconds trimmed of trailing zeros, like Go's `time.RFC3339Nano`.
+fn format_rfc3339_nano(ns: i64) -> String {
+    let t = Utc.timestamp_nanos(ns);
+    let mut s = t.format("%Y-%m-%dT%H:%M:%S").to_string();
+
+    let nanos = ns.rem_euclid(1_000_000_000);
+    if nanos != 0 {
+        let frac = format!("{nanos:09}");
+        s.push('.');
+        s.push_str(frac.trim_end_matches('0'));
+    }
+    s.push('Z');
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::datatypes::{Field, Schema};
+    use generated_types::influxdata::iox::querier::v1::influx_ql_metadata::TagKeyColumn;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn batch(metadata: Option<InfluxQlMetadata>) -> RecordBatch {
+        let schema = Schema::new(vec![
+            Field::new("iox::measurement", DataType::Utf8, false),
+            Field::new(
+                "time",
+                DataType::Timestamp(TimeUnit::Nanosecond, None),
+                false,
+            ),
+            Field::new("host", DataType::Utf8, true),
+            Field::new("usage", DataType::Float64, true),
+        ]);
+        let schema = match metadata {
+            Some(md) => schema.with_metadata(HashMap::from([(
+                schema::INFLUXQL_METADATA_KEY.to_string(),
+                serde_json::to_string(&md).unwrap(),
+            )])),
+            None => schema,
+        };
+
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(StringArray::from(vec!["cpu", "cpu", "cpu", "mem"])),
+                Arc::new(TimestampNanosecondArray::from(vec![
+                    0,
+                    1_500_000_000,
+                    2_000_000_000,
+                    0,
+                ])),
+                Arc::new(StringArray::from(vec![
+                    Some("a"),
+                    Some("a"),
+                    Some("b"),
+                    None,
+                ])),
+                Arc::new(Float64Array::from(vec![
+                    Some(1.5),
+                    None,
+                    Some(3.0),
+                    Some(4.0),
+                ])),
+            ],
+        )
+        .unwrap()
+    }
+
+    fn group_by_host() -> InfluxQlMetadata {
+        InfluxQlMetadata {
+            measurement_column_index: 0,
+            tag_key_columns: vec![TagKeyColumn {
+                tag_key: "host".to_string(),
+                column_index: 2,
+                is_projected: false,
+            }],
+        }
+    }
+
+    fn write_all(mut w: SeriesWriter, batch: &RecordBatch) -> Vec<Series> {
+        let mut got = w.write(batch).unwrap();
+        got.extend(w.finish());
+        got
+    }
+
+    #[test]
+    fn test_epoch() {
+        assert_eq!("ns".parse::<Epoch>().unwrap(), Epoch::Nanosecond);
+        assert_eq!("u".parse::<Epoch>().unwrap(), Epoch::Microsecond);
+        assert_eq!("µ".parse::<Epoch>().unwrap(), Epoch::Microsecond);
+        assert_eq!("ms".parse::<Epoch>().unwrap(), Epoch::Millisecond);
+        assert_eq!("h".parse::<Epoch>().unwrap(), Epoch::Hour);
+        assert!("d".parse::<Epoch>().is_err());
+
+        assert_eq!(Epoch::Millisecond.convert(1_500_000_000), 1_500);
+        assert_eq!(Epoch::Hour.convert(7_200_000_000_000), 2);
+    }
+
+    #[test]
+    fn test_format_rfc3339_nano() {
+        assert_eq!(format_rfc3339_nano(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_rfc3339_nano(1_500_000_000), "1970-01-01T00:00:01.5Z");
+        assert_eq!(format_rfc3339_nano(1), "1970-01-01T00:00:00.000000001Z");
+    }
+
+    #[test]
+    fn test_series_grouping() {
+        let batch = batch(Some(group_by_host()));
+        let w = SeriesWriter::try_new(&batch.schema(), None, None).unwrap();
+
+        let got = serde_json::to_value(write_all(w, &batch)).unwrap();
+        assert_eq!(
+            got,
+            json!([
+                {
+                    "name": "cpu",
+                    "tags": {"host": "a"},
+                    "columns": ["time", "usage"],
+                    "values": [["1970-01-01T00:00:00Z", 1.5], ["1970-01-01T00:00:01.5Z", null]],
+                },
+                {
+                    "name": "cpu",
+                    "tags": {"host": "b"},
+                    "columns": ["time", "usage"],
+                    "values": [["1970-01-01T00:00:02Z", 3.0]],
+                },
+                {
+                    "name": "mem",
+                    "tags": {"host": ""},
+                    "columns": ["time", "usage"],
+                    "values": [["1970-01-01T00:00:00Z", 4.0]],
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_series_epoch_and_projected_tag() {
+        let mut md = group_by_host();
+        md.tag_key_columns[0].is_projected = true;
+        let batch = batch(Some(md));
+        let w = SeriesWriter::try_new(&batch.schema(), Some(Epoch::Millisecond), None).unwrap();
+
+        let got = write_all(w, &batch);
+        assert_eq!(got.len(), 3);
+        assert_eq!(got[0].columns, ["time", "host", "usage"]);
+        assert_eq!(
+            got[0].values,
+            vec![
+                vec![json!(0), json!("a"), json!(1.5)],
+                vec![json!(1500), json!("a"), Value::Null]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_series_without_metadata() {
+        let batch = batch(None);
+        let w = SeriesWriter::try_new(&batch.schema(), None, None).unwrap();
+
+        let got = write_all(w, &batch);
+        assert_matches::assert_matches!(got.as_slice(), [series] => {
+            assert_eq!(series.name, "");
+            assert!(series.tags.is_empty());
+            assert_eq!(series.columns, ["iox::measurement", "time", "host", "usage"]);
+            assert_eq!(series.values.len(), 4);
+        });
+    }
+
+    #[test]
+    fn test_series_chunk_size() {
+        let batch = batch(Some(InfluxQlMetadata {
+            measurement_column_index: 0,
+            tag_key_columns: vec![],
+        }));
+        let w = SeriesWriter::try_new(&batch.schema(), None, Some(2)).unwrap();
+
+        let got = write_all(w, &batch);
+        let got = got
+            .iter()
+            .map(|s| (s.name.as_str(), s.values.len(), s.partial))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            got,
+            [("cpu", 2, true), ("cpu", 1, false), ("mem", 1, false)]
+        );
+    }
+
+    #[test]
+    fn test_encode_json() {
+        let resp = QueryResponse::new(vec![
+            StatementResult {
+                series: vec![Series {
+                    name: "cpu".to_string(),
+                    tags: BTreeMap::new(),
+                    columns: vec!["time".to_string(), "usage".to_string()],
+                    values: vec![vec![json!("1970-01-01T00:00:00Z"), json!(1.5)]],
+                    partial: false,
+                }],
+                ..StatementResult::new(0)
+            },
+            StatementResult::error(1, "bananas"),
+        ]);
+
+        let got = String::from_utf8(resp.encode(Format::Json { pretty: false }, None)).unwrap();
+        assert_eq!(
+            got,
+            r#"{"results":[{"statement_id":0,"series":[{"name":"cpu","columns":["time","usage"],"values":[["1970-01-01T00:00:00Z",1.5]]}]},{"statement_id":1,"error":"bananas"}]}"#
+                .to_string()
+                + "\n"
+        );
+
+        let got = String::from_utf8(
+            QueryResponse::error("missing required parameter \"q\"")
+                .encode(Format::Json { pretty: false }, None),
+        )
+        .unwrap();
+        assert_eq!(
+            got,
+            "{\"error\":\"missing required parameter \\\"q\\\"\"}\n"
+        );
+    }
+
+    #[test]
+    fn test_encode_csv() {
+        let resp = QueryResponse::new(vec![StatementResult {
+            series: vec![
+                Series {
+                    name: "cpu".to_string(),
+                    tags: BTreeMap::from([
+                        ("host".to_string(), "a".to_string()),
+                        ("region".to_string(), "west".to_string()),
+                    ]),
+                    columns: vec!["time".to_string(), "usage".to_string()],
+                    values: vec![vec![json!("1970-01-01T00:00:01Z"), json!(1.5)]],
+                    partial: false,
+                },
+                Series {
+                    name: "cpu".to_string(),
+                    tags: BTreeMap::from([("host".to_string(), "b".to_string())]),
+                    columns: vec!["time".to_string(), "usage".to_string()],
+                    values: vec![vec![json!("1970-01-01T00:00:02Z"), Value::Null]],
+                    partial: false,
+                },
+            ],
+            ..StatementResult::new(0)
+        }]);
+
+        let got = String::from_utf8(resp.encode(Format::Csv, None)).unwrap();
+        assert_eq!(
+            got,
+            "name,tags,time,usage\n\
+             cpu,\"host=a,region=west\",1000000000,1.5\n\
+             cpu,host=b,2000000000,\n"
+        );
+    }
+}
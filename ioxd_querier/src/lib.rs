@@ -1,6 +1,9 @@
 use async_trait::async_trait;
 use authz::{Authorizer, IoxAuthorizer};
-use clap_blocks::querier::QuerierConfig;
+use clap_blocks::{
+    querier::{QuerierConfig, QueryLogOutputType},
+    self_monitoring::SelfMonitoringConfig,
+};
 use datafusion_util::config::register_iox_object_store;
 use hyper::{Body, Request, Response};
 use iox_catalog::interface::Catalog;
@@ -8,8 +11,9 @@ use iox_query::exec::{Executor, ExecutorType};
 use iox_time::TimeProvider;
 use ioxd_common::{
     add_service,
-    http::error::{HttpApiError, HttpApiErrorCode, HttpApiErrorSource},
+    http::error::HttpApiErrorSource,
     rpc::RpcBuilderInput,
+    self_monitoring::SelfMonitor,
     serve_builder,
     server_type::{CommonServerState, RpcError, ServerType},
     setup_builder,
@@ -17,25 +21,25 @@ use ioxd_common::{
 use metric::Registry;
 use object_store::DynObjectStore;
 use querier::{
-    create_ingester_connections, QuerierCatalogCache, QuerierDatabase, QuerierHandler,
-    QuerierHandlerImpl, QuerierServer,
-};
-use std::{
-    fmt::{Debug, Display},
-    sync::Arc,
+    create_ingester_connections, MaxQueryRuntime, QuerierCatalogCache, QuerierDatabase,
+    QuerierHandler, QuerierHandlerImpl, QuerierServer, QueryLogOutput,
 };
+use std::{fmt::Debug, sync::Arc};
 use thiserror::Error;
 use tokio::runtime::Handle;
 use tokio_util::sync::CancellationToken;
 use trace::TraceCollector;
 
+mod http;
 mod rpc;
 
 pub struct QuerierServerType<C: QuerierHandler> {
     database: Arc<QuerierDatabase>,
     server: QuerierServer<C>,
+    http: http::HttpDelegate<QuerierDatabase>,
     trace_collector: Option<Arc<dyn TraceCollector>>,
     authz: Option<Arc<dyn Authorizer>>,
+    self_monitoring_shutdown: CancellationToken,
 }
 
 impl<C: QuerierHandler> std::fmt::Debug for QuerierServerType<C> {
@@ -50,12 +54,15 @@ impl<C: QuerierHandler> QuerierServerType<C> {
         database: Arc<QuerierDatabase>,
         common_state: &CommonServerState,
         authz: Option<Arc<dyn Authorizer>>,
+        self_monitoring_shutdown: CancellationToken,
     ) -> Self {
         Self {
             server,
+            http: http::HttpDelegate::new(Arc::clone(&database), authz.as_ref().map(Arc::clone)),
             database,
             trace_collector: common_state.trace_collector(),
             authz,
+            self_monitoring_shutdown,
         }
     }
 }
@@ -77,12 +84,16 @@ impl<C: QuerierHandler + std::fmt::Debug + 'static> ServerType for QuerierServer
         self.trace_collector.as_ref().map(Arc::clone)
     }
 
-    /// Just return "not found".
+    /// Dispatches `req` to the querier [`HttpDelegate`](http::HttpDelegate)
+    /// handler.
     async fn route_http_request(
         &self,
-        _req: Request<Body>,
+        req: Request<Body>,
     ) -> Result<Response<Body>, Box<dyn HttpApiErrorSource>> {
-        Err(Box::new(IoxHttpError::NotFound))
+        self.http
+            .route(req)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn HttpApiErrorSource>)
     }
 
     /// Configure the gRPC services.
@@ -118,38 +129,11 @@ impl<C: QuerierHandler + std::fmt::Debug + 'static> ServerType for QuerierServer
 
     fn shutdown(&self, frontend: CancellationToken) {
         frontend.cancel();
+        self.self_monitoring_shutdown.cancel();
         self.server.shutdown();
     }
 }
 
-/// Simple error struct, we're not really providing an HTTP interface for the compactor.
-#[derive(Debug)]
-pub enum IoxHttpError {
-    NotFound,
-}
-
-impl IoxHttpError {
-    fn status_code(&self) -> HttpApiErrorCode {
-        match self {
-            IoxHttpError::NotFound => HttpApiErrorCode::NotFound,
-        }
-    }
-}
-
-impl Display for IoxHttpError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{self:?}")
-    }
-}
-
-impl std::error::Error for IoxHttpError {}
-
-impl HttpApiErrorSource for IoxHttpError {
-    fn to_http_api_error(&self) -> HttpApiError {
-        HttpApiError::new(self.status_code(), self.to_string())
-    }
-}
-
 /// Arguments required to create a [`ServerType`] for the querier.
 #[derive(Debug)]
 pub struct QuerierServerTypeArgs<'a> {
@@ -160,6 +144,7 @@ pub struct QuerierServerTypeArgs<'a> {
     pub exec: Arc<Executor>,
     pub time_provider: Arc<dyn TimeProvider>,
     pub querier_config: QuerierConfig,
+    pub self_monitoring_config: Option<SelfMonitoringConfig>,
 }
 
 #[derive(Debug, Error)]
@@ -174,6 +159,21 @@ pub enum Error {
     },
 }
 
+fn query_log_output(config: &QuerierConfig) -> QueryLogOutput {
+    match config.query_log_output {
+        QueryLogOutputType::None => QueryLogOutput::None,
+        QueryLogOutputType::Log => QueryLogOutput::Log,
+        QueryLogOutputType::File => QueryLogOutput::File {
+            path: config
+                .query_log_file
+                .clone()
+                .expect("--query-log-file is required for file output"),
+            max_bytes: config.query_log_file_max_bytes,
+            max_files: config.query_log_file_max_files,
+        },
+    }
+}
+
 /// Instantiate a querier server
 pub async fn create_querier_server_type(
     args: QuerierServerTypeArgs<'_>,
@@ -185,6 +185,9 @@ pub async fn create_querier_server_type(
         Arc::clone(&args.object_store),
         args.querier_config.ram_pool_metadata_bytes(),
         args.querier_config.ram_pool_data_bytes(),
+        args.querier_config.disk_cache_dir().cloned(),
+        args.querier_config.disk_pool_data_bytes(),
+        args.querier_config.cache_object_ranges(),
         &Handle::current(),
     ));
 
@@ -233,6 +236,11 @@ pub async fn create_querier_server_type(
         ))
     };
 
+    let query_log_output = query_log_output(&args.querier_config);
+    let max_query_runtime = MaxQueryRuntime {
+        default: args.querier_config.max_query_runtime,
+        namespaces: args.querier_config.namespace_max_query_runtime,
+    };
     let database = Arc::new(
         QuerierDatabase::new(
             catalog_cache,
@@ -241,8 +249,10 @@ pub async fn create_querier_server_type(
             ingester_connections,
             args.querier_config.max_concurrent_queries(),
             Arc::new(args.querier_config.datafusion_config),
+            query_log_output,
         )
-        .await?,
+        .await?
+        .with_max_query_runtime(max_query_runtime),
     );
     let querier_handler = Arc::new(QuerierHandlerImpl::new(
         args.catalog,
@@ -250,11 +260,21 @@ pub async fn create_querier_server_type(
         Arc::clone(&args.object_store),
     ));
 
+    let self_monitoring_shutdown = CancellationToken::new();
+    if let Some(self_monitor) = args
+        .self_monitoring_config
+        .as_ref()
+        .and_then(|c| SelfMonitor::new(c, Arc::clone(&args.metric_registry)))
+    {
+        self_monitor.spawn(self_monitoring_shutdown.clone());
+    }
+
     let querier = QuerierServer::new(args.metric_registry, querier_handler);
     Ok(Arc::new(QuerierServerType::new(
         querier,
         database,
         args.common_state,
         authz,
+        self_monitoring_shutdown,
     )))
 }
@@ -38,6 +38,10 @@ fn namespace_to_proto(namespace: Namespace) -> proto::Namespace {
         retention_period_ns: namespace.retention_period_ns,
         max_tables: namespace.max_tables,
         max_columns_per_table: namespace.max_columns_per_table,
+        partition_template: namespace.partition_template.as_ref().map(Into::into),
+        max_concurrent_queries: namespace.max_concurrent_queries,
+        max_query_memory_bytes: namespace.max_query_memory_bytes,
+        max_total_query_memory_bytes: namespace.max_total_query_memory_bytes,
     }
 }
 
@@ -76,6 +80,15 @@ impl proto::namespace_service_server::NamespaceService for NamespaceServiceImpl
         ))
     }
 
+    async fn undelete_namespace(
+        &self,
+        _request: tonic::Request<proto::UndeleteNamespaceRequest>,
+    ) -> Result<tonic::Response<proto::UndeleteNamespaceResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented(
+            "use router instances to manage namespaces",
+        ))
+    }
+
     async fn update_namespace_retention(
         &self,
         _request: tonic::Request<proto::UpdateNamespaceRetentionRequest>,
@@ -94,6 +107,42 @@ impl proto::namespace_service_server::NamespaceService for NamespaceServiceImpl
             "use router instances to manage namespaces",
         ))
     }
+
+    async fn delete_table(
+        &self,
+        _request: tonic::Request<proto::DeleteTableRequest>,
+    ) -> Result<tonic::Response<proto::DeleteTableResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented(
+            "use router instances to manage namespaces",
+        ))
+    }
+
+    async fn undelete_table(
+        &self,
+        _request: tonic::Request<proto::UndeleteTableRequest>,
+    ) -> Result<tonic::Response<proto::UndeleteTableResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented(
+            "use router instances to manage namespaces",
+        ))
+    }
+
+    async fn delete_column(
+        &self,
+        _request: tonic::Request<proto::DeleteColumnRequest>,
+    ) -> Result<tonic::Response<proto::DeleteColumnResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented(
+            "use router instances to manage namespaces",
+        ))
+    }
+
+    async fn undelete_column(
+        &self,
+        _request: tonic::Request<proto::UndeleteColumnRequest>,
+    ) -> Result<tonic::Response<proto::UndeleteColumnResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented(
+            "use router instances to manage namespaces",
+        ))
+    }
 }
 
 #[cfg(test)]
@@ -103,7 +152,7 @@ mod tests {
     use super::*;
     use generated_types::influxdata::iox::namespace::v1::namespace_service_server::NamespaceService;
     use iox_tests::TestCatalog;
-    use querier::{create_ingester_connection_for_testing, QuerierCatalogCache};
+    use querier::{create_ingester_connection_for_testing, QuerierCatalogCache, QueryLogOutput};
     use tokio::runtime::Handle;
 
     use iox_catalog::{
@@ -133,6 +182,7 @@ mod tests {
                 Some(create_ingester_connection_for_testing()),
                 QuerierDatabase::MAX_CONCURRENT_QUERIES_MAX,
                 Arc::new(HashMap::default()),
+                QueryLogOutput::default(),
             )
             .await
             .unwrap(),
@@ -166,6 +216,7 @@ mod tests {
                 Some(create_ingester_connection_for_testing()),
                 QuerierDatabase::MAX_CONCURRENT_QUERIES_MAX,
                 Arc::new(HashMap::default()),
+                QueryLogOutput::default(),
             )
             .await
             .unwrap(),
@@ -186,6 +237,10 @@ mod tests {
                         retention_period_ns: TEST_RETENTION_PERIOD_NS,
                         max_tables: TEST_MAX_TABLES,
                         max_columns_per_table: TEST_MAX_COLUMNS_PER_TABLE,
+                        partition_template: None,
+                        max_concurrent_queries: None,
+                        max_query_memory_bytes: None,
+                        max_total_query_memory_bytes: None,
                     },
                     proto::Namespace {
                         id: 2,
@@ -193,6 +248,10 @@ mod tests {
                         retention_period_ns: TEST_RETENTION_PERIOD_NS,
                         max_tables: TEST_MAX_TABLES,
                         max_columns_per_table: TEST_MAX_COLUMNS_PER_TABLE,
+                        partition_template: None,
+                        max_concurrent_queries: None,
+                        max_query_memory_bytes: None,
+                        max_total_query_memory_bytes: None,
                     },
                 ]
             }
@@ -6,7 +6,7 @@ use std::{
 use async_trait::async_trait;
 use authz::{Authorizer, IoxAuthorizer};
 use clap_blocks::router::RouterConfig;
-use data_types::{DefaultPartitionTemplate, NamespaceName};
+use data_types::{DefaultPartitionTemplate, NamespaceName, NamespaceNameError};
 use hashbrown::HashMap;
 use hyper::{Body, Request, Response};
 use iox_catalog::interface::Catalog;
@@ -17,6 +17,7 @@ use ioxd_common::{
         generated_types::influxdata::iox::{
             catalog::v1::catalog_service_server, namespace::v1::namespace_service_server,
             object_store::v1::object_store_service_server, schema::v1::schema_service_server,
+            table::v1::table_service_server,
         },
         tonic::transport::Endpoint,
     },
@@ -32,14 +33,16 @@ use router::{
     dml_handlers::{
         lazy_connector::LazyConnector, DmlHandler, DmlHandlerChainExt, FanOutAdaptor,
         InstrumentationDecorator, Partitioner, RetentionValidator, RpcWrite, SchemaValidator,
+        TombstoneRecorder,
     },
     namespace_cache::{
         metrics::InstrumentedCache, MemoryNamespaceCache, NamespaceCache, ReadThroughCache,
-        ShardedCache,
+        SchemaCacheRefresher, ShardedCache,
     },
     namespace_resolver::{
         MissingNamespaceAction, NamespaceAutocreation, NamespaceResolver, NamespaceSchemaResolver,
     },
+    self_monitoring::SelfMonitor,
     server::{
         grpc::RpcWriteGrpcDelegate,
         http::{
@@ -69,6 +72,9 @@ pub enum Error {
         source: Box<dyn std::error::Error>,
         addr: String,
     },
+
+    #[error("invalid self-monitoring namespace: {0}")]
+    SelfMonitoringNamespace(#[from] NamespaceNameError),
 }
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
@@ -155,6 +161,10 @@ where
                 self.server.grpc().namespace_service()
             )
         );
+        add_service!(
+            builder,
+            table_service_server::TableServiceServer::new(self.server.grpc().table_service())
+        );
         serve_builder!(builder);
 
         Ok(())
@@ -213,11 +223,14 @@ pub async fn create_router_server_type(
     });
 
     // Initialise the DML handler that sends writes to the ingester using the RPC write path.
-    let rpc_writer = RpcWrite::new(
+    let mut rpc_writer = RpcWrite::new(
         ingester_connections,
         router_config.rpc_write_replicas,
         &metrics,
     );
+    if router_config.rpc_write_partition_affinity {
+        rpc_writer = rpc_writer.with_partition_affinity();
+    }
     let rpc_writer = InstrumentationDecorator::new("rpc_writer", &metrics, rpc_writer);
 
     // # Namespace cache
@@ -239,6 +252,11 @@ pub async fn create_router_server_type(
         .await
         .expect("namespace cache pre-warming failed");
 
+    // Periodically replace the cached schemas, dropping tables and columns
+    // soft-deleted since they were cached.
+    let schema_cache_refresher =
+        SchemaCacheRefresher::new(Arc::clone(&ns_cache), Arc::clone(&catalog));
+
     // # Schema validator
     //
     // Initialise and instrument the schema validator
@@ -254,6 +272,17 @@ pub async fn create_router_server_type(
     let retention_validator =
         InstrumentationDecorator::new("retention_validator", &metrics, retention_validator);
 
+    // # Tombstone recorder
+    //
+    // Record deletes as tombstones in the catalog, from where they are applied
+    // at query time and by the compactor.
+    //
+    // This MUST be the last handler in the stack, so that deletes are applied
+    // to the data buffered by the ingesters before the tombstone is recorded.
+    let tombstone_recorder = TombstoneRecorder::new(Arc::clone(&catalog));
+    let tombstone_recorder =
+        InstrumentationDecorator::new("tombstone_recorder", &metrics, tombstone_recorder);
+
     // # Write partitioner
     //
     // Add a write partitioner into the handler stack that splits by the date
@@ -266,7 +295,7 @@ pub async fn create_router_server_type(
     // Initialise the Namespace ID lookup + cache
     let namespace_resolver = NamespaceSchemaResolver::new(Arc::clone(&ns_cache));
 
-    let namespace_resolver = NamespaceAutocreation::new(
+    let namespace_resolver = Arc::new(NamespaceAutocreation::new(
         namespace_resolver,
         Arc::clone(&ns_cache),
         Arc::clone(&catalog),
@@ -281,7 +310,7 @@ pub async fn create_router_server_type(
                 MissingNamespaceAction::Reject
             }
         },
-    );
+    ));
     //
     ////////////////////////////////////////////////////////////////////////////
 
@@ -304,10 +333,40 @@ pub async fn create_router_server_type(
             "parallel_write",
             &metrics,
             parallel_write,
-        ));
+        ))
+        .and_then(tombstone_recorder);
 
     // Record the overall request handling latency
-    let handler_stack = InstrumentationDecorator::new("request", &metrics, handler_stack);
+    let handler_stack = Arc::new(InstrumentationDecorator::new(
+        "request",
+        &metrics,
+        handler_stack,
+    ));
+
+    // # Self-monitoring
+    //
+    // Optionally write the metrics of this process into a namespace through
+    // the same handler stack as HTTP writes.
+    //
+    // The other server types write their metrics through the HTTP write API
+    // of a router. In all-in-one mode `metrics` is shared by all the services,
+    // so their metrics are exported here instead.
+    let self_monitor = match router_config.self_monitoring_interval_seconds {
+        Some(interval) => {
+            let namespace =
+                NamespaceName::try_from(router_config.self_monitoring_namespace.clone())?;
+            Some((
+                SelfMonitor::new(
+                    namespace,
+                    Arc::clone(&metrics),
+                    Arc::clone(&namespace_resolver),
+                    Arc::clone(&handler_stack),
+                ),
+                interval,
+            ))
+        }
+        None => None,
+    };
 
     // Initialize the HTTP API delegate
     let write_request_unifier: Result<Box<dyn WriteRequestUnifier>> = match (
@@ -355,8 +414,28 @@ pub async fn create_router_server_type(
 
     let router_server =
         RpcWriteRouterServer::new(http, grpc, metrics, common_state.trace_collector());
-    let server_type = Arc::new(RpcWriteRouterServerType::new(router_server, common_state));
-    Ok(server_type)
+    let server_type = RpcWriteRouterServerType::new(router_server, common_state);
+
+    let refresh_interval = router_config.namespace_cache_refresh_interval_seconds;
+    let shutdown = server_type.shutdown.clone();
+    tokio::spawn(async move {
+        tokio::select! {
+            _ = schema_cache_refresher.run(refresh_interval) => {},
+            _ = shutdown.cancelled() => {},
+        }
+    });
+
+    if let Some((self_monitor, interval)) = self_monitor {
+        let shutdown = server_type.shutdown.clone();
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = self_monitor.run(interval) => {},
+                _ = shutdown.cancelled() => {},
+            }
+        });
+    }
+
+    Ok(Arc::new(server_type))
 }
 
 /// Pre-populate `cache` with the all existing schemas in `catalog`.
@@ -391,7 +470,11 @@ mod tests {
         let catalog = Arc::new(MemCatalog::new(Default::default()));
 
         let mut repos = catalog.repositories().await;
-        let namespace = repos.namespaces().create("test_ns", None).await.unwrap();
+        let namespace = repos
+            .namespaces()
+            .create("test_ns", None, None)
+            .await
+            .unwrap();
 
         let table = repos
             .tables()
@@ -18,6 +18,9 @@ use prometheus::{
     Encoder, TextEncoder,
 };
 
+mod line_protocol;
+pub use line_protocol::LineProtocolEncoder;
+
 /// A `metric::Reporter` that writes data in the prometheus text exposition format
 ///
 /// In order to comply with the prometheus naming best-practices, certain metrics may have
@@ -0,0 +1,172 @@
+use influxdb_line_protocol::{
+    builder::{AfterField, AfterMeasurement},
+    LineProtocolBuilder,
+};
+use metric::{Attributes, HistogramObservation, MetricKind, Observation};
+use std::time::Duration;
+
+type Builder<'a> = LineProtocolBuilder<&'a mut Vec<u8>, AfterMeasurement>;
+type FieldBuilder<'a> = LineProtocolBuilder<&'a mut Vec<u8>, AfterField>;
+
+/// A `metric::Reporter` that writes a snapshot of every observation as a line of
+/// [line protocol], all sharing the same timestamp
+///
+/// Each metric is written to a measurement of the same name, with the attributes of an
+/// observation as tags. Counters and gauges are written to a single `value` field, as an
+/// unsigned integer, or as a float number of seconds for durations.
+///
+/// Histograms are written as one line per bucket with an `le` tag holding the upper bound
+/// of the bucket (or `+Inf`) and a cumulative `count` field, followed by a line without the
+/// `le` tag holding the total `count` and `sum` of the observations.
+///
+/// Attributes with an empty value are omitted, as line protocol does not permit empty tag
+/// values.
+///
+/// [line protocol]: https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol
+#[derive(Debug)]
+pub struct LineProtocolEncoder<'a> {
+    metric_name: Option<&'static str>,
+    timestamp: i64,
+    buf: &'a mut Vec<u8>,
+}
+
+impl<'a> LineProtocolEncoder<'a> {
+    /// Create an encoder appending lines with the given timestamp, in nanoseconds since
+    /// the epoch, to `buf`.
+    pub fn new(buf: &'a mut Vec<u8>, timestamp: i64) -> Self {
+        Self {
+            metric_name: None,
+            timestamp,
+            buf,
+        }
+    }
+
+    fn write_line<F>(&mut self, attributes: &Attributes, le: Option<&str>, fields: F)
+    where
+        F: for<'b> FnOnce(Builder<'b>) -> FieldBuilder<'b>,
+    {
+        let metric_name = self.metric_name.expect("no metric in progress");
+
+        let mut builder = LineProtocolBuilder::new_with(&mut *self.buf).measurement(metric_name);
+        for (key, value) in attributes.iter().filter(|(_, v)| !v.is_empty()) {
+            builder = builder.tag(key, value);
+        }
+        if let Some(le) = le {
+            builder = builder.tag("le", le);
+        }
+
+        fields(builder).timestamp(self.timestamp).close_line();
+    }
+
+    fn write_histogram<T: Copy>(
+        &mut self,
+        attributes: &Attributes,
+        histogram: HistogramObservation<T>,
+        le: impl Fn(T) -> String,
+        sum: impl FnOnce(T) -> f64,
+    ) {
+        let mut cumulative_count = 0;
+        for bucket in &histogram.buckets {
+            cumulative_count += bucket.count;
+            self.write_line(attributes, Some(&le(bucket.le)), |b| {
+                b.field("count", cumulative_count)
+            });
+        }
+
+        let total = sum(histogram.total);
+        self.write_line(attributes, None, |b| {
+            b.field("count", cumulative_count).field("sum", total)
+        });
+    }
+}
+
+impl<'a> metric::Reporter for LineProtocolEncoder<'a> {
+    fn start_metric(
+        &mut self,
+        metric_name: &'static str,
+        _description: &'static str,
+        _kind: MetricKind,
+    ) {
+        assert!(self.metric_name.is_none(), "metric already in progress");
+        self.metric_name = Some(metric_name);
+    }
+
+    fn report_observation(&mut self, attributes: &Attributes, observation: Observation) {
+        match observation {
+            Observation::U64Counter(v) | Observation::U64Gauge(v) => {
+                self.write_line(attributes, None, |b| b.field("value", v))
+            }
+            Observation::DurationCounter(v) | Observation::DurationGauge(v) => {
+                self.write_line(attributes, None, |b| b.field("value", v.as_secs_f64()))
+            }
+            Observation::U64Histogram(v) => self.write_histogram(
+                attributes,
+                v,
+                |le| match le {
+                    u64::MAX => "+Inf".to_string(),
+                    le => le.to_string(),
+                },
+                |total| total as f64,
+            ),
+            Observation::DurationHistogram(v) => self.write_histogram(
+                attributes,
+                v,
+                |le: Duration| match le {
+                    metric::DURATION_MAX => "+Inf".to_string(),
+                    le => le.as_secs_f64().to_string(),
+                },
+                |total| total.as_secs_f64(),
+            ),
+        }
+    }
+
+    fn finish_metric(&mut self) {
+        self.metric_name = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metric::{DurationGauge, Metric, Registry, U64Counter, U64Histogram, U64HistogramOptions};
+
+    #[test]
+    fn test_encode() {
+        let registry = Registry::new();
+
+        let counter: Metric<U64Counter> = registry.register_metric("foo", "a counter metric");
+        counter.recorder(&[("tag1", "value"), ("tag2", "")]).inc(5);
+
+        let histogram: Metric<U64Histogram> =
+            registry.register_metric_with_options("bar", "a histogram metric", || {
+                U64HistogramOptions::new([5, 10])
+            });
+        let recorder = histogram.recorder(&[("tag1", "a value")]);
+        recorder.record(3);
+        recorder.record(40);
+
+        let duration: Metric<DurationGauge> =
+            registry.register_metric("duration_gauge", "a duration gauge");
+        duration
+            .recorder(&[("tag1", "value1")])
+            .set(Duration::from_millis(100));
+
+        let mut buffer = Vec::new();
+        let mut encoder = LineProtocolEncoder::new(&mut buffer, 42);
+        registry.report(&mut encoder);
+
+        let buffer = String::from_utf8(buffer).unwrap();
+
+        let expected = r#"
+bar,tag1=a\ value,le=5 count=1u 42
+bar,tag1=a\ value,le=10 count=1u 42
+bar,tag1=a\ value,le=+Inf count=2u 42
+bar,tag1=a\ value count=2u,sum=43 42
+duration_gauge,tag1=value1 value=0.1 42
+foo,tag1=value value=5u 42
+"#
+        .trim_start();
+
+        assert_eq!(&buffer, expected, "{buffer}");
+    }
+}
@@ -53,6 +53,9 @@ pub enum Error {
 
     #[snafu(context(false))]
     WriterError { source: writer::Error },
+
+    #[snafu(display("Invalid time format in partition template"))]
+    InvalidPartitionTemplate,
 }
 
 /// A specialized `Error` for [`MutableBatch`] errors
@@ -98,15 +98,18 @@ impl<'a> PartitionWrite<'a> {
 
     /// Create a collection of [`PartitionWrite`] indexed by partition key
     /// from a [`MutableBatch`] and [`PartitionTemplate`]
+    ///
+    /// Returns an error if the partition keys cannot be rendered from `partition_template`.
     pub fn partition(
         batch: &'a MutableBatch,
         partition_template: &PartitionTemplate,
-    ) -> HashMap<PartitionKey, Self> {
+    ) -> Result<HashMap<PartitionKey, Self>> {
         use hashbrown::hash_map::Entry;
         let time = get_time_column(batch);
 
         let mut partition_ranges = HashMap::new();
-        for (partition, range) in partition::partition_batch(batch, partition_template) {
+        for partitioned in partition::partition_batch(batch, partition_template) {
+            let (partition, range) = partitioned?;
             let row_count = NonZeroUsize::new(range.end - range.start).unwrap();
             let (min_timestamp, max_timestamp) = min_max_time(&time[range.clone()]);
 
@@ -129,7 +132,7 @@ impl<'a> PartitionWrite<'a> {
                 }
             }
         }
-        partition_ranges
+        Ok(partition_ranges)
     }
 }
 
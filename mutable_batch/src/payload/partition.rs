@@ -4,7 +4,7 @@
 
 use crate::{
     column::{Column, ColumnData},
-    MutableBatch,
+    InvalidPartitionTemplateSnafu, MutableBatch, Result,
 };
 use chrono::{format::StrftimeItems, TimeZone, Utc};
 use data_types::{PartitionTemplate, TemplatePart};
@@ -12,11 +12,17 @@ use schema::TIME_COLUMN_NAME;
 use std::ops::Range;
 
 /// Returns an iterator identifying consecutive ranges for a given partition key
+///
+/// Yields an error if a partition key cannot be rendered, e.g. because the template contains an
+/// invalid strftime format.
 pub fn partition_batch<'a>(
     batch: &'a MutableBatch,
     template: &'a PartitionTemplate,
-) -> impl Iterator<Item = (String, Range<usize>)> + 'a {
-    range_encode(partition_keys(batch, template))
+) -> impl Iterator<Item = Result<(String, Range<usize>)>> + 'a {
+    range_encode(partition_keys(batch, template)).map(|(key, range)| match key {
+        Ok(key) => Ok((key, range)),
+        Err(std::fmt::Error) => InvalidPartitionTemplateSnafu.fail(),
+    })
 }
 
 /// A [`PartitionTemplate`] is made up of one of more [`TemplatePart`] that are rendered and
@@ -73,7 +79,7 @@ impl<'a> Template<'a> {
 fn partition_keys<'a>(
     batch: &'a MutableBatch,
     template: &'a PartitionTemplate,
-) -> impl Iterator<Item = String> + 'a {
+) -> impl Iterator<Item = Result<String, std::fmt::Error>> + 'a {
     let time = batch.column(TIME_COLUMN_NAME).expect("time column");
     let time = match &time.data {
         ColumnData::I64(col_data, _) => col_data.as_slice(),
@@ -95,14 +101,14 @@ fn partition_keys<'a>(
     (0..batch.row_count).map(move |idx| {
         let mut string = String::new();
         for (col_idx, col) in cols.iter().enumerate() {
-            col.fmt_row(&mut string, idx)
-                .expect("string writing is infallible");
+            // Writing to a string is infallible, but rendering an invalid time format is not
+            col.fmt_row(&mut string, idx)?;
 
             if col_idx + 1 != cols.len() {
                 string.push('-');
             }
         }
-        string
+        Ok(string)
     })
 }
 
@@ -213,7 +219,9 @@ mod tests {
 
         writer.commit();
 
-        let keys: Vec<_> = partition_keys(&batch, &template).collect();
+        let keys: Vec<_> = partition_keys(&batch, &template)
+            .collect::<Result<_, _>>()
+            .unwrap();
 
         assert_eq!(
             keys,
@@ -226,4 +234,21 @@ mod tests {
             ]
         )
     }
+
+    #[test]
+    fn test_partition_invalid_time_format() {
+        let mut batch = MutableBatch::new();
+        let mut writer = Writer::new(&mut batch, 1);
+        writer.write_time("time", vec![1].into_iter()).unwrap();
+        writer.commit();
+
+        let template = PartitionTemplate {
+            parts: vec![TemplatePart::TimeFormat("%Q".to_string())],
+        };
+
+        let err = partition_batch(&batch, &template)
+            .collect::<Result<Vec<_>>>()
+            .unwrap_err();
+        assert!(matches!(err, crate::Error::InvalidPartitionTemplate));
+    }
 }
@@ -35,8 +35,24 @@ pub enum Error {
     #[snafu(display("empty write payload"))]
     EmptyPayload,
 
-    #[snafu(display("timestamp overflows i64"))]
-    TimestampOverflow,
+    #[snafu(display("timestamp overflows i64 on line {}", line))]
+    TimestampOverflow { line: usize },
+
+    #[snafu(display("line {} rejected: {}", line, reason))]
+    Rejected { line: usize, reason: String },
+}
+
+impl Error {
+    /// The 1-based number of the line that caused this error, if any.
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            Self::LineProtocol { line, .. }
+            | Self::Write { line, .. }
+            | Self::TimestampOverflow { line }
+            | Self::Rejected { line, .. } => Some(*line),
+            Self::EmptyPayload => None,
+        }
+    }
 }
 
 /// Result type for line protocol conversion
@@ -101,37 +117,88 @@ impl LinesConverter {
     ///
     pub fn write_lp(&mut self, lines: &str) -> Result<()> {
         for (line_idx, maybe_line) in parse_lines(lines).enumerate() {
-            let mut line = maybe_line.context(LineProtocolSnafu { line: line_idx + 1 })?;
+            self.write_parsed_line(line_idx + 1, maybe_line, |_| Ok(()))?;
+        }
+        Ok(())
+    }
 
-            if let Some(t) = line.timestamp.as_mut() {
-                *t = t
-                    .checked_mul(self.timestamp_base)
-                    .ok_or(Error::TimestampOverflow)?;
-            }
+    /// Write some line protocol data, skipping any line that cannot be
+    /// written instead of stopping at the first error.
+    ///
+    /// Each successfully parsed line is passed to `filter` before it is
+    /// written, with its timestamp already converted to nanoseconds. Lines
+    /// for which `filter` returns an error are skipped and reported as
+    /// [`Error::Rejected`].
+    ///
+    /// Returns the errors of all skipped lines, in line order. The data of
+    /// skipped lines is never written to the batches, and skipped lines are
+    /// not included in the [`PayloadStatistics`].
+    pub fn write_lp_partial<F>(&mut self, lines: &str, mut filter: F) -> Vec<Error>
+    where
+        F: FnMut(&ParsedLine<'_>) -> Result<(), String>,
+    {
+        parse_lines(lines)
+            .enumerate()
+            .filter_map(|(line_idx, maybe_line)| {
+                self.write_parsed_line(line_idx + 1, maybe_line, &mut filter)
+                    .err()
+            })
+            .collect()
+    }
 
-            self.stats.num_lines += 1;
-            self.stats.num_fields += line.field_set.len();
+    /// Write a single `line` (numbered from 1) to the batch for its
+    /// measurement, if it parsed successfully and is accepted by `filter`.
+    ///
+    /// The batch is left unchanged if an error is returned.
+    fn write_parsed_line<F>(
+        &mut self,
+        line_number: usize,
+        maybe_line: Result<ParsedLine<'_>, influxdb_line_protocol::Error>,
+        filter: F,
+    ) -> Result<()>
+    where
+        F: FnOnce(&ParsedLine<'_>) -> Result<(), String>,
+    {
+        let mut line = maybe_line.context(LineProtocolSnafu { line: line_number })?;
+
+        if let Some(t) = line.timestamp.as_mut() {
+            *t = t
+                .checked_mul(self.timestamp_base)
+                .ok_or(Error::TimestampOverflow { line: line_number })?;
+        }
 
-            let measurement = line.series.measurement.as_str();
+        filter(&line).map_err(|reason| Error::Rejected {
+            line: line_number,
+            reason,
+        })?;
 
-            let (_, batch) = self
-                .batches
-                .raw_entry_mut()
-                .from_key(measurement)
-                .or_insert_with(|| (measurement.to_string(), MutableBatch::new()));
+        let measurement = line.series.measurement.as_str();
+
+        let (_, batch) = self
+            .batches
+            .raw_entry_mut()
+            .from_key(measurement)
+            .or_insert_with(|| (measurement.to_string(), MutableBatch::new()));
+
+        // TODO: Reuse writer
+        let mut writer = Writer::new(batch, 1);
+        write_line(&mut writer, &line, self.default_time)
+            .context(WriteSnafu { line: line_number })?;
+        writer.commit();
+
+        self.stats.num_lines += 1;
+        self.stats.num_fields += line.field_set.len();
 
-            // TODO: Reuse writer
-            let mut writer = Writer::new(batch, 1);
-            write_line(&mut writer, &line, self.default_time)
-                .context(WriteSnafu { line: line_idx + 1 })?;
-            writer.commit();
-        }
         Ok(())
     }
 
     /// Consume this [`LinesConverter`] returning the [`MutableBatch`]
     /// and the [`PayloadStatistics`] for the written data
-    pub fn finish(self) -> Result<(HashMap<String, MutableBatch>, PayloadStatistics)> {
+    pub fn finish(mut self) -> Result<(HashMap<String, MutableBatch>, PayloadStatistics)> {
+        // A batch is created before its first line is written, and is left
+        // empty if every line for the measurement was skipped.
+        self.batches.retain(|_, batch| batch.rows() > 0);
+
         match self.batches.is_empty() {
             false => Ok((self.batches, self.stats)),
             true => Err(Error::EmptyPayload),
@@ -445,6 +512,58 @@ m b=t 1639612800000000000
         assert!(!u.is_valid(2));
     }
 
+    #[test]
+    fn test_write_lp_partial() {
+        let lp = r#"cpu,host=a v=1i 1
+cpu,host=b v=1.0 2
+not line protocol
+mem v=1i 3
+cpu,host=c v=2i 4
+        "#;
+
+        let mut converter = LinesConverter::new(5);
+        let errors =
+            converter.write_lp_partial(lp, |line| match line.series.measurement.as_str() {
+                "mem" => Err("no mem allowed".to_string()),
+                _ => Ok(()),
+            });
+
+        assert_eq!(errors.len(), 3);
+        assert_matches!(
+            errors[0],
+            Error::Write {
+                source: LineWriteError::MutableBatch { .. },
+                line: 2
+            }
+        );
+        assert_matches!(errors[1], Error::LineProtocol { line: 3, .. });
+        assert_matches!(&errors[2], Error::Rejected { line: 4, reason } => {
+            assert_eq!(reason, "no mem allowed");
+        });
+        assert_eq!(
+            errors.iter().map(Error::line).collect::<Vec<_>>(),
+            [Some(2), Some(3), Some(4)]
+        );
+
+        let (batches, stats) = converter.finish().unwrap();
+        assert_eq!(stats.num_lines, 2);
+        assert_eq!(stats.num_fields, 2);
+
+        // The batch for "mem" contains no rows, and is not returned.
+        assert_eq!(batches.len(), 1);
+        assert_batches_eq!(
+            &[
+                "+------+--------------------------------+---+",
+                "| host | time                           | v |",
+                "+------+--------------------------------+---+",
+                "| a    | 1970-01-01T00:00:00.000000001Z | 1 |",
+                "| c    | 1970-01-01T00:00:00.000000004Z | 2 |",
+                "+------+--------------------------------+---+",
+            ],
+            &[batches["cpu"].to_arrow(Projection::All).unwrap()]
+        );
+    }
+
     // https://github.com/influxdata/influxdb_iox/issues/4326
     mod issue4326 {
         use super::*;
@@ -0,0 +1,495 @@
+//! Row group selection using the parquet bloom filters of tag columns.
+//!
+//! The min/max statistics used to prune chunks are of little help for
+//! equality predicates on high-cardinality tags, as almost every file spans a
+//! wide range of tag values. Bloom filters answer "is this value definitely
+//! absent?" instead, which allows files and row groups to be skipped for
+//! predicates such as `host = 'a'` or `host IN ('a', 'b')`.
+//!
+//! Tags are part of the primary key, so skipping row groups that contain no
+//! rows matching a tag predicate never changes the result of deduplication.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use bytes::{Buf, Bytes, BytesMut};
+use datafusion::{
+    datasource::listing::FileRange,
+    logical_expr::{BinaryExpr, Expr, Operator},
+    optimizer::utils::split_conjunction,
+    scalar::ScalarValue,
+};
+use object_store::{path::Path, ObjectStore};
+use parquet::{
+    data_type::ByteArray,
+    errors::ParquetError,
+    file::{
+        footer::{decode_footer, decode_metadata},
+        metadata::RowGroupMetaData,
+        properties::ReaderProperties,
+        reader::{ChunkReader, FileReader, Length, RowGroupReader},
+        serialized_reader::{ReadOptionsBuilder, SerializedFileReader},
+        FOOTER_SIZE,
+    },
+};
+use predicate::Predicate;
+use thiserror::Error;
+
+/// The values a query requires of each column, extracted from the equality
+/// and `IN` list expressions of a [`Predicate`].
+///
+/// A row can only match the predicate if, for every column, it holds one of
+/// the listed values.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TagValues(BTreeMap<String, BTreeSet<String>>);
+
+impl TagValues {
+    /// Extract the string values required by the conjunction of expressions
+    /// in `predicate`.
+    ///
+    /// Expressions that are not of the form `col = 'value'` or
+    /// `col IN ('a', 'b')` are ignored, as are expressions nested within a
+    /// disjunction or negation.
+    pub fn from_predicate(predicate: &Predicate) -> Self {
+        let mut values = BTreeMap::<String, BTreeSet<String>>::new();
+
+        for expr in predicate.exprs.iter().flat_map(split_conjunction) {
+            let Some((column, expr_values)) = column_values(expr) else {
+                continue;
+            };
+
+            // Multiple expressions on the same column are ANDed, so only the
+            // values permitted by all of them can match.
+            match values.get_mut(&column) {
+                Some(existing) => existing.retain(|v| expr_values.contains(v)),
+                None => {
+                    values.insert(column, expr_values);
+                }
+            }
+        }
+
+        Self(values)
+    }
+
+    /// Returns true if the predicate places no requirement on any column
+    /// value.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the values required of `column`, if any.
+    pub fn get(&self, column: &str) -> Option<&BTreeSet<String>> {
+        self.0.get(column)
+    }
+}
+
+/// Returns the column and set of string values `expr` requires it to hold.
+fn column_values(expr: &Expr) -> Option<(String, BTreeSet<String>)> {
+    match expr {
+        Expr::BinaryExpr(BinaryExpr {
+            left,
+            op: Operator::Eq,
+            right,
+        }) => match (left.as_ref(), right.as_ref()) {
+            (Expr::Column(c), Expr::Literal(v)) | (Expr::Literal(v), Expr::Column(c)) => {
+                Some((c.name.clone(), BTreeSet::from([string_value(v)?])))
+            }
+            _ => None,
+        },
+        Expr::InList {
+            expr,
+            list,
+            negated: false,
+        } => {
+            let Expr::Column(c) = expr.as_ref() else {
+                return None;
+            };
+            let values = list
+                .iter()
+                .map(|e| match e {
+                    Expr::Literal(v) => string_value(v),
+                    _ => None,
+                })
+                .collect::<Option<BTreeSet<_>>>()?;
+            Some((c.name.clone(), values))
+        }
+        _ => None,
+    }
+}
+
+fn string_value(v: &ScalarValue) -> Option<String> {
+    match v {
+        ScalarValue::Utf8(Some(s)) | ScalarValue::LargeUtf8(Some(s)) => Some(s.clone()),
+        ScalarValue::Dictionary(_, v) => string_value(v),
+        _ => None,
+    }
+}
+
+/// The row groups of a parquet file that may contain rows matching a set of
+/// [`TagValues`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowGroupSelection {
+    /// Whether each row group may contain matching rows.
+    keep: Vec<bool>,
+
+    /// The `[start, end)` byte range of each row group within the file.
+    spans: Vec<(i64, i64)>,
+}
+
+impl RowGroupSelection {
+    /// Returns true if no row group can contain matching rows, and therefore
+    /// the file can be skipped entirely.
+    pub fn is_empty(&self) -> bool {
+        !self.keep.iter().any(|keep| *keep)
+    }
+
+    /// Returns the byte range of the file that covers all selected row groups,
+    /// or [`None`] if every row group is selected (or none are).
+    ///
+    /// A row group is read by DataFusion if its offset falls within the range,
+    /// so the returned range spans from the start of the first selected row
+    /// group to the end of the last. Unselected row groups in between these
+    /// are still read.
+    pub fn file_range(&self) -> Option<FileRange> {
+        if self.keep.iter().all(|keep| *keep) {
+            return None;
+        }
+
+        let first = self.keep.iter().position(|keep| *keep)?;
+        let last = self.keep.iter().rposition(|keep| *keep)?;
+
+        Some(FileRange {
+            start: self.spans[first].0,
+            end: self.spans[last].1 + 1,
+        })
+    }
+}
+
+/// The number of bytes read from the end of a parquet file to find its
+/// metadata.
+///
+/// This matches the amount read by the [`SerializedFileReader`], so the reader
+/// never needs bytes before those fetched.
+const FOOTER_READ_SIZE: usize = 64 * 1024;
+
+/// Errors reading the bloom filters of a parquet file.
+#[derive(Debug, Error)]
+pub enum BloomFilterError {
+    /// Fetching a byte range of the file from the object store failed.
+    #[error("failed to read parquet file: {0}")]
+    ObjectStore(#[from] object_store::Error),
+
+    /// Decoding the parquet metadata or a bloom filter failed.
+    #[error("failed to decode parquet file: {0}")]
+    Parquet(#[from] ParquetError),
+}
+
+/// Evaluate the bloom filters of the parquet file of `file_size` bytes at
+/// `path` against `values`, returning the row groups that may contain rows
+/// matching them.
+///
+/// Only the metadata and the bloom filters are read from `store`, which are
+/// both written at the end of the file, after the row group data.
+///
+/// Row groups without a bloom filter for a constrained column are always
+/// selected.
+pub async fn select_row_groups(
+    store: &dyn ObjectStore,
+    path: &Path,
+    file_size: usize,
+    values: &TagValues,
+) -> Result<RowGroupSelection, BloomFilterError> {
+    // Read the footer, and the metadata if it fits within the same range.
+    let mut start = file_size.saturating_sub(FOOTER_READ_SIZE);
+    let mut tail = store.get_range(path, start..file_size).await?;
+    if tail.len() < FOOTER_SIZE {
+        return Err(ParquetError::EOF("file too small to be a parquet file".to_string()).into());
+    }
+
+    let footer: [u8; FOOTER_SIZE] = tail[tail.len() - FOOTER_SIZE..]
+        .try_into()
+        .expect("footer slice has the footer size");
+    let metadata_len = decode_footer(&footer)?;
+    let metadata_start = file_size
+        .checked_sub(FOOTER_SIZE + metadata_len)
+        .ok_or_else(|| ParquetError::EOF("metadata exceeds the file size".to_string()))?;
+
+    // Extend the read to the start of the metadata, and then to the first
+    // bloom filter, both of which precede the footer.
+    //
+    // The reader reads the bloom filters of all columns, not only those with
+    // required values.
+    let metadata = read_from(store, path, metadata_start, &mut start, &mut tail).await?;
+    let metadata = decode_metadata(&metadata[..metadata_len])?;
+    let bloom_filter_start = metadata
+        .row_groups()
+        .iter()
+        .flat_map(|row_group| row_group.columns())
+        .filter_map(|column| column.bloom_filter_offset())
+        .min();
+    if let Some(offset) = bloom_filter_start {
+        let offset = usize::try_from(offset)
+            .map_err(|_| ParquetError::General("invalid bloom filter offset".to_string()))?;
+        read_from(store, path, offset, &mut start, &mut tail).await?;
+    }
+
+    select_from_reader(FileTail { start, data: tail }, values)
+}
+
+/// Extend `tail`, holding the bytes of the file from `start` onwards, to begin
+/// at `offset` (if it does not already), returning the bytes from `offset`.
+async fn read_from(
+    store: &dyn ObjectStore,
+    path: &Path,
+    offset: usize,
+    start: &mut usize,
+    tail: &mut Bytes,
+) -> Result<Bytes, object_store::Error> {
+    if offset < *start {
+        let head = store.get_range(path, offset..*start).await?;
+        let mut buf = BytesMut::with_capacity(head.len() + tail.len());
+        buf.extend_from_slice(&head);
+        buf.extend_from_slice(tail);
+        *tail = buf.freeze();
+        *start = offset;
+    }
+
+    Ok(tail.slice(offset - *start..))
+}
+
+/// Evaluate the bloom filters of the parquet file read by `reader` against
+/// `values`.
+fn select_from_reader<R>(
+    reader: R,
+    values: &TagValues,
+) -> Result<RowGroupSelection, BloomFilterError>
+where
+    R: ChunkReader + 'static,
+{
+    let options = ReadOptionsBuilder::new()
+        .with_reader_properties(
+            ReaderProperties::builder()
+                .set_read_bloom_filter(true)
+                .build(),
+        )
+        .build();
+    let reader = SerializedFileReader::new_with_options(reader, options)?;
+
+    let num_row_groups = reader.metadata().num_row_groups();
+    let mut keep = Vec::with_capacity(num_row_groups);
+    let mut spans = Vec::with_capacity(num_row_groups);
+
+    for i in 0..num_row_groups {
+        let row_group = reader.get_row_group(i)?;
+        let meta = row_group.metadata();
+
+        let may_match = meta.columns().iter().enumerate().all(|(idx, column)| {
+            let (Some(required), Some(filter)) = (
+                values.get(column.column_path().string().as_str()),
+                row_group.get_column_bloom_filter(idx),
+            ) else {
+                return true;
+            };
+
+            required
+                .iter()
+                .any(|v| filter.check(&ByteArray::from(v.as_str())))
+        });
+
+        keep.push(may_match);
+        spans.push(row_group_span(meta));
+    }
+
+    Ok(RowGroupSelection { keep, spans })
+}
+
+/// A [`ChunkReader`] over the bytes of a parquet file from `start` to the end
+/// of the file.
+///
+/// Reading any byte before `start` is an error.
+struct FileTail {
+    start: usize,
+    data: Bytes,
+}
+
+impl Length for FileTail {
+    fn len(&self) -> u64 {
+        (self.start + self.data.len()) as u64
+    }
+}
+
+impl ChunkReader for FileTail {
+    type T = bytes::buf::Reader<Bytes>;
+
+    fn get_read(&self, start: u64, length: usize) -> Result<Self::T, ParquetError> {
+        Ok(self.get_bytes(start, length)?.reader())
+    }
+
+    fn get_bytes(&self, start: u64, length: usize) -> Result<Bytes, ParquetError> {
+        let offset = (start as usize).checked_sub(self.start).ok_or_else(|| {
+            ParquetError::General(format!(
+                "read at offset {start} precedes the fetched range starting at {}",
+                self.start
+            ))
+        })?;
+        if offset + length > self.data.len() {
+            return Err(ParquetError::EOF(format!(
+                "read of {length} bytes at offset {start} exceeds the file size"
+            )));
+        }
+
+        Ok(self.data.slice(offset..offset + length))
+    }
+}
+
+/// The `[start, end)` byte range of the column chunks in `meta`.
+fn row_group_span(meta: &RowGroupMetaData) -> (i64, i64) {
+    meta.columns()
+        .iter()
+        .map(|c| {
+            let start = c
+                .dictionary_page_offset()
+                .unwrap_or_else(|| c.data_page_offset());
+            (start, start + c.compressed_size())
+        })
+        .fold((i64::MAX, i64::MIN), |(start, end), (s, e)| {
+            (start.min(s), end.max(e))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::{array::StringArray, record_batch::RecordBatch};
+    use datafusion::prelude::{col, lit};
+    use object_store::memory::InMemory;
+    use parquet::{
+        arrow::ArrowWriter, file::properties::WriterProperties, schema::types::ColumnPath,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_tag_values() {
+        let predicate = Predicate::new()
+            .with_expr(col("host").eq(lit("a")))
+            .with_expr(col("region").in_list(vec![lit("west"), lit("east")], false))
+            .with_expr(lit("east").eq(col("region")))
+            .with_expr(col("dc").eq(lit("1")).or(col("dc").eq(lit("2"))))
+            .with_expr(col("rack").in_list(vec![lit("r1")], true))
+            .with_expr(col("v").eq(lit(1.0)));
+
+        let got = TagValues::from_predicate(&predicate);
+        assert_eq!(
+            got,
+            TagValues(BTreeMap::from([
+                ("host".to_string(), BTreeSet::from(["a".to_string()])),
+                ("region".to_string(), BTreeSet::from(["east".to_string()])),
+            ]))
+        );
+
+        assert!(TagValues::from_predicate(&Predicate::new().with_range(0, 10)).is_empty());
+    }
+
+    /// Write `batch` to a parquet file in a new in-memory object store, with
+    /// `rows_per_group` rows in each row group and a bloom filter on "host".
+    async fn write_file(batch: &RecordBatch, rows_per_group: usize) -> (InMemory, Path, usize) {
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(rows_per_group)
+            .set_column_bloom_filter_enabled(ColumnPath::from("host"), true)
+            .build();
+        let mut buf = vec![];
+        let mut writer = ArrowWriter::try_new(&mut buf, batch.schema(), Some(props)).unwrap();
+        writer.write(batch).unwrap();
+        writer.close().unwrap();
+
+        let store = InMemory::new();
+        let path = Path::from("file.parquet");
+        let size = buf.len();
+        store.put(&path, Bytes::from(buf)).await.unwrap();
+
+        (store, path, size)
+    }
+
+    async fn select(
+        (store, path, size): &(InMemory, Path, usize),
+        predicate: Predicate,
+    ) -> RowGroupSelection {
+        select_row_groups(store, path, *size, &TagValues::from_predicate(&predicate))
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_select_row_groups() {
+        let batch = RecordBatch::try_from_iter([
+            (
+                "host",
+                Arc::new(StringArray::from(vec!["a", "a", "b", "b", "c", "c"])) as _,
+            ),
+            (
+                "region",
+                Arc::new(StringArray::from(vec!["w", "w", "w", "w", "w", "w"])) as _,
+            ),
+        ])
+        .unwrap();
+
+        // Three row groups, one per host value, with a bloom filter on "host"
+        // only.
+        let file = write_file(&batch, 2).await;
+
+        // Matches a single row group.
+        let got = select(&file, Predicate::new().with_expr(col("host").eq(lit("b")))).await;
+        assert_eq!(got.keep, vec![false, true, false]);
+        assert!(!got.is_empty());
+        let range = got.file_range().unwrap();
+        assert_eq!(range.start, got.spans[1].0);
+        assert_eq!(range.end, got.spans[1].1 + 1);
+
+        // Matches the first and last row group, so all must be read.
+        let got = select(
+            &file,
+            Predicate::new().with_expr(col("host").in_list(vec![lit("a"), lit("c")], false)),
+        )
+        .await;
+        assert_eq!(got.keep, vec![true, false, true]);
+        let range = got.file_range().unwrap();
+        assert_eq!(range.start, got.spans[0].0);
+        assert_eq!(range.end, got.spans[2].1 + 1);
+
+        // Matches nothing.
+        let got = select(&file, Predicate::new().with_expr(col("host").eq(lit("z")))).await;
+        assert!(got.is_empty());
+
+        // No bloom filter on "region", so everything may match.
+        let got = select(
+            &file,
+            Predicate::new().with_expr(col("region").eq(lit("z"))),
+        )
+        .await;
+        assert_eq!(got.keep, vec![true, true, true]);
+        assert!(got.file_range().is_none());
+    }
+
+    /// The row group data of a file larger than [`FOOTER_READ_SIZE`] is not
+    /// read, as [`FileTail`] errors on reads before the fetched range.
+    #[tokio::test]
+    async fn test_select_row_groups_reads_tail() {
+        let n = 30_000;
+        let hosts = (0..n)
+            .map(|i| ["a", "b", "c"][i * 3 / n])
+            .collect::<Vec<_>>();
+        let values = (0..n).map(|i| format!("value-{i:020}")).collect::<Vec<_>>();
+        let batch = RecordBatch::try_from_iter([
+            ("host", Arc::new(StringArray::from(hosts)) as _),
+            ("value", Arc::new(StringArray::from(values)) as _),
+        ])
+        .unwrap();
+
+        let file = write_file(&batch, n / 3).await;
+        assert!(file.2 > 2 * FOOTER_READ_SIZE);
+
+        let got = select(&file, Predicate::new().with_expr(col("host").eq(lit("c")))).await;
+        assert_eq!(got.keep, vec![false, false, true]);
+    }
+}
@@ -15,6 +15,7 @@
 )]
 #![allow(clippy::missing_docs_in_private_items)]
 
+pub mod bloom_filter;
 pub mod chunk;
 pub mod metadata;
 pub mod serialize;
@@ -2,8 +2,13 @@
 //!
 //! [`RecordBatch`]: arrow::record_batch::RecordBatch
 
-use std::{io::Write, sync::Arc};
+use std::{collections::HashSet, io::Write, sync::Arc};
 
+use arrow::{
+    array::{DictionaryArray, StringArray},
+    datatypes::{Int32Type, SchemaRef},
+    record_batch::RecordBatch,
+};
 use datafusion::{error::DataFusionError, physical_plan::SendableRecordBatchStream};
 use datafusion_util::config::BATCH_SIZE;
 use futures::{pin_mut, TryStreamExt};
@@ -13,7 +18,9 @@ use parquet::{
     basic::Compression,
     errors::ParquetError,
     file::{metadata::KeyValue, properties::WriterProperties},
+    schema::types::ColumnPath,
 };
+use schema::Schema;
 use thiserror::Error;
 
 use crate::metadata::{IoxMetadata, METADATA_KEY};
@@ -116,8 +123,20 @@ where
     let stream = batches;
     pin_mut!(stream);
 
+    // Buffer the batches of the first row group, from which the size of the
+    // bloom filters is derived.
+    let mut first_row_group = vec![];
+    let mut first_row_group_rows = 0;
+    while first_row_group_rows < ROW_GROUP_WRITE_SIZE {
+        let Some(batch) = stream.try_next().await? else {
+            break;
+        };
+        first_row_group_rows += batch.num_rows();
+        first_row_group.push(batch);
+    }
+
     // Serialize the IoxMetadata to the protobuf bytes.
-    let props = writer_props(meta)?;
+    let props = writer_props(meta, &schema, &first_row_group)?;
     let write_batch_size = props.write_batch_size();
     let max_row_group_size = props.max_row_group_size();
 
@@ -126,6 +145,10 @@ where
     let mut writer = ArrowWriter::try_new(sink, Arc::clone(&schema), Some(props))?;
 
     let mut num_batches = 0;
+    for batch in first_row_group {
+        writer.write(&batch)?;
+        num_batches += 1;
+    }
     while let Some(batch) = stream.try_next().await? {
         writer.write(&batch)?;
         num_batches += 1;
@@ -176,8 +199,18 @@ pub async fn to_parquet_bytes(
 /// Helper to construct [`WriterProperties`] for the [`ArrowWriter`],
 /// serialising the given [`IoxMetadata`] and embedding it as a key=value
 /// property keyed by [`METADATA_KEY`].
-fn writer_props(meta: &IoxMetadata) -> Result<WriterProperties, prost::EncodeError> {
-    let builder = WriterProperties::builder()
+///
+/// A bloom filter is written for each tag column in `schema`, allowing the
+/// querier to skip files and row groups for equality predicates on tags. The
+/// size of a bloom filter is fixed when it is created, so each is sized for
+/// the number of distinct values of its column in `first_row_group`, the
+/// batches written to the first row group of the file.
+fn writer_props(
+    meta: &IoxMetadata,
+    schema: &SchemaRef,
+    first_row_group: &[RecordBatch],
+) -> Result<WriterProperties, prost::EncodeError> {
+    let mut builder = WriterProperties::builder()
         .set_key_value_metadata(Some(vec![KeyValue {
             key: METADATA_KEY.to_string(),
             value: Some(meta.to_base64()?),
@@ -185,39 +218,75 @@ fn writer_props(meta: &IoxMetadata) -> Result<WriterProperties, prost::EncodeErr
         .set_compression(Compression::ZSTD(Default::default()))
         .set_max_row_group_size(ROW_GROUP_WRITE_SIZE);
 
+    // Batches without an IOx schema have no tag columns to index.
+    if let Ok(schema) = Schema::try_from(Arc::clone(schema)) {
+        for tag in schema.tags_iter() {
+            let column = ColumnPath::from(tag.name().as_str());
+            builder = builder
+                .set_column_bloom_filter_enabled(column.clone(), true)
+                .set_column_bloom_filter_ndv(column, distinct_values(first_row_group, tag.name()));
+        }
+    }
+
     Ok(builder.build())
 }
 
+/// Returns the number of distinct values of the tag `column` in `batches`, or
+/// the number of rows if it is not a dictionary of strings.
+///
+/// At least 1 is returned, as a bloom filter cannot be sized for 0 values.
+fn distinct_values(batches: &[RecordBatch], column: &str) -> u64 {
+    let mut values = HashSet::new();
+
+    for array in batches.iter().filter_map(|b| b.column_by_name(column)) {
+        let dict = array
+            .as_any()
+            .downcast_ref::<DictionaryArray<Int32Type>>()
+            .and_then(|d| Some((d, d.values().as_any().downcast_ref::<StringArray>()?)));
+        let Some((dict, strings)) = dict else {
+            let rows: usize = batches.iter().map(|b| b.num_rows()).sum();
+            return rows.max(1) as u64;
+        };
+
+        values.extend(
+            dict.keys()
+                .iter()
+                .flatten()
+                .map(|k| strings.value(k as usize)),
+        );
+    }
+
+    values.len().max(1) as u64
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::metadata::IoxParquetMetaData;
+    use crate::{
+        bloom_filter::{select_row_groups, TagValues},
+        metadata::IoxParquetMetaData,
+    };
     use arrow::{
-        array::{ArrayRef, StringArray},
+        array::{ArrayRef, DictionaryArray, StringArray, TimestampNanosecondArray},
+        datatypes::Int32Type,
         record_batch::RecordBatch,
     };
     use bytes::Bytes;
     use data_types::{CompactionLevel, NamespaceId, PartitionId, TableId};
-    use datafusion::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use datafusion::{
+        parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder,
+        prelude::{col, lit},
+    };
     use datafusion_util::MemoryStream;
     use iox_time::Time;
+    use object_store::{memory::InMemory, path::Path, ObjectStore};
+    use predicate::Predicate;
+    use schema::{builder::SchemaBuilder, InfluxFieldType};
     use std::sync::Arc;
 
     #[tokio::test]
     async fn test_encode_stream() {
-        let meta = IoxMetadata {
-            object_store_id: Default::default(),
-            creation_timestamp: Time::from_timestamp_nanos(42),
-            namespace_id: NamespaceId::new(1),
-            namespace_name: "bananas".into(),
-            table_id: TableId::new(3),
-            table_name: "platanos".into(),
-            partition_id: PartitionId::new(4),
-            partition_key: "potato".into(),
-            compaction_level: CompactionLevel::FileNonOverlapped,
-            sort_key: None,
-            max_l0_created_at: Time::from_timestamp_nanos(42),
-        };
+        let meta = meta();
 
         let batch = RecordBatch::try_from_iter([("a", to_string_array(&["value"]))]).unwrap();
         let stream = Box::pin(MemoryStream::new(vec![batch.clone()]));
@@ -255,6 +324,103 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_tag_bloom_filters() {
+        let schema = SchemaBuilder::new()
+            .tag("host")
+            .influx_field("f", InfluxFieldType::String)
+            .timestamp()
+            .build()
+            .expect("could not create schema")
+            .as_arrow();
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(DictionaryArray::<Int32Type>::from_iter(["a", "b"])),
+                to_string_array(&["x", "y"]),
+                Arc::new(TimestampNanosecondArray::from(vec![1, 2])),
+            ],
+        )
+        .unwrap();
+        let stream = Box::pin(MemoryStream::new(vec![batch]));
+
+        let (bytes, _file_meta) = to_parquet_bytes(stream, &meta())
+            .await
+            .expect("should serialize");
+        let store = InMemory::new();
+        let path = Path::from("file.parquet");
+        let size = bytes.len();
+        store.put(&path, Bytes::from(bytes)).await.unwrap();
+
+        let select = |predicate: Predicate| {
+            let store = &store;
+            let path = &path;
+            async move {
+                select_row_groups(store, path, size, &TagValues::from_predicate(&predicate))
+                    .await
+                    .expect("should read bloom filters")
+            }
+        };
+
+        // The tag column has a bloom filter.
+        assert!(
+            !select(Predicate::new().with_expr(col("host").eq(lit("a"))))
+                .await
+                .is_empty()
+        );
+        assert!(select(Predicate::new().with_expr(col("host").eq(lit("c"))))
+            .await
+            .is_empty());
+
+        // The field column does not.
+        assert!(!select(Predicate::new().with_expr(col("f").eq(lit("z"))))
+            .await
+            .is_empty());
+    }
+
+    #[test]
+    fn test_distinct_values() {
+        let batch = RecordBatch::try_from_iter([
+            (
+                "tag",
+                Arc::new(DictionaryArray::<Int32Type>::from_iter([
+                    Some("a"),
+                    Some("b"),
+                    Some("a"),
+                    None,
+                ])) as ArrayRef,
+            ),
+            ("field", to_string_array(&["x", "x", "x", "x"])),
+        ])
+        .unwrap();
+
+        // Distinct values are counted across batches.
+        assert_eq!(distinct_values(&[batch.clone(), batch.clone()], "tag"), 2);
+
+        // Columns that are not dictionary encoded are sized per row.
+        assert_eq!(distinct_values(&[batch.clone(), batch.clone()], "field"), 8);
+
+        // At least one value is always returned.
+        assert_eq!(distinct_values(&[batch.slice(3, 1)], "tag"), 1);
+        assert_eq!(distinct_values(&[batch], "missing"), 1);
+    }
+
+    fn meta() -> IoxMetadata {
+        IoxMetadata {
+            object_store_id: Default::default(),
+            creation_timestamp: Time::from_timestamp_nanos(42),
+            namespace_id: NamespaceId::new(1),
+            namespace_name: "bananas".into(),
+            table_id: TableId::new(3),
+            table_name: "platanos".into(),
+            partition_id: PartitionId::new(4),
+            partition_key: "potato".into(),
+            compaction_level: CompactionLevel::FileNonOverlapped,
+            sort_key: None,
+            max_l0_created_at: Time::from_timestamp_nanos(42),
+        }
+    }
+
     fn to_string_array(strs: &[&str]) -> ArrayRef {
         let array: StringArray = strs.iter().map(|s| Some(*s)).collect();
         Arc::new(array)
@@ -12,7 +12,10 @@ use arrow::{
 };
 use bytes::Bytes;
 use datafusion::{
-    datasource::{listing::PartitionedFile, object_store::ObjectStoreUrl},
+    datasource::{
+        listing::{FileRange, PartitionedFile},
+        object_store::ObjectStoreUrl,
+    },
     error::DataFusionError,
     physical_plan::{
         file_format::{FileScanConfig, ParquetExec},
@@ -99,6 +102,11 @@ pub struct ParquetExecInput {
 
     /// Object metadata.
     pub object_meta: ObjectMeta,
+
+    /// Byte range of the file to scan.
+    ///
+    /// Only row groups starting within this range are read. If [`None`], the entire file is scanned.
+    pub range: Option<FileRange>,
 }
 
 impl ParquetExecInput {
@@ -293,6 +301,7 @@ impl ParquetStorage {
                 last_modified: Default::default(),
                 size: file_size,
             },
+            range: None,
         }
     }
 }
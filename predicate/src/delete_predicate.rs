@@ -1,6 +1,6 @@
 use crate::delete_expr::{df_to_expr, expr_to_df};
 use chrono::DateTime;
-use data_types::{DeleteExpr, DeletePredicate, TimestampRange};
+use data_types::{DeleteExpr, DeletePredicate, TimestampRange, Tombstone};
 use datafusion::{
     logical_expr::Operator,
     prelude::{binary_expr, lit, Column, Expr},
@@ -11,6 +11,7 @@ use sqlparser::{
     dialect::GenericDialect,
     parser::Parser,
 };
+use std::sync::Arc;
 
 /// Parse Delete Predicates
 /// Parse Error
@@ -51,6 +52,33 @@ impl From<DeletePredicate> for crate::Predicate {
     }
 }
 
+/// Return a DataFusion expression that selects the rows not removed by any of
+/// `delete_predicates`, or [`None`] if there are no delete predicates.
+///
+/// Unlike [`Predicate::negated_expr`](crate::Predicate::negated_expr), an
+/// expression that evaluates to NULL for a row (such as `tag = 'A'` for a row
+/// without `tag`) does not delete the row, so each expression is evaluated as
+/// `expr IS TRUE` before it is negated.
+pub fn negated_delete_expr<S>(delete_predicates: &[S]) -> Option<Expr>
+where
+    S: AsRef<DeletePredicate>,
+{
+    let preds: Vec<_> = delete_predicates
+        .iter()
+        .map(|pred| {
+            let mut pred = crate::Predicate::from(pred.as_ref().clone());
+            pred.exprs = pred
+                .exprs
+                .into_iter()
+                .map(|expr| Expr::IsTrue(Box::new(expr)))
+                .collect();
+            Arc::new(pred)
+        })
+        .collect();
+
+    crate::Predicate::negated_expr(&preds)
+}
+
 /// Parse and convert the delete grpc API into ParseDeletePredicate to send to server
 pub fn parse_delete_predicate(
     start_time: &str,
@@ -69,6 +97,14 @@ pub fn parse_delete_predicate(
     })
 }
 
+/// Rebuild the [`DeletePredicate`] of a [`Tombstone`] recorded in the catalog.
+pub fn parse_tombstone_predicate(tombstone: &Tombstone) -> Result<DeletePredicate> {
+    Ok(DeletePredicate {
+        range: TimestampRange::new(tombstone.min_time.get(), tombstone.max_time.get()),
+        exprs: parse_predicate(&tombstone.serialized_predicate)?,
+    })
+}
+
 /// Parse the predicate and convert it into datafusion expression
 /// A delete predicate is a conjunctive expression of many
 /// binary expressions of 'colum = constant' or 'column != constant'
@@ -240,7 +276,8 @@ fn parse_time_range(start: &str, stop: &str) -> Result<(i64, i64)> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use data_types::{Op, Scalar};
+    use data_types::{Op, Scalar, TableId, Timestamp, TombstoneId};
+    use datafusion::prelude::{col, lit_timestamp_nano};
 
     #[test]
     fn test_time_range_valid() {
@@ -436,4 +473,41 @@ mod tests {
         let result = parse_delete_predicate(start, stop, pred);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_tombstone_predicate_round_trip() {
+        let pred = parse_delete_predicate(
+            "100",
+            "200",
+            r#"city = Boston and cost != 100 and state != "MA""#,
+        )
+        .unwrap();
+
+        let tombstone = Tombstone {
+            id: TombstoneId::new(1),
+            table_id: TableId::new(2),
+            min_time: Timestamp::new(pred.range.start()),
+            max_time: Timestamp::new(pred.range.end()),
+            serialized_predicate: pred.expr_sql_string(),
+            created_at: Timestamp::new(0),
+        };
+
+        let got = parse_tombstone_predicate(&tombstone).unwrap();
+        assert_eq!(got, pred);
+    }
+
+    #[test]
+    fn test_negated_delete_expr() {
+        assert!(negated_delete_expr::<Arc<DeletePredicate>>(&[]).is_none());
+
+        let pred = parse_delete_predicate("100", "200", r#"city = Boston"#).unwrap();
+        let got = negated_delete_expr(&[Arc::new(pred)]).unwrap();
+
+        // A row without a city is not deleted.
+        let expected = col("time")
+            .lt(lit_timestamp_nano(100))
+            .or(col("time").gt(lit_timestamp_nano(200)))
+            .or(Expr::IsTrue(Box::new(col("city").eq(lit("Boston")))).not());
+        assert_eq!(got, expected);
+    }
 }
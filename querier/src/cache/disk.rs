@@ -0,0 +1,459 @@
+//! On-disk tier of the [object store cache](super::object_store::ObjectStoreCache).
+use std::{
+    any::Any,
+    collections::HashMap,
+    fs, io,
+    ops::{Add, Sub},
+    path::{Path as FsPath, PathBuf},
+    sync::Arc,
+};
+
+use bytes::Bytes;
+use cache_system::{
+    backend::{
+        policy::{
+            lru::{LruPolicy, ResourcePool},
+            PolicyBackend,
+        },
+        CacheBackend,
+    },
+    resource_consumption::{FunctionEstimator, Resource},
+};
+use iox_time::TimeProvider;
+use metric::{Attributes, DurationHistogram};
+use object_store::path::{Path, DELIMITER};
+use observability_deps::tracing::{info, warn};
+use parking_lot::Mutex;
+use uuid::Uuid;
+
+const CACHE_ID: &str = "object_store_disk";
+
+/// Directory (relative to the cache directory) containing the cached objects, laid out like the object store.
+const OBJECTS_DIR: &str = "objects";
+
+/// Directory (relative to the cache directory) used to stage objects while they are written.
+const TMP_DIR: &str = "tmp";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd)]
+pub struct DiskSize(pub usize);
+
+impl Resource for DiskSize {
+    fn zero() -> Self {
+        Self(0)
+    }
+
+    fn unit() -> &'static str {
+        "bytes"
+    }
+}
+
+impl From<DiskSize> for u64 {
+    fn from(s: DiskSize) -> Self {
+        s.0 as Self
+    }
+}
+
+impl Add for DiskSize {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0.checked_add(rhs.0).expect("overflow"))
+    }
+}
+
+impl Sub for DiskSize {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0.checked_sub(rhs.0).expect("underflow"))
+    }
+}
+
+/// Index of the objects stored on disk.
+///
+/// Removing an entry -- e.g. when it is evicted by the LRU policy -- deletes the file.
+#[derive(Debug)]
+struct DiskBackend {
+    objects_dir: PathBuf,
+    entries: HashMap<Path, DiskSize>,
+}
+
+impl CacheBackend for DiskBackend {
+    type K = Path;
+    type V = DiskSize;
+
+    fn get(&mut self, k: &Self::K) -> Option<Self::V> {
+        self.entries.get(k).copied()
+    }
+
+    fn set(&mut self, k: Self::K, v: Self::V) {
+        self.entries.insert(k, v);
+    }
+
+    fn remove(&mut self, k: &Self::K) {
+        if self.entries.remove(k).is_none() {
+            return;
+        }
+
+        match fs::remove_file(object_file(&self.objects_dir, k)) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => warn!(path=%k, %e, "cannot remove object from disk cache"),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self as &dyn Any
+    }
+}
+
+/// Local disk cache for immutable objects, bounded by a [`ResourcePool`] and evicting the least recently used objects.
+///
+/// Objects are stored as individual files so that the cache survives restarts: the cache directory is re-indexed
+/// when the cache is created. Errors while reading or writing objects are logged and treated as cache misses.
+///
+/// In contrast to the in-memory tier, "not found" results are NOT cached.
+#[derive(Debug)]
+pub struct DiskCache {
+    objects_dir: PathBuf,
+    tmp_dir: PathBuf,
+    backend: Mutex<PolicyBackend<Path, DiskSize>>,
+    time_provider: Arc<dyn TimeProvider>,
+    metric_get_hit: DurationHistogram,
+    metric_get_miss: DurationHistogram,
+}
+
+impl DiskCache {
+    /// Create cache within `dir`, indexing the objects that a previous process left there.
+    ///
+    /// Objects are evicted if the existing content exceeds the pool limit.
+    pub fn new(
+        dir: impl Into<PathBuf>,
+        pool: Arc<ResourcePool<DiskSize>>,
+        time_provider: Arc<dyn TimeProvider>,
+        metric_registry: &metric::Registry,
+    ) -> io::Result<Self> {
+        let dir = dir.into();
+        let objects_dir = dir.join(OBJECTS_DIR);
+        let tmp_dir = dir.join(TMP_DIR);
+
+        // objects that were not completely written by a previous process are useless
+        match fs::remove_dir_all(&tmp_dir) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+        fs::create_dir_all(&tmp_dir)?;
+        fs::create_dir_all(&objects_dir)?;
+
+        let mut backend = PolicyBackend::new(
+            Box::new(DiskBackend {
+                objects_dir: objects_dir.clone(),
+                entries: HashMap::new(),
+            }),
+            Arc::clone(&time_provider),
+        );
+        backend.add_policy(LruPolicy::new(
+            pool,
+            CACHE_ID,
+            Arc::new(FunctionEstimator::new(|_k: &Path, v: &DiskSize| *v)),
+        ));
+
+        let mut entries = vec![];
+        index_dir(&objects_dir, &objects_dir, &mut entries)?;
+        info!(dir=%dir.display(), n_objects=entries.len(), "indexed disk cache");
+        for (path, size) in entries {
+            backend.set(path, size);
+        }
+
+        let metric_get = metric_registry
+            .register_metric::<DurationHistogram>("iox_cache_get", "Cache GET requests");
+        let mut attributes = Attributes::from(&[("name", CACHE_ID)]);
+        attributes.insert("status", "hit");
+        let metric_get_hit = metric_get.recorder(attributes.clone());
+        attributes.insert("status", "miss");
+        let metric_get_miss = metric_get.recorder(attributes);
+
+        Ok(Self {
+            objects_dir,
+            tmp_dir,
+            backend: Mutex::new(backend),
+            time_provider,
+            metric_get_hit,
+            metric_get_miss,
+        })
+    }
+
+    /// Read object from disk, if it is cached.
+    pub async fn get(&self, path: &Path) -> Option<Bytes> {
+        let t_start = self.time_provider.now();
+
+        let cached = self.backend.lock().get(path).is_some();
+        let data = if cached {
+            match tokio::fs::read(object_file(&self.objects_dir, path)).await {
+                Ok(data) => Some(Bytes::from(data)),
+                Err(e) => {
+                    warn!(%path, %e, "cannot read object from disk cache");
+                    self.backend.lock().remove(path);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let metric = if data.is_some() {
+            &self.metric_get_hit
+        } else {
+            &self.metric_get_miss
+        };
+        if let Some(d) = self.time_provider.now().checked_duration_since(t_start) {
+            metric.record(d);
+        }
+
+        data
+    }
+
+    /// Write object to disk.
+    ///
+    /// This may evict other objects, or -- if the object alone exceeds the pool limit -- the object itself.
+    pub async fn put(&self, path: &Path, data: &Bytes) {
+        let tmp_file = self.tmp_dir.join(Uuid::new_v4().to_string());
+        let file = object_file(&self.objects_dir, path);
+
+        // stage the file first, so that a concurrent reader or a restart never sees a partial object
+        let res = async {
+            tokio::fs::write(&tmp_file, data).await?;
+            if let Some(parent) = file.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            tokio::fs::rename(&tmp_file, &file).await
+        }
+        .await;
+
+        if let Err(e) = res {
+            warn!(%path, %e, "cannot write object to disk cache");
+            tokio::fs::remove_file(&tmp_file).await.ok();
+            return;
+        }
+
+        self.backend.lock().set(path.clone(), DiskSize(data.len()));
+    }
+}
+
+/// Location of the file that stores the object at `path`.
+fn object_file(objects_dir: &FsPath, path: &Path) -> PathBuf {
+    path.parts().fold(objects_dir.to_path_buf(), |file, part| {
+        file.join(part.as_ref())
+    })
+}
+
+/// Inverse of [`object_file`].
+fn object_path(objects_dir: &FsPath, file: &FsPath) -> Option<Path> {
+    let parts = file
+        .strip_prefix(objects_dir)
+        .ok()?
+        .iter()
+        .map(|part| part.to_str())
+        .collect::<Option<Vec<_>>>()?;
+
+    Path::parse(parts.join(DELIMITER)).ok()
+}
+
+/// Recursively collect all objects stored within `dir`, removing files that cannot be mapped to an object.
+fn index_dir(
+    objects_dir: &FsPath,
+    dir: &FsPath,
+    entries: &mut Vec<(Path, DiskSize)>,
+) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let file = entry.path();
+
+        if entry.file_type()?.is_dir() {
+            index_dir(objects_dir, &file, entries)?;
+            continue;
+        }
+
+        match object_path(objects_dir, &file) {
+            Some(path) => entries.push((path, DiskSize(entry.metadata()?.len() as usize))),
+            None => {
+                warn!(file=%file.display(), "removing unknown file from disk cache");
+                fs::remove_file(&file)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod test_util {
+    use super::*;
+
+    pub fn test_disk_pool(limit: usize) -> Arc<ResourcePool<DiskSize>> {
+        Arc::new(ResourcePool::new(
+            "pool",
+            DiskSize(limit),
+            Arc::new(metric::Registry::new()),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use iox_time::{MockProvider, SystemProvider, Time};
+    use metric::Metric;
+
+    use super::{test_util::test_disk_pool, *};
+
+    #[tokio::test]
+    async fn test_get_put() {
+        let dir = test_helpers::tmp_dir().unwrap();
+        let metric_registry = metric::Registry::new();
+        let cache = DiskCache::new(
+            dir.path(),
+            test_disk_pool(usize::MAX),
+            Arc::new(SystemProvider::new()),
+            &metric_registry,
+        )
+        .unwrap();
+
+        let path = Path::from("1/2/foo.parquet");
+        let data = Bytes::from(b"data_foo" as &'static [u8]);
+
+        assert_eq!(cache.get(&path).await, None);
+        assert_eq!(get_count(&metric_registry, "miss"), 1);
+
+        cache.put(&path, &data).await;
+        assert_eq!(cache.get(&path).await, Some(data.clone()));
+        assert_eq!(get_count(&metric_registry, "hit"), 1);
+
+        assert_eq!(
+            Bytes::from(fs::read(dir.path().join("objects/1/2/foo.parquet")).unwrap()),
+            data
+        );
+        assert!(fs::read_dir(dir.path().join("tmp"))
+            .unwrap()
+            .next()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_eviction() {
+        let dir = test_helpers::tmp_dir().unwrap();
+        let time_provider = Arc::new(MockProvider::new(Time::from_timestamp_nanos(0)));
+        let cache = DiskCache::new(
+            dir.path(),
+            test_disk_pool(10),
+            Arc::clone(&time_provider) as _,
+            &metric::Registry::new(),
+        )
+        .unwrap();
+
+        let path_1 = Path::from("foo");
+        let path_2 = Path::from("bar");
+        let path_3 = Path::from("baz");
+        let data = Bytes::from(b"12345" as &'static [u8]);
+
+        cache.put(&path_1, &data).await;
+        time_provider.inc(Duration::from_secs(1));
+        cache.put(&path_2, &data).await;
+        time_provider.inc(Duration::from_secs(1));
+        assert!(cache.get(&path_1).await.is_some());
+        time_provider.inc(Duration::from_secs(1));
+
+        // evicts the least recently used object
+        cache.put(&path_3, &data).await;
+        assert!(cache.get(&path_1).await.is_some());
+        assert!(cache.get(&path_2).await.is_none());
+        assert!(cache.get(&path_3).await.is_some());
+        assert!(!dir.path().join("objects/bar").exists());
+
+        // oversized objects are not kept
+        cache
+            .put(&path_2, &Bytes::from(b"12345678901" as &'static [u8]))
+            .await;
+        assert!(cache.get(&path_2).await.is_none());
+        assert!(!dir.path().join("objects/bar").exists());
+    }
+
+    #[tokio::test]
+    async fn test_reindex() {
+        let dir = test_helpers::tmp_dir().unwrap();
+        let path_1 = Path::from("1/foo.parquet");
+        let path_2 = Path::from("2/bar.parquet");
+        let data = Bytes::from(b"12345" as &'static [u8]);
+
+        let cache = DiskCache::new(
+            dir.path(),
+            test_disk_pool(usize::MAX),
+            Arc::new(SystemProvider::new()),
+            &metric::Registry::new(),
+        )
+        .unwrap();
+        cache.put(&path_1, &data).await;
+        cache.put(&path_2, &data).await;
+        drop(cache);
+
+        // leftovers of an interrupted write
+        fs::write(dir.path().join("tmp/partial"), b"123").unwrap();
+
+        let pool = test_disk_pool(usize::MAX);
+        let cache = DiskCache::new(
+            dir.path(),
+            Arc::clone(&pool),
+            Arc::new(SystemProvider::new()),
+            &metric::Registry::new(),
+        )
+        .unwrap();
+        assert_eq!(cache.get(&path_1).await, Some(data.clone()));
+        assert_eq!(cache.get(&path_2).await, Some(data.clone()));
+        assert_eq!(pool.current(), DiskSize(10));
+        assert!(!dir.path().join("tmp/partial").exists());
+        drop(cache);
+
+        // existing objects beyond the limit are evicted
+        let pool = test_disk_pool(5);
+        let cache = DiskCache::new(
+            dir.path(),
+            Arc::clone(&pool),
+            Arc::new(SystemProvider::new()),
+            &metric::Registry::new(),
+        )
+        .unwrap();
+        assert_eq!(pool.current(), DiskSize(5));
+        let n_cached = cache.get(&path_1).await.is_some() as usize
+            + cache.get(&path_2).await.is_some() as usize;
+        assert_eq!(n_cached, 1);
+    }
+
+    #[test]
+    fn test_object_path_roundtrip() {
+        let objects_dir = FsPath::new("/cache/objects");
+
+        for path in ["foo", "1/2/3/foo.parquet", "a%20b/c"] {
+            let path = Path::parse(path).unwrap();
+            let file = object_file(objects_dir, &path);
+            assert_eq!(object_path(objects_dir, &file), Some(path));
+        }
+
+        assert_eq!(object_path(objects_dir, FsPath::new("/other/foo")), None);
+    }
+
+    fn get_count(metric_registry: &metric::Registry, status: &'static str) -> u64 {
+        metric_registry
+            .get_instrument::<Metric<DurationHistogram>>("iox_cache_get")
+            .unwrap()
+            .get_observer(&Attributes::from(&[("name", CACHE_ID), ("status", status)]))
+            .unwrap()
+            .fetch()
+            .sample_count()
+    }
+}
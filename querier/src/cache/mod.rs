@@ -5,20 +5,28 @@ use backoff::BackoffConfig;
 use cache_system::backend::policy::lru::ResourcePool;
 use iox_catalog::interface::Catalog;
 use iox_time::TimeProvider;
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 use tokio::runtime::Handle;
 
 use self::{
-    namespace::NamespaceCache, object_store::ObjectStoreCache, parquet_file::ParquetFileCache,
-    partition::PartitionCache, projected_schema::ProjectedSchemaCache, ram::RamSize,
+    disk::{DiskCache, DiskSize},
+    namespace::NamespaceCache,
+    object_store::ObjectStoreCache,
+    parquet_file::ParquetFileCache,
+    partition::PartitionCache,
+    projected_schema::ProjectedSchemaCache,
+    ram::RamSize,
+    tombstone::TombstoneCache,
 };
 
+mod disk;
 pub mod namespace;
 pub mod object_store;
 pub mod parquet_file;
 pub mod partition;
 pub mod projected_schema;
 mod ram;
+pub mod tombstone;
 
 #[cfg(test)]
 mod test_util;
@@ -41,6 +49,9 @@ pub struct CatalogCache {
     /// Projected schema cache.
     projected_schema_cache: ProjectedSchemaCache,
 
+    /// Tombstone cache.
+    tombstone_cache: TombstoneCache,
+
     /// Object store cache.
     object_store_cache: ObjectStoreCache,
 
@@ -53,6 +64,17 @@ pub struct CatalogCache {
 
 impl CatalogCache {
     /// Create empty cache.
+    ///
+    /// If `disk_cache_dir` is set, objects are additionally cached on disk within this directory, using at most
+    /// `disk_pool_data_bytes`. Objects already present in the directory are reused.
+    ///
+    /// If `cache_object_ranges` is set, the object store cache operates in
+    /// [range mode](ObjectStoreCache#range-mode).
+    ///
+    /// # Panic
+    ///
+    /// Panics if the disk cache directory cannot be set up.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         catalog: Arc<dyn Catalog>,
         time_provider: Arc<dyn TimeProvider>,
@@ -60,6 +82,9 @@ impl CatalogCache {
         object_store: Arc<dyn ObjectStore>,
         ram_pool_metadata_bytes: usize,
         ram_pool_data_bytes: usize,
+        disk_cache_dir: Option<PathBuf>,
+        disk_pool_data_bytes: usize,
+        cache_object_ranges: bool,
         handle: &Handle,
     ) -> Self {
         Self::new_internal(
@@ -69,6 +94,8 @@ impl CatalogCache {
             object_store,
             ram_pool_metadata_bytes,
             ram_pool_data_bytes,
+            disk_cache_dir.map(|dir| (dir, disk_pool_data_bytes)),
+            cache_object_ranges,
             handle,
             false,
         )
@@ -91,6 +118,8 @@ impl CatalogCache {
             object_store,
             usize::MAX,
             usize::MAX,
+            None,
+            false,
             handle,
             true,
         )
@@ -104,6 +133,8 @@ impl CatalogCache {
         object_store: Arc<dyn ObjectStore>,
         ram_pool_metadata_bytes: usize,
         ram_pool_data_bytes: usize,
+        disk_cache: Option<(PathBuf, usize)>,
+        cache_object_ranges: bool,
         handle: &Handle,
         testing: bool,
     ) -> Self {
@@ -119,6 +150,21 @@ impl CatalogCache {
             RamSize(ram_pool_data_bytes),
             Arc::clone(&metric_registry),
         ));
+        let disk_cache = disk_cache.map(|(dir, disk_pool_data_bytes)| {
+            let disk_pool_data = Arc::new(ResourcePool::new(
+                "disk_data",
+                DiskSize(disk_pool_data_bytes),
+                Arc::clone(&metric_registry),
+            ));
+            let disk_cache = DiskCache::new(
+                &dir,
+                disk_pool_data,
+                Arc::clone(&time_provider),
+                &metric_registry,
+            )
+            .unwrap_or_else(|e| panic!("cannot set up disk cache in {}: {e}", dir.display()));
+            Arc::new(disk_cache)
+        });
 
         let partition_cache = PartitionCache::new(
             Arc::clone(&catalog),
@@ -151,12 +197,22 @@ impl CatalogCache {
             Arc::clone(&ram_pool_metadata),
             testing,
         );
+        let tombstone_cache = TombstoneCache::new(
+            Arc::clone(&catalog),
+            backoff_config.clone(),
+            Arc::clone(&time_provider),
+            &metric_registry,
+            Arc::clone(&ram_pool_metadata),
+            testing,
+        );
         let object_store_cache = ObjectStoreCache::new(
             backoff_config,
             object_store,
             Arc::clone(&time_provider),
             &metric_registry,
             Arc::clone(&ram_pool_data),
+            disk_cache,
+            cache_object_ranges,
             testing,
         );
 
@@ -166,6 +222,7 @@ impl CatalogCache {
             namespace_cache,
             parquet_file_cache,
             projected_schema_cache,
+            tombstone_cache,
             object_store_cache,
             metric_registry,
             time_provider,
@@ -207,6 +264,11 @@ impl CatalogCache {
         &self.projected_schema_cache
     }
 
+    /// Tombstone cache.
+    pub(crate) fn tombstone(&self) -> &TombstoneCache {
+        &self.tombstone_cache
+    }
+
     /// Object store cache.
     #[allow(dead_code)]
     pub(crate) fn object_store(&self) -> &ObjectStoreCache {
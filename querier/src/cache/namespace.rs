@@ -13,7 +13,9 @@ use cache_system::{
     loader::{metrics::MetricsLoader, FunctionLoader},
     resource_consumption::FunctionEstimator,
 };
-use data_types::{ColumnId, NamespaceId, NamespaceSchema, TableId, TableSchema};
+use data_types::{
+    ColumnId, NamespaceId, NamespaceQueryLimits, NamespaceSchema, TableId, TableSchema,
+};
 use iox_catalog::interface::{get_schema_by_name, Catalog, SoftDeletedRows};
 use iox_time::TimeProvider;
 use schema::Schema;
@@ -98,7 +100,19 @@ impl NamespaceCache {
                         )
                         .await
                         {
-                            Ok(schema) => Ok(Some(schema)),
+                            Ok(schema) => {
+                                // Parquet files may still reference soft-deleted columns, so
+                                // remember their IDs to avoid expiring the namespace for them.
+                                let deleted_column_ids = repos
+                                    .columns()
+                                    .list_by_namespace_id(schema.id)
+                                    .await?
+                                    .into_iter()
+                                    .filter(|c| c.deleted_at.is_some())
+                                    .map(|c| c.id)
+                                    .collect::<HashSet<_>>();
+                                Ok(Some((schema, deleted_column_ids)))
+                            }
                             Err(iox_catalog::interface::Error::NamespaceNotFoundByName {
                                 ..
                             }) => Ok(None),
@@ -106,9 +120,13 @@ impl NamespaceCache {
                         }
                     })
                     .await
-                    .expect("retry forever")?;
+                    .expect("retry forever");
+                let (schema, mut deleted_column_ids) = schema?;
 
-                Some(Arc::new(schema.into()))
+                let mut namespace = CachedNamespace::from(schema);
+                deleted_column_ids.shrink_to_fit();
+                namespace.deleted_column_ids = deleted_column_ids;
+                Some(Arc::new(namespace))
             }
         });
         let loader = Arc::new(MetricsLoader::new(
@@ -175,7 +193,8 @@ impl NamespaceCache {
     /// Get namespace schema by name.
     ///
     /// Expire namespace if the cached schema does NOT cover the given set of columns. The set is given as a list of
-    /// pairs of table name and column set.
+    /// pairs of table name and column set. Soft-deleted columns are never part of the schema and do not cause an
+    /// expiry.
     pub async fn get(
         &self,
         name: Arc<str>,
@@ -192,6 +211,7 @@ impl NamespaceCache {
                             if let Some(table) = namespace.tables.get(*table_name) {
                                 columns
                                     .iter()
+                                    .filter(|col| !namespace.deleted_column_ids.contains(col))
                                     .any(|col| !table.column_id_map.contains_key(col))
                             } else {
                                 // table unknown => need to update
@@ -284,7 +304,10 @@ impl From<TableSchema> for CachedTable {
 pub struct CachedNamespace {
     pub id: NamespaceId,
     pub retention_period: Option<Duration>,
+    pub query_limits: NamespaceQueryLimits,
     pub tables: HashMap<Arc<str>, Arc<CachedTable>>,
+    /// IDs of the soft-deleted columns of this namespace, which are excluded from `tables`.
+    pub deleted_column_ids: HashSet<ColumnId>,
 }
 
 impl CachedNamespace {
@@ -296,6 +319,7 @@ impl CachedNamespace {
                 .iter()
                 .map(|(name, table)| name.len() + table.size())
                 .sum::<usize>()
+            + self.deleted_column_ids.capacity() * size_of::<ColumnId>()
     }
 }
 
@@ -317,7 +341,9 @@ impl From<NamespaceSchema> for CachedNamespace {
         Self {
             id: ns.id,
             retention_period,
+            query_limits: ns.query_limits,
             tables,
+            deleted_column_ids: HashSet::new(),
         }
     }
 }
@@ -372,6 +398,7 @@ mod tests {
         let expected_ns_1 = CachedNamespace {
             id: ns1.namespace.id,
             retention_period,
+            query_limits: Default::default(),
             tables: HashMap::from([
                 (
                     Arc::from("table1"),
@@ -419,6 +446,7 @@ mod tests {
                     }),
                 ),
             ]),
+            deleted_column_ids: HashSet::new(),
         };
         assert_eq!(actual_ns_1_a.as_ref(), &expected_ns_1);
         assert_histogram_metric_count(&catalog.metric_registry, "namespace_get_by_name", 1);
@@ -434,6 +462,7 @@ mod tests {
         let expected_ns_2 = CachedNamespace {
             id: ns2.namespace.id,
             retention_period,
+            query_limits: Default::default(),
             tables: HashMap::from([(
                 Arc::from("table1"),
                 Arc::new(CachedTable {
@@ -450,6 +479,7 @@ mod tests {
                     primary_key_column_ids: vec![col211.column.id],
                 }),
             )]),
+            deleted_column_ids: HashSet::new(),
         };
         assert_eq!(actual_ns_2.as_ref(), &expected_ns_2);
         assert_histogram_metric_count(&catalog.metric_registry, "namespace_get_by_name", 2);
@@ -571,5 +601,52 @@ mod tests {
             .await
             .is_some());
         assert_histogram_metric_count(&catalog.metric_registry, "namespace_get_by_name", 6);
+
+        // ========== soft-deleted columns ==========
+        catalog
+            .catalog()
+            .repositories()
+            .await
+            .columns()
+            .soft_delete(c2.column.id)
+            .await
+            .unwrap();
+
+        // the cached schema still covers c2
+        let ns = cache
+            .get(
+                Arc::from("ns1"),
+                &[("t1", &HashSet::from([c2.column.id]))],
+                None,
+            )
+            .await
+            .unwrap();
+        assert_histogram_metric_count(&catalog.metric_registry, "namespace_get_by_name", 6);
+        assert!(ns.tables["t1"].column_id_map.contains_key(&c2.column.id));
+
+        // a new column forces a refresh, which excludes c2 ...
+        let c3 = t1.create_column("c3", ColumnType::Bool).await;
+        let ns = cache
+            .get(
+                Arc::from("ns1"),
+                &[("t1", &HashSet::from([c3.column.id]))],
+                None,
+            )
+            .await
+            .unwrap();
+        assert_histogram_metric_count(&catalog.metric_registry, "namespace_get_by_name", 7);
+        assert!(!ns.tables["t1"].column_id_map.contains_key(&c2.column.id));
+        assert!(ns.deleted_column_ids.contains(&c2.column.id));
+
+        // ... and files that still contain c2 do not expire the namespace again
+        assert!(cache
+            .get(
+                Arc::from("ns1"),
+                &[("t1", &HashSet::from([c1.column.id, c2.column.id]))],
+                None
+            )
+            .await
+            .is_some());
+        assert_histogram_metric_count(&catalog.metric_registry, "namespace_get_by_name", 7);
     }
 }
@@ -22,9 +22,11 @@ use object_store::{
 use tokio::io::AsyncWrite;
 use trace::span::Span;
 
-use super::ram::RamSize;
+use super::{disk::DiskCache, ram::RamSize};
 
 const CACHE_ID: &str = "object_store";
+const CACHE_ID_SIZE: &str = "object_store_size";
+const CACHE_ID_RANGE: &str = "object_store_range";
 
 async fn read_from_store(
     store: &dyn ObjectStore,
@@ -45,6 +47,36 @@ async fn read_from_store(
     Ok(Some(data))
 }
 
+async fn read_size_from_store(
+    store: &dyn ObjectStore,
+    path: &Path,
+) -> Result<Option<usize>, ObjectStoreError> {
+    match store.head(path).await {
+        Ok(meta) => Ok(Some(meta.size)),
+        Err(ObjectStoreError::NotFound { .. }) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+async fn read_range_from_store(
+    store: &dyn ObjectStore,
+    key: &RangeKey,
+) -> Result<Option<Bytes>, ObjectStoreError> {
+    match store.get_range(&key.path, key.start..key.end).await {
+        Ok(data) => Ok(Some(data)),
+        Err(ObjectStoreError::NotFound { .. }) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Byte range of an object.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct RangeKey {
+    path: Path,
+    start: usize,
+    end: usize,
+}
+
 type CacheT = Box<
     dyn Cache<
         K = Path,
@@ -54,6 +86,24 @@ type CacheT = Box<
     >,
 >;
 
+type SizeCacheT = Box<
+    dyn Cache<
+        K = Path,
+        V = Option<usize>,
+        GetExtra = ((), Option<Span>),
+        PeekExtra = ((), Option<Span>),
+    >,
+>;
+
+type RangeCacheT = Box<
+    dyn Cache<
+        K = RangeKey,
+        V = Option<Bytes>,
+        GetExtra = ((), Option<Span>),
+        PeekExtra = ((), Option<Span>),
+    >,
+>;
+
 /// Cache for object store read operation.
 ///
 /// This assumes that objects are written once and are NEVER modified afterwards. Deletions are NOT propagated into the
@@ -61,6 +111,20 @@ type CacheT = Box<
 ///
 /// ["Not found"](ObjectStoreError::NotFound) results are cached forever, so make sure to only retrieve objects that
 /// shall exist.
+///
+/// Objects are kept in RAM. If a [`DiskCache`] is provided, it is used as a second tier that is consulted before the
+/// underlying object store is and that is populated with every object read from that store.
+///
+/// # Range Mode
+///
+/// By default, whole objects are fetched and cached, even if only a few byte ranges are read. For parquet files this
+/// means that the entire file is cached when a query only needs the footer, the page index and a few column chunks.
+///
+/// If range caching is enabled, [`get_range`](ObjectStore::get_range) and [`get_ranges`](ObjectStore::get_ranges)
+/// requests instead fetch and cache the requested ranges individually, keyed by object path and range, next to the
+/// object sizes that are used to validate these ranges and to answer [`head`](ObjectStore::head) requests.
+/// Overlapping ranges are cached independently. Full [`get`](ObjectStore::get) requests still cache whole objects,
+/// which is also the only granularity the disk tier operates on.
 #[derive(Debug)]
 pub struct ObjectStoreCache {
     // this is the virtual object store
@@ -75,15 +139,35 @@ impl ObjectStoreCache {
         time_provider: Arc<dyn TimeProvider>,
         metric_registry: &metric::Registry,
         ram_pool: Arc<ResourcePool<RamSize>>,
+        disk_cache: Option<Arc<DiskCache>>,
+        cache_ranges: bool,
         testing: bool,
     ) -> Self {
+        let range_caches = cache_ranges.then(|| {
+            RangeCaches::new(
+                backoff_config.clone(),
+                Arc::clone(&object_store),
+                Arc::clone(&time_provider),
+                metric_registry,
+                Arc::clone(&ram_pool),
+                testing,
+            )
+        });
+
         let object_store_captured = Arc::clone(&object_store);
         let loader = FunctionLoader::new(move |key: Path, _extra: ()| {
             let backoff_config = backoff_config.clone();
             let object_store = Arc::clone(&object_store_captured);
+            let disk_cache = disk_cache.clone();
 
             async move {
-                Backoff::new(&backoff_config)
+                if let Some(disk_cache) = &disk_cache {
+                    if let Some(data) = disk_cache.get(&key).await {
+                        return Some(data);
+                    }
+                }
+
+                let data = Backoff::new(&backoff_config)
                     .retry_all_errors::<_, _, _, ObjectStoreError>(
                         "get object from object store",
                         || async {
@@ -93,7 +177,13 @@ impl ObjectStoreCache {
                         },
                     )
                     .await
-                    .expect("retry forever")
+                    .expect("retry forever");
+
+                if let (Some(disk_cache), Some(data)) = (&disk_cache, &data) {
+                    disk_cache.put(&key, data).await;
+                }
+
+                data
             }
         });
         let loader = Arc::new(MetricsLoader::new(
@@ -129,6 +219,7 @@ impl ObjectStoreCache {
 
         let object_store = Arc::new(CachedObjectStore {
             cache,
+            range_caches,
             inner: object_store,
         });
 
@@ -142,9 +233,140 @@ impl ObjectStoreCache {
     }
 }
 
+/// Caches used in [range mode](ObjectStoreCache#range-mode).
+#[derive(Debug)]
+struct RangeCaches {
+    size: SizeCacheT,
+    range: RangeCacheT,
+}
+
+impl RangeCaches {
+    fn new(
+        backoff_config: BackoffConfig,
+        object_store: Arc<dyn ObjectStore>,
+        time_provider: Arc<dyn TimeProvider>,
+        metric_registry: &metric::Registry,
+        ram_pool: Arc<ResourcePool<RamSize>>,
+        testing: bool,
+    ) -> Self {
+        let backoff_config_captured = backoff_config.clone();
+        let object_store_captured = Arc::clone(&object_store);
+        let loader = FunctionLoader::new(move |key: Path, _extra: ()| {
+            let backoff_config = backoff_config_captured.clone();
+            let object_store = Arc::clone(&object_store_captured);
+
+            async move {
+                Backoff::new(&backoff_config)
+                    .retry_all_errors::<_, _, _, ObjectStoreError>(
+                        "get object size from object store",
+                        || async { read_size_from_store(object_store.as_ref(), &key).await },
+                    )
+                    .await
+                    .expect("retry forever")
+            }
+        });
+        let loader = Arc::new(MetricsLoader::new(
+            loader,
+            CACHE_ID_SIZE,
+            Arc::clone(&time_provider),
+            metric_registry,
+            testing,
+        ));
+
+        let mut backend = PolicyBackend::hashmap_backed(Arc::clone(&time_provider));
+        backend.add_policy(LruPolicy::new(
+            Arc::clone(&ram_pool),
+            CACHE_ID_SIZE,
+            Arc::new(FunctionEstimator::new(|k: &Path, v: &Option<usize>| {
+                RamSize(size_of_val(k) + k.as_ref().len() + size_of_val(v))
+            })),
+        ));
+
+        let size = CacheDriver::new(loader, backend);
+        let size = Box::new(CacheWithMetrics::new(
+            size,
+            CACHE_ID_SIZE,
+            Arc::clone(&time_provider),
+            metric_registry,
+        ));
+
+        let loader = FunctionLoader::new(move |key: RangeKey, _extra: ()| {
+            let backoff_config = backoff_config.clone();
+            let object_store = Arc::clone(&object_store);
+
+            async move {
+                Backoff::new(&backoff_config)
+                    .retry_all_errors::<_, _, _, ObjectStoreError>(
+                        "get object range from object store",
+                        || async { read_range_from_store(object_store.as_ref(), &key).await },
+                    )
+                    .await
+                    .expect("retry forever")
+            }
+        });
+        let loader = Arc::new(MetricsLoader::new(
+            loader,
+            CACHE_ID_RANGE,
+            Arc::clone(&time_provider),
+            metric_registry,
+            testing,
+        ));
+
+        let mut backend = PolicyBackend::hashmap_backed(Arc::clone(&time_provider));
+        backend.add_policy(LruPolicy::new(
+            ram_pool,
+            CACHE_ID_RANGE,
+            Arc::new(FunctionEstimator::new(|k: &RangeKey, v: &Option<Bytes>| {
+                RamSize(
+                    size_of_val(k)
+                        + k.path.as_ref().len()
+                        + size_of_val(v)
+                        + v.as_ref().map(|v| v.len()).unwrap_or_default(),
+                )
+            })),
+        ));
+
+        let range = CacheDriver::new(loader, backend);
+        let range = Box::new(CacheWithMetrics::new(
+            range,
+            CACHE_ID_RANGE,
+            time_provider,
+            metric_registry,
+        ));
+
+        Self { size, range }
+    }
+}
+
+fn not_found(location: &Path) -> ObjectStoreError {
+    ObjectStoreError::NotFound {
+        path: location.to_string(),
+        source: String::from("not found").into(),
+    }
+}
+
+/// Ensure that `range` is a valid range of an object with `len` bytes.
+fn check_range(range: &Range<usize>, len: usize) -> Result<(), ObjectStoreError> {
+    if range.end > len {
+        return Err(ObjectStoreError::Generic {
+            store: "CachedObjectStore",
+            source: format!("Out of range: len={}, range end={}", len, range.end).into(),
+        });
+    }
+    if range.start > range.end {
+        return Err(ObjectStoreError::Generic {
+            store: "CachedObjectStore",
+            source: format!("Invalid range: start={}, end={}", range.start, range.end).into(),
+        });
+    }
+
+    Ok(())
+}
+
 #[derive(Debug)]
 struct CachedObjectStore {
     cache: CacheT,
+    range_caches: Option<RangeCaches>,
     inner: Arc<dyn ObjectStore>,
 }
 
@@ -153,10 +375,18 @@ impl CachedObjectStore {
         self.cache
             .get(location.clone(), ((), None))
             .await
-            .ok_or_else(|| ObjectStoreError::NotFound {
-                path: location.to_string(),
-                source: String::from("not found").into(),
-            })
+            .ok_or_else(|| not_found(location))
+    }
+
+    async fn get_size(&self, location: &Path) -> Result<usize, ObjectStoreError> {
+        match &self.range_caches {
+            Some(range_caches) => range_caches
+                .size
+                .get(location.clone(), ((), None))
+                .await
+                .ok_or_else(|| not_found(location)),
+            None => Ok(self.get_data(location).await?.len()),
+        }
     }
 }
 
@@ -200,33 +430,49 @@ impl ObjectStore for CachedObjectStore {
         location: &Path,
         range: Range<usize>,
     ) -> Result<Bytes, ObjectStoreError> {
-        let data = self.get_data(location).await?;
-
-        if range.end > data.len() {
-            return Err(ObjectStoreError::Generic {
-                store: "CachedObjectStore",
-                source: format!("Out of range: len={}, range end={}", data.len(), range.end).into(),
-            });
-        }
-        if range.start > range.end {
-            return Err(ObjectStoreError::Generic {
-                store: "CachedObjectStore",
-                source: format!("Invalid range: start={}, end={}", range.start, range.end).into(),
-            });
-        }
+        let Some(range_caches) = &self.range_caches else {
+            let data = self.get_data(location).await?;
+            check_range(&range, data.len())?;
+            return Ok(data.slice(range));
+        };
+
+        check_range(&range, self.get_size(location).await?)?;
+
+        let key = RangeKey {
+            path: location.clone(),
+            start: range.start,
+            end: range.end,
+        };
+        range_caches
+            .range
+            .get(key, ((), None))
+            .await
+            .ok_or_else(|| not_found(location))
+    }
 
-        Ok(data.slice(range))
+    async fn get_ranges(
+        &self,
+        location: &Path,
+        ranges: &[Range<usize>],
+    ) -> Result<Vec<Bytes>, ObjectStoreError> {
+        // Don't coalesce ranges like the default implementation does, so that every range maps to a stable cache key.
+        futures::future::try_join_all(
+            ranges
+                .iter()
+                .map(|range| self.get_range(location, range.clone())),
+        )
+        .await
     }
 
     async fn head(&self, location: &Path) -> Result<ObjectMeta, ObjectStoreError> {
-        let data = self.get_data(location).await?;
+        let size = self.get_size(location).await?;
 
         Ok(ObjectMeta {
             location: location.clone(),
             // nobody really cares about the "last modified" field and it is wasteful to issue a HEAD request just to
             // retrieve it.
             last_modified: Default::default(),
-            size: data.len(),
+            size,
         })
     }
 
@@ -266,7 +512,7 @@ mod tests {
     use object_store::memory::InMemory;
     use object_store_metrics::ObjectStoreMetrics;
 
-    use crate::cache::ram::test_util::test_ram_pool;
+    use crate::cache::{disk::test_util::test_disk_pool, ram::test_util::test_ram_pool};
 
     use super::*;
 
@@ -304,6 +550,8 @@ mod tests {
             time_provider,
             &metric_registry,
             test_ram_pool(),
+            None,
+            false,
             true,
         );
         let cached_store = cache.object_store();
@@ -410,6 +658,150 @@ mod tests {
         assert_eq!(get_count_miss(&metric_registry), 1);
     }
 
+    #[tokio::test]
+    async fn test_disk_tier() {
+        let inner = Arc::new(InMemory::new());
+        let path = Path::from("foo");
+        let bytes = Bytes::from(b"data_foo" as &'static [u8]);
+        inner.put(&path, bytes.clone()).await.unwrap();
+
+        let dir = test_helpers::tmp_dir().unwrap();
+        let metric_registry = metric::Registry::new();
+        let time_provider = Arc::new(SystemProvider::new());
+        let cache = |metric_registry: &metric::Registry| {
+            let disk_cache = DiskCache::new(
+                dir.path(),
+                test_disk_pool(usize::MAX),
+                Arc::clone(&time_provider) as _,
+                metric_registry,
+            )
+            .unwrap();
+            ObjectStoreCache::new(
+                BackoffConfig::default(),
+                Arc::clone(&inner) as _,
+                Arc::clone(&time_provider) as _,
+                metric_registry,
+                test_ram_pool(),
+                Some(Arc::new(disk_cache)),
+                false,
+                true,
+            )
+        };
+
+        // populates both tiers
+        let cached_store = Arc::clone(cache(&metric_registry).object_store());
+        assert_eq!(cached_store.get_range(&path, 0..8).await.unwrap(), bytes);
+        assert_eq!(disk_count(&metric_registry, "miss"), 1);
+
+        // served from RAM
+        assert_eq!(cached_store.get_range(&path, 0..8).await.unwrap(), bytes);
+        assert_eq!(disk_count(&metric_registry, "miss"), 1);
+        assert_eq!(disk_count(&metric_registry, "hit"), 0);
+
+        // a new cache (e.g. after a restart) is served from disk, even if the object is gone from the store
+        inner.delete(&path).await.unwrap();
+        let metric_registry = metric::Registry::new();
+        let cached_store = Arc::clone(cache(&metric_registry).object_store());
+        assert_eq!(cached_store.get_range(&path, 0..8).await.unwrap(), bytes);
+        assert_eq!(disk_count(&metric_registry, "hit"), 1);
+        assert_eq!(disk_count(&metric_registry, "miss"), 0);
+
+        // "not found" results are not stored on disk
+        let path_2 = Path::from("bar");
+        assert_matches!(
+            cached_store.get(&path_2).await.unwrap_err(),
+            ObjectStoreError::NotFound { .. }
+        );
+        assert!(!dir.path().join("objects/bar").exists());
+    }
+
+    #[tokio::test]
+    async fn test_range_mode() {
+        let inner = Arc::new(InMemory::new());
+        let path_1 = Path::from("foo");
+        inner
+            .put(&path_1, Bytes::from(b"0123456789" as &'static [u8]))
+            .await
+            .unwrap();
+        let path_2 = Path::from("bar");
+
+        let metric_registry = metric::Registry::new();
+        let time_provider = Arc::new(SystemProvider::new());
+        let instrumented_store = ObjectStoreMetrics::new(
+            Arc::clone(&inner) as _,
+            Arc::clone(&time_provider) as _,
+            &metric_registry,
+        );
+        let ram_pool = test_ram_pool();
+        let cache = ObjectStoreCache::new(
+            BackoffConfig::default(),
+            Arc::new(instrumented_store),
+            time_provider,
+            &metric_registry,
+            Arc::clone(&ram_pool),
+            None,
+            true,
+            true,
+        );
+        let cached_store = cache.object_store();
+
+        // ranges are fetched individually and cached
+        assert_eq!(
+            cached_store.get_range(&path_1, 0..4).await.unwrap(),
+            Bytes::from(b"0123" as &'static [u8]),
+        );
+        assert_eq!(op_count(&metric_registry, "get_range", "success"), 1);
+        assert_eq!(op_count(&metric_registry, "head", "success"), 1);
+        let ram_usage = ram_pool.current();
+        assert!(ram_usage > RamSize(4));
+
+        assert_eq!(
+            cached_store.get_range(&path_1, 0..4).await.unwrap(),
+            Bytes::from(b"0123" as &'static [u8]),
+        );
+        assert_eq!(op_count(&metric_registry, "get_range", "success"), 1);
+        assert_eq!(ram_pool.current(), ram_usage);
+
+        assert_eq!(
+            cached_store
+                .get_ranges(&path_1, &[0..4, 6..10])
+                .await
+                .unwrap(),
+            vec![
+                Bytes::from(b"0123" as &'static [u8]),
+                Bytes::from(b"6789" as &'static [u8]),
+            ],
+        );
+        assert_eq!(op_count(&metric_registry, "get_range", "success"), 2);
+        assert!(ram_pool.current() > ram_usage);
+
+        // the object size is cached as well
+        assert_eq!(cached_store.head(&path_1).await.unwrap().size, 10);
+        assert_eq!(op_count(&metric_registry, "head", "success"), 1);
+
+        // invalid ranges are rejected without a request
+        assert_matches!(
+            cached_store.get_range(&path_1, 8..11).await.unwrap_err(),
+            ObjectStoreError::Generic { .. }
+        );
+        assert_eq!(op_count(&metric_registry, "get_range", "success"), 2);
+        assert_eq!(op_count(&metric_registry, "get_range", "error"), 0);
+
+        // "not found" results are cached
+        assert_matches!(
+            cached_store.get_range(&path_2, 0..1).await.unwrap_err(),
+            ObjectStoreError::NotFound { .. }
+        );
+        assert_matches!(
+            cached_store.head(&path_2).await.unwrap_err(),
+            ObjectStoreError::NotFound { .. }
+        );
+        assert_eq!(op_count(&metric_registry, "head", "error"), 1);
+
+        // whole objects are never fetched
+        assert_eq!(op_count(&metric_registry, "get", "success"), 0);
+    }
+
     async fn list(store: &dyn ObjectStore) -> Vec<Path> {
         let mut paths: Vec<_> = store
             .list(None)
@@ -453,6 +845,29 @@ mod tests {
             .sample_count()
     }
 
+    fn disk_count(metric_registry: &metric::Registry, status: &'static str) -> u64 {
+        metric_registry
+            .get_instrument::<Metric<DurationHistogram>>("iox_cache_get")
+            .unwrap()
+            .get_observer(&Attributes::from(&[
+                ("name", "object_store_disk"),
+                ("status", status),
+            ]))
+            .unwrap()
+            .fetch()
+            .sample_count()
+    }
+
+    fn op_count(metric_registry: &metric::Registry, op: &'static str, result: &'static str) -> u64 {
+        metric_registry
+            .get_instrument::<Metric<DurationHistogram>>("object_store_op_duration")
+            .unwrap()
+            .get_observer(&Attributes::from(&[("op", op), ("result", result)]))
+            .unwrap()
+            .fetch()
+            .sample_count()
+    }
+
     fn list_count(metric_registry: &metric::Registry) -> u64 {
         metric_registry
             .get_instrument::<Metric<DurationHistogram>>("object_store_op_duration")
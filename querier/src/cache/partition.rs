@@ -11,7 +11,7 @@ use cache_system::{
     loader::{metrics::MetricsLoader, FunctionLoader},
     resource_consumption::FunctionEstimator,
 };
-use data_types::{ColumnId, PartitionId};
+use data_types::{ColumnId, PartitionId, Timestamp};
 use iox_catalog::interface::Catalog;
 use iox_time::TimeProvider;
 use schema::sort::SortKey;
@@ -74,7 +74,11 @@ impl PartitionCache {
                         Arc::new(PartitionSortKey::new(sort_key, &extra.column_id_map_rev))
                     });
 
-                    Some(CachedPartition { sort_key })
+                    Some(CachedPartition {
+                        key: Arc::from(partition.partition_key.to_string()),
+                        sort_key,
+                        new_file_at: partition.new_file_at,
+                    })
                 }
             });
         let loader = Arc::new(MetricsLoader::new(
@@ -144,21 +148,45 @@ impl PartitionCache {
             .await
             .and_then(|p| p.sort_key)
     }
+
+    /// Get the cached attributes of a partition, or [`None`] if the
+    /// partition does not exist.
+    ///
+    /// The cached entry is never expired by this call, so the returned
+    /// attributes may be outdated.
+    pub async fn get(
+        &self,
+        cached_table: Arc<CachedTable>,
+        partition_id: PartitionId,
+        span: Option<Span>,
+    ) -> Option<CachedPartition> {
+        self.cache.get(partition_id, (cached_table, span)).await
+    }
 }
 
+/// The attributes of a partition held by the [`PartitionCache`].
 #[derive(Debug, Clone)]
-struct CachedPartition {
-    sort_key: Option<Arc<PartitionSortKey>>,
+pub struct CachedPartition {
+    /// The partition key.
+    pub key: Arc<str>,
+
+    /// The sort key, if any data of the partition has been persisted.
+    pub sort_key: Option<Arc<PartitionSortKey>>,
+
+    /// The time at which the newest file of the partition was created.
+    pub new_file_at: Option<Timestamp>,
 }
 
 impl CachedPartition {
     /// RAM-bytes EXCLUDING `self`.
     fn size(&self) -> usize {
         // Arc content
-        self.sort_key
-            .as_ref()
-            .map(|sk| sk.size())
-            .unwrap_or_default()
+        self.key.len()
+            + self
+                .sort_key
+                .as_ref()
+                .map(|sk| sk.size())
+                .unwrap_or_default()
     }
 }
 
@@ -294,6 +322,67 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_get() {
+        let catalog = TestCatalog::new();
+
+        let ns = catalog.create_namespace_1hr_retention("ns").await;
+        let t = ns.create_table("table").await;
+        let c1 = t.create_column("tag", ColumnType::Tag).await;
+        let c2 = t.create_column("time", ColumnType::Time).await;
+        let p1 = t
+            .create_partition_with_sort_key("k1", &["tag", "time"])
+            .await
+            .partition
+            .clone();
+        let cached_table = Arc::new(CachedTable {
+            id: t.table.id,
+            schema: schema(),
+            column_id_map: HashMap::from([
+                (c1.column.id, Arc::from(c1.column.name.clone())),
+                (c2.column.id, Arc::from(c2.column.name.clone())),
+            ]),
+            column_id_map_rev: HashMap::from([
+                (Arc::from(c1.column.name.clone()), c1.column.id),
+                (Arc::from(c2.column.name.clone()), c2.column.id),
+            ]),
+            primary_key_column_ids: vec![c1.column.id, c2.column.id],
+        });
+
+        let cache = PartitionCache::new(
+            catalog.catalog(),
+            BackoffConfig::default(),
+            catalog.time_provider(),
+            &catalog.metric_registry(),
+            test_ram_pool(),
+            true,
+        );
+
+        let cached = cache
+            .get(Arc::clone(&cached_table), p1.id, None)
+            .await
+            .unwrap();
+        assert_eq!(cached.key.as_ref(), "k1");
+        assert_eq!(
+            cached.sort_key.unwrap().sort_key.as_ref(),
+            &p1.sort_key().unwrap()
+        );
+        assert_eq!(cached.new_file_at, p1.new_file_at);
+        assert_histogram_metric_count(&catalog.metric_registry, "partition_get_by_id", 1);
+
+        // Shares the entry loaded for the sort key.
+        cache
+            .sort_key(Arc::clone(&cached_table), p1.id, &Vec::new(), None)
+            .await;
+        assert_histogram_metric_count(&catalog.metric_registry, "partition_get_by_id", 1);
+
+        // Unknown partition.
+        assert!(cache
+            .get(cached_table, PartitionId::new(i64::MAX), None)
+            .await
+            .is_none());
+    }
+
     #[tokio::test]
     async fn test_cache_sharing() {
         let catalog = TestCatalog::new();
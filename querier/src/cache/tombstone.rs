@@ -0,0 +1,306 @@
+//! Tombstone cache.
+
+use backoff::{Backoff, BackoffConfig};
+use cache_system::{
+    backend::policy::{
+        lru::{LruPolicy, ResourcePool},
+        remove_if::{RemoveIfHandle, RemoveIfPolicy},
+        PolicyBackend,
+    },
+    cache::{driver::CacheDriver, metrics::CacheWithMetrics, Cache},
+    loader::{metrics::MetricsLoader, FunctionLoader},
+    resource_consumption::FunctionEstimator,
+};
+use data_types::{DeletePredicate, TableId, Timestamp};
+use iox_catalog::interface::Catalog;
+use iox_time::{Time, TimeProvider};
+use observability_deps::tracing::warn;
+use predicate::delete_predicate::parse_tombstone_predicate;
+use std::{mem, sync::Arc, time::Duration};
+use trace::span::Span;
+
+use super::ram::RamSize;
+
+/// Duration after which the tombstones of a table are re-read from the catalog.
+///
+/// Deletes are not visible to queries until the cached entry of the affected
+/// table expires.
+pub const TTL: Duration = Duration::from_secs(60);
+
+const CACHE_ID: &str = "tombstone";
+
+/// A tombstone recorded for a table, with its parsed predicate.
+#[derive(Debug, Clone)]
+pub struct CachedTombstone {
+    /// When the delete was requested.
+    pub created_at: Timestamp,
+
+    /// The parsed tombstone predicate.
+    pub delete_predicate: Arc<DeletePredicate>,
+}
+
+impl CachedTombstone {
+    /// Returns true if this tombstone removes rows from a parquet file with the
+    /// given `max_l0_created_at`.
+    ///
+    /// See [`Tombstone::applies_to`](data_types::Tombstone::applies_to).
+    pub fn applies_to(&self, max_l0_created_at: Timestamp) -> bool {
+        max_l0_created_at < self.created_at
+    }
+}
+
+/// All tombstones recorded for a table.
+#[derive(Debug)]
+pub struct CachedTombstones {
+    /// Parsed tombstones, ordered by tombstone ID.
+    pub tombstones: Arc<Vec<CachedTombstone>>,
+
+    /// Time at which this entry was loaded from the catalog.
+    loaded_at: Time,
+}
+
+impl CachedTombstones {
+    /// Estimate the memory consumption of this object and its contents.
+    fn size(&self) -> usize {
+        mem::size_of_val(self)
+            + mem::size_of_val(self.tombstones.as_ref())
+            + self.tombstones.capacity() * mem::size_of::<CachedTombstone>()
+            + self
+                .tombstones
+                .iter()
+                .map(|t| t.delete_predicate.size())
+                .sum::<usize>()
+    }
+}
+
+type CacheT = Box<
+    dyn Cache<
+        K = TableId,
+        V = Arc<CachedTombstones>,
+        GetExtra = ((), Option<Span>),
+        PeekExtra = ((), Option<Span>),
+    >,
+>;
+
+/// Cache for the tombstones of a table.
+#[derive(Debug)]
+pub struct TombstoneCache {
+    cache: CacheT,
+    remove_if_handle: RemoveIfHandle<TableId, Arc<CachedTombstones>>,
+    time_provider: Arc<dyn TimeProvider>,
+}
+
+impl TombstoneCache {
+    /// Create new empty cache.
+    pub fn new(
+        catalog: Arc<dyn Catalog>,
+        backoff_config: BackoffConfig,
+        time_provider: Arc<dyn TimeProvider>,
+        metric_registry: &metric::Registry,
+        ram_pool: Arc<ResourcePool<RamSize>>,
+        testing: bool,
+    ) -> Self {
+        let time_provider_captured = Arc::clone(&time_provider);
+        let loader = FunctionLoader::new(move |table_id: TableId, _extra: ()| {
+            let catalog = Arc::clone(&catalog);
+            let backoff_config = backoff_config.clone();
+            let time_provider = Arc::clone(&time_provider_captured);
+
+            async move {
+                let tombstones = Backoff::new(&backoff_config)
+                    .retry_all_errors("get tombstones", || async {
+                        catalog
+                            .repositories()
+                            .await
+                            .tombstones()
+                            .list_by_table_id(table_id)
+                            .await
+                    })
+                    .await
+                    .expect("retry forever");
+
+                // The router validates predicates before recording them, so a
+                // tombstone that does not parse indicates catalog corruption.
+                // Skip it rather than failing every query against the table.
+                let mut tombstones: Vec<_> = tombstones
+                    .iter()
+                    .filter_map(|t| match parse_tombstone_predicate(t) {
+                        Ok(p) => Some(CachedTombstone {
+                            created_at: t.created_at,
+                            delete_predicate: Arc::new(p),
+                        }),
+                        Err(e) => {
+                            warn!(
+                                tombstone_id=%t.id,
+                                %table_id,
+                                error=%e,
+                                "ignoring invalid tombstone"
+                            );
+                            None
+                        }
+                    })
+                    .collect();
+                tombstones.shrink_to_fit();
+
+                Arc::new(CachedTombstones {
+                    tombstones: Arc::new(tombstones),
+                    loaded_at: time_provider.now(),
+                })
+            }
+        });
+        let loader = Arc::new(MetricsLoader::new(
+            loader,
+            CACHE_ID,
+            Arc::clone(&time_provider),
+            metric_registry,
+            testing,
+        ));
+
+        let mut backend = PolicyBackend::hashmap_backed(Arc::clone(&time_provider));
+        let (policy_constructor, remove_if_handle) =
+            RemoveIfPolicy::create_constructor_and_handle(CACHE_ID, metric_registry);
+        backend.add_policy(policy_constructor);
+        backend.add_policy(LruPolicy::new(
+            Arc::clone(&ram_pool),
+            CACHE_ID,
+            Arc::new(FunctionEstimator::new(
+                |k: &TableId, v: &Arc<CachedTombstones>| {
+                    RamSize(mem::size_of_val(k) + mem::size_of_val(v) + v.size())
+                },
+            )),
+        ));
+
+        let cache = CacheDriver::new(loader, backend);
+        let cache = Box::new(CacheWithMetrics::new(
+            cache,
+            CACHE_ID,
+            Arc::clone(&time_provider),
+            metric_registry,
+        ));
+
+        Self {
+            cache,
+            remove_if_handle,
+            time_provider,
+        }
+    }
+
+    /// Get all tombstones recorded for `table_id`.
+    ///
+    /// # Expiration
+    ///
+    /// Entries older than [`TTL`] are re-read from the catalog.
+    pub async fn get(&self, table_id: TableId, span: Option<Span>) -> Arc<CachedTombstones> {
+        let now = self.time_provider.now();
+
+        self.remove_if_handle
+            .remove_if_and_get(
+                &self.cache,
+                table_id,
+                |cached| {
+                    now.checked_duration_since(cached.loaded_at)
+                        .map(|age| age >= TTL)
+                        .unwrap_or_default()
+                },
+                ((), span),
+            )
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use data_types::Timestamp;
+    use iox_tests::TestCatalog;
+
+    use crate::cache::{ram::test_util::test_ram_pool, test_util::assert_histogram_metric_count};
+
+    const METRIC_NAME: &str = "tombstone_list_by_table_id";
+
+    #[tokio::test]
+    async fn test_tombstones() {
+        let catalog = TestCatalog::new();
+        let ns = catalog.create_namespace_1hr_retention("ns").await;
+        let table1 = ns.create_table("table1").await;
+        let table2 = ns.create_table("table2").await;
+
+        catalog
+            .catalog()
+            .repositories()
+            .await
+            .tombstones()
+            .create(
+                table1.table.id,
+                Timestamp::new(1),
+                Timestamp::new(10),
+                r#""tag1"='A'"#,
+            )
+            .await
+            .unwrap();
+
+        let cache = make_cache(&catalog);
+
+        let got = cache.get(table1.table.id, None).await;
+        assert_eq!(got.tombstones.len(), 1);
+        let tombstone = &got.tombstones[0];
+        assert!(tombstone.applies_to(Timestamp::new(tombstone.created_at.get() - 1)));
+        assert!(!tombstone.applies_to(tombstone.created_at));
+        let pred = &tombstone.delete_predicate;
+        assert_eq!(pred.range.start(), 1);
+        assert_eq!(pred.range.end(), 10);
+        assert_eq!(pred.expr_sql_string(), r#""tag1"='A'"#);
+
+        let got = cache.get(table2.table.id, None).await;
+        assert!(got.tombstones.is_empty());
+
+        // a second request doesn't result in a catalog request
+        assert_histogram_metric_count(&catalog.metric_registry, METRIC_NAME, 2);
+        cache.get(table1.table.id, None).await;
+        assert_histogram_metric_count(&catalog.metric_registry, METRIC_NAME, 2);
+    }
+
+    #[tokio::test]
+    async fn test_expiry() {
+        let catalog = TestCatalog::new();
+        let ns = catalog.create_namespace_1hr_retention("ns").await;
+        let table = ns.create_table("table").await;
+
+        let cache = make_cache(&catalog);
+
+        let got = cache.get(table.table.id, None).await;
+        assert!(got.tombstones.is_empty());
+        assert_histogram_metric_count(&catalog.metric_registry, METRIC_NAME, 1);
+
+        catalog
+            .catalog()
+            .repositories()
+            .await
+            .tombstones()
+            .create(table.table.id, Timestamp::new(1), Timestamp::new(10), "")
+            .await
+            .unwrap();
+
+        // still cached
+        let got = cache.get(table.table.id, None).await;
+        assert!(got.tombstones.is_empty());
+        assert_histogram_metric_count(&catalog.metric_registry, METRIC_NAME, 1);
+
+        catalog.mock_time_provider().inc(TTL);
+
+        let got = cache.get(table.table.id, None).await;
+        assert_eq!(got.tombstones.len(), 1);
+        assert_histogram_metric_count(&catalog.metric_registry, METRIC_NAME, 2);
+    }
+
+    fn make_cache(catalog: &TestCatalog) -> TombstoneCache {
+        TombstoneCache::new(
+            catalog.catalog(),
+            BackoffConfig::default(),
+            catalog.time_provider(),
+            &catalog.metric_registry(),
+            test_ram_pool(),
+            true,
+        )
+    }
+}
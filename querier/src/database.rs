@@ -1,21 +1,30 @@
 //! Database for the querier that contains all namespaces.
 
 use crate::{
-    cache::CatalogCache, ingester::IngesterConnection, namespace::QuerierNamespace,
-    parquet::ChunkAdapter, query_log::QueryLog, table::PruneMetrics,
+    cache::{namespace::CachedNamespace, CatalogCache},
+    ingester::IngesterConnection,
+    namespace::QuerierNamespace,
+    namespace_quota::NamespaceQuotas,
+    parquet::ChunkAdapter,
+    query_log::QueryLog,
+    table::PruneMetrics,
+    QueryLogOutput,
 };
 use async_trait::async_trait;
 use backoff::{Backoff, BackoffConfig};
-use data_types::Namespace;
+use data_types::{DeletePredicate, Namespace, Table, Timestamp};
+use datafusion::error::DataFusionError;
 use iox_catalog::interface::SoftDeletedRows;
-use iox_query::exec::Executor;
-use service_common::QueryNamespaceProvider;
+use iox_query::exec::{Executor, ExecutorType, IOxSessionContext};
+use observability_deps::tracing::info;
+use service_common::{NamespaceSummary, QueryNamespaceProvider, QueryPermit};
 use snafu::Snafu;
-use std::{collections::HashMap, sync::Arc};
-use trace::span::{Span, SpanRecorder};
-use tracker::{
-    AsyncSemaphoreMetrics, InstrumentedAsyncOwnedSemaphorePermit, InstrumentedAsyncSemaphore,
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use trace::{
+    ctx::SpanContext,
+    span::{Span, SpanRecorder},
 };
+use tracker::{AsyncSemaphoreMetrics, InstrumentedAsyncSemaphore};
 
 /// The number of entries to store in the circular query buffer log.
 ///
@@ -65,11 +74,35 @@ pub struct QuerierDatabase {
     /// If the same namespace is requested twice for different queries, it is counted twice.
     query_execution_semaphore: Arc<InstrumentedAsyncSemaphore>,
 
+    /// Concurrency and memory quotas of the namespaces, limiting each on top of the limits shared
+    /// by all namespaces.
+    namespace_quotas: NamespaceQuotas,
+
     /// Chunk prune metrics.
     prune_metrics: Arc<PruneMetrics>,
 
     /// DataFusion config.
     datafusion_config: Arc<HashMap<String, String>>,
+
+    /// Maximum runtime of queries.
+    max_query_runtime: MaxQueryRuntime,
+}
+
+/// Maximum runtime of the queries against each namespace, after which they are aborted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MaxQueryRuntime {
+    /// Maximum runtime for namespaces without an override, unlimited if `None`.
+    pub default: Option<Duration>,
+
+    /// Maximum runtime by namespace name, overriding `default`.
+    pub namespaces: HashMap<String, Duration>,
+}
+
+impl MaxQueryRuntime {
+    /// The maximum runtime of queries against the namespace `name`.
+    pub fn for_namespace(&self, name: &str) -> Option<Duration> {
+        self.namespaces.get(name).copied().or(self.default)
+    }
 }
 
 #[async_trait]
@@ -80,11 +113,128 @@ impl QueryNamespaceProvider for QuerierDatabase {
         self.namespace(name, span).await
     }
 
-    async fn acquire_semaphore(&self, span: Option<Span>) -> InstrumentedAsyncOwnedSemaphorePermit {
-        Arc::clone(&self.query_execution_semaphore)
-            .acquire_owned(span)
+    async fn list_namespaces(&self, _span: Option<Span>) -> Vec<NamespaceSummary> {
+        self.namespaces()
             .await
-            .expect("Semaphore should not be closed by anyone")
+            .into_iter()
+            .map(|ns| NamespaceSummary {
+                name: ns.name,
+                retention_period: ns
+                    .retention_period_ns
+                    .map(|ns| Duration::from_nanos(ns as u64)),
+            })
+            .collect()
+    }
+
+    fn new_query_context(&self, span_ctx: Option<SpanContext>) -> IOxSessionContext {
+        let mut cfg = self
+            .exec
+            .new_execution_config(ExecutorType::Query)
+            .with_span_context(span_ctx);
+
+        for (k, v) in self.datafusion_config.as_ref() {
+            cfg = cfg.with_config_option(k, v);
+        }
+
+        cfg.build()
+    }
+
+    async fn drop_table(
+        &self,
+        namespace: &str,
+        table: &str,
+        _span: Option<Span>,
+    ) -> Result<(), DataFusionError> {
+        let table = self.table(namespace, table).await?;
+        self.catalog_cache
+            .catalog()
+            .repositories()
+            .await
+            .tables()
+            .soft_delete(table.id)
+            .await
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+        info!(%namespace, table=%table.name, table_id=%table.id, "soft-deleted table");
+        Ok(())
+    }
+
+    async fn delete(
+        &self,
+        namespace: &str,
+        table: &str,
+        predicate: &DeletePredicate,
+        span: Option<Span>,
+    ) -> Result<(), DataFusionError> {
+        let table = self.table(namespace, table).await?;
+
+        // The ingesters do not read tombstones, so remove the matching rows from their buffers
+        // first. The tombstone is created afterwards and therefore only covers files persisted
+        // before it, which includes any file persisted from data buffered before the delete.
+        if let Some(ingester_connection) = &self.ingester_connection {
+            ingester_connection
+                .delete(table.namespace_id, &table.name, predicate, span)
+                .await
+                .map_err(|e| DataFusionError::External(Box::new(e)))?;
+        }
+
+        let serialized_predicate = predicate.expr_sql_string();
+        let tombstone = self
+            .catalog_cache
+            .catalog()
+            .repositories()
+            .await
+            .tombstones()
+            .create(
+                table.id,
+                Timestamp::new(predicate.range.start()),
+                Timestamp::new(predicate.range.end()),
+                &serialized_predicate,
+            )
+            .await
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+        info!(
+            %namespace,
+            table=%table.name,
+            tombstone_id=%tombstone.id,
+            predicate=%serialized_predicate,
+            "recorded tombstone"
+        );
+        Ok(())
+    }
+
+    async fn acquire_semaphore(&self, namespace: &str, span: Option<Span>) -> QueryPermit {
+        let span_recorder = SpanRecorder::new(span);
+
+        // Wait for the namespace quota first, so that queries held back by it do not take up
+        // permits shared by all namespaces.
+        let namespace_permit = match self
+            .cached_namespace(
+                namespace,
+                span_recorder.child_span("cache GET namespace schema"),
+            )
+            .await
+        {
+            Some(ns) => {
+                self.namespace_quotas
+                    .get(ns.id, ns.query_limits)
+                    .acquire(span_recorder.child_span("namespace query semaphore"))
+                    .await
+            }
+            None => None,
+        };
+
+        let permit = QueryPermit::new(
+            Arc::clone(&self.query_execution_semaphore)
+                .acquire_owned(span_recorder.child_span("query semaphore"))
+                .await
+                .expect("Semaphore should not be closed by anyone"),
+        );
+        match namespace_permit {
+            Some(namespace_permit) => permit.with_namespace_permit(namespace_permit),
+            None => permit,
+        }
     }
 }
 
@@ -104,6 +254,7 @@ impl QuerierDatabase {
         ingester_connection: Option<Arc<dyn IngesterConnection>>,
         max_concurrent_queries: usize,
         datafusion_config: Arc<HashMap<String, String>>,
+        query_log_output: QueryLogOutput,
     ) -> Result<Self, Error> {
         assert!(
             max_concurrent_queries <= Self::MAX_CONCURRENT_QUERIES_MAX,
@@ -118,13 +269,17 @@ impl QuerierDatabase {
             Arc::clone(&catalog_cache),
             Arc::clone(&metric_registry),
         ));
-        let query_log = Arc::new(QueryLog::new(QUERY_LOG_SIZE, catalog_cache.time_provider()));
+        let query_log = Arc::new(
+            QueryLog::new(QUERY_LOG_SIZE, catalog_cache.time_provider())
+                .with_output(query_log_output),
+        );
         let semaphore_metrics = Arc::new(AsyncSemaphoreMetrics::new(
             &metric_registry,
             &[("semaphore", "query_execution")],
         ));
         let query_execution_semaphore =
             Arc::new(semaphore_metrics.new_semaphore(max_concurrent_queries));
+        let namespace_quotas = NamespaceQuotas::new(exec.memory_pool(), &metric_registry);
 
         let prune_metrics = Arc::new(PruneMetrics::new(&metric_registry));
 
@@ -137,28 +292,36 @@ impl QuerierDatabase {
             ingester_connection,
             query_log,
             query_execution_semaphore,
+            namespace_quotas,
             prune_metrics,
             datafusion_config,
+            max_query_runtime: MaxQueryRuntime::default(),
         })
     }
 
+    /// Abort queries that run for longer than `max_query_runtime`.
+    pub fn with_max_query_runtime(self, max_query_runtime: MaxQueryRuntime) -> Self {
+        Self {
+            max_query_runtime,
+            ..self
+        }
+    }
+
     /// Get namespace if it exists.
     ///
     /// This will await the internal namespace semaphore. Existence of namespaces is checked AFTER
     /// a semaphore permit was acquired since this lowers the chance that we obtain stale data.
     pub async fn namespace(&self, name: &str, span: Option<Span>) -> Option<Arc<QuerierNamespace>> {
         let span_recorder = SpanRecorder::new(span);
-        let name = Arc::from(name.to_owned());
         let ns = self
-            .catalog_cache
-            .namespace()
-            .get(
-                Arc::clone(&name),
-                // we have no specific need for any tables or columns at this point, so nothing to cover
-                &[],
-                span_recorder.child_span("cache GET namespace schema"),
-            )
+            .cached_namespace(name, span_recorder.child_span("cache GET namespace schema"))
             .await?;
+        let name = Arc::from(name.to_owned());
+        let max_query_runtime = self.max_query_runtime.for_namespace(&name);
+        let memory_pool = self
+            .namespace_quotas
+            .get(ns.id, ns.query_limits)
+            .memory_pool();
         Some(Arc::new(QuerierNamespace::new(
             Arc::clone(&self.chunk_adapter),
             ns,
@@ -168,9 +331,28 @@ impl QuerierDatabase {
             Arc::clone(&self.query_log),
             Arc::clone(&self.prune_metrics),
             Arc::clone(&self.datafusion_config),
+            max_query_runtime,
+            memory_pool,
         )))
     }
 
+    /// Get the cached schema of the namespace `name`, if it exists.
+    async fn cached_namespace(
+        &self,
+        name: &str,
+        span: Option<Span>,
+    ) -> Option<Arc<CachedNamespace>> {
+        self.catalog_cache
+            .namespace()
+            .get(
+                Arc::from(name),
+                // we have no specific need for any tables or columns at this point, so nothing to cover
+                &[],
+                span,
+            )
+            .await
+    }
+
     /// Return all namespaces this querier knows about
     pub async fn namespaces(&self) -> Vec<Namespace> {
         let catalog = &self.catalog_cache.catalog();
@@ -187,6 +369,28 @@ impl QuerierDatabase {
             .expect("retry forever")
     }
 
+    /// Look up the table `name` of the namespace `namespace` in the catalog.
+    ///
+    /// Returns a planning error if either does not exist or has been deleted.
+    async fn table(&self, namespace: &str, name: &str) -> Result<Table, DataFusionError> {
+        let mut repos = self.catalog_cache.catalog().repositories().await;
+
+        let ns = repos
+            .namespaces()
+            .get_by_name(namespace, SoftDeletedRows::ExcludeDeleted)
+            .await
+            .map_err(|e| DataFusionError::External(Box::new(e)))?
+            .ok_or_else(|| DataFusionError::Plan(format!("database not found: {namespace}")))?;
+
+        repos
+            .tables()
+            .get_by_namespace_and_name(ns.id, name)
+            .await
+            .map_err(|e| DataFusionError::External(Box::new(e)))?
+            .filter(|table| table.deleted_at.is_none())
+            .ok_or_else(|| DataFusionError::Plan(format!("measurement not found: {name}")))
+    }
+
     /// Return connection to ingester(s) to get and aggregate information from them
     pub fn ingester_connection(&self) -> Option<Arc<dyn IngesterConnection>> {
         self.ingester_connection.clone()
@@ -201,7 +405,11 @@ impl QuerierDatabase {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::create_ingester_connection_for_testing;
+    use crate::{
+        create_ingester_connection_for_testing, ingester::test_util::MockIngesterConnection,
+    };
+    use datafusion::execution::memory_pool::MemoryConsumer;
+    use iox_query::exec::ExecutionContextProvider;
     use iox_tests::TestCatalog;
     use tokio::runtime::Handle;
 
@@ -226,6 +434,7 @@ mod tests {
             Some(create_ingester_connection_for_testing()),
             QuerierDatabase::MAX_CONCURRENT_QUERIES_MAX.saturating_add(1),
             Arc::new(HashMap::default()),
+            QueryLogOutput::default(),
         )
         .await
         .unwrap();
@@ -249,6 +458,7 @@ mod tests {
             Some(create_ingester_connection_for_testing()),
             QuerierDatabase::MAX_CONCURRENT_QUERIES_MAX,
             Arc::new(HashMap::default()),
+            QueryLogOutput::default(),
         )
         .await
         .unwrap();
@@ -277,6 +487,7 @@ mod tests {
             Some(create_ingester_connection_for_testing()),
             QuerierDatabase::MAX_CONCURRENT_QUERIES_MAX,
             Arc::new(HashMap::default()),
+            QueryLogOutput::default(),
         )
         .await
         .unwrap();
@@ -290,4 +501,161 @@ mod tests {
         assert_eq!(namespaces[0].name, "ns1");
         assert_eq!(namespaces[1].name, "ns2");
     }
+
+    #[tokio::test]
+    async fn test_namespace_query_limits() {
+        let catalog = TestCatalog::new();
+
+        let catalog_cache = Arc::new(CatalogCache::new_testing(
+            catalog.catalog(),
+            catalog.time_provider(),
+            catalog.metric_registry(),
+            catalog.object_store(),
+            &Handle::current(),
+        ));
+        let db = QuerierDatabase::new(
+            catalog_cache,
+            catalog.metric_registry(),
+            catalog.exec(),
+            Some(create_ingester_connection_for_testing()),
+            QuerierDatabase::MAX_CONCURRENT_QUERIES_MAX,
+            Arc::new(HashMap::default()),
+            QueryLogOutput::default(),
+        )
+        .await
+        .unwrap();
+
+        catalog.create_namespace_1hr_retention("ns1").await;
+        catalog.create_namespace_1hr_retention("ns2").await;
+        let mut repos = catalog.catalog().repositories().await;
+        repos
+            .namespaces()
+            .update_concurrent_query_limit("ns1", Some(1))
+            .await
+            .unwrap();
+        repos
+            .namespaces()
+            .update_query_memory_limit("ns1", Some(10))
+            .await
+            .unwrap();
+        drop(repos);
+
+        let permit = db.acquire_semaphore("ns1", None).await;
+        let mut blocked = Box::pin(db.acquire_semaphore("ns1", None));
+        assert!(futures::poll!(&mut blocked).is_pending());
+
+        // other namespaces are not held back
+        db.acquire_semaphore("ns2", None).await;
+
+        drop(permit);
+        blocked.await;
+
+        let ns = db.namespace("ns1", None).await.unwrap();
+        let ctx = ns.new_query_context(None);
+        let mut reservation =
+            MemoryConsumer::new("test").register(&ctx.inner().runtime_env().memory_pool);
+        reservation.try_grow(10).unwrap();
+        reservation.try_grow(1).unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn test_drop_table_and_delete() {
+        let catalog = TestCatalog::new();
+
+        let catalog_cache = Arc::new(CatalogCache::new_testing(
+            catalog.catalog(),
+            catalog.time_provider(),
+            catalog.metric_registry(),
+            catalog.object_store(),
+            &Handle::current(),
+        ));
+        let db = QuerierDatabase::new(
+            catalog_cache,
+            catalog.metric_registry(),
+            catalog.exec(),
+            Some(create_ingester_connection_for_testing()),
+            QuerierDatabase::MAX_CONCURRENT_QUERIES_MAX,
+            Arc::new(HashMap::default()),
+            QueryLogOutput::default(),
+        )
+        .await
+        .unwrap();
+
+        let ns = catalog.create_namespace_1hr_retention("ns1").await;
+        let table = ns.create_table("cpu").await;
+
+        let predicate = DeletePredicate {
+            range: data_types::TimestampRange::new(0, 10),
+            exprs: vec![],
+        };
+        db.delete("ns1", "cpu", &predicate, None).await.unwrap();
+        let tombstones = catalog
+            .catalog()
+            .repositories()
+            .await
+            .tombstones()
+            .list_by_table_id(table.table.id)
+            .await
+            .unwrap();
+        assert_eq!(tombstones.len(), 1);
+
+        // The delete was also sent to the ingesters
+        let ingester_connection = db.ingester_connection().unwrap();
+        let deletes = ingester_connection
+            .as_any()
+            .downcast_ref::<MockIngesterConnection>()
+            .unwrap()
+            .deletes();
+        assert_eq!(
+            deletes,
+            vec![(ns.namespace.id, String::from("cpu"), predicate.clone())]
+        );
+
+        db.drop_table("ns1", "cpu", None).await.unwrap();
+        let got = catalog
+            .catalog()
+            .repositories()
+            .await
+            .tables()
+            .get_by_id(table.table.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(got.deleted_at.is_some());
+
+        // The table can no longer be found
+        let err = db.drop_table("ns1", "cpu", None).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: measurement not found: cpu"
+        );
+        let err = db.delete("ns1", "cpu", &predicate, None).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: measurement not found: cpu"
+        );
+
+        let err = db.drop_table("ns2", "cpu", None).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: database not found: ns2"
+        );
+    }
+
+    #[test]
+    fn test_max_query_runtime() {
+        let max_query_runtime = MaxQueryRuntime {
+            default: Some(Duration::from_secs(60)),
+            namespaces: HashMap::from([("ns1".to_string(), Duration::from_secs(5))]),
+        };
+        assert_eq!(
+            max_query_runtime.for_namespace("ns1"),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(
+            max_query_runtime.for_namespace("ns2"),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(MaxQueryRuntime::default().for_namespace("ns1"), None);
+    }
 }
@@ -159,7 +159,7 @@ impl Drop for QuerierHandlerImpl {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{cache::CatalogCache, create_ingester_connection_for_testing};
+    use crate::{cache::CatalogCache, create_ingester_connection_for_testing, QueryLogOutput};
     use iox_catalog::mem::MemCatalog;
     use iox_query::exec::Executor;
     use iox_time::{MockProvider, Time};
@@ -212,6 +212,7 @@ mod tests {
                     Some(create_ingester_connection_for_testing()),
                     QuerierDatabase::MAX_CONCURRENT_QUERIES_MAX,
                     Arc::new(HashMap::default()),
+                    QueryLogOutput::default(),
                 )
                 .await
                 .unwrap(),
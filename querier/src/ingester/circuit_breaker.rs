@@ -12,6 +12,7 @@ use std::{
 
 use async_trait::async_trait;
 use backoff::{Backoff, BackoffConfig};
+use data_types::{DeletePredicate, NamespaceId};
 use generated_types::ingester::IngesterQueryRequest;
 use iox_time::{Time, TimeProvider};
 use metric::{Metric, Registry, U64Gauge};
@@ -496,6 +497,20 @@ impl IngesterFlightClient for CircuitBreakerFlightClient {
 
         res
     }
+
+    /// Deletes bypass the circuit: a delete that skips an ingester would leave the deleted rows
+    /// visible, so it must fail instead.
+    async fn delete(
+        &self,
+        ingester_addr: Arc<str>,
+        namespace_id: NamespaceId,
+        table_name: &str,
+        predicate: &DeletePredicate,
+    ) -> Result<(), FlightClientError> {
+        self.inner
+            .delete(ingester_addr, namespace_id, table_name, predicate)
+            .await
+    }
 }
 
 #[cfg(test)]
@@ -504,7 +519,7 @@ mod tests {
 
     use arrow_flight::decode::DecodedPayload;
     use assert_matches::assert_matches;
-    use data_types::{NamespaceId, TableId};
+    use data_types::TableId;
     use generated_types::google::FieldViolation;
     use influxdb_iox_client::flight::generated_types::IngesterQueryResponseMetadata;
     use iox_time::MockProvider;
@@ -1066,6 +1081,16 @@ mod tests {
 
             Ok(Box::new(MockQueryData))
         }
+
+        async fn delete(
+            &self,
+            _ingester_addr: Arc<str>,
+            _namespace_id: NamespaceId,
+            _table_name: &str,
+            _predicate: &DeletePredicate,
+        ) -> Result<(), FlightClientError> {
+            unimplemented!()
+        }
     }
 
     #[derive(Debug)]
@@ -4,6 +4,7 @@ use arrow_flight::{
 };
 use async_trait::async_trait;
 use client_util::connection::{self, Connection};
+use data_types::{DeletePredicate, NamespaceId};
 use futures::StreamExt;
 use generated_types::ingester::IngesterQueryRequest;
 use influxdb_iox_client::flight::generated_types as proto;
@@ -39,6 +40,13 @@ pub enum Error {
     #[snafu(display("Failed to perform flight request: {}", source))]
     Flight { source: FlightError },
 
+    #[snafu(display("Failed ingester delete '{}': {}", ingester_address, source))]
+    Delete {
+        ingester_address: String,
+        #[snafu(source(from(influxdb_iox_client::error::Error, Box::new)))]
+        source: Box<influxdb_iox_client::error::Error>,
+    },
+
     #[snafu(display("Can not contact ingester. Circuit broken: {}", ingester_address))]
     CircuitBroken { ingester_address: String },
 }
@@ -58,6 +66,11 @@ impl Error {
                 e.code(),
                 tonic::Code::NotFound | tonic::Code::ResourceExhausted
             ),
+            Self::Delete { source, .. } => !matches!(
+                source.as_ref(),
+                influxdb_iox_client::error::Error::InvalidArgument(_)
+                    | influxdb_iox_client::error::Error::ResourceExhausted(_)
+            ),
             Self::Connecting { .. } | Self::Handshake { .. } | Self::Flight { .. } => true,
             // do NOT break circuit for client-side errors
             Self::CreatingRequest { .. } => false,
@@ -84,6 +97,17 @@ pub trait IngesterFlightClient: Debug + Send + Sync + 'static {
         request: IngesterQueryRequest,
         span_context: Option<SpanContext>,
     ) -> Result<Box<dyn QueryData>, Error>;
+
+    /// Delete rows matching `predicate` from the data buffered by the given ingester.
+    ///
+    /// An empty `table_name` deletes from all tables of the namespace.
+    async fn delete(
+        &self,
+        ingester_address: Arc<str>,
+        namespace_id: NamespaceId,
+        table_name: &str,
+        predicate: &DeletePredicate,
+    ) -> Result<(), Error>;
 }
 
 /// Default [`IngesterFlightClient`] implementation that uses a real connection
@@ -192,6 +216,24 @@ impl IngesterFlightClient for FlightClientImpl {
             recorder: span_recorder_comm.child("stream"),
         }))
     }
+
+    async fn delete(
+        &self,
+        ingester_addr: Arc<str>,
+        namespace_id: NamespaceId,
+        table_name: &str,
+        predicate: &DeletePredicate,
+    ) -> Result<(), Error> {
+        let connection = self.connect(Arc::clone(&ingester_addr)).await?;
+
+        debug!(%ingester_addr, %namespace_id, %table_name, ?predicate, "Sending delete to ingester");
+        influxdb_iox_client::delete::Client::new(connection)
+            .delete(namespace_id.get(), table_name, predicate.clone().into())
+            .await
+            .context(DeleteSnafu {
+                ingester_address: ingester_addr.as_ref(),
+            })
+    }
 }
 
 /// Tries to serialize the request to the ingester
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use data_types::{DeletePredicate, NamespaceId};
 use generated_types::ingester::IngesterQueryRequest;
 use trace::{ctx::SpanContext, span::SpanRecorder};
 
@@ -54,6 +55,29 @@ impl IngesterFlightClient for InvalidateOnErrorFlightClient {
             span_recorder.event("invalidate connection");
         }
 
+        res
+    }
+    async fn delete(
+        &self,
+        ingester_addr: Arc<str>,
+        namespace_id: NamespaceId,
+        table_name: &str,
+        predicate: &DeletePredicate,
+    ) -> Result<(), FlightClientError> {
+        let res = self
+            .inner
+            .delete(
+                Arc::clone(&ingester_addr),
+                namespace_id,
+                table_name,
+                predicate,
+            )
+            .await;
+
+        if matches!(&res, Err(e) if e.is_upstream_error()) {
+            self.inner.invalidate_connection(ingester_addr).await;
+        }
+
         res
     }
 }
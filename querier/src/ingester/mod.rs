@@ -85,6 +85,12 @@ pub enum Error {
         source: FlightClientError,
     },
 
+    #[snafu(display("Failed ingester delete '{}': {}", ingester_address, source))]
+    RemoteDelete {
+        ingester_address: String,
+        source: FlightClientError,
+    },
+
     #[snafu(display("Failed to connect to ingester '{}': {}", ingester_address, source))]
     Connecting {
         ingester_address: String,
@@ -177,6 +183,17 @@ pub trait IngesterConnection: std::fmt::Debug + Send + Sync + 'static {
         span: Option<Span>,
     ) -> Result<Vec<IngesterPartition>>;
 
+    /// Deletes rows matching `predicate` from the data buffered by all ingesters.
+    ///
+    /// An empty `table_name` deletes from all tables of the namespace.
+    async fn delete(
+        &self,
+        namespace_id: NamespaceId,
+        table_name: &str,
+        predicate: &DeletePredicate,
+        span: Option<Span>,
+    ) -> Result<()>;
+
     /// Return backend as [`Any`] which can be used to downcast to a specific implementation.
     fn as_any(&self) -> &dyn Any;
 }
@@ -729,6 +746,58 @@ impl IngesterConnection for IngesterConnectionImpl {
         Ok(ingester_partitions)
     }
 
+    async fn delete(
+        &self,
+        namespace_id: NamespaceId,
+        table_name: &str,
+        predicate: &DeletePredicate,
+        span: Option<Span>,
+    ) -> Result<()> {
+        let mut span_recorder = SpanRecorder::new(span);
+
+        // Every ingester may buffer rows of the table, so the delete has to reach all of them.
+        self.unique_ingester_addresses
+            .iter()
+            .cloned()
+            .map(|ingester_address| {
+                let flight_client = Arc::clone(&self.flight_client);
+                let backoff_config = self.backoff_config.clone();
+                async move {
+                    Backoff::new(&backoff_config)
+                        .retry_all_errors("ingester delete", || {
+                            let flight_client = Arc::clone(&flight_client);
+                            let ingester_address = Arc::clone(&ingester_address);
+                            async move {
+                                flight_client
+                                    .delete(
+                                        Arc::clone(&ingester_address),
+                                        namespace_id,
+                                        table_name,
+                                        predicate,
+                                    )
+                                    .await
+                                    .context(RemoteDeleteSnafu {
+                                        ingester_address: ingester_address.as_ref(),
+                                    })
+                            }
+                        })
+                        .await
+                }
+            })
+            .collect::<FuturesUnordered<_>>()
+            .try_collect::<Vec<_>>()
+            .await
+            .map_err(|e| {
+                span_recorder.error("failed");
+                match e {
+                    BackoffError::DeadlineExceeded { source, .. } => source,
+                }
+            })?;
+
+        span_recorder.ok("done");
+        Ok(())
+    }
+
     fn as_any(&self) -> &dyn Any {
         self as &dyn Any
     }
@@ -1138,6 +1207,37 @@ mod tests {
         assert!(partitions.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_delete_sent_to_all_ingesters() {
+        let mock_flight_client = Arc::new(
+            MockFlightClient::new([
+                ("addr1", Ok(MockQueryData { results: vec![] })),
+                ("addr2", Ok(MockQueryData { results: vec![] })),
+            ])
+            .await,
+        );
+        let ingester_conn = mock_flight_client.ingester_conn().await;
+
+        let predicate = DeletePredicate {
+            range: data_types::TimestampRange::new(1, 2),
+            exprs: vec![],
+        };
+        ingester_conn
+            .delete(NamespaceId::new(1), "table", &predicate, None)
+            .await
+            .unwrap();
+
+        let mut deletes = mock_flight_client.deletes.lock().await.clone();
+        deletes.sort();
+        assert_eq!(
+            deletes,
+            vec![
+                (String::from("addr1"), String::from("table")),
+                (String::from("addr2"), String::from("table")),
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn test_flight_no_batches() {
         let ingester_uuid = Uuid::new_v4();
@@ -1584,6 +1684,8 @@ mod tests {
     struct MockFlightClient {
         catalog: Arc<TestCatalog>,
         responses: Mutex<HashMap<String, Result<MockQueryData, FlightClientError>>>,
+        /// `(ingester_address, table_name)` of every delete received.
+        deletes: Mutex<Vec<(String, String)>>,
     }
 
     impl MockFlightClient {
@@ -1606,6 +1708,7 @@ mod tests {
                         .map(|(k, v)| (String::from(k), v))
                         .collect(),
                 ),
+                deletes: Default::default(),
             }
         }
 
@@ -1651,6 +1754,20 @@ mod tests {
                 .expect("Response not mocked")
                 .map(|query_data| Box::new(query_data) as _)
         }
+
+        async fn delete(
+            &self,
+            ingester_address: Arc<str>,
+            _namespace_id: NamespaceId,
+            table_name: &str,
+            _predicate: &DeletePredicate,
+        ) -> Result<(), FlightClientError> {
+            self.deletes
+                .lock()
+                .await
+                .push((ingester_address.to_string(), table_name.to_string()));
+            Ok(())
+        }
     }
 
     #[test]
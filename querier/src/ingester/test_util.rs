@@ -1,7 +1,7 @@
 use super::IngesterConnection;
 use crate::cache::namespace::CachedTable;
 use async_trait::async_trait;
-use data_types::NamespaceId;
+use data_types::{DeletePredicate, NamespaceId};
 use iox_query::util::create_basic_summary;
 use parking_lot::Mutex;
 use schema::{Projection, Schema as IOxSchema};
@@ -12,6 +12,7 @@ use trace::span::Span;
 #[derive(Debug, Default)]
 pub struct MockIngesterConnection {
     next_response: Mutex<Option<super::Result<Vec<super::IngesterPartition>>>>,
+    deletes: Mutex<Vec<(NamespaceId, String, DeletePredicate)>>,
 }
 
 impl MockIngesterConnection {
@@ -25,6 +26,12 @@ impl MockIngesterConnection {
     pub fn next_response(&self, response: super::Result<Vec<super::IngesterPartition>>) {
         *self.next_response.lock() = Some(response);
     }
+
+    /// Deletes received by this connection, in order.
+    #[allow(dead_code)]
+    pub fn deletes(&self) -> Vec<(NamespaceId, String, DeletePredicate)> {
+        self.deletes.lock().clone()
+    }
 }
 
 #[async_trait]
@@ -111,6 +118,19 @@ impl IngesterConnection for MockIngesterConnection {
         Ok(partitions)
     }
 
+    async fn delete(
+        &self,
+        namespace_id: NamespaceId,
+        table_name: &str,
+        predicate: &DeletePredicate,
+        _span: Option<Span>,
+    ) -> super::Result<()> {
+        self.deletes
+            .lock()
+            .push((namespace_id, table_name.to_string(), predicate.clone()));
+        Ok(())
+    }
+
     fn as_any(&self) -> &dyn Any {
         self as &dyn Any
     }
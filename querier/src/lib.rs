@@ -16,6 +16,7 @@ mod database;
 mod handler;
 mod ingester;
 mod namespace;
+mod namespace_quota;
 mod parquet;
 mod poison;
 mod query_log;
@@ -24,7 +25,7 @@ mod system_tables;
 mod table;
 
 pub use cache::CatalogCache as QuerierCatalogCache;
-pub use database::{Error as QuerierDatabaseError, QuerierDatabase};
+pub use database::{Error as QuerierDatabaseError, MaxQueryRuntime, QuerierDatabase};
 pub use handler::{QuerierHandler, QuerierHandlerImpl};
 pub use ingester::{
     create_ingester_connection_for_testing, create_ingester_connections,
@@ -35,4 +36,5 @@ pub use ingester::{
     Error as IngesterError, IngesterConnection, IngesterConnectionImpl, IngesterPartition,
 };
 pub use namespace::QuerierNamespace;
+pub use query_log::QueryLogOutput;
 pub use server::QuerierServer;
@@ -8,8 +8,9 @@ use crate::{
     table::{PruneMetrics, QuerierTable, QuerierTableArgs},
 };
 use data_types::NamespaceId;
+use datafusion::execution::memory_pool::MemoryPool;
 use iox_query::exec::Executor;
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 mod query_access;
 
@@ -36,6 +37,9 @@ pub struct QuerierNamespace {
     /// Tables in this namespace.
     tables: Arc<HashMap<Arc<str>, Arc<QuerierTable>>>,
 
+    /// Cached catalog view of this namespace, backing the system tables.
+    cached_namespace: Arc<CachedNamespace>,
+
     /// Executor for queries.
     exec: Arc<Executor>,
 
@@ -47,6 +51,12 @@ pub struct QuerierNamespace {
 
     /// DataFusion config.
     datafusion_config: Arc<HashMap<String, String>>,
+
+    /// Queries running for longer than this are aborted.
+    max_query_runtime: Option<Duration>,
+
+    /// Pool shared by the queries against this namespace, if their total memory is limited.
+    memory_pool: Option<Arc<dyn MemoryPool>>,
 }
 
 impl QuerierNamespace {
@@ -61,6 +71,8 @@ impl QuerierNamespace {
         query_log: Arc<QueryLog>,
         prune_metrics: Arc<PruneMetrics>,
         datafusion_config: Arc<HashMap<String, String>>,
+        max_query_runtime: Option<Duration>,
+        memory_pool: Option<Arc<dyn MemoryPool>>,
     ) -> Self {
         let tables: HashMap<_, _> = ns
             .tables
@@ -88,10 +100,13 @@ impl QuerierNamespace {
             id,
             name,
             tables: Arc::new(tables),
+            cached_namespace: ns,
             exec,
             catalog_cache: Arc::clone(chunk_adapter.catalog_cache()),
             query_log,
             datafusion_config,
+            max_query_runtime,
+            memory_pool,
         }
     }
 
@@ -119,6 +134,8 @@ impl QuerierNamespace {
             query_log,
             prune_metrics,
             Arc::new(HashMap::default()),
+            None,
+            None,
         )
     }
 
@@ -1,8 +1,9 @@
 //! This module contains implementations of [`iox_query`] interfaces for [QuerierNamespace].
 
 use crate::{
+    cache::{namespace::CachedNamespace, CatalogCache},
     namespace::QuerierNamespace,
-    query_log::QueryLog,
+    query_log::{IngesterStats, QueryLog, QueryLogStats},
     system_tables::{SystemSchemaProvider, SYSTEM_SCHEMA},
     table::QuerierTable,
 };
@@ -16,7 +17,8 @@ use datafusion::{
 use datafusion_util::config::DEFAULT_SCHEMA;
 use iox_query::{
     exec::{ExecutionContextProvider, ExecutorType, IOxSessionContext},
-    QueryChunk, QueryCompletedToken, QueryNamespace, QueryText,
+    CancelQueryError, QueryCancellation, QueryChunk, QueryCompletedToken, QueryNamespace,
+    QueryText,
 };
 use observability_deps::tracing::{debug, trace};
 use predicate::{rpc_predicate::QueryNamespaceMeta, Predicate};
@@ -56,11 +58,17 @@ impl QueryNamespace for QuerierNamespace {
             }
         };
 
+        let ingester_stats = ctx
+            .inner()
+            .state()
+            .config()
+            .get_extension::<IngesterStats>();
         let mut chunks = table
             .chunks(
                 predicate,
                 ctx.child_span("QuerierNamespace chunks"),
                 projection,
+                ingester_stats.as_deref(),
             )
             .await?;
 
@@ -91,7 +99,24 @@ impl QueryNamespace for QuerierNamespace {
         let query_log = Arc::clone(&self.query_log);
         let trace_id = ctx.span().map(|s| s.ctx.trace_id);
         let entry = query_log.push(self.id, query_type, query_text, trace_id);
-        QueryCompletedToken::new(move |success| query_log.set_completed(entry, success))
+        let ingester_stats = ctx
+            .inner()
+            .state()
+            .config()
+            .get_extension::<IngesterStats>()
+            .unwrap_or_default();
+        let memory_tracker = Arc::clone(ctx.memory_tracker());
+        let cancellation =
+            QueryCancellation::new(entry.cancellation_token(), self.max_query_runtime);
+        QueryCompletedToken::new(move |success, stats| {
+            let stats = QueryLogStats::new(stats, &ingester_stats, memory_tracker.peak());
+            query_log.set_completed(entry, success, stats)
+        })
+        .with_cancellation(cancellation)
+    }
+
+    fn cancel_query(&self, query_id: &str) -> Result<(), CancelQueryError> {
+        self.query_log.cancel(self.id, query_id)
     }
 
     fn as_meta(&self) -> &dyn QueryNamespaceMeta {
@@ -108,6 +133,12 @@ pub struct QuerierCatalogProvider {
 
     /// Query log.
     query_log: Arc<QueryLog>,
+
+    /// Cached catalog view of the namespace.
+    cached_namespace: Arc<CachedNamespace>,
+
+    /// Catalog cache.
+    catalog_cache: Arc<CatalogCache>,
 }
 
 impl QuerierCatalogProvider {
@@ -116,6 +147,8 @@ impl QuerierCatalogProvider {
             namespace_id: namespace.id,
             tables: Arc::clone(&namespace.tables),
             query_log: Arc::clone(&namespace.query_log),
+            cached_namespace: Arc::clone(&namespace.cached_namespace),
+            catalog_cache: Arc::clone(&namespace.catalog_cache),
         }
     }
 }
@@ -137,6 +170,8 @@ impl CatalogProvider for QuerierCatalogProvider {
             SYSTEM_SCHEMA => Some(Arc::new(SystemSchemaProvider::new(
                 Arc::clone(&self.query_log),
                 self.namespace_id,
+                Arc::clone(&self.cached_namespace),
+                Arc::clone(&self.catalog_cache),
             ))),
             _ => None,
         }
@@ -190,12 +225,20 @@ impl ExecutionContextProvider for QuerierNamespace {
             .exec
             .new_execution_config(ExecutorType::Query)
             .with_default_catalog(Arc::new(QuerierCatalogProvider::from_namespace(self)) as _)
-            .with_span_context(span_ctx);
+            .with_span_context(span_ctx)
+            .with_extension(Arc::new(IngesterStats::default()));
 
         for (k, v) in self.datafusion_config.as_ref() {
             cfg = cfg.with_config_option(k, v);
         }
 
+        if let Some(memory_pool) = &self.memory_pool {
+            cfg = cfg.with_memory_pool(Arc::clone(memory_pool));
+        }
+        if let Some(limit) = self.cached_namespace.query_limits.max_query_memory_bytes {
+            cfg = cfg.with_memory_limit(limit);
+        }
+
         cfg.build()
     }
 }
@@ -564,6 +607,121 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_system_tables() {
+        test_helpers::maybe_start_logging();
+
+        let catalog = TestCatalog::new();
+        let ns = catalog.create_namespace_with_retention("ns", None).await;
+
+        let table_cpu = ns.create_table("cpu").await;
+        let table_mem = ns.create_table("mem").await;
+
+        table_cpu.create_column("host", ColumnType::Tag).await;
+        table_cpu.create_column("time", ColumnType::Time).await;
+        table_cpu.create_column("load", ColumnType::F64).await;
+        table_mem.create_column("host", ColumnType::Tag).await;
+        table_mem.create_column("time", ColumnType::Time).await;
+        table_mem.create_column("perc", ColumnType::F64).await;
+
+        let partition_cpu_a = table_cpu.create_partition("a").await;
+        let partition_cpu_b = table_cpu.create_partition("b").await;
+        let partition_mem_c = table_mem.create_partition("c").await;
+
+        let builder = TestParquetFileBuilder::default()
+            .with_line_protocol("cpu,host=a load=1 11")
+            .with_min_time(11)
+            .with_max_time(11);
+        partition_cpu_a.create_parquet_file(builder).await;
+
+        let builder = TestParquetFileBuilder::default()
+            .with_line_protocol("cpu,host=a load=2 22\ncpu,host=a load=3 33")
+            .with_min_time(22)
+            .with_max_time(33);
+        partition_cpu_a.create_parquet_file(builder).await;
+
+        let builder = TestParquetFileBuilder::default()
+            .with_line_protocol("cpu,host=b load=4 44")
+            .with_min_time(44)
+            .with_max_time(44);
+        partition_cpu_b.create_parquet_file(builder).await;
+
+        let builder = TestParquetFileBuilder::default()
+            .with_line_protocol("mem,host=c perc=50 11")
+            .with_min_time(11)
+            .with_max_time(11);
+        partition_mem_c.create_parquet_file(builder).await;
+
+        let querier_namespace = Arc::new(querier_namespace(&ns).await);
+
+        insta::assert_yaml_snapshot!(
+            format_query(
+                &querier_namespace,
+                "SELECT table_name, column_count FROM system.tables",
+            ).await,
+            @r###"
+        ---
+        - +------------+--------------+
+        - "| table_name | column_count |"
+        - +------------+--------------+
+        - "| cpu        | 3            |"
+        - "| mem        | 3            |"
+        - +------------+--------------+
+        "###
+        );
+
+        insta::assert_yaml_snapshot!(
+            format_query(
+                &querier_namespace,
+                "SELECT column_name, column_type, primary_key FROM system.columns WHERE table_name = 'cpu'",
+            ).await,
+            @r###"
+        ---
+        - +-------------+-------------+-------------+
+        - "| column_name | column_type | primary_key |"
+        - +-------------+-------------+-------------+
+        - "| host        | tag         | true        |"
+        - "| load        | f64         | false       |"
+        - "| time        | time        | true        |"
+        - +-------------+-------------+-------------+
+        "###
+        );
+
+        insta::assert_yaml_snapshot!(
+            format_query(
+                &querier_namespace,
+                "SELECT table_name, partition_key, file_count FROM system.partitions",
+            ).await,
+            @r###"
+        ---
+        - +------------+---------------+------------+
+        - "| table_name | partition_key | file_count |"
+        - +------------+---------------+------------+
+        - "| cpu        | a             | 2          |"
+        - "| cpu        | b             | 1          |"
+        - "| mem        | c             | 1          |"
+        - +------------+---------------+------------+
+        "###
+        );
+
+        insta::assert_yaml_snapshot!(
+            format_query(
+                &querier_namespace,
+                "SELECT row_count, min_time, max_time FROM system.parquet_files WHERE table_name = 'cpu'",
+            ).await,
+            @r###"
+        ---
+        - +-----------+--------------------------------+--------------------------------+
+        - "| row_count | min_time                       | max_time                       |"
+        - +-----------+--------------------------------+--------------------------------+
+        - "| 1         | 1970-01-01T00:00:00.000000011Z | 1970-01-01T00:00:00.000000011Z |"
+        - "| 1         | 1970-01-01T00:00:00.000000044Z | 1970-01-01T00:00:00.000000044Z |"
+        - "| 2         | 1970-01-01T00:00:00.000000022Z | 1970-01-01T00:00:00.000000033Z |"
+        - +-----------+--------------------------------+--------------------------------+
+        "###
+        );
+    }
+
     async fn format_query(querier_namespace: &Arc<QuerierNamespace>, sql: &str) -> Vec<String> {
         format_query_with_span_ctx(querier_namespace, sql, None).await
     }
@@ -0,0 +1,162 @@
+//! Per-namespace quotas on the queries run by the querier.
+
+use std::{collections::HashMap, sync::Arc};
+
+use data_types::{NamespaceId, NamespaceQueryLimits};
+use datafusion::execution::memory_pool::MemoryPool;
+use iox_query::exec::memory_tracker::LimitedMemoryPool;
+use parking_lot::Mutex;
+use trace::span::Span;
+use tracker::{
+    AsyncSemaphoreMetrics, InstrumentedAsyncOwnedSemaphorePermit, InstrumentedAsyncSemaphore,
+};
+
+use crate::QuerierDatabase;
+
+/// Resources shared by all queries against a single namespace, enforcing its
+/// [`NamespaceQueryLimits`].
+#[derive(Debug)]
+pub(crate) struct NamespaceQuota {
+    /// The limits this quota enforces.
+    limits: NamespaceQueryLimits,
+
+    /// Limits the number of concurrent queries, if limited.
+    semaphore: Option<Arc<InstrumentedAsyncSemaphore>>,
+
+    /// Pool from which all queries reserve their memory, if limited.
+    memory_pool: Option<Arc<dyn MemoryPool>>,
+}
+
+impl NamespaceQuota {
+    /// Wait until the namespace is below its concurrency limit, returning the permit that counts
+    /// the query against it.
+    ///
+    /// Returns `None` if the number of concurrent queries is not limited.
+    pub(crate) async fn acquire(
+        &self,
+        span: Option<Span>,
+    ) -> Option<InstrumentedAsyncOwnedSemaphorePermit> {
+        let semaphore = self.semaphore.as_ref()?;
+        Some(
+            Arc::clone(semaphore)
+                .acquire_owned(span)
+                .await
+                .expect("Semaphore should not be closed by anyone"),
+        )
+    }
+
+    /// The pool from which queries reserve their memory, or `None` if they reserve it straight
+    /// from the pool of the executor.
+    pub(crate) fn memory_pool(&self) -> Option<Arc<dyn MemoryPool>> {
+        self.memory_pool.clone()
+    }
+}
+
+/// The [`NamespaceQuota`]s of all namespaces queried so far.
+///
+/// The quota of a namespace is rebuilt when its limits change. Queries that are already running
+/// keep the quota they were admitted with, so until they complete the namespace may exceed new,
+/// lower limits.
+#[derive(Debug)]
+pub(crate) struct NamespaceQuotas {
+    /// The pool shared by all queries, from which all namespace pools reserve their memory.
+    exec_memory_pool: Arc<dyn MemoryPool>,
+
+    /// Metrics shared by the semaphores of all namespaces.
+    semaphore_metrics: Arc<AsyncSemaphoreMetrics>,
+
+    quotas: Mutex<HashMap<NamespaceId, Arc<NamespaceQuota>>>,
+}
+
+impl NamespaceQuotas {
+    pub(crate) fn new(
+        exec_memory_pool: Arc<dyn MemoryPool>,
+        metric_registry: &metric::Registry,
+    ) -> Self {
+        let semaphore_metrics = Arc::new(AsyncSemaphoreMetrics::new(
+            metric_registry,
+            &[("semaphore", "namespace_query_execution")],
+        ));
+
+        Self {
+            exec_memory_pool,
+            semaphore_metrics,
+            quotas: Default::default(),
+        }
+    }
+
+    /// The quota of the namespace `id`, enforcing `limits`.
+    pub(crate) fn get(&self, id: NamespaceId, limits: NamespaceQueryLimits) -> Arc<NamespaceQuota> {
+        let mut quotas = self.quotas.lock();
+        if let Some(quota) = quotas.get(&id).filter(|quota| quota.limits == limits) {
+            return Arc::clone(quota);
+        }
+
+        let semaphore = limits.max_concurrent_queries.map(|n| {
+            // a limit of 0 would block all queries forever
+            let n = n.clamp(1, QuerierDatabase::MAX_CONCURRENT_QUERIES_MAX);
+            Arc::new(self.semaphore_metrics.new_semaphore(n))
+        });
+        let memory_pool = limits.max_total_query_memory_bytes.map(|limit| {
+            Arc::new(LimitedMemoryPool::new(
+                Arc::clone(&self.exec_memory_pool),
+                limit,
+            )) as Arc<dyn MemoryPool>
+        });
+
+        let quota = Arc::new(NamespaceQuota {
+            limits,
+            semaphore,
+            memory_pool,
+        });
+        quotas.insert(id, Arc::clone(&quota));
+        quota
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::execution::memory_pool::{GreedyMemoryPool, MemoryConsumer};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_quota() {
+        let exec_memory_pool: Arc<dyn MemoryPool> = Arc::new(GreedyMemoryPool::new(1000));
+        let quotas =
+            NamespaceQuotas::new(Arc::clone(&exec_memory_pool), &metric::Registry::default());
+        let id = NamespaceId::new(1);
+
+        // no limits
+        let quota = quotas.get(id, NamespaceQueryLimits::default());
+        assert!(quota.acquire(None).await.is_none());
+        assert!(quota.memory_pool().is_none());
+
+        let limits = NamespaceQueryLimits {
+            max_concurrent_queries: Some(1),
+            max_query_memory_bytes: Some(10),
+            max_total_query_memory_bytes: Some(100),
+        };
+        let quota = quotas.get(id, limits);
+        assert!(Arc::ptr_eq(&quota, &quotas.get(id, limits)));
+
+        let permit = quota.acquire(None).await.unwrap();
+        let mut blocked = Box::pin(quota.acquire(None));
+        assert!(futures::poll!(&mut blocked).is_pending());
+        drop(permit);
+        assert!(blocked.await.is_some());
+
+        let memory_pool = quota.memory_pool().unwrap();
+        let mut reservation = MemoryConsumer::new("test").register(&memory_pool);
+        reservation.try_grow(100).unwrap();
+        reservation.try_grow(1).unwrap_err();
+        assert_eq!(exec_memory_pool.reserved(), 100);
+
+        // changing the limits rebuilds the quota
+        let new_limits = NamespaceQueryLimits {
+            max_concurrent_queries: Some(2),
+            ..limits
+        };
+        assert!(!Arc::ptr_eq(&quota, &quotas.get(id, new_limits)));
+    }
+}
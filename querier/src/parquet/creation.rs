@@ -5,7 +5,11 @@ use futures::StreamExt;
 use iox_catalog::interface::Catalog;
 use iox_query::{pruning::prune_summaries, util::create_basic_summary};
 use observability_deps::tracing::debug;
-use parquet_file::chunk::ParquetChunk;
+use parquet_file::{
+    bloom_filter::{select_row_groups, TagValues},
+    chunk::ParquetChunk,
+    ParquetFilePath,
+};
 use predicate::Predicate;
 use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
 use schema::sort::SortKey;
@@ -128,6 +132,8 @@ impl ChunkAdapter {
             parquet_files.shuffle(&mut rng);
         }
 
+        let tag_values = TagValues::from_predicate(predicate);
+
         {
             let span_recorder = span_recorder.child("create individual chunks");
 
@@ -135,9 +141,19 @@ impl ChunkAdapter {
                 .map(|cached_parquet_file| {
                     let span_recorder = &span_recorder;
                     let cached_table = Arc::clone(&cached_table);
+                    let tag_values = &tag_values;
+                    let observer = &early_pruning_observer;
                     async move {
                         let span = span_recorder.child_span("new_chunk");
-                        self.new_chunk(cached_table, cached_parquet_file, span)
+                        let chunk = self
+                            .new_chunk(cached_table, cached_parquet_file, span)
+                            .await?;
+
+                        if tag_values.is_empty() {
+                            return Some(chunk);
+                        }
+                        let span = span_recorder.child_span("apply bloom filters");
+                        self.apply_bloom_filters(chunk, tag_values, observer, span)
                             .await
                     }
                 })
@@ -237,4 +253,49 @@ impl ChunkAdapter {
 
         Some(QuerierParquetChunk::new(parquet_chunk, meta))
     }
+
+    /// Use the bloom filters of the parquet file of `chunk` to restrict the scan to the row groups that may contain the
+    /// required `tag_values`, returning [`None`] if no row group can.
+    ///
+    /// Only the metadata and bloom filters at the end of the file are read, through the object store cache.
+    ///
+    /// Failing to read the bloom filters is not fatal, the chunk is then scanned in full.
+    async fn apply_bloom_filters(
+        &self,
+        chunk: QuerierParquetChunk,
+        tag_values: &TagValues,
+        observer: &MetricPruningObserver,
+        span: Option<Span>,
+    ) -> Option<QuerierParquetChunk> {
+        let _span_recorder = SpanRecorder::new(span);
+
+        let parquet_file = Arc::clone(chunk.parquet_chunk.parquet_file());
+        let path = ParquetFilePath::from(parquet_file.as_ref()).object_store_path();
+        let store = self.catalog_cache.parquet_store();
+
+        let selection = match select_row_groups(
+            store.object_store().as_ref(),
+            &path,
+            parquet_file.file_size_bytes as usize,
+            tag_values,
+        )
+        .await
+        {
+            Ok(selection) => selection,
+            Err(e) => {
+                debug!(%e, %path, "could not apply bloom filters");
+                return Some(chunk);
+            }
+        };
+
+        if selection.is_empty() {
+            observer.was_pruned_bloom_filter(
+                parquet_file.row_count as u64,
+                parquet_file.file_size_bytes as u64,
+            );
+            return None;
+        }
+
+        Some(chunk.with_file_range(selection.file_range()))
+    }
 }
@@ -1,8 +1,9 @@
 //! Querier Chunks
 
 use data_types::{
-    ChunkId, ChunkOrder, CompactionLevel, DeletePredicate, PartitionId, TableSummary,
+    ChunkId, ChunkOrder, CompactionLevel, DeletePredicate, PartitionId, TableSummary, Timestamp,
 };
+use datafusion::datasource::listing::FileRange;
 use iox_query::util::create_basic_summary;
 use parquet_file::chunk::ParquetChunk;
 use schema::sort::SortKey;
@@ -67,6 +68,9 @@ pub struct QuerierParquetChunk {
 
     /// Table summary
     table_summary: Arc<TableSummary>,
+
+    /// Byte range of the parquet file containing the row groups that may match the query, if not the entire file.
+    file_range: Option<FileRange>,
 }
 
 impl QuerierParquetChunk {
@@ -83,9 +87,15 @@ impl QuerierParquetChunk {
             delete_predicates: Vec::new(),
             parquet_chunk,
             table_summary,
+            file_range: None,
         }
     }
 
+    /// Restrict the scan of the parquet file to the given byte range.
+    pub fn with_file_range(self, file_range: Option<FileRange>) -> Self {
+        Self { file_range, ..self }
+    }
+
     /// Set delete predicates of the given chunk.
     pub fn with_delete_predicates(self, delete_predicates: Vec<Arc<DeletePredicate>>) -> Self {
         Self {
@@ -99,6 +109,11 @@ impl QuerierParquetChunk {
         self.meta.as_ref()
     }
 
+    /// The creation time of the newest level 0 file whose data is in this chunk.
+    pub fn max_l0_created_at(&self) -> Timestamp {
+        self.parquet_chunk.parquet_file().max_l0_created_at
+    }
+
     pub fn estimate_size(&self) -> usize {
         self.parquet_chunk.parquet_file().file_size_bytes as usize
     }
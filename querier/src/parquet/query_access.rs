@@ -74,7 +74,9 @@ impl QueryChunk for QuerierParquetChunk {
     }
 
     fn data(&self) -> QueryChunkData {
-        QueryChunkData::Parquet(self.parquet_chunk.parquet_exec_input())
+        let mut input = self.parquet_chunk.parquet_exec_input();
+        input.range = self.file_range.clone();
+        QueryChunkData::Parquet(input)
     }
 
     fn chunk_type(&self) -> &str {
@@ -1,21 +1,42 @@
 //! Ring buffer of queries that have been run with some brief information
+//!
+//! Completed entries can additionally be emitted as structured log lines or appended to a local
+//! rolling file for auditing, see [`QueryLogOutput`].
 
 use data_types::NamespaceId;
-use iox_query::QueryText;
+use iox_query::{CancelQueryError, QueryStats, QueryText};
 use iox_time::{Time, TimeProvider};
+use observability_deps::tracing::{info, warn};
 use parking_lot::Mutex;
 use std::{
     collections::VecDeque,
-    sync::{atomic, Arc},
+    fs::{self, File, OpenOptions},
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+    sync::{
+        atomic::{self, AtomicU64},
+        mpsc::{self, SyncSender, TrySendError},
+        Arc,
+    },
+    thread::JoinHandle,
     time::Duration,
 };
+use tokio_util::sync::CancellationToken;
 use trace::ctx::TraceId;
+use uuid::Uuid;
 
 // The query duration used for queries still running.
 const UNCOMPLETED_DURATION: i64 = -1;
 
+/// The number of completed entries waiting to be written to the query log file before further
+/// entries are dropped.
+const FILE_OUTPUT_QUEUE_DEPTH: usize = 1_000;
+
 /// Information about a single query that was executed
 pub struct QueryLogEntry {
+    /// Unique ID of this query, used to cancel it.
+    pub id: Uuid,
+
     /// Namespace ID.
     pub namespace_id: NamespaceId,
 
@@ -37,16 +58,24 @@ pub struct QueryLogEntry {
 
     /// If the query completed successfully
     pub success: atomic::AtomicBool,
+
+    /// Resource usage, set when the query completes.
+    stats: Mutex<Option<QueryLogStats>>,
+
+    /// Cancels the execution of this query.
+    cancellation_token: CancellationToken,
 }
 
 impl std::fmt::Debug for QueryLogEntry {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("QueryLogEntry")
+            .field("id", &self.id)
             .field("query_type", &self.query_type)
             .field("query_text", &self.query_text.to_string())
             .field("issue_time", &self.issue_time)
             .field("query_completed_duration", &self.query_completed_duration)
             .field("success", &self.success)
+            .field("stats", &self.stats)
             .finish()
     }
 }
@@ -61,6 +90,7 @@ impl QueryLogEntry {
         issue_time: Time,
     ) -> Self {
         Self {
+            id: Uuid::new_v4(),
             namespace_id,
             query_type,
             query_text,
@@ -68,9 +98,16 @@ impl QueryLogEntry {
             issue_time,
             query_completed_duration: UNCOMPLETED_DURATION.into(),
             success: atomic::AtomicBool::new(false),
+            stats: Mutex::new(None),
+            cancellation_token: CancellationToken::new(),
         }
     }
 
+    /// Token that is cancelled when this query is cancelled through [`QueryLog::cancel`].
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.cancellation_token.clone()
+    }
+
     /// If this query is completed, returns `Some(duration)` of how
     /// long it took
     pub fn query_completed_duration(&self) -> Option<Duration> {
@@ -96,6 +133,136 @@ impl QueryLogEntry {
             .store(dur.as_nanos() as i64, atomic::Ordering::Relaxed);
         self.success.store(success, atomic::Ordering::SeqCst);
     }
+
+    /// Resource usage of this query, if it has completed.
+    pub fn stats(&self) -> Option<QueryLogStats> {
+        self.stats.lock().clone()
+    }
+
+    fn set_stats(&self, stats: QueryLogStats) {
+        *self.stats.lock() = Some(stats);
+    }
+}
+
+/// Resource usage of a completed query.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryLogStats {
+    /// Time spent planning the query.
+    pub planning_duration: Option<Duration>,
+
+    /// Time spent executing the query and returning the results, after planning.
+    pub execution_duration: Option<Duration>,
+
+    /// Number of rows returned to the client.
+    pub rows_returned: u64,
+
+    /// Size of the results returned to the client, in bytes.
+    pub bytes_returned: u64,
+
+    /// Number of parquet files scanned.
+    pub parquet_files: u64,
+
+    /// Total size of the parquet files scanned, in bytes.
+    pub parquet_bytes: u64,
+
+    /// Number of partitions returned by the ingesters.
+    pub ingester_partitions: u64,
+
+    /// The longest time spent waiting for a response from the ingesters, if they were queried.
+    pub ingester_latency: Option<Duration>,
+
+    /// Peak memory reserved from the DataFusion memory pool, in bytes.
+    pub peak_memory_bytes: u64,
+
+    /// The error the query failed with, if any.
+    pub error: Option<String>,
+}
+
+impl QueryLogStats {
+    /// Combine the statistics reported on query completion with those collected by the querier.
+    pub fn new(query: QueryStats, ingester: &IngesterStats, peak_memory_bytes: usize) -> Self {
+        Self {
+            planning_duration: query.planning_duration,
+            execution_duration: None,
+            rows_returned: query.rows_returned,
+            bytes_returned: query.bytes_returned,
+            parquet_files: query.parquet_files,
+            parquet_bytes: query.parquet_bytes,
+            ingester_partitions: ingester.partitions.load(atomic::Ordering::Relaxed),
+            ingester_latency: ingester.latency(),
+            peak_memory_bytes: peak_memory_bytes as u64,
+            error: query.error,
+        }
+    }
+}
+
+/// Ingester requests made while planning a single query.
+///
+/// Attached to the DataFusion session of each query so that it can be updated by the tables of
+/// the query.
+#[derive(Debug)]
+pub struct IngesterStats {
+    partitions: AtomicU64,
+
+    /// Longest latency in nanoseconds, or [`UNCOMPLETED_DURATION`] if the ingesters were not
+    /// queried.
+    latency: atomic::AtomicI64,
+}
+
+impl Default for IngesterStats {
+    fn default() -> Self {
+        Self {
+            partitions: AtomicU64::new(0),
+            latency: UNCOMPLETED_DURATION.into(),
+        }
+    }
+}
+
+impl IngesterStats {
+    /// Record an ingester request returning `partitions` after `latency`.
+    pub fn record(&self, partitions: usize, latency: Duration) {
+        self.partitions
+            .fetch_add(partitions as u64, atomic::Ordering::Relaxed);
+        self.latency
+            .fetch_max(latency.as_nanos() as i64, atomic::Ordering::Relaxed);
+    }
+
+    fn latency(&self) -> Option<Duration> {
+        match self.latency.load(atomic::Ordering::Relaxed) {
+            UNCOMPLETED_DURATION => None,
+            d => Some(Duration::from_nanos(d as u64)),
+        }
+    }
+}
+
+/// Where completed query log entries are written, in addition to the in-memory log.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum QueryLogOutput {
+    /// Only keep entries in memory.
+    #[default]
+    None,
+
+    /// Emit an `info` log line per completed query.
+    Log,
+
+    /// Append a JSON line per completed query to a file.
+    ///
+    /// Lines are written by a dedicated thread, so queries never wait on file I/O. If the thread
+    /// falls more than a fixed number of entries behind, further entries are dropped from the
+    /// file (but kept in memory).
+    ///
+    /// Once the file exceeds `max_bytes` it is renamed to `<path>.1` (shifting older files to
+    /// `<path>.2` and so on) and a new file is started, keeping at most `max_files` old files.
+    File {
+        /// Path of the current file.
+        path: PathBuf,
+
+        /// Size at which the file is rotated.
+        max_bytes: u64,
+
+        /// Number of rotated files to keep.
+        max_files: usize,
+    },
 }
 
 /// Stores a fixed number `QueryExecutions` -- handles locking
@@ -105,6 +272,7 @@ pub struct QueryLog {
     log: Mutex<VecDeque<Arc<QueryLogEntry>>>,
     max_size: usize,
     time_provider: Arc<dyn TimeProvider>,
+    output: Output,
 }
 
 impl QueryLog {
@@ -115,9 +283,27 @@ impl QueryLog {
             log: Mutex::new(VecDeque::with_capacity(max_size)),
             max_size,
             time_provider,
+            output: Output::None,
         }
     }
 
+    /// Also write completed entries to `output`.
+    pub fn with_output(self, output: QueryLogOutput) -> Self {
+        let output = match output {
+            QueryLogOutput::None => Output::None,
+            QueryLogOutput::Log => Output::Log,
+            QueryLogOutput::File {
+                path,
+                max_bytes,
+                max_files,
+            } => Output::File(FileOutput::new(RollingFile::new(
+                path, max_bytes, max_files,
+            ))),
+        };
+
+        Self { output, ..self }
+    }
+
     pub fn push(
         &self,
         namespace_id: NamespaceId,
@@ -153,15 +339,274 @@ impl QueryLog {
         log.clone()
     }
 
+    /// Cancel the running query with `query_id` in the given namespace.
+    pub fn cancel(
+        &self,
+        namespace_id: NamespaceId,
+        query_id: &str,
+    ) -> Result<(), CancelQueryError> {
+        let id = Uuid::parse_str(query_id).map_err(|_| CancelQueryError::InvalidId {
+            query_id: query_id.to_string(),
+        })?;
+
+        let entry = self
+            .log
+            .lock()
+            .iter()
+            .find(|entry| entry.id == id && entry.namespace_id == namespace_id)
+            .map(Arc::clone)
+            .ok_or_else(|| CancelQueryError::NotFound {
+                query_id: query_id.to_string(),
+            })?;
+
+        if entry.query_completed_duration().is_some() {
+            return Err(CancelQueryError::Completed {
+                query_id: query_id.to_string(),
+            });
+        }
+
+        info!(%namespace_id, %query_id, "cancelling query");
+        entry.cancellation_token.cancel();
+        Ok(())
+    }
+
     /// Marks the provided query entry as completed using the current time.
     /// `success` specifies the query ran successfully
-    pub fn set_completed(&self, entry: Arc<QueryLogEntry>, success: bool) {
-        entry.set_completed(self.time_provider.now(), success)
+    pub fn set_completed(
+        &self,
+        entry: Arc<QueryLogEntry>,
+        success: bool,
+        mut stats: QueryLogStats,
+    ) {
+        entry.set_completed(self.time_provider.now(), success);
+
+        if let (Some(total), Some(planning)) =
+            (entry.query_completed_duration(), stats.planning_duration)
+        {
+            stats.execution_duration = Some(total.saturating_sub(planning));
+        }
+        entry.set_stats(stats);
+
+        match &self.output {
+            Output::None => {}
+            Output::Log => log_entry(&entry),
+            Output::File(file) => {
+                let mut line = entry_to_json(&entry).to_string();
+                line.push('\n');
+                file.send(line);
+            }
+        }
+    }
+}
+
+/// Resolved [`QueryLogOutput`].
+#[derive(Debug)]
+enum Output {
+    None,
+    Log,
+    File(FileOutput),
+}
+
+/// Sends lines to a thread that appends them to a [`RollingFile`].
+///
+/// Dropping the [`FileOutput`] waits for all queued lines to be written.
+#[derive(Debug)]
+struct FileOutput {
+    tx: Option<SyncSender<String>>,
+    writer: Option<JoinHandle<()>>,
+}
+
+impl FileOutput {
+    fn new(mut file: RollingFile) -> Self {
+        let (tx, rx) = mpsc::sync_channel::<String>(FILE_OUTPUT_QUEUE_DEPTH);
+
+        let writer = std::thread::Builder::new()
+            .name("query log writer".to_string())
+            .spawn(move || {
+                while let Ok(line) = rx.recv() {
+                    // Write everything that is queued before flushing.
+                    let res = std::iter::once(line)
+                        .chain(rx.try_iter())
+                        .try_for_each(|line| file.write(line.as_bytes()))
+                        .and_then(|_| file.flush());
+                    if let Err(e) = res {
+                        warn!(%e, "failed to write query log file");
+                    }
+                }
+            })
+            .expect("failed to spawn query log writer thread");
+
+        Self {
+            tx: Some(tx),
+            writer: Some(writer),
+        }
+    }
+
+    /// Queue `line` to be written, dropping it if the writer is too far behind.
+    fn send(&self, line: String) {
+        let tx = self.tx.as_ref().expect("sender only taken on drop");
+        match tx.try_send(line) {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) => {
+                warn!("query log file writer is falling behind, dropping entry")
+            }
+            Err(TrySendError::Disconnected(_)) => {
+                warn!("query log file writer stopped, dropping entry")
+            }
+        }
+    }
+}
+
+impl Drop for FileOutput {
+    fn drop(&mut self) {
+        // Closing the channel stops the writer once it has written the queued lines.
+        drop(self.tx.take());
+        if let Some(writer) = self.writer.take() {
+            if writer.join().is_err() {
+                warn!("query log writer thread panicked");
+            }
+        }
+    }
+}
+
+fn log_entry(entry: &QueryLogEntry) {
+    let stats = entry.stats().unwrap_or_default();
+    info!(
+        query_id=%entry.id,
+        namespace_id=%entry.namespace_id,
+        query_type=%entry.query_type,
+        query_text=%entry.query_text,
+        trace_id=?entry.trace_id.map(|x| format!("{:x}", x.0)),
+        issue_time=%entry.issue_time,
+        duration=?entry.query_completed_duration(),
+        success=entry.success(),
+        planning_duration=?stats.planning_duration,
+        execution_duration=?stats.execution_duration,
+        rows_returned=stats.rows_returned,
+        bytes_returned=stats.bytes_returned,
+        parquet_files=stats.parquet_files,
+        parquet_bytes=stats.parquet_bytes,
+        ingester_partitions=stats.ingester_partitions,
+        ingester_latency=?stats.ingester_latency,
+        peak_memory_bytes=stats.peak_memory_bytes,
+        error=?stats.error,
+        "query completed",
+    );
+}
+
+fn entry_to_json(entry: &QueryLogEntry) -> serde_json::Value {
+    let nanos = |d: Option<Duration>| d.map(|d| d.as_nanos() as u64);
+    let stats = entry.stats().unwrap_or_default();
+
+    serde_json::json!({
+        "query_id": entry.id.to_string(),
+        "namespace_id": entry.namespace_id.get(),
+        "query_type": entry.query_type,
+        "query_text": entry.query_text.to_string(),
+        "trace_id": entry.trace_id.map(|x| format!("{:x}", x.0)),
+        "issue_time": entry.issue_time.to_rfc3339(),
+        "duration_ns": nanos(entry.query_completed_duration()),
+        "success": entry.success(),
+        "planning_duration_ns": nanos(stats.planning_duration),
+        "execution_duration_ns": nanos(stats.execution_duration),
+        "rows_returned": stats.rows_returned,
+        "bytes_returned": stats.bytes_returned,
+        "parquet_files": stats.parquet_files,
+        "parquet_bytes": stats.parquet_bytes,
+        "ingester_partitions": stats.ingester_partitions,
+        "ingester_latency_ns": nanos(stats.ingester_latency),
+        "peak_memory_bytes": stats.peak_memory_bytes,
+        "error": stats.error,
+    })
+}
+
+/// A file that is rotated once it exceeds a maximum size.
+#[derive(Debug)]
+struct RollingFile {
+    path: PathBuf,
+    max_bytes: u64,
+    max_files: usize,
+
+    /// The open file and its size, opened on first write.
+    current: Option<(BufWriter<File>, u64)>,
+}
+
+impl RollingFile {
+    fn new(path: PathBuf, max_bytes: u64, max_files: usize) -> Self {
+        Self {
+            path,
+            max_bytes,
+            max_files,
+            current: None,
+        }
+    }
+
+    /// Append `buf` to the file, rotating it first if `buf` would take it over `max_bytes`.
+    fn write(&mut self, buf: &[u8]) -> io::Result<()> {
+        let size = match &self.current {
+            Some((_, size)) => *size,
+            None => fs::metadata(&self.path).map(|m| m.len()).unwrap_or(0),
+        };
+
+        if size > 0 && size + buf.len() as u64 > self.max_bytes {
+            self.flush()?;
+            self.current = None;
+            self.rotate()?;
+        }
+
+        let (file, size) = match &mut self.current {
+            Some(current) => current,
+            None => {
+                let file = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&self.path)?;
+                let size = file.metadata()?.len();
+                self.current.insert((BufWriter::new(file), size))
+            }
+        };
+
+        file.write_all(buf)?;
+        *size += buf.len() as u64;
+        Ok(())
+    }
+
+    /// Flush buffered writes to the open file, if any.
+    fn flush(&mut self) -> io::Result<()> {
+        match &mut self.current {
+            Some((file, _)) => file.flush(),
+            None => Ok(()),
+        }
+    }
+
+    /// Shift `<path>.N` to `<path>.N+1`, dropping the oldest file, and move `<path>` to
+    /// `<path>.1`.
+    fn rotate(&self) -> io::Result<()> {
+        if self.max_files == 0 {
+            return fs::remove_file(&self.path);
+        }
+
+        for n in (1..self.max_files).rev() {
+            let from = self.rotated_path(n);
+            if from.exists() {
+                fs::rename(from, self.rotated_path(n + 1))?;
+            }
+        }
+        fs::rename(&self.path, self.rotated_path(1))
+    }
+
+    fn rotated_path(&self, n: usize) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{n}"));
+        path.into()
     }
 }
 
 #[cfg(test)]
 mod test_super {
+    use std::path::Path;
+
+    use assert_matches::assert_matches;
     use iox_time::MockProvider;
 
     use super::*;
@@ -198,4 +643,151 @@ mod test_super {
         );
         assert!(!entry.success());
     }
+
+    #[test]
+    fn test_query_log_cancel() {
+        let time_provider = Arc::new(MockProvider::new(Time::from_timestamp_millis(100).unwrap()));
+        let query_log = QueryLog::new(10, Arc::clone(&time_provider) as _);
+        let id = NamespaceId::new(1);
+        let entry = query_log.push(id, "sql", Box::new("SELECT 1"), None);
+        let query_id = entry.id.to_string();
+        let token = entry.cancellation_token();
+
+        assert_eq!(
+            query_log.cancel(id, "foo"),
+            Err(CancelQueryError::InvalidId {
+                query_id: "foo".to_string()
+            })
+        );
+        assert_matches!(
+            query_log.cancel(id, &Uuid::new_v4().to_string()),
+            Err(CancelQueryError::NotFound { .. })
+        );
+        // queries of other namespaces cannot be cancelled
+        assert_eq!(
+            query_log.cancel(NamespaceId::new(2), &query_id),
+            Err(CancelQueryError::NotFound {
+                query_id: query_id.clone()
+            })
+        );
+        assert!(!token.is_cancelled());
+
+        query_log.cancel(id, &query_id).unwrap();
+        assert!(token.is_cancelled());
+
+        query_log.set_completed(entry, false, QueryLogStats::default());
+        assert_eq!(
+            query_log.cancel(id, &query_id),
+            Err(CancelQueryError::Completed { query_id })
+        );
+    }
+
+    #[test]
+    fn test_query_log_stats() {
+        let time_provider = Arc::new(MockProvider::new(Time::from_timestamp_millis(100).unwrap()));
+        let query_log = QueryLog::new(10, Arc::clone(&time_provider) as _);
+        let entry = query_log.push(NamespaceId::new(1), "sql", Box::new("SELECT 1"), None);
+        assert_eq!(entry.stats(), None);
+
+        let ingester_stats = IngesterStats::default();
+        ingester_stats.record(2, Duration::from_millis(30));
+        ingester_stats.record(1, Duration::from_millis(10));
+
+        let query_stats = QueryStats {
+            planning_duration: Some(Duration::from_millis(50)),
+            rows_returned: 3,
+            bytes_returned: 100,
+            parquet_files: 2,
+            parquet_bytes: 2000,
+            error: Some("boom".to_string()),
+        };
+
+        time_provider.set(Time::from_timestamp_millis(300).unwrap());
+        query_log.set_completed(
+            Arc::clone(&entry),
+            false,
+            QueryLogStats::new(query_stats, &ingester_stats, 1024),
+        );
+
+        assert_eq!(
+            entry.stats(),
+            Some(QueryLogStats {
+                planning_duration: Some(Duration::from_millis(50)),
+                execution_duration: Some(Duration::from_millis(150)),
+                rows_returned: 3,
+                bytes_returned: 100,
+                parquet_files: 2,
+                parquet_bytes: 2000,
+                ingester_partitions: 3,
+                ingester_latency: Some(Duration::from_millis(30)),
+                peak_memory_bytes: 1024,
+                error: Some("boom".to_string()),
+            })
+        );
+        assert_eq!(IngesterStats::default().latency(), None);
+    }
+
+    #[test]
+    fn test_query_log_file_output() {
+        let dir = test_helpers::tmp_dir().unwrap();
+        let path = dir.path().join("queries.log");
+
+        let time_provider = Arc::new(MockProvider::new(Time::from_timestamp_millis(100).unwrap()));
+        let query_log = QueryLog::new(10, time_provider).with_output(QueryLogOutput::File {
+            path: path.clone(),
+            max_bytes: 1,
+            max_files: 2,
+        });
+
+        for query in ["SELECT 1", "SELECT 2", "SELECT 3", "SELECT 4"] {
+            let entry = query_log.push(NamespaceId::new(1), "sql", Box::new(query), None);
+            query_log.set_completed(entry, true, QueryLogStats::default());
+        }
+
+        // dropping the log waits for the queued entries to be written
+        drop(query_log);
+
+        // every entry exceeds the maximum size, so each is written to a new file and only the
+        // two most recent rotated files are kept
+        let read = |path: &Path| -> serde_json::Value {
+            let contents = fs::read_to_string(path).unwrap();
+            assert_eq!(contents.lines().count(), 1);
+            serde_json::from_str(&contents).unwrap()
+        };
+        let rotated = |n: usize| PathBuf::from(format!("{}.{n}", path.display()));
+
+        assert_eq!(read(&path)["query_text"], "SELECT 4");
+        assert_eq!(read(&rotated(1))["query_text"], "SELECT 3");
+        assert_eq!(read(&rotated(2))["query_text"], "SELECT 2");
+        assert!(!rotated(3).exists());
+
+        let entry = read(&path);
+        assert_eq!(entry["namespace_id"], 1);
+        assert_eq!(entry["query_type"], "sql");
+        assert_eq!(entry["success"], true);
+        assert_eq!(entry["duration_ns"], 0);
+        assert_eq!(entry["error"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn test_rolling_file_appends_below_limit() {
+        let dir = test_helpers::tmp_dir().unwrap();
+        let path = dir.path().join("queries.log");
+
+        let mut file = RollingFile::new(path.clone(), 10, 1);
+        file.write(b"abc\n").unwrap();
+        file.write(b"def\n").unwrap();
+        file.flush().unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "abc\ndef\n");
+
+        // reopening picks up the size of the existing file
+        let mut file = RollingFile::new(path.clone(), 10, 1);
+        file.write(b"ghi\n").unwrap();
+        file.flush().unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "ghi\n");
+        assert_eq!(
+            fs::read_to_string(file.rotated_path(1)).unwrap(),
+            "abc\ndef\n"
+        );
+    }
 }
@@ -0,0 +1,105 @@
+use crate::{
+    cache::namespace::CachedNamespace,
+    system_tables::{batch_iterator, sorted_tables, BatchIterator, IoxSystemTable},
+};
+use arrow::{
+    array::{ArrayRef, BooleanArray, Int64Array, StringArray},
+    datatypes::{DataType, Field, Schema, SchemaRef},
+    error::Result,
+    record_batch::RecordBatch,
+};
+use async_trait::async_trait;
+use data_types::ColumnType;
+use std::{collections::HashSet, sync::Arc};
+
+/// Implementation of system.columns table
+#[derive(Debug)]
+pub(super) struct ColumnsTable {
+    schema: SchemaRef,
+    namespace: Arc<CachedNamespace>,
+}
+
+impl ColumnsTable {
+    pub(super) fn new(namespace: Arc<CachedNamespace>) -> Self {
+        Self {
+            schema: columns_schema(),
+            namespace,
+        }
+    }
+}
+
+/// A single row of the system.columns table.
+struct ColumnRow<'a> {
+    table_name: &'a str,
+    column_id: Option<i64>,
+    column_name: &'a str,
+    column_type: ColumnType,
+    primary_key: bool,
+}
+
+#[async_trait]
+impl IoxSystemTable for ColumnsTable {
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    async fn scan(&self, batch_size: usize) -> Result<BatchIterator> {
+        let mut rows = vec![];
+        for (table_name, table) in sorted_tables(&self.namespace) {
+            let primary_key: HashSet<_> = table.schema.primary_key().into_iter().collect();
+
+            let mut columns = table.schema.iter().collect::<Vec<_>>();
+            columns.sort_unstable_by(|(_, a), (_, b)| a.name().cmp(b.name()));
+
+            rows.extend(columns.into_iter().map(|(column_type, field)| {
+                ColumnRow {
+                    table_name,
+                    column_id: table
+                        .column_id_map_rev
+                        .get(field.name().as_str())
+                        .map(|id| id.get()),
+                    column_name: field.name(),
+                    column_type: column_type.into(),
+                    primary_key: primary_key.contains(field.name().as_str()),
+                }
+            }));
+        }
+
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(
+                rows.iter()
+                    .map(|r| Some(r.table_name))
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(rows.iter().map(|r| r.column_id).collect::<Int64Array>()),
+            Arc::new(
+                rows.iter()
+                    .map(|r| Some(r.column_name))
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|r| Some(r.column_type.as_str()))
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|r| Some(r.primary_key))
+                    .collect::<BooleanArray>(),
+            ),
+        ];
+
+        let batch = RecordBatch::try_new(self.schema(), columns)?;
+        Ok(batch_iterator(batch, batch_size))
+    }
+}
+
+fn columns_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("table_name", DataType::Utf8, false),
+        Field::new("column_id", DataType::Int64, true),
+        Field::new("column_name", DataType::Utf8, false),
+        Field::new("column_type", DataType::Utf8, false),
+        Field::new("primary_key", DataType::Boolean, false),
+    ]))
+}
@@ -1,8 +1,13 @@
-use crate::query_log::QueryLog;
+use crate::{
+    cache::{
+        namespace::{CachedNamespace, CachedTable},
+        CatalogCache,
+    },
+    query_log::QueryLog,
+};
 use arrow::{datatypes::SchemaRef, error::Result as ArrowResult, record_batch::RecordBatch};
 use async_trait::async_trait;
 use data_types::NamespaceId;
-use datafusion::error::DataFusionError;
 use datafusion::{
     catalog::schema::SchemaProvider,
     datasource::TableProvider,
@@ -10,37 +15,80 @@ use datafusion::{
     execution::context::{SessionState, TaskContext},
     logical_expr::TableType,
     physical_plan::{
-        expressions::PhysicalSortExpr, ExecutionPlan, Partitioning, RecordBatchStream,
-        SendableRecordBatchStream, Statistics,
+        expressions::PhysicalSortExpr, stream::RecordBatchStreamAdapter, ExecutionPlan,
+        Partitioning, SendableRecordBatchStream, Statistics,
     },
     prelude::Expr,
 };
-use std::{
-    any::Any,
-    pin::Pin,
-    sync::Arc,
-    task::{Context, Poll},
-};
+use futures::StreamExt;
+use std::{any::Any, sync::Arc};
 
+mod columns;
+mod parquet_files;
+mod partitions;
 mod queries;
+mod tables;
 
 pub const SYSTEM_SCHEMA: &str = "system";
 
 const QUERIES_TABLE: &str = "queries";
-
-const ALL_SYSTEM_TABLES: &[&str] = &[QUERIES_TABLE];
+const TABLES_TABLE: &str = "tables";
+const COLUMNS_TABLE: &str = "columns";
+const PARTITIONS_TABLE: &str = "partitions";
+const PARQUET_FILES_TABLE: &str = "parquet_files";
+
+const ALL_SYSTEM_TABLES: &[&str] = &[
+    QUERIES_TABLE,
+    TABLES_TABLE,
+    COLUMNS_TABLE,
+    PARTITIONS_TABLE,
+    PARQUET_FILES_TABLE,
+];
 
 pub struct SystemSchemaProvider {
     queries: Arc<dyn TableProvider>,
+    tables: Arc<dyn TableProvider>,
+    columns: Arc<dyn TableProvider>,
+    partitions: Arc<dyn TableProvider>,
+    parquet_files: Arc<dyn TableProvider>,
 }
 
 impl SystemSchemaProvider {
-    pub fn new(query_log: Arc<QueryLog>, namespace_id: NamespaceId) -> Self {
+    pub fn new(
+        query_log: Arc<QueryLog>,
+        namespace_id: NamespaceId,
+        namespace: Arc<CachedNamespace>,
+        catalog_cache: Arc<CatalogCache>,
+    ) -> Self {
         let queries = Arc::new(SystemTableProvider {
             table: Arc::new(queries::QueriesTable::new(query_log, Some(namespace_id))),
         });
+        let tables = Arc::new(SystemTableProvider {
+            table: Arc::new(tables::TablesTable::new(Arc::clone(&namespace))),
+        });
+        let columns = Arc::new(SystemTableProvider {
+            table: Arc::new(columns::ColumnsTable::new(Arc::clone(&namespace))),
+        });
+        let partitions = Arc::new(SystemTableProvider {
+            table: Arc::new(partitions::PartitionsTable::new(
+                Arc::clone(&namespace),
+                Arc::clone(&catalog_cache),
+            )),
+        });
+        let parquet_files = Arc::new(SystemTableProvider {
+            table: Arc::new(parquet_files::ParquetFilesTable::new(
+                namespace,
+                catalog_cache,
+            )),
+        });
 
-        Self { queries }
+        Self {
+            queries,
+            tables,
+            columns,
+            partitions,
+            parquet_files,
+        }
     }
 }
 
@@ -60,6 +108,10 @@ impl SchemaProvider for SystemSchemaProvider {
     async fn table(&self, name: &str) -> Option<Arc<dyn TableProvider>> {
         match name {
             QUERIES_TABLE => Some(Arc::clone(&self.queries)),
+            TABLES_TABLE => Some(Arc::clone(&self.tables)),
+            COLUMNS_TABLE => Some(Arc::clone(&self.columns)),
+            PARTITIONS_TABLE => Some(Arc::clone(&self.partitions)),
+            PARQUET_FILES_TABLE => Some(Arc::clone(&self.parquet_files)),
             _ => None,
         }
     }
@@ -74,12 +126,36 @@ impl SchemaProvider for SystemSchemaProvider {
 type BatchIterator = Box<dyn Iterator<Item = ArrowResult<RecordBatch>> + Send + Sync>;
 
 /// The minimal thing that a system table needs to implement
+#[async_trait]
 trait IoxSystemTable: Send + Sync {
     /// Produce the schema from this system table
     fn schema(&self) -> SchemaRef;
 
     /// Get the contents of the system table
-    fn scan(&self, batch_size: usize) -> ArrowResult<BatchIterator>;
+    async fn scan(&self, batch_size: usize) -> ArrowResult<BatchIterator>;
+}
+
+/// Split `batch` into batches of at most `batch_size` rows.
+fn batch_iterator(batch: RecordBatch, batch_size: usize) -> BatchIterator {
+    let num_rows = batch.num_rows();
+    let batch_size = batch_size.max(1);
+
+    Box::new(
+        (0..num_rows)
+            .step_by(batch_size)
+            .map(move |offset| Ok(batch.slice(offset, batch_size.min(num_rows - offset)))),
+    )
+}
+
+/// The tables of `namespace`, ordered by name.
+fn sorted_tables(namespace: &CachedNamespace) -> Vec<(&str, &Arc<CachedTable>)> {
+    let mut tables = namespace
+        .tables
+        .iter()
+        .map(|(name, table)| (name.as_ref(), table))
+        .collect::<Vec<_>>();
+    tables.sort_unstable_by_key(|(name, _)| *name);
+    tables
 }
 
 /// Adapter that makes any `IoxSystemTable` a DataFusion `TableProvider`
@@ -173,41 +249,31 @@ impl<T: IoxSystemTable + 'static> ExecutionPlan for SystemTableExecutionPlan<T>
         context: Arc<TaskContext>,
     ) -> DataFusionResult<SendableRecordBatchStream> {
         let batch_size = context.session_config().batch_size();
-
-        Ok(Box::pin(SystemTableStream {
-            projected_schema: Arc::clone(&self.projected_schema),
-            batches: self.table.scan(batch_size)?,
-            projection: self.projection.clone(),
-        }))
+        let table = Arc::clone(&self.table);
+        let projection = self.projection.clone();
+
+        // The table contents are only read once the stream is polled, as
+        // reading them may require loading data into the querier caches.
+        let batches = futures::stream::once(async move { table.scan(batch_size).await })
+            .flat_map(|batches| match batches {
+                Ok(batches) => futures::stream::iter(batches).boxed(),
+                Err(e) => futures::stream::iter([Err(e)]).boxed(),
+            })
+            .map(move |maybe_batch| -> DataFusionResult<RecordBatch> {
+                let batch = maybe_batch?;
+                match &projection {
+                    Some(projection) => Ok(batch.project(projection)?),
+                    None => Ok(batch),
+                }
+            });
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            Arc::clone(&self.projected_schema),
+            batches,
+        )))
     }
 
     fn statistics(&self) -> Statistics {
         Statistics::default()
     }
 }
-
-struct SystemTableStream {
-    projected_schema: SchemaRef,
-    projection: Option<Vec<usize>>,
-    batches: BatchIterator,
-}
-
-impl RecordBatchStream for SystemTableStream {
-    fn schema(&self) -> SchemaRef {
-        Arc::clone(&self.projected_schema)
-    }
-}
-
-impl futures::Stream for SystemTableStream {
-    type Item = Result<RecordBatch, DataFusionError>;
-
-    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        Poll::Ready(self.batches.next().map(|maybe_batch| {
-            let batch = maybe_batch?;
-            match &self.projection {
-                Some(projection) => Ok(batch.project(projection)?),
-                None => Ok(batch),
-            }
-        }))
-    }
-}
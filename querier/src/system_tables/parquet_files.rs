@@ -0,0 +1,136 @@
+use crate::{
+    cache::{namespace::CachedNamespace, CatalogCache},
+    system_tables::{batch_iterator, sorted_tables, BatchIterator, IoxSystemTable},
+};
+use arrow::{
+    array::{ArrayRef, Int64Array, StringArray, TimestampNanosecondArray},
+    datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit},
+    error::Result,
+    record_batch::RecordBatch,
+};
+use async_trait::async_trait;
+use data_types::ParquetFile;
+use std::sync::Arc;
+
+/// Implementation of system.parquet_files table
+#[derive(Debug)]
+pub(super) struct ParquetFilesTable {
+    schema: SchemaRef,
+    namespace: Arc<CachedNamespace>,
+    catalog_cache: Arc<CatalogCache>,
+}
+
+impl ParquetFilesTable {
+    pub(super) fn new(namespace: Arc<CachedNamespace>, catalog_cache: Arc<CatalogCache>) -> Self {
+        Self {
+            schema: parquet_files_schema(),
+            namespace,
+            catalog_cache,
+        }
+    }
+}
+
+#[async_trait]
+impl IoxSystemTable for ParquetFilesTable {
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    async fn scan(&self, batch_size: usize) -> Result<BatchIterator> {
+        let mut files: Vec<(&str, Arc<ParquetFile>)> = vec![];
+        for (table_name, table) in sorted_tables(&self.namespace) {
+            let cached = self
+                .catalog_cache
+                .parquet_file()
+                .get(table.id, None, None)
+                .await;
+
+            let mut table_files = cached.files.iter().map(Arc::clone).collect::<Vec<_>>();
+            table_files.sort_unstable_by_key(|f| f.id);
+            files.extend(table_files.into_iter().map(|f| (table_name, f)));
+        }
+
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(
+                files
+                    .iter()
+                    .map(|(_, f)| Some(f.id.get()))
+                    .collect::<Int64Array>(),
+            ),
+            Arc::new(
+                files
+                    .iter()
+                    .map(|(table_name, _)| Some(*table_name))
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                files
+                    .iter()
+                    .map(|(_, f)| Some(f.partition_id.get()))
+                    .collect::<Int64Array>(),
+            ),
+            Arc::new(
+                files
+                    .iter()
+                    .map(|(_, f)| Some(f.object_store_id.to_string()))
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                files
+                    .iter()
+                    .map(|(_, f)| Some(f.compaction_level as i64))
+                    .collect::<Int64Array>(),
+            ),
+            Arc::new(
+                files
+                    .iter()
+                    .map(|(_, f)| Some(f.row_count))
+                    .collect::<Int64Array>(),
+            ),
+            Arc::new(
+                files
+                    .iter()
+                    .map(|(_, f)| Some(f.file_size_bytes))
+                    .collect::<Int64Array>(),
+            ),
+            Arc::new(
+                files
+                    .iter()
+                    .map(|(_, f)| Some(f.min_time.get()))
+                    .collect::<TimestampNanosecondArray>(),
+            ),
+            Arc::new(
+                files
+                    .iter()
+                    .map(|(_, f)| Some(f.max_time.get()))
+                    .collect::<TimestampNanosecondArray>(),
+            ),
+            Arc::new(
+                files
+                    .iter()
+                    .map(|(_, f)| Some(f.created_at.get()))
+                    .collect::<TimestampNanosecondArray>(),
+            ),
+        ];
+
+        let batch = RecordBatch::try_new(self.schema(), columns)?;
+        Ok(batch_iterator(batch, batch_size))
+    }
+}
+
+fn parquet_files_schema() -> SchemaRef {
+    let timestamp = || DataType::Timestamp(TimeUnit::Nanosecond, None);
+
+    Arc::new(Schema::new(vec![
+        Field::new("parquet_file_id", DataType::Int64, false),
+        Field::new("table_name", DataType::Utf8, false),
+        Field::new("partition_id", DataType::Int64, false),
+        Field::new("object_store_id", DataType::Utf8, false),
+        Field::new("compaction_level", DataType::Int64, false),
+        Field::new("row_count", DataType::Int64, false),
+        Field::new("file_size_bytes", DataType::Int64, false),
+        Field::new("min_time", timestamp(), false),
+        Field::new("max_time", timestamp(), false),
+        Field::new("created_at", timestamp(), false),
+    ]))
+}
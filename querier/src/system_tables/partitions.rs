@@ -0,0 +1,150 @@
+use crate::{
+    cache::{namespace::CachedNamespace, CatalogCache},
+    system_tables::{batch_iterator, sorted_tables, BatchIterator, IoxSystemTable},
+};
+use arrow::{
+    array::{ArrayRef, Int64Array, StringArray, TimestampNanosecondArray},
+    datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit},
+    error::Result,
+    record_batch::RecordBatch,
+};
+use async_trait::async_trait;
+use data_types::PartitionId;
+use std::{collections::BTreeMap, sync::Arc};
+
+/// Implementation of system.partitions table
+///
+/// Only partitions with persisted parquet files are listed.
+#[derive(Debug)]
+pub(super) struct PartitionsTable {
+    schema: SchemaRef,
+    namespace: Arc<CachedNamespace>,
+    catalog_cache: Arc<CatalogCache>,
+}
+
+impl PartitionsTable {
+    pub(super) fn new(namespace: Arc<CachedNamespace>, catalog_cache: Arc<CatalogCache>) -> Self {
+        Self {
+            schema: partitions_schema(),
+            namespace,
+            catalog_cache,
+        }
+    }
+}
+
+/// A single row of the system.partitions table.
+struct PartitionRow<'a> {
+    partition_id: PartitionId,
+    table_name: &'a str,
+    partition_key: Option<Arc<str>>,
+    sort_key: Option<String>,
+    new_file_at: Option<i64>,
+    file_count: i64,
+    file_size_bytes: i64,
+}
+
+#[async_trait]
+impl IoxSystemTable for PartitionsTable {
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    async fn scan(&self, batch_size: usize) -> Result<BatchIterator> {
+        let mut rows = vec![];
+        for (table_name, table) in sorted_tables(&self.namespace) {
+            let files = self
+                .catalog_cache
+                .parquet_file()
+                .get(table.id, None, None)
+                .await;
+
+            // (file count, total file size) by partition
+            let mut partitions: BTreeMap<PartitionId, (i64, i64)> = BTreeMap::new();
+            for file in files.files.iter() {
+                let entry = partitions.entry(file.partition_id).or_default();
+                entry.0 += 1;
+                entry.1 += file.file_size_bytes;
+            }
+
+            for (partition_id, (file_count, file_size_bytes)) in partitions {
+                let partition = self
+                    .catalog_cache
+                    .partition()
+                    .get(Arc::clone(table), partition_id, None)
+                    .await;
+
+                rows.push(PartitionRow {
+                    partition_id,
+                    table_name,
+                    partition_key: partition.as_ref().map(|p| Arc::clone(&p.key)),
+                    sort_key: partition.as_ref().and_then(|p| p.sort_key.as_ref()).map(
+                        |sort_key| sort_key.sort_key.to_columns().collect::<Vec<_>>().join(","),
+                    ),
+                    new_file_at: partition
+                        .as_ref()
+                        .and_then(|p| p.new_file_at)
+                        .map(|t| t.get()),
+                    file_count,
+                    file_size_bytes,
+                });
+            }
+        }
+
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(
+                rows.iter()
+                    .map(|r| Some(r.partition_id.get()))
+                    .collect::<Int64Array>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|r| Some(r.table_name))
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|r| r.partition_key.as_deref())
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|r| r.sort_key.as_deref())
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|r| r.new_file_at)
+                    .collect::<TimestampNanosecondArray>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|r| Some(r.file_count))
+                    .collect::<Int64Array>(),
+            ),
+            Arc::new(
+                rows.iter()
+                    .map(|r| Some(r.file_size_bytes))
+                    .collect::<Int64Array>(),
+            ),
+        ];
+
+        let batch = RecordBatch::try_new(self.schema(), columns)?;
+        Ok(batch_iterator(batch, batch_size))
+    }
+}
+
+fn partitions_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("partition_id", DataType::Int64, false),
+        Field::new("table_name", DataType::Utf8, false),
+        Field::new("partition_key", DataType::Utf8, true),
+        Field::new("sort_key", DataType::Utf8, true),
+        Field::new(
+            "new_file_at",
+            DataType::Timestamp(TimeUnit::Nanosecond, None),
+            true,
+        ),
+        Field::new("file_count", DataType::Int64, false),
+        Field::new("file_size_bytes", DataType::Int64, false),
+    ]))
+}
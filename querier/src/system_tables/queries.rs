@@ -1,19 +1,20 @@
 use crate::{
-    query_log::{QueryLog, QueryLogEntry},
+    query_log::{QueryLog, QueryLogEntry, QueryLogStats},
     system_tables::{BatchIterator, IoxSystemTable},
 };
 use arrow::{
     array::{
         ArrayRef, BooleanArray, DurationNanosecondArray, Int64Array, StringArray,
-        TimestampNanosecondArray,
+        TimestampNanosecondArray, UInt64Array,
     },
     datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit},
     error::Result,
     record_batch::RecordBatch,
 };
+use async_trait::async_trait;
 use data_types::NamespaceId;
 use observability_deps::tracing::error;
-use std::{collections::VecDeque, sync::Arc};
+use std::{collections::VecDeque, sync::Arc, time::Duration};
 
 /// Implementation of system.queries table
 #[derive(Debug)]
@@ -33,12 +34,13 @@ impl QueriesTable {
     }
 }
 
+#[async_trait]
 impl IoxSystemTable for QueriesTable {
     fn schema(&self) -> SchemaRef {
         Arc::clone(&self.schema)
     }
 
-    fn scan(&self, batch_size: usize) -> Result<BatchIterator> {
+    async fn scan(&self, batch_size: usize) -> Result<BatchIterator> {
         let schema = self.schema();
 
         let mut entries = self.query_log.entries();
@@ -80,6 +82,7 @@ fn queries_schema(include_namespace_id: bool) -> SchemaRef {
         columns.push(Field::new("namespace_id", DataType::Int64, false));
     }
     columns.append(&mut vec![
+        Field::new("query_id", DataType::Utf8, false),
         Field::new(
             "issue_time",
             DataType::Timestamp(TimeUnit::Nanosecond, None),
@@ -94,6 +97,28 @@ fn queries_schema(include_namespace_id: bool) -> SchemaRef {
         ),
         Field::new("success", DataType::Boolean, false),
         Field::new("trace_id", DataType::Utf8, true),
+        Field::new(
+            "planning_duration",
+            DataType::Duration(TimeUnit::Nanosecond),
+            true,
+        ),
+        Field::new(
+            "execution_duration",
+            DataType::Duration(TimeUnit::Nanosecond),
+            true,
+        ),
+        Field::new("rows_returned", DataType::UInt64, true),
+        Field::new("bytes_returned", DataType::UInt64, true),
+        Field::new("parquet_files", DataType::UInt64, true),
+        Field::new("parquet_bytes", DataType::UInt64, true),
+        Field::new("ingester_partitions", DataType::UInt64, true),
+        Field::new(
+            "ingester_latency",
+            DataType::Duration(TimeUnit::Nanosecond),
+            true,
+        ),
+        Field::new("peak_memory_bytes", DataType::UInt64, true),
+        Field::new("error", DataType::Utf8, true),
     ]);
 
     Arc::new(Schema::new(columns))
@@ -119,6 +144,15 @@ fn from_query_log_entries(
         ));
     }
 
+    columns.push(Arc::new(
+        entries
+            .iter()
+            .skip(offset)
+            .take(len)
+            .map(|e| Some(e.id.to_string()))
+            .collect::<StringArray>(),
+    ));
+
     columns.push(Arc::new(
         entries
             .iter()
@@ -174,6 +208,46 @@ fn from_query_log_entries(
             .collect::<StringArray>(),
     ));
 
+    // statistics are only available once a query has completed
+    let stats = entries
+        .iter()
+        .skip(offset)
+        .take(len)
+        .map(|e| e.stats())
+        .collect::<Vec<_>>();
+    let duration_column = |f: fn(&QueryLogStats) -> Option<Duration>| -> ArrayRef {
+        Arc::new(
+            stats
+                .iter()
+                .map(|s| s.as_ref().and_then(f).map(|d| d.as_nanos() as i64))
+                .collect::<DurationNanosecondArray>(),
+        )
+    };
+    let count_column = |f: fn(&QueryLogStats) -> u64| -> ArrayRef {
+        Arc::new(
+            stats
+                .iter()
+                .map(|s| s.as_ref().map(f))
+                .collect::<UInt64Array>(),
+        )
+    };
+
+    columns.push(duration_column(|s| s.planning_duration));
+    columns.push(duration_column(|s| s.execution_duration));
+    columns.push(count_column(|s| s.rows_returned));
+    columns.push(count_column(|s| s.bytes_returned));
+    columns.push(count_column(|s| s.parquet_files));
+    columns.push(count_column(|s| s.parquet_bytes));
+    columns.push(count_column(|s| s.ingester_partitions));
+    columns.push(duration_column(|s| s.ingester_latency));
+    columns.push(count_column(|s| s.peak_memory_bytes));
+    columns.push(Arc::new(
+        stats
+            .iter()
+            .map(|s| s.as_ref().and_then(|s| s.error.as_deref()))
+            .collect::<StringArray>(),
+    ));
+
     RecordBatch::try_new(schema, columns)
 }
 
@@ -184,8 +258,8 @@ mod tests {
     use iox_time::{Time, TimeProvider};
     use trace::ctx::TraceId;
 
-    #[test]
-    fn test_from_query_log() {
+    #[tokio::test]
+    async fn test_from_query_log() {
         let now = Time::from_rfc3339("1996-12-19T16:39:57+00:00").unwrap();
         let time_provider = Arc::new(iox_time::MockProvider::new(now));
 
@@ -218,7 +292,7 @@ mod tests {
             "+--------------+----------------------+-------------+-------------------+--------------------+---------+----------+",
         ];
 
-        let entries = table.scan(3).unwrap().collect::<Result<Vec<_>>>().unwrap();
+        let entries = scan_entry_columns(&table, 3).await;
         assert_eq!(entries.len(), 1);
         assert_batches_eq!(&expected, &entries);
 
@@ -239,7 +313,7 @@ mod tests {
             "+--------------+----------------------+-------------+-------------------+--------------------+---------+----------+",
         ];
 
-        let entries = table.scan(2).unwrap().collect::<Result<Vec<_>>>().unwrap();
+        let entries = scan_entry_columns(&table, 2).await;
         assert_eq!(entries.len(), 2);
         assert_batches_eq!(&expected, &entries);
 
@@ -255,8 +329,110 @@ mod tests {
             "+----------------------+------------+-------------------+--------------------+---------+----------+",
         ];
 
-        let entries = table.scan(3).unwrap().collect::<Result<Vec<_>>>().unwrap();
+        let entries = scan_entry_columns(&table, 3).await;
         assert_eq!(entries.len(), 1);
         assert_batches_eq!(&expected, &entries);
     }
+
+    #[tokio::test]
+    async fn test_query_log_stats() {
+        let now = Time::from_rfc3339("1996-12-19T16:39:57+00:00").unwrap();
+        let time_provider = Arc::new(iox_time::MockProvider::new(now));
+
+        let query_log = Arc::new(QueryLog::new(
+            10,
+            Arc::clone(&time_provider) as Arc<dyn TimeProvider>,
+        ));
+        let id = NamespaceId::new(1);
+        query_log.push(id, "sql", Box::new("select * from foo"), None);
+        let entry = query_log.push(id, "sql", Box::new("select * from bar"), None);
+
+        time_provider.inc(std::time::Duration::from_secs(4));
+        query_log.set_completed(
+            entry,
+            false,
+            QueryLogStats {
+                planning_duration: Some(Duration::from_secs(1)),
+                execution_duration: None,
+                rows_returned: 2,
+                bytes_returned: 100,
+                parquet_files: 3,
+                parquet_bytes: 3000,
+                ingester_partitions: 1,
+                ingester_latency: Some(Duration::from_millis(20)),
+                peak_memory_bytes: 1024,
+                error: Some("Resources exhausted".to_string()),
+            },
+        );
+
+        let table = QueriesTable::new(Arc::clone(&query_log), Some(id));
+        let entries = table
+            .scan(3)
+            .await
+            .unwrap()
+            .map(|batch| {
+                let batch = batch?;
+                let projection = (6..batch.num_columns()).collect::<Vec<_>>();
+                batch.project(&projection)
+            })
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+
+        let expected = vec![
+            "+----------+-------------------+--------------------+---------------+----------------+---------------+---------------+---------------------+------------------+-------------------+---------------------+",
+            "| trace_id | planning_duration | execution_duration | rows_returned | bytes_returned | parquet_files | parquet_bytes | ingester_partitions | ingester_latency | peak_memory_bytes | error               |",
+            "+----------+-------------------+--------------------+---------------+----------------+---------------+---------------+---------------------+------------------+-------------------+---------------------+",
+            "|          |                   |                    |               |                |               |               |                     |                  |                   |                     |",
+            "|          | 1s                | 3s                 | 2             | 100            | 3             | 3000          | 1                   | 20ms             | 1024              | Resources exhausted |",
+            "+----------+-------------------+--------------------+---------------+----------------+---------------+---------------+---------------------+------------------+-------------------+---------------------+",
+        ];
+        assert_batches_eq!(&expected, &entries);
+    }
+
+    #[tokio::test]
+    async fn test_query_id() {
+        let time_provider = Arc::new(iox_time::MockProvider::new(Time::MIN));
+        let query_log = Arc::new(QueryLog::new(10, time_provider));
+        let entries = [
+            query_log.push(NamespaceId::new(1), "sql", Box::new("select 1"), None),
+            query_log.push(NamespaceId::new(1), "sql", Box::new("select 2"), None),
+        ];
+
+        let table = QueriesTable::new(Arc::clone(&query_log), Some(NamespaceId::new(1)));
+        let batches = table
+            .scan(10)
+            .await
+            .unwrap()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        let query_ids = batches[0]
+            .column(batches[0].schema().index_of("query_id").unwrap())
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap()
+            .iter()
+            .map(|id| id.unwrap().to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(query_ids, entries.map(|e| e.id.to_string()),);
+    }
+
+    /// Scan `table`, only keeping the columns describing the query itself except for the random
+    /// query ID.
+    async fn scan_entry_columns(table: &QueriesTable, batch_size: usize) -> Vec<RecordBatch> {
+        let schema = table.schema();
+        let query_id = schema.index_of("query_id").unwrap();
+        let trace_id = schema.index_of("trace_id").unwrap();
+        let projection = (0..=trace_id)
+            .filter(|&i| i != query_id)
+            .collect::<Vec<_>>();
+
+        table
+            .scan(batch_size)
+            .await
+            .unwrap()
+            .map(|batch| batch?.project(&projection))
+            .collect::<Result<Vec<_>>>()
+            .unwrap()
+    }
 }
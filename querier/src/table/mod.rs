@@ -2,6 +2,7 @@ use self::{query_access::QuerierTableChunkPruner, state_reconciler::Reconciler};
 use crate::{
     ingester::{self, IngesterPartition},
     parquet::ChunkAdapter,
+    query_log::IngesterStats,
     IngesterConnection,
 };
 use data_types::{ColumnId, DeletePredicate, NamespaceId, TableId};
@@ -143,15 +144,18 @@ impl QuerierTable {
     }
 
     /// Query all chunks within this table.
+    ///
+    /// Requests made to the ingesters are recorded in `ingester_stats`, if given.
     pub async fn chunks(
         &self,
         predicate: &Predicate,
         span: Option<Span>,
         projection: Option<&Vec<usize>>,
+        ingester_stats: Option<&IngesterStats>,
     ) -> Result<Vec<Arc<dyn QueryChunk>>> {
         let mut span_recorder = SpanRecorder::new(span);
         match self
-            .chunks_inner(predicate, &span_recorder, projection)
+            .chunks_inner(predicate, &span_recorder, projection, ingester_stats)
             .await
         {
             Ok(chunks) => {
@@ -170,6 +174,7 @@ impl QuerierTable {
         predicate: &Predicate,
         span_recorder: &SpanRecorder,
         projection: Option<&Vec<usize>>,
+        ingester_stats: Option<&IngesterStats>,
     ) -> Result<Vec<Arc<dyn QueryChunk>>> {
         debug!(
             ?predicate,
@@ -222,6 +227,7 @@ impl QuerierTable {
                         &predicate,
                         span_recorder.child_span("ingester partitions"),
                         projection,
+                        ingester_stats,
                     )
                     .await;
                 ingester_ready.cancel();
@@ -309,10 +315,29 @@ impl QuerierTable {
             )
             .await;
 
+        // A tombstone that references a column unknown to the table cannot match any row (the
+        // column is NULL everywhere), so it is skipped rather than failing the query.
+        let tombstones = catalog_cache
+            .tombstone()
+            .get(self.id(), span_recorder.child_span("cache GET tombstone"))
+            .await;
+        let tombstones: Vec<_> = tombstones
+            .tombstones
+            .iter()
+            .filter(|t| {
+                t.delete_predicate
+                    .exprs
+                    .iter()
+                    .all(|expr| cached_table.schema.find_index_of(expr.column()).is_some())
+            })
+            .cloned()
+            .collect();
+
         let chunks = reconciler
             .reconcile(
                 partitions,
                 retention_delete_pred,
+                &tombstones,
                 parquet_files,
                 span_recorder.child_span("reconcile"),
             )
@@ -353,10 +378,13 @@ impl QuerierTable {
         predicate: &Predicate,
         span: Option<Span>,
         projection: Option<&Vec<usize>>,
+        ingester_stats: Option<&IngesterStats>,
     ) -> Result<Vec<IngesterPartition>> {
         let mut span_recorder = SpanRecorder::new(span);
 
         if let Some(ingester_connection) = &self.ingester_connection {
+            let time_provider = self.chunk_adapter.catalog_cache().time_provider();
+            let start = time_provider.now();
             match self
                 .ingester_partitions_inner(
                     Arc::clone(ingester_connection),
@@ -368,6 +396,12 @@ impl QuerierTable {
             {
                 Ok(partitions) => {
                     span_recorder.ok("Got partitions");
+                    if let (Some(ingester_stats), Some(latency)) = (
+                        ingester_stats,
+                        time_provider.now().checked_duration_since(start),
+                    ) {
+                        ingester_stats.record(partitions.len(), latency);
+                    }
                     Ok(partitions)
                 }
                 Err(e) => {
@@ -461,10 +495,12 @@ mod tests {
         table::test_util::{querier_table, IngesterPartitionBuilder},
     };
     use arrow_util::assert_batches_eq;
-    use data_types::{ChunkId, ColumnType};
+    use data_types::{ChunkId, ColumnType, Timestamp};
+    use datafusion::prelude::{col, lit};
     use iox_query::exec::IOxSessionContext;
     use iox_tests::{TestCatalog, TestParquetFileBuilder, TestTable};
-    use iox_time::TimeProvider;
+    use iox_time::{SystemProvider, Time, TimeProvider};
+    use metric::{Observation, RawReporter};
     use predicate::Predicate;
     use schema::{builder::SchemaBuilder, InfluxFieldType};
     use std::sync::Arc;
@@ -571,6 +607,57 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_prune_parquet_chunks_with_bloom_filters() {
+        test_helpers::maybe_start_logging();
+
+        let catalog = TestCatalog::new();
+        let ns = catalog.create_namespace_with_retention("ns", None).await;
+        let table = ns.create_table("cpu").await;
+
+        table.create_column("host", ColumnType::Tag).await;
+        table.create_column("time", ColumnType::Time).await;
+        table.create_column("load", ColumnType::F64).await;
+
+        let partition = table.create_partition("a").await;
+
+        let querier_table = TestQuerierTable::new(&catalog, &table).await;
+
+        let builder = TestParquetFileBuilder::default()
+            .with_line_protocol("cpu,host=a load=1 11\ncpu,host=b load=2 12")
+            .with_min_time(11)
+            .with_max_time(12);
+        let file_a_b = partition.create_parquet_file(builder).await;
+
+        let builder = TestParquetFileBuilder::default()
+            .with_line_protocol("cpu,host=c load=3 13")
+            .with_min_time(13)
+            .with_max_time(13);
+        let _file_c = partition.create_parquet_file(builder).await;
+
+        // The time range and column names of both files match the predicate, but only the first contains host=b
+        let pred = Predicate::new()
+            .with_range(0, 100)
+            .with_expr(col("host").eq(lit("b")));
+        let chunks = querier_table.chunks_with_predicate(&pred).await.unwrap();
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(
+            chunks[0].id(),
+            ChunkId::new_test(file_a_b.parquet_file.id.get() as u128),
+        );
+
+        let mut reporter = RawReporter::default();
+        catalog.metric_registry().report(&mut reporter);
+        assert_eq!(
+            reporter
+                .metric("query_pruner_chunks")
+                .unwrap()
+                .observation(&[("result", "pruned_bloom_filter")])
+                .unwrap(),
+            &Observation::U64Counter(1),
+        );
+    }
+
     #[tokio::test]
     async fn test_parquet_chunks() {
         maybe_start_logging();
@@ -691,6 +778,57 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_tombstones() {
+        maybe_start_logging();
+        let catalog = TestCatalog::new();
+
+        // Namespace with infinite retention policy, so that only tombstones produce delete
+        // predicates
+        let ns = catalog.create_namespace_with_retention("ns", None).await;
+        let table = ns.create_table("table").await;
+        let partition = table.create_partition("k").await;
+        table.create_column("time", ColumnType::Time).await;
+        table.create_column("foo", ColumnType::F64).await;
+
+        let builder = TestParquetFileBuilder::default()
+            .with_line_protocol("table foo=1 11")
+            .with_min_time(11)
+            .with_max_time(11)
+            .with_max_l0_created_at(Time::from_timestamp_nanos(1));
+        partition.create_parquet_file(builder).await;
+
+        // The catalog records the tombstone at the current wall clock time
+        catalog
+            .catalog()
+            .repositories()
+            .await
+            .tombstones()
+            .create(table.table.id, Timestamp::new(1), Timestamp::new(20), "")
+            .await
+            .unwrap();
+
+        // A file persisted after the delete was requested is not subject to it
+        let builder = TestParquetFileBuilder::default()
+            .with_line_protocol("table foo=2 12")
+            .with_min_time(12)
+            .with_max_time(12)
+            .with_max_l0_created_at(SystemProvider::new().minutes_into_future(60));
+        partition.create_parquet_file(builder).await;
+
+        let querier_table = TestQuerierTable::new(&catalog, &table).await;
+
+        let mut chunks = querier_table.chunks().await.unwrap();
+        assert_eq!(chunks.len(), 2);
+        chunks.sort_by_key(|c| c.delete_predicates().len());
+        assert!(chunks[0].delete_predicates().is_empty());
+        let delete_predicates = chunks[1].delete_predicates();
+        assert_eq!(delete_predicates.len(), 1);
+        assert_eq!(delete_predicates[0].range.start(), 1);
+        assert_eq!(delete_predicates[0].range.end(), 20);
+        assert!(delete_predicates[0].exprs.is_empty());
+    }
+
     #[tokio::test]
     async fn test_parquet_with_projection_pushdown_to_ingester() {
         maybe_start_logging();
@@ -907,7 +1045,9 @@ mod tests {
                 .next_response(Ok(self.ingester_partitions.clone()));
 
             let span = Some(Span::root("root", Arc::clone(&self.traces) as _));
-            self.querier_table.chunks(pred, span, projection).await
+            self.querier_table
+                .chunks(pred, span, projection, None)
+                .await
         }
     }
 }
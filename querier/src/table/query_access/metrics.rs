@@ -62,6 +62,10 @@ pub struct PruneMetrics {
     /// At the moment we can prune chunks early only based on "time".
     pub pruned_early: PruneMetricsGroup,
 
+    /// Chunks that have been pruned because the bloom filters of their parquet file show that no row group contains the
+    /// tag values required by the predicate.
+    pub pruned_bloom_filter: PruneMetricsGroup,
+
     /// Chunks that have been pruned after they have been created. At this stage we likely had better/more statistics available.
     pub pruned_late: PruneMetricsGroup,
 
@@ -85,6 +89,8 @@ pub struct PruneMetrics {
 impl PruneMetrics {
     pub fn new(metric_registry: &metric::Registry) -> Self {
         let pruned_early = PruneMetricsGroup::new(metric_registry, &[("result", "pruned_early")]);
+        let pruned_bloom_filter =
+            PruneMetricsGroup::new(metric_registry, &[("result", "pruned_bloom_filter")]);
         let pruned_late = PruneMetricsGroup::new(metric_registry, &[("result", "pruned_late")]);
         let not_pruned = PruneMetricsGroup::new(metric_registry, &[("result", "not_pruned")]);
         let could_not_prune_no_expression = PruneMetricsGroup::new(
@@ -117,6 +123,7 @@ impl PruneMetrics {
 
         Self {
             pruned_early,
+            pruned_bloom_filter,
             pruned_late,
             not_pruned,
             could_not_prune_no_expression,
@@ -19,7 +19,7 @@ use iox_query::{
 use predicate::Predicate;
 use schema::Schema;
 
-use crate::{ingester::IngesterChunk, parquet::QuerierParquetChunk};
+use crate::{ingester::IngesterChunk, parquet::QuerierParquetChunk, query_log::IngesterStats};
 
 use self::metrics::PruneMetrics;
 
@@ -59,11 +59,13 @@ impl TableProvider for QuerierTable {
             .cloned()
             .fold(Predicate::default(), Predicate::with_expr);
 
+        let ingester_stats = ctx.config().get_extension::<IngesterStats>();
         let chunks = self
             .chunks(
                 &pruning_predicate,
                 ctx.child_span("QuerierTable chunks"),
                 projection,
+                ingester_stats.as_deref(),
             )
             .await?;
 
@@ -155,6 +157,13 @@ impl MetricPruningObserver {
     pub(crate) fn was_pruned_early(&self, row_count: u64, size_estimate: u64) {
         self.metrics.pruned_early.inc(1, row_count, size_estimate);
     }
+
+    /// Called when pruning a chunk using the bloom filters of its parquet file
+    pub(crate) fn was_pruned_bloom_filter(&self, row_count: u64, size_estimate: u64) {
+        self.metrics
+            .pruned_bloom_filter
+            .inc(1, row_count, size_estimate);
+    }
 }
 
 impl PruningObserver for MetricPruningObserver {
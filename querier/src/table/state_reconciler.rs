@@ -9,7 +9,7 @@ use snafu::Snafu;
 use std::sync::Arc;
 use trace::span::{Span, SpanRecorder};
 
-use crate::{parquet::QuerierParquetChunk, IngesterPartition};
+use crate::{cache::tombstone::CachedTombstone, parquet::QuerierParquetChunk, IngesterPartition};
 
 #[derive(Snafu, Debug)]
 #[allow(missing_copy_implementations)]
@@ -34,11 +34,15 @@ impl Reconciler {
     }
 
     /// Reconciles ingester state (ingester_partitions) and catalog state (parquet_files),
-    /// producing a list of chunks to query
+    /// producing a list of chunks to query.
+    ///
+    /// `tombstones` are only attached to the parquet files they apply to. The ingesters apply
+    /// deletes to the data they buffer, so ingester chunks are not subject to them.
     pub(crate) async fn reconcile(
         &self,
         ingester_partitions: Vec<IngesterPartition>,
         retention_delete_pred: Option<Arc<DeletePredicate>>,
+        tombstones: &[CachedTombstone],
         parquet_files: Vec<QuerierParquetChunk>,
         span: Option<Span>,
     ) -> Result<Vec<Arc<dyn QueryChunk>>, ReconcileError> {
@@ -47,6 +51,7 @@ impl Reconciler {
             .build_chunks_from_parquet(
                 &ingester_partitions,
                 retention_delete_pred.clone(),
+                tombstones,
                 parquet_files,
                 span_recorder.child_span("build_chunks_from_parquet"),
             )
@@ -61,6 +66,7 @@ impl Reconciler {
         &self,
         ingester_partitions: &[IngesterPartition],
         retention_delete_pred: Option<Arc<DeletePredicate>>,
+        tombstones: &[CachedTombstone],
         parquet_files: Vec<QuerierParquetChunk>,
         _span: Option<Span>,
     ) -> Result<Vec<Arc<dyn QueryChunk>>, ReconcileError> {
@@ -76,13 +82,18 @@ impl Reconciler {
         let mut chunks: Vec<Arc<dyn QueryChunk>> =
             Vec::with_capacity(parquet_files.len() + ingester_partitions.len());
 
-        let retention_expr_len = usize::from(retention_delete_pred.is_some());
         for chunk in parquet_files.into_iter() {
-            let mut delete_predicates = Vec::with_capacity(retention_expr_len);
-
-            if let Some(retention_delete_pred) = retention_delete_pred.clone() {
-                delete_predicates.push(retention_delete_pred);
-            }
+            let max_l0_created_at = chunk.max_l0_created_at();
+            let delete_predicates = retention_delete_pred
+                .iter()
+                .cloned()
+                .chain(
+                    tombstones
+                        .iter()
+                        .filter(|t| t.applies_to(max_l0_created_at))
+                        .map(|t| Arc::clone(&t.delete_predicate)),
+                )
+                .collect();
 
             let chunk = chunk.with_delete_predicates(delete_predicates);
 
@@ -0,0 +1,595 @@
+//! User-defined aggregate functions that implement the InfluxQL
+//! [`holt_winters`] and `holt_winters_with_fit` functions.
+//!
+//! The functions fit a [Holt-Winters] model, with an optional seasonal
+//! component, to a series of values sampled at a regular interval, and forecast
+//! the values of the series for a number of intervals. Unlike most aggregate
+//! functions, the result is a list of points, as a `time` and `value` struct:
+//!
+//! ```sql
+//! SELECT
+//!   host,
+//!   holt_winters(usage, time, 10, 4, 3600000000000)
+//! FROM (
+//!   SELECT host, date_bin(INTERVAL '1 hour', time) AS time, avg(usage) AS usage
+//!   FROM cpu
+//!   GROUP BY 1, 2
+//! )
+//! GROUP BY host
+//! ```
+//!
+//! The arguments are the value, the time, the number of values to forecast, the
+//! number of values of a season, which disables the seasonal component when less
+//! than 2, and the sampling interval, in nanoseconds. The `holt_winters` function
+//! returns the forecast values, following the last value of the series, whereas
+//! `holt_winters_with_fit` additionally includes the values of the fitted model
+//! for the time range of the series.
+//!
+//! The model is ported from the implementation of InfluxDB 1.x, to produce
+//! identical results.
+//!
+//! [`holt_winters`]: https://docs.influxdata.com/influxdb/v1.8/query_language/functions/#holt_winters
+//! [Holt-Winters]: https://www.otexts.org/fpp/7/5
+use std::sync::Arc;
+
+use arrow::{
+    array::{Array, ArrayRef},
+    compute::cast,
+    datatypes::{DataType, Field, Fields},
+};
+use datafusion::{
+    common::cast::{
+        as_float64_array, as_int64_array, as_list_array, as_timestamp_nanosecond_array,
+    },
+    error::{DataFusionError, Result as DataFusionResult},
+    logical_expr::{
+        AccumulatorFunctionImplementation, ReturnTypeFunction, Signature, StateTypeFunction,
+        TypeSignature, Volatility,
+    },
+    physical_plan::{udaf::AggregateUDF, Accumulator},
+    scalar::ScalarValue,
+};
+use once_cell::sync::Lazy;
+use schema::TIME_DATA_TYPE;
+
+/// Internal implementation of the Nelder-Mead optimizer.
+mod neldermead;
+
+/// The name of the holt_winters UDAF given to DataFusion.
+pub const HOLT_WINTERS_UDAF_NAME: &str = "holt_winters";
+
+/// The name of the holt_winters_with_fit UDAF given to DataFusion.
+pub const HOLT_WINTERS_WITH_FIT_UDAF_NAME: &str = "holt_winters_with_fit";
+
+/// Implementation of holt_winters.
+pub(crate) static HOLT_WINTERS: Lazy<Arc<AggregateUDF>> =
+    Lazy::new(|| make_udaf(HOLT_WINTERS_UDAF_NAME, false));
+
+/// Implementation of holt_winters_with_fit.
+pub(crate) static HOLT_WINTERS_WITH_FIT: Lazy<Arc<AggregateUDF>> =
+    Lazy::new(|| make_udaf(HOLT_WINTERS_WITH_FIT_UDAF_NAME, true));
+
+/// The fields of the struct of each point returned by the functions.
+fn point_fields() -> Fields {
+    Fields::from(vec![
+        Field::new("time", TIME_DATA_TYPE(), true),
+        Field::new("value", DataType::Float64, true),
+    ])
+}
+
+/// The return type of the functions, which is a list of points.
+fn list_type() -> DataType {
+    DataType::List(Arc::new(Field::new(
+        "item",
+        DataType::Struct(point_fields()),
+        true,
+    )))
+}
+
+fn make_udaf(name: &'static str, include_fit: bool) -> Arc<AggregateUDF> {
+    // value, time, h, m, interval
+    let signature = Signature::one_of(
+        [DataType::Float64, DataType::Int64]
+            .into_iter()
+            .map(|dt| {
+                TypeSignature::Exact(vec![
+                    dt,
+                    TIME_DATA_TYPE(),
+                    DataType::Int64,
+                    DataType::Int64,
+                    DataType::Int64,
+                ])
+            })
+            .collect(),
+        Volatility::Immutable,
+    );
+
+    let return_type: ReturnTypeFunction = Arc::new(|_| Ok(Arc::new(list_type())));
+    let accumulator: AccumulatorFunctionImplementation =
+        Arc::new(move |_| Ok(Box::new(HoltWintersAccumulator::new(include_fit))));
+    // The accumulated points, and the arguments of the model, which are not
+    // otherwise available when merging the intermediate state.
+    let state_type: StateTypeFunction = Arc::new(|_| {
+        Ok(Arc::new(vec![
+            DataType::List(Arc::new(Field::new("item", TIME_DATA_TYPE(), true))),
+            DataType::List(Arc::new(Field::new("item", DataType::Float64, true))),
+            DataType::Int64,
+            DataType::Int64,
+            DataType::Int64,
+        ]))
+    });
+
+    Arc::new(AggregateUDF::new(
+        name,
+        &signature,
+        &return_type,
+        &accumulator,
+        &state_type,
+    ))
+}
+
+/// Accumulates the points of a series, and evaluates the Holt-Winters model.
+#[derive(Debug)]
+struct HoltWintersAccumulator {
+    include_fit: bool,
+    /// The `h`, `m` and `interval` arguments, which are initialized from the
+    /// first batch or state.
+    args: Option<(i64, i64, i64)>,
+    /// The non-null points of the series.
+    points: Vec<(i64, f64)>,
+}
+
+impl HoltWintersAccumulator {
+    fn new(include_fit: bool) -> Self {
+        Self {
+            include_fit,
+            args: None,
+            points: vec![],
+        }
+    }
+}
+
+impl Accumulator for HoltWintersAccumulator {
+    fn state(&self) -> DataFusionResult<Vec<ScalarValue>> {
+        let (h, m, interval) = match self.args {
+            Some((h, m, interval)) => (Some(h), Some(m), Some(interval)),
+            None => (None, None, None),
+        };
+
+        Ok(vec![
+            ScalarValue::new_list(
+                Some(
+                    self.points
+                        .iter()
+                        .map(|(t, _)| ScalarValue::TimestampNanosecond(Some(*t), None))
+                        .collect(),
+                ),
+                TIME_DATA_TYPE(),
+            ),
+            ScalarValue::new_list(
+                Some(
+                    self.points
+                        .iter()
+                        .map(|(_, v)| ScalarValue::Float64(Some(*v)))
+                        .collect(),
+                ),
+                DataType::Float64,
+            ),
+            ScalarValue::Int64(h),
+            ScalarValue::Int64(m),
+            ScalarValue::Int64(interval),
+        ])
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> DataFusionResult<()> {
+        if values[0].is_empty() {
+            return Ok(());
+        }
+
+        if self.args.is_none() {
+            self.args = Some((
+                as_int64_array(&values[2])?.value(0),
+                as_int64_array(&values[3])?.value(0),
+                as_int64_array(&values[4])?.value(0),
+            ));
+        }
+
+        let arr = cast(&values[0], &DataType::Float64)?;
+        let arr = as_float64_array(&arr)?;
+        let times = as_timestamp_nanosecond_array(&values[1])?;
+        self.points.extend(
+            arr.iter()
+                .zip(times.iter())
+                .filter_map(|(v, t)| Some((t?, v?))),
+        );
+
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> DataFusionResult<()> {
+        let times = as_list_array(&states[0])?;
+        let values = as_list_array(&states[1])?;
+        let h = as_int64_array(&states[2])?;
+        let m = as_int64_array(&states[3])?;
+        let interval = as_int64_array(&states[4])?;
+
+        for i in 0..times.len() {
+            if self.args.is_none() && !h.is_null(i) {
+                self.args = Some((h.value(i), m.value(i), interval.value(i)));
+            }
+
+            if times.is_null(i) {
+                continue;
+            }
+            let t = times.value(i);
+            let t = as_timestamp_nanosecond_array(&t)?;
+            let v = values.value(i);
+            let v = as_float64_array(&v)?;
+            self.points
+                .extend(t.iter().zip(v.iter()).filter_map(|(t, v)| Some((t?, v?))));
+        }
+
+        Ok(())
+    }
+
+    fn evaluate(&self) -> DataFusionResult<ScalarValue> {
+        let points = match self.args {
+            Some((h, m, interval)) if interval > 0 => {
+                let h = usize::try_from(h).map_err(|_| {
+                    DataFusionError::Plan(format!(
+                        "holt_winters N argument must be greater than 0, got {h}"
+                    ))
+                })?;
+                let m = usize::try_from(m).map_err(|_| {
+                    DataFusionError::Plan(format!(
+                        "holt_winters S argument cannot be negative, got {m}"
+                    ))
+                })?;
+
+                let mut points = self.points.clone();
+                points.sort_by_key(|(t, _)| *t);
+                HoltWinters::new(h, m, interval, self.include_fit).forecast(&points)
+            }
+            _ => vec![],
+        };
+
+        let fields = point_fields();
+        Ok(ScalarValue::new_list(
+            Some(
+                points
+                    .into_iter()
+                    .map(|(t, v)| {
+                        ScalarValue::Struct(
+                            Some(vec![
+                                ScalarValue::TimestampNanosecond(Some(t), None),
+                                ScalarValue::Float64(Some(v)),
+                            ]),
+                            fields.clone(),
+                        )
+                    })
+                    .collect(),
+            ),
+            DataType::Struct(fields),
+        ))
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self) + self.points.capacity() * std::mem::size_of::<(i64, f64)>()
+    }
+}
+
+/// The epsilon of the minimization of the sum of squared errors of the model.
+const EPSILON: f64 = 1.0e-4;
+
+/// A Holt-Winters model of a series of values sampled at a regular interval.
+#[derive(Debug)]
+struct HoltWinters {
+    /// The number of values to forecast.
+    h: usize,
+    /// The number of values of a season.
+    m: usize,
+    seasonal: bool,
+    include_fit: bool,
+    interval: i64,
+    half_interval: i64,
+    /// The values of the series, for each interval, which are `NaN` for
+    /// intervals without a value.
+    y: Vec<f64>,
+}
+
+impl HoltWinters {
+    fn new(h: usize, m: usize, interval: i64, include_fit: bool) -> Self {
+        Self {
+            h,
+            m,
+            seasonal: m >= 2,
+            include_fit,
+            interval,
+            half_interval: interval / 2,
+            y: vec![],
+        }
+    }
+
+    fn round_time(&self, t: i64) -> i64 {
+        let remainder = t % self.interval;
+        if remainder > self.half_interval {
+            // round up
+            (t / self.interval + 1) * self.interval
+        } else {
+            // round down
+            (t / self.interval) * self.interval
+        }
+    }
+
+    /// Fit the model to the `points`, which are sorted by time, and return the
+    /// forecast points.
+    fn forecast(mut self, points: &[(i64, f64)]) -> Vec<(i64, f64)> {
+        let (Some(first), Some(last)) = (points.first(), points.last()) else {
+            return vec![];
+        };
+
+        let start = self.round_time(first.0);
+        let stop = self.round_time(last.0);
+        if (stop - start) / self.interval <= 0 {
+            return vec![];
+        }
+
+        // Fill in y with the values, and NaNs for the missing values.
+        self.y.push(first.1);
+        let mut t = start;
+        for (time, value) in &points[1..] {
+            let rt = self.round_time(*time);
+            if rt <= t {
+                // Drop values that occur for the same time bucket
+                continue;
+            }
+            t += self.interval;
+            // Add any missing values before the next point
+            while rt != t {
+                self.y.push(f64::NAN);
+                t += self.interval;
+            }
+            self.y.push(*value);
+        }
+
+        let m = self.m;
+        let y_at = |i: usize| self.y.get(i).copied().unwrap_or(f64::NAN);
+
+        // Starting guesses, which skip the missing values.
+        let l0 = if m == 0 {
+            self.y[0]
+        } else {
+            (0..m)
+                .map(y_at)
+                .filter(|v| !v.is_nan())
+                .map(|v| (1.0 / m as f64) * v)
+                .sum()
+        };
+
+        let b0 = if m == 0 {
+            self.y[1] - self.y[0]
+        } else {
+            (0..m)
+                .take_while(|i| m + i < self.y.len())
+                .filter(|i| !self.y[*i].is_nan() && !self.y[m + i].is_nan())
+                .map(|i| 1.0 / (m * m) as f64 * (self.y[m + i] - self.y[i]))
+                .sum()
+        };
+
+        // alpha, beta, gamma, phi, l0, b0, followed by the seasonal components
+        let mut parameters = vec![0.0; 6];
+        parameters[4] = l0;
+        parameters[5] = b0;
+        parameters.extend((0..m).map(|i| y_at(i) / l0));
+
+        // Determine best fit for the various parameters
+        let (_, mut best) = neldermead::optimize(|p| self.sse(p), &parameters, EPSILON, 1.0);
+
+        let forecast = self.forecast_values(self.h, &mut best);
+        if self.include_fit {
+            forecast
+                .into_iter()
+                .enumerate()
+                .filter(|(_, v)| !v.is_nan())
+                .map(|(i, v)| (first.0 + self.interval * i as i64, v))
+                .collect()
+        } else {
+            forecast
+                .into_iter()
+                .skip(self.y.len())
+                .enumerate()
+                .filter(|(_, v)| !v.is_nan())
+                .map(|(i, v)| (last.0 + self.interval * (i as i64 + 1), v))
+                .collect()
+        }
+    }
+
+    /// Using the recursive relations, compute the next values.
+    #[allow(clippy::too_many_arguments)]
+    fn next(
+        alpha: f64,
+        beta: f64,
+        gamma: f64,
+        phi: f64,
+        phi_h: f64,
+        y_t: f64,
+        l_tp: f64,
+        b_tp: f64,
+        s_tm: f64,
+        s_tmh: f64,
+    ) -> (f64, f64, f64, f64) {
+        let l_t = alpha * (y_t / s_tm) + (1.0 - alpha) * (l_tp + phi * b_tp);
+        let b_t = beta * (l_t - l_tp) + (1.0 - beta) * phi * b_tp;
+        let s_t = gamma * (y_t / (l_tp + phi * b_tp)) + (1.0 - gamma) * s_tm;
+        let y_th = (l_t + phi_h * b_t) * s_tmh;
+        (y_th, l_t, b_t, s_t)
+    }
+
+    /// Forecast the values `h` intervals beyond the series, using `params`.
+    ///
+    /// The parameters are constrained, and the seasonal components are updated
+    /// in place.
+    fn forecast_values(&self, h: usize, params: &mut [f64]) -> Vec<f64> {
+        // Constrain alpha, beta, gamma, phi in the range [0, 1]
+        for p in &mut params[..4] {
+            *p = p.clamp(0.0, 1.0);
+        }
+
+        let (alpha, beta, gamma, phi) = (params[0], params[1], params[2], params[3]);
+        let mut phi_h = phi;
+        let mut y_t = self.y[0];
+        let mut l_t = params[4];
+        let mut b_t = params[5];
+
+        // The seasonal components are a ring buffer of past s_t values, where
+        // so is the season index offset.
+        let seasonals = &mut params[6..];
+        let m = seasonals.len();
+        let mut so = m.saturating_sub(1);
+
+        let len = self.y.len() + h;
+        let mut forecast = Vec::with_capacity(len);
+        forecast.push(y_t);
+        for t in 1..len {
+            let (s_tm, s_tmh) = if self.seasonal {
+                let hm = t % m;
+                (
+                    seasonals[(t + so - m) % m],
+                    seasonals[(t + so + hm - m) % m],
+                )
+            } else {
+                (1.0, 1.0)
+            };
+
+            let (y, l, b, s_t) =
+                Self::next(alpha, beta, gamma, phi, phi_h, y_t, l_t, b_t, s_tm, s_tmh);
+            (y_t, l_t, b_t) = (y, l, b);
+            phi_h += phi.powf(t as f64);
+
+            if self.seasonal {
+                seasonals[(t + so) % m] = s_t;
+                so += 1;
+            }
+
+            forecast.push(y_t);
+        }
+
+        forecast
+    }
+
+    /// Compute the sum of squared errors of the model for the given parameters.
+    fn sse(&self, params: &mut [f64]) -> f64 {
+        let mut sse = 0.0;
+        for (y, f) in self.y.iter().zip(self.forecast_values(0, params)) {
+            // Skip missing values since we cannot use them to compute an error.
+            if !y.is_nan() {
+                if f.is_nan() {
+                    // Penalize forecast NaNs
+                    return f64::MAX;
+                }
+                let diff = f - y;
+                sse += diff * diff;
+            }
+        }
+        sse
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use arrow::array::{Float64Array, Int64Array, TimestampNanosecondArray};
+
+    fn assert_points_eq(got: &[(i64, f64)], expected: &[(i64, f64)]) {
+        assert_eq!(got.len(), expected.len(), "{got:?}");
+        for ((gt, gv), (et, ev)) in got.iter().zip(expected) {
+            assert_eq!(gt, et, "{got:?}");
+            assert!((gv - ev).abs() < 1e-4, "{got:?}");
+        }
+    }
+
+    #[test]
+    fn test_forecast() {
+        // A linear series continues the trend.
+        let points = (0..10)
+            .map(|i| (i * 10, (i + 1) as f64))
+            .collect::<Vec<_>>();
+        let got = HoltWinters::new(3, 0, 10, false).forecast(&points);
+        assert_points_eq(
+            &got,
+            &[(100, 11.037665), (110, 12.089439), (120, 13.160827)],
+        );
+
+        // The fitted values are included with the forecast.
+        let got = HoltWinters::new(2, 0, 10, true).forecast(&points);
+        assert_eq!(got.len(), 12);
+        assert_points_eq(&got[..2], &[(0, 1.0), (10, 1.973717)]);
+        assert_points_eq(&got[10..], &[(100, 11.037665), (110, 12.089439)]);
+
+        // A seasonal series.
+        let season = [1.0, 3.0, 2.0, 4.0];
+        let points = (0..12)
+            .map(|i| (i * 10, season[i as usize % 4] + i as f64 * 0.5))
+            .collect::<Vec<_>>();
+        let got = HoltWinters::new(4, 4, 10, false).forecast(&points);
+        assert_points_eq(
+            &got,
+            &[
+                (120, 9.524346),
+                (130, 9.079793),
+                (140, 11.267351),
+                (150, 13.392152),
+            ],
+        );
+
+        // A single interval cannot be modelled.
+        assert!(HoltWinters::new(3, 0, 10, false)
+            .forecast(&[(0, 1.0), (4, 2.0)])
+            .is_empty());
+    }
+
+    #[test]
+    fn test_accumulator() {
+        let times = TimestampNanosecondArray::from(vec![20, 0, 10, 30, 40]);
+        let values = Float64Array::from(vec![Some(3.0), Some(1.0), Some(2.0), Some(4.0), None]);
+        let args: Vec<ArrayRef> = vec![
+            Arc::new(values),
+            Arc::new(times),
+            Arc::new(Int64Array::from(vec![1; 5])),
+            Arc::new(Int64Array::from(vec![0; 5])),
+            Arc::new(Int64Array::from(vec![10; 5])),
+        ];
+
+        // Merging the state of partial aggregates produces the same result.
+        let mut partial1 = HoltWintersAccumulator::new(false);
+        partial1
+            .update_batch(&args.iter().map(|a| a.slice(0, 2)).collect::<Vec<_>>())
+            .unwrap();
+        let mut partial2 = HoltWintersAccumulator::new(false);
+        partial2
+            .update_batch(&args.iter().map(|a| a.slice(2, 3)).collect::<Vec<_>>())
+            .unwrap();
+
+        let mut single = HoltWintersAccumulator::new(false);
+        single.update_batch(&args).unwrap();
+
+        let mut merged = HoltWintersAccumulator::new(false);
+        for acc in [partial1, partial2] {
+            let state = acc
+                .state()
+                .unwrap()
+                .into_iter()
+                .map(|v| v.to_array())
+                .collect::<Vec<_>>();
+            merged.merge_batch(&state).unwrap();
+        }
+
+        let got = merged.evaluate().unwrap();
+        assert_eq!(got, single.evaluate().unwrap());
+        assert_eq!(got.get_datatype(), list_type());
+        let ScalarValue::List(Some(points), _) = got else {
+            panic!("unexpected result {got:?}")
+        };
+        assert_eq!(points.len(), 1);
+    }
+}
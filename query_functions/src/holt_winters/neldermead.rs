@@ -0,0 +1,189 @@
+//! An implementation of the [Nelder-Mead] method, which is used to find the
+//! parameters of the Holt-Winters model that minimize the sum of squared errors.
+//!
+//! This is a port of the optimizer of InfluxDB 1.x, including its quirks, such as
+//! permitting the objective function to modify the vertices of the simplex, to
+//! ensure the models produce identical results.
+//!
+//! [Nelder-Mead]: https://en.wikipedia.org/wiki/Nelder%E2%80%93Mead_method
+
+/// The maximum number of iterations of the minimization process.
+const MAX_ITERATIONS: usize = 1000;
+/// The reflection coefficient.
+const ALPHA: f64 = 1.0;
+/// The contraction coefficient.
+const BETA: f64 = 0.5;
+/// The expansion coefficient.
+const GAMMA: f64 = 2.0;
+
+/// Returns the minimum value of `objective` and the parameters that produce it,
+/// starting from the parameters `start`.
+///
+/// The minimization terminates when the standard deviation of the values of the
+/// vertices of the simplex is less than `epsilon`. The size of the initial
+/// simplex is determined by `scale`.
+pub(super) fn optimize<F>(
+    mut objective: F,
+    start: &[f64],
+    epsilon: f64,
+    scale: f64,
+) -> (f64, Vec<f64>)
+where
+    F: FnMut(&mut [f64]) -> f64,
+{
+    let n = start.len();
+    let nf = n as f64;
+
+    // The vertices of the simplex, the first of which is the starting point.
+    let pn = scale * ((nf + 1.0).sqrt() - 1.0 + nf) / (nf * 2.0_f64.sqrt());
+    let qn = scale * ((nf + 1.0).sqrt() - 1.0) / (nf * 2.0_f64.sqrt());
+    let mut v = vec![start.to_vec()];
+    for i in 1..=n {
+        v.push(
+            start
+                .iter()
+                .enumerate()
+                .map(|(j, s)| if i - 1 == j { pn + s } else { qn + s })
+                .collect(),
+        );
+    }
+
+    // The value of the objective function at each vertex.
+    let mut f = v.iter_mut().map(|v| objective(v)).collect::<Vec<_>>();
+
+    let mut vr = vec![0.0; n];
+    let mut ve = vec![0.0; n];
+    let mut vc = vec![0.0; n];
+    let mut vm = vec![0.0; n];
+
+    for _ in 0..MAX_ITERATIONS {
+        // Find the indexes of the largest and smallest values.
+        let mut vg = 0;
+        let mut vs = 0;
+        for (i, fi) in f.iter().enumerate() {
+            if *fi > f[vg] {
+                vg = i;
+            }
+            if *fi < f[vs] {
+                vs = i;
+            }
+        }
+        // Find the index of the second largest value.
+        let mut vh = vs;
+        for (i, fi) in f.iter().enumerate() {
+            if *fi > f[vh] && *fi < f[vg] {
+                vh = i;
+            }
+        }
+
+        // Calculate the centroid, excluding the largest value.
+        for (i, vm) in vm.iter_mut().enumerate() {
+            let cent: f64 = (0..=n).filter(|m| *m != vg).map(|m| v[m][i]).sum();
+            *vm = cent / nf;
+        }
+
+        // Reflect the vertex with the largest value.
+        for ((vr, vm), x) in vr.iter_mut().zip(&vm).zip(&v[vg]) {
+            *vr = vm + ALPHA * (vm - x);
+        }
+        let fr = objective(&mut vr);
+
+        if fr < f[vh] && fr >= f[vs] {
+            v[vg].copy_from_slice(&vr);
+            f[vg] = fr;
+        }
+
+        // Investigate a step further in this direction.
+        if fr < f[vs] {
+            for ((ve, vm), vr) in ve.iter_mut().zip(&vm).zip(&vr) {
+                *ve = vm + GAMMA * (vr - vm);
+            }
+            let fe = objective(&mut ve);
+
+            if fe < fr {
+                v[vg].copy_from_slice(&ve);
+                f[vg] = fe;
+            } else {
+                v[vg].copy_from_slice(&vr);
+                f[vg] = fr;
+            }
+        }
+
+        // Check to see if a contraction is necessary.
+        if fr >= f[vh] {
+            if fr < f[vg] && fr >= f[vh] {
+                // Perform an outside contraction.
+                for ((vc, vm), vr) in vc.iter_mut().zip(&vm).zip(&vr) {
+                    *vc = vm + BETA * (vr - vm);
+                }
+            } else {
+                // Perform an inside contraction.
+                for ((vc, vm), x) in vc.iter_mut().zip(&vm).zip(&v[vg]) {
+                    *vc = vm - BETA * (vm - x);
+                }
+            }
+            let fc = objective(&mut vc);
+
+            if fc < f[vg] {
+                v[vg].copy_from_slice(&vc);
+                f[vg] = fc;
+            } else {
+                // The contraction was not successful, so halve the distance from
+                // the vertex with the smallest value to all other vertices.
+                let best = v[vs].clone();
+                for (row, vertex) in v.iter_mut().enumerate() {
+                    if row != vs {
+                        for (x, b) in vertex.iter_mut().zip(&best) {
+                            *x = b + (*x - b) / 2.0;
+                        }
+                    }
+                }
+                f[vg] = objective(&mut v[vg]);
+                f[vh] = objective(&mut v[vh]);
+            }
+        }
+
+        // Test for convergence.
+        let favg = f.iter().sum::<f64>() / (nf + 1.0);
+        let s = f
+            .iter()
+            .map(|f| (f - favg).powf(2.0) / nf)
+            .sum::<f64>()
+            .sqrt();
+        if s < epsilon {
+            break;
+        }
+    }
+
+    // Find the index of the smallest value.
+    let mut vs = 0;
+    for (i, fi) in f.iter().enumerate() {
+        if *fi < f[vs] {
+            vs = i;
+        }
+    }
+
+    let parameters = v[vs].clone();
+    let min = objective(&mut v[vs]);
+
+    (min, parameters)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_optimize() {
+        // The minimum of the Rosenbrock function is 0, at (1, 1).
+        let (min, parameters) = optimize(
+            |x| (1.0 - x[0]).powi(2) + 100.0 * (x[1] - x[0].powi(2)).powi(2),
+            &[-1.2, 1.0],
+            1e-10,
+            1.0,
+        );
+        assert!(min < 1e-6, "{min}");
+        assert!((parameters[0] - 1.0).abs() < 1e-2, "{parameters:?}");
+        assert!((parameters[1] - 1.0).abs() < 1e-2, "{parameters:?}");
+    }
+}
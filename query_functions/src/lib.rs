@@ -34,6 +34,12 @@ mod window;
 
 pub mod gapfill;
 
+/// InfluxQL Holt-Winters forecasting functions
+pub mod holt_winters;
+
+/// InfluxQL technical analysis functions
+pub mod technical_analysis;
+
 /// Function registry
 mod registry;
 
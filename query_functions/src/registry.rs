@@ -7,7 +7,7 @@ use datafusion::{
 };
 use once_cell::sync::Lazy;
 
-use crate::{gapfill, regex, window};
+use crate::{gapfill, holt_winters, regex, technical_analysis as ta, window};
 
 static REGISTRY: Lazy<IOxFunctionRegistry> = Lazy::new(IOxFunctionRegistry::new);
 
@@ -51,9 +51,31 @@ impl FunctionRegistry for IOxFunctionRegistry {
     }
 
     fn udaf(&self, name: &str) -> DataFusionResult<Arc<AggregateUDF>> {
-        Err(DataFusionError::Plan(format!(
-            "IOx FunctionRegistry does not contain user defined aggregate function '{name}'"
-        )))
+        match name {
+            holt_winters::HOLT_WINTERS_UDAF_NAME => Ok(holt_winters::HOLT_WINTERS.clone()),
+            holt_winters::HOLT_WINTERS_WITH_FIT_UDAF_NAME => {
+                Ok(holt_winters::HOLT_WINTERS_WITH_FIT.clone())
+            }
+            ta::EXPONENTIAL_MOVING_AVERAGE_UDAF_NAME => Ok(ta::EXPONENTIAL_MOVING_AVERAGE.clone()),
+            ta::DOUBLE_EXPONENTIAL_MOVING_AVERAGE_UDAF_NAME => {
+                Ok(ta::DOUBLE_EXPONENTIAL_MOVING_AVERAGE.clone())
+            }
+            ta::TRIPLE_EXPONENTIAL_MOVING_AVERAGE_UDAF_NAME => {
+                Ok(ta::TRIPLE_EXPONENTIAL_MOVING_AVERAGE.clone())
+            }
+            ta::RELATIVE_STRENGTH_INDEX_UDAF_NAME => Ok(ta::RELATIVE_STRENGTH_INDEX.clone()),
+            ta::TRIPLE_EXPONENTIAL_DERIVATIVE_UDAF_NAME => {
+                Ok(ta::TRIPLE_EXPONENTIAL_DERIVATIVE.clone())
+            }
+            ta::KAUFMANS_EFFICIENCY_RATIO_UDAF_NAME => Ok(ta::KAUFMANS_EFFICIENCY_RATIO.clone()),
+            ta::KAUFMANS_ADAPTIVE_MOVING_AVERAGE_UDAF_NAME => {
+                Ok(ta::KAUFMANS_ADAPTIVE_MOVING_AVERAGE.clone())
+            }
+            ta::CHANDE_MOMENTUM_OSCILLATOR_UDAF_NAME => Ok(ta::CHANDE_MOMENTUM_OSCILLATOR.clone()),
+            _ => Err(DataFusionError::Plan(format!(
+                "IOx FunctionRegistry does not contain user defined aggregate function '{name}'"
+            ))),
+        }
     }
 }
 
@@ -93,6 +93,16 @@
 //! with the same minimum / maximum value, the value with the smallest
 //! timestamp is chosen.
 //!
+//! In addition, IOx supports the following InfluxQL selectors, which
+//! evaluate all the rows of the group:
+//!
+//! 1. `selector_percentile`: `time` and `value` of the row at the specified percentile of the values
+//! 2. `selector_top`: list of the `time` and `value` of the `N` rows with the largest values
+//! 3. `selector_bottom`: list of the `time` and `value` of the `N` rows with the smallest values
+//! 4. `selector_sample`: list of the `time` and `value` of `N` random rows
+//!
+//! as well as the `mode` aggregate, which returns the most frequent value.
+//!
 //! [InfluxQL]: https://docs.influxdata.com/influxdb/v1.8/query_language/
 //! [selector functions]: https://docs.influxdata.com/influxdb/v1.8/query_language/functions/#selectors
 use std::{fmt::Debug, sync::Arc};
@@ -116,6 +126,11 @@ use internal::{
     I64MaxSelector, I64MinSelector, LastSelector, U64MaxSelector, U64MinSelector, Utf8MaxSelector,
     Utf8MinSelector,
 };
+
+/// Internal implementations of the selector functions that evaluate all the
+/// points of a group
+mod points;
+use points::{make_list_type, make_point_fields, PointsAccumulator, PointsFunction};
 use schema::TIME_DATA_TYPE;
 
 /// registers selector functions so they can be invoked via SQL
@@ -198,6 +213,187 @@ pub fn selector_max() -> AggregateUDF {
     make_uda("selector_max", FactoryBuilder::new(SelectorType::Max))
 }
 
+/// Returns a DataFusion user defined aggregate function for computing
+/// the percentile(value, time, percentile) selector function, returning
+/// a struct:
+///
+/// percentile(value, time, percentile) -> struct { value, time }
+///
+/// ```text
+/// {
+///   value: the value at the specified percentile, between 0 and 100
+///   time: value of time for the row with the value
+/// }
+/// ```
+///
+/// The value is chosen as InfluxQL does, from the values sorted in
+/// ascending order, and is `NULL` if the percentile is too small to
+/// select any value.
+pub fn selector_percentile() -> AggregateUDF {
+    let signature = numeric_signature(|t| vec![t, TIME_DATA_TYPE(), DataType::Float64]);
+    let return_type: ReturnTypeFunction = Arc::new(|arg_types| {
+        Ok(Arc::new(DataType::Struct(make_struct_fields(
+            arg_types[0].clone(),
+        ))))
+    });
+    make_points_uda(
+        "selector_percentile",
+        PointsFunction::Percentile,
+        signature,
+        return_type,
+    )
+}
+
+/// Returns a DataFusion user defined aggregate function for computing
+/// the top(value, time, N, aux...) selector function, returning a
+/// list of structs:
+///
+/// top(value, time, N, aux...) -> list(struct { value, time, aux0, ... })
+///
+/// ```text
+/// {
+///   value: one of the N largest values
+///   time: value of time for the row with the value
+///   aux0, ...: value of any additional arguments for the row with the value
+/// }
+/// ```
+///
+/// The points are ordered by time. If there are multiple rows with the same
+/// value, the value with the first (earliest/smallest) timestamp is chosen.
+///
+/// If there are additional arguments, such as tag columns, at most one
+/// value, the largest, is chosen for each distinct set of their values.
+pub fn selector_top() -> AggregateUDF {
+    make_points_uda(
+        "selector_top",
+        PointsFunction::Top,
+        Signature::variadic_any(Volatility::Stable),
+        top_bottom_return_type("selector_top"),
+    )
+}
+
+/// Returns a DataFusion user defined aggregate function for computing
+/// the bottom(value, time, N, aux...) selector function, returning a
+/// list of structs, like [`selector_top`], for the `N` smallest values.
+pub fn selector_bottom() -> AggregateUDF {
+    make_points_uda(
+        "selector_bottom",
+        PointsFunction::Bottom,
+        Signature::variadic_any(Volatility::Stable),
+        top_bottom_return_type("selector_bottom"),
+    )
+}
+
+/// Returns a DataFusion user defined aggregate function for computing
+/// the sample(value, time, N) selector function, returning a list of
+/// structs:
+///
+/// sample(value, time, N) -> list(struct { value, time })
+///
+/// ```text
+/// {
+///   value: value of one of N rows chosen at random
+///   time: value of time for the row
+/// }
+/// ```
+///
+/// The points are ordered by time.
+pub fn selector_sample() -> AggregateUDF {
+    let signature = any_value_signature(|t| vec![t, TIME_DATA_TYPE(), DataType::Int64]);
+    let return_type: ReturnTypeFunction = Arc::new(|arg_types| {
+        Ok(Arc::new(make_list_type(make_point_fields(
+            arg_types[0].clone(),
+            &[],
+        ))))
+    });
+    make_points_uda(
+        "selector_sample",
+        PointsFunction::Sample,
+        signature,
+        return_type,
+    )
+}
+
+/// Returns a DataFusion user defined aggregate function for computing
+/// the InfluxQL mode(value, time) function, which returns the most
+/// frequent value.
+///
+/// If there are multiple values with the same frequency, the value
+/// that occurred first is chosen, however, as with InfluxQL, a value
+/// that occurs once is only chosen if it is the smallest value.
+pub fn mode() -> AggregateUDF {
+    let signature = any_value_signature(|t| vec![t, TIME_DATA_TYPE()]);
+    let return_type: ReturnTypeFunction = Arc::new(|arg_types| Ok(Arc::new(arg_types[0].clone())));
+    make_points_uda("mode", PointsFunction::Mode, signature, return_type)
+}
+
+/// Returns a signature for each numeric value type, with the arguments
+/// returned by `args`.
+fn numeric_signature(args: impl Fn(DataType) -> Vec<DataType>) -> Signature {
+    Signature::one_of(
+        [DataType::Float64, DataType::Int64, DataType::UInt64]
+            .into_iter()
+            .map(|t| TypeSignature::Exact(args(t)))
+            .collect(),
+        Volatility::Stable,
+    )
+}
+
+/// Returns a signature for each supported value type, with the arguments
+/// returned by `args`.
+fn any_value_signature(args: impl Fn(DataType) -> Vec<DataType>) -> Signature {
+    Signature::one_of(
+        [
+            DataType::Float64,
+            DataType::Int64,
+            DataType::UInt64,
+            DataType::Utf8,
+            DataType::Boolean,
+        ]
+        .into_iter()
+        .map(|t| TypeSignature::Exact(args(t)))
+        .collect(),
+        Volatility::Stable,
+    )
+}
+
+/// Returns the return type function of the `top` and `bottom` selectors,
+/// which accept any number of additional arguments.
+fn top_bottom_return_type(name: &'static str) -> ReturnTypeFunction {
+    Arc::new(move |arg_types| {
+        match arg_types {
+        [value_type @ (DataType::Float64 | DataType::Int64 | DataType::UInt64), time_type, DataType::Int64, aux_types @ ..]
+            if time_type == &TIME_DATA_TYPE() =>
+        {
+            Ok(Arc::new(make_list_type(make_point_fields(
+                value_type.clone(),
+                aux_types,
+            ))))
+        }
+        _ => Err(DataFusionError::Plan(format!(
+            "{name} requires a numeric value, a timestamp and an integer argument, got {arg_types:?}"
+        ))),
+    }
+    })
+}
+
+/// Create a User Defined Aggregate Function (UDAF) for a selector that
+/// evaluates all the points of a group.
+fn make_points_uda(
+    name: &str,
+    function: PointsFunction,
+    signature: Signature,
+    return_type: ReturnTypeFunction,
+) -> AggregateUDF {
+    let accumulator: AccumulatorFunctionImplementation = Arc::new(move |return_type| {
+        Ok(Box::new(PointsAccumulator::try_new(function, return_type)?))
+    });
+    let state_type: StateTypeFactory =
+        Arc::new(move |return_type| Ok(Arc::new(points::make_state_types(function, return_type)?)));
+
+    AggregateUDF::new(name, &signature, &return_type, &accumulator, &state_type)
+}
+
 #[derive(Debug, Clone, Copy)]
 enum SelectorType {
     First,
@@ -788,6 +984,161 @@ mod test {
         }
     }
 
+    mod percentile {
+        use super::*;
+
+        #[tokio::test]
+        async fn test_f64() {
+            run_case(
+                selector_percentile().call(vec![col("f64_value"), col("time"), lit(50.0)]),
+                vec![
+                    "+-----------------------------------------------------+",
+                    "| selector_percentile(t.f64_value,t.time,Float64(50)) |",
+                    "+-----------------------------------------------------+",
+                    "| {value: 3.0, time: 1970-01-01T00:00:00.000006}      |",
+                    "+-----------------------------------------------------+",
+                ],
+            )
+            .await;
+        }
+
+        #[tokio::test]
+        async fn test_i64() {
+            run_case(
+                selector_percentile().call(vec![col("i64_value"), col("time"), lit(90.0)]),
+                vec![
+                    "+-----------------------------------------------------+",
+                    "| selector_percentile(t.i64_value,t.time,Float64(90)) |",
+                    "+-----------------------------------------------------+",
+                    "| {value: 50, time: 1970-01-01T00:00:00.000005}       |",
+                    "+-----------------------------------------------------+",
+                ],
+            )
+            .await;
+        }
+
+        #[tokio::test]
+        async fn test_out_of_range() {
+            run_case(
+                selector_percentile().call(vec![col("u64_value"), col("time"), lit(5.0)]),
+                vec![
+                    "+----------------------------------------------------+",
+                    "| selector_percentile(t.u64_value,t.time,Float64(5)) |",
+                    "+----------------------------------------------------+",
+                    "|                                                    |",
+                    "+----------------------------------------------------+",
+                ],
+            )
+            .await;
+        }
+    }
+
+    mod top {
+        use super::*;
+
+        #[tokio::test]
+        async fn test_f64() {
+            run_case(
+                selector_top().call(vec![col("f64_value"), col("time"), lit(2_i64)]),
+                vec![
+                    "+--------------------------------------------------------------------------------------------------+",
+                    "| selector_top(t.f64_value,t.time,Int64(2))                                                        |",
+                    "+--------------------------------------------------------------------------------------------------+",
+                    "| [{value: 4.0, time: 1970-01-01T00:00:00.000002}, {value: 5.0, time: 1970-01-01T00:00:00.000005}] |",
+                    "+--------------------------------------------------------------------------------------------------+",
+                ],
+            )
+            .await;
+        }
+
+        #[tokio::test]
+        async fn test_aux() {
+            run_case(
+                selector_top().call(vec![col("i64_value"), col("time"), lit(2_i64), col("bool_value")]),
+                vec![
+                    "+-------------------------------------------------------------------------------------------------------------------------+",
+                    "| selector_top(t.i64_value,t.time,Int64(2),t.bool_value)                                                                  |",
+                    "+-------------------------------------------------------------------------------------------------------------------------+",
+                    "| [{value: 20, time: 1970-01-01T00:00:00.000001, aux0: true}, {value: 50, time: 1970-01-01T00:00:00.000005, aux0: false}] |",
+                    "+-------------------------------------------------------------------------------------------------------------------------+",
+                ],
+            )
+            .await;
+        }
+    }
+
+    mod bottom {
+        use super::*;
+
+        #[tokio::test]
+        async fn test_u64() {
+            run_case(
+                selector_bottom().call(vec![col("u64_value"), col("time"), lit(2_i64)]),
+                vec![
+                    "+------------------------------------------------------------------------------------------------+",
+                    "| selector_bottom(t.u64_value,t.time,Int64(2))                                                   |",
+                    "+------------------------------------------------------------------------------------------------+",
+                    "| [{value: 20, time: 1970-01-01T00:00:00.000001}, {value: 10, time: 1970-01-01T00:00:00.000004}] |",
+                    "+------------------------------------------------------------------------------------------------+",
+                ],
+            )
+            .await;
+        }
+    }
+
+    mod sample {
+        use super::*;
+
+        #[tokio::test]
+        async fn test_all() {
+            run_case(
+                selector_sample().call(vec![col("string_value"), col("time"), lit(10_i64)]),
+                vec![
+                    "+----------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------+",
+                    "| selector_sample(t.string_value,t.time,Int64(10))                                                                                                                                                                                                         |",
+                    "+----------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------+",
+                    "| [{value: two, time: 1970-01-01T00:00:00.000001}, {value: four, time: 1970-01-01T00:00:00.000002}, {value: a_one, time: 1970-01-01T00:00:00.000004}, {value: z_five, time: 1970-01-01T00:00:00.000005}, {value: three, time: 1970-01-01T00:00:00.000006}] |",
+                    "+----------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------+",
+                ],
+            )
+            .await;
+        }
+    }
+
+    mod mode {
+        use super::*;
+
+        #[tokio::test]
+        async fn test_string() {
+            run_case(
+                mode().call(vec![col("string_value"), col("time")]),
+                vec![
+                    "+-----------------------------+",
+                    "| mode(t.string_value,t.time) |",
+                    "+-----------------------------+",
+                    "| a_one                       |",
+                    "+-----------------------------+",
+                ],
+            )
+            .await;
+        }
+
+        #[tokio::test]
+        async fn test_bool() {
+            run_case(
+                mode().call(vec![col("bool_value"), col("time")]),
+                vec![
+                    "+---------------------------+",
+                    "| mode(t.bool_value,t.time) |",
+                    "+---------------------------+",
+                    "| false                     |",
+                    "+---------------------------+",
+                ],
+            )
+            .await;
+        }
+    }
+
     mod utils {
         use super::*;
 
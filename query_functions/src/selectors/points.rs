@@ -0,0 +1,406 @@
+//! Implementation of the InfluxQL selector functions that must see all
+//! the points of a group, `percentile`, `top`, `bottom`, `sample` and
+//! `mode`, before choosing any of them.
+//!
+//! Tests are in selector module
+
+use std::{cmp::Ordering, collections::HashSet, sync::Arc};
+
+use arrow::{
+    array::{Array, ArrayRef},
+    datatypes::{DataType, Field, Fields},
+};
+use datafusion::{
+    common::cast::{as_float64_array, as_int64_array, as_list_array},
+    error::{DataFusionError, Result as DataFusionResult},
+    physical_plan::Accumulator,
+    scalar::ScalarValue,
+};
+use schema::TIME_DATA_TYPE;
+
+/// The kind of function implemented by a [`PointsAccumulator`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum PointsFunction {
+    Percentile,
+    Top,
+    Bottom,
+    Sample,
+    Mode,
+}
+
+impl PointsFunction {
+    /// Returns `true` if the function has an integer argument, `N`, following
+    /// the `value` and `time` arguments.
+    fn has_limit(&self) -> bool {
+        matches!(self, Self::Top | Self::Bottom | Self::Sample)
+    }
+}
+
+/// Return the struct fields of a point, which are the `value` and `time`,
+/// followed by any additional values, named `aux0`, `aux1`, etc.
+pub(super) fn make_point_fields(value_type: DataType, aux_types: &[DataType]) -> Fields {
+    [
+        Field::new("value", value_type, true),
+        Field::new("time", TIME_DATA_TYPE(), true),
+    ]
+    .into_iter()
+    .chain(
+        aux_types
+            .iter()
+            .enumerate()
+            .map(|(i, t)| Field::new(format!("aux{i}"), t.clone(), true)),
+    )
+    .collect()
+}
+
+/// Return the type of a list of points with `fields`.
+pub(super) fn make_list_type(fields: Fields) -> DataType {
+    DataType::List(Arc::new(Field::new("item", DataType::Struct(fields), true)))
+}
+
+/// Return the types of the columns of the points, given the return type of
+/// the function.
+pub(super) fn column_types_from_return_type(
+    function: PointsFunction,
+    return_type: &DataType,
+) -> DataFusionResult<Vec<DataType>> {
+    let fields = match (function, return_type) {
+        (PointsFunction::Mode, t) => return Ok(vec![t.clone(), TIME_DATA_TYPE()]),
+        (PointsFunction::Percentile, DataType::Struct(fields)) => fields,
+        (_, DataType::List(field)) => match field.data_type() {
+            DataType::Struct(fields) => fields,
+            t => {
+                return Err(DataFusionError::Internal(format!(
+                    "expected list of struct for {function:?}, got list of {t}"
+                )))
+            }
+        },
+        (_, t) => {
+            return Err(DataFusionError::Internal(format!(
+                "unexpected return type for {function:?}: {t}"
+            )))
+        }
+    };
+
+    Ok(fields.iter().map(|f| f.data_type().clone()).collect())
+}
+
+/// Return the types of the intermediate state, which is a list for each
+/// column of the points, followed by the argument of the function, if any.
+pub(super) fn make_state_types(
+    function: PointsFunction,
+    return_type: &DataType,
+) -> DataFusionResult<Vec<DataType>> {
+    let columns = column_types_from_return_type(function, return_type)?
+        .into_iter()
+        .map(|t| DataType::List(Arc::new(Field::new("item", t, true))));
+
+    Ok(match function {
+        PointsFunction::Percentile => columns.chain([DataType::Float64]).collect(),
+        PointsFunction::Top | PointsFunction::Bottom | PointsFunction::Sample => {
+            columns.chain([DataType::Int64]).collect()
+        }
+        PointsFunction::Mode => columns.collect(),
+    })
+}
+
+/// The argument of the function, which is initialized from the first batch or
+/// state.
+#[derive(Debug, Clone, Copy)]
+enum Argument {
+    Percentile(f64),
+    Limit(i64),
+}
+
+/// Accumulates the points of a group, which are evaluated by the function
+/// once all of them are known.
+///
+/// Points with a `NULL` value or time are ignored.
+#[derive(Debug)]
+pub(super) struct PointsAccumulator {
+    function: PointsFunction,
+    /// The types of the `value`, `time` and any additional columns.
+    types: Vec<DataType>,
+    /// The values of the columns of the points.
+    columns: Vec<Vec<ScalarValue>>,
+    argument: Option<Argument>,
+}
+
+impl PointsAccumulator {
+    pub(super) fn try_new(
+        function: PointsFunction,
+        return_type: &DataType,
+    ) -> DataFusionResult<Self> {
+        let types = column_types_from_return_type(function, return_type)?;
+        Ok(Self {
+            function,
+            columns: vec![vec![]; types.len()],
+            types,
+            argument: None,
+        })
+    }
+
+    fn len(&self) -> usize {
+        self.columns[0].len()
+    }
+
+    fn value(&self, i: usize) -> &ScalarValue {
+        &self.columns[0][i]
+    }
+
+    fn time(&self, i: usize) -> &ScalarValue {
+        &self.columns[1][i]
+    }
+
+    /// Returns the point at index `i` as a struct.
+    fn point(&self, i: usize) -> ScalarValue {
+        ScalarValue::Struct(
+            Some(self.columns.iter().map(|c| c[i].clone()).collect()),
+            self.fields(),
+        )
+    }
+
+    fn fields(&self) -> Fields {
+        make_point_fields(self.types[0].clone(), &self.types[2..])
+    }
+
+    /// Returns the points at `indexes`, as a list of structs.
+    fn points(&self, indexes: impl IntoIterator<Item = usize>) -> ScalarValue {
+        ScalarValue::new_list(
+            Some(indexes.into_iter().map(|i| self.point(i)).collect()),
+            DataType::Struct(self.fields()),
+        )
+    }
+
+    /// Returns the `N` argument of the function, which must be positive.
+    fn limit(&self) -> DataFusionResult<usize> {
+        match self.argument {
+            Some(Argument::Limit(n)) if n > 0 => Ok(n as usize),
+            Some(Argument::Limit(n)) => Err(DataFusionError::Plan(format!(
+                "{:?} N argument must be greater than 0, got {n}",
+                self.function
+            ))),
+            _ => Ok(0),
+        }
+    }
+
+    /// Returns the indexes of the points, ordered by `value` and then `time`.
+    fn indexes_by_value(&self) -> Vec<usize> {
+        let mut indexes = (0..self.len()).collect::<Vec<_>>();
+        indexes.sort_by(|a, b| {
+            compare(self.value(*a), self.value(*b)).then(compare(self.time(*a), self.time(*b)))
+        });
+        indexes
+    }
+
+    /// Sorts `indexes` by the time of the points.
+    fn sort_by_time(&self, indexes: &mut [usize]) {
+        indexes.sort_by(|a, b| compare(self.time(*a), self.time(*b)));
+    }
+
+    fn evaluate_percentile(&self, percentile: f64) -> ScalarValue {
+        let indexes = self.indexes_by_value();
+        let i = ((indexes.len() as f64) * percentile / 100.0 + 0.5).floor() as i64 - 1;
+        match usize::try_from(i).ok().and_then(|i| indexes.get(i)) {
+            Some(i) => self.point(*i),
+            None => ScalarValue::Struct(None, self.fields()),
+        }
+    }
+
+    /// Selects the `N` points with the largest (`top`) or smallest (`bottom`)
+    /// values, of which the earliest is chosen for equal values.
+    ///
+    /// When there are additional columns, only the point with the largest or
+    /// smallest value of each distinct set of the additional values is selected.
+    fn evaluate_top_bottom(&self) -> DataFusionResult<ScalarValue> {
+        let mut indexes = (0..self.len()).collect::<Vec<_>>();
+        indexes.sort_by(|a, b| {
+            let by_value = compare(self.value(*a), self.value(*b));
+            match self.function {
+                PointsFunction::Top => by_value.reverse(),
+                _ => by_value,
+            }
+            .then(compare(self.time(*a), self.time(*b)))
+        });
+
+        if self.types.len() > 2 {
+            let mut seen = HashSet::new();
+            indexes.retain(|i| {
+                seen.insert(
+                    self.columns[2..]
+                        .iter()
+                        .map(|c| c[*i].clone())
+                        .collect::<Vec<_>>(),
+                )
+            });
+        }
+
+        indexes.truncate(self.limit()?);
+        self.sort_by_time(&mut indexes);
+        Ok(self.points(indexes))
+    }
+
+    fn evaluate_sample(&self) -> DataFusionResult<ScalarValue> {
+        let n = self.limit()?.min(self.len());
+        let mut indexes =
+            rand::seq::index::sample(&mut rand::thread_rng(), self.len(), n).into_vec();
+        self.sort_by_time(&mut indexes);
+        Ok(self.points(indexes))
+    }
+
+    /// Returns the most frequent value, using the same algorithm as InfluxDB 1.x,
+    /// which selects the value with the earliest time when there are several
+    /// with the same frequency, except that a value that occurs only once is
+    /// never selected over the smallest value.
+    fn evaluate_mode(&self) -> DataFusionResult<ScalarValue> {
+        let indexes = self.indexes_by_value();
+        let Some(first) = indexes.first() else {
+            return ScalarValue::try_from(&self.types[0]);
+        };
+
+        let (mut most_freq, mut curr_freq) = (0, 0);
+        let (mut most_mode, mut most_time) = (*first, self.time(*first));
+        let (mut curr_mode, mut curr_time) = (*first, self.time(*first));
+        for i in indexes {
+            if self.value(i) != self.value(curr_mode) {
+                curr_freq = 1;
+                curr_mode = i;
+                curr_time = self.time(i);
+                continue;
+            }
+            curr_freq += 1;
+            if most_freq > curr_freq || (most_freq == curr_freq && curr_time > most_time) {
+                continue;
+            }
+            most_freq = curr_freq;
+            most_mode = i;
+            most_time = self.time(i);
+        }
+
+        Ok(self.value(most_mode).clone())
+    }
+}
+
+/// Compare two scalar values of the same type.
+fn compare(a: &ScalarValue, b: &ScalarValue) -> Ordering {
+    a.partial_cmp(b).unwrap_or(Ordering::Equal)
+}
+
+impl Accumulator for PointsAccumulator {
+    fn state(&self) -> DataFusionResult<Vec<ScalarValue>> {
+        let columns = self
+            .columns
+            .iter()
+            .zip(&self.types)
+            .map(|(c, t)| ScalarValue::new_list(Some(c.clone()), t.clone()));
+
+        Ok(match self.function {
+            PointsFunction::Percentile => columns
+                .chain([ScalarValue::Float64(match self.argument {
+                    Some(Argument::Percentile(p)) => Some(p),
+                    _ => None,
+                })])
+                .collect(),
+            PointsFunction::Top | PointsFunction::Bottom | PointsFunction::Sample => columns
+                .chain([ScalarValue::Int64(match self.argument {
+                    Some(Argument::Limit(n)) => Some(n),
+                    _ => None,
+                })])
+                .collect(),
+            PointsFunction::Mode => columns.collect(),
+        })
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> DataFusionResult<()> {
+        if values[0].is_empty() {
+            return Ok(());
+        }
+
+        // The arguments are the value and time, followed by the argument of
+        // the function, if any, and the additional columns.
+        if self.argument.is_none() {
+            match self.function {
+                PointsFunction::Percentile => {
+                    let p = as_float64_array(&values[2])?;
+                    self.argument = Some(Argument::Percentile(p.value(0)));
+                }
+                f if f.has_limit() => {
+                    let n = as_int64_array(&values[2])?;
+                    self.argument = Some(Argument::Limit(n.value(0)));
+                }
+                _ => {}
+            }
+        }
+        let columns = values[..2].iter().chain(if self.function.has_limit() {
+            &values[3..]
+        } else {
+            &[]
+        });
+        let columns = columns.collect::<Vec<_>>();
+
+        for row in 0..values[0].len() {
+            if values[0].is_null(row) || values[1].is_null(row) {
+                continue;
+            }
+            for (column, arr) in self.columns.iter_mut().zip(&columns) {
+                column.push(ScalarValue::try_from_array(arr, row)?);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> DataFusionResult<()> {
+        let (lists, argument) = states.split_at(self.types.len());
+
+        if self.argument.is_none() {
+            match self.function {
+                PointsFunction::Percentile => {
+                    let p = as_float64_array(&argument[0])?;
+                    self.argument = p.iter().flatten().next().map(Argument::Percentile);
+                }
+                f if f.has_limit() => {
+                    let n = as_int64_array(&argument[0])?;
+                    self.argument = n.iter().flatten().next().map(Argument::Limit);
+                }
+                _ => {}
+            }
+        }
+
+        for row in 0..lists[0].len() {
+            if lists[0].is_null(row) {
+                continue;
+            }
+            for (column, list) in self.columns.iter_mut().zip(lists) {
+                let arr = as_list_array(list)?.value(row);
+                for i in 0..arr.len() {
+                    column.push(ScalarValue::try_from_array(&arr, i)?);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn evaluate(&self) -> DataFusionResult<ScalarValue> {
+        match (self.function, self.argument) {
+            (PointsFunction::Percentile, Some(Argument::Percentile(p))) => {
+                Ok(self.evaluate_percentile(p))
+            }
+            (PointsFunction::Percentile, _) => Ok(ScalarValue::Struct(None, self.fields())),
+            (PointsFunction::Top | PointsFunction::Bottom, _) => self.evaluate_top_bottom(),
+            (PointsFunction::Sample, _) => self.evaluate_sample(),
+            (PointsFunction::Mode, _) => self.evaluate_mode(),
+        }
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+            + self
+                .columns
+                .iter()
+                .map(ScalarValue::size_of_vec)
+                .sum::<usize>()
+            + std::mem::size_of::<DataType>() * self.types.capacity()
+    }
+}
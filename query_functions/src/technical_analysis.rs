@@ -0,0 +1,878 @@
+//! User-defined aggregate functions that implement the InfluxQL
+//! [technical analysis] functions, such as `exponential_moving_average`
+//! and `relative_strength_index`.
+//!
+//! The functions are intended to be evaluated as window functions, over a
+//! window frame that spans from the first row of each series to the current
+//! row, ordered by time:
+//!
+//! ```sql
+//! SELECT
+//!   time,
+//!   exponential_moving_average(usage, time, 5, -1, 'exponential') OVER (
+//!     PARTITION BY host
+//!     ORDER BY time
+//!     ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW
+//!   )
+//! FROM cpu
+//! ```
+//!
+//! Each function accepts a numeric value, the timestamp of the row, the period,
+//! the hold period and, with the exception of the `kaufmans_*` functions, the
+//! warmup type. A hold period of `-1` selects the default of the function, which
+//! is the number of values required to warm up the algorithm. The result is
+//! `NULL` for the rows of the hold period and rows with a `NULL` value.
+//!
+//! The algorithms are ported from the implementation of InfluxDB 1.x, to produce
+//! identical results.
+//!
+//! [technical analysis]: https://docs.influxdata.com/influxdb/v1.8/query_language/functions/#technical-analysis
+use std::{collections::VecDeque, fmt::Debug, sync::Arc};
+
+use arrow::{
+    array::{Array, ArrayRef},
+    compute::cast,
+    datatypes::DataType,
+};
+use datafusion::{
+    common::cast::{as_float64_array, as_int64_array, as_string_array},
+    error::{DataFusionError, Result as DataFusionResult},
+    logical_expr::{
+        AccumulatorFunctionImplementation, ReturnTypeFunction, Signature, StateTypeFunction,
+        TypeSignature, Volatility,
+    },
+    physical_plan::{udaf::AggregateUDF, Accumulator},
+    scalar::ScalarValue,
+};
+use once_cell::sync::Lazy;
+use schema::TIME_DATA_TYPE;
+
+/// The name of the exponential_moving_average UDAF given to DataFusion.
+pub const EXPONENTIAL_MOVING_AVERAGE_UDAF_NAME: &str = "exponential_moving_average";
+
+/// The name of the double_exponential_moving_average UDAF given to DataFusion.
+pub const DOUBLE_EXPONENTIAL_MOVING_AVERAGE_UDAF_NAME: &str = "double_exponential_moving_average";
+
+/// The name of the triple_exponential_moving_average UDAF given to DataFusion.
+pub const TRIPLE_EXPONENTIAL_MOVING_AVERAGE_UDAF_NAME: &str = "triple_exponential_moving_average";
+
+/// The name of the relative_strength_index UDAF given to DataFusion.
+pub const RELATIVE_STRENGTH_INDEX_UDAF_NAME: &str = "relative_strength_index";
+
+/// The name of the triple_exponential_derivative UDAF given to DataFusion.
+pub const TRIPLE_EXPONENTIAL_DERIVATIVE_UDAF_NAME: &str = "triple_exponential_derivative";
+
+/// The name of the kaufmans_efficiency_ratio UDAF given to DataFusion.
+pub const KAUFMANS_EFFICIENCY_RATIO_UDAF_NAME: &str = "kaufmans_efficiency_ratio";
+
+/// The name of the kaufmans_adaptive_moving_average UDAF given to DataFusion.
+pub const KAUFMANS_ADAPTIVE_MOVING_AVERAGE_UDAF_NAME: &str = "kaufmans_adaptive_moving_average";
+
+/// The name of the chande_momentum_oscillator UDAF given to DataFusion.
+pub const CHANDE_MOMENTUM_OSCILLATOR_UDAF_NAME: &str = "chande_momentum_oscillator";
+
+/// Implementation of exponential_moving_average.
+pub(crate) static EXPONENTIAL_MOVING_AVERAGE: Lazy<Arc<AggregateUDF>> =
+    Lazy::new(|| make_udaf(EXPONENTIAL_MOVING_AVERAGE_UDAF_NAME, Algorithm::Ema));
+
+/// Implementation of double_exponential_moving_average.
+pub(crate) static DOUBLE_EXPONENTIAL_MOVING_AVERAGE: Lazy<Arc<AggregateUDF>> =
+    Lazy::new(|| make_udaf(DOUBLE_EXPONENTIAL_MOVING_AVERAGE_UDAF_NAME, Algorithm::Dema));
+
+/// Implementation of triple_exponential_moving_average.
+pub(crate) static TRIPLE_EXPONENTIAL_MOVING_AVERAGE: Lazy<Arc<AggregateUDF>> =
+    Lazy::new(|| make_udaf(TRIPLE_EXPONENTIAL_MOVING_AVERAGE_UDAF_NAME, Algorithm::Tema));
+
+/// Implementation of relative_strength_index.
+pub(crate) static RELATIVE_STRENGTH_INDEX: Lazy<Arc<AggregateUDF>> =
+    Lazy::new(|| make_udaf(RELATIVE_STRENGTH_INDEX_UDAF_NAME, Algorithm::Rsi));
+
+/// Implementation of triple_exponential_derivative.
+pub(crate) static TRIPLE_EXPONENTIAL_DERIVATIVE: Lazy<Arc<AggregateUDF>> =
+    Lazy::new(|| make_udaf(TRIPLE_EXPONENTIAL_DERIVATIVE_UDAF_NAME, Algorithm::Trix));
+
+/// Implementation of kaufmans_efficiency_ratio.
+pub(crate) static KAUFMANS_EFFICIENCY_RATIO: Lazy<Arc<AggregateUDF>> =
+    Lazy::new(|| make_udaf(KAUFMANS_EFFICIENCY_RATIO_UDAF_NAME, Algorithm::Ker));
+
+/// Implementation of kaufmans_adaptive_moving_average.
+pub(crate) static KAUFMANS_ADAPTIVE_MOVING_AVERAGE: Lazy<Arc<AggregateUDF>> =
+    Lazy::new(|| make_udaf(KAUFMANS_ADAPTIVE_MOVING_AVERAGE_UDAF_NAME, Algorithm::Kama));
+
+/// Implementation of chande_momentum_oscillator.
+pub(crate) static CHANDE_MOMENTUM_OSCILLATOR: Lazy<Arc<AggregateUDF>> =
+    Lazy::new(|| make_udaf(CHANDE_MOMENTUM_OSCILLATOR_UDAF_NAME, Algorithm::Cmo));
+
+fn make_udaf(name: &'static str, algorithm: Algorithm) -> Arc<AggregateUDF> {
+    // value, time, period, hold period[, warmup type]
+    let mut extra = vec![DataType::Int64, DataType::Int64];
+    if algorithm.has_warmup_type() {
+        extra.push(DataType::Utf8);
+    }
+    let signature = Signature::one_of(
+        [DataType::Float64, DataType::Int64, DataType::UInt64]
+            .into_iter()
+            .map(|dt| {
+                let mut args = vec![dt, TIME_DATA_TYPE()];
+                args.extend_from_slice(&extra);
+                TypeSignature::Exact(args)
+            })
+            .collect(),
+        Volatility::Immutable,
+    );
+
+    let return_type: ReturnTypeFunction = Arc::new(|_| Ok(Arc::new(DataType::Float64)));
+    let accumulator: AccumulatorFunctionImplementation =
+        Arc::new(move |_| Ok(Box::new(TechnicalAnalysisAccumulator::new(name, algorithm))));
+    // The functions are only evaluated as window functions, and therefore
+    // never produce an intermediate state.
+    let state_type: StateTypeFunction = Arc::new(|_| Ok(Arc::new(vec![])));
+
+    Arc::new(AggregateUDF::new(
+        name,
+        &signature,
+        &return_type,
+        &accumulator,
+        &state_type,
+    ))
+}
+
+/// The algorithms of the technical analysis functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Algorithm {
+    Ema,
+    Dema,
+    Tema,
+    Rsi,
+    Trix,
+    Ker,
+    Kama,
+    Cmo,
+}
+
+impl Algorithm {
+    fn has_warmup_type(&self) -> bool {
+        !matches!(self, Self::Ker | Self::Kama)
+    }
+
+    fn indicator(&self, period: usize, warmup: WarmupType) -> Box<dyn Indicator> {
+        match self {
+            Self::Ema => Box::new(Ema::new(period, warmup)),
+            Self::Dema => Box::new(Dema::new(period, warmup)),
+            Self::Tema => Box::new(Tema::new(period, warmup)),
+            Self::Rsi => Box::new(Rsi::new(period, warmup)),
+            Self::Trix => Box::new(Trix::new(period, warmup)),
+            Self::Ker => Box::new(Ker::new(period)),
+            Self::Kama => Box::new(Kama::new(period)),
+            Self::Cmo if warmup == WarmupType::None => Box::new(Cmo::new(period)),
+            Self::Cmo => Box::new(Cmos::new(period, warmup)),
+        }
+    }
+}
+
+/// Determines how the exponential moving averages are computed before
+/// they have observed a full period of values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WarmupType {
+    /// Only valid for `chande_momentum_oscillator`, which does not smooth
+    /// the values with an exponential moving average.
+    None,
+    /// Computes the exponential moving average, using the first value as the seed.
+    Exponential,
+    /// Computes the simple moving average until a full period of values
+    /// is observed.
+    Simple,
+}
+
+impl WarmupType {
+    fn try_from_str(s: &str) -> DataFusionResult<Self> {
+        match s {
+            "none" => Ok(Self::None),
+            "exponential" => Ok(Self::Exponential),
+            "simple" => Ok(Self::Simple),
+            _ => Err(DataFusionError::Plan(format!(
+                "warmup type must be one of: 'none', 'exponential' or 'simple', got {s}"
+            ))),
+        }
+    }
+}
+
+/// A technical analysis algorithm, which produces a value for each value
+/// of the input series.
+trait Indicator: Debug + Send + Sync {
+    /// Adds `v` to the series, returning the result of the algorithm.
+    fn add(&mut self, v: f64) -> f64;
+
+    /// The number of values required to warm up the algorithm.
+    fn warm_count(&self) -> usize;
+}
+
+/// Implements the technical analysis functions, by evaluating an [`Indicator`]
+/// for each row.
+#[derive(Debug)]
+struct TechnicalAnalysisAccumulator {
+    name: &'static str,
+    algorithm: Algorithm,
+    /// The indicator and hold period, which are initialized from the arguments
+    /// of the first batch.
+    state: Option<(Box<dyn Indicator>, usize)>,
+    /// The number of non-null values observed.
+    count: usize,
+    result: Option<f64>,
+}
+
+impl TechnicalAnalysisAccumulator {
+    fn new(name: &'static str, algorithm: Algorithm) -> Self {
+        Self {
+            name,
+            algorithm,
+            state: None,
+            count: 0,
+            result: None,
+        }
+    }
+
+    /// Initialize the indicator from the arguments of the first row of `values`.
+    fn init(&self, values: &[ArrayRef]) -> DataFusionResult<(Box<dyn Indicator>, usize)> {
+        let name = self.name;
+        let period = as_int64_array(&values[2])?.value(0);
+        let period = usize::try_from(period)
+            .ok()
+            .filter(|v| *v >= 1)
+            .ok_or_else(|| {
+                DataFusionError::Plan(format!(
+                    "{name} period must be greater than 1, got {period}"
+                ))
+            })?;
+        let hold_period = as_int64_array(&values[3])?.value(0);
+
+        let warmup = match values.get(4) {
+            Some(arr) => WarmupType::try_from_str(as_string_array(arr)?.value(0))?,
+            None => WarmupType::Exponential,
+        };
+
+        let indicator = self.algorithm.indicator(period, warmup);
+        let hold_period = match hold_period {
+            -1 => indicator.warm_count(),
+            v => usize::try_from(v).map_err(|_| {
+                DataFusionError::Plan(format!(
+                    "{name} hold period must be greater than or equal to 0"
+                ))
+            })?,
+        };
+
+        Ok((indicator, hold_period))
+    }
+}
+
+impl Accumulator for TechnicalAnalysisAccumulator {
+    fn state(&self) -> DataFusionResult<Vec<ScalarValue>> {
+        Err(DataFusionError::Internal(format!(
+            "unexpected call to {} state",
+            self.name
+        )))
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> DataFusionResult<()> {
+        if values[0].is_empty() {
+            return Ok(());
+        }
+
+        if self.state.is_none() {
+            self.state = Some(self.init(values)?);
+        }
+        let Some((indicator, hold_period)) = &mut self.state else {
+            unreachable!("state is initialized")
+        };
+
+        let arr = cast(&values[0], &DataType::Float64)?;
+        let arr = as_float64_array(&arr)?;
+        for v in arr.iter() {
+            let Some(v) = v else {
+                self.result = None;
+                continue;
+            };
+
+            let v = indicator.add(v);
+            self.count += 1;
+            self.result = (self.count > *hold_period && v.is_finite()).then_some(v);
+        }
+
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, _states: &[ArrayRef]) -> DataFusionResult<()> {
+        Err(DataFusionError::Internal(format!(
+            "unexpected call to {} merge_batch",
+            self.name
+        )))
+    }
+
+    fn evaluate(&self) -> DataFusionResult<ScalarValue> {
+        Ok(ScalarValue::Float64(self.result))
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+    }
+}
+
+/// Exponential moving average.
+#[derive(Debug, Clone)]
+struct Ema {
+    period: usize,
+    alpha: f64,
+    warmup: WarmupType,
+    count: usize,
+    last: f64,
+}
+
+impl Ema {
+    fn new(period: usize, warmup: WarmupType) -> Self {
+        Self {
+            period,
+            alpha: 2.0 / (period as f64 + 1.0),
+            warmup,
+            count: 0,
+            last: 0.0,
+        }
+    }
+
+    fn warmed(&self) -> bool {
+        self.count == self.period
+    }
+
+    /// Returns `true` if the next average in the chain should observe the
+    /// output of this average.
+    fn feeds_next(&self) -> bool {
+        self.warmed() || self.warmup == WarmupType::Exponential
+    }
+}
+
+impl Indicator for Ema {
+    fn add(&mut self, v: f64) -> f64 {
+        let avg = if self.count == 0 {
+            v
+        } else if !self.warmed() && self.warmup == WarmupType::Simple {
+            (self.last * self.count as f64 + v) / (self.count as f64 + 1.0)
+        } else {
+            self.alpha * v + (1.0 - self.alpha) * self.last
+        };
+
+        self.last = avg;
+        if self.count < self.period {
+            self.count += 1;
+        }
+        avg
+    }
+
+    fn warm_count(&self) -> usize {
+        self.period - 1
+    }
+}
+
+/// Double exponential moving average.
+#[derive(Debug)]
+struct Dema {
+    ema1: Ema,
+    ema2: Ema,
+}
+
+impl Dema {
+    fn new(period: usize, warmup: WarmupType) -> Self {
+        Self {
+            ema1: Ema::new(period, warmup),
+            ema2: Ema::new(period, warmup),
+        }
+    }
+}
+
+impl Indicator for Dema {
+    fn add(&mut self, v: f64) -> f64 {
+        let avg1 = self.ema1.add(v);
+        let avg2 = if self.ema1.feeds_next() {
+            self.ema2.add(avg1)
+        } else {
+            avg1
+        };
+        2.0 * avg1 - avg2
+    }
+
+    fn warm_count(&self) -> usize {
+        if self.ema1.warmup == WarmupType::Exponential {
+            self.ema1.warm_count()
+        } else {
+            self.ema1.warm_count() + self.ema2.warm_count()
+        }
+    }
+}
+
+/// Triple exponential moving average.
+#[derive(Debug)]
+struct Tema {
+    ema1: Ema,
+    ema2: Ema,
+    ema3: Ema,
+}
+
+impl Tema {
+    fn new(period: usize, warmup: WarmupType) -> Self {
+        Self {
+            ema1: Ema::new(period, warmup),
+            ema2: Ema::new(period, warmup),
+            ema3: Ema::new(period, warmup),
+        }
+    }
+}
+
+impl Indicator for Tema {
+    fn add(&mut self, v: f64) -> f64 {
+        let avg1 = self.ema1.add(v);
+        let avg2 = if self.ema1.feeds_next() {
+            self.ema2.add(avg1)
+        } else {
+            avg1
+        };
+        let avg3 = if self.ema2.feeds_next() {
+            self.ema3.add(avg2)
+        } else {
+            avg2
+        };
+        3.0 * avg1 - 3.0 * avg2 + avg3
+    }
+
+    fn warm_count(&self) -> usize {
+        if self.ema1.warmup == WarmupType::Exponential {
+            self.ema1.warm_count()
+        } else {
+            self.ema1.warm_count() + self.ema2.warm_count() + self.ema3.warm_count()
+        }
+    }
+}
+
+/// Triple exponential derivative, which is the percentage rate of change
+/// of a triple exponential moving average.
+#[derive(Debug)]
+struct Trix {
+    ema1: Ema,
+    ema2: Ema,
+    ema3: Ema,
+    last: f64,
+}
+
+impl Trix {
+    fn new(period: usize, warmup: WarmupType) -> Self {
+        Self {
+            ema1: Ema::new(period, warmup),
+            ema2: Ema::new(period, warmup),
+            ema3: Ema::new(period, warmup),
+            last: 0.0,
+        }
+    }
+}
+
+impl Indicator for Trix {
+    fn add(&mut self, v: f64) -> f64 {
+        let mut cur = self.ema1.add(v);
+        if self.ema1.feeds_next() {
+            cur = self.ema2.add(cur);
+            if self.ema2.feeds_next() {
+                cur = self.ema3.add(cur);
+            }
+        }
+
+        let rate = ((cur / self.last) - 1.0) * 100.0;
+        self.last = cur;
+        rate
+    }
+
+    fn warm_count(&self) -> usize {
+        if self.ema1.warmup == WarmupType::Exponential {
+            self.ema1.warm_count() + 1
+        } else {
+            self.ema1.warm_count() * 3 + 1
+        }
+    }
+}
+
+/// Smoothed averages of the upward and downward changes of the series, used by
+/// the relative strength index and the smoothed Chande momentum oscillator.
+#[derive(Debug)]
+struct UpDown {
+    up: Ema,
+    down: Ema,
+    last: f64,
+}
+
+impl UpDown {
+    fn new(period: usize, warmup: WarmupType) -> Self {
+        let mut ema = Ema::new(period + 1, warmup);
+        ema.alpha = 1.0 / period as f64;
+        Self {
+            up: ema.clone(),
+            down: ema,
+            last: 0.0,
+        }
+    }
+
+    /// Adds `v` to the series, returning the smoothed upward and downward changes.
+    fn add(&mut self, v: f64) -> (f64, f64) {
+        let (up, down) = if v > self.last {
+            (v - self.last, 0.0)
+        } else if v < self.last {
+            (0.0, self.last - v)
+        } else {
+            (0.0, 0.0)
+        };
+        self.last = v;
+        (self.up.add(up), self.down.add(down))
+    }
+
+    fn warm_count(&self) -> usize {
+        self.up.warm_count()
+    }
+}
+
+/// Relative strength index.
+#[derive(Debug)]
+struct Rsi(UpDown);
+
+impl Rsi {
+    fn new(period: usize, warmup: WarmupType) -> Self {
+        Self(UpDown::new(period, warmup))
+    }
+}
+
+impl Indicator for Rsi {
+    fn add(&mut self, v: f64) -> f64 {
+        let (up, down) = self.0.add(v);
+        100.0 - (100.0 / (1.0 + up / down))
+    }
+
+    fn warm_count(&self) -> usize {
+        self.0.warm_count()
+    }
+}
+
+/// Chande momentum oscillator, smoothed by exponential moving averages.
+#[derive(Debug)]
+struct Cmos(UpDown);
+
+impl Cmos {
+    fn new(period: usize, warmup: WarmupType) -> Self {
+        Self(UpDown::new(period, warmup))
+    }
+}
+
+impl Indicator for Cmos {
+    fn add(&mut self, v: f64) -> f64 {
+        let (up, down) = self.0.add(v);
+        100.0 * ((up - down) / (up + down))
+    }
+
+    fn warm_count(&self) -> usize {
+        self.0.warm_count()
+    }
+}
+
+/// Chande momentum oscillator.
+#[derive(Debug)]
+struct Cmo {
+    /// The number of changes retained in addition to the most recent change.
+    capacity: usize,
+    /// The retained changes, including the change of the first value, which is zero.
+    diffs: VecDeque<f64>,
+    last: Option<f64>,
+    sum_up: f64,
+    sum_down: f64,
+}
+
+impl Cmo {
+    fn new(period: usize) -> Self {
+        Self {
+            capacity: period - 1,
+            diffs: VecDeque::with_capacity(period),
+            last: None,
+            sum_up: 0.0,
+            sum_down: 0.0,
+        }
+    }
+}
+
+impl Indicator for Cmo {
+    fn add(&mut self, v: f64) -> f64 {
+        let diff = self.last.map_or(0.0, |last| v - last);
+        if diff > 0.0 {
+            self.sum_up += diff;
+        } else if diff < 0.0 {
+            self.sum_down -= diff;
+        }
+
+        let out = if self.sum_up != 0.0 || self.sum_down != 0.0 {
+            100.0 * ((self.sum_up - self.sum_down) / (self.sum_up + self.sum_down))
+        } else {
+            0.0
+        };
+
+        self.diffs.push_back(diff);
+        if self.diffs.len() > self.capacity {
+            match self.diffs.pop_front() {
+                Some(oldest) if oldest > 0.0 => self.sum_up -= oldest,
+                Some(oldest) if oldest < 0.0 => self.sum_down += oldest,
+                _ => {}
+            }
+        }
+        self.last = Some(v);
+
+        out
+    }
+
+    fn warm_count(&self) -> usize {
+        self.capacity
+    }
+}
+
+/// Kaufman efficiency ratio.
+#[derive(Debug)]
+struct Ker {
+    /// A ring buffer of the most recent prices and the absolute change from
+    /// the preceding price, which is initialized to zero values.
+    points: Vec<(f64, f64)>,
+    /// The index of the most recent point.
+    idx: usize,
+    noise: f64,
+    count: usize,
+}
+
+impl Ker {
+    fn new(period: usize) -> Self {
+        Self {
+            points: vec![(0.0, 0.0); period],
+            idx: 0,
+            noise: 0.0,
+            count: 0,
+        }
+    }
+
+    fn warmed(&self) -> bool {
+        self.count == self.points.len() + 1
+    }
+
+    /// The most recent price.
+    fn last_price(&self) -> f64 {
+        self.points[self.idx].0
+    }
+}
+
+impl Indicator for Ker {
+    fn add(&mut self, v: f64) -> f64 {
+        let oldest = (self.idx + 1) % self.points.len();
+        let signal = (v - self.points[oldest].0).abs();
+        let diff = (v - self.points[self.idx].0).abs();
+
+        self.noise -= self.points[oldest].1;
+        self.noise += diff;
+
+        self.idx = oldest;
+        self.points[oldest] = (v, diff);
+        if !self.warmed() {
+            self.count += 1;
+        }
+
+        if signal == 0.0 || self.noise == 0.0 {
+            0.0
+        } else {
+            signal / self.noise
+        }
+    }
+
+    fn warm_count(&self) -> usize {
+        self.points.len()
+    }
+}
+
+/// Kaufman adaptive moving average.
+#[derive(Debug)]
+struct Kama {
+    ker: Ker,
+    last: f64,
+}
+
+impl Kama {
+    fn new(period: usize) -> Self {
+        Self {
+            ker: Ker::new(period),
+            last: 0.0,
+        }
+    }
+}
+
+impl Indicator for Kama {
+    fn add(&mut self, v: f64) -> f64 {
+        if !self.ker.warmed() {
+            // initialize with the last value
+            self.last = self.ker.last_price();
+        }
+
+        let er = self.ker.add(v);
+        let sc = (er * (2.0 / (2.0 + 1.0) - 2.0 / (30.0 + 1.0)) + 2.0 / (30.0 + 1.0)).powi(2);
+        self.last += sc * (v - self.last);
+        self.last
+    }
+
+    fn warm_count(&self) -> usize {
+        self.ker.warm_count()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use arrow::array::{Float64Array, Int64Array, StringArray, TimestampNanosecondArray};
+
+    /// Evaluate `algorithm` for each of `values`, one row at a time, as the
+    /// DataFusion window operator does for a growing window frame.
+    fn evaluate(
+        algorithm: Algorithm,
+        values: &[Option<f64>],
+        period: i64,
+        hold_period: i64,
+        warmup: &str,
+    ) -> Vec<Option<f64>> {
+        let n = values.len();
+        let mut args: Vec<ArrayRef> = vec![
+            Arc::new(Float64Array::from(values.to_vec())),
+            Arc::new(TimestampNanosecondArray::from_iter_values(0..n as i64)),
+            Arc::new(Int64Array::from(vec![period; n])),
+            Arc::new(Int64Array::from(vec![hold_period; n])),
+        ];
+        if algorithm.has_warmup_type() {
+            args.push(Arc::new(StringArray::from(vec![warmup; n])));
+        }
+
+        let mut acc = TechnicalAnalysisAccumulator::new("test", algorithm);
+        (0..n)
+            .map(|i| {
+                let row = args.iter().map(|arr| arr.slice(i, 1)).collect::<Vec<_>>();
+                acc.update_batch(&row).unwrap();
+                match acc.evaluate().unwrap() {
+                    ScalarValue::Float64(v) => v.map(|v| (v * 1e6).round() / 1e6),
+                    v => panic!("unexpected result {v:?}"),
+                }
+            })
+            .collect()
+    }
+
+    fn values(v: &[f64]) -> Vec<Option<f64>> {
+        v.iter().copied().map(Some).collect()
+    }
+
+    #[test]
+    fn test_exponential_moving_average() {
+        let input = values(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+
+        // alpha = 2 / 3
+        assert_eq!(
+            evaluate(Algorithm::Ema, &input, 2, -1, "exponential"),
+            vec![
+                None,
+                Some(1.666667),
+                Some(2.555556),
+                Some(3.518519),
+                Some(4.506173)
+            ]
+        );
+        assert_eq!(
+            evaluate(Algorithm::Ema, &input, 3, -1, "simple"),
+            vec![None, None, Some(2.0), Some(3.0), Some(4.0)]
+        );
+        assert_eq!(
+            evaluate(Algorithm::Ema, &input, 2, 0, "exponential")[0],
+            Some(1.0)
+        );
+
+        // NULL values are skipped
+        let input = vec![Some(1.0), None, Some(2.0)];
+        assert_eq!(
+            evaluate(Algorithm::Ema, &input, 2, -1, "exponential"),
+            vec![None, None, Some(1.666667)]
+        );
+    }
+
+    #[test]
+    fn test_double_and_triple_exponential_moving_average() {
+        // The averages of a linear series lag by a constant, which the double and
+        // triple averages correct for.
+        let input = values(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(
+            evaluate(Algorithm::Dema, &input, 2, -1, "exponential"),
+            vec![
+                None,
+                Some(1.888889),
+                Some(2.925926),
+                Some(3.962963),
+                Some(4.983539)
+            ]
+        );
+        assert_eq!(
+            evaluate(Algorithm::Tema, &input, 2, 0, "exponential")[..2],
+            [Some(1.0), Some(1.962963)]
+        );
+    }
+
+    #[test]
+    fn test_relative_strength_index() {
+        // Only gains produce an index of 100.
+        let input = values(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(
+            evaluate(Algorithm::Rsi, &input, 2, -1, "exponential"),
+            vec![None, None, Some(100.0), Some(100.0), Some(100.0)]
+        );
+
+        let input = values(&[1.0, 2.0, 1.0, 2.0]);
+        assert_eq!(
+            evaluate(Algorithm::Rsi, &input, 2, -1, "exponential"),
+            vec![None, None, Some(50.0), Some(75.0)]
+        );
+    }
+
+    #[test]
+    fn test_triple_exponential_derivative() {
+        let input = values(&[1.0, 2.0, 4.0, 8.0]);
+        assert_eq!(
+            evaluate(Algorithm::Trix, &input, 2, -1, "exponential"),
+            vec![None, None, Some(68.571429), Some(90.39548)]
+        );
+    }
+
+    #[test]
+    fn test_kaufmans() {
+        // A series with no noise is perfectly efficient.
+        let input = values(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(
+            evaluate(Algorithm::Ker, &input, 2, -1, ""),
+            vec![None, None, Some(1.0), Some(1.0), Some(1.0)]
+        );
+
+        let input = values(&[1.0, 3.0, 2.0, 4.0]);
+        assert_eq!(
+            evaluate(Algorithm::Ker, &input, 2, -1, ""),
+            vec![None, None, Some(0.333333), Some(0.333333)]
+        );
+
+        let input = values(&[1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(
+            evaluate(Algorithm::Kama, &input, 2, -1, ""),
+            vec![None, None, Some(2.444444), Some(3.135802)]
+        );
+    }
+
+    #[test]
+    fn test_chande_momentum_oscillator() {
+        let input = values(&[1.0, 2.0, 1.0, 3.0, 4.0]);
+        assert_eq!(
+            evaluate(Algorithm::Cmo, &input, 2, -1, "none"),
+            vec![None, Some(100.0), Some(0.0), Some(33.333333), Some(100.0)]
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use data_types::{NamespaceName, NamespaceSchema};
+use data_types::{DeletePredicate, NamespaceId, NamespaceName, NamespaceSchema};
 use iox_time::{SystemProvider, TimeProvider};
 use metric::{DurationHistogram, Metric};
 use std::sync::Arc;
@@ -18,6 +18,9 @@ pub struct InstrumentationDecorator<T, P = SystemProvider> {
 
     write_success: DurationHistogram,
     write_error: DurationHistogram,
+
+    delete_success: DurationHistogram,
+    delete_error: DurationHistogram,
 }
 
 impl<T> InstrumentationDecorator<T> {
@@ -30,12 +33,22 @@ impl<T> InstrumentationDecorator<T> {
         let write_success = write.recorder(&[("handler", name), ("result", "success")]);
         let write_error = write.recorder(&[("handler", name), ("result", "error")]);
 
+        let delete: Metric<DurationHistogram> = registry.register_metric(
+            "dml_handler_delete_duration",
+            "delete handler call duration",
+        );
+
+        let delete_success = delete.recorder(&[("handler", name), ("result", "success")]);
+        let delete_error = delete.recorder(&[("handler", name), ("result", "error")]);
+
         Self {
             name,
             inner,
             time_provider: Default::default(),
             write_success,
             write_error,
+            delete_success,
+            delete_error,
         }
     }
 }
@@ -48,6 +61,7 @@ where
     type WriteInput = T::WriteInput;
     type WriteError = T::WriteError;
     type WriteOutput = T::WriteOutput;
+    type DeleteError = T::DeleteError;
 
     /// Call the inner `write` method and record the call latency.
     async fn write(
@@ -85,6 +99,44 @@ where
 
         res
     }
+
+    /// Call the inner `delete` method and record the call latency.
+    async fn delete(
+        &self,
+        namespace: &NamespaceName<'static>,
+        namespace_id: NamespaceId,
+        table_name: &str,
+        predicate: &DeletePredicate,
+        span_ctx: Option<SpanContext>,
+    ) -> Result<(), Self::DeleteError> {
+        let t = self.time_provider.now();
+
+        // Create a tracing span for this handler.
+        let mut span_recorder =
+            SpanRecorder::new(span_ctx.clone().map(|parent| parent.child(self.name)));
+
+        let res = self
+            .inner
+            .delete(namespace, namespace_id, table_name, predicate, span_ctx)
+            .await;
+
+        // Avoid exploding if time goes backwards - simply drop the measurement
+        // if it happens.
+        if let Some(delta) = self.time_provider.now().checked_duration_since(t) {
+            match &res {
+                Ok(_) => {
+                    span_recorder.ok("success");
+                    self.delete_success.record(delta)
+                }
+                Err(e) => {
+                    span_recorder.error(e.to_string());
+                    self.delete_error.record(delta)
+                }
+            };
+        }
+
+        res
+    }
 }
 
 #[cfg(test)]
@@ -92,7 +144,7 @@ mod tests {
     use std::sync::Arc;
 
     use assert_matches::assert_matches;
-    use data_types::NamespaceId;
+    use data_types::TimestampRange;
     use metric::Attributes;
     use trace::{span::SpanStatus, RingBufferTraceCollector, TraceCollector};
 
@@ -108,6 +160,7 @@ mod tests {
             tables: Default::default(),
             max_columns_per_table: 500,
             max_tables: 200,
+            query_limits: Default::default(),
             retention_period_ns: None,
             partition_template: None,
         })
@@ -194,4 +247,59 @@ mod tests {
         assert_metric_hit(&metrics, "dml_handler_write_duration", "error");
         assert_trace(traces, SpanStatus::Err);
     }
+
+    #[tokio::test]
+    async fn test_delete_ok() {
+        let ns = "platanos".try_into().unwrap();
+        let handler = Arc::new(MockDmlHandler::<()>::default().with_delete_return([Ok(())]));
+
+        let metrics = Arc::new(metric::Registry::default());
+        let traces: Arc<dyn TraceCollector> = Arc::new(RingBufferTraceCollector::new(5));
+        let span = SpanContext::new(Arc::clone(&traces));
+
+        let decorator = InstrumentationDecorator::new(HANDLER_NAME, &metrics, handler);
+
+        let pred = DeletePredicate {
+            range: TimestampRange::new(1, 2),
+            exprs: vec![],
+        };
+
+        decorator
+            .delete(&ns, NamespaceId::new(42), "a table", &pred, Some(span))
+            .await
+            .expect("inner handler configured to succeed");
+
+        assert_metric_hit(&metrics, "dml_handler_delete_duration", "success");
+        assert_trace(traces, SpanStatus::Ok);
+    }
+
+    #[tokio::test]
+    async fn test_delete_err() {
+        let ns = "platanos".try_into().unwrap();
+        let handler = Arc::new(
+            MockDmlHandler::<()>::default()
+                .with_delete_return([Err(DmlError::NamespaceNotFound("nope".to_owned()))]),
+        );
+
+        let metrics = Arc::new(metric::Registry::default());
+        let traces: Arc<dyn TraceCollector> = Arc::new(RingBufferTraceCollector::new(5));
+        let span = SpanContext::new(Arc::clone(&traces));
+
+        let decorator = InstrumentationDecorator::new(HANDLER_NAME, &metrics, handler);
+
+        let pred = DeletePredicate {
+            range: TimestampRange::new(1, 2),
+            exprs: vec![],
+        };
+
+        let err = decorator
+            .delete(&ns, NamespaceId::new(42), "a table", &pred, Some(span))
+            .await
+            .expect_err("inner handler configured to fail");
+
+        assert_matches!(err, DmlError::NamespaceNotFound(_));
+
+        assert_metric_hit(&metrics, "dml_handler_delete_duration", "error");
+        assert_trace(traces, SpanStatus::Err);
+    }
 }
@@ -39,6 +39,10 @@
 //! to the catalog and populates the [`NamespaceCache`], converging it to match
 //! the set of [`NamespaceSchema`] in the global catalog.
 //!
+//! Deletes are recorded as tombstones in the catalog by the
+//! [`TombstoneRecorder`] (a NOP layer for writes), from where they are applied
+//! by the queriers and compactors.
+//!
 //! [`NamespaceCache`]: crate::namespace_cache::NamespaceCache
 //! [`NamespaceSchema`]: data_types::NamespaceSchema
 
@@ -53,6 +57,9 @@ pub mod nop;
 mod retention_validation;
 pub use retention_validation::*;
 
+mod tombstone;
+pub use tombstone::*;
+
 mod partitioner;
 pub use partitioner::*;
 
@@ -3,7 +3,7 @@
 use std::{fmt::Debug, marker::PhantomData, sync::Arc};
 
 use async_trait::async_trait;
-use data_types::{NamespaceName, NamespaceSchema};
+use data_types::{DeletePredicate, NamespaceId, NamespaceName, NamespaceSchema};
 use observability_deps::tracing::*;
 use trace::ctx::SpanContext;
 
@@ -25,6 +25,7 @@ where
     T: Debug + Send + Sync,
 {
     type WriteError = DmlError;
+    type DeleteError = DmlError;
     type WriteInput = T;
     type WriteOutput = T;
 
@@ -38,4 +39,16 @@ where
         info!(%namespace, %namespace_schema.id, ?batches, "dropping write operation");
         Ok(batches)
     }
+
+    async fn delete(
+        &self,
+        namespace: &NamespaceName<'static>,
+        namespace_id: NamespaceId,
+        table_name: &str,
+        predicate: &DeletePredicate,
+        _span_ctx: Option<SpanContext>,
+    ) -> Result<(), Self::DeleteError> {
+        info!(%namespace, %namespace_id, %table_name, ?predicate, "dropping delete operation");
+        Ok(())
+    }
 }
@@ -1,7 +1,7 @@
 use async_trait::async_trait;
 use data_types::{
-    DefaultPartitionTemplate, NamespaceName, NamespaceSchema, PartitionKey, PartitionTemplate,
-    TableId, TablePartitionTemplateOverride,
+    DefaultPartitionTemplate, DeletePredicate, NamespaceId, NamespaceName, NamespaceSchema,
+    PartitionKey, PartitionTemplate, TableId, TablePartitionTemplateOverride,
 };
 use hashbrown::HashMap;
 use mutable_batch::{MutableBatch, PartitionWrite, WritePayload};
@@ -68,6 +68,7 @@ impl Partitioner {
 #[async_trait]
 impl DmlHandler for Partitioner {
     type WriteError = PartitionError;
+    type DeleteError = PartitionError;
 
     type WriteInput = HashMap<
         TableId,
@@ -103,7 +104,7 @@ impl DmlHandler for Partitioner {
             );
 
             for (partition_key, partition_payload) in
-                PartitionWrite::partition(&batch, partition_template)
+                PartitionWrite::partition(&batch, partition_template)?
             {
                 let partition = partitions.entry(partition_key).or_default();
                 let table_batch = partition
@@ -122,6 +123,18 @@ impl DmlHandler for Partitioner {
             .map(|(key, batch)| Partitioned::new(key, batch))
             .collect::<Vec<_>>())
     }
+
+    /// Pass the delete request through unmodified to the next handler.
+    async fn delete(
+        &self,
+        _namespace: &NamespaceName<'static>,
+        _namespace_id: NamespaceId,
+        _table_name: &str,
+        _predicate: &DeletePredicate,
+        _span_ctx: Option<SpanContext>,
+    ) -> Result<(), Self::DeleteError> {
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -163,6 +176,7 @@ mod tests {
             tables: Default::default(),
             max_columns_per_table: 500,
             max_tables: 200,
+            query_limits: Default::default(),
             retention_period_ns: None,
             partition_template,
         })
@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use data_types::{NamespaceName, NamespaceSchema};
+use data_types::{DeletePredicate, NamespaceId, NamespaceName, NamespaceSchema};
 use hashbrown::HashMap;
 use iox_time::{SystemProvider, TimeProvider};
 use mutable_batch::MutableBatch;
@@ -39,6 +39,7 @@ impl RetentionValidator {
 #[async_trait]
 impl DmlHandler for RetentionValidator {
     type WriteError = RetentionError;
+    type DeleteError = RetentionError;
 
     type WriteInput = HashMap<String, MutableBatch>;
     type WriteOutput = Self::WriteInput;
@@ -66,6 +67,18 @@ impl DmlHandler for RetentionValidator {
 
         Ok(batch)
     }
+
+    /// Deletes are not subject to the retention period.
+    async fn delete(
+        &self,
+        _namespace: &NamespaceName<'static>,
+        _namespace_id: NamespaceId,
+        _table_name: &str,
+        _predicate: &DeletePredicate,
+        _span_ctx: Option<SpanContext>,
+    ) -> Result<(), Self::DeleteError> {
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -17,13 +17,19 @@ use self::{
 
 use super::{DmlHandler, Partitioned};
 use async_trait::async_trait;
-use data_types::{NamespaceName, NamespaceSchema, TableId};
+use data_types::{
+    DeletePredicate, NamespaceId, NamespaceName, NamespaceSchema, PartitionKey, TableId,
+};
 use dml::{DmlMeta, DmlWrite};
-use generated_types::influxdata::iox::ingester::v1::WriteRequest;
+use generated_types::influxdata::iox::{
+    delete::v1::{DeletePayload, DeleteRequest},
+    ingester::v1::WriteRequest,
+};
 use hashbrown::HashMap;
 use mutable_batch::MutableBatch;
 use mutable_batch_pb::encode::encode_write;
 use observability_deps::tracing::*;
+use sharder::JumpHash;
 use std::{fmt::Debug, num::NonZeroUsize, sync::Arc, time::Duration};
 use thiserror::Error;
 use trace::ctx::SpanContext;
@@ -76,6 +82,21 @@ pub enum RpcWriteError {
 /// distributed approximately uniformly across all downstream Ingesters. There
 /// is no effort made to enforce or attempt data locality.
 ///
+/// # Partition Affinity
+///
+/// If configured with [`RpcWrite::with_partition_affinity()`], the data for a
+/// given (namespace, partition key) is consistently sent to the same preferred
+/// Ingester, concentrating each partition's data in fewer Ingesters so that
+/// larger files are persisted.
+///
+/// All the tables in a write are sent to the preferred Ingester of the write's
+/// (namespace, partition key) in a single request, rather than splitting the
+/// write per table - a split write that partially failed would duplicate the
+/// data of the successful requests when retried by the client. If the
+/// preferred Ingester is unhealthy (its circuit breaker is open) the request
+/// fails over to the next Ingester in the configured order, and any replicas
+/// are sent to the Ingesters following the preferred Ingester.
+///
 /// # Replication
 ///
 /// If replication is configured, the total number of upstream ingesters
@@ -88,8 +109,18 @@ pub enum RpcWriteError {
 ///
 /// # Deletes
 ///
-/// This handler drops delete requests, logging the attempt and returning an
-/// error to the client.
+/// Deletes are sent to every configured upstream ingester (regardless of its
+/// health) as any of them may buffer data matching the delete, and the delete
+/// is only successful once all of them have acknowledged it. A failed delete may
+/// have been applied by some of the ingesters, but as applying a delete again
+/// has no further effect, it can be safely retried.
+///
+/// This handler MUST run before the [`TombstoneRecorder`] durably records the
+/// delete in the catalog: a tombstone is only applied to the files persisted
+/// before it was created, so the ingesters must have removed the deleted data
+/// from their buffers by then.
+///
+/// [`TombstoneRecorder`]: super::TombstoneRecorder
 ///
 /// [gRPC write service]: client::WriteClient
 #[derive(Debug)]
@@ -108,6 +139,10 @@ pub struct RpcWrite<T, C = CircuitBreaker> {
     /// may NACK a write, having already buffered the data. When this request is
     /// retried, the data will be duplicated.
     n_copies: usize,
+
+    /// If [`Some`], the mapping of (namespace, partition key) to the index of
+    /// the preferred upstream in `endpoints`.
+    partition_affinity: Option<JumpHash<usize>>,
 }
 
 impl<T> RpcWrite<T> {
@@ -155,44 +190,48 @@ impl<T> RpcWrite<T> {
         Self {
             endpoints,
             n_copies,
+            partition_affinity: None,
+        }
+    }
+
+    /// Route the writes for each (namespace, partition key) to a consistent
+    /// preferred upstream, failing over to the next upstream in the
+    /// configured order when the preferred upstream is unhealthy.
+    ///
+    /// See the [`RpcWrite`] docs for details.
+    pub fn with_partition_affinity(self) -> Self
+    where
+        T: Send + Sync + Debug + 'static,
+    {
+        Self {
+            partition_affinity: Some(JumpHash::new(0..self.endpoints.len())),
+            ..self
         }
     }
 }
 
-#[async_trait]
-impl<T, C> DmlHandler for RpcWrite<T, C>
+impl<T, C> RpcWrite<T, C>
 where
     T: WriteClient + 'static,
     C: CircuitBreakerState + 'static,
 {
-    type WriteInput = Partitioned<HashMap<TableId, (String, MutableBatch)>>;
-    type WriteOutput = Vec<DmlMeta>;
-
-    type WriteError = RpcWriteError;
-
-    async fn write(
+    /// Write the desired number of copies of `writes` to the upstreams in
+    /// `snap`, returning the [`DmlMeta`] of the dispatched write.
+    async fn write_tables(
         &self,
         namespace: &NamespaceName<'static>,
-        namespace_schema: Arc<NamespaceSchema>,
-        writes: Self::WriteInput,
+        namespace_id: NamespaceId,
+        partition_key: &PartitionKey,
+        writes: HashMap<TableId, MutableBatch>,
+        snap: Option<UpstreamSnapshot<'_, CircuitBreakingClient<T, C>>>,
         span_ctx: Option<SpanContext>,
-    ) -> Result<Self::WriteOutput, RpcWriteError> {
-        let namespace_id = namespace_schema.id;
-        // Extract the partition key & DML writes.
-        let (partition_key, writes) = writes.into_parts();
-
-        // Drop the table names from the value tuple.
-        let writes = writes
-            .into_iter()
-            .map(|(id, (_name, data))| (id, data))
-            .collect();
-
+    ) -> Result<DmlMeta, RpcWriteError> {
         // Build the DmlWrite
         let op = DmlWrite::new(
             namespace_id,
             writes,
             partition_key.clone(),
-            DmlMeta::unsequenced(span_ctx.clone()),
+            DmlMeta::unsequenced(span_ctx),
         );
 
         // Serialise this write into the wire format.
@@ -200,12 +239,9 @@ where
             payload: Some(encode_write(namespace_id.get(), &op)),
         };
 
-        // Obtain a snapshot of currently-healthy upstreams (and potentially
-        // some that need probing)
-        let mut snap = self
-            .endpoints
-            .endpoints()
-            .ok_or(RpcWriteError::NoUpstreams)?;
+        // The snapshot of currently-healthy upstreams (and potentially some
+        // that need probing)
+        let mut snap = snap.ok_or(RpcWriteError::NoUpstreams)?;
 
         // Validate the required number of writes is possible given the current
         // number of healthy endpoints.
@@ -243,8 +279,13 @@ where
                 }
             })?;
             // Remove the upstream that was successfully wrote to from the
-            // candidates
-            snap.remove_last_unstable();
+            // candidates, preserving the order of the remaining upstreams when
+            // routing with partition affinity so replicas are sent to the
+            // upstreams following the preferred upstream.
+            match self.partition_affinity {
+                Some(_) => snap.remove_last(),
+                None => snap.remove_last_unstable(),
+            }
         }
 
         debug!(
@@ -256,7 +297,101 @@ where
             "dispatched write to ingester"
         );
 
-        Ok(vec![op.meta().clone()])
+        Ok(op.meta().clone())
+    }
+}
+
+#[async_trait]
+impl<T, C> DmlHandler for RpcWrite<T, C>
+where
+    T: WriteClient + 'static,
+    C: CircuitBreakerState + 'static,
+{
+    type WriteInput = Partitioned<HashMap<TableId, (String, MutableBatch)>>;
+    type WriteOutput = Vec<DmlMeta>;
+
+    type WriteError = RpcWriteError;
+    type DeleteError = RpcWriteError;
+
+    async fn write(
+        &self,
+        namespace: &NamespaceName<'static>,
+        namespace_schema: Arc<NamespaceSchema>,
+        writes: Self::WriteInput,
+        span_ctx: Option<SpanContext>,
+    ) -> Result<Self::WriteOutput, RpcWriteError> {
+        let namespace_id = namespace_schema.id;
+        // Extract the partition key & DML writes.
+        let (partition_key, writes) = writes.into_parts();
+
+        // Drop the table names from the value tuple.
+        let writes = writes
+            .into_iter()
+            .map(|(id, (_name, data))| (id, data))
+            .collect();
+
+        // Obtain a snapshot of currently-healthy upstreams (and potentially
+        // some that need probing), starting with the preferred upstream of
+        // this partition if routing with partition affinity.
+        //
+        // The affinity key omits the table so that all the tables of this
+        // write remain a single request to a single ingester.
+        let snap = match &self.partition_affinity {
+            Some(affinity) => self
+                .endpoints
+                .endpoints_from(*affinity.hash((namespace.as_str(), &partition_key))),
+            None => self.endpoints.endpoints(),
+        };
+
+        let meta = self
+            .write_tables(
+                namespace,
+                namespace_id,
+                &partition_key,
+                writes,
+                snap,
+                span_ctx,
+            )
+            .await?;
+
+        Ok(vec![meta])
+    }
+
+    async fn delete(
+        &self,
+        namespace: &NamespaceName<'static>,
+        namespace_id: NamespaceId,
+        table_name: &str,
+        predicate: &DeletePredicate,
+        _span_ctx: Option<SpanContext>,
+    ) -> Result<(), RpcWriteError> {
+        let req = DeleteRequest {
+            payload: Some(DeletePayload {
+                database_id: namespace_id.get(),
+                table_name: table_name.to_string(),
+                predicate: Some(predicate.clone().into()),
+            }),
+        };
+
+        futures::future::try_join_all(self.endpoints.all().map(|client| {
+            let req = req.clone();
+            async move {
+                tokio::time::timeout(RPC_TIMEOUT, client.delete(req))
+                    .await
+                    .map_err(RpcWriteError::Timeout)?
+                    .map_err(RpcWriteError::Client)
+            }
+        }))
+        .await?;
+
+        debug!(
+            %namespace,
+            %namespace_id,
+            %table_name,
+            "dispatched delete to ingesters"
+        );
+
+        Ok(())
     }
 }
 
@@ -353,6 +488,7 @@ mod tests {
             tables: Default::default(),
             max_columns_per_table: 500,
             max_tables: 200,
+            query_limits: Default::default(),
             retention_period_ns: None,
             partition_template: None,
         })
@@ -371,6 +507,7 @@ mod tests {
         let handler = RpcWrite {
             endpoints: Balancer::new(endpoints, None),
             n_copies,
+            partition_affinity: None,
         };
 
         assert!(
@@ -871,4 +1008,240 @@ mod tests {
             .chain(client_3.calls().iter())
             .all(|v| *v == calls_1[0]));
     }
+
+    /// With partition affinity, all the tables of a write are sent in a single
+    /// request, consistently to the same upstream for each partition.
+    #[tokio::test]
+    async fn test_write_partition_affinity() {
+        let batches = lp_to_writes(
+            "\
+                bananas,tag1=A,tag2=B val=42i 1\n\
+                platanos,tag1=A,tag2=B value=42i 2\n\
+                another,tag1=A,tag2=B value=42i 3\n\
+                table,tag1=A,tag2=B val=42i 1\n\
+                cpu,tag1=A,tag2=B val=42i 1\n\
+                mem,tag1=A,tag2=B val=42i 1\n\
+            ",
+        );
+
+        const N_UPSTREAMS: usize = 3;
+
+        let clients = (0..N_UPSTREAMS)
+            .map(|_| Arc::new(MockWriteClient::default()))
+            .collect::<Vec<_>>();
+        let handler = RpcWrite::new(
+            clients.iter().map(|c| (Arc::clone(c), "mock client")),
+            None,
+            &metric::Registry::default(),
+        )
+        .with_partition_affinity();
+
+        let affinity = JumpHash::new(0..N_UPSTREAMS);
+        let partition_keys = (1..=9)
+            .map(|day| PartitionKey::from(format!("2022-01-0{day}")))
+            .collect::<Vec<_>>();
+
+        // Write the same tables twice to each partition.
+        for partition_key in partition_keys.iter().chain(&partition_keys) {
+            let input = Partitioned::new(partition_key.clone(), batches.clone());
+            let got = handler
+                .write(
+                    &NamespaceName::new(NAMESPACE_NAME).unwrap(),
+                    new_empty_namespace_schema(),
+                    input,
+                    None,
+                )
+                .await
+                .expect("write should succeed");
+            assert_eq!(got.len(), 1);
+        }
+
+        // Each write was sent, with all its tables, in a single request to
+        // the preferred upstream of its partition.
+        let want_tables = batches
+            .into_keys()
+            .map(|id| id.get())
+            .collect::<HashSet<_>>();
+        for (i, client) in clients.iter().enumerate() {
+            let want_keys = partition_keys
+                .iter()
+                .chain(&partition_keys)
+                .filter(|k| *affinity.hash((NAMESPACE_NAME, *k)) == i)
+                .map(|k| k.to_string())
+                .collect::<Vec<_>>();
+
+            let calls = client.calls();
+            assert_eq!(calls.len(), want_keys.len());
+            for (call, want_key) in calls.into_iter().zip(want_keys) {
+                let payload = assert_matches!(call.payload, Some(p) => p);
+                assert_eq!(payload.partition_key, want_key);
+                let tables = payload
+                    .table_batches
+                    .into_iter()
+                    .map(|t| t.table_id)
+                    .collect::<HashSet<_>>();
+                assert_eq!(tables, want_tables);
+            }
+        }
+    }
+
+    /// With partition affinity, a write (and its replicas) are sent to the
+    /// preferred upstream and the upstreams following it, skipping unhealthy
+    /// upstreams.
+    #[tokio::test]
+    async fn test_write_partition_affinity_failover() {
+        const N_UPSTREAMS: usize = 4;
+
+        let affinity = JumpHash::new(0..N_UPSTREAMS);
+        let partition_key = PartitionKey::from("2022-01-01");
+        let preferred = *affinity.hash((NAMESPACE_NAME, &partition_key));
+
+        for preferred_healthy in [true, false] {
+            let clients = (0..N_UPSTREAMS)
+                .map(|_| Arc::new(MockWriteClient::default()))
+                .collect::<Vec<_>>();
+            let circuits = (0..N_UPSTREAMS)
+                .map(|i| {
+                    let c = Arc::new(MockCircuitBreaker::default());
+                    c.set_healthy(i != preferred || preferred_healthy);
+                    c.set_should_probe(false);
+                    c
+                })
+                .collect::<Vec<_>>();
+
+            let handler = RpcWrite {
+                endpoints: Balancer::new(
+                    clients.iter().zip(&circuits).map(|(client, circuit)| {
+                        CircuitBreakingClient::new(Arc::clone(client), "client")
+                            .with_circuit_breaker(Arc::clone(circuit))
+                    }),
+                    None,
+                ),
+                n_copies: 2,
+                partition_affinity: Some(JumpHash::new(0..N_UPSTREAMS)),
+            };
+
+            let input = Partitioned::new(
+                partition_key.clone(),
+                lp_to_writes("bananas,tag1=A,tag2=B val=42i 1"),
+            );
+            let got = handler
+                .write(
+                    &NamespaceName::new(NAMESPACE_NAME).unwrap(),
+                    new_empty_namespace_schema(),
+                    input,
+                    None,
+                )
+                .await;
+            assert_matches!(got, Ok(v) if v.len() == 1);
+
+            // The write was sent to the first two healthy upstreams, in order,
+            // starting from the preferred upstream.
+            let skip = usize::from(!preferred_healthy);
+            let want = (0..N_UPSTREAMS)
+                .map(|i| (preferred + skip + i) % N_UPSTREAMS)
+                .take(2)
+                .collect::<HashSet<_>>();
+            for (i, client) in clients.iter().enumerate() {
+                assert_eq!(
+                    client.calls().len(),
+                    usize::from(want.contains(&i)),
+                    "unexpected calls for upstream {i} (preferred {preferred}, \
+                    healthy {preferred_healthy})"
+                );
+            }
+        }
+    }
+
+    /// Deletes are sent to all upstreams, including those that are unhealthy.
+    #[tokio::test]
+    async fn test_delete() {
+        let clients = (0..3)
+            .map(|_| Arc::new(MockWriteClient::default()))
+            .collect::<Vec<_>>();
+        let circuits = (0..3)
+            .map(|i| {
+                let c = Arc::new(MockCircuitBreaker::default());
+                c.set_healthy(i != 1);
+                c.set_should_probe(false);
+                c
+            })
+            .collect::<Vec<_>>();
+
+        let handler = RpcWrite {
+            endpoints: Balancer::new(
+                clients.iter().zip(&circuits).map(|(client, circuit)| {
+                    CircuitBreakingClient::new(Arc::clone(client), "client")
+                        .with_circuit_breaker(Arc::clone(circuit))
+                }),
+                None,
+            ),
+            n_copies: 1,
+            partition_affinity: None,
+        };
+
+        let predicate = DeletePredicate {
+            range: data_types::TimestampRange::new(1, 2),
+            exprs: vec![],
+        };
+
+        handler
+            .delete(
+                &NamespaceName::new(NAMESPACE_NAME).unwrap(),
+                NAMESPACE_ID,
+                "bananas",
+                &predicate,
+                None,
+            )
+            .await
+            .expect("delete should succeed");
+
+        for client in &clients {
+            assert!(client.calls().is_empty());
+            assert_matches!(client.delete_calls().as_slice(), [req] => {
+                let payload = req.payload.as_ref().unwrap();
+                assert_eq!(payload.database_id, NAMESPACE_ID.get());
+                assert_eq!(payload.table_name, "bananas");
+                assert_eq!(payload.predicate, Some(predicate.clone().into()));
+            });
+        }
+    }
+
+    /// A delete that is not acknowledged by every upstream is an error.
+    #[tokio::test]
+    async fn test_delete_upstream_error() {
+        let client_1 = Arc::new(MockWriteClient::default());
+        let client_2 = Arc::new(MockWriteClient::default().with_ret(iter::once(Err(
+            RpcWriteClientError::Upstream(tonic::Status::internal("bananas")),
+        ))));
+
+        let handler = RpcWrite {
+            endpoints: Balancer::new(
+                [
+                    CircuitBreakingClient::new(Arc::clone(&client_1), "client_1"),
+                    CircuitBreakingClient::new(Arc::clone(&client_2), "client_2"),
+                ],
+                None,
+            ),
+            n_copies: 1,
+            partition_affinity: None,
+        };
+
+        let got = handler
+            .delete(
+                &NamespaceName::new(NAMESPACE_NAME).unwrap(),
+                NAMESPACE_ID,
+                "bananas",
+                &DeletePredicate {
+                    range: data_types::TimestampRange::new(1, 2),
+                    exprs: vec![],
+                },
+                None,
+            )
+            .await;
+
+        assert_matches!(got, Err(RpcWriteError::Client(_)));
+        assert_eq!(client_1.delete_calls().len(), 1);
+        assert_eq!(client_2.delete_calls().len(), 1);
+    }
 }
@@ -66,6 +66,12 @@ where
         self.endpoints.len()
     }
 
+    /// Return all the configured [`CircuitBreakingClient`], regardless of their
+    /// health.
+    pub(super) fn all(&self) -> impl Iterator<Item = &CircuitBreakingClient<T, C>> {
+        self.endpoints.iter()
+    }
+
     /// Return an (infinite) iterator of healthy [`CircuitBreakingClient`], and
     /// at most one client needing a health probe.
     ///
@@ -122,6 +128,45 @@ where
 
         UpstreamSnapshot::new(probe.into_iter().chain(healthy), idx)
     }
+
+    /// Return an (infinite) iterator of healthy [`CircuitBreakingClient`] in a
+    /// stable order, starting from the 0-indexed `idx`-th configured endpoint
+    /// (modulo wrapping) and followed by each subsequent endpoint in turn.
+    ///
+    /// This provides affinity between a caller and a preferred upstream: the
+    /// same `idx` always yields the same order of endpoints, and an unhealthy
+    /// endpoint is skipped in favour of the next endpoint in the order.
+    ///
+    /// At most one unhealthy client needing a health probe is included, at its
+    /// position in the stable order (rather than first, as in
+    /// [`Balancer::endpoints()`]).
+    pub(super) fn endpoints_from(
+        &self,
+        idx: usize,
+    ) -> Option<UpstreamSnapshot<'_, CircuitBreakingClient<T, C>>> {
+        let n = self.endpoints.len();
+
+        let mut probe = false;
+        let candidates = self
+            .endpoints
+            .iter()
+            .cycle()
+            .skip(idx % max(n, 1))
+            .take(n)
+            .filter(|e| {
+                if e.is_healthy() {
+                    return true;
+                }
+                // As above, at most one node needing a probe is yielded.
+                if !probe && e.should_probe() {
+                    probe = true;
+                    return true;
+                }
+                false
+            });
+
+        UpstreamSnapshot::new(candidates, 0)
+    }
 }
 
 /// Initialise the health metric exported by the RPC balancer, and return the
@@ -375,6 +420,61 @@ mod tests {
         assert_eq!(circuit_err_2.err_count(), 0);
     }
 
+    /// Endpoints are yielded in a stable order starting from the requested
+    /// index, skipping unhealthy endpoints.
+    #[tokio::test]
+    async fn test_balancer_endpoints_from() {
+        let circuits = (0..4)
+            .map(|_| Arc::new(MockCircuitBreaker::default()))
+            .collect::<Vec<_>>();
+        circuits.iter().for_each(|c| c.set_healthy(true));
+
+        let balancer = Balancer::new(
+            circuits.iter().enumerate().map(|(i, c)| {
+                CircuitBreakingClient::new(Arc::new(MockWriteClient::default()), i.to_string())
+                    .with_circuit_breaker(Arc::clone(c))
+            }),
+            None,
+        );
+
+        let names = |idx: usize, n: usize| {
+            balancer
+                .endpoints_from(idx)
+                .map(|snap| {
+                    snap.take(n)
+                        .map(|e| e.endpoint_name().to_string())
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        };
+
+        // The order is stable across calls, and wraps around.
+        assert_eq!(names(1, 5), ["1", "2", "3", "0", "1"]);
+        assert_eq!(names(1, 5), ["1", "2", "3", "0", "1"]);
+        assert_eq!(names(3, 2), ["3", "0"]);
+        assert_eq!(names(6, 2), ["2", "3"]);
+
+        // An unhealthy endpoint is skipped in favour of the next endpoint.
+        circuits[1].set_healthy(false);
+        circuits[1].set_should_probe(false);
+        assert_eq!(names(1, 4), ["2", "3", "0", "2"]);
+
+        // At most one endpoint needing a probe is yielded, in its position in
+        // the order.
+        circuits[1].set_should_probe(true);
+        circuits[2].set_healthy(false);
+        circuits[2].set_should_probe(true);
+        assert_eq!(names(0, 3), ["0", "1", "3"]);
+        assert_eq!(names(2, 3), ["2", "3", "0"]);
+
+        // No healthy endpoints yields no snapshot.
+        circuits.iter().for_each(|c| {
+            c.set_healthy(false);
+            c.set_should_probe(false);
+        });
+        assert!(balancer.endpoints_from(0).is_none());
+    }
+
     /// An unhealthy node that recovers is yielded to the caller.
     #[tokio::test]
     async fn test_balancer_upstream_recovery() {
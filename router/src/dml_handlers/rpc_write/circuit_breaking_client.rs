@@ -1,7 +1,7 @@
 use std::{fmt::Debug, sync::Arc};
 
 use async_trait::async_trait;
-use generated_types::influxdata::iox::ingester::v1::WriteRequest;
+use generated_types::influxdata::iox::{delete::v1::DeleteRequest, ingester::v1::WriteRequest};
 
 use super::{
     circuit_breaker::CircuitBreaker,
@@ -103,6 +103,12 @@ where
         self.state.observe(&res);
         res
     }
+
+    async fn delete(&self, op: DeleteRequest) -> Result<(), RpcWriteClientError> {
+        let res = self.inner.delete(op).await;
+        self.state.observe(&res);
+        res
+    }
 }
 
 #[cfg(test)]
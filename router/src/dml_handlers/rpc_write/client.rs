@@ -1,9 +1,7 @@
 //! Abstraction over RPC client
 
 use async_trait::async_trait;
-use generated_types::influxdata::iox::ingester::v1::{
-    write_service_client::WriteServiceClient, WriteRequest,
-};
+use generated_types::influxdata::iox::{delete::v1::DeleteRequest, ingester::v1::WriteRequest};
 use thiserror::Error;
 
 /// Request errors returned by [`WriteClient`] implementations.
@@ -24,15 +22,10 @@ pub enum RpcWriteClientError {
 pub(super) trait WriteClient: Send + Sync + std::fmt::Debug {
     /// Write `op` and wait for a response.
     async fn write(&self, op: WriteRequest) -> Result<(), RpcWriteClientError>;
-}
 
-/// An implementation of [`WriteClient`] for the tonic gRPC client.
-#[async_trait]
-impl WriteClient for WriteServiceClient<tonic::transport::Channel> {
-    async fn write(&self, op: WriteRequest) -> Result<(), RpcWriteClientError> {
-        WriteServiceClient::write(&mut self.clone(), op).await?;
-        Ok(())
-    }
+    /// Apply the delete in `op` to the data buffered by the receiver, and wait
+    /// for a response.
+    async fn delete(&self, op: DeleteRequest) -> Result<(), RpcWriteClientError>;
 }
 
 /// Mocks for testing
@@ -43,6 +36,7 @@ pub mod mock {
 
     struct State {
         calls: Vec<WriteRequest>,
+        delete_calls: Vec<DeleteRequest>,
         ret: Box<dyn Iterator<Item = Result<(), RpcWriteClientError>> + Send + Sync>,
     }
 
@@ -65,6 +59,7 @@ pub mod mock {
             Self {
                 state: Mutex::new(State {
                     calls: Default::default(),
+                    delete_calls: Default::default(),
                     ret: Box::new(iter::repeat_with(|| Ok(()))),
                 }),
             }
@@ -77,8 +72,13 @@ pub mod mock {
             self.state.lock().calls.clone()
         }
 
+        /// Retrieve the delete requests that this mock received.
+        pub fn delete_calls(&self) -> Vec<DeleteRequest> {
+            self.state.lock().delete_calls.clone()
+        }
+
         /// Read values off of the provided iterator and return them for calls
-        /// to [`Self::write()`].
+        /// to [`Self::write()`] and [`Self::delete()`].
         #[cfg(test)]
         pub(crate) fn with_ret<T, U>(self, ret: T) -> Self
         where
@@ -97,5 +97,11 @@ pub mod mock {
             guard.calls.push(op);
             guard.ret.next().expect("no mock response")
         }
+
+        async fn delete(&self, op: DeleteRequest) -> Result<(), RpcWriteClientError> {
+            let mut guard = self.state.lock();
+            guard.delete_calls.push(op);
+            guard.ret.next().expect("no mock response")
+        }
     }
 }
@@ -9,8 +9,9 @@ use std::{
 };
 
 use async_trait::async_trait;
-use generated_types::influxdata::iox::ingester::v1::{
-    write_service_client::WriteServiceClient, WriteRequest,
+use generated_types::influxdata::iox::{
+    delete::v1::{delete_service_client::DeleteServiceClient, DeleteRequest},
+    ingester::v1::{write_service_client::WriteServiceClient, WriteRequest},
 };
 use observability_deps::tracing::*;
 use parking_lot::Mutex;
@@ -92,29 +93,29 @@ impl LazyConnector {
     }
 }
 
-#[async_trait]
-impl WriteClient for LazyConnector {
-    async fn write(&self, op: WriteRequest) -> Result<(), RpcWriteClientError> {
-        let conn = self.connection.lock().clone();
-        let conn = conn.ok_or_else(|| {
-            RpcWriteClientError::UpstreamNotConnected(self.addr.uri().to_string())
-        })?;
+impl LazyConnector {
+    /// Returns the established connection, or
+    /// [`RpcWriteClientError::UpstreamNotConnected`] if there is none.
+    fn connection(&self) -> Result<Channel, RpcWriteClientError> {
+        self.connection
+            .lock()
+            .clone()
+            .ok_or_else(|| RpcWriteClientError::UpstreamNotConnected(self.addr.uri().to_string()))
+    }
 
-        match WriteServiceClient::new(conn)
-            .max_encoding_message_size(self.max_outgoing_msg_bytes)
-            .max_decoding_message_size(MAX_INCOMING_MSG_BYTES)
-            .write(op)
-            .await
-        {
+    /// Record the outcome of a request to the upstream, driving a reconnection
+    /// after too many consecutive errors.
+    fn observe<T>(&self, res: Result<T, tonic::Status>) -> Result<(), RpcWriteClientError> {
+        match res.map_err(RpcWriteClientError::from) {
             Err(e) if is_envoy_unavailable_error(&e) => {
                 warn!(error=%e, "detected envoy proxy upstream network error translation, reconnecting");
                 self.consecutive_errors
                     .store(RECONNECT_ERROR_COUNT + 1, Ordering::Relaxed);
-                return Err(e);
+                Err(e)
             }
             Err(e) => {
                 self.consecutive_errors.fetch_add(1, Ordering::Relaxed);
-                return Err(e);
+                Err(e)
             }
             Ok(_) => {
                 self.consecutive_errors.store(0, Ordering::Relaxed);
@@ -124,6 +125,28 @@ impl WriteClient for LazyConnector {
     }
 }
 
+#[async_trait]
+impl WriteClient for LazyConnector {
+    async fn write(&self, op: WriteRequest) -> Result<(), RpcWriteClientError> {
+        let res = WriteServiceClient::new(self.connection()?)
+            .max_encoding_message_size(self.max_outgoing_msg_bytes)
+            .max_decoding_message_size(MAX_INCOMING_MSG_BYTES)
+            .write(op)
+            .await;
+
+        self.observe(res)
+    }
+
+    async fn delete(&self, op: DeleteRequest) -> Result<(), RpcWriteClientError> {
+        let res = DeleteServiceClient::new(self.connection()?)
+            .max_decoding_message_size(MAX_INCOMING_MSG_BYTES)
+            .delete(op)
+            .await;
+
+        self.observe(res)
+    }
+}
+
 /// Returns `true` if `e` is a gRPC error with the status [`Code::Unavailable`],
 /// and a metadata entry indicating the response was generated by an envoy proxy
 /// instance.
@@ -4,7 +4,9 @@ use smallvec::SmallVec;
 /// (modulo wrapping).
 ///
 /// The last yielded element can be removed from the iterator by calling
-/// [`UpstreamSnapshot::remove_last_unstable()`].
+/// [`UpstreamSnapshot::remove_last_unstable()`], or
+/// [`UpstreamSnapshot::remove_last()`] to preserve the order of the remaining
+/// elements.
 #[derive(Debug)]
 pub(super) struct UpstreamSnapshot<'a, C> {
     clients: SmallVec<[&'a C; 3]>,
@@ -49,10 +51,28 @@ impl<'a, C> UpstreamSnapshot<'a, C> {
         self.idx = self.idx.wrapping_sub(1);
     }
 
+    /// Remove the last yielded upstream from this snapshot, preserving the
+    /// relative order of the remaining elements.
+    ///
+    /// This is an `O(n)` operation, unlike
+    /// [`UpstreamSnapshot::remove_last_unstable()`].
+    ///
+    /// # Correctness
+    ///
+    /// If called before [`UpstreamSnapshot`] has yielded any elements, this MAY
+    /// remove an arbitrary element from the snapshot.
+    pub(super) fn remove_last(&mut self) {
+        let idx = self.idx();
+        self.clients.remove(idx);
+        // Try the element that followed the removed element next.
+        self.idx = idx.wrapping_sub(1);
+    }
+
     /// Returns the number of clients in this [`UpstreamSnapshot`].
     ///
     /// This value decreases as upstreams are removed by calls to
-    /// [`UpstreamSnapshot::remove_last_unstable()`].
+    /// [`UpstreamSnapshot::remove_last_unstable()`] or
+    /// [`UpstreamSnapshot::remove_last()`].
     pub(super) fn len(&self) -> usize {
         self.clients.len()
     }
@@ -212,6 +232,49 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_remove_element_stable() {
+        let elements = [1, 2, 3, 4];
+
+        // First element removed
+        {
+            let mut snap = UpstreamSnapshot::new(elements.iter(), 0)
+                .expect("non-empty element set should yield snapshot");
+            assert_eq!(snap.next(), Some(&1));
+            snap.remove_last();
+            assert_eq!(snap.next(), Some(&2));
+            assert_eq!(snap.next(), Some(&3));
+            assert_eq!(snap.next(), Some(&4));
+            assert_eq!(snap.next(), Some(&2));
+        }
+
+        // Middle element removed, starting part way through the set
+        {
+            let mut snap = UpstreamSnapshot::new(elements.iter(), 2)
+                .expect("non-empty element set should yield snapshot");
+            assert_eq!(snap.next(), Some(&3));
+            snap.remove_last();
+            assert_eq!(snap.next(), Some(&4));
+            snap.remove_last();
+            assert_eq!(snap.next(), Some(&1));
+            assert_eq!(snap.next(), Some(&2));
+            assert_eq!(snap.next(), Some(&1));
+            assert_eq!(snap.len(), 2);
+        }
+
+        // Last element removed
+        {
+            let mut snap = UpstreamSnapshot::new(elements.iter(), 3)
+                .expect("non-empty element set should yield snapshot");
+            assert_eq!(snap.next(), Some(&4));
+            snap.remove_last();
+            assert_eq!(snap.next(), Some(&1));
+            assert_eq!(snap.next(), Some(&2));
+            assert_eq!(snap.next(), Some(&3));
+            assert_eq!(snap.next(), Some(&1));
+        }
+    }
+
     #[test]
     fn test_remove_all_elements() {
         let elements = [42];
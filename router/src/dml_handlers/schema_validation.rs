@@ -1,7 +1,10 @@
 use std::{ops::DerefMut, sync::Arc};
 
 use async_trait::async_trait;
-use data_types::{NamespaceName, NamespaceSchema, TableId, TablePartitionTemplateOverride};
+use data_types::{
+    DeletePredicate, NamespaceId, NamespaceName, NamespaceSchema, TableId,
+    TablePartitionTemplateOverride,
+};
 use hashbrown::HashMap;
 use iox_catalog::{
     interface::{Catalog, Error as CatalogError},
@@ -65,6 +68,13 @@ pub enum SchemaError {
 /// relatively rare - it results in additional requests being made to the
 /// catalog until the cached schema converges to match the catalog schema.
 ///
+/// Soft-deleted tables and columns are not loaded into the cache. A write to a
+/// soft-deleted table creates a new table of the same name, while writes that
+/// reference a soft-deleted column are rejected when it is looked up in the
+/// catalog. A table or column deleted after it was cached continues to accept
+/// writes until the cached schema is replaced by the periodic
+/// [`SchemaCacheRefresher`](crate::namespace_cache::SchemaCacheRefresher).
+///
 /// Note that the namespace-wide limit of the number of columns allowed per table
 /// is also cached, which has two implications:
 ///
@@ -141,6 +151,7 @@ where
     C: NamespaceCache<ReadError = iox_catalog::interface::Error>, // The handler expects the cache to read from the catalog if necessary.
 {
     type WriteError = SchemaError;
+    type DeleteError = SchemaError;
 
     // Accepts a map of TableName -> MutableBatch
     type WriteInput = HashMap<String, MutableBatch>;
@@ -240,6 +251,18 @@ where
                     self.schema_conflict.inc(1);
                     SchemaError::Conflict(e)
                 }
+                // Writes to soft-deleted columns
+                CatalogError::ColumnDeleted { .. } => {
+                    warn!(
+                        %namespace,
+                        %namespace_id,
+                        table_name=%e.table(),
+                        error=%e,
+                        "write to soft-deleted column"
+                    );
+                    self.schema_conflict.inc(1);
+                    SchemaError::Conflict(e)
+                }
                 // Service limits
                 CatalogError::ColumnCreateLimitError { table_id, .. } => {
                     warn!(
@@ -307,6 +330,18 @@ where
 
         Ok(batches)
     }
+
+    /// No schema validation is performed on deletes.
+    async fn delete(
+        &self,
+        _namespace: &NamespaceName<'static>,
+        _namespace_id: NamespaceId,
+        _table_name: &str,
+        _predicate: &DeletePredicate,
+        _span_ctx: Option<SpanContext>,
+    ) -> Result<(), Self::DeleteError> {
+        Ok(())
+    }
 }
 
 /// An error returned by schema limit evaluation against a cached
@@ -858,6 +893,37 @@ mod tests {
         assert_eq!(1, handler.schema_conflict.fetch());
     }
 
+    #[tokio::test]
+    async fn test_write_soft_deleted_table() {
+        let (catalog, namespace) = test_setup().await;
+        let table = namespace.create_table("bananas").await;
+        catalog
+            .catalog()
+            .repositories()
+            .await
+            .tables()
+            .soft_delete(table.table.id)
+            .await
+            .expect("failed to soft delete table");
+
+        let metrics = Arc::new(metric::Registry::default());
+        let handler = SchemaValidator::new(catalog.catalog(), setup_test_cache(&catalog), &metrics);
+
+        // The write creates a new table in place of the deleted one.
+        let writes = lp_to_writes("bananas,tag1=A,tag2=B val=42i 123456");
+        let got = handler
+            .write(&NAMESPACE, namespace.schema().await.into(), writes, None)
+            .await
+            .expect("request should succeed");
+        let (new_id, _) = got.into_iter().next().unwrap();
+        assert_ne!(new_id, table.table.id);
+
+        assert_cache(&handler, "bananas", "val", ColumnType::I64).await;
+        let cached = handler.cache.get_schema(&NAMESPACE).await.unwrap();
+        assert_eq!(cached.tables.get("bananas").unwrap().id, new_id);
+        assert_eq!(0, handler.schema_conflict.fetch());
+    }
+
     #[tokio::test]
     async fn test_write_table_service_limit() {
         let (catalog, namespace) = test_setup().await;
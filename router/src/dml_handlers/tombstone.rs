@@ -0,0 +1,232 @@
+use std::{fmt::Debug, marker::PhantomData, ops::DerefMut, sync::Arc};
+
+use async_trait::async_trait;
+use data_types::{DeletePredicate, NamespaceId, NamespaceName, NamespaceSchema, Table, Timestamp};
+use iox_catalog::interface::{Catalog, RepoCollection};
+use observability_deps::tracing::*;
+use trace::ctx::SpanContext;
+
+use super::{DmlError, DmlHandler};
+
+/// A [`DmlHandler`] implementation that durably records delete requests as
+/// tombstones in the catalog.
+///
+/// A tombstone is recorded for the table named in the delete request, or for
+/// every table in the namespace if no table name is given. Deleting from a
+/// table that does not exist is a successful NOP.
+///
+/// Writes pass through unmodified.
+#[derive(Debug)]
+pub struct TombstoneRecorder<T> {
+    catalog: Arc<dyn Catalog>,
+    _input: PhantomData<T>,
+}
+
+impl<T> TombstoneRecorder<T> {
+    /// Initialise a new [`TombstoneRecorder`] that writes tombstones to
+    /// `catalog`.
+    pub fn new(catalog: Arc<dyn Catalog>) -> Self {
+        Self {
+            catalog,
+            _input: Default::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl<T> DmlHandler for TombstoneRecorder<T>
+where
+    T: Debug + Send + Sync,
+{
+    type WriteError = DmlError;
+    type DeleteError = DmlError;
+    type WriteInput = T;
+    type WriteOutput = T;
+
+    async fn write(
+        &self,
+        _namespace: &NamespaceName<'static>,
+        _namespace_schema: Arc<NamespaceSchema>,
+        input: Self::WriteInput,
+        _span_ctx: Option<SpanContext>,
+    ) -> Result<Self::WriteOutput, Self::WriteError> {
+        Ok(input)
+    }
+
+    /// Record a tombstone for each table `predicate` applies to.
+    async fn delete(
+        &self,
+        namespace: &NamespaceName<'static>,
+        namespace_id: NamespaceId,
+        table_name: &str,
+        predicate: &DeletePredicate,
+        _span_ctx: Option<SpanContext>,
+    ) -> Result<(), Self::DeleteError> {
+        let mut repos = self.catalog.repositories().await;
+
+        let tables = resolve_tables(repos.deref_mut(), namespace_id, table_name)
+            .await
+            .map_err(|e| DmlError::Internal(e.into()))?;
+
+        let serialized_predicate = predicate.expr_sql_string();
+        for table in tables {
+            let tombstone = repos
+                .tombstones()
+                .create(
+                    table.id,
+                    Timestamp::new(predicate.range.start()),
+                    Timestamp::new(predicate.range.end()),
+                    &serialized_predicate,
+                )
+                .await
+                .map_err(|e| DmlError::Internal(e.into()))?;
+
+            debug!(
+                %namespace,
+                %namespace_id,
+                table_name=%table.name,
+                tombstone_id=%tombstone.id,
+                predicate=%serialized_predicate,
+                "recorded tombstone"
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Return the tables named by `table_name`, or all the tables in the namespace
+/// if `table_name` is empty.
+async fn resolve_tables<R>(
+    repos: &mut R,
+    namespace_id: NamespaceId,
+    table_name: &str,
+) -> Result<Vec<Table>, iox_catalog::interface::Error>
+where
+    R: RepoCollection + ?Sized,
+{
+    if table_name.is_empty() {
+        return repos.tables().list_by_namespace_id(namespace_id).await;
+    }
+
+    Ok(repos
+        .tables()
+        .get_by_namespace_and_name(namespace_id, table_name)
+        .await?
+        .into_iter()
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use data_types::{DeleteExpr, Op, Scalar, TimestampRange};
+    use iox_tests::TestCatalog;
+    use once_cell::sync::Lazy;
+
+    use super::*;
+
+    static NAMESPACE: Lazy<NamespaceName<'static>> = Lazy::new(|| "bananas".try_into().unwrap());
+
+    fn predicate() -> DeletePredicate {
+        DeletePredicate {
+            range: TimestampRange::new(1, 2),
+            exprs: vec![DeleteExpr::new(
+                "tag1".to_string(),
+                Op::Eq,
+                Scalar::String("A".to_string()),
+            )],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delete_table() {
+        let catalog = TestCatalog::new();
+        let namespace = catalog.create_namespace_1hr_retention(&NAMESPACE).await;
+        let table = namespace.create_table("platanos").await;
+        let other = namespace.create_table("other").await;
+
+        let handler = TombstoneRecorder::<()>::new(catalog.catalog());
+        handler
+            .delete(
+                &NAMESPACE,
+                namespace.namespace.id,
+                "platanos",
+                &predicate(),
+                None,
+            )
+            .await
+            .expect("delete should succeed");
+
+        let mut repos = catalog.catalog().repositories().await;
+        let got = repos
+            .tombstones()
+            .list_by_table_id(table.table.id)
+            .await
+            .unwrap();
+        assert_matches!(got.as_slice(), [t] => {
+            assert_eq!(t.min_time, Timestamp::new(1));
+            assert_eq!(t.max_time, Timestamp::new(2));
+            assert_eq!(t.serialized_predicate, r#""tag1"='A'"#);
+        });
+
+        let got = repos
+            .tombstones()
+            .list_by_table_id(other.table.id)
+            .await
+            .unwrap();
+        assert!(got.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_delete_all_tables() {
+        let catalog = TestCatalog::new();
+        let namespace = catalog.create_namespace_1hr_retention(&NAMESPACE).await;
+        namespace.create_table("platanos").await;
+        namespace.create_table("other").await;
+
+        let handler = TombstoneRecorder::<()>::new(catalog.catalog());
+        handler
+            .delete(&NAMESPACE, namespace.namespace.id, "", &predicate(), None)
+            .await
+            .expect("delete should succeed");
+
+        let got = catalog
+            .catalog()
+            .repositories()
+            .await
+            .tombstones()
+            .list_by_namespace_id(namespace.namespace.id)
+            .await
+            .unwrap();
+        assert_eq!(got.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_delete_unknown_table() {
+        let catalog = TestCatalog::new();
+        let namespace = catalog.create_namespace_1hr_retention(&NAMESPACE).await;
+
+        let handler = TombstoneRecorder::<()>::new(catalog.catalog());
+        handler
+            .delete(
+                &NAMESPACE,
+                namespace.namespace.id,
+                "missing",
+                &predicate(),
+                None,
+            )
+            .await
+            .expect("delete should succeed");
+
+        let got = catalog
+            .catalog()
+            .repositories()
+            .await
+            .tombstones()
+            .list_by_namespace_id(namespace.namespace.id)
+            .await
+            .unwrap();
+        assert!(got.is_empty());
+    }
+}
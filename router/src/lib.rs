@@ -132,4 +132,5 @@
 pub mod dml_handlers;
 pub mod namespace_cache;
 pub mod namespace_resolver;
+pub mod self_monitoring;
 pub mod server;
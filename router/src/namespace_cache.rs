@@ -11,6 +11,9 @@ pub mod metrics;
 mod read_through_cache;
 pub use read_through_cache::*;
 
+mod refresh;
+pub use refresh::*;
+
 use std::{error::Error, fmt::Debug, sync::Arc};
 
 use async_trait::async_trait;
@@ -44,6 +47,18 @@ pub trait NamespaceCache: Debug + Send + Sync {
         namespace: NamespaceName<'static>,
         schema: NamespaceSchema,
     ) -> (Arc<NamespaceSchema>, ChangeStats);
+
+    /// Place `schema` in the cache, replacing the existing entry for
+    /// `namespace`, if any, which is returned.
+    ///
+    /// Unlike [`NamespaceCache::put_schema()`], any tables and columns missing
+    /// from `schema` (such as those soft-deleted since they were cached) are
+    /// removed from the cache.
+    fn replace_schema(
+        &self,
+        namespace: NamespaceName<'static>,
+        schema: NamespaceSchema,
+    ) -> Option<Arc<NamespaceSchema>>;
 }
 
 /// Change statistics describing how the cache entry was modified by the
@@ -72,6 +72,14 @@ impl NamespaceCache for Arc<MemoryNamespaceCache> {
         self.cache.write().insert(namespace, Arc::clone(&ret));
         (ret, change_stats)
     }
+
+    fn replace_schema(
+        &self,
+        namespace: NamespaceName<'static>,
+        schema: NamespaceSchema,
+    ) -> Option<Arc<NamespaceSchema>> {
+        self.cache.write().insert(namespace, Arc::new(schema))
+    }
 }
 
 /// Merges into `new_ns` any table or column schema which are
@@ -167,6 +175,7 @@ mod tests {
             tables: Default::default(),
             max_columns_per_table: 50,
             max_tables: 24,
+            query_limits: Default::default(),
             retention_period_ns: Some(876),
             partition_template: None,
         };
@@ -184,6 +193,7 @@ mod tests {
             tables: Default::default(),
             max_columns_per_table: 10,
             max_tables: 42,
+            query_limits: Default::default(),
             retention_period_ns: Some(876),
             partition_template: None,
         };
@@ -211,12 +221,14 @@ mod tests {
             table_id,
             name: String::from("brötchen"),
             column_type: ColumnType::String,
+            deleted_at: None,
         };
         let column_2 = Column {
             id: ColumnId::new(2),
             table_id,
             name: String::from("pain"),
             column_type: ColumnType::String,
+            deleted_at: None,
         };
 
         let mut first_write_table_schema = TableSchema::new(table_id);
@@ -232,6 +244,7 @@ mod tests {
             tables: BTreeMap::from([(String::from(table_name), first_write_table_schema)]),
             max_columns_per_table: 50,
             max_tables: 24,
+            query_limits: Default::default(),
             retention_period_ns: None,
             partition_template: None,
         };
@@ -299,6 +312,7 @@ mod tests {
             table_id: TableId::new(1),
             name: "column_a".to_string(),
             column_type: ColumnType::String,
+            deleted_at: None,
         });
         let mut table_2 = TableSchema::new(TableId::new(2));
         table_2.add_column(Column {
@@ -306,6 +320,7 @@ mod tests {
             table_id: TableId::new(2),
             name: "column_b".to_string(),
             column_type: ColumnType::String,
+            deleted_at: None,
         });
         let mut table_3 = TableSchema::new(TableId::new(3));
         table_3.add_column(Column {
@@ -313,6 +328,7 @@ mod tests {
             table_id: TableId::new(3),
             name: "column_c".to_string(),
             column_type: ColumnType::String,
+            deleted_at: None,
         });
 
         let schema_update_1 = NamespaceSchema {
@@ -323,6 +339,7 @@ mod tests {
             ]),
             max_columns_per_table: 50,
             max_tables: 24,
+            query_limits: Default::default(),
             retention_period_ns: None,
             partition_template: None,
         };
@@ -378,6 +395,38 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_replace_removes_tables() {
+        let ns = NamespaceName::new("arán").expect("namespace name is valid");
+        let schema_1 = NamespaceSchema {
+            id: NamespaceId::new(42),
+            tables: BTreeMap::from([
+                (String::from("table_1"), TableSchema::new(TableId::new(1))),
+                (String::from("table_2"), TableSchema::new(TableId::new(2))),
+            ]),
+            max_columns_per_table: 50,
+            max_tables: 24,
+            query_limits: Default::default(),
+            retention_period_ns: None,
+            partition_template: None,
+        };
+        // table_2 has been deleted since the namespace was cached.
+        let schema_2 = NamespaceSchema {
+            tables: BTreeMap::from([(String::from("table_1"), TableSchema::new(TableId::new(1)))]),
+            ..schema_1.clone()
+        };
+
+        let cache = Arc::new(MemoryNamespaceCache::default());
+        assert!(cache.replace_schema(ns.clone(), schema_1.clone()).is_none());
+
+        // Putting the schema retains the deleted table, replacing it does not.
+        let (got, _) = cache.put_schema(ns.clone(), schema_2.clone());
+        assert_eq!(*got, schema_1);
+        let old = cache.replace_schema(ns.clone(), schema_2.clone());
+        assert_eq!(old.as_deref(), Some(&schema_1));
+        assert_eq!(*cache.get_schema(&ns).await.unwrap(), schema_2);
+    }
+
     /// A set of table and column names from which arbitrary names are selected
     /// in prop tests, instead of using random values that have a low
     /// probability of overlap.
@@ -427,6 +476,7 @@ mod tests {
                 tables,
                 max_columns_per_table,
                 max_tables,
+                query_limits: Default::default(),
                 retention_period_ns,
                 partition_template: None,
             }
@@ -121,6 +121,34 @@ where
 
         (result, change_stats)
     }
+
+    fn replace_schema(
+        &self,
+        namespace: NamespaceName<'static>,
+        schema: NamespaceSchema,
+    ) -> Option<Arc<NamespaceSchema>> {
+        let (new_tables, new_columns) = schema_size(&schema);
+        let old = self.inner.replace_schema(namespace, schema);
+
+        // Swap the size of the replaced entry for that of the new one.
+        if let Some(old) = &old {
+            let (old_tables, old_columns) = schema_size(old);
+            self.table_count.dec(old_tables as u64);
+            self.column_count.dec(old_columns as u64);
+        }
+        self.table_count.inc(new_tables as u64);
+        self.column_count.inc(new_columns as u64);
+
+        old
+    }
+}
+
+/// Returns the number of tables and columns (across all tables) in `schema`.
+fn schema_size(schema: &NamespaceSchema) -> (usize, usize) {
+    (
+        schema.tables.len(),
+        schema.tables.values().map(|t| t.column_count()).sum(),
+    )
 }
 
 #[cfg(test)]
@@ -148,6 +176,7 @@ mod tests {
                         column_type: ColumnType::Bool,
                         name: i.to_string(),
                         table_id: TableId::new(i as _),
+                        deleted_at: None,
                     })
                     .collect::<Vec<_>>();
 
@@ -167,6 +196,7 @@ mod tests {
             tables,
             max_columns_per_table: 100,
             max_tables: 42,
+            query_limits: Default::default(),
             retention_period_ns: None,
             partition_template: None,
         }
@@ -319,4 +349,21 @@ mod tests {
             1,
         );
     }
+
+    #[tokio::test]
+    async fn test_replace() {
+        let ns = NamespaceName::new("test").expect("namespace name is valid");
+        let registry = metric::Registry::default();
+        let cache = Arc::new(MemoryNamespaceCache::default());
+        let cache = Arc::new(InstrumentedCache::new(cache, &registry));
+
+        cache.replace_schema(ns.clone(), new_schema(&[5, 10, 4]));
+        assert_eq!(cache.table_count.observe(), Observation::U64Gauge(3));
+        assert_eq!(cache.column_count.observe(), Observation::U64Gauge(19));
+
+        // Removing a table and columns reduces the counts
+        cache.replace_schema(ns.clone(), new_schema(&[5, 2]));
+        assert_eq!(cache.table_count.observe(), Observation::U64Gauge(2));
+        assert_eq!(cache.column_count.observe(), Observation::U64Gauge(7));
+    }
 }
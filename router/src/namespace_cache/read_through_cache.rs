@@ -93,6 +93,14 @@ where
     ) -> (Arc<NamespaceSchema>, ChangeStats) {
         self.inner_cache.put_schema(namespace, schema)
     }
+
+    fn replace_schema(
+        &self,
+        namespace: NamespaceName<'static>,
+        schema: NamespaceSchema,
+    ) -> Option<Arc<NamespaceSchema>> {
+        self.inner_cache.replace_schema(namespace, schema)
+    }
 }
 
 #[cfg(test)]
@@ -123,6 +131,7 @@ mod tests {
             tables: Default::default(),
             max_columns_per_table: iox_catalog::DEFAULT_MAX_COLUMNS_PER_TABLE as usize,
             max_tables: iox_catalog::DEFAULT_MAX_TABLES as usize,
+            query_limits: Default::default(),
             retention_period_ns: iox_catalog::DEFAULT_RETENTION_PERIOD,
             partition_template: None,
         };
@@ -159,6 +168,7 @@ mod tests {
             tables: Default::default(),
             max_columns_per_table: iox_catalog::DEFAULT_MAX_COLUMNS_PER_TABLE as usize,
             max_tables: iox_catalog::DEFAULT_MAX_TABLES as usize,
+            query_limits: Default::default(),
             retention_period_ns: iox_catalog::DEFAULT_RETENTION_PERIOD,
             partition_template: None,
         };
@@ -168,7 +178,7 @@ mod tests {
                 .repositories()
                 .await
                 .namespaces()
-                .create(&ns, iox_catalog::DEFAULT_RETENTION_PERIOD,)
+                .create(&ns, None, iox_catalog::DEFAULT_RETENTION_PERIOD,)
                 .await,
             Ok(_)
         );
@@ -0,0 +1,139 @@
+//! Periodic refresh of a [`NamespaceCache`] from the catalog.
+
+use std::{sync::Arc, time::Duration};
+
+use data_types::NamespaceName;
+use iox_catalog::interface::{list_schemas, Catalog};
+use observability_deps::tracing::*;
+use tokio::time::MissedTickBehavior;
+
+use super::NamespaceCache;
+
+/// Replaces the entries of a [`NamespaceCache`] with the schemas in the
+/// catalog at a fixed interval.
+///
+/// Cache entries are otherwise only ever added to, so without a refresh a
+/// table or column soft-deleted after it was cached would continue to accept
+/// writes. The refresh interval bounds how long that can happen for.
+#[derive(Debug)]
+pub struct SchemaCacheRefresher<C> {
+    cache: C,
+    catalog: Arc<dyn Catalog>,
+}
+
+impl<C> SchemaCacheRefresher<C>
+where
+    C: NamespaceCache,
+{
+    /// Initialise a [`SchemaCacheRefresher`] replacing the entries of `cache`
+    /// with the schemas in `catalog`.
+    pub fn new(cache: C, catalog: Arc<dyn Catalog>) -> Self {
+        Self { cache, catalog }
+    }
+
+    /// Refresh the cache every `interval`, starting after the first
+    /// `interval` has elapsed.
+    pub async fn run(&self, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        // The first tick completes immediately, when the cache is still warm.
+        ticker.tick().await;
+
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.refresh().await {
+                warn!(error=%e, "failed to refresh namespace schema cache");
+            }
+        }
+    }
+
+    /// Replace the cached schema of every namespace in the catalog.
+    pub async fn refresh(&self) -> Result<(), iox_catalog::interface::Error> {
+        let mut refreshed = 0;
+        for (ns, schema) in list_schemas(&*self.catalog).await? {
+            let name = NamespaceName::try_from(ns.name)
+                .expect("cannot convert existing namespace string to a `NamespaceName` instance");
+
+            self.cache.replace_schema(name, schema);
+            refreshed += 1;
+        }
+
+        debug!(namespaces = refreshed, "refreshed namespace schema cache");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use data_types::ColumnType;
+    use iox_catalog::mem::MemCatalog;
+
+    use super::*;
+    use crate::namespace_cache::MemoryNamespaceCache;
+
+    #[tokio::test]
+    async fn test_refresh_removes_deleted_tables() {
+        let catalog: Arc<dyn Catalog> = Arc::new(MemCatalog::new(Default::default()));
+
+        let mut repos = catalog.repositories().await;
+        let namespace = repos
+            .namespaces()
+            .create("test_ns", None, None)
+            .await
+            .unwrap();
+        let mut tables = Vec::new();
+        for name in ["bananas", "platanos"] {
+            let table = repos
+                .tables()
+                .create_or_get(name, namespace.id)
+                .await
+                .unwrap();
+            repos
+                .columns()
+                .create_or_get("value", table.id, ColumnType::U64)
+                .await
+                .unwrap();
+            tables.push(table);
+        }
+        drop(repos); // Or it'll deadlock.
+
+        let cache = Arc::new(MemoryNamespaceCache::default());
+        let refresher = SchemaCacheRefresher::new(Arc::clone(&cache), Arc::clone(&catalog));
+        refresher.refresh().await.expect("refresh failed");
+
+        let name = NamespaceName::new("test_ns").unwrap();
+        let got = cache.get_schema(&name).await.expect("should be cached");
+        assert_eq!(
+            got.tables.keys().collect::<Vec<_>>(),
+            ["bananas", "platanos"]
+        );
+
+        // Soft-delete a table, which the cached schema still contains until
+        // it is refreshed.
+        catalog
+            .repositories()
+            .await
+            .tables()
+            .soft_delete(tables[0].id)
+            .await
+            .unwrap();
+        refresher.refresh().await.expect("refresh failed");
+
+        let got = cache.get_schema(&name).await.expect("should be cached");
+        assert_eq!(got.tables.keys().collect::<Vec<_>>(), ["platanos"]);
+
+        // Deleting the last table leaves the namespace cached without tables.
+        catalog
+            .repositories()
+            .await
+            .tables()
+            .soft_delete(tables[1].id)
+            .await
+            .unwrap();
+        refresher.refresh().await.expect("refresh failed");
+
+        let got = cache.get_schema(&name).await.expect("should be cached");
+        assert!(got.tables.is_empty());
+    }
+}
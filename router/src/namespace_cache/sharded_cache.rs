@@ -43,6 +43,16 @@ where
     ) -> (Arc<NamespaceSchema>, ChangeStats) {
         self.shards.hash(&namespace).put_schema(namespace, schema)
     }
+
+    fn replace_schema(
+        &self,
+        namespace: NamespaceName<'static>,
+        schema: NamespaceSchema,
+    ) -> Option<Arc<NamespaceSchema>> {
+        self.shards
+            .hash(&namespace)
+            .replace_schema(namespace, schema)
+    }
 }
 
 #[cfg(test)]
@@ -73,6 +83,7 @@ mod tests {
             tables: Default::default(),
             max_columns_per_table: 7,
             max_tables: 42,
+            query_limits: Default::default(),
             retention_period_ns: None,
             partition_template: None,
         }
@@ -34,6 +34,19 @@ pub trait NamespaceResolver: std::fmt::Debug + Send + Sync {
     ) -> Result<Arc<NamespaceSchema>, Error>;
 }
 
+#[async_trait]
+impl<T> NamespaceResolver for Arc<T>
+where
+    T: NamespaceResolver,
+{
+    async fn get_namespace_schema(
+        &self,
+        namespace: &NamespaceName<'static>,
+    ) -> Result<Arc<NamespaceSchema>, Error> {
+        (**self).get_namespace_schema(namespace).await
+    }
+}
+
 /// An implementation of [`NamespaceResolver`] that resolves the [`NamespaceSchema`]
 /// for a given name through a [`NamespaceCache`].
 #[derive(Debug)]
@@ -99,6 +112,7 @@ mod tests {
                 tables: Default::default(),
                 max_columns_per_table: 4,
                 max_tables: 42,
+                query_limits: Default::default(),
                 retention_period_ns: None,
                 partition_template: None,
             },
@@ -144,7 +158,7 @@ mod tests {
             let mut repos = catalog.repositories().await;
             repos
                 .namespaces()
-                .create(&ns, None)
+                .create(&ns, None, None)
                 .await
                 .expect("failed to setup catalog state");
         }
@@ -176,7 +190,7 @@ mod tests {
             let mut repos = catalog.repositories().await;
             repos
                 .namespaces()
-                .create(&ns, None)
+                .create(&ns, None, None)
                 .await
                 .expect("failed to setup catalog state");
             repos
@@ -44,6 +44,7 @@ fn new_empty_namespace_schema(id: NamespaceId) -> NamespaceSchema {
         tables: BTreeMap::new(),
         max_columns_per_table: 500,
         max_tables: 200,
+        query_limits: Default::default(),
         retention_period_ns: None,
         partition_template: None,
     }
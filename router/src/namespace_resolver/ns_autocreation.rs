@@ -107,7 +107,7 @@ where
                         .repositories()
                         .await
                         .namespaces()
-                        .create(namespace.as_str(), retention_period_ns)
+                        .create(namespace.as_str(), None, retention_period_ns)
                         .await
                     {
                         Ok(_) => {
@@ -170,6 +170,7 @@ mod tests {
                 tables: Default::default(),
                 max_columns_per_table: 4,
                 max_tables: 42,
+                query_limits: Default::default(),
                 retention_period_ns: None,
                 partition_template: None,
             },
@@ -245,8 +246,12 @@ mod tests {
                 name: ns.to_string(),
                 max_tables: iox_catalog::DEFAULT_MAX_TABLES,
                 max_columns_per_table: iox_catalog::DEFAULT_MAX_COLUMNS_PER_TABLE,
+                max_concurrent_queries: None,
+                max_query_memory_bytes: None,
+                max_total_query_memory_bytes: None,
                 retention_period_ns: TEST_RETENTION_PERIOD_NS,
                 deleted_at: None,
+                partition_template: None,
             }
         );
     }
@@ -0,0 +1,230 @@
+//! Periodic writes of the router's own metrics into an IOx namespace.
+//!
+//! Deployments without a Prometheus server can enable self-monitoring to
+//! query the health of IOx with SQL or InfluxQL instead. The metric registry
+//! is periodically snapshotted to line protocol by the
+//! [`LineProtocolEncoder`] and written through the same namespace resolver
+//! and [`DmlHandler`] stack as HTTP writes.
+//!
+//! Only the router's own [`metric::Registry`] is exported - ingesters,
+//! queriers and compactors running as separate processes write their metrics
+//! through the HTTP write API of a router instead. In the all-in-one mode
+//! every service shares a single registry, so the metrics of all services are
+//! exported.
+
+use std::{sync::Arc, time::Duration};
+
+use data_types::NamespaceName;
+use hashbrown::HashMap;
+use iox_time::{SystemProvider, TimeProvider};
+use metric::U64Counter;
+use metric_exporters::LineProtocolEncoder;
+use mutable_batch::MutableBatch;
+use observability_deps::tracing::*;
+use thiserror::Error;
+use tokio::time::MissedTickBehavior;
+
+use crate::{
+    dml_handlers::{DmlError, DmlHandler},
+    namespace_resolver::{self, NamespaceResolver},
+};
+
+/// Errors writing a snapshot of the metric registry.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// The metric snapshot could not be converted from line protocol.
+    #[error("failed to parse metric line protocol: {0}")]
+    ParseLineProtocol(#[from] mutable_batch_lp::Error),
+
+    /// The monitoring namespace could not be resolved.
+    #[error(transparent)]
+    NamespaceResolver(#[from] namespace_resolver::Error),
+
+    /// The write was rejected by the DML handler stack.
+    #[error(transparent)]
+    DmlHandler(#[from] DmlError),
+}
+
+/// Writes a snapshot of a [`metric::Registry`] to a namespace at a fixed
+/// interval.
+#[derive(Debug)]
+pub struct SelfMonitor<D, N, T = SystemProvider> {
+    namespace: NamespaceName<'static>,
+    metrics: Arc<metric::Registry>,
+    namespace_resolver: N,
+    dml_handler: D,
+    time_provider: T,
+
+    write_errors: U64Counter,
+}
+
+impl<D, N> SelfMonitor<D, N> {
+    /// Initialise a [`SelfMonitor`] writing the contents of `metrics` to
+    /// `namespace` via `namespace_resolver` and `dml_handler`.
+    ///
+    /// The namespace must exist, or be created by `namespace_resolver`.
+    pub fn new(
+        namespace: NamespaceName<'static>,
+        metrics: Arc<metric::Registry>,
+        namespace_resolver: N,
+        dml_handler: D,
+    ) -> Self {
+        let write_errors = metrics
+            .register_metric::<U64Counter>(
+                "self_monitoring_write_errors",
+                "number of metric snapshots that could not be written to the monitoring namespace",
+            )
+            .recorder(&[]);
+
+        Self {
+            namespace,
+            metrics,
+            namespace_resolver,
+            dml_handler,
+            time_provider: SystemProvider::default(),
+            write_errors,
+        }
+    }
+}
+
+impl<D, N, T> SelfMonitor<D, N, T> {
+    #[cfg(test)]
+    fn with_time_provider<U>(self, time_provider: U) -> SelfMonitor<D, N, U> {
+        SelfMonitor {
+            namespace: self.namespace,
+            metrics: self.metrics,
+            namespace_resolver: self.namespace_resolver,
+            dml_handler: self.dml_handler,
+            time_provider,
+            write_errors: self.write_errors,
+        }
+    }
+}
+
+impl<D, N, T> SelfMonitor<D, N, T>
+where
+    D: DmlHandler<WriteInput = HashMap<String, MutableBatch>, WriteOutput = ()>,
+    N: NamespaceResolver,
+    T: TimeProvider,
+{
+    /// Write a snapshot of the metric registry every `interval`, forever.
+    ///
+    /// Failed writes are logged and counted, and retried with the next
+    /// snapshot.
+    pub async fn run(self, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+
+            if let Err(e) = self.write_snapshot().await {
+                warn!(error=%e, namespace=%self.namespace, "failed to write metric snapshot");
+                self.write_errors.inc(1);
+            }
+        }
+    }
+
+    /// Write the current value of every metric in the registry to the
+    /// monitoring namespace.
+    pub async fn write_snapshot(&self) -> Result<(), Error> {
+        let now = self.time_provider.now().timestamp_nanos();
+
+        let mut lp = Vec::new();
+        self.metrics
+            .report(&mut LineProtocolEncoder::new(&mut lp, now));
+        let lp = String::from_utf8(lp).expect("line protocol encoder produced non-utf8 output");
+
+        let batches = match mutable_batch_lp::lines_to_batches(&lp, now) {
+            Ok(v) => v,
+            Err(mutable_batch_lp::Error::EmptyPayload) => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let namespace_schema = self
+            .namespace_resolver
+            .get_namespace_schema(&self.namespace)
+            .await?;
+
+        self.dml_handler
+            .write(&self.namespace, namespace_schema, batches, None)
+            .await
+            .map_err(Into::into)?;
+
+        debug!(namespace=%self.namespace, "wrote metric snapshot");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use data_types::NamespaceId;
+    use iox_time::{MockProvider, Time};
+
+    use super::*;
+    use crate::{
+        dml_handlers::mock::{MockDmlHandler, MockDmlHandlerCall},
+        namespace_resolver::mock::MockNamespaceResolver,
+    };
+
+    const NAMESPACE: &str = "_monitoring";
+
+    fn monitor(
+        metrics: &Arc<metric::Registry>,
+        dml_handler: &Arc<MockDmlHandler<HashMap<String, MutableBatch>>>,
+    ) -> SelfMonitor<
+        Arc<MockDmlHandler<HashMap<String, MutableBatch>>>,
+        MockNamespaceResolver,
+        MockProvider,
+    > {
+        SelfMonitor::new(
+            NamespaceName::new(NAMESPACE).unwrap(),
+            Arc::clone(metrics),
+            MockNamespaceResolver::default().with_mapping(NAMESPACE, NamespaceId::new(42)),
+            Arc::clone(dml_handler),
+        )
+        .with_time_provider(MockProvider::new(Time::from_timestamp_nanos(1_000)))
+    }
+
+    #[tokio::test]
+    async fn test_write_snapshot() {
+        let metrics = Arc::new(metric::Registry::default());
+        metrics
+            .register_metric::<U64Counter>("bananas", "a counter")
+            .recorder(&[("kind", "ripe")])
+            .inc(3);
+
+        let dml_handler = Arc::new(MockDmlHandler::default().with_write_return([Ok(())]));
+        monitor(&metrics, &dml_handler)
+            .write_snapshot()
+            .await
+            .expect("write should succeed");
+
+        let calls = dml_handler.calls();
+        assert_matches!(calls.as_slice(), [MockDmlHandlerCall::Write { namespace, write_input, .. }] => {
+            assert_eq!(namespace, NAMESPACE);
+
+            // Both the counter and the write error counter of the monitor are written.
+            assert!(write_input.contains_key("self_monitoring_write_errors"));
+            let bananas = write_input.get("bananas").expect("missing metric table");
+            assert_eq!(bananas.rows(), 1);
+            assert_eq!(bananas.timestamp_summary().unwrap().stats.min, Some(1_000));
+        });
+    }
+
+    #[tokio::test]
+    async fn test_write_snapshot_error() {
+        let metrics = Arc::new(metric::Registry::default());
+        let dml_handler = Arc::new(MockDmlHandler::default().with_write_return([Ok(())]));
+
+        let mut monitor = monitor(&metrics, &dml_handler);
+        monitor.namespace = NamespaceName::new("platanos").unwrap();
+
+        assert_matches!(
+            monitor.write_snapshot().await,
+            Err(Error::NamespaceResolver(_))
+        );
+        assert!(dml_handler.calls().is_empty());
+    }
+}
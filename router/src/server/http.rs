@@ -1,5 +1,6 @@
 //! HTTP service implementations for `router`.
 
+pub mod delete;
 pub mod write;
 
 use std::{str::Utf8Error, time::Instant};
@@ -17,9 +18,14 @@ use thiserror::Error;
 use tokio::sync::{Semaphore, TryAcquireError};
 use trace::ctx::SpanContext;
 
-use self::write::{
-    multi_tenant::MultiTenantExtractError, single_tenant::SingleTenantExtractError, WriteParams,
-    WriteRequestUnifier,
+use self::{
+    delete::{parse_delete_request, DeleteRequestError},
+    write::{
+        multi_tenant::MultiTenantExtractError,
+        partial::{LineValidator, PartialWriteError},
+        single_tenant::SingleTenantExtractError,
+        WriteParams, WriteRequestUnifier,
+    },
 };
 use crate::{
     dml_handlers::{
@@ -36,10 +42,6 @@ pub enum Error {
     #[error("not found")]
     NoHandler,
 
-    /// A delete request was rejected (not supported).
-    #[error("deletes are not supported")]
-    DeletesUnsupported,
-
     /// An error parsing a single-tenant HTTP request.
     #[error(transparent)]
     SingleTenantError(#[from] SingleTenantExtractError),
@@ -76,6 +78,15 @@ pub enum Error {
     #[error("failed to parse line protocol: {0}")]
     ParseLineProtocol(mutable_batch_lp::Error),
 
+    /// Some lines of a partial write request were rejected, and the rest
+    /// were written.
+    #[error(transparent)]
+    PartialWrite(#[from] PartialWriteError),
+
+    /// Failure to decode the provided delete request.
+    #[error("failed to parse delete request: {0}")]
+    ParseDelete(#[from] DeleteRequestError),
+
     /// An error returned from the [`DmlHandler`].
     #[error("dml handler error: {0}")]
     DmlHandler(#[from] DmlError),
@@ -107,12 +118,13 @@ impl Error {
     pub fn as_status_code(&self) -> StatusCode {
         match self {
             Error::NoHandler => StatusCode::NOT_FOUND,
-            Error::DeletesUnsupported => StatusCode::NOT_IMPLEMENTED,
             Error::ClientHangup(_) => StatusCode::BAD_REQUEST,
             Error::InvalidGzip(_) => StatusCode::BAD_REQUEST,
             Error::NonUtf8ContentHeader(_) => StatusCode::BAD_REQUEST,
             Error::NonUtf8Body(_) => StatusCode::BAD_REQUEST,
             Error::ParseLineProtocol(_) => StatusCode::BAD_REQUEST,
+            Error::PartialWrite(_) => StatusCode::BAD_REQUEST,
+            Error::ParseDelete(_) => StatusCode::BAD_REQUEST,
             Error::RequestSizeExceeded(_) => StatusCode::PAYLOAD_TOO_LARGE,
             Error::InvalidContentEncoding(_) => {
                 // https://www.rfc-editor.org/rfc/rfc7231#section-6.5.13
@@ -190,10 +202,12 @@ pub struct HttpDelegate<D, N, T = SystemProvider> {
     request_sem: Semaphore,
 
     write_metric_lines: U64Counter,
+    write_metric_rejected_lines: U64Counter,
     http_line_protocol_parse_duration: DurationHistogram,
     write_metric_fields: U64Counter,
     write_metric_tables: U64Counter,
     write_metric_body_size: U64Counter,
+    delete_metric_body_size: U64Counter,
     request_limit_rejected: U64Counter,
 }
 
@@ -217,6 +231,12 @@ impl<D, N> HttpDelegate<D, N, SystemProvider> {
                 "cumulative number of line protocol lines successfully routed",
             )
             .recorder(&[]);
+        let write_metric_rejected_lines = metrics
+            .register_metric::<U64Counter>(
+                "http_write_rejected_lines",
+                "cumulative number of line protocol lines skipped by partial writes",
+            )
+            .recorder(&[]);
         let write_metric_fields = metrics
             .register_metric::<U64Counter>(
                 "http_write_fields",
@@ -235,6 +255,12 @@ impl<D, N> HttpDelegate<D, N, SystemProvider> {
                 "cumulative byte size of successfully routed (decompressed) line protocol write requests",
             )
             .recorder(&[]);
+        let delete_metric_body_size = metrics
+            .register_metric::<U64Counter>(
+                "http_delete_body_bytes",
+                "cumulative byte size of successfully routed (decompressed) delete requests",
+            )
+            .recorder(&[]);
         let request_limit_rejected = metrics
             .register_metric::<U64Counter>(
                 "http_request_limit_rejected",
@@ -256,10 +282,12 @@ impl<D, N> HttpDelegate<D, N, SystemProvider> {
             dml_handler,
             request_sem: Semaphore::new(max_requests),
             write_metric_lines,
+            write_metric_rejected_lines,
             http_line_protocol_parse_duration,
             write_metric_fields,
             write_metric_tables,
             write_metric_body_size,
+            delete_metric_body_size,
             request_limit_rejected,
         }
     }
@@ -301,7 +329,10 @@ where
                 let dml_info = self.write_request_mode_handler.parse_v2(&req).await?;
                 self.write_handler(req, dml_info).await
             }
-            (&Method::POST, "/api/v2/delete") => return Err(Error::DeletesUnsupported),
+            (&Method::POST, "/api/v2/delete") => {
+                let dml_info = self.write_request_mode_handler.parse_v2(&req).await?;
+                self.delete_handler(req, dml_info).await
+            }
             _ => return Err(Error::NoHandler),
         }
         .map(|_summary| {
@@ -335,12 +366,33 @@ where
 
         let mut converter = LinesConverter::new(default_time);
         converter.set_timestamp_base(write_info.precision.timestamp_base());
-        let (batches, stats) = match converter.write_lp(body).and_then(|_| converter.finish()) {
+
+        // Partial writes skip the lines that conflict with the namespace schema
+        // rather than failing the write in the DML handlers, so the schema must
+        // be resolved before the body is converted.
+        let (namespace_schema, rejected) = if write_info.partial_writes {
+            let namespace_schema = self
+                .namespace_resolver
+                .get_namespace_schema(&write_info.namespace)
+                .await?;
+            let validator = LineValidator::new(&namespace_schema, default_time);
+            let rejected = converter.write_lp_partial(body, |line| validator.validate(line));
+            (Some(namespace_schema), rejected)
+        } else {
+            converter.write_lp(body).map_err(Error::ParseLineProtocol)?;
+            (None, vec![])
+        };
+        self.write_metric_rejected_lines.inc(rejected.len() as _);
+
+        let (batches, stats) = match converter.finish() {
             Ok(v) => v,
-            Err(mutable_batch_lp::Error::EmptyPayload) => {
+            Err(mutable_batch_lp::Error::EmptyPayload) if rejected.is_empty() => {
                 debug!("nothing to write");
                 return Ok(());
             }
+            Err(mutable_batch_lp::Error::EmptyPayload) => {
+                return Err(PartialWriteError::new(0, rejected).into());
+            }
             Err(e) => return Err(Error::ParseLineProtocol(e)),
         };
 
@@ -351,6 +403,7 @@ where
             num_lines=stats.num_lines,
             num_fields=stats.num_fields,
             num_tables,
+            num_rejected_lines=rejected.len(),
             precision=?write_info.precision,
             body_size=body.len(),
             namespace=%write_info.namespace,
@@ -358,11 +411,16 @@ where
             "routing write",
         );
 
-        // Retrieve the namespace schema for this namespace.
-        let namespace_schema = self
-            .namespace_resolver
-            .get_namespace_schema(&write_info.namespace)
-            .await?;
+        // Retrieve the namespace schema for this namespace, if not already
+        // resolved for a partial write.
+        let namespace_schema = match namespace_schema {
+            Some(v) => v,
+            None => {
+                self.namespace_resolver
+                    .get_namespace_schema(&write_info.namespace)
+                    .await?
+            }
+        };
 
         self.dml_handler
             .write(&write_info.namespace, namespace_schema, batches, span_ctx)
@@ -374,6 +432,57 @@ where
         self.write_metric_tables.inc(num_tables as _);
         self.write_metric_body_size.inc(body.len() as _);
 
+        if !rejected.is_empty() {
+            return Err(PartialWriteError::new(stats.num_lines, rejected).into());
+        }
+
+        Ok(())
+    }
+
+    async fn delete_handler(
+        &self,
+        req: Request<Body>,
+        write_info: WriteParams,
+    ) -> Result<(), Error> {
+        let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
+
+        trace!(namespace=%write_info.namespace, "processing delete request");
+
+        // Read the HTTP body and convert it to a str.
+        let body = self.read_body(req).await?;
+        let body = std::str::from_utf8(&body).map_err(Error::NonUtf8Body)?;
+
+        // Parse and extract table name (which can be empty), start, stop, and
+        // predicate
+        let parsed_delete = parse_delete_request(body)?;
+        let predicate = parsed_delete.predicate;
+        let table_name = parsed_delete.table_name;
+
+        debug!(
+            table_name,
+            ?predicate,
+            namespace=%write_info.namespace,
+            "routing delete"
+        );
+
+        let namespace_schema = self
+            .namespace_resolver
+            .get_namespace_schema(&write_info.namespace)
+            .await?;
+
+        self.dml_handler
+            .delete(
+                &write_info.namespace,
+                namespace_schema.id,
+                &table_name,
+                &predicate,
+                span_ctx,
+            )
+            .await
+            .map_err(Into::into)?;
+
+        self.delete_metric_body_size.inc(body.len() as _);
+
         Ok(())
     }
 
@@ -576,6 +685,7 @@ mod tests {
                         .with_mapping(NAMESPACE_NAME, NAMESPACE_ID);
                     let dml_handler = Arc::new(MockDmlHandler::default()
                         .with_write_return($dml_write_handler)
+                        .with_delete_return($dml_delete_handler)
                     );
                     let metrics = Arc::new(metric::Registry::default());
                     let delegate = HttpDelegate::new(
@@ -755,6 +865,54 @@ mod tests {
         want_dml_calls = []
     );
 
+    test_write_handler!(
+        partial_write_all_valid,
+        query_string = "?org=bananas&bucket=test&partial_writes=true",
+        body = "platanos,tag1=A val=42i 123456\nplatanos,tag1=B val=24i 123457".as_bytes(),
+        dml_handler = [Ok(())],
+        want_result = Ok(_),
+        want_dml_calls = [
+            MockDmlHandlerCall::Write { namespace, write_input, .. }
+        ] => {
+            assert_eq!(namespace, NAMESPACE_NAME);
+            assert_eq!(write_input.get("platanos").expect("table not found").rows(), 2);
+        }
+    );
+
+    test_write_handler!(
+        partial_write_invalid_lines,
+        query_string = "?org=bananas&bucket=test&partial_writes=true",
+        // The second line is not valid line protocol, and the third conflicts
+        // with the type of "val" in the first.
+        body = "platanos,tag1=A val=42i 123456\nbananas\nplatanos,tag1=B val=4.2 123457".as_bytes(),
+        dml_handler = [Ok(())],
+        want_result = Err(Error::PartialWrite(_)),
+        want_dml_calls = [
+            MockDmlHandlerCall::Write { namespace, write_input, .. }
+        ] => {
+            assert_eq!(namespace, NAMESPACE_NAME);
+            assert_eq!(write_input.get("platanos").expect("table not found").rows(), 1);
+        }
+    );
+
+    test_write_handler!(
+        partial_write_no_valid_lines,
+        query_string = "?org=bananas&bucket=test&partial_writes=true",
+        body = "bananas\nplatanos".as_bytes(),
+        dml_handler = [Ok(())],
+        want_result = Err(Error::PartialWrite(_)),
+        want_dml_calls = []
+    );
+
+    test_write_handler!(
+        invalid_lines_without_partial_write,
+        query_string = "?org=bananas&bucket=test",
+        body = "platanos,tag1=A val=42i 123456\nbananas".as_bytes(),
+        dml_handler = [Ok(())],
+        want_result = Err(Error::ParseLineProtocol(_)),
+        want_dml_calls = []
+    );
+
     test_write_handler!(
         no_query_params,
         query_string = "",
@@ -904,6 +1062,108 @@ mod tests {
         want_dml_calls = []
     );
 
+    // Wrapper over test_http_handler specifically for delete requests.
+    macro_rules! test_delete_handler {
+        (
+            $name:ident,
+            query_string = $query_string:expr,   // Request URI query string
+            body = $body:expr,                   // Request body content
+            dml_handler = $dml_handler:expr,     // DML delete handler response (if called)
+            want_result = $want_result:pat,
+            want_dml_calls = $($want_dml_calls:tt )+
+        ) => {
+            paste::paste! {
+                test_http_handler!(
+                    [<delete_ $name>],
+                    uri = format!("https://bananas.example/api/v2/delete{}", $query_string),
+                    body = $body,
+                    dml_write_handler = [],
+                    dml_delete_handler = $dml_handler,
+                    want_result = $want_result,
+                    want_dml_calls = $($want_dml_calls)+
+                );
+            }
+        };
+    }
+
+    test_delete_handler!(
+        ok,
+        query_string = "?org=bananas&bucket=test",
+        body = r#"{"start":"2021-04-01T14:00:00Z","stop":"2021-04-02T14:00:00Z", "predicate":"_measurement=its_a_table and location=Boston"}"#.as_bytes(),
+        dml_handler = [Ok(())],
+        want_result = Ok(_),
+        want_dml_calls = [
+            MockDmlHandlerCall::Delete { namespace, namespace_id, table, predicate }
+        ] => {
+            assert_eq!(table, "its_a_table");
+            assert_eq!(namespace, NAMESPACE_NAME);
+            assert_eq!(*namespace_id, NAMESPACE_ID);
+            assert!(!predicate.exprs.is_empty());
+        }
+    );
+
+    test_delete_handler!(
+        invalid_delete_body,
+        query_string = "?org=bananas&bucket=test",
+        body = r#"{wat}"#.as_bytes(),
+        dml_handler = [],
+        want_result = Err(Error::ParseDelete(DeleteRequestError::InvalidBody(_))),
+        want_dml_calls = []
+    );
+
+    test_delete_handler!(
+        invalid_predicate,
+        query_string = "?org=bananas&bucket=test",
+        body = r#"{"start":"2021-04-01T14:00:00Z","stop":"2021-04-02T14:00:00Z", "predicate":"location > Boston"}"#.as_bytes(),
+        dml_handler = [],
+        want_result = Err(Error::ParseDelete(DeleteRequestError::InvalidPredicate(_))),
+        want_dml_calls = []
+    );
+
+    test_delete_handler!(
+        no_query_params,
+        query_string = "",
+        body = "".as_bytes(),
+        dml_handler = [Ok(())],
+        want_result = Err(Error::MultiTenantError(
+            MultiTenantExtractError::ParseV2Request(V2WriteParseError::NoQueryParams)
+        )),
+        want_dml_calls = [] // None
+    );
+
+    test_delete_handler!(
+        non_utf8_body,
+        query_string = "?org=bananas&bucket=test",
+        body = vec![0xc3, 0x28],
+        dml_handler = [Ok(())],
+        want_result = Err(Error::NonUtf8Body(_)),
+        want_dml_calls = [] // None
+    );
+
+    test_delete_handler!(
+        db_not_found,
+        query_string = "?org=bananas&bucket=test",
+        body = r#"{"start":"2021-04-01T14:00:00Z","stop":"2021-04-02T14:00:00Z", "predicate":"_measurement=its_a_table and location=Boston"}"#.as_bytes(),
+        dml_handler = [Err(DmlError::NamespaceNotFound(NAMESPACE_NAME.to_string()))],
+        want_result = Err(Error::DmlHandler(DmlError::NamespaceNotFound(_))),
+        want_dml_calls = [MockDmlHandlerCall::Delete { namespace, table, .. }] => {
+            assert_eq!(namespace, NAMESPACE_NAME);
+            assert_eq!(table, "its_a_table");
+        }
+    );
+
+    test_delete_handler!(
+        dml_handler_error,
+        query_string = "?org=bananas&bucket=test",
+        body = r#"{"start":"2021-04-01T14:00:00Z","stop":"2021-04-02T14:00:00Z", "predicate":"_measurement=its_a_table and location=Boston"}"#.as_bytes(),
+        dml_handler = [Err(DmlError::Internal("💣".into()))],
+        want_result = Err(Error::DmlHandler(DmlError::Internal(_))),
+        want_dml_calls = [MockDmlHandlerCall::Delete { namespace, table, .. }] => {
+            assert_eq!(namespace, NAMESPACE_NAME);
+            assert_eq!(table, "its_a_table");
+        }
+    );
+
     test_http_handler!(
         not_found,
         uri = "https://bananas.example/wat",
@@ -1055,6 +1315,7 @@ mod tests {
                     Ok(WriteParams {
                         namespace: NamespaceName::new(NAMESPACE_NAME).unwrap(),
                         precision: Precision::default(),
+                        partial_writes: false,
                     })
                 })),
             ),
@@ -1211,6 +1472,7 @@ mod tests {
                 Ok(WriteParams {
                     namespace: NamespaceName::new(NAMESPACE_NAME).unwrap(),
                     precision: Precision::default(),
+                    partial_writes: false,
                 })
             }),
         ));
@@ -1318,11 +1580,6 @@ mod tests {
             "not found",
         ),
 
-        (
-            DeletesUnsupported,
-            "deletes are not supported",
-        ),
-
         (
             NonUtf8Body(std::str::from_utf8(&[0, 159]).unwrap_err()),
             "body content is not valid utf8: invalid utf-8 sequence of 1 bytes from index 1",
@@ -1397,8 +1654,27 @@ mod tests {
         ),
 
         (
-            ParseLineProtocol(mutable_batch_lp::Error::TimestampOverflow),
-            "failed to parse line protocol: timestamp overflows i64",
+            ParseDelete(DeleteRequestError::InvalidMeasurement),
+            "failed to parse delete request: \
+            delete predicate must select a single _measurement by equality",
+        ),
+
+        (
+            ParseLineProtocol(mutable_batch_lp::Error::TimestampOverflow { line: 1 }),
+            "failed to parse line protocol: timestamp overflows i64 on line 1",
+        ),
+
+        (
+            PartialWrite(PartialWriteError::new(2, vec![
+                mutable_batch_lp::Error::Rejected {
+                    line: 3,
+                    reason: "data is outside of the retention period".into(),
+                },
+                mutable_batch_lp::Error::TimestampOverflow { line: 7 },
+            ])),
+            "partial write: 2 lines rejected, 2 lines written: \
+            line 3 rejected: data is outside of the retention period; \
+            timestamp overflows i64 on line 7",
         ),
 
         (
@@ -0,0 +1,157 @@
+//! Parsing of HTTP requests that conform to the [V2 Delete API].
+//!
+//! [V2 Delete API]:
+//!     https://docs.influxdata.com/influxdb/v2.6/api/#operation/PostDelete
+
+use data_types::{DeletePredicate, Op, Scalar};
+use predicate::delete_predicate::parse_delete_predicate;
+use serde::Deserialize;
+use thiserror::Error;
+
+/// The pseudo-column used in a delete predicate to select the table the
+/// delete applies to.
+const MEASUREMENT_COLUMN: &str = "_measurement";
+
+/// Errors returned when parsing a delete request body.
+#[derive(Debug, Error)]
+pub enum DeleteRequestError {
+    /// The request body is not a valid JSON delete request.
+    #[error("invalid delete request body: {0}")]
+    InvalidBody(#[from] serde_json::Error),
+
+    /// The time range or predicate of the delete is invalid.
+    #[error(transparent)]
+    InvalidPredicate(#[from] predicate::delete_predicate::Error),
+
+    /// The predicate selects the table with something other than a single
+    /// equality expression.
+    #[error("delete predicate must select a single {MEASUREMENT_COLUMN} by equality")]
+    InvalidMeasurement,
+}
+
+/// The JSON body of a delete request.
+#[derive(Debug, Deserialize)]
+struct DeleteRequest {
+    start: String,
+    stop: String,
+    #[serde(default)]
+    predicate: String,
+}
+
+/// A parsed delete request.
+#[derive(Debug, PartialEq)]
+pub(crate) struct ParsedDelete {
+    /// The table to delete from, or an empty string for all tables in the
+    /// namespace.
+    pub(crate) table_name: String,
+    /// The time range and (table-independent) tag predicate of the delete.
+    pub(crate) predicate: DeletePredicate,
+}
+
+/// Parse a delete request body of the form:
+///
+/// ```json
+/// {
+///     "start": "1970-01-01T00:00:00Z",
+///     "stop": "2070-01-02T00:00:00Z",
+///     "predicate": "_measurement=\"cpu\" and host=\"a\""
+/// }
+/// ```
+///
+/// Timestamps may be RFC3339 strings or integer nanoseconds. An optional
+/// `_measurement` equality expression selects the table the delete applies to,
+/// and is removed from the returned predicate.
+pub(crate) fn parse_delete_request(body: &str) -> Result<ParsedDelete, DeleteRequestError> {
+    let req: DeleteRequest = serde_json::from_str(body)?;
+    let mut predicate = parse_delete_predicate(&req.start, &req.stop, &req.predicate)?;
+
+    let (measurement, exprs): (Vec<_>, Vec<_>) = predicate
+        .exprs
+        .into_iter()
+        .partition(|e| e.column() == MEASUREMENT_COLUMN);
+    predicate.exprs = exprs;
+
+    let table_name = match measurement.as_slice() {
+        [] => String::new(),
+        [expr] => match (expr.op(), expr.scalar()) {
+            (Op::Eq, Scalar::String(name)) => name.clone(),
+            _ => return Err(DeleteRequestError::InvalidMeasurement),
+        },
+        _ => return Err(DeleteRequestError::InvalidMeasurement),
+    };
+
+    Ok(ParsedDelete {
+        table_name,
+        predicate,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use data_types::DeleteExpr;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_with_measurement() {
+        let got = parse_delete_request(
+            r#"{"start":"1970-01-01T00:00:00Z","stop":"100","predicate":"_measurement=\"cpu\" and host=\"a\""}"#,
+        )
+        .expect("valid request");
+
+        assert_eq!(got.table_name, "cpu");
+        assert_eq!(got.predicate.range.start(), 0);
+        assert_eq!(got.predicate.range.end(), 100);
+        assert_eq!(
+            got.predicate.exprs,
+            vec![DeleteExpr::new(
+                "host".to_string(),
+                Op::Eq,
+                Scalar::String("a".to_string())
+            )]
+        );
+    }
+
+    #[test]
+    fn test_parse_all_tables() {
+        let got = parse_delete_request(r#"{"start":"1","stop":"2"}"#).expect("valid request");
+
+        assert_eq!(got.table_name, "");
+        assert!(got.predicate.exprs.is_empty());
+    }
+
+    #[test]
+    fn test_parse_invalid_json() {
+        assert_matches!(
+            parse_delete_request(r#"{"start":"1"}"#),
+            Err(DeleteRequestError::InvalidBody(_))
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_time_range() {
+        assert_matches!(
+            parse_delete_request(r#"{"start":"2","stop":"1"}"#),
+            Err(DeleteRequestError::InvalidPredicate(_))
+        );
+    }
+
+    #[test]
+    fn test_parse_measurement_not_equal() {
+        assert_matches!(
+            parse_delete_request(r#"{"start":"1","stop":"2","predicate":"_measurement!=\"cpu\""}"#),
+            Err(DeleteRequestError::InvalidMeasurement)
+        );
+    }
+
+    #[test]
+    fn test_parse_multiple_measurements() {
+        assert_matches!(
+            parse_delete_request(
+                r#"{"start":"1","stop":"2","predicate":"_measurement=\"cpu\" and _measurement=\"mem\""}"#
+            ),
+            Err(DeleteRequestError::InvalidMeasurement)
+        );
+    }
+}
@@ -5,6 +5,7 @@ pub mod v1;
 pub mod v2;
 
 pub mod multi_tenant;
+pub mod partial;
 pub mod single_tenant;
 
 mod params;
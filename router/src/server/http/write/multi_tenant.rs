@@ -72,6 +72,7 @@ fn parse_v2(req: &Request<Body>) -> Result<WriteParams, MultiTenantExtractError>
     Ok(WriteParams {
         namespace,
         precision: write_params.precision,
+        partial_writes: write_params.partial_writes,
     })
 }
 
@@ -225,10 +226,33 @@ mod tests {
         query_string = "?org=banana&bucket=cool&precision=ms",
         want = Ok(WriteParams {
             namespace,
-            precision
+            precision,
+            partial_writes,
         }) => {
             assert_eq!(namespace.as_str(), "banana_cool");
             assert_matches!(precision, Precision::Milliseconds);
+            assert!(!partial_writes);
         }
     );
+
+    test_parse_v2!(
+        partial_writes,
+        query_string = "?org=banana&bucket=cool&partial_writes=true",
+        want = Ok(WriteParams {
+            namespace,
+            partial_writes,
+            ..
+        }) => {
+            assert_eq!(namespace.as_str(), "banana_cool");
+            assert!(partial_writes);
+        }
+    );
+
+    test_parse_v2!(
+        partial_writes_invalid,
+        query_string = "?org=banana&bucket=cool&partial_writes=bananas",
+        want = Err(Error::MultiTenantError(
+            MultiTenantExtractError::ParseV2Request(V2WriteParseError::DecodeFail(_))
+        ))
+    );
 }
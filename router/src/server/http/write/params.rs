@@ -42,6 +42,9 @@ impl Precision {
 pub struct WriteParams {
     pub(crate) namespace: NamespaceName<'static>,
     pub(crate) precision: Precision,
+    /// Write the valid lines of the request, skipping (and reporting) any
+    /// invalid lines instead of rejecting the entire request.
+    pub(crate) partial_writes: bool,
 }
 
 /// A [`WriteRequestUnifier`] abstraction returns a unified [`WriteParams`]
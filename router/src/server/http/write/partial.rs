@@ -0,0 +1,278 @@
+//! Support for partial writes, in which the valid lines of a write request are
+//! written and the invalid lines are skipped and reported back to the client.
+//!
+//! Partial writes are opt-in, and enabled per request by setting the
+//! `partial_writes=true` query parameter.
+
+use std::fmt::Display;
+
+use data_types::{ColumnType, NamespaceSchema};
+use influxdb_line_protocol::{FieldValue, ParsedLine};
+
+/// The maximum number of rejected lines described in a
+/// [`PartialWriteError`].
+///
+/// Bounds the size of the response to a request containing many bad lines.
+pub(crate) const MAX_REPORTED_LINES: usize = 100;
+
+/// Some (or all) of the lines of a partial write request were rejected.
+///
+/// All other lines in the request were written successfully.
+#[derive(Debug)]
+pub struct PartialWriteError {
+    num_written: usize,
+    num_rejected: usize,
+
+    /// The first [`MAX_REPORTED_LINES`] rejected lines, in line order.
+    rejected: Vec<mutable_batch_lp::Error>,
+}
+
+impl PartialWriteError {
+    pub(crate) fn new(num_written: usize, mut rejected: Vec<mutable_batch_lp::Error>) -> Self {
+        let num_rejected = rejected.len();
+        rejected.truncate(MAX_REPORTED_LINES);
+
+        Self {
+            num_written,
+            num_rejected,
+            rejected,
+        }
+    }
+
+    /// The number of lines that were written successfully.
+    pub fn num_written(&self) -> usize {
+        self.num_written
+    }
+
+    /// The total number of lines that were rejected, including those not
+    /// described in this error.
+    pub fn num_rejected(&self) -> usize {
+        self.num_rejected
+    }
+
+    /// The line errors reported to the client, at most
+    /// [`MAX_REPORTED_LINES`].
+    pub fn rejected(&self) -> &[mutable_batch_lp::Error] {
+        &self.rejected
+    }
+}
+
+impl Display for PartialWriteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "partial write: {} lines rejected, {} lines written: ",
+            self.num_rejected, self.num_written
+        )?;
+
+        for (i, e) in self.rejected.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{e}")?;
+        }
+
+        let omitted = self.num_rejected - self.rejected.len();
+        if omitted > 0 {
+            write!(f, "; {omitted} more rejected lines not shown")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for PartialWriteError {}
+
+/// Checks individual lines of a partial write against the schema and
+/// retention period of the namespace, so that lines that would cause the
+/// whole write to be rejected by the DML handlers can be skipped instead.
+#[derive(Debug)]
+pub(crate) struct LineValidator<'a> {
+    namespace_schema: &'a NamespaceSchema,
+    now: i64,
+    min_timestamp: Option<i64>,
+}
+
+impl<'a> LineValidator<'a> {
+    /// Validate lines against `namespace_schema` at time `now`, in
+    /// nanoseconds since the epoch. Lines without a timestamp are assumed
+    /// to be written at `now`.
+    pub(crate) fn new(namespace_schema: &'a NamespaceSchema, now: i64) -> Self {
+        let min_timestamp = namespace_schema
+            .retention_period_ns
+            .map(|retention_period_ns| now - retention_period_ns);
+
+        Self {
+            namespace_schema,
+            now,
+            min_timestamp,
+        }
+    }
+
+    /// Returns a description of why `line` cannot be written, if it
+    /// conflicts with the namespace.
+    pub(crate) fn validate(&self, line: &ParsedLine<'_>) -> Result<(), String> {
+        if let Some(min_timestamp) = self.min_timestamp {
+            if line.timestamp.unwrap_or(self.now) < min_timestamp {
+                return Err("data is outside of the retention period".to_string());
+            }
+        }
+
+        // Lines for tables that do not yet exist cannot conflict with the
+        // schema.
+        let Some(table) = self
+            .namespace_schema
+            .tables
+            .get(line.series.measurement.as_str())
+        else {
+            return Ok(());
+        };
+
+        for (name, _) in line.series.tag_set.iter().flatten() {
+            match table.columns.get(name.as_str()) {
+                Some(col) if !col.is_tag() => {
+                    return Err(type_conflict(name, col.column_type, ColumnType::Tag))
+                }
+                _ => {}
+            }
+        }
+
+        for (name, value) in &line.field_set {
+            match table.columns.get(name.as_str()) {
+                Some(col) if !col.matches_field_type(value) => {
+                    return Err(type_conflict(name, col.column_type, field_type(value)))
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn type_conflict(name: impl Display, existing: ColumnType, new: ColumnType) -> String {
+    format!("column {name} is type {existing} but write has type {new}")
+}
+
+fn field_type(value: &FieldValue<'_>) -> ColumnType {
+    match value {
+        FieldValue::I64(_) => ColumnType::I64,
+        FieldValue::U64(_) => ColumnType::U64,
+        FieldValue::F64(_) => ColumnType::F64,
+        FieldValue::String(_) => ColumnType::String,
+        FieldValue::Boolean(_) => ColumnType::Bool,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use data_types::{ColumnId, ColumnSchema, ColumnsByName, NamespaceId, TableId, TableSchema};
+    use influxdb_line_protocol::parse_lines;
+
+    use super::*;
+
+    const NOW: i64 = 1_000_000;
+
+    fn namespace_schema(retention_period_ns: Option<i64>) -> NamespaceSchema {
+        let columns = ColumnsByName::from(BTreeMap::from([
+            (
+                "host".to_string(),
+                ColumnSchema {
+                    id: ColumnId::new(1),
+                    column_type: ColumnType::Tag,
+                },
+            ),
+            (
+                "v".to_string(),
+                ColumnSchema {
+                    id: ColumnId::new(2),
+                    column_type: ColumnType::I64,
+                },
+            ),
+        ]));
+
+        NamespaceSchema {
+            id: NamespaceId::new(1),
+            tables: BTreeMap::from([(
+                "cpu".to_string(),
+                TableSchema {
+                    id: TableId::new(1),
+                    partition_template: None,
+                    columns,
+                },
+            )]),
+            max_columns_per_table: 500,
+            max_tables: 200,
+            query_limits: Default::default(),
+            retention_period_ns,
+            partition_template: None,
+        }
+    }
+
+    fn validate(schema: &NamespaceSchema, lp: &str) -> Result<(), String> {
+        let line = parse_lines(lp).next().unwrap().unwrap();
+        LineValidator::new(schema, NOW).validate(&line)
+    }
+
+    #[test]
+    fn test_validate_schema() {
+        let schema = namespace_schema(None);
+
+        assert!(validate(&schema, "cpu,host=a v=1i 1").is_ok());
+        assert!(validate(&schema, "cpu,host=a v=1i,new=1.0 1").is_ok());
+        assert!(validate(&schema, "mem,v=a host=1.0 1").is_ok());
+
+        assert_eq!(
+            validate(&schema, "cpu,host=a v=1.0 1").unwrap_err(),
+            "column v is type i64 but write has type f64"
+        );
+        assert_eq!(
+            validate(&schema, "cpu,v=a x=1i 1").unwrap_err(),
+            "column v is type i64 but write has type tag"
+        );
+        assert_eq!(
+            validate(&schema, "cpu host=\"a\" 1").unwrap_err(),
+            "column host is type tag but write has type string"
+        );
+    }
+
+    #[test]
+    fn test_validate_retention() {
+        let schema = namespace_schema(Some(100));
+
+        assert!(validate(&schema, "cpu v=1i 999950").is_ok());
+        // Lines without a timestamp are written at NOW.
+        assert!(validate(&schema, "cpu v=1i").is_ok());
+        assert_eq!(
+            validate(&schema, "cpu v=1i 1").unwrap_err(),
+            "data is outside of the retention period"
+        );
+
+        // No retention period.
+        assert!(validate(&namespace_schema(None), "cpu v=1i 1").is_ok());
+    }
+
+    #[test]
+    fn test_partial_write_error_capped() {
+        let rejected = (1..=MAX_REPORTED_LINES + 2)
+            .map(|line| mutable_batch_lp::Error::Rejected {
+                line,
+                reason: "bananas".to_string(),
+            })
+            .collect();
+
+        let err = PartialWriteError::new(3, rejected);
+        assert_eq!(err.num_written(), 3);
+        assert_eq!(err.num_rejected(), MAX_REPORTED_LINES + 2);
+        assert_eq!(err.rejected().len(), MAX_REPORTED_LINES);
+
+        let msg = err.to_string();
+        assert!(msg.starts_with(&format!(
+            "partial write: {} lines rejected, 3 lines written: line 1 rejected: bananas; ",
+            MAX_REPORTED_LINES + 2
+        )));
+        assert!(msg.ends_with("; 2 more rejected lines not shown"), "{msg}");
+    }
+}
@@ -147,6 +147,7 @@ async fn parse_v1(
     Ok(WriteParams {
         namespace,
         precision: write_params.precision,
+        partial_writes: write_params.partial_writes,
     })
 }
 
@@ -174,6 +175,7 @@ async fn parse_v2(
     Ok(WriteParams {
         namespace,
         precision: write_params.precision,
+        partial_writes: write_params.partial_writes,
     })
 }
 
@@ -310,9 +312,19 @@ mod tests {
     test_parse_v1!(
         no_rp,
         query_string = "?db=bananas",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, partial_writes }) => {
             assert_eq!(namespace.as_str(), "bananas");
             assert_matches!(precision, Precision::Nanoseconds);
+            assert!(!partial_writes);
+        }
+    );
+
+    test_parse_v1!(
+        partial_writes,
+        query_string = "?db=bananas&partial_writes=true",
+        want = Ok(WriteParams{ namespace, partial_writes, .. }) => {
+            assert_eq!(namespace.as_str(), "bananas");
+            assert!(partial_writes);
         }
     );
 
@@ -337,7 +349,7 @@ mod tests {
     test_parse_v1!(
         rp_empty,
         query_string = "?db=bananas&rp=",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "bananas");
             assert_matches!(precision, Precision::Nanoseconds);
         }
@@ -346,7 +358,7 @@ mod tests {
     test_parse_v1!(
         rp_empty_quotes,
         query_string = "?db=bananas&rp=''",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "bananas");
             assert_matches!(precision, Precision::Nanoseconds);
         }
@@ -355,7 +367,7 @@ mod tests {
     test_parse_v1!(
         rp_autogen,
         query_string = "?db=bananas&rp=autogen",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "bananas");
             assert_matches!(precision, Precision::Nanoseconds);
         }
@@ -364,7 +376,7 @@ mod tests {
     test_parse_v1!(
         rp_specified,
         query_string = "?db=bananas&rp=ageless",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "bananas/ageless");
             assert_matches!(precision, Precision::Nanoseconds);
         }
@@ -405,7 +417,7 @@ mod tests {
     test_parse_v1!(
         with_precision,
         query_string = "?db=bananas&rp=ageless&precision=ms",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "bananas/ageless");
             assert_matches!(precision, Precision::Milliseconds);
         }
@@ -452,7 +464,7 @@ mod tests {
     test_parse_v2!(
         bucket_only,
         query_string = "?bucket=bananas",
-        want = Ok(WriteParams{ namespace, precision }) => {
+        want = Ok(WriteParams{ namespace, precision, .. }) => {
             assert_eq!(namespace.as_str(), "bananas");
             assert_matches!(precision, Precision::Nanoseconds);
         }
@@ -506,10 +518,12 @@ mod tests {
         query_string = "?org=wat&bucket=bananas",
         want = Ok(WriteParams {
             namespace,
-            precision
+            precision,
+            partial_writes,
         }) => {
             assert_eq!(namespace.as_str(), "bananas");
             assert_matches!(precision, Precision::Nanoseconds);
+            assert!(!partial_writes);
         }
     );
 
@@ -559,10 +573,24 @@ mod tests {
         query_string = "?bucket=bananas&precision=ms",
         want = Ok(WriteParams {
             namespace,
-            precision
+            precision,
+            ..
         }) => {
             assert_eq!(namespace.as_str(), "bananas");
             assert_matches!(precision, Precision::Milliseconds);
         }
     );
+
+    test_parse_v2!(
+        partial_writes,
+        query_string = "?bucket=bananas&partial_writes=true",
+        want = Ok(WriteParams {
+            namespace,
+            partial_writes,
+            ..
+        }) => {
+            assert_eq!(namespace.as_str(), "bananas");
+            assert!(partial_writes);
+        }
+    );
 }
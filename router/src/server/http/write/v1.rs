@@ -76,6 +76,8 @@ pub(crate) struct WriteParamsV1 {
     pub(crate) precision: Precision,
     #[serde(default)]
     pub(crate) rp: RetentionPolicy,
+    #[serde(default)]
+    pub(crate) partial_writes: bool,
 
     // `username` is an optional v1 query parameter, but is ignored
     // in the CST spec, we treat the `p` parameter as a token
@@ -1,7 +1,7 @@
 use std::{iter, string::String, sync::Arc, time::Duration};
 
 use data_types::{DefaultPartitionTemplate, TableId};
-use generated_types::influxdata::iox::ingester::v1::WriteRequest;
+use generated_types::influxdata::iox::{delete::v1::DeleteRequest, ingester::v1::WriteRequest};
 use hashbrown::HashMap;
 use hyper::{Body, Request, Response};
 use iox_catalog::{
@@ -14,7 +14,7 @@ use router::{
     dml_handlers::{
         client::mock::MockWriteClient, Chain, DmlHandlerChainExt, FanOutAdaptor,
         InstrumentationDecorator, Partitioned, Partitioner, RetentionValidator, RpcWrite,
-        SchemaValidator,
+        SchemaValidator, TombstoneRecorder,
     },
     namespace_cache::{MemoryNamespaceCache, ReadThroughCache, ShardedCache},
     namespace_resolver::{MissingNamespaceAction, NamespaceAutocreation, NamespaceSchemaResolver},
@@ -105,17 +105,20 @@ type HttpDelegateStack = HttpDelegate<
         Chain<
             Chain<
                 Chain<
-                    RetentionValidator,
-                    SchemaValidator<
-                        Arc<ReadThroughCache<Arc<ShardedCache<Arc<MemoryNamespaceCache>>>>>,
+                    Chain<
+                        RetentionValidator,
+                        SchemaValidator<
+                            Arc<ReadThroughCache<Arc<ShardedCache<Arc<MemoryNamespaceCache>>>>>,
+                        >,
                     >,
+                    Partitioner,
+                >,
+                FanOutAdaptor<
+                    RpcWrite<Arc<MockWriteClient>>,
+                    Vec<Partitioned<HashMap<TableId, (String, MutableBatch)>>>,
                 >,
-                Partitioner,
-            >,
-            FanOutAdaptor<
-                RpcWrite<Arc<MockWriteClient>>,
-                Vec<Partitioned<HashMap<TableId, (String, MutableBatch)>>>,
             >,
+            TombstoneRecorder<()>,
         >,
     >,
     NamespaceAutocreation<
@@ -149,6 +152,8 @@ impl TestContext {
 
         let retention_validator = RetentionValidator::new();
 
+        let tombstone_recorder = TombstoneRecorder::new(Arc::clone(&catalog));
+
         let partitioner = Partitioner::new(DefaultPartitionTemplate::default());
 
         let namespace_resolver = NamespaceSchemaResolver::new(Arc::clone(&ns_cache));
@@ -164,7 +169,8 @@ impl TestContext {
         let handler_stack = retention_validator
             .and_then(schema_validator)
             .and_then(partitioner)
-            .and_then(parallel_write);
+            .and_then(parallel_write)
+            .and_then(tombstone_recorder);
 
         let handler_stack = InstrumentationDecorator::new("request", &metrics, handler_stack);
 
@@ -227,6 +233,12 @@ impl TestContext {
         self.client.calls()
     }
 
+    /// Get a list of delete requests made to the [`MockWriteClient`] write
+    /// client.
+    pub fn delete_calls(&self) -> Vec<DeleteRequest> {
+        self.client.delete_calls()
+    }
+
     /// Get a reference to the test context's metrics.
     pub fn metrics(&self) -> &metric::Registry {
         self.metrics.as_ref()
@@ -1,8 +1,13 @@
 use std::time::Duration;
 
 use assert_matches::assert_matches;
-use generated_types::influxdata::iox::namespace::v1::{
-    namespace_service_server::NamespaceService, *,
+use generated_types::influxdata::{
+    iox::{
+        namespace::v1::{namespace_service_server::NamespaceService, *},
+        partition_template::v1::{template_part, PartitionTemplate, TemplatePart},
+        table::v1::{table_service_server::TableService, CreateTableRequest},
+    },
+    pbdata::v1::DatabaseBatch,
 };
 use hyper::StatusCode;
 use iox_catalog::interface::{Error as CatalogError, SoftDeletedRows};
@@ -79,6 +84,7 @@ async fn test_namespace_create() {
     let req = CreateNamespaceRequest {
         name: "bananas_test".to_string(),
         retention_period_ns: Some(RETENTION),
+        partition_template: None,
     };
     let got = ctx
         .grpc_delegate()
@@ -151,6 +157,7 @@ async fn test_namespace_delete() {
     let req = CreateNamespaceRequest {
         name: "bananas_test".to_string(),
         retention_period_ns: Some(RETENTION),
+        partition_template: None,
     };
     let got = ctx
         .grpc_delegate()
@@ -280,6 +287,7 @@ async fn test_create_namespace_0_retention_period() {
     let req = CreateNamespaceRequest {
         name: "bananas_test".to_string(),
         retention_period_ns: Some(0), // A zero!
+        partition_template: None,
     };
     let got = ctx
         .grpc_delegate()
@@ -344,6 +352,7 @@ async fn test_create_namespace_negative_retention_period() {
     let req = CreateNamespaceRequest {
         name: "bananas_test".to_string(),
         retention_period_ns: Some(-42),
+        partition_template: None,
     };
     let err = ctx
         .grpc_delegate()
@@ -407,6 +416,7 @@ async fn test_update_namespace_0_retention_period() {
         .create_namespace(Request::new(CreateNamespaceRequest {
             name: "bananas_test".to_string(),
             retention_period_ns: Some(42),
+            partition_template: None,
         }))
         .await
         .expect("failed to create namespace")
@@ -511,6 +521,7 @@ async fn test_update_namespace_negative_retention_period() {
         .create_namespace(Request::new(CreateNamespaceRequest {
             name: "bananas_test".to_string(),
             retention_period_ns: Some(42),
+            partition_template: None,
         }))
         .await
         .expect("failed to create namespace")
@@ -780,6 +791,7 @@ async fn test_update_namespace_limit_0_max_tables_max_columns() {
         .create_namespace(Request::new(CreateNamespaceRequest {
             name: "bananas_test".to_string(),
             retention_period_ns: Some(0),
+            partition_template: None,
         }))
         .await
         .expect("failed to create namespace")
@@ -839,3 +851,94 @@ async fn test_update_namespace_limit_0_max_tables_max_columns() {
         });
     }
 }
+
+/// Ensure partition templates set via the gRPC NamespaceService and
+/// TableService are used to partition writes.
+#[tokio::test]
+async fn test_create_namespace_and_table_with_partition_template() {
+    let ctx = TestContextBuilder::default().build().await;
+
+    let template_part = |part| TemplatePart { part: Some(part) };
+
+    // Create a namespace partitioned by the "tag1" value and the year.
+    let namespace_template = PartitionTemplate {
+        parts: vec![
+            template_part(template_part::Part::ColumnValue("tag1".to_string())),
+            template_part(template_part::Part::TimeFormat("%Y".to_string())),
+        ],
+    };
+    let namespace = ctx
+        .grpc_delegate()
+        .namespace_service()
+        .create_namespace(Request::new(CreateNamespaceRequest {
+            name: "bananas_test".to_string(),
+            retention_period_ns: None,
+            partition_template: Some(namespace_template.clone()),
+        }))
+        .await
+        .expect("failed to create namespace")
+        .into_inner()
+        .namespace
+        .expect("no namespace in response");
+    assert_eq!(namespace.partition_template, Some(namespace_template));
+
+    // Create a table that overrides the namespace template with "tag2".
+    let table_template = PartitionTemplate {
+        parts: vec![template_part(template_part::Part::ColumnValue(
+            "tag2".to_string(),
+        ))],
+    };
+    let table = ctx
+        .grpc_delegate()
+        .table_service()
+        .create_table(Request::new(CreateTableRequest {
+            namespace: "bananas_test".to_string(),
+            name: "overridden".to_string(),
+            partition_template: Some(table_template.clone()),
+        }))
+        .await
+        .expect("failed to create table")
+        .into_inner()
+        .table
+        .expect("no table in response");
+    assert_eq!(table.partition_template, Some(table_template));
+
+    // "platanos" is created implicitly by the write and inherits the namespace
+    // template, while "overridden" uses its own.
+    let lp = "\
+        platanos,tag1=A,tag2=B val=42i 0\n\
+        overridden,tag1=A,tag2=B val=42i 0\n\
+    ";
+    let response = ctx
+        .write_lp("bananas", "test", lp)
+        .await
+        .expect("write failed");
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let platanos_id = ctx.table_id("bananas_test", "platanos").await.get();
+    let overridden_id = ctx.table_id("bananas_test", "overridden").await.get();
+    assert_eq!(overridden_id, table.id);
+
+    let mut partition_keys = ctx
+        .write_calls()
+        .into_iter()
+        .flat_map(|w| {
+            let DatabaseBatch {
+                partition_key,
+                table_batches,
+                ..
+            } = w.payload.expect("write must have a payload");
+            table_batches
+                .into_iter()
+                .map(move |b| (b.table_id, partition_key.clone()))
+        })
+        .collect::<Vec<_>>();
+    partition_keys.sort();
+
+    let mut want = vec![
+        (platanos_id, "tag1_A-1970".to_string()),
+        (overridden_id, "tag2_B".to_string()),
+    ];
+    want.sort();
+    assert_eq!(partition_keys, want);
+}
@@ -270,7 +270,7 @@ async fn test_write_propagate_ids() {
         .repositories()
         .await
         .namespaces()
-        .create("bananas_test", None)
+        .create("bananas_test", None, None)
         .await
         .expect("failed to update table limit");
 
@@ -341,27 +341,101 @@ async fn test_write_propagate_ids() {
 }
 
 #[tokio::test]
-async fn test_delete_unsupported() {
+async fn test_delete() {
     let ctx = TestContextBuilder::default()
         .with_autocreate_namespace(None)
         .build()
         .await;
 
-    // Create the namespace and a set of tables.
-    ctx.catalog()
+    // Create the namespace and a pair of tables.
+    let namespace = ctx
+        .catalog()
         .repositories()
         .await
         .namespaces()
-        .create("bananas_test", None)
+        .create("bananas_test", None, None)
         .await
-        .expect("failed to update table limit");
+        .expect("failed to create namespace");
+    for table in ["bananas", "platanos"] {
+        ctx.catalog()
+            .repositories()
+            .await
+            .tables()
+            .create_or_get(table, namespace.id)
+            .await
+            .expect("failed to create table");
+    }
+
+    let request = Request::builder()
+        .uri("https://bananas.example/api/v2/delete?org=bananas&bucket=test")
+        .method("POST")
+        .body(Body::from(
+            r#"{
+                "predicate": "_measurement=bananas and tag1=A",
+                "start": "1970-01-01T00:00:00Z",
+                "stop": "2070-01-02T00:00:00Z"
+            }"#,
+        ))
+        .expect("failed to construct HTTP request");
+
+    let response = ctx
+        .http_delegate()
+        .route(request)
+        .await
+        .expect("delete request failed");
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    // The delete must be sent to the ingesters, and not as a write.
+    assert!(ctx.write_calls().is_empty());
+    assert_matches!(ctx.delete_calls().as_slice(), [req] => {
+        let payload = req.payload.as_ref().expect("delete payload");
+        assert_eq!(payload.database_id, namespace.id.get());
+        assert_eq!(payload.table_name, "bananas");
+    });
+
+    // A tombstone should have been recorded for only the "bananas" table.
+    let tombstones = ctx
+        .catalog()
+        .repositories()
+        .await
+        .tombstones()
+        .list_by_namespace_id(namespace.id)
+        .await
+        .expect("query should succeed");
+    let table_id = ctx.table_id("bananas_test", "bananas").await;
+    assert_matches!(tombstones.as_slice(), [t] => {
+        assert_eq!(t.table_id, table_id);
+        assert_eq!(t.min_time.get(), 0);
+        assert_eq!(t.max_time.get(), 3155846400000000000);
+        assert_eq!(t.serialized_predicate, r#""tag1"='A'"#);
+    });
+
+    let histogram = ctx
+        .metrics()
+        .get_instrument::<Metric<DurationHistogram>>("dml_handler_delete_duration")
+        .expect("failed to read metric")
+        .get_observer(&Attributes::from(&[
+            ("handler", "request"),
+            ("result", "success"),
+        ]))
+        .expect("failed to get observer")
+        .fetch();
+    assert_eq!(histogram.sample_count(), 1);
+}
+
+#[tokio::test]
+async fn test_delete_invalid_predicate() {
+    let ctx = TestContextBuilder::default()
+        .with_autocreate_namespace(None)
+        .build()
+        .await;
 
     let request = Request::builder()
         .uri("https://bananas.example/api/v2/delete?org=bananas&bucket=test")
         .method("POST")
         .body(Body::from(
             r#"{
-                "predicate": "_measurement=bananas",
+                "predicate": "_measurement=bananas or tag1=A",
                 "start": "1970-01-01T00:00:00Z",
                 "stop": "2070-01-02T00:00:00Z"
             }"#,
@@ -370,14 +444,6 @@ async fn test_delete_unsupported() {
 
     let err = ctx.http_delegate().route(request).await.unwrap_err();
 
-    assert_matches!(
-        &err,
-        e @ router::server::http::Error::DeletesUnsupported => {
-            assert_eq!(
-                e.to_string(),
-                "deletes are not supported"
-            );
-        }
-    );
-    assert_eq!(err.as_status_code(), StatusCode::NOT_IMPLEMENTED);
+    assert_matches!(err, router::server::http::Error::ParseDelete(_));
+    assert_eq!(err.as_status_code(), StatusCode::BAD_REQUEST);
 }
@@ -1,5 +1,6 @@
 //! Routines for error handling
 use datafusion::error::DataFusionError;
+use iox_query::query_cancellation::QueryAbortedError;
 
 /// Converts a [`DataFusionError`] into the appropriate [`tonic::Code`]
 ///
@@ -53,6 +54,22 @@ pub fn datafusion_error_to_tonic_code(e: &DataFusionError) -> tonic::Code {
         | DataFusionError::NotImplemented(_)
         | DataFusionError::Plan(_) => tonic::Code::InvalidArgument,
         DataFusionError::Context(_,_) => unreachable!("handled in chain traversal above"),
+        // Authorization failures of statements, such as InfluxQL
+        // `DROP MEASUREMENT`, that check permissions when executed
+        DataFusionError::External(e) if matches!(e.downcast_ref(), Some(authz::Error::Forbidden)) => {
+            tonic::Code::PermissionDenied
+        }
+        DataFusionError::External(e) if matches!(e.downcast_ref(), Some(authz::Error::NoToken)) => {
+            tonic::Code::Unauthenticated
+        }
+        // Queries cancelled by the user or aborted after running for
+        // too long
+        DataFusionError::External(e) if matches!(e.downcast_ref(), Some(QueryAbortedError::Cancelled)) => {
+            tonic::Code::Cancelled
+        }
+        DataFusionError::External(e) if matches!(e.downcast_ref(), Some(QueryAbortedError::Timeout { .. })) => {
+            tonic::Code::DeadlineExceeded
+        }
         // Map as many as possible back into user visible
         // (non internal) errors and only treat the ones
         // the user likely can't do anything about as internal
@@ -102,6 +119,25 @@ mod test {
 
         do_transl_test(DataFusionError::Internal(s), tonic::Code::Internal);
 
+        do_transl_test(
+            DataFusionError::External(Box::new(authz::Error::Forbidden)),
+            tonic::Code::PermissionDenied,
+        );
+        do_transl_test(
+            DataFusionError::External(Box::new(authz::Error::NoToken)),
+            tonic::Code::Unauthenticated,
+        );
+        do_transl_test(
+            DataFusionError::External(Box::new(QueryAbortedError::Cancelled)),
+            tonic::Code::Cancelled,
+        );
+        do_transl_test(
+            DataFusionError::External(Box::new(QueryAbortedError::Timeout {
+                max_runtime: std::time::Duration::from_secs(1),
+            })),
+            tonic::Code::DeadlineExceeded,
+        );
+
         // traversal
         do_transl_test(
             DataFusionError::Context(
@@ -0,0 +1,258 @@
+//! Databases visible to InfluxQL queries.
+use std::{fmt::Debug, sync::Arc};
+
+use async_trait::async_trait;
+use authz::{Action, Authorizer, Permission, Resource};
+use data_types::DeletePredicate;
+use datafusion::{catalog::catalog::CatalogProvider, error::DataFusionError};
+use iox_query::exec::ExecutionContextProvider;
+use iox_query_influxql::frontend::database::{Database, DatabaseProvider};
+use trace::{ctx::SpanContext, span::SpanExt};
+
+use crate::{planner::Result, QueryNamespaceProvider};
+
+pub use iox_query_influxql::frontend::planner::statement_namespace;
+
+/// Presents the namespaces of a [`QueryNamespaceProvider`] as the databases
+/// of an InfluxQL query.
+///
+/// Only the namespaces the request token is authorised to read are visible.
+#[derive(Debug)]
+pub struct NamespaceDatabases<S> {
+    server: Arc<S>,
+    authz: Option<Arc<dyn Authorizer>>,
+    token: Option<Vec<u8>>,
+    default_database: Option<String>,
+    span_ctx: Option<SpanContext>,
+}
+
+impl<S> NamespaceDatabases<S>
+where
+    S: QueryNamespaceProvider,
+{
+    /// Create a new provider for a query against `default_database`, if
+    /// any, made with the authorization `token`.
+    pub fn new(
+        server: Arc<S>,
+        authz: Option<Arc<dyn Authorizer>>,
+        token: Option<Vec<u8>>,
+        default_database: Option<String>,
+        span_ctx: Option<SpanContext>,
+    ) -> Self {
+        Self {
+            server,
+            authz,
+            token,
+            default_database,
+            span_ctx,
+        }
+    }
+
+    /// Returns the subset of `names` the request token is authorised to read.
+    async fn authorized(&self, names: Vec<String>) -> Result<Vec<String>> {
+        let perms = names
+            .into_iter()
+            .map(|name| Permission::ResourceAction(Resource::Database(name), Action::Read))
+            .collect::<Vec<_>>();
+
+        let granted = self
+            .authz
+            .permissions(self.token.clone(), &perms)
+            .await
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+        Ok(granted
+            .into_iter()
+            .filter_map(|p| match p {
+                Permission::ResourceAction(Resource::Database(name), Action::Read) => Some(name),
+                _ => None,
+            })
+            .collect())
+    }
+
+    /// Returns an error unless the request token is authorised to delete
+    /// data from `database`.
+    async fn require_delete(&self, database: &str) -> Result<()> {
+        let perms = [Permission::ResourceAction(
+            Resource::Database(database.to_string()),
+            Action::Delete,
+        )];
+        self.authz
+            .require_any_permission(self.token.clone(), &perms)
+            .await
+            .map_err(|e| DataFusionError::External(Box::new(e)))
+    }
+}
+
+#[async_trait]
+impl<S> DatabaseProvider for NamespaceDatabases<S>
+where
+    S: QueryNamespaceProvider,
+{
+    fn default_database(&self) -> Option<&str> {
+        self.default_database.as_deref()
+    }
+
+    async fn databases(&self) -> Result<Vec<Database>> {
+        let namespaces = self
+            .server
+            .list_namespaces(self.span_ctx.child_span("list namespaces"))
+            .await;
+
+        let authorized = self
+            .authorized(namespaces.iter().map(|ns| ns.name.clone()).collect())
+            .await?;
+
+        Ok(namespaces
+            .into_iter()
+            .filter(|ns| authorized.contains(&ns.name))
+            .map(|ns| Database {
+                name: ns.name,
+                retention_period: ns.retention_period,
+            })
+            .collect())
+    }
+
+    async fn catalog(&self, name: &str) -> Result<Option<Arc<dyn CatalogProvider>>> {
+        if self.authorized(vec![name.to_string()]).await?.is_empty() {
+            return Ok(None);
+        }
+
+        let Some(db) = self
+            .server
+            .db(name, self.span_ctx.child_span("get namespace"))
+            .await else {
+            return Ok(None);
+        };
+
+        let ctx = db.new_query_context(self.span_ctx.clone());
+        let default_catalog = ctx
+            .inner()
+            .copied_config()
+            .options()
+            .catalog
+            .default_catalog
+            .clone();
+        Ok(ctx.inner().catalog(&default_catalog))
+    }
+
+    async fn drop_measurement(&self, database: &str, name: &str) -> Result<()> {
+        self.require_delete(database).await?;
+        self.server
+            .drop_table(database, name, self.span_ctx.child_span("drop table"))
+            .await
+    }
+
+    async fn delete(
+        &self,
+        database: &str,
+        measurement: &str,
+        predicate: &DeletePredicate,
+    ) -> Result<()> {
+        self.require_delete(database).await?;
+        self.server
+            .delete(
+                database,
+                measurement,
+                predicate,
+                self.span_ctx.child_span("delete"),
+            )
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TestDatabaseStore;
+    use assert_matches::assert_matches;
+
+    #[derive(Debug)]
+    struct MockAuthorizer;
+
+    #[async_trait]
+    impl Authorizer for MockAuthorizer {
+        async fn permissions(
+            &self,
+            _token: Option<Vec<u8>>,
+            perms: &[Permission],
+        ) -> std::result::Result<Vec<Permission>, authz::Error> {
+            Ok(perms
+                .iter()
+                .filter(|p| {
+                    !matches!(p, Permission::ResourceAction(Resource::Database(name), _) if name == "secret")
+                })
+                .cloned()
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_authorized_databases() {
+        let server = Arc::new(TestDatabaseStore::new());
+        server.db_or_create("foo").await;
+        server.db_or_create("secret").await;
+
+        let databases = NamespaceDatabases::new(
+            Arc::clone(&server),
+            Some(Arc::new(MockAuthorizer)),
+            None,
+            Some("foo".to_string()),
+            None,
+        );
+
+        assert_eq!(databases.default_database(), Some("foo"));
+        assert_eq!(
+            databases.databases().await.unwrap(),
+            vec![Database {
+                name: "foo".into(),
+                retention_period: None,
+            }]
+        );
+        assert!(databases.catalog("foo").await.unwrap().is_some());
+        assert!(databases.catalog("secret").await.unwrap().is_none());
+        assert!(databases.catalog("missing").await.unwrap().is_none());
+
+        // everything is visible without an authorizer
+        let databases = NamespaceDatabases::new(server, None, None, None, None);
+        assert_eq!(databases.default_database(), None);
+        assert_eq!(databases.databases().await.unwrap().len(), 2);
+        assert!(databases.catalog("secret").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_delete_requires_permission() {
+        let server = Arc::new(TestDatabaseStore::new());
+        server.db_or_create("secret").await;
+
+        let databases = NamespaceDatabases::new(
+            server,
+            Some(Arc::new(MockAuthorizer)),
+            None,
+            Some("secret".to_string()),
+            None,
+        );
+
+        let err = databases
+            .drop_measurement("secret", "cpu")
+            .await
+            .unwrap_err();
+        assert_matches!(
+            err,
+            DataFusionError::External(e) if matches!(e.downcast_ref(), Some(authz::Error::Forbidden))
+        );
+
+        let predicate = DeletePredicate {
+            range: data_types::TimestampRange::new(0, 10),
+            exprs: vec![],
+        };
+        let err = databases
+            .delete("secret", "cpu", &predicate)
+            .await
+            .unwrap_err();
+        assert_matches!(
+            err,
+            DataFusionError::External(e) if matches!(e.downcast_ref(), Some(authz::Error::Forbidden))
+        );
+    }
+}
@@ -1,14 +1,20 @@
 //! Common methods for RPC service implementations
 
 mod error;
+pub mod influxql;
 pub mod planner;
 pub mod test_util;
 
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use async_trait::async_trait;
-use iox_query::{exec::ExecutionContextProvider, QueryNamespace};
-use trace::span::Span;
+use data_types::DeletePredicate;
+use datafusion::error::DataFusionError;
+use iox_query::{
+    exec::{ExecutionContextProvider, IOxSessionContext},
+    QueryNamespace,
+};
+use trace::{ctx::SpanContext, span::Span};
 use tracker::InstrumentedAsyncOwnedSemaphorePermit;
 
 /// Trait that allows the query engine (which includes flight and storage/InfluxRPC) to access a
@@ -23,8 +29,72 @@ pub trait QueryNamespaceProvider: std::fmt::Debug + Send + Sync + 'static {
     /// Get namespace if it exists.
     async fn db(&self, name: &str, span: Option<Span>) -> Option<Arc<Self::Db>>;
 
-    /// Acquire concurrency-limiting sempahore
-    async fn acquire_semaphore(&self, span: Option<Span>) -> InstrumentedAsyncOwnedSemaphorePermit;
+    /// List the namespaces that can be queried.
+    async fn list_namespaces(&self, span: Option<Span>) -> Vec<NamespaceSummary>;
+
+    /// Create a query context that is not bound to a namespace, for statements such as InfluxQL
+    /// `SHOW DATABASES` that do not read from one.
+    fn new_query_context(&self, span_ctx: Option<SpanContext>) -> IOxSessionContext;
+
+    /// Drop the table `table` of `namespace`, along with all of its data.
+    async fn drop_table(
+        &self,
+        namespace: &str,
+        table: &str,
+        span: Option<Span>,
+    ) -> Result<(), DataFusionError>;
+
+    /// Delete the rows of `table` in `namespace` that match `predicate`.
+    async fn delete(
+        &self,
+        namespace: &str,
+        table: &str,
+        predicate: &DeletePredicate,
+        span: Option<Span>,
+    ) -> Result<(), DataFusionError>;
+
+    /// Acquire concurrency-limiting semaphores for a query against the namespace `namespace`.
+    ///
+    /// The returned permit must be held until the query completes.
+    async fn acquire_semaphore(&self, namespace: &str, span: Option<Span>) -> QueryPermit;
+}
+
+/// Semaphore permits that limit the number of concurrent queries, held for as long as a query
+/// runs.
+#[derive(Debug)]
+pub struct QueryPermit {
+    _permit: InstrumentedAsyncOwnedSemaphorePermit,
+    _namespace_permit: Option<InstrumentedAsyncOwnedSemaphorePermit>,
+}
+
+impl QueryPermit {
+    /// Hold `permit`, acquired from the semaphore shared by all queries.
+    pub fn new(permit: InstrumentedAsyncOwnedSemaphorePermit) -> Self {
+        Self {
+            _permit: permit,
+            _namespace_permit: None,
+        }
+    }
+
+    /// Also hold `permit`, acquired from a semaphore limiting the queries against a single
+    /// namespace.
+    pub fn with_namespace_permit(self, permit: InstrumentedAsyncOwnedSemaphorePermit) -> Self {
+        Self {
+            _namespace_permit: Some(permit),
+            ..self
+        }
+    }
+}
+
+/// The name and retention period of a namespace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamespaceSummary {
+    /// The name of the namespace.
+    pub name: String,
+
+    /// The retention period of the namespace, or `None` if data is retained
+    /// indefinitely.
+    pub retention_period: Option<Duration>,
 }
 
 pub use error::datafusion_error_to_tonic_code;
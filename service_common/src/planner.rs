@@ -7,13 +7,14 @@ use flightsql::{FlightSQLCommand, FlightSQLPlanner};
 use iox_query::{
     exec::IOxSessionContext,
     frontend::sql::SqlQueryPlanner,
+    params::StatementParams,
     plan::{fieldlist::FieldListPlan, seriesset::SeriesSetPlans, stringset::StringSetPlan},
     Aggregate, QueryNamespace, WindowDuration,
 };
 use iox_query_influxrpc::InfluxRpcPlanner;
 
 pub use datafusion::error::{DataFusionError as Error, Result};
-use iox_query_influxql::frontend::planner::InfluxQLQueryPlanner;
+use iox_query_influxql::frontend::{database::DatabaseProvider, planner::InfluxQLQueryPlanner};
 use predicate::rpc_predicate::InfluxRpcPredicate;
 
 /// Query planner that plans queries on a separate threadpool.
@@ -48,17 +49,21 @@ impl Planner {
     }
 
     /// Plan an InfluxQL query against the data in `database`, and return a
-    /// DataFusion physical execution plan.
+    /// DataFusion physical execution plan. The bind parameters of the query
+    /// are replaced by the values in `params`, and any other databases it
+    /// references are resolved using `databases`.
     pub async fn influxql(
         &self,
         query: impl Into<String> + Send,
+        params: StatementParams,
+        databases: Arc<dyn DatabaseProvider>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
         let planner = InfluxQLQueryPlanner::new();
         let query = query.into();
         let ctx = self.ctx.child_ctx("planner influxql");
 
         self.ctx
-            .run(async move { planner.query(&query, &ctx).await })
+            .run(async move { planner.query(&query, &params, databases, &ctx).await })
             .await
     }
 
@@ -1,14 +1,17 @@
 use std::{collections::BTreeMap, sync::Arc};
 
 use async_trait::async_trait;
-use iox_query::{exec::Executor, test::TestDatabase};
-use parking_lot::Mutex;
-use trace::span::Span;
-use tracker::{
-    AsyncSemaphoreMetrics, InstrumentedAsyncOwnedSemaphorePermit, InstrumentedAsyncSemaphore,
+use data_types::DeletePredicate;
+use datafusion::error::DataFusionError;
+use iox_query::{
+    exec::{Executor, ExecutorType, IOxSessionContext},
+    test::TestDatabase,
 };
+use parking_lot::Mutex;
+use trace::{ctx::SpanContext, span::Span};
+use tracker::{AsyncSemaphoreMetrics, InstrumentedAsyncSemaphore};
 
-use crate::QueryNamespaceProvider;
+use crate::{NamespaceSummary, QueryNamespaceProvider, QueryPermit};
 
 #[derive(Debug)]
 pub struct TestDatabaseStore {
@@ -67,10 +70,50 @@ impl QueryNamespaceProvider for TestDatabaseStore {
         databases.get(name).cloned()
     }
 
-    async fn acquire_semaphore(&self, span: Option<Span>) -> InstrumentedAsyncOwnedSemaphorePermit {
-        Arc::clone(&self.query_semaphore)
-            .acquire_owned(span)
-            .await
-            .unwrap()
+    async fn list_namespaces(&self, _span: Option<Span>) -> Vec<NamespaceSummary> {
+        let databases = self.databases.lock();
+
+        databases
+            .keys()
+            .map(|name| NamespaceSummary {
+                name: name.clone(),
+                retention_period: None,
+            })
+            .collect()
+    }
+
+    fn new_query_context(&self, span_ctx: Option<SpanContext>) -> IOxSessionContext {
+        self.executor
+            .new_execution_config(ExecutorType::Query)
+            .with_span_context(span_ctx)
+            .build()
+    }
+
+    async fn drop_table(
+        &self,
+        _namespace: &str,
+        _table: &str,
+        _span: Option<Span>,
+    ) -> Result<(), DataFusionError> {
+        Err(DataFusionError::NotImplemented("drop_table".to_string()))
+    }
+
+    async fn delete(
+        &self,
+        _namespace: &str,
+        _table: &str,
+        _predicate: &DeletePredicate,
+        _span: Option<Span>,
+    ) -> Result<(), DataFusionError> {
+        Err(DataFusionError::NotImplemented("delete".to_string()))
+    }
+
+    async fn acquire_semaphore(&self, _namespace: &str, span: Option<Span>) -> QueryPermit {
+        QueryPermit::new(
+            Arc::clone(&self.query_semaphore)
+                .acquire_owned(span)
+                .await
+                .unwrap(),
+        )
     }
 }
@@ -214,7 +214,7 @@ mod tests {
             let mut repos = catalog.repositories().await;
             let namespace = repos
                 .namespaces()
-                .create("catalog_partition_test", None)
+                .create("catalog_partition_test", None, None)
                 .await
                 .unwrap();
             let table = repos
@@ -277,7 +277,7 @@ mod tests {
             let mut repos = catalog.repositories().await;
             let namespace = repos
                 .namespaces()
-                .create("catalog_partition_test", None)
+                .create("catalog_partition_test", None, None)
                 .await
                 .unwrap();
             let table = repos
@@ -1,30 +1,39 @@
 //! Implements the InfluxDB IOx Flight API and Arrow FlightSQL, based
 //! on Arrow Flight and gRPC. See [`FlightService`] for full detail.
 
+mod prepared;
 mod request;
 
 use arrow::error::ArrowError;
 use arrow_flight::{
+    decode::FlightRecordBatchStream,
     encode::{FlightDataEncoder, FlightDataEncoderBuilder},
+    error::FlightError,
     flight_descriptor::DescriptorType,
     flight_service_server::{FlightService as Flight, FlightServiceServer as FlightServer},
     Action, ActionType, Criteria, Empty, FlightData, FlightDescriptor, FlightEndpoint, FlightInfo,
     HandshakeRequest, HandshakeResponse, PutResult, SchemaResult, Ticket,
 };
 use authz::{extract_token, Authorizer};
+use bytes::Bytes;
 use data_types::NamespaceNameError;
 use datafusion::{error::DataFusionError, physical_plan::ExecutionPlan};
-use flightsql::FlightSQLCommand;
+use flightsql::{DoPutPreparedStatementResult, FlightSQLCommand};
 use futures::{ready, Stream, StreamExt, TryStreamExt};
 use generated_types::influxdata::iox::querier::v1 as proto;
 use iox_query::{
     exec::{ExecutionContextProvider, IOxSessionContext},
-    QueryCompletedToken, QueryNamespace,
+    params::StatementParams,
+    CancelQueryError, QueryCompletedToken, QueryNamespace,
 };
 use observability_deps::tracing::{debug, info, warn};
+use prepared::params_from_batches;
 use prost::Message;
 use request::{IoxGetRequest, RunQuery};
-use service_common::{datafusion_error_to_tonic_code, planner::Planner, QueryNamespaceProvider};
+use service_common::{
+    datafusion_error_to_tonic_code, influxql::NamespaceDatabases, planner::Planner,
+    QueryNamespaceProvider, QueryPermit,
+};
 use snafu::{OptionExt, ResultExt, Snafu};
 use std::{fmt::Debug, pin::Pin, sync::Arc, task::Poll, time::Instant};
 use tonic::{
@@ -33,7 +42,6 @@ use tonic::{
 };
 use trace::{ctx::SpanContext, span::SpanExt};
 use trace_http::ctx::{RequestLogContext, RequestLogContextExt};
-use tracker::InstrumentedAsyncOwnedSemaphorePermit;
 
 /// The supported names of the grpc header that contain the target database
 /// for FlightSQL requests.
@@ -47,6 +55,11 @@ const IOX_FLIGHT_SQL_DATABASE_HEADERS: [&str; 4] = [
     "iox-namespace-name", // deprecated
 ];
 
+/// The type of the `DoAction` request that cancels a running query.
+///
+/// The body is a [`proto::CancelQueryRequest`].
+pub const CANCEL_QUERY_ACTION: &str = "CancelQuery";
+
 #[allow(clippy::enum_variant_names)]
 #[derive(Debug, Snafu)]
 pub enum Error {
@@ -59,6 +72,12 @@ pub enum Error {
     #[snafu(display("Invalid handshake. No payload provided"))]
     InvalidHandshake {},
 
+    #[snafu(display("Invalid DoPut request. No FlightDescriptor provided"))]
+    InvalidPut {},
+
+    #[snafu(display("Invalid prepared statement parameters: {}", source))]
+    InvalidParameters { source: DataFusionError },
+
     #[snafu(display("Database '{}' not found", namespace_name))]
     DatabaseNotFound { namespace_name: String },
 
@@ -115,6 +134,9 @@ pub enum Error {
 
     #[snafu(display("Authz error: {}", source))]
     Authz { source: authz::Error },
+
+    #[snafu(display("Error cancelling query: {}", source))]
+    CancelQuery { source: CancelQueryError },
 }
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
@@ -129,11 +151,13 @@ impl From<Error> for tonic::Status {
             Error::DatabaseNotFound { .. }
             | Error::InvalidTicket { .. }
             | Error::InvalidHandshake { .. }
+            | Error::InvalidPut { .. }
+            | Error::InvalidParameters { .. }
             | Error::Unauthenticated { .. }
             | Error::PermissionDenied { .. }
             // TODO(edd): this should be `debug`. Keeping at info while IOx in early development
             | Error::InvalidDatabaseName { .. } => info!(e=%err, msg),
-            Error::Query { .. } => info!(e=%err, msg),
+            Error::Query { .. } | Error::CancelQuery { .. } => info!(e=%err, msg),
             Error::Optimize { .. }
             | Error::TooManyFlightSQLDatabases { .. }
             | Error::NoFlightSQLDatabase
@@ -162,6 +186,8 @@ impl Error {
             Self::DatabaseNotFound { .. } => tonic::Code::NotFound,
             Self::InvalidTicket { .. }
             | Self::InvalidHandshake { .. }
+            | Self::InvalidPut { .. }
+            | Self::InvalidParameters { .. }
             | Self::Deserialization { .. }
             | Self::TooManyFlightSQLDatabases { .. }
             | Self::NoFlightSQLDatabase
@@ -173,6 +199,8 @@ impl Error {
             Self::UnsupportedMessageType { .. } => tonic::Code::Unimplemented,
             Self::FlightSQL { source } => match source {
                 flightsql::Error::InvalidHandle { .. }
+                | flightsql::Error::InvalidEncodedHandle { .. }
+                | flightsql::Error::InvalidHandleParams { .. }
                 | flightsql::Error::Decode { .. }
                 | flightsql::Error::Protocol { .. }
                 | flightsql::Error::UnsupportedMessageType { .. } => tonic::Code::InvalidArgument,
@@ -189,6 +217,11 @@ impl Error {
             }
             Self::Unauthenticated => tonic::Code::Unauthenticated,
             Self::PermissionDenied => tonic::Code::PermissionDenied,
+            Self::CancelQuery { source } => match source {
+                CancelQueryError::InvalidId { .. } => tonic::Code::InvalidArgument,
+                CancelQueryError::NotFound { .. } => tonic::Code::NotFound,
+                CancelQueryError::Completed { .. } => tonic::Code::FailedPrecondition,
+            },
         };
 
         tonic::Status::new(code, msg)
@@ -312,7 +345,7 @@ type TonicStream<T> = Pin<Box<dyn Stream<Item = Result<T, tonic::Status>> + Send
 ///       ┃                                                  ┃
 /// ```
 ///
-/// ## FlightSQL Prepared Statement (no bind parameters like $1, etc)
+/// ## FlightSQL Prepared Statement
 ///
 /// To run a prepared query, via FlightSQL, the client undertakes a
 /// few more steps:
@@ -331,6 +364,16 @@ type TonicStream<T> = Pin<Box<dyn Stream<Item = Result<T, tonic::Status>> + Send
 ///
 /// 5. Steps 5,6,7 proceed the same as for a FlightSQL ad-hoc query
 ///
+/// If the query has bind parameters like `$1`, the client calls the
+/// `DoPut` method with the same [`FlightDescriptor`] before step 4,
+/// sending a single row with a column per parameter. The `PutResult`
+/// contains a [`DoPutPreparedStatementResult`] with a new handle that
+/// carries the bound values, which the client uses in step 4 instead.
+/// As with the original handle, no state is kept by the server, so
+/// any querier can run it.
+///
+/// [`DoPutPreparedStatementResult`]: flightsql::DoPutPreparedStatementResult
+///
 /// ```text
 ///                                                      .───────.
 /// ╔═══════════╗                                       (         )
@@ -391,9 +434,11 @@ where
     async fn run_do_get(
         &self,
         span_ctx: Option<SpanContext>,
-        permit: InstrumentedAsyncOwnedSemaphorePermit,
+        permit: QueryPermit,
         query: &RunQuery,
+        params: &StatementParams,
         namespace: String,
+        authz_token: Option<Vec<u8>>,
     ) -> Result<Response<TonicStream<FlightData>>, tonic::Status> {
         let db = self
             .server
@@ -403,39 +448,92 @@ where
                 namespace_name: &namespace,
             })?;
 
-        let ctx = db.new_query_context(span_ctx);
-        let (query_completed_token, physical_plan) = match query {
+        let ctx = db.new_query_context(span_ctx.clone());
+        let (mut query_completed_token, physical_plan) = match query {
             RunQuery::Sql(sql_query) => {
                 let token = db.record_query(&ctx, "sql", Box::new(sql_query.clone()));
-                let plan = Planner::new(&ctx)
-                    .sql(sql_query)
-                    .await
-                    .context(PlanningSnafu)?;
+                let plan = Planner::new(&ctx).sql(sql_query).await;
                 (token, plan)
             }
             RunQuery::InfluxQL(sql_query) => {
                 let token = db.record_query(&ctx, "influxql", Box::new(sql_query.clone()));
+                let databases = Arc::new(NamespaceDatabases::new(
+                    Arc::clone(&self.server),
+                    self.authz.clone(),
+                    authz_token,
+                    Some(namespace.to_string()),
+                    span_ctx,
+                ));
                 let plan = Planner::new(&ctx)
-                    .influxql(sql_query)
-                    .await
-                    .context(PlanningSnafu)?;
+                    .influxql(sql_query, params.clone(), databases)
+                    .await;
                 (token, plan)
             }
             RunQuery::FlightSQL(msg) => {
                 let token = db.record_query(&ctx, "flightsql", Box::new(msg.to_string()));
                 let plan = Planner::new(&ctx)
                     .flight_sql_do_get(&namespace, db, msg.clone())
-                    .await
-                    .context(PlanningSnafu)?;
+                    .await;
                 (token, plan)
             }
         };
 
+        let physical_plan = physical_plan
+            .map_err(|e| {
+                query_completed_token.set_error(&e);
+                e
+            })
+            .context(PlanningSnafu)?;
+        query_completed_token.set_planned(physical_plan.as_ref());
+
         let output =
             GetStream::new(ctx, physical_plan, namespace, query_completed_token, permit).await?;
 
         Ok(Response::new(Box::pin(output) as TonicStream<FlightData>))
     }
+
+    /// Implementation of the `CancelQuery` action
+    async fn run_cancel_query(
+        &self,
+        span_ctx: Option<SpanContext>,
+        authz_token: Option<Vec<u8>>,
+        body: Bytes,
+        trace: &str,
+    ) -> Result<Response<TonicStream<arrow_flight::Result>>, tonic::Status> {
+        let proto::CancelQueryRequest {
+            namespace_name,
+            query_id,
+        } = proto::CancelQueryRequest::decode(body).context(DeserializationSnafu)?;
+
+        info!(%namespace_name, %query_id, %trace, "CancelQuery request");
+
+        let perms = [authz::Permission::ResourceAction(
+            authz::Resource::Database(namespace_name.clone()),
+            authz::Action::Read,
+        )];
+        self.authz
+            .require_any_permission(authz_token, &perms)
+            .await
+            .map_err(Error::from)?;
+
+        let db = self
+            .server
+            .db(&namespace_name, span_ctx.child_span("get namespace"))
+            .await
+            .context(DatabaseNotFoundSnafu {
+                namespace_name: &namespace_name,
+            })?;
+
+        db.cancel_query(&query_id).context(CancelQuerySnafu)?;
+        debug!(%namespace_name, %query_id, %trace, "Completed CancelQuery request");
+
+        let result = arrow_flight::Result {
+            body: proto::CancelQueryResponse {}.encode_to_vec().into(),
+        };
+        let stream = futures::stream::iter([Ok(result)]);
+
+        Ok(Response::new(stream.boxed()))
+    }
 }
 
 #[tonic::async_trait]
@@ -489,13 +587,16 @@ where
             )],
         };
         self.authz
-            .require_any_permission(authz_token, &perms)
+            .require_any_permission(authz_token.clone(), &perms)
             .await
             .map_err(Error::from)?;
 
         let permit = self
             .server
-            .acquire_semaphore(span_ctx.child_span("query rate limit semaphore"))
+            .acquire_semaphore(
+                namespace_name,
+                span_ctx.child_span("query rate limit semaphore"),
+            )
             .await;
 
         // Log after we acquire the permit and are about to start execution
@@ -509,7 +610,14 @@ where
         );
 
         let response = self
-            .run_do_get(span_ctx, permit, query, namespace_name.to_string())
+            .run_do_get(
+                span_ctx,
+                permit,
+                query,
+                request.params(),
+                namespace_name.to_string(),
+                authz_token,
+            )
             .await;
 
         if let Err(e) = &response {
@@ -651,13 +759,69 @@ where
         Ok(tonic::Response::new(flight_info))
     }
 
+    /// Handles `DoPut` RPC requests, which bind the parameter values
+    /// of a FlightSQL prepared statement. The [`FlightDescriptor`] of
+    /// the first message contains a `CommandPreparedStatementQuery`,
+    /// and the data is a single row with a column per parameter.
+    ///
+    /// see [`FlightService`] for more details.
     async fn do_put(
         &self,
-        _request: Request<Streaming<FlightData>>,
+        request: Request<Streaming<FlightData>>,
     ) -> Result<Response<Self::DoPutStream>, tonic::Status> {
-        info!("Handling flightsql do_put body");
+        let external_span_ctx: Option<RequestLogContext> = request.extensions().get().cloned();
+        let trace = external_span_ctx.format_jaeger();
 
-        Err(tonic::Status::unimplemented("Not yet implemented: do_put"))
+        let namespace_name = get_flightsql_namespace(request.metadata())?;
+        let authz_token = get_flight_authz(request.metadata());
+        let mut stream = request.into_inner();
+
+        // The descriptor is only sent with the first message
+        let first = stream.message().await?.context(InvalidPutSnafu)?;
+        let flight_descriptor = first.flight_descriptor.clone().context(InvalidPutSnafu)?;
+
+        // extract the FlightSQL message
+        let cmd = cmd_from_descriptor(flight_descriptor)?;
+        info!(%namespace_name, %cmd, %trace, "DoPut request");
+
+        let perms = flightsql_permissions(&namespace_name, &cmd);
+        self.authz
+            .require_any_permission(authz_token, &perms)
+            .await
+            .map_err(Error::from)?;
+
+        let handle = match cmd {
+            FlightSQLCommand::CommandPreparedStatementQuery(handle) => handle,
+            cmd => return Err(Error::unsupported_message_type(format!("DoPut with {cmd}")).into()),
+        };
+
+        let batches: Vec<_> = FlightRecordBatchStream::new_from_flight_data(
+            futures::stream::once(async { Ok(first) })
+                .chain(stream)
+                .map_err(FlightError::from),
+        )
+        .try_collect()
+        .await?;
+        let params = params_from_batches(&batches).context(InvalidParametersSnafu)?;
+
+        debug!(
+            %namespace_name,
+            %handle,
+            %trace,
+            num_params=params.len(),
+            "Completed DoPut request",
+        );
+
+        // Return the handle with the values bound to it, rather than
+        // keeping them here, so any querier can run the statement
+        let result = DoPutPreparedStatementResult::try_new(handle.with_params(params))
+            .context(FlightSQLSnafu)?;
+        let result = PutResult {
+            app_metadata: result.encode_to_vec().into(),
+        };
+        let stream = futures::stream::iter([Ok(result)]);
+
+        Ok(Response::new(stream.boxed()))
     }
 
     async fn do_action(
@@ -668,8 +832,15 @@ where
         let span_ctx: Option<SpanContext> = request.extensions().get().cloned();
         let trace = external_span_ctx.format_jaeger();
 
-        let namespace_name = get_flightsql_namespace(request.metadata())?;
         let authz_token = get_flight_authz(request.metadata());
+        if request.get_ref().r#type == CANCEL_QUERY_ACTION {
+            let body = request.into_inner().body;
+            return self
+                .run_cancel_query(span_ctx, authz_token, body, &trace)
+                .await;
+        }
+
+        let namespace_name = get_flightsql_namespace(request.metadata())?;
         let Action {
             r#type: action_type,
             body,
@@ -716,9 +887,23 @@ where
         &self,
         _request: Request<Empty>,
     ) -> Result<Response<Self::ListActionsStream>, tonic::Status> {
-        Err(tonic::Status::unimplemented(
-            "Not yet implemented: list_actions",
-        ))
+        let actions = [
+            ActionType {
+                r#type: CANCEL_QUERY_ACTION.to_string(),
+                description: "Cancel a running query by its id in system.queries".to_string(),
+            },
+            ActionType {
+                r#type: "CreatePreparedStatement".to_string(),
+                description: "Create a FlightSQL prepared statement".to_string(),
+            },
+            ActionType {
+                r#type: "ClosePreparedStatement".to_string(),
+                description: "Close a FlightSQL prepared statement".to_string(),
+            },
+        ];
+        let stream = futures::stream::iter(actions.map(Ok));
+
+        Ok(Response::new(stream.boxed()))
     }
 
     async fn do_exchange(
@@ -815,7 +1000,7 @@ fn flightsql_permissions(namespace_name: &str, cmd: &FlightSQLCommand) -> Vec<au
 struct GetStream {
     inner: FlightDataEncoder,
     #[allow(dead_code)]
-    permit: InstrumentedAsyncOwnedSemaphorePermit,
+    permit: QueryPermit,
     query_completed_token: QueryCompletedToken,
     done: bool,
 }
@@ -825,19 +1010,27 @@ impl GetStream {
         ctx: IOxSessionContext,
         physical_plan: Arc<dyn ExecutionPlan>,
         namespace_name: String,
-        query_completed_token: QueryCompletedToken,
-        permit: InstrumentedAsyncOwnedSemaphorePermit,
+        mut query_completed_token: QueryCompletedToken,
+        permit: QueryPermit,
     ) -> Result<Self, tonic::Status> {
         let app_metadata = proto::AppMetadata {};
 
         let schema = physical_plan.schema();
+        let result_counter = query_completed_token.result_counter();
 
         let query_results = ctx
             .execute_stream(Arc::clone(&physical_plan))
             .await
+            .map_err(|e| {
+                query_completed_token.set_error(&e);
+                e
+            })
             .context(QuerySnafu {
                 namespace_name: namespace_name.clone(),
-            })?
+            })?;
+        let query_results = query_completed_token
+            .abortable(query_results)
+            .inspect_ok(move |batch| result_counter.record(batch))
             .map_err(|e| {
                 let code = datafusion_error_to_tonic_code(&e);
                 tonic::Status::new(code, e.to_string()).into()
@@ -882,7 +1075,9 @@ impl Stream for GetStream {
                 }
                 Some(Err(e)) => {
                     self.done = true;
-                    return Poll::Ready(Some(Err(e.into())));
+                    let e: tonic::Status = e.into();
+                    self.query_completed_token.set_error(e.message());
+                    return Poll::Ready(Some(Err(e)));
                 }
             }
         }
@@ -1229,4 +1424,93 @@ mod tests {
         assert_code(&svc, tonic::Code::PermissionDenied, request("Bearer BAD")).await;
         assert_code(&svc, tonic::Code::Internal, request("Bearer UGLY")).await;
     }
+
+    #[tokio::test]
+    async fn cancel_query() {
+        let test_storage = Arc::new(TestDatabaseStore::default());
+        test_storage.clone().db_or_create("bananas").await;
+
+        let svc = FlightService {
+            server: Arc::clone(&test_storage),
+            authz: Some(Arc::new(MockAuthorizer {})),
+        };
+
+        async fn assert_code(
+            svc: &FlightService<TestDatabaseStore>,
+            want: tonic::Code,
+            request: tonic::Request<Action>,
+        ) {
+            let got = match svc.do_action(request).await {
+                Ok(_) => tonic::Code::Ok,
+                Err(e) => e.code(),
+            };
+            assert_eq!(want, got);
+        }
+
+        fn request(namespace_name: &str, authorization: &'static str) -> tonic::Request<Action> {
+            let body = proto::CancelQueryRequest {
+                namespace_name: namespace_name.to_string(),
+                query_id: "1234".to_string(),
+            };
+            let mut req = tonic::Request::new(Action {
+                r#type: CANCEL_QUERY_ACTION.to_string(),
+                body: body.encode_to_vec().into(),
+            });
+            if !authorization.is_empty() {
+                req.metadata_mut().insert(
+                    MetadataKey::from_static("authorization"),
+                    MetadataValue::from_static(authorization),
+                );
+            }
+            req
+        }
+
+        assert_code(&svc, tonic::Code::Unauthenticated, request("bananas", "")).await;
+        assert_code(
+            &svc,
+            tonic::Code::PermissionDenied,
+            request("bananas", "Bearer BAD"),
+        )
+        .await;
+        // the test database does not know of any queries
+        assert_code(
+            &svc,
+            tonic::Code::NotFound,
+            request("bananas", "Bearer GOOD"),
+        )
+        .await;
+        assert_code(
+            &svc,
+            tonic::Code::NotFound,
+            request("apples", "Bearer GOOD"),
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn list_actions() {
+        let svc = FlightService {
+            server: Arc::new(TestDatabaseStore::default()),
+            authz: Option::<Arc<dyn Authorizer>>::None,
+        };
+
+        let actions = svc
+            .list_actions(tonic::Request::new(Empty {}))
+            .await
+            .unwrap()
+            .into_inner()
+            .map_ok(|action| action.r#type)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            actions,
+            [
+                CANCEL_QUERY_ACTION,
+                "CreatePreparedStatement",
+                "ClosePreparedStatement"
+            ]
+        );
+    }
 }
@@ -0,0 +1,106 @@
+//! Parameters bound to FlightSQL prepared statements
+
+use arrow::record_batch::RecordBatch;
+use datafusion::{error::DataFusionError, scalar::ScalarValue};
+
+/// Returns the parameter values in `batches`, which must hold a single
+/// row with a column per parameter.
+pub(crate) fn params_from_batches(
+    batches: &[RecordBatch],
+) -> Result<Vec<ScalarValue>, DataFusionError> {
+    let num_rows: usize = batches.iter().map(|b| b.num_rows()).sum();
+    if num_rows != 1 {
+        return Err(DataFusionError::Plan(format!(
+            "expected a single row of parameter values, got {num_rows}"
+        )));
+    }
+
+    let batch = batches
+        .iter()
+        .find(|b| b.num_rows() == 1)
+        .expect("one batch has the row");
+    batch
+        .columns()
+        .iter()
+        .map(|array| ScalarValue::try_from_array(array, 0))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::array::{ArrayRef, Int64Array, StringArray};
+    use arrow_flight::sql::{Any, CommandPreparedStatementQuery};
+    use bytes::Bytes;
+    use flightsql::{FlightSQLCommand, PreparedStatementHandle};
+    use prost::Message;
+
+    use super::*;
+
+    fn prepared_handle(cmd: FlightSQLCommand) -> PreparedStatementHandle {
+        match cmd {
+            FlightSQLCommand::CommandPreparedStatementQuery(handle) => handle,
+            cmd => panic!("unexpected command: {cmd}"),
+        }
+    }
+
+    #[test]
+    fn test_handle_carries_params() {
+        let handle = PreparedStatementHandle::new("SELECT $1, $2".to_string()).with_params(vec![
+            ScalarValue::Int64(Some(42)),
+            ScalarValue::Utf8(Some("foo".to_string())),
+        ]);
+
+        let encoded = FlightSQLCommand::CommandPreparedStatementQuery(handle.clone())
+            .try_encode()
+            .unwrap();
+        let decoded = prepared_handle(FlightSQLCommand::try_decode(encoded).unwrap());
+        assert_eq!(decoded, handle);
+
+        // Handles without bound values roundtrip too
+        let handle = PreparedStatementHandle::new("SELECT 1".to_string());
+        let encoded = FlightSQLCommand::CommandPreparedStatementQuery(handle.clone())
+            .try_encode()
+            .unwrap();
+        let decoded = prepared_handle(FlightSQLCommand::try_decode(encoded).unwrap());
+        assert_eq!(decoded, handle);
+        assert!(decoded.params().is_empty());
+    }
+
+    #[test]
+    fn test_legacy_handle() {
+        // Handles issued by older versions are the raw query text
+        let cmd = CommandPreparedStatementQuery {
+            prepared_statement_handle: Bytes::from("SELECT 1"),
+        };
+        let encoded = Bytes::from(Any::pack(&cmd).unwrap().encode_to_vec());
+
+        let decoded = prepared_handle(FlightSQLCommand::try_decode(encoded).unwrap());
+        assert_eq!(decoded.query(), "SELECT 1");
+        assert!(decoded.params().is_empty());
+    }
+
+    #[test]
+    fn test_params_from_batches() {
+        let batch = RecordBatch::try_from_iter([
+            ("$1", Arc::new(Int64Array::from(vec![42])) as ArrayRef),
+            ("$2", Arc::new(StringArray::from(vec!["foo"])) as ArrayRef),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            params_from_batches(&[batch.slice(0, 0), batch.clone()]).unwrap(),
+            [
+                ScalarValue::Int64(Some(42)),
+                ScalarValue::Utf8(Some("foo".to_string()))
+            ]
+        );
+
+        let err = params_from_batches(&[batch.clone(), batch]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: expected a single row of parameter values, got 2"
+        );
+    }
+}
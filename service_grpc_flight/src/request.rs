@@ -4,11 +4,14 @@ use arrow_flight::Ticket;
 use bytes::Bytes;
 use flightsql::FlightSQLCommand;
 use generated_types::influxdata::iox::querier::v1 as proto;
+use generated_types::influxdata::iox::querier::v1::query_param_value::Value as ParamValue;
 use generated_types::influxdata::iox::querier::v1::read_info::QueryType;
+use iox_query::params::{StatementParam, StatementParams};
 use observability_deps::tracing::trace;
 use prost::Message;
 use serde::Deserialize;
 use snafu::{ResultExt, Snafu};
+use std::collections::HashMap;
 use std::fmt::{Debug, Display, Formatter};
 
 #[derive(Debug, Snafu)]
@@ -75,10 +78,23 @@ pub type Result<T, E = Error> = std::result::Result<T, E>;
 ///   "query_type": "influxql"
 /// }
 /// ```
+///
+/// This runs an InfluxQL query with the bind parameter `$host` set to `server01`
+///
+/// ```json
+/// {
+///   "namespace_name": "my_db",
+///   "sql_query": "SELECT usage_idle FROM cpu WHERE host = $host;"
+///   "query_type": "influxql",
+///   "params": { "host": "server01" }
+/// }
+/// ```
 #[derive(Debug, PartialEq, Clone)]
 pub struct IoxGetRequest {
     namespace_name: String,
     query: RunQuery,
+    /// Values of the bind parameters of an InfluxQL query.
+    params: StatementParams,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -119,9 +135,15 @@ impl IoxGetRequest {
         Self {
             namespace_name: namespace_name.into(),
             query,
+            params: StatementParams::default(),
         }
     }
 
+    /// Set the values of the bind parameters of an InfluxQL query
+    pub fn with_params(self, params: StatementParams) -> Self {
+        Self { params, ..self }
+    }
+
     /// try to decode a ReadInfo structure from a Token
     pub fn try_decode(ticket: Ticket) -> Result<Self> {
         // decode ticket
@@ -142,14 +164,21 @@ impl IoxGetRequest {
         let Self {
             namespace_name,
             query,
+            params,
         } = self;
 
+        let params = params
+            .into_iter()
+            .map(|(name, value)| (name, encode_param(value)))
+            .collect();
+
         let read_info = match query {
             RunQuery::Sql(sql_query) => proto::ReadInfo {
                 namespace_name,
                 sql_query,
                 query_type: QueryType::Sql.into(),
                 flightsql_command: vec![],
+                params,
             },
             RunQuery::InfluxQL(influxql) => proto::ReadInfo {
                 namespace_name,
@@ -157,6 +186,7 @@ impl IoxGetRequest {
                 sql_query: influxql,
                 query_type: QueryType::InfluxQl.into(),
                 flightsql_command: vec![],
+                params,
             },
             RunQuery::FlightSQL(flightsql_command) => proto::ReadInfo {
                 namespace_name,
@@ -166,6 +196,7 @@ impl IoxGetRequest {
                     .try_encode()
                     .context(FlightSQLSnafu)?
                     .into(),
+                params,
             },
         };
 
@@ -187,12 +218,16 @@ impl IoxGetRequest {
             sql_query: String,
             // If query type is not supplied, defaults to SQL
             query_type: Option<String>,
+            // Values of InfluxQL bind parameters
+            #[serde(default)]
+            params: HashMap<String, serde_json::Value>,
         }
 
         let ReadInfoJson {
             namespace_name,
             sql_query,
             query_type,
+            params,
         } = serde_json::from_str(&json_str).map_err(|e| format!("JSON parse error: {e}"))?;
 
         let query = if let Some(query_type) = query_type {
@@ -210,9 +245,23 @@ impl IoxGetRequest {
             RunQuery::Sql(sql_query)
         };
 
+        if !params.is_empty() && !matches!(query, RunQuery::InfluxQL(_)) {
+            return Err("params are only supported by InfluxQL queries".to_string());
+        }
+
+        let params = params
+            .into_iter()
+            .map(|(name, value)| {
+                let value = StatementParam::try_from(value)
+                    .map_err(|value| format!("invalid value for parameter {name}: {value}"))?;
+                Ok((name, value))
+            })
+            .collect::<Result<_, String>>()?;
+
         Ok(Self {
             namespace_name,
             query,
+            params,
         })
     }
 
@@ -226,10 +275,30 @@ impl IoxGetRequest {
             sql_query,
             query_type: _,
             flightsql_command,
+            params,
         } = read_info;
 
+        if !params.is_empty() && query_type != QueryType::InfluxQl {
+            return InvalidContentSnafu {
+                msg: "params are only supported by QueryType::InfluxQl",
+            }
+            .fail();
+        }
+
+        let params = params
+            .into_iter()
+            .map(|(name, value)| match decode_param(value) {
+                Some(value) => Ok((name, value)),
+                None => InvalidContentSnafu {
+                    msg: format!("parameter {name} has no value"),
+                }
+                .fail(),
+            })
+            .collect::<Result<_>>()?;
+
         Ok(Self {
             namespace_name,
+            params,
             query: match query_type {
                 QueryType::Unspecified | QueryType::Sql => {
                     if !flightsql_command.is_empty() {
@@ -271,6 +340,31 @@ impl IoxGetRequest {
     pub fn query(&self) -> &RunQuery {
         &self.query
     }
+
+    pub fn params(&self) -> &StatementParams {
+        &self.params
+    }
+}
+
+fn encode_param(value: StatementParam) -> proto::QueryParamValue {
+    let value = match value {
+        StatementParam::Boolean(v) => ParamValue::BoolValue(v),
+        StatementParam::Integer(v) => ParamValue::I64Value(v),
+        StatementParam::Unsigned(v) => ParamValue::U64Value(v),
+        StatementParam::Float(v) => ParamValue::F64Value(v),
+        StatementParam::String(v) => ParamValue::StringValue(v),
+    };
+    proto::QueryParamValue { value: Some(value) }
+}
+
+fn decode_param(value: proto::QueryParamValue) -> Option<StatementParam> {
+    Some(match value.value? {
+        ParamValue::BoolValue(v) => StatementParam::Boolean(v),
+        ParamValue::I64Value(v) => StatementParam::Integer(v),
+        ParamValue::U64Value(v) => StatementParam::Unsigned(v),
+        ParamValue::F64Value(v) => StatementParam::Float(v),
+        ParamValue::StringValue(v) => StatementParam::String(v),
+    })
 }
 
 #[cfg(test)]
@@ -310,6 +404,7 @@ mod tests {
                     expected: IoxGetRequest {
                         namespace_name: String::from(expected_namespace),
                         query: RunQuery::Sql(String::from(query)),
+                        params: Default::default(),
                     },
                 }
             }
@@ -320,6 +415,7 @@ mod tests {
                     expected: IoxGetRequest {
                         namespace_name: String::from(expected_namespace),
                         query: RunQuery::InfluxQL(String::from(query)),
+                        params: Default::default(),
                     },
                 }
             }
@@ -389,6 +485,38 @@ mod tests {
         assert_matches!(e, Error::Invalid);
     }
 
+    #[test]
+    fn json_ticket_decoding_params() {
+        let ticket = make_json_ticket(
+            r#"{"namespace_name": "my_db", "sql_query": "SELECT $f FROM cpu", "query_type": "influxql", "params": {"s": "a", "b": true, "i": -1, "u": 18446744073709551615, "f": 1.5}}"#,
+        );
+        let ri = IoxGetRequest::try_decode(ticket).unwrap();
+        assert_eq!(
+            ri.params,
+            StatementParams::from([
+                ("s".to_string(), StatementParam::String("a".into())),
+                ("b".to_string(), StatementParam::Boolean(true)),
+                ("i".to_string(), StatementParam::Integer(-1)),
+                ("u".to_string(), StatementParam::Unsigned(u64::MAX)),
+                ("f".to_string(), StatementParam::Float(1.5)),
+            ])
+        );
+
+        // params are not supported by SQL queries
+        let ticket = make_json_ticket(
+            r#"{"namespace_name": "my_db", "sql_query": "SELECT 1;", "params": {"s": "a"}}"#,
+        );
+        let e = IoxGetRequest::try_decode(ticket).unwrap_err();
+        assert_matches!(e, Error::Invalid);
+
+        // invalid parameter value
+        let ticket = make_json_ticket(
+            r#"{"namespace_name": "my_db", "sql_query": "SELECT $s FROM cpu", "query_type": "influxql", "params": {"s": ["a"]}}"#,
+        );
+        let e = IoxGetRequest::try_decode(ticket).unwrap_err();
+        assert_matches!(e, Error::Invalid);
+    }
+
     #[test]
     fn proto_ticket_decoding_unspecified() {
         let ticket = make_proto_ticket(&proto::ReadInfo {
@@ -396,6 +524,7 @@ mod tests {
             sql_query: "SELECT 1".to_string(),
             query_type: QueryType::Unspecified.into(),
             flightsql_command: vec![],
+            params: Default::default(),
         });
 
         // Reverts to default (unspecified) for invalid query_type enumeration, and thus SQL
@@ -411,6 +540,7 @@ mod tests {
             sql_query: "SELECT 1".to_string(),
             query_type: QueryType::Sql.into(),
             flightsql_command: vec![],
+            params: Default::default(),
         });
 
         let ri = IoxGetRequest::try_decode(ticket).unwrap();
@@ -425,6 +555,7 @@ mod tests {
             sql_query: "SELECT 1".to_string(),
             query_type: QueryType::InfluxQl.into(),
             flightsql_command: vec![],
+            params: Default::default(),
         });
 
         let ri = IoxGetRequest::try_decode(ticket).unwrap();
@@ -439,6 +570,7 @@ mod tests {
             sql_query: "SELECT 1".into(),
             query_type: 42, // not a known query type
             flightsql_command: vec![],
+            params: Default::default(),
         });
 
         // Reverts to default (unspecified) for invalid query_type enumeration, and thus SQL
@@ -455,6 +587,7 @@ mod tests {
             query_type: QueryType::Sql.into(),
             // can't have both sql_query and flightsql
             flightsql_command: vec![1, 2, 3],
+            params: Default::default(),
         });
 
         let e = IoxGetRequest::try_decode(ticket).unwrap_err();
@@ -469,6 +602,7 @@ mod tests {
             query_type: QueryType::InfluxQl.into(),
             // can't have both sql_query and flightsql
             flightsql_command: vec![1, 2, 3],
+            params: Default::default(),
         });
 
         let e = IoxGetRequest::try_decode(ticket).unwrap_err();
@@ -483,6 +617,7 @@ mod tests {
             query_type: QueryType::FlightSqlMessage.into(),
             // can't have both sql_query and flightsql
             flightsql_command: vec![1, 2, 3],
+            params: Default::default(),
         });
 
         let e = IoxGetRequest::try_decode(ticket).unwrap_err();
@@ -505,6 +640,7 @@ mod tests {
         let request = IoxGetRequest {
             namespace_name: "foo_blarg".into(),
             query: RunQuery::Sql("select * from bar".into()),
+            params: Default::default(),
         };
 
         let ticket = request.clone().try_encode().expect("encoding failed");
@@ -519,6 +655,7 @@ mod tests {
         let request = IoxGetRequest {
             namespace_name: "foo_blarg".into(),
             query: RunQuery::InfluxQL("select * from bar".into()),
+            params: Default::default(),
         };
 
         let ticket = request.clone().try_encode().expect("encoding failed");
@@ -528,6 +665,44 @@ mod tests {
         assert_eq!(request, roundtripped)
     }
 
+    #[test]
+    fn round_trip_influxql_params() {
+        let request = IoxGetRequest::new(
+            "foo_blarg",
+            RunQuery::InfluxQL("select * from bar where host = $host".into()),
+        )
+        .with_params(StatementParams::from([
+            ("host".to_string(), StatementParam::from("server01")),
+            ("n".to_string(), StatementParam::from(1_u64)),
+        ]));
+
+        let ticket = request.clone().try_encode().expect("encoding failed");
+
+        let roundtripped = IoxGetRequest::try_decode(ticket).expect("decode failed");
+
+        assert_eq!(request, roundtripped)
+    }
+
+    #[test]
+    fn proto_ticket_decoding_sql_params() {
+        let ticket = make_proto_ticket(&proto::ReadInfo {
+            namespace_name: "<foo>_<bar>".to_string(),
+            sql_query: "SELECT 1".to_string(),
+            query_type: QueryType::Sql.into(),
+            flightsql_command: vec![],
+            // params are only supported by InfluxQL
+            params: HashMap::from([(
+                "host".to_string(),
+                proto::QueryParamValue {
+                    value: Some(ParamValue::StringValue("server01".into())),
+                },
+            )]),
+        });
+
+        let e = IoxGetRequest::try_decode(ticket).unwrap_err();
+        assert_matches!(e, Error::Invalid);
+    }
+
     #[test]
     fn round_trip_flightsql() {
         let cmd = FlightSQLCommand::CommandStatementQuery(CommandStatementQuery {
@@ -537,6 +712,7 @@ mod tests {
         let request = IoxGetRequest {
             namespace_name: "foo_blarg".into(),
             query: RunQuery::FlightSQL(cmd),
+            params: Default::default(),
         };
 
         let ticket = request.clone().try_encode().expect("encoding failed");
@@ -1,4 +1,5 @@
 use std::{
+    fmt::Display,
     pin::Pin,
     task::{Context, Poll},
 };
@@ -6,7 +7,8 @@ use std::{
 use futures::{ready, Stream, StreamExt};
 use iox_query::QueryCompletedToken;
 
-/// Wraps an inner query stream, calling the `QueryCompletedToken::set_success` on success
+/// Wraps an inner query stream, calling the `QueryCompletedToken::set_success` on success and
+/// `QueryCompletedToken::set_error` with the first error
 pub struct QueryCompletedTokenStream<S, T, E>
 where
     S: Stream<Item = Result<T, E>> + Unpin,
@@ -32,6 +34,7 @@ where
 impl<S, T, E> Stream for QueryCompletedTokenStream<S, T, E>
 where
     S: Stream<Item = Result<T, E>> + Unpin,
+    E: Display,
 {
     type Item = Result<T, E>;
 
@@ -47,6 +50,9 @@ where
             }
             Some(Ok(x)) => Poll::Ready(Some(Ok(x))),
             Some(Err(e)) => {
+                if !this.found_err {
+                    this.token.set_error(&e);
+                }
                 this.found_err = true;
                 Poll::Ready(Some(Err(e)))
             }
@@ -66,41 +72,45 @@ mod tests {
 
     use super::*;
 
+    type Outcome = Arc<Mutex<Option<(bool, Option<String>)>>>;
+
     #[tokio::test]
     async fn test_empty() {
         let (res, token) = token();
         let stream =
-            QueryCompletedTokenStream::new(futures::stream::empty::<Result<(), ()>>(), token);
+            QueryCompletedTokenStream::new(futures::stream::empty::<Result<(), &str>>(), token);
 
         assert_eq!(stream.collect::<Vec<_>>().await, vec![],);
-        assert_eq!(*res.lock(), Some(true));
+        assert_eq!(*res.lock(), Some((true, None)));
     }
 
     #[tokio::test]
     async fn test_not_finished() {
         let (res, token) = token();
-        QueryCompletedTokenStream::new(futures::stream::empty::<Result<(), ()>>(), token);
-        assert_eq!(*res.lock(), Some(false));
+        QueryCompletedTokenStream::new(futures::stream::empty::<Result<(), &str>>(), token);
+        assert_eq!(*res.lock(), Some((false, None)));
     }
 
     #[tokio::test]
     async fn test_err() {
         let (res, token) = token();
-        let stream =
-            QueryCompletedTokenStream::new(futures::stream::iter([Ok(()), Err(()), Ok(())]), token);
+        let stream = QueryCompletedTokenStream::new(
+            futures::stream::iter([Ok(()), Err("first"), Err("second"), Ok(())]),
+            token,
+        );
 
         assert_eq!(
             stream.collect::<Vec<_>>().await,
-            vec![Ok(()), Err(()), Ok(())],
+            vec![Ok(()), Err("first"), Err("second"), Ok(())],
         );
-        assert_eq!(*res.lock(), Some(false));
+        assert_eq!(*res.lock(), Some((false, Some("first".to_string()))));
     }
 
-    fn token() -> (Arc<Mutex<Option<bool>>>, QueryCompletedToken) {
+    fn token() -> (Outcome, QueryCompletedToken) {
         let token = Arc::new(Mutex::new(None));
         let token_captured = Arc::clone(&token);
-        let qct = QueryCompletedToken::new(move |success| {
-            *token_captured.lock() = Some(success);
+        let qct = QueryCompletedToken::new(move |success, stats| {
+            *token_captured.lock() = Some((success, stats.error));
         });
         (token, qct)
     }
@@ -15,7 +15,7 @@ use crate::{
 };
 use data_types::NamespaceName;
 use datafusion::error::DataFusionError;
-use futures::{stream::BoxStream, Stream, StreamExt, TryStreamExt};
+use futures::{stream::BoxStream, Future, Stream, StreamExt, TryStreamExt};
 use generated_types::{
     google::protobuf::{Any as ProtoAny, Empty},
     influxdata::platform::errors::InfluxDbError,
@@ -36,11 +36,13 @@ use iox_query::{
         fieldlist::FieldList, seriesset::converter::Error as SeriesSetError,
         ExecutionContextProvider, IOxSessionContext,
     },
-    QueryCompletedToken, QueryNamespace, QueryText,
+    QueryAbortedError, QueryCompletedToken, QueryNamespace, QueryText,
 };
 use observability_deps::tracing::{error, info, trace};
 use prost::{bytes::BytesMut, Message};
-use service_common::{datafusion_error_to_tonic_code, planner::Planner, QueryNamespaceProvider};
+use service_common::{
+    datafusion_error_to_tonic_code, planner::Planner, QueryNamespaceProvider, QueryPermit,
+};
 use snafu::{OptionExt, ResultExt, Snafu};
 use std::{
     collections::{BTreeSet, HashMap},
@@ -51,7 +53,6 @@ use tokio_stream::wrappers::ReceiverStream;
 use tonic::{metadata::MetadataMap, Response, Status};
 use trace::{ctx::SpanContext, span::SpanExt};
 use trace_http::ctx::{RequestLogContext, RequestLogContextExt};
-use tracker::InstrumentedAsyncOwnedSemaphorePermit;
 
 /// The size to which we limit our [`ReadResponse`] payloads.
 ///
@@ -191,6 +192,9 @@ pub enum Error {
 
     #[snafu(display("Operation not yet implemented:  {}", operation))]
     NotYetImplemented { operation: String },
+
+    #[snafu(context(false), display("{}", source))]
+    Aborted { source: QueryAbortedError },
 }
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
@@ -248,6 +252,12 @@ impl Error {
                 tonic::Code::Internal
             }
             Self::NotYetImplemented { .. } => tonic::Code::Unimplemented,
+            Self::Aborted {
+                source: QueryAbortedError::Cancelled,
+            } => tonic::Code::Cancelled,
+            Self::Aborted {
+                source: QueryAbortedError::Timeout { .. },
+            } => tonic::Code::DeadlineExceeded,
         };
 
         // InfluxRPC clients expect an instance of InfluxDbError
@@ -375,11 +385,11 @@ where
         let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
 
         let req = req.into_inner();
+        let db_name = get_namespace_name(&req)?;
         let permit = self
             .db_store
-            .acquire_semaphore(span_ctx.child_span("query rate limit semaphore"))
+            .acquire_semaphore(&db_name, span_ctx.child_span("query rate limit semaphore"))
             .await;
-        let db_name = get_namespace_name(&req)?;
         info!(
             %db_name,
             ?req.range,
@@ -397,9 +407,12 @@ where
         let ctx = db.new_query_context(span_ctx);
         let query_completed_token = db.record_query(&ctx, "read_filter", defer_json(&req));
 
-        let frames = read_filter_impl(Arc::clone(&db), db_name, req, &ctx)
-            .await?
-            .map_err(|e| e.into_status());
+        let frames = abortable(
+            &query_completed_token,
+            read_filter_impl(Arc::clone(&db), db_name, req, &ctx),
+        )
+        .await?;
+        let frames = abortable_stream(&query_completed_token, frames).map_err(|e| e.into_status());
 
         make_response(
             ChunkReadResponses::new(frames, MAX_READ_RESPONSE_SIZE),
@@ -418,13 +431,12 @@ where
         let external_span_ctx: Option<RequestLogContext> = req.extensions().get().cloned();
         let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
         let req = req.into_inner();
+        let db_name = get_namespace_name(&req)?;
         let permit = self
             .db_store
-            .acquire_semaphore(span_ctx.child_span("query rate limit semaphore"))
+            .acquire_semaphore(&db_name, span_ctx.child_span("query rate limit semaphore"))
             .await;
 
-        let db_name = get_namespace_name(&req)?;
-
         info!(
             %db_name,
             ?req.range,
@@ -464,18 +476,21 @@ where
         let gby_agg = expr::make_read_group_aggregate(aggregate, group, group_keys)
             .context(ConvertingReadGroupAggregateSnafu { aggregate_string })?;
 
-        let frames = query_group_impl(
-            Arc::clone(&db),
-            db_name,
-            range,
-            predicate,
-            gby_agg,
-            TagKeyMetaNames::Text,
-            &ctx,
+        let frames = abortable(
+            &query_completed_token,
+            query_group_impl(
+                Arc::clone(&db),
+                db_name,
+                range,
+                predicate,
+                gby_agg,
+                TagKeyMetaNames::Text,
+                &ctx,
+            ),
         )
         .await
-        .map_err(|e| e.into_status())?
-        .map_err(|e| e.into_status());
+        .map_err(|e| e.into_status())?;
+        let frames = abortable_stream(&query_completed_token, frames).map_err(|e| e.into_status());
 
         make_response(
             ChunkReadResponses::new(frames, MAX_READ_RESPONSE_SIZE),
@@ -494,12 +509,12 @@ where
         let external_span_ctx: Option<RequestLogContext> = req.extensions().get().cloned();
         let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
         let req = req.into_inner();
+        let db_name = get_namespace_name(&req)?;
         let permit = self
             .db_store
-            .acquire_semaphore(span_ctx.child_span("query rate limit semaphore"))
+            .acquire_semaphore(&db_name, span_ctx.child_span("query rate limit semaphore"))
             .await;
 
-        let db_name = get_namespace_name(&req)?;
         info!(
             %db_name,
             ?req.range,
@@ -540,18 +555,21 @@ where
         let gby_agg = expr::make_read_window_aggregate(aggregate, window_every, offset, window)
             .context(ConvertingWindowAggregateSnafu { aggregate_string })?;
 
-        let frames = query_group_impl(
-            Arc::clone(&db),
-            db_name,
-            range,
-            predicate,
-            gby_agg,
-            TagKeyMetaNames::from_i32(tag_key_meta_names).unwrap_or_default(),
-            &ctx,
+        let frames = abortable(
+            &query_completed_token,
+            query_group_impl(
+                Arc::clone(&db),
+                db_name,
+                range,
+                predicate,
+                gby_agg,
+                TagKeyMetaNames::from_i32(tag_key_meta_names).unwrap_or_default(),
+                &ctx,
+            ),
         )
         .await
-        .map_err(|e| e.into_status())?
-        .map_err(|e| e.into_status());
+        .map_err(|e| e.into_status())?;
+        let frames = abortable_stream(&query_completed_token, frames).map_err(|e| e.into_status());
 
         make_response(
             ChunkReadResponses::new(frames, MAX_READ_RESPONSE_SIZE),
@@ -576,12 +594,12 @@ where
         let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
 
         let req = req.into_inner();
+        let db_name = get_namespace_name(&req)?;
         let permit = self
             .db_store
-            .acquire_semaphore(span_ctx.child_span("query rate limit semaphore"))
+            .acquire_semaphore(&db_name, span_ctx.child_span("query rate limit semaphore"))
             .await;
 
-        let db_name = get_namespace_name(&req)?;
         info!(
             %db_name,
             ?req.range,
@@ -607,13 +625,16 @@ where
 
         let measurement = None;
 
-        let response = tag_keys_impl(
-            Arc::clone(&db),
-            db_name,
-            measurement,
-            range,
-            predicate,
-            &ctx,
+        let response = abortable(
+            &query_completed_token,
+            tag_keys_impl(
+                Arc::clone(&db),
+                db_name,
+                measurement,
+                range,
+                predicate,
+                &ctx,
+            ),
         )
         .await
         .map_err(|e| e.into_status());
@@ -641,12 +662,12 @@ where
         let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
 
         let req = req.into_inner();
+        let db_name = get_namespace_name(&req)?;
         let permit = self
             .db_store
-            .acquire_semaphore(span_ctx.child_span("query rate limit semaphore"))
+            .acquire_semaphore(&db_name, span_ctx.child_span("query rate limit semaphore"))
             .await;
 
-        let db_name = get_namespace_name(&req)?;
         let tag_key = DecodedTagKey::try_from(req.tag_key.clone())
             .context(ConvertingTagKeyInTagValuesSnafu)?;
         info!(
@@ -688,12 +709,18 @@ where
                     .into_status());
                 }
 
-                measurement_name_impl(Arc::clone(&db), db_name, range, predicate, &ctx).await
+                abortable(
+                    &query_completed_token,
+                    measurement_name_impl(Arc::clone(&db), db_name, range, predicate, &ctx),
+                )
+                .await
             }
             DecodedTagKey::Field => {
-                let fieldlist =
-                    field_names_impl(Arc::clone(&db), db_name, None, range, predicate, &ctx)
-                        .await?;
+                let fieldlist = abortable(
+                    &query_completed_token,
+                    field_names_impl(Arc::clone(&db), db_name, None, range, predicate, &ctx),
+                )
+                .await?;
 
                 // Pick out the field names into a Vec<Vec<u8>>for return
                 let values = fieldlist
@@ -705,14 +732,17 @@ where
                 Ok(StringValuesResponse { values })
             }
             DecodedTagKey::Normal(tag_key) => {
-                tag_values_impl(
-                    Arc::clone(&db),
-                    db_name,
-                    tag_key,
-                    measurement,
-                    range,
-                    predicate,
-                    &ctx,
+                abortable(
+                    &query_completed_token,
+                    tag_values_impl(
+                        Arc::clone(&db),
+                        db_name,
+                        tag_key,
+                        measurement,
+                        range,
+                        predicate,
+                        &ctx,
+                    ),
                 )
                 .await
             }
@@ -743,12 +773,12 @@ where
         let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
 
         let req = req.into_inner();
+        let db_name = get_namespace_name(&req)?;
         let permit = self
             .db_store
-            .acquire_semaphore(span_ctx.child_span("query rate limit semaphore"))
+            .acquire_semaphore(&db_name, span_ctx.child_span("query rate limit semaphore"))
             .await;
 
-        let db_name = get_namespace_name(&req)?;
         info!(
             %db_name,
             ?req.measurement_patterns,
@@ -771,13 +801,15 @@ where
             defer_json(&req),
         );
 
-        let results =
-            tag_values_grouped_by_measurement_and_tag_key_impl(Arc::clone(&db), db_name, req, &ctx)
-                .await
-                .map_err(|e| e.into_status())?
-                .into_iter()
-                .map(Ok)
-                .collect::<Vec<_>>();
+        let results = abortable(
+            &query_completed_token,
+            tag_values_grouped_by_measurement_and_tag_key_impl(Arc::clone(&db), db_name, req, &ctx),
+        )
+        .await
+        .map_err(|e| e.into_status())?
+        .into_iter()
+        .map(Ok)
+        .collect::<Vec<_>>();
 
         make_response(
             futures::stream::iter(results),
@@ -853,12 +885,12 @@ where
         let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
 
         let req = req.into_inner();
+        let db_name = get_namespace_name(&req)?;
         let permit = self
             .db_store
-            .acquire_semaphore(span_ctx.child_span("query rate limit semaphore"))
+            .acquire_semaphore(&db_name, span_ctx.child_span("query rate limit semaphore"))
             .await;
 
-        let db_name = get_namespace_name(&req)?;
         info!(
             %db_name,
             ?req.range,
@@ -882,9 +914,12 @@ where
             predicate,
         } = req;
 
-        let response = measurement_name_impl(Arc::clone(&db), db_name, range, predicate, &ctx)
-            .await
-            .map_err(|e| e.into_status());
+        let response = abortable(
+            &query_completed_token,
+            measurement_name_impl(Arc::clone(&db), db_name, range, predicate, &ctx),
+        )
+        .await
+        .map_err(|e| e.into_status());
 
         make_response(
             futures::stream::once(async move { response }).boxed(),
@@ -909,12 +944,12 @@ where
         let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
 
         let req = req.into_inner();
+        let db_name = get_namespace_name(&req)?;
         let permit = self
             .db_store
-            .acquire_semaphore(span_ctx.child_span("query rate limit semaphore"))
+            .acquire_semaphore(&db_name, span_ctx.child_span("query rate limit semaphore"))
             .await;
 
-        let db_name = get_namespace_name(&req)?;
         info!(
             %db_name,
             ?req.range,
@@ -942,13 +977,16 @@ where
 
         let measurement = Some(measurement);
 
-        let response = tag_keys_impl(
-            Arc::clone(&db),
-            db_name,
-            measurement,
-            range,
-            predicate,
-            &ctx,
+        let response = abortable(
+            &query_completed_token,
+            tag_keys_impl(
+                Arc::clone(&db),
+                db_name,
+                measurement,
+                range,
+                predicate,
+                &ctx,
+            ),
         )
         .await
         .map_err(|e| e.into_status());
@@ -976,12 +1014,12 @@ where
         let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
 
         let req = req.into_inner();
+        let db_name = get_namespace_name(&req)?;
         let permit = self
             .db_store
-            .acquire_semaphore(span_ctx.child_span("query rate limit semaphore"))
+            .acquire_semaphore(&db_name, span_ctx.child_span("query rate limit semaphore"))
             .await;
 
-        let db_name = get_namespace_name(&req)?;
         info!(
             %db_name,
             ?req.range,
@@ -1012,14 +1050,17 @@ where
 
         let measurement = Some(measurement);
 
-        let response = tag_values_impl(
-            Arc::clone(&db),
-            db_name,
-            tag_key,
-            measurement,
-            range,
-            predicate,
-            &ctx,
+        let response = abortable(
+            &query_completed_token,
+            tag_values_impl(
+                Arc::clone(&db),
+                db_name,
+                tag_key,
+                measurement,
+                range,
+                predicate,
+                &ctx,
+            ),
         )
         .await
         .map_err(|e| e.into_status());
@@ -1047,12 +1088,12 @@ where
         let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
 
         let req = req.into_inner();
+        let db_name = get_namespace_name(&req)?;
         let permit = self
             .db_store
-            .acquire_semaphore(span_ctx.child_span("query rate limit semaphore"))
+            .acquire_semaphore(&db_name, span_ctx.child_span("query rate limit semaphore"))
             .await;
 
-        let db_name = get_namespace_name(&req)?;
         info!(
             %db_name,
             ?req.range,
@@ -1080,13 +1121,16 @@ where
 
         let measurement = Some(measurement);
 
-        let response = field_names_impl(
-            Arc::clone(&db),
-            db_name,
-            measurement,
-            range,
-            predicate,
-            &ctx,
+        let response = abortable(
+            &query_completed_token,
+            field_names_impl(
+                Arc::clone(&db),
+                db_name,
+                measurement,
+                range,
+                predicate,
+                &ctx,
+            ),
         )
         .await
         .map(|fieldlist| {
@@ -1699,10 +1743,35 @@ impl<T, E: std::fmt::Debug> ErrorLogger for Result<T, E> {
 
 /// Return the stream of results as a gRPC (tonic) response
 #[allow(clippy::type_complexity)]
+/// Run `fut`, failing once the query of `token` is cancelled or exceeds its maximum runtime.
+///
+/// `fut` is dropped on abort, which stops the execution of any plan it runs.
+async fn abortable<F, T>(token: &QueryCompletedToken, fut: F) -> Result<T>
+where
+    F: Future<Output = Result<T>> + Send,
+{
+    tokio::select! {
+        // Check for an abort first, so that an aborted query is not run at all.
+        biased;
+        e = token.aborted() => Err(e.into()),
+        res = fut => res,
+    }
+}
+
+/// Wrap the results `stream` of the query of `token` so that it fails once the query is
+/// cancelled or exceeds its maximum runtime.
+fn abortable_stream<S, T>(token: &QueryCompletedToken, stream: S) -> BoxStream<'static, Result<T>>
+where
+    S: Stream<Item = Result<T>> + Send + 'static,
+    T: Send + 'static,
+{
+    token.abortable_stream(stream.boxed(), Error::from)
+}
+
 pub fn make_response<S, T, E>(
     stream: S,
     token: QueryCompletedToken,
-    permit: InstrumentedAsyncOwnedSemaphorePermit,
+    permit: QueryPermit,
 ) -> Result<Response<StreamWithPermit<QueryCompletedTokenStream<S, T, E>>>, Status>
 where
     S: Stream<Item = Result<T, E>> + Unpin,
@@ -1810,6 +1879,38 @@ mod tests {
         OrgAndBucket::new(NonZeroU64::new(123).unwrap(), NonZeroU64::new(456).unwrap())
     }
 
+    #[tokio::test]
+    async fn test_storage_rpc_cancelled() {
+        test_helpers::maybe_start_logging();
+        // Start a test gRPC server on a randomally allocated port
+        let mut fixture = Fixture::new().await.expect("Connecting to test server");
+
+        let db_info = org_and_bucket();
+
+        let chunk = TestChunk::new("h2o")
+            .with_id(0)
+            .with_predicate_match(PredicateMatch::AtLeastOneNonNullField);
+
+        let db = fixture.test_storage.db_or_create(db_info.db_name()).await;
+        db.add_chunk("my_partition_key", Arc::new(chunk));
+        // Cancel the query while it runs, as a `CancelQuery` request would
+        db.cancel_new_queries();
+
+        let request = MeasurementNamesRequest {
+            source: Some(StorageClient::read_source(&db_info, 1)),
+            range: None,
+            predicate: None,
+        };
+
+        let status = fixture
+            .storage_client
+            .measurement_names(request)
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Cancelled);
+        assert_contains!(status.message(), "Query was cancelled");
+    }
+
     #[tokio::test]
     async fn test_storage_rpc_measurement_names() {
         test_helpers::maybe_start_logging();
@@ -1,11 +1,14 @@
 //! Implementation of the namespace gRPC service
 use std::sync::Arc;
 
-use data_types::{Namespace as CatalogNamespace, NamespaceName};
-use generated_types::influxdata::iox::namespace::v1::{
-    update_namespace_service_protection_limit_request::LimitUpdate, *,
+use data_types::{Column, ColumnType, Namespace as CatalogNamespace, NamespaceName, Table};
+use generated_types::{
+    google::FromOptionalField,
+    influxdata::iox::namespace::v1::{
+        update_namespace_service_protection_limit_request::LimitUpdate, *,
+    },
 };
-use iox_catalog::interface::{Catalog, SoftDeletedRows};
+use iox_catalog::interface::{Catalog, RepoCollection, SoftDeletedRows};
 use observability_deps::tracing::{debug, info, warn};
 use tonic::{Request, Response, Status};
 
@@ -53,6 +56,7 @@ impl namespace_service_server::NamespaceService for NamespaceService {
         let CreateNamespaceRequest {
             name: namespace_name,
             retention_period_ns,
+            partition_template,
         } = request.into_inner();
 
         // Ensure the namespace name is consistently processed within IOx - this
@@ -61,12 +65,18 @@ impl namespace_service_server::NamespaceService for NamespaceService {
             .map_err(|v| Status::invalid_argument(v.to_string()))?;
 
         let retention_period_ns = map_retention_period(retention_period_ns)?;
+        let partition_template = partition_template.optional("partition_template")?;
 
-        debug!(%namespace_name, ?retention_period_ns, "Creating namespace");
+        debug!(
+            %namespace_name,
+            ?retention_period_ns,
+            ?partition_template,
+            "Creating namespace"
+        );
 
         let namespace = repos
             .namespaces()
-            .create(&namespace_name, retention_period_ns)
+            .create(&namespace_name, partition_template, retention_period_ns)
             .await
             .map_err(|e| {
                 warn!(error=%e, %namespace_name, "failed to create namespace");
@@ -104,6 +114,35 @@ impl namespace_service_server::NamespaceService for NamespaceService {
         Ok(Response::new(Default::default()))
     }
 
+    async fn undelete_namespace(
+        &self,
+        request: Request<UndeleteNamespaceRequest>,
+    ) -> Result<Response<UndeleteNamespaceResponse>, Status> {
+        let namespace_name = request.into_inner().name;
+
+        let namespace = self
+            .catalog
+            .repositories()
+            .await
+            .namespaces()
+            .undelete(&namespace_name)
+            .await
+            .map_err(|e| {
+                warn!(error=%e, %namespace_name, "failed to undelete namespace");
+                status_from_catalog_namespace_error(e)
+            })?;
+
+        info!(
+            %namespace_name,
+            namespace_id = %namespace.id,
+            "undeleted namespace"
+        );
+
+        Ok(Response::new(UndeleteNamespaceResponse {
+            namespace: Some(namespace_to_proto(namespace)),
+        }))
+    }
+
     async fn update_namespace_retention(
         &self,
         request: Request<UpdateNamespaceRetentionRequest>,
@@ -202,6 +241,54 @@ impl namespace_service_server::NamespaceService for NamespaceService {
                         status_from_catalog_namespace_error(e)
                     })
             }
+            Some(LimitUpdate::MaxConcurrentQueries(n)) => {
+                let new_max = map_query_limit(n, "concurrent query")?;
+                repos
+                    .namespaces()
+                    .update_concurrent_query_limit(&namespace_name, new_max)
+                    .await
+                    .map_err(|e| {
+                        warn!(
+                            error = %e,
+                            %namespace_name,
+                            concurrent_query_limit = ?new_max,
+                            "failed to update concurrent query limit for namespace",
+                        );
+                        status_from_catalog_namespace_error(e)
+                    })
+            }
+            Some(LimitUpdate::MaxQueryMemoryBytes(n)) => {
+                let new_max = map_query_limit(n, "query memory")?;
+                repos
+                    .namespaces()
+                    .update_query_memory_limit(&namespace_name, new_max)
+                    .await
+                    .map_err(|e| {
+                        warn!(
+                            error = %e,
+                            %namespace_name,
+                            query_memory_limit = ?new_max,
+                            "failed to update query memory limit for namespace",
+                        );
+                        status_from_catalog_namespace_error(e)
+                    })
+            }
+            Some(LimitUpdate::MaxTotalQueryMemoryBytes(n)) => {
+                let new_max = map_query_limit(n, "total query memory")?;
+                repos
+                    .namespaces()
+                    .update_total_query_memory_limit(&namespace_name, new_max)
+                    .await
+                    .map_err(|e| {
+                        warn!(
+                            error = %e,
+                            %namespace_name,
+                            total_query_memory_limit = ?new_max,
+                            "failed to update total query memory limit for namespace",
+                        );
+                        status_from_catalog_namespace_error(e)
+                    })
+            }
             None => Err(Status::invalid_argument(
                 "unsupported service protection limit change requested",
             )),
@@ -212,6 +299,9 @@ impl namespace_service_server::NamespaceService for NamespaceService {
             namespace_id = %namespace.id,
             max_tables = %namespace.max_tables,
             max_columns_per_table = %namespace.max_columns_per_table,
+            max_concurrent_queries = ?namespace.max_concurrent_queries,
+            max_query_memory_bytes = ?namespace.max_query_memory_bytes,
+            max_total_query_memory_bytes = ?namespace.max_total_query_memory_bytes,
             "updated namespace service protection limits",
         );
 
@@ -221,6 +311,180 @@ impl namespace_service_server::NamespaceService for NamespaceService {
             },
         ))
     }
+
+    async fn delete_table(
+        &self,
+        request: Request<DeleteTableRequest>,
+    ) -> Result<Response<DeleteTableResponse>, Status> {
+        let mut repos = self.catalog.repositories().await;
+
+        let DeleteTableRequest {
+            namespace: namespace_name,
+            table: table_name,
+        } = request.into_inner();
+
+        let table = get_table(repos.as_mut(), &namespace_name, &table_name).await?;
+
+        repos.tables().soft_delete(table.id).await.map_err(|e| {
+            warn!(error=%e, %namespace_name, %table_name, "failed to soft-delete table");
+            Status::internal(e.to_string())
+        })?;
+
+        info!(%namespace_name, %table_name, table_id = %table.id, "soft-deleted table");
+
+        Ok(Response::new(Default::default()))
+    }
+
+    async fn undelete_table(
+        &self,
+        request: Request<UndeleteTableRequest>,
+    ) -> Result<Response<UndeleteTableResponse>, Status> {
+        let mut repos = self.catalog.repositories().await;
+
+        let UndeleteTableRequest {
+            namespace: namespace_name,
+            table: table_name,
+        } = request.into_inner();
+
+        let table = get_table(repos.as_mut(), &namespace_name, &table_name).await?;
+
+        repos.tables().undelete(table.id).await.map_err(|e| {
+            warn!(error=%e, %namespace_name, %table_name, "failed to undelete table");
+            Status::internal(e.to_string())
+        })?;
+
+        info!(%namespace_name, %table_name, table_id = %table.id, "undeleted table");
+
+        Ok(Response::new(Default::default()))
+    }
+
+    async fn delete_column(
+        &self,
+        request: Request<DeleteColumnRequest>,
+    ) -> Result<Response<DeleteColumnResponse>, Status> {
+        let mut repos = self.catalog.repositories().await;
+
+        let DeleteColumnRequest {
+            namespace: namespace_name,
+            table: table_name,
+            column: column_name,
+        } = request.into_inner();
+
+        let table = get_table(repos.as_mut(), &namespace_name, &table_name).await?;
+        let column = get_column(repos.as_mut(), &table, &column_name).await?;
+
+        // Every table must have a time column.
+        if column.column_type == ColumnType::Time {
+            return Err(Status::invalid_argument(
+                "the time column cannot be deleted",
+            ));
+        }
+
+        repos.columns().soft_delete(column.id).await.map_err(|e| {
+            warn!(
+                error=%e,
+                %namespace_name,
+                %table_name,
+                %column_name,
+                "failed to soft-delete column"
+            );
+            Status::internal(e.to_string())
+        })?;
+
+        info!(
+            %namespace_name,
+            %table_name,
+            %column_name,
+            column_id = %column.id,
+            "soft-deleted column"
+        );
+
+        Ok(Response::new(Default::default()))
+    }
+
+    async fn undelete_column(
+        &self,
+        request: Request<UndeleteColumnRequest>,
+    ) -> Result<Response<UndeleteColumnResponse>, Status> {
+        let mut repos = self.catalog.repositories().await;
+
+        let UndeleteColumnRequest {
+            namespace: namespace_name,
+            table: table_name,
+            column: column_name,
+        } = request.into_inner();
+
+        let table = get_table(repos.as_mut(), &namespace_name, &table_name).await?;
+        let column = get_column(repos.as_mut(), &table, &column_name).await?;
+
+        repos.columns().undelete(column.id).await.map_err(|e| {
+            warn!(
+                error=%e,
+                %namespace_name,
+                %table_name,
+                %column_name,
+                "failed to undelete column"
+            );
+            Status::internal(e.to_string())
+        })?;
+
+        info!(
+            %namespace_name,
+            %table_name,
+            %column_name,
+            column_id = %column.id,
+            "undeleted column"
+        );
+
+        Ok(Response::new(Default::default()))
+    }
+}
+
+/// Look up the table `table_name` of the namespace `namespace_name`, including
+/// soft-deleted tables.
+async fn get_table(
+    repos: &mut dyn RepoCollection,
+    namespace_name: &str,
+    table_name: &str,
+) -> Result<Table, Status> {
+    let namespace = repos
+        .namespaces()
+        .get_by_name(namespace_name, SoftDeletedRows::ExcludeDeleted)
+        .await
+        .map_err(|e| Status::internal(e.to_string()))?
+        .ok_or_else(|| Status::not_found(format!("namespace {namespace_name} not found")))?;
+
+    repos
+        .tables()
+        .get_by_namespace_and_name(namespace.id, table_name)
+        .await
+        .map_err(|e| Status::internal(e.to_string()))?
+        .ok_or_else(|| {
+            Status::not_found(format!(
+                "table {table_name} not found in namespace {namespace_name}"
+            ))
+        })
+}
+
+/// Look up the column `column_name` of `table`, including soft-deleted columns.
+async fn get_column(
+    repos: &mut dyn RepoCollection,
+    table: &Table,
+    column_name: &str,
+) -> Result<Column, Status> {
+    repos
+        .columns()
+        .list_by_table_id(table.id)
+        .await
+        .map_err(|e| Status::internal(e.to_string()))?
+        .into_iter()
+        .find(|c| c.name == column_name)
+        .ok_or_else(|| {
+            Status::not_found(format!(
+                "column {column_name} not found in table {}",
+                table.name
+            ))
+        })
 }
 
 fn namespace_to_proto(namespace: CatalogNamespace) -> Namespace {
@@ -230,18 +494,16 @@ fn namespace_to_proto(namespace: CatalogNamespace) -> Namespace {
         retention_period_ns: namespace.retention_period_ns,
         max_tables: namespace.max_tables,
         max_columns_per_table: namespace.max_columns_per_table,
+        partition_template: namespace.partition_template.as_ref().map(Into::into),
+        max_concurrent_queries: namespace.max_concurrent_queries,
+        max_query_memory_bytes: namespace.max_query_memory_bytes,
+        max_total_query_memory_bytes: namespace.max_total_query_memory_bytes,
     }
 }
 
 fn namespace_to_create_response_proto(namespace: CatalogNamespace) -> CreateNamespaceResponse {
     CreateNamespaceResponse {
-        namespace: Some(Namespace {
-            id: namespace.id.get(),
-            name: namespace.name.clone(),
-            retention_period_ns: namespace.retention_period_ns,
-            max_tables: namespace.max_tables,
-            max_columns_per_table: namespace.max_columns_per_table,
-        }),
+        namespace: Some(namespace_to_proto(namespace)),
     }
 }
 
@@ -262,11 +524,34 @@ fn map_retention_period(v: Option<i64>) -> Result<Option<i64>, Status> {
     }
 }
 
+/// Map a user-submitted query limit to the correct internal encoding.
+///
+/// 0 is mapped to [`None`], removing the limit. Negative limits are rejected
+/// with an error.
+fn map_query_limit<T>(v: T, limit: &str) -> Result<Option<T>, Status>
+where
+    T: Default + PartialOrd,
+{
+    let zero = T::default();
+    if v == zero {
+        Ok(None)
+    } else if v > zero {
+        Ok(Some(v))
+    } else {
+        Err(Status::invalid_argument(format!(
+            "invalid negative {limit} limit"
+        )))
+    }
+}
+
 fn status_from_catalog_namespace_error(err: iox_catalog::interface::Error) -> Status {
     match err {
         iox_catalog::interface::Error::NamespaceNotFoundByName { .. } => {
             Status::not_found(err.to_string())
         }
+        iox_catalog::interface::Error::NamespaceNotDeleted { .. } => {
+            Status::failed_precondition(err.to_string())
+        }
         _ => Status::internal(err.to_string()),
     }
 }
@@ -276,7 +561,10 @@ mod tests {
     use std::time::Duration;
 
     use assert_matches::assert_matches;
-    use generated_types::influxdata::iox::namespace::v1::namespace_service_server::NamespaceService as _;
+    use generated_types::influxdata::iox::{
+        namespace::v1::namespace_service_server::NamespaceService as _,
+        partition_template::v1::{template_part, PartitionTemplate, TemplatePart},
+    };
     use iox_catalog::mem::MemCatalog;
     use tonic::Code;
 
@@ -346,6 +634,7 @@ mod tests {
         let req = CreateNamespaceRequest {
             name: NS_NAME.to_string(),
             retention_period_ns: Some(RETENTION),
+            partition_template: None,
         };
         let created_ns = handler
             .create_namespace(Request::new(req))
@@ -458,6 +747,116 @@ mod tests {
                 .namespaces;
             assert_matches!(current.as_slice(), []);
         }
+
+        // Undeleting the namespace should restore it
+        let undeleted_ns = handler
+            .undelete_namespace(Request::new(UndeleteNamespaceRequest {
+                name: NS_NAME.to_string(),
+            }))
+            .await
+            .expect("must undelete")
+            .into_inner()
+            .namespace
+            .expect("no namespace in response");
+        assert_eq!(undeleted_ns.id, created_ns.id);
+        {
+            let current = handler
+                .get_namespaces(Request::new(Default::default()))
+                .await
+                .expect("must return namespaces")
+                .into_inner()
+                .namespaces;
+            assert_matches!(current.as_slice(), [ns] => {
+                assert_eq!(ns, &undeleted_ns);
+            })
+        }
+
+        // Undeleting a namespace that is not deleted fails
+        let status = handler
+            .undelete_namespace(Request::new(UndeleteNamespaceRequest {
+                name: NS_NAME.to_string(),
+            }))
+            .await
+            .expect_err("undeleting a live namespace should fail");
+        assert_eq!(status.code(), Code::FailedPrecondition);
+
+        // Undeleting an unknown namespace fails
+        let status = handler
+            .undelete_namespace(Request::new(UndeleteNamespaceRequest {
+                name: "platanos".to_string(),
+            }))
+            .await
+            .expect_err("undeleting an unknown namespace should fail");
+        assert_eq!(status.code(), Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_create_with_partition_template() {
+        let catalog: Arc<dyn Catalog> =
+            Arc::new(MemCatalog::new(Arc::new(metric::Registry::default())));
+
+        let handler = NamespaceService::new(catalog);
+
+        let template = PartitionTemplate {
+            parts: vec![
+                TemplatePart {
+                    part: Some(template_part::Part::ColumnValue("region".to_string())),
+                },
+                TemplatePart {
+                    part: Some(template_part::Part::TimeFormat("%Y-%m-%d %H".to_string())),
+                },
+            ],
+        };
+
+        let created_ns = handler
+            .create_namespace(Request::new(CreateNamespaceRequest {
+                name: NS_NAME.to_string(),
+                retention_period_ns: None,
+                partition_template: Some(template.clone()),
+            }))
+            .await
+            .expect("failed to create namespace")
+            .into_inner()
+            .namespace
+            .expect("no namespace in response");
+        assert_eq!(created_ns.partition_template, Some(template));
+
+        // The template is returned when listing namespaces
+        let current = handler
+            .get_namespaces(Request::new(Default::default()))
+            .await
+            .expect("must return namespaces")
+            .into_inner()
+            .namespaces;
+        assert_matches!(current.as_slice(), [ns] => {
+            assert_eq!(ns, &created_ns);
+        });
+
+        // A template without any parts is rejected
+        let status = handler
+            .create_namespace(Request::new(CreateNamespaceRequest {
+                name: "platanos".to_string(),
+                retention_period_ns: None,
+                partition_template: Some(PartitionTemplate { parts: vec![] }),
+            }))
+            .await
+            .expect_err("empty partition template should be rejected");
+        assert_eq!(status.code(), Code::InvalidArgument);
+
+        // As is a time format that cannot be rendered
+        let status = handler
+            .create_namespace(Request::new(CreateNamespaceRequest {
+                name: "platanos".to_string(),
+                retention_period_ns: None,
+                partition_template: Some(PartitionTemplate {
+                    parts: vec![TemplatePart {
+                        part: Some(template_part::Part::TimeFormat("%Q".to_string())),
+                    }],
+                }),
+            }))
+            .await
+            .expect_err("invalid time format should be rejected");
+        assert_eq!(status.code(), Code::InvalidArgument);
     }
 
     #[tokio::test]
@@ -469,6 +868,7 @@ mod tests {
         let req = CreateNamespaceRequest {
             name: NS_NAME.to_string(),
             retention_period_ns: Some(RETENTION),
+            partition_template: None,
         };
         let created_ns = handler
             .create_namespace(Request::new(req))
@@ -509,6 +909,205 @@ mod tests {
         assert_eq!(status.code(), Code::InvalidArgument);
     }
 
+    #[tokio::test]
+    async fn test_update_query_limits() {
+        let catalog: Arc<dyn Catalog> =
+            Arc::new(MemCatalog::new(Arc::new(metric::Registry::default())));
+
+        let handler = NamespaceService::new(catalog);
+        let created_ns = handler
+            .create_namespace(Request::new(CreateNamespaceRequest {
+                name: NS_NAME.to_string(),
+                retention_period_ns: None,
+                partition_template: None,
+            }))
+            .await
+            .expect("failed to create namespace")
+            .into_inner()
+            .namespace
+            .expect("no namespace in response");
+        assert_eq!(created_ns.max_concurrent_queries, None);
+        assert_eq!(created_ns.max_query_memory_bytes, None);
+        assert_eq!(created_ns.max_total_query_memory_bytes, None);
+
+        let update = |limit_update| {
+            handler.update_namespace_service_protection_limit(Request::new(
+                UpdateNamespaceServiceProtectionLimitRequest {
+                    name: NS_NAME.to_string(),
+                    limit_update: Some(limit_update),
+                },
+            ))
+        };
+
+        update(LimitUpdate::MaxConcurrentQueries(4)).await.unwrap();
+        update(LimitUpdate::MaxQueryMemoryBytes(1024))
+            .await
+            .unwrap();
+        let updated_ns = update(LimitUpdate::MaxTotalQueryMemoryBytes(4096))
+            .await
+            .unwrap()
+            .into_inner()
+            .namespace
+            .expect("no namespace in response");
+        assert_eq!(updated_ns.max_concurrent_queries, Some(4));
+        assert_eq!(updated_ns.max_query_memory_bytes, Some(1024));
+        assert_eq!(updated_ns.max_total_query_memory_bytes, Some(4096));
+        assert_eq!(updated_ns.max_tables, created_ns.max_tables);
+
+        // A zero removes the limit
+        let updated_ns = update(LimitUpdate::MaxConcurrentQueries(0))
+            .await
+            .unwrap()
+            .into_inner()
+            .namespace
+            .expect("no namespace in response");
+        assert_eq!(updated_ns.max_concurrent_queries, None);
+        assert_eq!(updated_ns.max_query_memory_bytes, Some(1024));
+
+        // Negative limits are rejected
+        let status = update(LimitUpdate::MaxQueryMemoryBytes(-1))
+            .await
+            .expect_err("negative query memory limit should fail");
+        assert_eq!(status.code(), Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_delete_undelete_table_and_column() {
+        let catalog: Arc<dyn Catalog> =
+            Arc::new(MemCatalog::new(Arc::new(metric::Registry::default())));
+
+        let handler = NamespaceService::new(Arc::clone(&catalog));
+        handler
+            .create_namespace(Request::new(CreateNamespaceRequest {
+                name: NS_NAME.to_string(),
+                retention_period_ns: None,
+                partition_template: None,
+            }))
+            .await
+            .expect("failed to create namespace");
+
+        let (table, column, time) = {
+            let mut repos = catalog.repositories().await;
+            let namespace = repos
+                .namespaces()
+                .get_by_name(NS_NAME, SoftDeletedRows::ExcludeDeleted)
+                .await
+                .unwrap()
+                .unwrap();
+            let table = repos
+                .tables()
+                .create_or_get("platanos", namespace.id)
+                .await
+                .unwrap();
+            let column = repos
+                .columns()
+                .create_or_get("colour", table.id, ColumnType::Tag)
+                .await
+                .unwrap();
+            let time = repos
+                .columns()
+                .create_or_get("time", table.id, ColumnType::Time)
+                .await
+                .unwrap();
+            (table, column, time)
+        };
+
+        async fn load_table(catalog: &dyn Catalog, table: &Table) -> Table {
+            catalog
+                .repositories()
+                .await
+                .tables()
+                .get_by_id(table.id)
+                .await
+                .unwrap()
+                .unwrap()
+        }
+        async fn load_column(catalog: &dyn Catalog, column: &Column) -> Column {
+            catalog
+                .repositories()
+                .await
+                .columns()
+                .list_by_table_id(column.table_id)
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|c| c.id == column.id)
+                .unwrap()
+        }
+
+        // Delete and restore the table
+        handler
+            .delete_table(Request::new(DeleteTableRequest {
+                namespace: NS_NAME.to_string(),
+                table: "platanos".to_string(),
+            }))
+            .await
+            .expect("must delete table");
+        assert!(load_table(&*catalog, &table).await.deleted_at.is_some());
+
+        handler
+            .undelete_table(Request::new(UndeleteTableRequest {
+                namespace: NS_NAME.to_string(),
+                table: "platanos".to_string(),
+            }))
+            .await
+            .expect("must undelete table");
+        assert!(load_table(&*catalog, &table).await.deleted_at.is_none());
+
+        // Delete and restore the column
+        handler
+            .delete_column(Request::new(DeleteColumnRequest {
+                namespace: NS_NAME.to_string(),
+                table: "platanos".to_string(),
+                column: "colour".to_string(),
+            }))
+            .await
+            .expect("must delete column");
+        assert!(load_column(&*catalog, &column).await.deleted_at.is_some());
+
+        handler
+            .undelete_column(Request::new(UndeleteColumnRequest {
+                namespace: NS_NAME.to_string(),
+                table: "platanos".to_string(),
+                column: "colour".to_string(),
+            }))
+            .await
+            .expect("must undelete column");
+        assert!(load_column(&*catalog, &column).await.deleted_at.is_none());
+
+        // The time column cannot be deleted
+        let status = handler
+            .delete_column(Request::new(DeleteColumnRequest {
+                namespace: NS_NAME.to_string(),
+                table: "platanos".to_string(),
+                column: "time".to_string(),
+            }))
+            .await
+            .expect_err("deleting the time column should fail");
+        assert_eq!(status.code(), Code::InvalidArgument);
+        assert!(load_column(&*catalog, &time).await.deleted_at.is_none());
+
+        // Unknown tables and columns are not found
+        let status = handler
+            .delete_table(Request::new(DeleteTableRequest {
+                namespace: NS_NAME.to_string(),
+                table: "bananas".to_string(),
+            }))
+            .await
+            .expect_err("deleting an unknown table should fail");
+        assert_eq!(status.code(), Code::NotFound);
+
+        let status = handler
+            .delete_column(Request::new(DeleteColumnRequest {
+                namespace: NS_NAME.to_string(),
+                table: "platanos".to_string(),
+                column: "size".to_string(),
+            }))
+            .await
+            .expect_err("deleting an unknown column should fail");
+        assert_eq!(status.code(), Code::NotFound);
+    }
+
     macro_rules! test_create_namespace_name {
         (
             $test_name:ident,
@@ -528,6 +1127,7 @@ mod tests {
                     let req = CreateNamespaceRequest {
                         name: String::from($name),
                         retention_period_ns: Some(RETENTION),
+                        partition_template: None,
                     };
 
                     let got = handler.create_namespace(Request::new(req)).await;
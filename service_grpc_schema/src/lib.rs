@@ -69,10 +69,12 @@ fn schema_to_proto(schema: Arc<data_types::NamespaceSchema>) -> GetSchemaRespons
                                     )
                                 })
                                 .collect(),
+                            partition_template: t.partition_template.as_deref().map(Into::into),
                         },
                     )
                 })
                 .collect(),
+            partition_template: schema.partition_template.as_deref().map(Into::into),
         }),
     };
     response
@@ -95,7 +97,7 @@ mod tests {
             let mut repos = catalog.repositories().await;
             let namespace = repos
                 .namespaces()
-                .create("namespace_schema_test", None)
+                .create("namespace_schema_test", None, None)
                 .await
                 .unwrap();
             let table = repos
@@ -0,0 +1,217 @@
+//! Implementation of the table gRPC service
+use std::sync::Arc;
+
+use data_types::{NamespaceName, Table as CatalogTable};
+use generated_types::{google::FromOptionalField, influxdata::iox::table::v1::*};
+use iox_catalog::interface::{Catalog, SoftDeletedRows};
+use observability_deps::tracing::{debug, info, warn};
+use tonic::{Request, Response, Status};
+
+/// Implementation of the gRPC table service
+#[derive(Debug)]
+pub struct TableService {
+    /// Catalog.
+    catalog: Arc<dyn Catalog>,
+}
+
+impl TableService {
+    pub fn new(catalog: Arc<dyn Catalog>) -> Self {
+        Self { catalog }
+    }
+}
+
+#[tonic::async_trait]
+impl table_service_server::TableService for TableService {
+    // create a table
+    async fn create_table(
+        &self,
+        request: Request<CreateTableRequest>,
+    ) -> Result<Response<CreateTableResponse>, Status> {
+        let mut repos = self.catalog.repositories().await;
+
+        let CreateTableRequest {
+            namespace: namespace_name,
+            name: table_name,
+            partition_template,
+        } = request.into_inner();
+
+        let namespace_name = NamespaceName::try_from(namespace_name)
+            .map_err(|v| Status::invalid_argument(v.to_string()))?;
+        if table_name.is_empty() {
+            return Err(Status::invalid_argument("table name must not be empty"));
+        }
+        let partition_template = partition_template.optional("partition_template")?;
+
+        debug!(%namespace_name, %table_name, ?partition_template, "Creating table");
+
+        let namespace = repos
+            .namespaces()
+            .get_by_name(&namespace_name, SoftDeletedRows::ExcludeDeleted)
+            .await
+            .map_err(|e| {
+                warn!(error=%e, %namespace_name, "failed to retrieve namespace from catalog");
+                Status::internal(e.to_string())
+            })?
+            .ok_or_else(|| Status::not_found(format!("namespace {namespace_name} not found")))?;
+
+        let table = repos
+            .tables()
+            .create(&table_name, partition_template, namespace.id)
+            .await
+            .map_err(|e| {
+                warn!(error=%e, %namespace_name, %table_name, "failed to create table");
+                status_from_catalog_table_error(e)
+            })?;
+
+        info!(
+            %namespace_name,
+            %table_name,
+            table_id = %table.id,
+            "created table"
+        );
+
+        Ok(Response::new(CreateTableResponse {
+            table: Some(table_to_proto(table)),
+        }))
+    }
+}
+
+fn table_to_proto(table: CatalogTable) -> Table {
+    Table {
+        id: table.id.get(),
+        name: table.name,
+        namespace_id: table.namespace_id.get(),
+        partition_template: table.partition_template.as_ref().map(Into::into),
+    }
+}
+
+fn status_from_catalog_table_error(err: iox_catalog::interface::Error) -> Status {
+    match err {
+        iox_catalog::interface::Error::NameExists { .. } => Status::already_exists(err.to_string()),
+        iox_catalog::interface::Error::TableCreateLimitError { .. } => {
+            Status::resource_exhausted(err.to_string())
+        }
+        _ => Status::internal(err.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use generated_types::influxdata::iox::{
+        partition_template::v1::{template_part, PartitionTemplate, TemplatePart},
+        table::v1::table_service_server::TableService as _,
+    };
+    use iox_catalog::mem::MemCatalog;
+    use tonic::Code;
+
+    use super::*;
+
+    const NS_NAME: &str = "bananas";
+
+    async fn setup() -> (Arc<dyn Catalog>, TableService) {
+        let catalog: Arc<dyn Catalog> =
+            Arc::new(MemCatalog::new(Arc::new(metric::Registry::default())));
+        catalog
+            .repositories()
+            .await
+            .namespaces()
+            .create(NS_NAME, None, None)
+            .await
+            .expect("failed to create namespace");
+
+        let handler = TableService::new(Arc::clone(&catalog));
+        (catalog, handler)
+    }
+
+    #[tokio::test]
+    async fn test_create_table() {
+        let (catalog, handler) = setup().await;
+
+        let template = PartitionTemplate {
+            parts: vec![TemplatePart {
+                part: Some(template_part::Part::ColumnValue("region".to_string())),
+            }],
+        };
+
+        let created = handler
+            .create_table(Request::new(CreateTableRequest {
+                namespace: NS_NAME.to_string(),
+                name: "platanos".to_string(),
+                partition_template: Some(template.clone()),
+            }))
+            .await
+            .expect("failed to create table")
+            .into_inner()
+            .table
+            .expect("no table in response");
+        assert_eq!(created.name, "platanos");
+        assert_eq!(created.partition_template, Some(template));
+
+        // The table and its template are persisted in the catalog
+        let got = catalog
+            .repositories()
+            .await
+            .tables()
+            .get_by_id(data_types::TableId::new(created.id))
+            .await
+            .unwrap()
+            .expect("table should exist");
+        assert_eq!(table_to_proto(got), created);
+
+        // Creating the same table again is rejected
+        let status = handler
+            .create_table(Request::new(CreateTableRequest {
+                namespace: NS_NAME.to_string(),
+                name: "platanos".to_string(),
+                partition_template: None,
+            }))
+            .await
+            .expect_err("duplicate table should be rejected");
+        assert_eq!(status.code(), Code::AlreadyExists);
+    }
+
+    #[tokio::test]
+    async fn test_create_table_errors() {
+        let (_catalog, handler) = setup().await;
+
+        assert_matches!(
+            handler
+                .create_table(Request::new(CreateTableRequest {
+                    namespace: "missing".to_string(),
+                    name: "platanos".to_string(),
+                    partition_template: None,
+                }))
+                .await,
+            Err(e) => {
+                assert_eq!(e.code(), Code::NotFound);
+            }
+        );
+
+        assert_matches!(
+            handler
+                .create_table(Request::new(CreateTableRequest {
+                    namespace: NS_NAME.to_string(),
+                    name: String::new(),
+                    partition_template: None,
+                }))
+                .await,
+            Err(e) => {
+                assert_eq!(e.code(), Code::InvalidArgument);
+            }
+        );
+
+        assert_matches!(
+            handler
+                .create_table(Request::new(CreateTableRequest {
+                    namespace: NS_NAME.to_string(),
+                    name: "platanos".to_string(),
+                    partition_template: Some(PartitionTemplate { parts: vec![] }),
+                }))
+                .await,
+            Err(e) => {
+                assert_eq!(e.code(), Code::InvalidArgument);
+            }
+        );
+    }
+}